@@ -1,4 +1,4 @@
-use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::actions::{Action, CopyModeSelection};
 use zellij_tile::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -31,6 +31,10 @@ pub enum ActionType {
     ToggleTab,
     BreakPane,
     EditScrollback,
+    CopyModeSelectCharacters,
+    CopyModeSelectLines,
+    CopyModeSelectBlock,
+    CopyModeYank,
     NewTab,
     Detach,
     Quit,
@@ -85,6 +89,10 @@ impl ActionType {
             ActionType::ToggleTab => "Circle tab focus".to_string(),
             ActionType::BreakPane => "Break pane to new tab".to_string(),
             ActionType::EditScrollback => "Open pane scrollback in editor".to_string(),
+            ActionType::CopyModeSelectCharacters => "Select characters".to_string(),
+            ActionType::CopyModeSelectLines => "Select lines".to_string(),
+            ActionType::CopyModeSelectBlock => "Select block".to_string(),
+            ActionType::CopyModeYank => "Copy selection".to_string(),
             ActionType::NewTab => "New tab".to_string(),
             ActionType::Detach => "Detach".to_string(),
             ActionType::Quit => "Quit".to_string(),
@@ -132,6 +140,16 @@ impl ActionType {
             Action::ToggleTab => ActionType::ToggleTab,
             Action::BreakPane => ActionType::BreakPane,
             Action::EditScrollback { .. } => ActionType::EditScrollback,
+            Action::CopyModeSelect {
+                selection: CopyModeSelection::Character,
+            } => ActionType::CopyModeSelectCharacters,
+            Action::CopyModeSelect {
+                selection: CopyModeSelection::Line,
+            } => ActionType::CopyModeSelectLines,
+            Action::CopyModeSelect {
+                selection: CopyModeSelection::Block,
+            } => ActionType::CopyModeSelectBlock,
+            Action::CopyModeYank => ActionType::CopyModeYank,
             Action::Detach => ActionType::Detach,
             Action::Quit => ActionType::Quit,
             action if action.launches_plugin("session-manager") => ActionType::SessionManager,
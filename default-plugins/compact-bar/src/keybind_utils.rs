@@ -1,6 +1,6 @@
 use crate::action_types::ActionType;
 use std::collections::HashSet;
-use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::actions::{Action, CopyModeSelection};
 use zellij_tile::prelude::*;
 
 pub struct KeybindProcessor;
@@ -716,6 +716,52 @@ impl KeybindProcessor {
                             }
                         )
                     },
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::SearchToggleOption {
+                                option: actions::SearchOption::Regex
+                            }
+                        )
+                    },
+                ];
+                Self::find_predetermined_actions(mode_info, mode, ordered_predicates)
+            },
+            InputMode::Copy => {
+                let ordered_predicates = vec![
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::CopyModeSelect {
+                                selection: CopyModeSelection::Character
+                            }
+                        )
+                    },
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::CopyModeSelect {
+                                selection: CopyModeSelection::Line
+                            }
+                        )
+                    },
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::CopyModeSelect {
+                                selection: CopyModeSelection::Block
+                            }
+                        )
+                    },
+                    |action: &Action| matches!(action, Action::CopyModeYank),
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::SwitchToMode {
+                                input_mode: InputMode::EnterSearch
+                            }
+                        )
+                    },
                 ];
                 Self::find_predetermined_actions(mode_info, mode, ordered_predicates)
             },
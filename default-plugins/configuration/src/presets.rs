@@ -96,6 +96,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Locked"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Locked"; }}
@@ -110,9 +111,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Locked"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Locked"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -329,6 +362,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Normal"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
@@ -344,9 +378,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Normal"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Normal"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -567,6 +633,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Normal"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
@@ -581,9 +648,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Normal"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Normal"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -780,6 +879,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Normal"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
@@ -795,9 +895,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Normal"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Normal"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -999,6 +1131,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Normal"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
@@ -1013,9 +1146,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Normal"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Normal"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -1207,6 +1372,7 @@ keybinds clear-defaults=true {{
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
         bind "c" {{ CopyLastCommandOutput; SwitchToMode "Normal"; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
     }}
     search {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
@@ -1222,9 +1388,41 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
         bind "[" {{ ScrollToPreviousPrompt; }}
         bind "]" {{ ScrollToNextPrompt; }}
         bind "m" {{ SelectCommandAtScrollPosition; }}
+        bind "v" {{ SwitchToMode "Copy"; }}
+    }}
+    copy {{
+        bind "Ctrl c" "Esc" "q" {{ ScrollToBottom; SwitchToMode "Normal"; }}
+        bind "h" "Left" "Ctrl b" {{ CopyModeMove "Left"; }}
+        bind "l" "Right" "Ctrl f" {{ CopyModeMove "Right"; }}
+        bind "k" "Up" "Ctrl p" {{ CopyModeMove "Up"; }}
+        bind "j" "Down" "Ctrl n" {{ CopyModeMove "Down"; }}
+        bind "w" "Alt f" {{ CopyModeMove "WordForward"; }}
+        bind "b" "Alt b" {{ CopyModeMove "WordBackward"; }}
+        bind "e" {{ CopyModeMove "WordEnd"; }}
+        bind "0" "Home" "Ctrl a" {{ CopyModeMove "LineStart"; }}
+        bind "$" "End" "Ctrl e" {{ CopyModeMove "LineEnd"; }}
+        bind "^" "Alt m" {{ CopyModeMove "FirstNonBlank"; }}
+        bind "{{" "Alt {{" {{ CopyModeMove "ParagraphBackward"; }}
+        bind "}}" "Alt }}" {{ CopyModeMove "ParagraphForward"; }}
+        bind "g" "Alt <" {{ CopyModeMove "Top"; }}
+        bind "G" "Alt >" {{ CopyModeMove "Bottom"; }}
+        bind "PageUp" "Alt v" {{ CopyModeMove "PageUp"; }}
+        bind "PageDown" {{ CopyModeMove "PageDown"; }}
+        bind "u" {{ CopyModeMove "HalfPageUp"; }}
+        bind "d" {{ CopyModeMove "HalfPageDown"; }}
+        bind "[" {{ CopyModeMove "PreviousPrompt"; }}
+        bind "]" {{ CopyModeMove "NextPrompt"; }}
+        bind "n" {{ CopyModeMove "NextSearchResult"; }}
+        bind "N" {{ CopyModeMove "PreviousSearchResult"; }}
+        bind "/" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "v" "Ctrl Space" {{ CopyModeSelect "Character"; }}
+        bind "V" {{ CopyModeSelect "Line"; }}
+        bind "Ctrl v" {{ CopyModeSelect "Block"; }}
+        bind "y" "Enter" "Alt w" {{ CopyModeYank; ScrollToBottom; SwitchToMode "Normal"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
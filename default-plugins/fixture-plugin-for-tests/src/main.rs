@@ -75,6 +75,10 @@ impl ZellijPlugin for State {
             .get("subscribe_mode_update")
             .map(|v| v == "true")
             .unwrap_or(false);
+        let should_subscribe_permission_request_result = configuration
+            .get("subscribe_permission_request_result")
+            .map(|v| v == "true")
+            .unwrap_or(false);
         self.configuration = configuration;
         subscribe(&[
             EventType::InputReceived,
@@ -87,6 +91,7 @@ impl ZellijPlugin for State {
             EventType::BeforeClose,
             EventType::PluginConfigurationChanged,
             EventType::HighlightClicked,
+            EventType::PluginRequestResult,
         ]);
         if should_subscribe_initial_keybinds {
             subscribe(&[EventType::InitialKeybinds, EventType::ModeUpdate]);
@@ -94,6 +99,9 @@ impl ZellijPlugin for State {
         if should_subscribe_mode_update {
             subscribe(&[EventType::ModeUpdate]);
         }
+        if should_subscribe_permission_request_result {
+            subscribe(&[EventType::PermissionRequestResult]);
+        }
         watch_filesystem();
     }
 
@@ -959,6 +967,9 @@ impl ZellijPlugin for State {
             Event::PluginConfigurationChanged(new_config) => {
                 self.configuration = new_config.clone();
             },
+            Event::PluginRequestResult(reply, _context) => {
+                self.explicit_string_to_render = Some(format!("Reply from plugin: {:?}", reply));
+            },
             Event::SystemClipboardFailure => {
                 // this is just to trigger the worker message
                 post_message_to(PluginMessage {
@@ -993,6 +1004,39 @@ impl ZellijPlugin for State {
             );
         } else if name == "message_to_plugin" {
             self.message_to_plugin_payload = payload.clone();
+        } else if name == "plugin_request_to_self" {
+            send_plugin_request(
+                MessageToPlugin::new("plugin_request")
+                    .with_destination_plugin_id(get_plugin_ids().plugin_id),
+                None,
+                BTreeMap::new(),
+            );
+        } else if name == "plugin_request" {
+            if let Some(request_id) = pipe_message.request_id {
+                reply_to_plugin_request(request_id, Some("my_cool_reply".to_owned()));
+            }
+        } else if name == "request_read_only_folder" {
+            if let Some(folder) = payload {
+                request_permission_with_filesystem_access(
+                    &[PermissionType::ReadApplicationState],
+                    &[FilesystemPermission::read_only(folder)],
+                );
+            }
+        } else if name == "read_and_write_folder" {
+            if let Some(folder) = payload {
+                let entries: Vec<String> = std::fs::read_dir(&folder)
+                    .map(|entries| {
+                        entries
+                            .filter_map(|e| e.ok())
+                            .map(|e| e.file_name().to_string_lossy().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let written =
+                    std::fs::write(std::path::Path::new(&folder).join("new_file"), "hi").is_ok();
+                self.explicit_string_to_render =
+                    Some(format!("entries: {:?}, written: {}", entries, written));
+            }
         }
         let should_render = true;
         should_render
@@ -1012,4 +1056,15 @@ impl ZellijPlugin for State {
             );
         }
     }
+
+    fn save_state(&mut self) -> Option<String> {
+        Some("state_from_previous_instance".to_owned())
+    }
+
+    fn restore_state(&mut self, state: String) {
+        // this is just so that tests can tell the state was restored
+        if state == "state_from_previous_instance" {
+            set_selectable(false);
+        }
+    }
 }
@@ -129,6 +129,7 @@ impl State {
             },
             Screen::ImportLayout(import_layout_screen) => import_layout_screen.handle_key(key),
             Screen::RenameLayout(rename_layout_screen) => rename_layout_screen.handle_key(key),
+            Screen::LayoutArgs(layout_args_screen) => layout_args_screen.handle_key(key),
             Screen::Error(ref mut error_screen) => error_screen.handle_key(key),
             Screen::ErrorDetail(ref mut error_detail_screen) => error_detail_screen.handle_key(key),
         }
@@ -288,6 +289,7 @@ impl ZellijPlugin for State {
             },
             Screen::ImportLayout(import_layout_screen) => import_layout_screen.render(rows, cols),
             Screen::RenameLayout(rename_layout_screen) => rename_layout_screen.render(rows, cols),
+            Screen::LayoutArgs(layout_args_screen) => layout_args_screen.render(rows, cols),
             Screen::Error(ref error_screen) => error_screen.render(rows, cols),
             Screen::ErrorDetail(ref error_detail_screen) => error_detail_screen.render(rows, cols),
         }
@@ -0,0 +1,249 @@
+use super::{KeyResponse, LayoutListScreen, Screen};
+use crate::text_input::{InputAction, TextInput};
+use crate::ui::truncate_with_ellipsis_start;
+use std::collections::BTreeMap;
+use zellij_tile::prelude::*;
+
+/// What happens to the layout once its parameters are filled in
+#[derive(Clone)]
+pub enum LayoutArgsTarget {
+    NewTabs {
+        should_rename_tab: bool,
+    },
+    Override {
+        retain_terminal_panes: bool,
+        retain_plugin_panes: bool,
+        apply_only_to_active_tab: bool,
+    },
+}
+
+/// Fill in the parameters of a layout before opening or applying it
+#[derive(Clone)]
+pub struct LayoutArgsScreen {
+    pub layout_info: LayoutInfo,
+    pub layout_name: String,
+    pub parameters: Vec<(String, TextInput)>, // parameter name, value (prefilled with its default)
+    pub selected_parameter_index: usize,
+    pub target: LayoutArgsTarget,
+    pub selected_layout_index: usize,
+}
+
+impl LayoutArgsScreen {
+    pub fn new(
+        layout_info: LayoutInfo,
+        layout_name: String,
+        parameters: &BTreeMap<String, String>,
+        target: LayoutArgsTarget,
+        selected_layout_index: usize,
+    ) -> Self {
+        Self {
+            layout_info,
+            layout_name,
+            parameters: parameters
+                .iter()
+                .map(|(name, default_value)| (name.clone(), TextInput::new(default_value.clone())))
+                .collect(),
+            selected_parameter_index: 0,
+            target,
+            selected_layout_index,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> KeyResponse {
+        match key.bare_key {
+            BareKey::Up if key.has_no_modifiers() => {
+                self.select_previous_parameter();
+                return KeyResponse::render();
+            },
+            BareKey::Down if key.has_no_modifiers() => {
+                self.select_next_parameter();
+                return KeyResponse::render();
+            },
+            _ => {},
+        }
+        let Some((_, value_input)) = self.parameters.get_mut(self.selected_parameter_index) else {
+            return KeyResponse::none();
+        };
+        match value_input.handle_key(key) {
+            InputAction::Continue => KeyResponse::render(),
+            InputAction::Submit => {
+                self.open_layout();
+                KeyResponse::none()
+            },
+            InputAction::Cancel => KeyResponse::new_screen(self.cancel()),
+            InputAction::Complete => {
+                self.select_next_parameter();
+                KeyResponse::render()
+            },
+            InputAction::NoAction => KeyResponse::none(),
+        }
+    }
+
+    fn select_next_parameter(&mut self) {
+        if !self.parameters.is_empty() {
+            self.selected_parameter_index =
+                (self.selected_parameter_index + 1) % self.parameters.len();
+        }
+    }
+
+    fn select_previous_parameter(&mut self) {
+        if !self.parameters.is_empty() {
+            self.selected_parameter_index = self
+                .selected_parameter_index
+                .checked_sub(1)
+                .unwrap_or(self.parameters.len() - 1);
+        }
+    }
+
+    fn cancel(&self) -> Screen {
+        show_cursor(None);
+        Screen::LayoutList(LayoutListScreen::with_selected_index(
+            self.selected_layout_index,
+        ))
+    }
+
+    fn open_layout(&self) {
+        let layout_args = self
+            .parameters
+            .iter()
+            .map(|(name, value_input)| (name.clone(), value_input.get_text().to_owned()))
+            .collect();
+        match self.target {
+            LayoutArgsTarget::NewTabs { should_rename_tab } => {
+                let tab_ids = new_tabs_with_layout_info_and_args(&self.layout_info, layout_args);
+                if should_rename_tab {
+                    if let Some(&tab_id) = tab_ids.first() {
+                        rename_tab_with_id(tab_id as u64, &self.layout_name);
+                    }
+                }
+            },
+            LayoutArgsTarget::Override {
+                retain_terminal_panes,
+                retain_plugin_panes,
+                apply_only_to_active_tab,
+            } => {
+                override_layout_with_args(
+                    &self.layout_info,
+                    retain_terminal_panes,
+                    retain_plugin_panes,
+                    apply_only_to_active_tab,
+                    layout_args,
+                    Default::default(),
+                );
+            },
+        }
+        close_self();
+    }
+
+    fn name_column_width(&self) -> usize {
+        self.parameters
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn parameter_line_text(&self, index: usize, max_width: Option<usize>) -> (String, usize) {
+        // Returns (text, cursor_position_in_line)
+        let (name, value_input) = &self.parameters[index];
+        let prompt = format!("{:width$}: ", name, width = self.name_column_width());
+        let prompt_len = prompt.chars().count();
+
+        let value = value_input.get_text();
+        let cursor_pos = value_input.get_cursor_position();
+
+        let mut text = format!("{}{}", prompt, value);
+        let mut cursor_position_in_line = prompt_len + cursor_pos;
+
+        if let Some(max_width) = max_width {
+            if text.chars().count() > max_width {
+                let truncated_value =
+                    truncate_with_ellipsis_start(value, max_width.saturating_sub(prompt_len));
+                text = format!("{}{}", prompt, truncated_value);
+                let truncated_len = truncated_value.chars().count();
+                cursor_position_in_line = prompt_len + cursor_pos.min(truncated_len);
+            }
+        }
+
+        (text, cursor_position_in_line)
+    }
+
+    fn action_name(&self) -> &str {
+        match self.target {
+            LayoutArgsTarget::NewTabs { .. } => "Open",
+            LayoutArgsTarget::Override { .. } => "Apply",
+        }
+    }
+
+    fn help_text(&self) -> (&str, &[&str]) {
+        let text = match self.target {
+            LayoutArgsTarget::NewTabs { .. } => {
+                "<Enter> - Open, <Tab>/<↓↑> - Next parameter, <Esc> - Cancel"
+            },
+            LayoutArgsTarget::Override { .. } => {
+                "<Enter> - Apply, <Tab>/<↓↑> - Next parameter, <Esc> - Cancel"
+            },
+        };
+        (text, &["<Enter>", "<Tab>", "<↓↑>", "<Esc>"])
+    }
+
+    fn render_help_text(&self, x: usize, y: usize) {
+        let (text, items_to_color) = self.help_text();
+        let mut text_obj = Text::new(text);
+        for item in items_to_color {
+            text_obj = text_obj.color_substring(3, item);
+        }
+        print_text_with_coordinates(text_obj, x, y, None, None);
+    }
+
+    fn render_title(&self, x: usize, y: usize, width: usize) {
+        let title = Text::new(format!("{} {}", self.action_name(), self.layout_name)).color_all(2);
+        print_text_with_coordinates(title, x, y, Some(width), None);
+    }
+
+    fn render_parameters(&self, x: usize, y: usize, width: usize) {
+        for index in 0..self.parameters.len() {
+            let (text, _) = self.parameter_line_text(index, Some(width));
+            let name_len = self.name_column_width();
+            let mut parameter_line = Text::new(&text).color_range(0, 0..name_len);
+            if index == self.selected_parameter_index {
+                parameter_line = parameter_line.selected();
+            }
+            print_text_with_coordinates(parameter_line, x, y + index, None, None);
+        }
+    }
+
+    pub fn render(&self, rows: usize, cols: usize) {
+        let widest_parameter_line = (0..self.parameters.len())
+            .map(|index| self.parameter_line_text(index, None).0.chars().count())
+            .max()
+            .unwrap_or(0);
+        let desired_ui_width =
+            std::cmp::max(self.help_text().0.chars().count(), widest_parameter_line);
+
+        // Leave at least 4 columns margin (2 on each side) to prevent text from reaching screen edge
+        let max_allowed_width = cols.saturating_sub(4);
+        let actual_ui_width = std::cmp::min(desired_ui_width, max_allowed_width);
+
+        // title(1) + spacing(1) + parameters + spacing(1) + help(1)
+        let desired_ui_height = 4 + self.parameters.len();
+        let actual_ui_height = std::cmp::min(desired_ui_height, rows);
+
+        let base_y = rows.saturating_sub(actual_ui_height) / 2;
+        let base_x = cols.saturating_sub(actual_ui_width) / 2;
+
+        let parameters_y = base_y + 2;
+        if self.parameters.get(self.selected_parameter_index).is_some() {
+            let (_, cursor_position_in_line) =
+                self.parameter_line_text(self.selected_parameter_index, Some(actual_ui_width));
+            show_cursor(Some((
+                base_x + cursor_position_in_line,
+                parameters_y + self.selected_parameter_index,
+            )));
+        }
+
+        self.render_title(base_x, base_y, actual_ui_width);
+        self.render_parameters(base_x, parameters_y, actual_ui_width);
+        self.render_help_text(base_x, parameters_y + self.parameters.len() + 1);
+    }
+}
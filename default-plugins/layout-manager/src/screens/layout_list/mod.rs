@@ -98,12 +98,10 @@ impl LayoutListScreen {
                     KeyResponse::render()
                 },
                 BareKey::Enter if key.has_no_modifiers() => {
-                    self.open_selected_layout(display_layouts);
-                    KeyResponse::none()
+                    self.open_selected_layout(display_layouts)
                 },
                 BareKey::Char('w') if key.has_modifiers(&[KeyModifier::Alt]) => {
-                    self.apply_selected_layout(display_layouts);
-                    KeyResponse::none()
+                    self.apply_selected_layout(display_layouts)
                 },
                 BareKey::Char('t') if key.has_no_modifiers() => {
                     self.toggle_retain_options();
@@ -153,13 +151,11 @@ impl LayoutListScreen {
             },
             BareKey::Enter if key.has_no_modifiers() => {
                 // Open the currently selected layout as new tab(s)
-                self.open_selected_layout(display_layouts);
-                return KeyResponse::none();
+                return self.open_selected_layout(display_layouts);
             },
             BareKey::Char('w') if key.has_modifiers(&[KeyModifier::Alt]) => {
                 // Apply/override the currently selected layout to the session
-                self.apply_selected_layout(display_layouts);
-                return KeyResponse::none();
+                return self.apply_selected_layout(display_layouts);
             },
             BareKey::Tab if key.has_no_modifiers() => {
                 // Complete: fill input with selected match name
@@ -321,10 +317,27 @@ impl LayoutListScreen {
         self.apply_only_to_active_tab = self.should_default_to_current_tab(display_layouts);
     }
 
-    fn apply_selected_layout(&self, display_layouts: &[DisplayLayout]) {
-        if let Some(DisplayLayout::Valid(chosen_layout)) =
-            display_layouts.get(self.selected_layout_index)
-        {
+    fn apply_selected_layout(&self, display_layouts: &[DisplayLayout]) -> KeyResponse {
+        let selected = display_layouts.get(self.selected_layout_index);
+        if let Some(DisplayLayout::Valid(chosen_layout)) = selected {
+            if let LayoutInfo::File(_, metadata) = chosen_layout {
+                if !metadata.parameters.is_empty() {
+                    // let the user fill in the layout's parameters before applying it
+                    return KeyResponse::new_screen(Screen::LayoutArgs(
+                        super::LayoutArgsScreen::new(
+                            chosen_layout.clone(),
+                            selected.unwrap().name(),
+                            &metadata.parameters,
+                            super::LayoutArgsTarget::Override {
+                                retain_terminal_panes: self.retain_terminal_panes,
+                                retain_plugin_panes: self.retain_plugin_panes,
+                                apply_only_to_active_tab: self.apply_only_to_active_tab,
+                            },
+                            self.selected_layout_index,
+                        ),
+                    ));
+                }
+            }
             override_layout(
                 chosen_layout,
                 self.retain_terminal_panes,
@@ -334,6 +347,7 @@ impl LayoutListScreen {
             );
             close_self();
         }
+        KeyResponse::none()
     }
 
     fn toggle_retain_options(&mut self) {
@@ -392,18 +406,34 @@ impl LayoutListScreen {
         }
     }
 
-    fn open_selected_layout(&self, display_layouts: &[DisplayLayout]) {
+    fn open_selected_layout(&self, display_layouts: &[DisplayLayout]) -> KeyResponse {
         let selected = display_layouts.get(self.selected_layout_index);
         if let Some(DisplayLayout::Valid(chosen_layout)) = selected {
+            let should_rename_tab = self.should_default_to_current_tab(display_layouts);
+            let layout_name = selected.unwrap().name();
+            if let LayoutInfo::File(_, metadata) = chosen_layout {
+                if !metadata.parameters.is_empty() {
+                    // let the user fill in the layout's parameters before opening it
+                    return KeyResponse::new_screen(Screen::LayoutArgs(
+                        super::LayoutArgsScreen::new(
+                            chosen_layout.clone(),
+                            layout_name,
+                            &metadata.parameters,
+                            super::LayoutArgsTarget::NewTabs { should_rename_tab },
+                            self.selected_layout_index,
+                        ),
+                    ));
+                }
+            }
             let tab_ids = new_tabs_with_layout_info(chosen_layout);
-            if self.should_default_to_current_tab(display_layouts) {
+            if should_rename_tab {
                 if let Some(&tab_id) = tab_ids.first() {
-                    let layout_name = selected.unwrap().name();
                     rename_tab_with_id(tab_id as u64, layout_name);
                 }
             }
             close_self();
         }
+        KeyResponse::none()
     }
 
     pub fn render(&mut self, display_layouts: &[DisplayLayout], rows: usize, cols: usize) {
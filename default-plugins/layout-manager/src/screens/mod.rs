@@ -1,4 +1,5 @@
 mod import_layout;
+mod layout_args;
 mod layout_list;
 mod new_layout_from_session;
 mod rename_layout;
@@ -6,6 +7,7 @@ mod rename_layout;
 use zellij_tile::prelude::LayoutMetadata;
 
 pub use import_layout::ImportLayoutScreen;
+pub use layout_args::{LayoutArgsScreen, LayoutArgsTarget};
 pub use layout_list::LayoutListScreen;
 pub use new_layout_from_session::NewLayoutFromCurrentSessionScreen;
 pub use rename_layout::RenameLayoutScreen;
@@ -19,6 +21,7 @@ pub enum Screen {
     NewLayoutFromSession(NewLayoutFromCurrentSessionScreen),
     ImportLayout(ImportLayoutScreen),
     RenameLayout(RenameLayoutScreen),
+    LayoutArgs(LayoutArgsScreen),
     Error(ErrorScreen),
     ErrorDetail(ErrorDetailScreen),
 }
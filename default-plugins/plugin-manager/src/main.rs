@@ -483,6 +483,162 @@ impl NewPluginScreen {
     }
 }
 
+enum PermissionsRow {
+    PluginUrl(String),
+    Permission(String, PermissionType),
+    Filesystem(FilesystemPermission),
+}
+
+#[derive(Default)]
+pub struct PermissionsScreen {
+    granted: Vec<GrantedPluginPermissions>,
+    selected_index: Option<usize>,
+}
+
+impl PermissionsScreen {
+    pub fn new() -> Self {
+        let mut permissions_screen = PermissionsScreen::default();
+        permissions_screen.refresh();
+        permissions_screen
+    }
+    pub fn refresh(&mut self) {
+        self.granted = list_granted_permissions();
+        let row_count = self.rows().len();
+        if row_count == 0 {
+            self.selected_index = None;
+        } else {
+            self.selected_index = self.selected_index.map(|s| s.min(row_count - 1));
+        }
+    }
+    fn rows(&self) -> Vec<PermissionsRow> {
+        let mut rows = vec![];
+        for granted in &self.granted {
+            rows.push(PermissionsRow::PluginUrl(granted.plugin_url.clone()));
+            for permission in &granted.permissions {
+                rows.push(PermissionsRow::Permission(
+                    granted.plugin_url.clone(),
+                    *permission,
+                ));
+            }
+            for filesystem_permission in &granted.filesystem_permissions {
+                rows.push(PermissionsRow::Filesystem(filesystem_permission.clone()));
+            }
+        }
+        rows
+    }
+    pub fn render(&self, rows: usize, cols: usize) {
+        let title_text = "GRANTED PLUGIN PERMISSIONS";
+        let title_text_len = title_text.chars().count();
+        print_text_with_coordinates(
+            Text::new(title_text),
+            (cols / 2).saturating_sub(title_text_len / 2),
+            0,
+            None,
+            None,
+        );
+        if self.granted.is_empty() {
+            print_text_with_coordinates(
+                Text::new("No permissions were granted to any plugin.").color_range(2, ..),
+                0,
+                2,
+                None,
+                None,
+            );
+        } else {
+            let max_width = cols.saturating_sub(4); // 4 for the list bullet and padding
+            let mut list = vec![];
+            for (i, row) in self.rows().iter().enumerate() {
+                let item = match row {
+                    PermissionsRow::PluginUrl(plugin_url) => {
+                        NestedListItem::new(truncate_string_start(plugin_url, max_width))
+                            .color_range(0, ..)
+                    },
+                    PermissionsRow::Permission(_, permission) => {
+                        NestedListItem::new(truncate_string_start(
+                            &permission.display_name(),
+                            max_width.saturating_sub(2),
+                        ))
+                        .indent(1)
+                    },
+                    PermissionsRow::Filesystem(filesystem_permission) => {
+                        NestedListItem::new(truncate_string_start(
+                            &filesystem_permission.display_name(),
+                            max_width.saturating_sub(2),
+                        ))
+                        .indent(1)
+                    },
+                };
+                if Some(i) == self.selected_index {
+                    list.push(item.selected());
+                } else {
+                    list.push(item);
+                }
+            }
+            let list = list
+                .into_iter()
+                .take(rows.saturating_sub(4)) // 2 top padding, 2 bottom padding
+                .collect();
+            print_nested_list_with_coordinates(list, 0, 2, Some(cols), None);
+        }
+        self.render_help(rows);
+    }
+    fn render_help(&self, rows: usize) {
+        let help_text =
+            Text::new("Help: <↓↑> - Navigate, <Del> - Revoke (all if on plugin URL), <ESC> - Back")
+                .color_range(3, 6..=9)
+                .color_range(3, 23..=27)
+                .color_range(3, 62..=66);
+        print_text_with_coordinates(help_text, 0, rows, None, None);
+    }
+    fn revoke_selected(&mut self) {
+        let selected_row = self
+            .selected_index
+            .and_then(|selected_index| self.rows().into_iter().nth(selected_index));
+        match selected_row {
+            Some(PermissionsRow::PluginUrl(plugin_url)) => {
+                revoke_plugin_permissions(&plugin_url, &[]);
+            },
+            Some(PermissionsRow::Permission(plugin_url, permission)) => {
+                revoke_plugin_permissions(&plugin_url, &[permission]);
+            },
+            // folder access can only be revoked alongside everything else granted to the plugin
+            Some(PermissionsRow::Filesystem(_)) | None => return,
+        }
+        self.refresh();
+    }
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> (bool, bool) {
+        let (mut should_render, mut should_close) = (false, false);
+        let max_index = self.rows().len().saturating_sub(1);
+        match key.bare_key {
+            BareKey::Down if key.has_no_modifiers() => {
+                self.selected_index = match self.selected_index {
+                    None if !self.granted.is_empty() => Some(0),
+                    Some(selected_index) if selected_index < max_index => Some(selected_index + 1),
+                    _ => None,
+                };
+                should_render = true;
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                self.selected_index = match self.selected_index {
+                    None if !self.granted.is_empty() => Some(max_index),
+                    Some(selected_index) if selected_index > 0 => Some(selected_index - 1),
+                    _ => None,
+                };
+                should_render = true;
+            },
+            BareKey::Delete if key.has_no_modifiers() => {
+                self.revoke_selected();
+                should_render = true;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                should_close = true;
+            },
+            _ => {},
+        }
+        (should_render, should_close)
+    }
+}
+
 #[derive(Default)]
 struct State {
     userspace_configuration: BTreeMap<String, String>,
@@ -494,6 +650,7 @@ struct State {
     plugin_id_to_tab_position: HashMap<u32, usize>,
     search_term: String,
     new_plugin_screen: Option<NewPluginScreen>,
+    permissions_screen: Option<PermissionsScreen>,
     colors: Styling,
 }
 
@@ -576,6 +733,19 @@ impl ZellijPlugin for State {
                 }
             },
             Event::Key(key) => match self.new_plugin_screen.as_mut() {
+                None if self.permissions_screen.is_some() => {
+                    let (should_render_permissions_screen, should_close_permissions_screen) = self
+                        .permissions_screen
+                        .as_mut()
+                        .map(|p| p.handle_key(key))
+                        .unwrap_or_default();
+                    if should_close_permissions_screen {
+                        self.permissions_screen = None;
+                        should_render = true;
+                    } else {
+                        should_render = should_render_permissions_screen;
+                    }
+                },
                 Some(new_plugin_screen) => {
                     let (should_render_new_plugin_screen, should_close_new_plugin_screen) =
                         new_plugin_screen.handle_key(key);
@@ -594,11 +764,14 @@ impl ZellijPlugin for State {
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
-        match &self.new_plugin_screen {
-            Some(new_plugin_screen) => {
+        match (&self.new_plugin_screen, &self.permissions_screen) {
+            (Some(new_plugin_screen), _) => {
                 new_plugin_screen.render(rows, cols);
             },
-            None => {
+            (None, Some(permissions_screen)) => {
+                permissions_screen.render(rows, cols);
+            },
+            (None, None) => {
                 self.render_search(cols);
                 let list_y = 2;
                 let max_list_items = rows.saturating_sub(4); // 2 top padding, 2 bottom padding
@@ -718,14 +891,15 @@ impl State {
         cols: usize,
     ) -> Vec<NestedListItem> {
         let mut items = vec![];
+        let is_crashed = plugin_info.crash_reason.is_some();
         let plugin_location_len = plugin_info.location.chars().count();
-        let max_location_len = cols.saturating_sub(3); // 3 for the bulletin
+        let max_location_len = cols.saturating_sub(3 + crashed_indication_len(is_crashed)); // 3 for the bulletin
         let location_string = if plugin_location_len > max_location_len {
             truncate_string_start(&plugin_info.location, max_location_len)
         } else {
             plugin_info.location.clone()
         };
-        let mut item = self.render_plugin_line(location_string, None);
+        let mut item = self.render_plugin_line(location_string, None, is_crashed);
         if is_selected {
             item = item.selected();
         }
@@ -733,6 +907,9 @@ impl State {
         if is_expanded {
             let tab_line = self.render_tab_line(plugin_id, cols);
             items.push(tab_line);
+            if let Some(crash_reason) = &plugin_info.crash_reason {
+                items.push(self.render_crash_line(crash_reason, cols));
+            }
             if !plugin_info.configuration.is_empty() {
                 let config_line = NestedListItem::new(format!("Configuration:"))
                     .color_range(2, ..=13)
@@ -783,10 +960,13 @@ impl State {
         let plugin_info = &search_result.plugin_info;
         let plugin_id = search_result.plugin_id;
         let indices = &search_result.indices;
+        let is_crashed = plugin_info.crash_reason.is_some();
         let plus_indication_len = plus_indication
             .map(|p| p.to_string().chars().count() + 4)
             .unwrap_or(0); // 4 for the plus indication decorators and space
-        let max_location_len = cols.saturating_sub(plus_indication_len + 3); // 3 for the bulletin
+        let max_location_len = cols.saturating_sub(
+            plus_indication_len + 3 + crashed_indication_len(is_crashed), // 3 for the bulletin
+        );
         let (location_string, indices) = if plugin_info.location.chars().count() <= max_location_len
         {
             (plugin_info.location.clone(), indices.clone())
@@ -798,8 +978,9 @@ impl State {
                 location_string,
                 plus_indication,
                 Some(indices),
+                is_crashed,
             ),
-            None => self.render_plugin_line(location_string, Some(indices)),
+            None => self.render_plugin_line(location_string, Some(indices), is_crashed),
         };
         if is_selected {
             item = item.selected();
@@ -808,6 +989,9 @@ impl State {
         if is_expanded {
             let tab_line = self.render_tab_line(plugin_id, cols);
             items.push(tab_line);
+            if let Some(crash_reason) = &plugin_info.crash_reason {
+                items.push(self.render_crash_line(crash_reason, cols));
+            }
             if !plugin_info.configuration.is_empty() {
                 let config_line = NestedListItem::new(format!("Configuration:"))
                     .color_range(2, ..=13)
@@ -825,10 +1009,20 @@ impl State {
         location_string: String,
         plus_indication: usize,
         indices: Option<Vec<usize>>,
+        is_crashed: bool,
     ) -> NestedListItem {
-        let mut item = NestedListItem::new(&format!("{} [+{}]", location_string, plus_indication))
-            .color_range(0, ..)
-            .color_range(1, location_string.chars().count() + 1..);
+        let location_len = location_string.chars().count();
+        let plus_indication = format!(" [+{}]", plus_indication);
+        let plus_indication_len = plus_indication.chars().count();
+        let mut item = NestedListItem::new(&format!(
+            "{}{}{}",
+            location_string,
+            plus_indication,
+            crashed_indication(is_crashed)
+        ))
+        .color_range(0, ..)
+        .color_range(1, location_len + 1..location_len + plus_indication_len)
+        .color_range(2, location_len + plus_indication_len..);
         if let Some(indices) = indices {
             item = item.color_indices(3, indices);
         }
@@ -838,13 +1032,35 @@ impl State {
         &self,
         location_string: String,
         indices: Option<Vec<usize>>,
+        is_crashed: bool,
     ) -> NestedListItem {
-        let mut item = NestedListItem::new(location_string).color_range(0, ..);
+        let location_len = location_string.chars().count();
+        let mut item = NestedListItem::new(format!(
+            "{}{}",
+            location_string,
+            crashed_indication(is_crashed)
+        ))
+        .color_range(0, ..)
+        .color_range(2, location_len..);
         if let Some(indices) = indices {
             item = item.color_indices(3, indices);
         }
         item
     }
+    fn render_crash_line(&self, crash_reason: &str, max_width: usize) -> NestedListItem {
+        let crash_line_padding_count = 14; // 9 the length of the "Crashed: " + 5 for the left padding
+        let crash_reason = if crash_reason.chars().count() + crash_line_padding_count > max_width {
+            truncate_string_start(
+                crash_reason,
+                max_width.saturating_sub(crash_line_padding_count),
+            )
+        } else {
+            crash_reason.to_owned()
+        };
+        NestedListItem::new(format!("Crashed: {}", crash_reason))
+            .color_range(2, ..=7)
+            .indent(1)
+    }
     fn render_tab_line(&self, plugin_id: u32, max_width: usize) -> NestedListItem {
         let tab_of_plugin_id = self
             .get_tab_of_plugin_id(plugin_id)
@@ -867,11 +1083,11 @@ impl State {
         tab_line
     }
     pub fn render_help(&self, y: usize, cols: usize) {
-        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl p> - Permissions, <ESC> - Exit";
         let middle_text =
-            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl p> - Permissions, <ESC> - Exit";
         let short_text =
-            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a> - New, <ESC> - Exit";
+            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a/p> - New/Permissions, <ESC> - Exit";
         if cols >= full_text.chars().count() {
             let text = Text::new(full_text)
                 .color_range(3, 5..=11)
@@ -879,7 +1095,8 @@ impl State {
                 .color_range(3, 49..=53)
                 .color_range(3, 65..=69)
                 .color_range(3, 80..=87)
-                .color_range(3, 96..=100);
+                .color_range(3, 96..=103)
+                .color_range(3, 120..=124);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else if cols >= middle_text.chars().count() {
             let text = Text::new(middle_text)
@@ -887,13 +1104,14 @@ impl State {
                 .color_range(3, 31..=35)
                 .color_range(3, 47..=51)
                 .color_range(3, 62..=69)
-                .color_range(3, 78..=82);
+                .color_range(3, 78..=85)
+                .color_range(3, 102..=106);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else {
             let text = Text::new(short_text)
                 .color_range(3, ..=21)
-                .color_range(3, 53..=60)
-                .color_range(3, 69..=73);
+                .color_range(3, 53..=62)
+                .color_range(3, 83..=87);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         }
     }
@@ -1023,6 +1241,10 @@ impl State {
                 self.new_plugin_screen = Some(NewPluginScreen::new(self.colors));
                 should_render = true;
             },
+            BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.permissions_screen = Some(PermissionsScreen::new());
+                should_render = true;
+            },
             BareKey::Delete if key.has_no_modifiers() => {
                 self.close_selected();
             },
@@ -1101,3 +1323,15 @@ fn truncate_search_result(
         .collect();
     (truncated_location, adjusted_indices)
 }
+
+fn crashed_indication(is_crashed: bool) -> &'static str {
+    if is_crashed {
+        " [CRASHED]"
+    } else {
+        ""
+    }
+}
+
+fn crashed_indication_len(is_crashed: bool) -> usize {
+    crashed_indication(is_crashed).chars().count()
+}
@@ -9,7 +9,7 @@ use zellij_tile::prelude::*;
 use std::collections::{BTreeMap, HashMap};
 
 use main_screen::MainScreen;
-use token_management_screen::TokenManagementScreen;
+use token_management_screen::{NewTokenField, NewTokenInput, TokenManagementScreen};
 use token_screen::TokenScreen;
 
 static WEB_SERVER_QUERY_DURATION: f64 = 0.4; // Doherty threshold
@@ -231,11 +231,11 @@ impl App {
             BareKey::Down if key.has_no_modifiers() => self.tokens.navigate_down(),
             BareKey::Up if key.has_no_modifiers() => self.tokens.navigate_up(),
             BareKey::Char('n') if key.has_no_modifiers() => {
-                self.tokens.start_new_token_input();
+                self.tokens.start_new_token_input(false);
                 true
             },
             BareKey::Char('o') if key.has_no_modifiers() => {
-                self.tokens.start_new_read_only_token_input();
+                self.tokens.start_new_token_input(true);
                 true
             },
             BareKey::Enter if key.has_no_modifiers() => self.handle_enter_key(),
@@ -262,13 +262,18 @@ impl App {
     }
 
     fn handle_enter_key(&mut self) -> bool {
-        if let Some(token_name) = self.tokens.finish_new_token_input() {
-            self.generate_new_token(token_name, false);
-            return true;
-        }
-
-        if let Some(token_name) = self.tokens.finish_new_read_only_token_input() {
-            self.generate_new_token(token_name, true);
+        if let Some(new_token) = self.tokens.new_token.as_mut() {
+            if new_token.field == NewTokenField::ExpiresIn {
+                if let Err(e) = parse_expires_in(&new_token.expires_in) {
+                    self.web_server.error = Some(e);
+                    return true;
+                }
+            }
+            if !new_token.next_field() {
+                if let Some(new_token) = self.tokens.new_token.take() {
+                    self.generate_new_restricted_token(new_token);
+                }
+            }
             return true;
         }
 
@@ -287,9 +292,30 @@ impl App {
         }
     }
 
+    fn generate_new_restricted_token(&mut self, new_token: NewTokenInput) {
+        let name = Some(new_token.name).filter(|name| !name.is_empty());
+        let session_scope = Some(new_token.session_scope).filter(|scope| !scope.is_empty());
+        let expires_in = match parse_expires_in(&new_token.expires_in) {
+            Ok(expires_in) => expires_in,
+            Err(e) => {
+                self.web_server.error = Some(e);
+                return;
+            },
+        };
+        match generate_restricted_web_login_token(
+            name,
+            new_token.read_only,
+            expires_in,
+            session_scope,
+        ) {
+            Ok(token) => self.change_to_token_screen(token),
+            Err(e) => self.web_server.error = Some(e),
+        }
+    }
+
     fn rename_current_token(&mut self, new_name: String) {
         if let Some(current_token) = self.tokens.get_selected_token() {
-            match rename_web_token(&current_token.0, &new_name) {
+            match rename_web_token(&current_token.name, &new_name) {
                 Ok(_) => {
                     self.retrieve_token_list();
                     if self.tokens.adjust_selection_after_list_change() {
@@ -303,7 +329,7 @@ impl App {
 
     fn revoke_selected_token(&mut self) -> bool {
         if let Some(token) = self.tokens.get_selected_token() {
-            match revoke_web_login_token(&token.0) {
+            match revoke_web_login_token(&token.name) {
                 Ok(_) => {
                     self.retrieve_token_list();
                     if self.tokens.adjust_selection_after_list_change() {
@@ -457,18 +483,11 @@ impl App {
     }
 
     fn render_manage_tokens_screen(&self, rows: usize, cols: usize) {
-        // Pass whichever token input field is active (normal or read-only)
-        let entering_new_token_name = if self.tokens.entering_new_name.is_some() {
-            &self.tokens.entering_new_name
-        } else {
-            &self.tokens.entering_new_read_only_name
-        };
-
         TokenManagementScreen::new(
             &self.tokens.list,
             self.tokens.selected_index,
             &self.tokens.renaming_token,
-            entering_new_token_name,
+            &self.tokens.new_token,
             &self.web_server.error,
             &self.state.info,
             rows,
@@ -515,16 +534,15 @@ impl UIState {
 
 #[derive(Debug, Default)]
 struct TokenManager {
-    list: Vec<(String, String, bool)>, // bool -> is_read_only
+    list: Vec<WebLoginTokenInfo>,
     selected_index: Option<usize>,
-    entering_new_name: Option<String>,
-    entering_new_read_only_name: Option<String>,
+    new_token: Option<NewTokenInput>,
     renaming_token: Option<String>,
 }
 
 impl TokenManager {
     fn retrieve_list(&mut self) -> Result<(), String> {
-        match list_web_login_tokens() {
+        match list_web_login_token_details() {
             Ok(tokens) => {
                 self.list = tokens;
                 Ok(())
@@ -533,7 +551,7 @@ impl TokenManager {
         }
     }
 
-    fn get_selected_token(&self) -> Option<&(String, String, bool)> {
+    fn get_selected_token(&self) -> Option<&WebLoginTokenInfo> {
         self.selected_index.and_then(|i| self.list.get(i))
     }
 
@@ -573,12 +591,8 @@ impl TokenManager {
         false
     }
 
-    fn start_new_token_input(&mut self) {
-        self.entering_new_name = Some(String::new());
-    }
-
-    fn start_new_read_only_token_input(&mut self) {
-        self.entering_new_read_only_name = Some(String::new());
+    fn start_new_token_input(&mut self, read_only: bool) {
+        self.new_token = Some(NewTokenInput::new(read_only));
     }
 
     fn start_rename_input(&mut self) {
@@ -588,12 +602,8 @@ impl TokenManager {
     fn handle_text_input(&mut self, key: &KeyWithModifier) -> bool {
         match key.bare_key {
             BareKey::Char(c) if key.has_no_modifiers() => {
-                if let Some(ref mut name) = self.entering_new_name {
-                    name.push(c);
-                    return true;
-                }
-                if let Some(ref mut name) = self.entering_new_read_only_name {
-                    name.push(c);
+                if let Some(ref mut new_token) = self.new_token {
+                    new_token.current_field_mut().push(c);
                     return true;
                 }
                 if let Some(ref mut name) = self.renaming_token {
@@ -602,12 +612,8 @@ impl TokenManager {
                 }
             },
             BareKey::Backspace if key.has_no_modifiers() => {
-                if let Some(ref mut name) = self.entering_new_name {
-                    name.pop();
-                    return true;
-                }
-                if let Some(ref mut name) = self.entering_new_read_only_name {
-                    name.pop();
+                if let Some(ref mut new_token) = self.new_token {
+                    new_token.current_field_mut().pop();
                     return true;
                 }
                 if let Some(ref mut name) = self.renaming_token {
@@ -620,27 +626,24 @@ impl TokenManager {
         false
     }
 
-    fn finish_new_token_input(&mut self) -> Option<Option<String>> {
-        self.entering_new_name
-            .take()
-            .map(|name| if name.is_empty() { None } else { Some(name) })
-    }
-
-    fn finish_new_read_only_token_input(&mut self) -> Option<Option<String>> {
-        self.entering_new_read_only_name
-            .take()
-            .map(|name| if name.is_empty() { None } else { Some(name) })
-    }
-
     fn finish_rename_input(&mut self) -> Option<String> {
         self.renaming_token.take()
     }
 
     fn cancel_input(&mut self) -> bool {
-        self.entering_new_name.take().is_some()
-            || self.entering_new_read_only_name.take().is_some()
-            || self.renaming_token.take().is_some()
+        self.new_token.take().is_some() || self.renaming_token.take().is_some()
+    }
+}
+
+fn parse_expires_in(expires_in: &str) -> Result<Option<std::time::Duration>, String> {
+    if expires_in.trim().is_empty() {
+        return Ok(None);
     }
+    humantime::parse_duration(expires_in.trim())
+        .ok()
+        .filter(|duration| duration.as_secs() > 0)
+        .map(Some)
+        .ok_or_else(|| format!("Invalid expiry: \"{}\" (eg. 30m, 4h, 7days)", expires_in))
 }
 
 #[derive(Debug, Default)]
@@ -33,14 +33,74 @@ struct ColumnWidths {
     token: usize,
     date: usize,
     read_only: usize,
+    restrictions: usize,
     controls: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewTokenField {
+    Name,
+    ExpiresIn,
+    SessionScope,
+}
+
+#[derive(Debug)]
+pub struct NewTokenInput {
+    pub read_only: bool,
+    pub field: NewTokenField,
+    pub name: String,
+    pub expires_in: String,
+    pub session_scope: String,
+}
+
+impl NewTokenInput {
+    pub fn new(read_only: bool) -> Self {
+        NewTokenInput {
+            read_only,
+            field: NewTokenField::Name,
+            name: String::new(),
+            expires_in: String::new(),
+            session_scope: String::new(),
+        }
+    }
+
+    pub fn current_field(&self) -> &String {
+        match self.field {
+            NewTokenField::Name => &self.name,
+            NewTokenField::ExpiresIn => &self.expires_in,
+            NewTokenField::SessionScope => &self.session_scope,
+        }
+    }
+
+    pub fn current_field_mut(&mut self) -> &mut String {
+        match self.field {
+            NewTokenField::Name => &mut self.name,
+            NewTokenField::ExpiresIn => &mut self.expires_in,
+            NewTokenField::SessionScope => &mut self.session_scope,
+        }
+    }
+
+    // returns false if there are no more fields to fill
+    pub fn next_field(&mut self) -> bool {
+        match self.field {
+            NewTokenField::Name => {
+                self.field = NewTokenField::ExpiresIn;
+                true
+            },
+            NewTokenField::ExpiresIn => {
+                self.field = NewTokenField::SessionScope;
+                true
+            },
+            NewTokenField::SessionScope => false,
+        }
+    }
+}
+
 pub struct TokenManagementScreen<'a> {
-    token_list: &'a Vec<(String, String, bool)>, // bool -> is_read_only
+    token_list: &'a Vec<WebLoginTokenInfo>,
     selected_list_index: Option<usize>,
     renaming_token: &'a Option<String>,
-    entering_new_token_name: &'a Option<String>,
+    new_token: &'a Option<NewTokenInput>,
     error: &'a Option<String>,
     info: &'a Option<String>,
     rows: usize,
@@ -49,10 +109,10 @@ pub struct TokenManagementScreen<'a> {
 
 impl<'a> TokenManagementScreen<'a> {
     pub fn new(
-        token_list: &'a Vec<(String, String, bool)>,
+        token_list: &'a Vec<WebLoginTokenInfo>,
         selected_list_index: Option<usize>,
         renaming_token: &'a Option<String>,
-        entering_new_token_name: &'a Option<String>,
+        new_token: &'a Option<NewTokenInput>,
         error: &'a Option<String>,
         info: &'a Option<String>,
         rows: usize,
@@ -62,7 +122,7 @@ impl<'a> TokenManagementScreen<'a> {
             token_list,
             selected_list_index,
             renaming_token,
-            entering_new_token_name,
+            new_token,
             error,
             info,
             rows,
@@ -83,12 +143,14 @@ impl<'a> TokenManagementScreen<'a> {
         const MIN_TOKEN_WIDTH: usize = 10;
         const MIN_DATE_WIDTH: usize = 10; // Minimum for just date "YYYY-MM-DD"
         const MIN_READ_ONLY_WIDTH: usize = 5; // Minimum for "RO/RW"
+        const MIN_RESTRICTIONS_WIDTH: usize = 5; // Minimum for "[...]"
         const MIN_CONTROLS_WIDTH: usize = 6; // Minimum for "(<x>, <r>)"
-        const COLUMN_SPACING: usize = 5; // Space between columns (4 columns with table padding)
+        const COLUMN_SPACING: usize = 6; // Space between columns (5 columns with table padding)
 
         let min_total_width = MIN_TOKEN_WIDTH
             + MIN_DATE_WIDTH
             + MIN_READ_ONLY_WIDTH
+            + MIN_RESTRICTIONS_WIDTH
             + MIN_CONTROLS_WIDTH
             + COLUMN_SPACING;
 
@@ -97,6 +159,7 @@ impl<'a> TokenManagementScreen<'a> {
                 token: MIN_TOKEN_WIDTH,
                 date: MIN_DATE_WIDTH,
                 read_only: MIN_READ_ONLY_WIDTH,
+                restrictions: MIN_RESTRICTIONS_WIDTH,
                 controls: MIN_CONTROLS_WIDTH,
             };
         }
@@ -104,33 +167,46 @@ impl<'a> TokenManagementScreen<'a> {
         const PREFERRED_DATE_WIDTH: usize = 29; // "issued on YYYY-MM-DD HH:MM:SS"
         const PREFERRED_READ_ONLY_WIDTH: usize = 10; // "read-write"
         const PREFERRED_CONTROLS_WIDTH: usize = 24; // "(<x> revoke, <r> rename)"
+        let preferred_restrictions_width = self
+            .token_list
+            .iter()
+            .map(|token| self.format_restrictions(token).chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(MIN_RESTRICTIONS_WIDTH);
 
         let available_width = max_table_width.saturating_sub(COLUMN_SPACING);
-        let preferred_fixed_width =
-            PREFERRED_DATE_WIDTH + PREFERRED_READ_ONLY_WIDTH + PREFERRED_CONTROLS_WIDTH;
+        let preferred_fixed_width = PREFERRED_DATE_WIDTH
+            + PREFERRED_READ_ONLY_WIDTH
+            + preferred_restrictions_width
+            + PREFERRED_CONTROLS_WIDTH;
 
         if available_width >= preferred_fixed_width + MIN_TOKEN_WIDTH {
-            // We can use preferred widths for date, read_only, and controls
+            // We can use preferred widths for date, read_only, restrictions and controls
             ColumnWidths {
                 token: available_width.saturating_sub(preferred_fixed_width),
                 date: PREFERRED_DATE_WIDTH,
                 read_only: PREFERRED_READ_ONLY_WIDTH,
+                restrictions: preferred_restrictions_width,
                 controls: PREFERRED_CONTROLS_WIDTH,
             }
         } else {
             // Need to balance truncation across all columns
-            // Priority: controls > read_only > date > token (token gets remaining space)
+            // Priority: controls > read_only > restrictions > date > token (token gets remaining
+            // space)
             let remaining_width = available_width
                 .saturating_sub(MIN_TOKEN_WIDTH)
                 .saturating_sub(MIN_DATE_WIDTH)
                 .saturating_sub(MIN_READ_ONLY_WIDTH)
+                .saturating_sub(MIN_RESTRICTIONS_WIDTH)
                 .saturating_sub(MIN_CONTROLS_WIDTH);
-            let extra_per_column = remaining_width / 4;
+            let extra_per_column = remaining_width / 5;
 
             ColumnWidths {
                 token: MIN_TOKEN_WIDTH + extra_per_column,
                 date: MIN_DATE_WIDTH + extra_per_column,
                 read_only: MIN_READ_ONLY_WIDTH + extra_per_column,
+                restrictions: MIN_RESTRICTIONS_WIDTH + extra_per_column,
                 controls: MIN_CONTROLS_WIDTH + extra_per_column,
             }
         }
@@ -233,6 +309,30 @@ impl<'a> TokenManagementScreen<'a> {
         )
     }
 
+    fn format_restrictions(&self, token: &WebLoginTokenInfo) -> String {
+        let mut restrictions = vec![];
+        if let Some(session_scope) = &token.session_scope {
+            restrictions.push(format!("sessions: {}", session_scope));
+        }
+        if let Some(expires_at) = &token.expires_at {
+            restrictions.push(format!("expires {}", expires_at));
+        }
+        match &token.last_used_at {
+            Some(last_used_at) => restrictions.push(format!("used {}", last_used_at)),
+            None => restrictions.push("never used".to_owned()),
+        }
+        restrictions.join(", ")
+    }
+
+    fn format_restrictions_column(&self, token: &WebLoginTokenInfo, max_width: usize) -> String {
+        let text = self.format_restrictions(token);
+        if text.chars().count() <= max_width {
+            let padding = max_width - text.chars().count();
+            return format!("{}{}", text, " ".repeat(padding));
+        }
+        self.truncate_token_name(&text, max_width)
+    }
+
     fn format_controls(&self, max_width: usize, is_selected: bool) -> String {
         if !is_selected {
             return " ".repeat(max_width);
@@ -283,10 +383,9 @@ impl<'a> TokenManagementScreen<'a> {
         max_width = std::cmp::max(max_width, title_text.len());
 
         let mut items = vec![];
-        for (i, (token, created_at, read_only)) in self.token_list.iter().enumerate() {
+        for (i, token) in self.token_list.iter().enumerate() {
             let is_selected = Some(i) == self.selected_list_index;
-            let (row_text, row_items) =
-                self.create_token_item(token, created_at, *read_only, is_selected, &column_widths);
+            let (row_text, row_items) = self.create_token_item(token, is_selected, &column_widths);
             max_width = std::cmp::max(max_width, row_text.chars().count());
             items.push(row_items);
         }
@@ -404,35 +503,33 @@ impl<'a> TokenManagementScreen<'a> {
 
     fn create_token_item(
         &self,
-        token: &str,
-        created_at: &str,
-        is_read_only: bool,
+        token: &WebLoginTokenInfo,
         is_selected: bool,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
         if is_selected {
             if let Some(new_name) = &self.renaming_token {
-                self.create_renaming_item(new_name, created_at, is_read_only, column_widths)
+                self.create_renaming_item(new_name, token, column_widths)
             } else {
-                self.create_selected_item(token, created_at, is_read_only, column_widths)
+                self.create_selected_item(token, column_widths)
             }
         } else {
-            self.create_regular_item(token, created_at, is_read_only, column_widths)
+            self.create_regular_item(token, column_widths)
         }
     }
 
     fn create_renaming_item(
         &self,
         new_name: &str,
-        created_at: &str,
-        is_read_only: bool,
+        token: &WebLoginTokenInfo,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
         let truncated_name =
             self.truncate_token_name(new_name, column_widths.token.saturating_sub(1)); // -1 for cursor
         let item_text = format!("{}_", truncated_name);
-        let date_text = self.format_date(created_at, column_widths.date, true);
-        let read_only_text = self.format_read_only(is_read_only, column_widths.read_only);
+        let date_text = self.format_date(&token.created_at, column_widths.date, true);
+        let read_only_text = self.format_read_only(token.read_only, column_widths.read_only);
+        let restrictions_text = self.format_restrictions_column(token, column_widths.restrictions);
         let controls_text = " ".repeat(column_widths.controls);
 
         let token_end = truncated_name.chars().count();
@@ -442,12 +539,13 @@ impl<'a> TokenManagementScreen<'a> {
                 .selected(),
             Text::new(&date_text),
             Text::new(&read_only_text).color_all(1),
+            Text::new(&restrictions_text),
             Text::new(&controls_text),
         ];
         (
             format!(
-                "{} {} {} {}",
-                item_text, date_text, read_only_text, controls_text
+                "{} {} {} {} {}",
+                item_text, date_text, read_only_text, restrictions_text, controls_text
             ),
             items,
         )
@@ -455,18 +553,17 @@ impl<'a> TokenManagementScreen<'a> {
 
     fn create_selected_item(
         &self,
-        token: &str,
-        created_at: &str,
-        is_read_only: bool,
+        token: &WebLoginTokenInfo,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
-        let mut item_text = self.truncate_token_name(token, column_widths.token);
+        let mut item_text = self.truncate_token_name(&token.name, column_widths.token);
         if item_text.is_empty() {
             // otherwise the table gets messed up
             item_text.push(' ');
         };
-        let date_text = self.format_date(created_at, column_widths.date, true);
-        let read_only_text = self.format_read_only(is_read_only, column_widths.read_only);
+        let date_text = self.format_date(&token.created_at, column_widths.date, true);
+        let read_only_text = self.format_read_only(token.read_only, column_widths.read_only);
+        let restrictions_text = self.format_restrictions_column(token, column_widths.restrictions);
         let controls_text = self.format_controls(column_widths.controls, true);
 
         // Determine highlight ranges for controls based on the actual content
@@ -491,13 +588,14 @@ impl<'a> TokenManagementScreen<'a> {
             Text::new(&item_text).color_range(0, ..).selected(),
             Text::new(&date_text).selected(),
             Text::new(&read_only_text).color_all(1).selected(),
+            Text::new(&restrictions_text).selected(),
             controls_colored,
         ];
 
         (
             format!(
-                "{} {} {} {}",
-                item_text, date_text, read_only_text, controls_text
+                "{} {} {} {} {}",
+                item_text, date_text, read_only_text, restrictions_text, controls_text
             ),
             items,
         )
@@ -505,30 +603,30 @@ impl<'a> TokenManagementScreen<'a> {
 
     fn create_regular_item(
         &self,
-        token: &str,
-        created_at: &str,
-        is_read_only: bool,
+        token: &WebLoginTokenInfo,
         column_widths: &ColumnWidths,
     ) -> (String, Vec<Text>) {
-        let mut item_text = self.truncate_token_name(token, column_widths.token);
+        let mut item_text = self.truncate_token_name(&token.name, column_widths.token);
         if item_text.is_empty() {
             // otherwise the table gets messed up
             item_text.push(' ');
         };
-        let date_text = self.format_date(created_at, column_widths.date, true);
-        let read_only_text = self.format_read_only(is_read_only, column_widths.read_only);
+        let date_text = self.format_date(&token.created_at, column_widths.date, true);
+        let read_only_text = self.format_read_only(token.read_only, column_widths.read_only);
+        let restrictions_text = self.format_restrictions_column(token, column_widths.restrictions);
         let controls_text = " ".repeat(column_widths.controls);
 
         let items = vec![
             Text::new(&item_text).color_range(0, ..),
             Text::new(&date_text),
             Text::new(&read_only_text).color_all(1),
+            Text::new(&restrictions_text),
             Text::new(&controls_text),
         ];
         (
             format!(
-                "{} {} {} {}",
-                item_text, date_text, read_only_text, controls_text
+                "{} {} {} {} {}",
+                item_text, date_text, read_only_text, restrictions_text, controls_text
             ),
             items,
         )
@@ -539,16 +637,29 @@ impl<'a> TokenManagementScreen<'a> {
         let medium_create_text = "<n> - new token, <o> - read-only".to_string();
         let short_create_text = "<n> - new, <o> - RO".to_string();
 
-        if let Some(name) = &self.entering_new_token_name {
-            let max_width = self.cols.saturating_sub(1); // Leave room for cursor
-            let truncated_name = if name.chars().count() > max_width {
-                let chars: Vec<char> = name.chars().take(max_width).collect();
-                chars.into_iter().collect()
+        if let Some(new_token) = &self.new_token {
+            let prompt = match new_token.field {
+                NewTokenField::Name => "Name: ",
+                NewTokenField::ExpiresIn => "Expires in: ",
+                NewTokenField::SessionScope => "Sessions: ",
+            };
+            let value = new_token.current_field();
+            let max_width = self
+                .cols
+                .saturating_sub(prompt.chars().count())
+                .saturating_sub(1); // Leave room for cursor
+            let truncated_value: String = if value.chars().count() > max_width {
+                value.chars().take(max_width).collect()
             } else {
-                name.clone()
+                value.clone()
             };
-            let text = format!("{}_", truncated_name);
-            (text.clone(), Text::new(&text).color_range(3, ..))
+            let text = format!("{}{}_", prompt, truncated_value);
+            (
+                text.clone(),
+                Text::new(&text)
+                    .color_range(2, ..prompt.chars().count())
+                    .color_range(3, prompt.chars().count()..),
+            )
         } else {
             // Check which text fits
             let (text_to_use, n_range, o_range) = if full_create_text.chars().count() <= self.cols {
@@ -569,11 +680,21 @@ impl<'a> TokenManagementScreen<'a> {
     }
 
     fn create_help_line(&self) -> (String, Text) {
-        let (text, highlight_range) = if self.entering_new_token_name.is_some() {
-            (
-                "Help: Enter optional name for new token, <Enter> to submit",
-                41..=47,
-            )
+        let (text, highlight_range) = if let Some(new_token) = &self.new_token {
+            match new_token.field {
+                NewTokenField::Name => (
+                    "Help: Enter optional name for new token, <Enter> to continue",
+                    41..=47,
+                ),
+                NewTokenField::ExpiresIn => (
+                    "Help: Optional expiry (eg. 4h, 7days), <Enter> to continue",
+                    39..=45,
+                ),
+                NewTokenField::SessionScope => (
+                    "Help: Optional session name or glob (eg. pairing-*), <Enter> to submit",
+                    53..=59,
+                ),
+            }
         } else if self.renaming_token.is_some() {
             (
                 "Help: Enter new name for this token, <Enter> to submit",
@@ -589,7 +710,7 @@ impl<'a> TokenManagementScreen<'a> {
         let mut help_line = Text::new(text).color_range(3, highlight_range);
 
         // Add second highlight for the back option
-        if self.entering_new_token_name.is_none() && self.renaming_token.is_none() {
+        if self.new_token.is_none() && self.renaming_token.is_none() {
             help_line = help_line.color_range(3, 36..=40);
         }
 
@@ -648,7 +769,7 @@ impl<'a> TokenManagementScreen<'a> {
     fn print_items_to_screen(&self, content: ScreenContent, layout: Layout) {
         print_text_with_coordinates(content.title.1, layout.title_x, layout.base_y, None, None);
 
-        let mut table = Table::new().add_row(vec![" ", " ", " ", " "]);
+        let mut table = Table::new().add_row(vec![" ", " ", " ", " ", " "]);
         for item in content.items.into_iter() {
             table = table.add_styled_row(item);
         }
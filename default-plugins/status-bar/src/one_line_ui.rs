@@ -1543,6 +1543,7 @@ fn add_keygroup_separator(help: &ModeInfo, max_len: usize) -> Option<LinePart> {
             InputMode::RenameTab => Some("RENAMING TAB"),
             InputMode::EnterSearch => Some("ENTERING SEARCH TERM"),
             InputMode::Search => Some("SEARCHING"),
+            InputMode::Copy => Some("COPYING"),
             _ => None,
         };
         if let Some(mode_help_text) = mode_help_text {
@@ -1572,6 +1573,7 @@ fn add_keygroup_separator(help: &ModeInfo, max_len: usize) -> Option<LinePart> {
         InputMode::RenameTab => Some("RENAMING TAB"),
         InputMode::EnterSearch => Some("ENTERING SEARCH TERM"),
         InputMode::Search => Some("SEARCHING"),
+        InputMode::Copy => Some("COPYING"),
         _ => None,
     };
     if let Some(mode_help_text) = mode_help_text {
@@ -1786,6 +1788,8 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<KeyWithModifier
             action_key(&km, &[A::SearchToggleOption{option: SOpt::Wrap}])),
         (s("Whole words"), s("Whole"),
             action_key(&km, &[A::SearchToggleOption{option: SOpt::WholeWord}])),
+        (s("Regex"), s("Regex"),
+            action_key(&km, &[A::SearchToggleOption{option: SOpt::Regex}])),
     ]} else if mi.mode == IM::Session { vec![
         (s("Detach"), s("Detach"), action_key(&km, &[Action::Detach])),
         (s("Session Manager"), s("Manager"), session_manager_key(&km)),
@@ -27,19 +27,23 @@ use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
 
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
-    create_token, list_tokens, revoke_all_tokens, revoke_token,
+    create_restricted_token, list_tokens, revoke_all_tokens, revoke_token,
 };
 
+#[cfg(feature = "web_server_capability")]
+use zellij_utils::web_audit_log::{read_audit_log, AuditFilter};
+
 use miette::{Report, Result};
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
-    cli::{CliArgs, Command, SessionCommand, Sessions},
+    cli::{CliArgs, Command, PluginPermissionsCommand, SessionCommand, Sessions},
     data::ConnectToSession,
     envs,
     input::{
         actions::Action,
         config::{Config, ConfigError},
         options::Options,
+        permission::PermissionCache,
     },
     setup::Setup,
 };
@@ -86,7 +90,7 @@ pub(crate) fn delete_all_sessions(yes: bool, force: bool) {
         .map(|s| s.0.clone())
         .collect();
     let (_live_sessions, resurrectable_map) =
-        scan_session_list_default_dirs(&String::new(), &[], &BTreeMap::new());
+        scan_session_list_default_dirs(&String::new(), &[], &BTreeMap::new(), &BTreeMap::new());
     let mut resurrectable_sessions: Vec<(String, Duration)> =
         resurrectable_map.into_iter().collect();
     if force {
@@ -250,18 +254,43 @@ pub(crate) fn stop_web_server() -> Result<(), String> {
 }
 
 #[cfg(feature = "web_server_capability")]
-pub(crate) fn create_auth_token(name: Option<String>, read_only: bool) -> Result<String, String> {
+pub(crate) fn create_auth_token(
+    name: Option<String>,
+    read_only: bool,
+    expires_in: Option<Duration>,
+    session_scope: Option<String>,
+) -> Result<String, String> {
     // returns the token and it's name
-    create_token(name, read_only)
+    create_restricted_token(name, read_only, expires_in, session_scope.clone())
         .map(|(token, token_name)| {
             let access_type = if read_only { " (read-only)" } else { "" };
-            format!("{}: {}{}", token_name, token, access_type)
+            let mut restrictions = vec![];
+            if let Some(expires_in) = expires_in {
+                restrictions.push(format!(
+                    "expires in {}",
+                    humantime::format_duration(expires_in)
+                ));
+            }
+            if let Some(session_scope) = session_scope {
+                restrictions.push(format!("sessions: {}", session_scope));
+            }
+            let restrictions = if restrictions.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", restrictions.join(", "))
+            };
+            format!("{}: {}{}{}", token_name, token, access_type, restrictions)
         })
         .map_err(|e| e.to_string())
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-pub(crate) fn create_auth_token(_name: Option<String>, _read_only: bool) -> Result<String, String> {
+pub(crate) fn create_auth_token(
+    _name: Option<String>,
+    _read_only: bool,
+    _expires_in: Option<Duration>,
+    _session_scope: Option<String>,
+) -> Result<String, String> {
     log::error!(
         "This version of Zellij was compiled without web server support, cannot create auth token!"
     );
@@ -312,9 +341,21 @@ pub(crate) fn list_auth_tokens() -> Result<Vec<String>, String> {
             let mut res = vec![];
             for t in tokens {
                 let access_type = if t.read_only { " [READ-ONLY]" } else { "" };
+                let expiry = t
+                    .expires_at
+                    .map(|expires_at| format!(", expires at {}", expires_at))
+                    .unwrap_or_default();
+                let session_scope = t
+                    .session_scope
+                    .map(|session_scope| format!(", sessions: {}", session_scope))
+                    .unwrap_or_default();
+                let last_used = t
+                    .last_used_at
+                    .map(|last_used_at| format!(", last used at {}", last_used_at))
+                    .unwrap_or_else(|| ", never used".to_owned());
                 res.push(format!(
-                    "{}: created at {}{}",
-                    t.name, t.created_at, access_type
+                    "{}: created at {}{}{}{}{}",
+                    t.name, t.created_at, expiry, session_scope, last_used, access_type
                 ))
             }
             res
@@ -333,6 +374,51 @@ pub(crate) fn list_auth_tokens() -> Result<Vec<String>, String> {
     std::process::exit(2);
 }
 
+#[cfg(feature = "web_server_capability")]
+pub(crate) fn web_audit_log(
+    session: Option<String>,
+    token_name: Option<String>,
+    since: Option<Duration>,
+    as_json: bool,
+) -> Result<Vec<String>, String> {
+    // returns the matching audit entries line by line
+    let filter = AuditFilter {
+        session,
+        token_name,
+        since: since.and_then(|since| std::time::SystemTime::now().checked_sub(since)),
+    };
+    read_audit_log(&filter)
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|entry| {
+                    if as_json {
+                        serde_json::to_string(entry).unwrap_or_default()
+                    } else {
+                        entry.to_string()
+                    }
+                })
+                .collect()
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "web_server_capability"))]
+pub(crate) fn web_audit_log(
+    _session: Option<String>,
+    _token_name: Option<String>,
+    _since: Option<Duration>,
+    _as_json: bool,
+) -> Result<Vec<String>, String> {
+    log::error!(
+        "This version of Zellij was compiled without web server support, cannot show the audit log!"
+    );
+    eprintln!(
+        "This version of Zellij was compiled without web server support, cannot show the audit log!"
+    );
+    std::process::exit(2);
+}
+
 /// Default timeout for web server status check (in seconds)
 #[cfg(feature = "web_server_capability")]
 pub const DEFAULT_WEB_SERVER_STATUS_TIMEOUT_SECS: u64 = 30;
@@ -941,6 +1027,61 @@ pub(crate) fn list_aliases(opts: CliArgs) {
     process::exit(0);
 }
 
+// running sessions watch the permission cache, so revoking permissions applies to them as well
+pub(crate) fn plugin_permissions(command: PluginPermissionsCommand) {
+    let mut permission_cache = PermissionCache::from_path_or_default(None);
+    match command {
+        PluginPermissionsCommand::List => {
+            let granted = permission_cache.list();
+            if granted.is_empty() {
+                println!("No permissions have been granted to plugins.");
+            }
+            for plugin in granted {
+                println!("{}", plugin.plugin_url);
+                for permission in plugin.permissions {
+                    println!("    {}", permission);
+                }
+                for filesystem_permission in plugin.filesystem_permissions {
+                    println!("    {}", filesystem_permission.display_name());
+                }
+            }
+        },
+        PluginPermissionsCommand::Grant { url, permissions } => {
+            permission_cache.grant(url.clone(), permissions);
+            if let Err(e) = permission_cache.write_to_file() {
+                eprintln!("Failed to write permission cache: {}", e);
+                process::exit(2);
+            }
+            println!("Granted permissions to {}", url);
+        },
+        PluginPermissionsCommand::Revoke { url, permissions } => {
+            if !permission_cache.revoke(url.clone(), &permissions) {
+                eprintln!("No such permissions were granted to {}", url);
+                process::exit(2);
+            }
+            if let Err(e) = permission_cache.write_to_file() {
+                eprintln!("Failed to write permission cache: {}", e);
+                process::exit(2);
+            }
+            println!("Revoked permissions from {}", url);
+        },
+    }
+    process::exit(0);
+}
+
+pub(crate) fn replay(file: &std::path::Path, speed: f64, idle_time_limit: Option<f64>) {
+    let options = zellij_client::replay::ReplayOptions {
+        speed,
+        idle_time_limit: idle_time_limit
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(Duration::from_secs_f64),
+    };
+    if let Err(e) = zellij_client::replay::replay(file, options) {
+        eprintln!("{:?}", e);
+        process::exit(1);
+    }
+}
+
 pub(crate) fn watch_session(session_name: Option<String>, opts: CliArgs) {
     let (config, _, config_options, _, _) = match Setup::from_cli_args(&opts) {
         Ok(results) => results,
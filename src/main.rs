@@ -4,7 +4,7 @@ mod tests;
 
 use clap::Parser;
 use zellij_utils::{
-    cli::{CliAction, CliArgs, Command, Sessions},
+    cli::{CliAction, CliArgs, Command, PluginSubcommand, Sessions},
     consts::{create_config_and_cache_folders, VERSION},
     data::UnblockCondition,
     envs,
@@ -33,12 +33,15 @@ fn main() {
             command,
             direction,
             cwd,
+            env,
             floating,
             in_place,
             close_replaced_pane,
             name,
             close_on_exit,
             start_suspended,
+            restart,
+            max_restarts,
             x,
             y,
             width,
@@ -74,6 +77,7 @@ fn main() {
                 plugin: None,
                 direction,
                 cwd,
+                env,
                 floating,
                 in_place,
                 close_replaced_pane,
@@ -81,6 +85,8 @@ fn main() {
                 name,
                 close_on_exit,
                 start_suspended,
+                restart,
+                max_restarts,
                 configuration: None,
                 skip_plugin_cache,
                 x,
@@ -103,7 +109,15 @@ fn main() {
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
-            url,
+            subcommand: Some(PluginSubcommand::Permissions { ref command }),
+            ..
+        })) = opts.command
+        {
+            commands::plugin_permissions(command.clone());
+        }
+        if let Some(Command::Sessions(Sessions::Plugin {
+            url: Some(url),
+            subcommand: None,
             floating,
             in_place,
             close_replaced_pane,
@@ -128,6 +142,7 @@ fn main() {
                 plugin: Some(url),
                 direction: None,
                 cwd,
+                env: vec![],
                 floating,
                 in_place,
                 close_replaced_pane,
@@ -135,6 +150,8 @@ fn main() {
                 name: None,
                 close_on_exit: false,
                 start_suspended: false,
+                restart: None,
+                max_restarts: None,
                 configuration,
                 skip_plugin_cache,
                 x,
@@ -203,6 +220,75 @@ fn main() {
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Replay {
+            file,
+            speed,
+            idle_time_limit,
+            inline,
+            floating,
+        })) = opts.command
+        {
+            let mut file = file;
+            if file.is_relative() {
+                if let Ok(cwd) = std::env::current_dir() {
+                    file = cwd.join(file);
+                }
+            }
+            if inline || envs::get_session_name().is_err() {
+                commands::replay(&file, speed, idle_time_limit);
+                std::process::exit(0);
+            }
+            // inside a session, play the recording in its own (read-only) pane
+            let mut command = vec![
+                std::env::current_exe()
+                    .map(|exe| exe.display().to_string())
+                    .unwrap_or_else(|_| "zellij".to_owned()),
+                "replay".to_owned(),
+                "--inline".to_owned(),
+                "--speed".to_owned(),
+                speed.to_string(),
+            ];
+            if let Some(idle_time_limit) = idle_time_limit {
+                command.push("--idle-time-limit".to_owned());
+                command.push(idle_time_limit.to_string());
+            }
+            command.push(file.display().to_string());
+            let command_cli_action = CliAction::NewPane {
+                command,
+                plugin: None,
+                direction: None,
+                cwd: None,
+                env: vec![],
+                floating,
+                in_place: false,
+                close_replaced_pane: false,
+                pane_id: None,
+                name: Some(format!("Replay: {}", file.display())),
+                close_on_exit: false,
+                start_suspended: false,
+                restart: None,
+                max_restarts: None,
+                configuration: None,
+                skip_plugin_cache: false,
+                x: None,
+                y: None,
+                width: None,
+                height: None,
+                pinned: None,
+                stacked: false,
+                blocking: false,
+                block_until_exit_success: false,
+                block_until_exit_failure: false,
+                block_until_exit: false,
+                unblock_condition: None,
+                near_current_pane: false,
+                no_focus: false,
+                borderless: None,
+                tab_id: None,
+            };
+            commands::send_action_to_session(command_cli_action, opts.session, config);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::Pipe {
             name,
             payload,
@@ -271,6 +357,7 @@ fn main() {
                 layout: opts.layout.clone(),
                 layout_string: opts.layout_string.clone(),
                 layout_dir: options.as_ref().and_then(|o| o.layout_dir.clone()),
+                layout_args: opts.layout_args.clone(),
                 name: None,
                 cwd: options.as_ref().and_then(|o| o.default_cwd.clone()),
                 initial_command: vec![],
@@ -346,7 +433,12 @@ fn main() {
             }
         } else if web_opts.create_token {
             let read_only = false;
-            match commands::create_auth_token(web_opts.token_name.clone(), read_only) {
+            match commands::create_auth_token(
+                web_opts.token_name.clone(),
+                read_only,
+                web_opts.token_expires_in,
+                web_opts.token_session.clone(),
+            ) {
                 Ok(token_and_name) => {
                     println!("Created token successfully");
                     println!("");
@@ -359,7 +451,12 @@ fn main() {
             }
         } else if web_opts.create_read_only_token {
             let read_only = true;
-            match commands::create_auth_token(web_opts.token_name.clone(), read_only) {
+            match commands::create_auth_token(
+                web_opts.token_name.clone(),
+                read_only,
+                web_opts.token_expires_in,
+                web_opts.token_session.clone(),
+            ) {
                 Ok(token_and_name) => {
                     println!("Created token successfully");
                     println!("");
@@ -407,6 +504,23 @@ fn main() {
                     std::process::exit(2)
                 },
             }
+        } else if web_opts.audit {
+            match commands::web_audit_log(
+                web_opts.audit_session.clone(),
+                web_opts.audit_token.clone(),
+                web_opts.audit_since,
+                web_opts.audit_json,
+            ) {
+                Ok(audit_log) => {
+                    for item in audit_log {
+                        println!("{}", item);
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Failed to read the audit log: {}", e);
+                    std::process::exit(2)
+                },
+            }
         }
     } else {
         commands::start_client(opts);
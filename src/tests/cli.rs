@@ -1,16 +1,34 @@
 use std::net::{IpAddr, Ipv4Addr};
 
 use clap::{CommandFactory, Parser};
-use zellij_utils::cli::{CliArgs, Command};
+use zellij_utils::cli::{CliArgs, Command, PluginPermissionsCommand, PluginSubcommand, Sessions};
+use zellij_utils::data::PermissionType;
+use zellij_utils::input::command::RestartPolicy;
+
+// The whole command tree needs more stack than test threads get in unoptimized builds
+fn with_large_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn_scoped(scope, f)
+            .unwrap()
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    })
+}
+
+fn try_parse_from<const N: usize>(args: [&str; N]) -> Result<CliArgs, clap::Error> {
+    with_large_stack(|| CliArgs::try_parse_from(args))
+}
 
 #[test]
 fn verify_cli() {
-    CliArgs::command().debug_assert();
+    with_large_stack(|| CliArgs::command().debug_assert());
 }
 
 #[test]
 fn web_cli_status_alone_works() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status"]);
+    let args = try_parse_from(["zellij", "web", "--status"]);
     assert!(args.is_ok());
     if let Ok(CliArgs {
         command: Some(Command::Web(web)),
@@ -26,7 +44,7 @@ fn web_cli_status_alone_works() {
 
 #[test]
 fn web_cli_status_with_timeout_works() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status", "--timeout", "5"]);
+    let args = try_parse_from(["zellij", "web", "--status", "--timeout", "5"]);
     assert!(args.is_ok());
     if let Ok(CliArgs {
         command: Some(Command::Web(web)),
@@ -43,7 +61,7 @@ fn web_cli_status_with_timeout_works() {
 #[test]
 fn web_cli_timeout_with_status_works() {
     // Test with --timeout before --status (order shouldn't matter)
-    let args = CliArgs::try_parse_from(["zellij", "web", "--timeout", "10", "--status"]);
+    let args = try_parse_from(["zellij", "web", "--timeout", "10", "--status"]);
     assert!(args.is_ok());
     if let Ok(CliArgs {
         command: Some(Command::Web(web)),
@@ -59,25 +77,25 @@ fn web_cli_timeout_with_status_works() {
 
 #[test]
 fn web_cli_timeout_without_status_fails() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--timeout", "5"]);
+    let args = try_parse_from(["zellij", "web", "--timeout", "5"]);
     assert!(args.is_err());
 }
 
 #[test]
 fn web_cli_status_with_start_fails() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status", "--start"]);
+    let args = try_parse_from(["zellij", "web", "--status", "--start"]);
     assert!(args.is_err());
 }
 
 #[test]
 fn web_cli_status_with_stop_fails() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status", "--stop"]);
+    let args = try_parse_from(["zellij", "web", "--status", "--stop"]);
     assert!(args.is_err());
 }
 
 #[test]
 fn web_cli_status_with_ip_works() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status", "--ip", "127.0.0.1"]);
+    let args = try_parse_from(["zellij", "web", "--status", "--ip", "127.0.0.1"]);
     assert!(args.is_ok());
     if let Ok(CliArgs {
         command: Some(Command::Web(web)),
@@ -93,7 +111,7 @@ fn web_cli_status_with_ip_works() {
 
 #[test]
 fn web_cli_status_with_port_works() {
-    let args = CliArgs::try_parse_from(["zellij", "web", "--status", "--port", "9000"]);
+    let args = try_parse_from(["zellij", "web", "--status", "--port", "9000"]);
     assert!(args.is_ok());
     if let Ok(CliArgs {
         command: Some(Command::Web(web)),
@@ -109,7 +127,7 @@ fn web_cli_status_with_port_works() {
 
 #[test]
 fn web_cli_status_with_ip_and_port_works() {
-    let args = CliArgs::try_parse_from([
+    let args = try_parse_from([
         "zellij", "web", "--status", "--ip", "0.0.0.0", "--port", "9000",
     ]);
     assert!(args.is_ok());
@@ -125,3 +143,154 @@ fn web_cli_status_with_ip_and_port_works() {
         panic!("Expected Web command");
     }
 }
+
+#[test]
+fn plugin_cli_still_loads_a_plugin_url() {
+    let args = try_parse_from(["zellij", "plugin", "--floating", "--", "zellij:strider"]);
+    if let Ok(CliArgs {
+        command:
+            Some(Command::Sessions(Sessions::Plugin {
+                url,
+                subcommand,
+                floating,
+                ..
+            })),
+        ..
+    }) = args
+    {
+        assert_eq!(url, Some("zellij:strider".to_owned()));
+        assert!(subcommand.is_none());
+        assert!(floating);
+    } else {
+        panic!("Expected Plugin command, got: {:?}", args);
+    }
+}
+
+#[test]
+fn plugin_permissions_revoke_cli_works() {
+    let args = try_parse_from([
+        "zellij",
+        "plugin",
+        "permissions",
+        "revoke",
+        "file:/path/to/plugin.wasm",
+        "RunCommands",
+        "WebAccess",
+    ]);
+    if let Ok(CliArgs {
+        command:
+            Some(Command::Sessions(Sessions::Plugin {
+                url: None,
+                subcommand:
+                    Some(PluginSubcommand::Permissions {
+                        command: PluginPermissionsCommand::Revoke { url, permissions },
+                    }),
+                ..
+            })),
+        ..
+    }) = args
+    {
+        assert_eq!(url, "file:/path/to/plugin.wasm");
+        assert_eq!(
+            permissions,
+            vec![PermissionType::RunCommands, PermissionType::WebAccess]
+        );
+    } else {
+        panic!(
+            "Expected plugin permissions revoke command, got: {:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn plugin_permissions_grant_cli_requires_permissions() {
+    let args = try_parse_from([
+        "zellij",
+        "plugin",
+        "permissions",
+        "grant",
+        "file:/path/to/plugin.wasm",
+    ]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn run_cli_env_works() {
+    let args = try_parse_from([
+        "zellij",
+        "run",
+        "--env",
+        "DATABASE_URL=postgres://localhost/staging?sslmode=disable",
+        "--env",
+        "EMPTY=",
+        "--",
+        "./server",
+    ]);
+    if let Ok(CliArgs {
+        command: Some(Command::Sessions(Sessions::Run { env, command, .. })),
+        ..
+    }) = args
+    {
+        assert_eq!(
+            env,
+            vec![
+                (
+                    "DATABASE_URL".to_owned(),
+                    "postgres://localhost/staging?sslmode=disable".to_owned()
+                ),
+                ("EMPTY".to_owned(), "".to_owned()),
+            ]
+        );
+        assert_eq!(command, vec!["./server".to_owned()]);
+    } else {
+        panic!("Expected Run command, got: {:?}", args);
+    }
+}
+
+#[test]
+fn run_cli_env_requires_key_and_value() {
+    let args = try_parse_from(["zellij", "run", "--env", "DATABASE_URL", "--", "./server"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn run_cli_restart_works() {
+    let args = try_parse_from([
+        "zellij",
+        "run",
+        "--restart",
+        "on-failure",
+        "--max-restarts",
+        "5",
+        "--",
+        "./worker",
+    ]);
+    if let Ok(CliArgs {
+        command:
+            Some(Command::Sessions(Sessions::Run {
+                restart,
+                max_restarts,
+                ..
+            })),
+        ..
+    }) = args
+    {
+        assert_eq!(restart, Some(RestartPolicy::OnFailure));
+        assert_eq!(max_restarts, Some(5));
+    } else {
+        panic!("Expected Run command, got: {:?}", args);
+    }
+}
+
+#[test]
+fn run_cli_restart_rejects_unknown_policy() {
+    let args = try_parse_from(["zellij", "run", "--restart", "sometimes", "--", "./worker"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn run_cli_max_restarts_requires_restart() {
+    let args = try_parse_from(["zellij", "run", "--max-restarts", "5", "--", "./worker"]);
+    assert!(args.is_err());
+}
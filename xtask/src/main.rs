@@ -98,6 +98,10 @@ fn workspace_members() -> &'static Vec<WorkspaceMember> {
                 crate_name: "zellij-tile",
                 build: false,
             },
+            WorkspaceMember {
+                crate_name: "zellij-tile-test",
+                build: false,
+            },
             WorkspaceMember {
                 crate_name: "zellij-client",
                 build: false,
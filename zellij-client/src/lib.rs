@@ -14,6 +14,7 @@ mod keyboard_parser;
 mod nested_reannounce;
 #[cfg(feature = "web_server_capability")]
 pub mod remote_attach;
+pub mod replay;
 mod stdin_ansi_parser;
 mod stdin_handler;
 #[cfg(windows)]
@@ -1036,6 +1037,7 @@ pub fn start_client(
                 max_panes: cli_args.max_panes,
                 force_run_layout_commands: false,
                 cwd: None,
+                layout_args: cli_args.layout_args.iter().cloned().collect(),
                 host_terminal_env: host_terminal_env(),
             };
             (
@@ -1082,6 +1084,7 @@ pub fn start_client(
                 max_panes: cli_args.max_panes,
                 force_run_layout_commands: force_run_commands,
                 cwd,
+                layout_args: Default::default(), // resurrected sessions do not have parameters
                 host_terminal_env: host_terminal_env(),
             };
 
@@ -1139,6 +1142,7 @@ pub fn start_client(
                 max_panes: cli_args.max_panes,
                 force_run_layout_commands: false,
                 cwd: layout_cwd,
+                layout_args: cli_args.layout_args.iter().cloned().collect(),
                 host_terminal_env: host_terminal_env(),
             };
 
@@ -1663,6 +1667,7 @@ pub fn start_server_detached(
                 max_panes: cli_args.max_panes,
                 force_run_layout_commands: force_run_commands,
                 cwd,
+                layout_args: Default::default(),
                 host_terminal_env: host_terminal_env(),
             };
 
@@ -1721,6 +1726,7 @@ pub fn start_server_detached(
                 max_panes: cli_args.max_panes,
                 force_run_layout_commands: false,
                 cwd: layout_cwd,
+                layout_args: cli_args.layout_args.iter().cloned().collect(),
                 host_terminal_env: host_terminal_env(),
             };
 
@@ -0,0 +1,114 @@
+//! Plays back asciicast recordings (`zellij replay`) in the current terminal.
+
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use zellij_utils::asciicast::{Asciicast, AsciicastEvent};
+use zellij_utils::errors::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayOptions {
+    /// Playback speed multiplier (2.0 plays twice as fast)
+    pub speed: f64,
+    /// Pauses between events longer than this are shortened to it
+    pub idle_time_limit: Option<Duration>,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        ReplayOptions {
+            speed: 1.0,
+            idle_time_limit: None,
+        }
+    }
+}
+
+/// Plays the recording at `path` to STDOUT, until it ends or the user presses `q` or `Ctrl-c`.
+/// Any other input is ignored.
+pub fn replay(path: &Path, options: ReplayOptions) -> Result<()> {
+    let err_context = || format!("failed to replay {}", path.display());
+    let file = File::open(path).with_context(err_context)?;
+    let recording = Asciicast::from_reader(BufReader::new(file)).with_context(err_context)?;
+    let schedule = playback_schedule(&recording.events, options);
+
+    terminal::enable_raw_mode().with_context(err_context)?;
+    let result = play(&recording, &schedule);
+    let _ = terminal::disable_raw_mode();
+    // leave the last frame on screen, but in a sane state
+    let mut stdout = io::stdout();
+    let _ = write!(stdout, "\u{1b}[m\u{1b}[?25h\r\n");
+    let _ = stdout.flush();
+    result.with_context(err_context)
+}
+
+fn play(recording: &Asciicast, schedule: &[Duration]) -> Result<()> {
+    let mut stdout = io::stdout();
+    let started_at = Instant::now();
+    for ((_time, event), play_at) in recording.events.iter().zip(schedule) {
+        loop {
+            let now = started_at.elapsed();
+            if now >= *play_at {
+                break;
+            }
+            if event::poll(*play_at - now)? && quit_requested(event::read()?) {
+                return Ok(());
+            }
+        }
+        // input, markers and resizes are not played back - we cannot resize the terminal we
+        // are playing in
+        if let AsciicastEvent::Output(output) = event {
+            stdout.write_all(output.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn quit_requested(event: Event) -> bool {
+    match event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) => {
+            code == KeyCode::Char('q')
+                || (code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL))
+        },
+        _ => false,
+    }
+}
+
+/// The time (since the start of the playback) at which each event should be played
+fn playback_schedule(
+    events: &[(Duration, AsciicastEvent)],
+    options: ReplayOptions,
+) -> Vec<Duration> {
+    let speed = if options.speed.is_finite() && options.speed > 0.0 {
+        options.speed
+    } else {
+        1.0
+    };
+    let mut previous_event_time = Duration::ZERO;
+    let mut play_at = Duration::ZERO;
+    events
+        .iter()
+        .map(|(time, _event)| {
+            let mut pause = time.saturating_sub(previous_event_time);
+            if let Some(idle_time_limit) = options.idle_time_limit {
+                pause = pause.min(idle_time_limit);
+            }
+            previous_event_time = *time;
+            play_at += pause.div_f64(speed);
+            play_at
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "./unit/replay_tests.rs"]
+mod replay_tests;
@@ -0,0 +1,55 @@
+use super::{playback_schedule, ReplayOptions};
+use std::time::Duration;
+use zellij_utils::asciicast::AsciicastEvent;
+
+fn events(times_in_millis: &[u64]) -> Vec<(Duration, AsciicastEvent)> {
+    times_in_millis
+        .iter()
+        .map(|millis| {
+            (
+                Duration::from_millis(*millis),
+                AsciicastEvent::Output(String::new()),
+            )
+        })
+        .collect()
+}
+
+fn millis(durations: Vec<Duration>) -> Vec<u128> {
+    durations.iter().map(|d| d.as_millis()).collect()
+}
+
+#[test]
+fn schedule_follows_recording_times() {
+    let schedule = playback_schedule(&events(&[0, 100, 1100]), ReplayOptions::default());
+    assert_eq!(millis(schedule), vec![0, 100, 1100]);
+}
+
+#[test]
+fn schedule_is_scaled_by_speed() {
+    let options = ReplayOptions {
+        speed: 2.0,
+        ..Default::default()
+    };
+    let schedule = playback_schedule(&events(&[0, 100, 1100]), options);
+    assert_eq!(millis(schedule), vec![0, 50, 550]);
+}
+
+#[test]
+fn long_pauses_are_capped_by_the_idle_time_limit() {
+    let options = ReplayOptions {
+        speed: 1.0,
+        idle_time_limit: Some(Duration::from_millis(200)),
+    };
+    let schedule = playback_schedule(&events(&[0, 100, 5100, 5200]), options);
+    assert_eq!(millis(schedule), vec![0, 100, 300, 400]);
+}
+
+#[test]
+fn invalid_speeds_play_in_real_time() {
+    let options = ReplayOptions {
+        speed: 0.0,
+        ..Default::default()
+    };
+    let schedule = playback_schedule(&events(&[0, 100]), options);
+    assert_eq!(millis(schedule), vec![0, 100]);
+}
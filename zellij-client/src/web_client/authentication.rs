@@ -4,7 +4,8 @@ use axum::http::header::SET_COOKIE;
 use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
 use axum_extra::extract::cookie::{Cookie, SameSite};
 use zellij_utils::web_authentication_tokens::{
-    hash_token, is_session_token_read_only, validate_session_token,
+    hash_token, is_session_token_read_only, session_matches_scope, session_token_scope,
+    validate_session_token,
 };
 
 #[derive(Clone)]
@@ -13,6 +14,20 @@ pub struct SessionTokenHash(pub String);
 #[derive(Clone, Copy)]
 pub struct IsReadOnly(pub bool);
 
+/// The glob of session names the login token behind this request may attach to (None means all
+/// sessions)
+#[derive(Clone, Debug)]
+pub struct SessionScope(pub Option<String>);
+
+impl SessionScope {
+    pub fn allows(&self, session_name: &str) -> bool {
+        self.0
+            .as_ref()
+            .map(|scope| session_matches_scope(scope, session_name))
+            .unwrap_or(true)
+    }
+}
+
 pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
     let cookies = parse_cookies(&request);
 
@@ -26,12 +41,19 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
             // Check if this is a read-only token
             let is_read_only = is_session_token_read_only(&session_token).unwrap_or(true);
 
+            // Tokens scoped to sessions we can't look up are treated as having access to none
+            let session_scope = session_token_scope(&session_token).unwrap_or_else(|e| {
+                log::error!("Failed to get session scope of token: {}", e);
+                Some(String::new())
+            });
+
             // Compute session token hash for client ownership verification
             let session_token_hash = hash_token(&session_token);
 
             // Store in request extensions for downstream handlers
             let mut request = request;
             request.extensions_mut().insert(IsReadOnly(is_read_only));
+            request.extensions_mut().insert(SessionScope(session_scope));
             request
                 .extensions_mut()
                 .insert(SessionTokenHash(session_token_hash));
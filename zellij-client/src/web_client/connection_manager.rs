@@ -1,10 +1,19 @@
 use crate::os_input_output::ClientOsApi;
 use crate::web_client::control_message::WebServerToWebClientControlMessage;
-use crate::web_client::types::{ClientChannels, ClientConnectionBus, ConnectionTable};
+use crate::web_client::types::{
+    ClientAuditInfo, ClientChannels, ClientConnectionBus, ClientLoginToken, ConnectionTable,
+};
 use axum::extract::ws::{CloseFrame, Message};
-use std::sync::{atomic::AtomicBool, Arc};
+use std::collections::HashMap;
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::sync::CancellationToken;
+use zellij_utils::ipc::ClientToServerMsg;
+use zellij_utils::web_audit_log::{AuditEntry, AuditEvent};
+use zellij_utils::web_authentication_tokens::{is_login_token_hash_valid, session_matches_scope};
+
+const LOGIN_TOKEN_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 impl ConnectionTable {
     pub fn add_new_client(
@@ -13,11 +22,16 @@ impl ConnectionTable {
         client_os_api: Box<dyn ClientOsApi>,
         is_read_only: bool,
         session_token_hash: String,
+        session_scope: Option<String>,
     ) {
         self.client_id_to_channels
             .insert(client_id.clone(), ClientChannels::new(client_os_api));
         self.client_read_only_status
             .insert(client_id.clone(), is_read_only);
+        if let Some(session_scope) = session_scope {
+            self.client_session_scope
+                .insert(client_id.clone(), session_scope);
+        }
         self.client_session_token_hash
             .insert(client_id, session_token_hash);
     }
@@ -36,6 +50,69 @@ impl ConnectionTable {
             .unwrap_or(false)
     }
 
+    pub fn is_session_in_client_scope(&self, client_id: &str, session_name: &str) -> bool {
+        self.client_session_scope
+            .get(client_id)
+            .map(|scope| session_matches_scope(scope, session_name))
+            .unwrap_or(true)
+    }
+
+    pub fn add_client_audit_info(&mut self, client_id: &str, audit_info: ClientAuditInfo) {
+        self.client_audit_info
+            .insert(client_id.to_owned(), audit_info);
+    }
+
+    pub fn add_client_login_token(&mut self, client_id: &str, login_token: ClientLoginToken) {
+        self.client_login_token
+            .insert(client_id.to_owned(), login_token);
+    }
+
+    pub fn clients_with_expired_login_token(&self, now: Instant) -> Vec<String> {
+        self.client_login_token
+            .iter()
+            .filter(|(_, login_token)| {
+                login_token
+                    .expires_at
+                    .map(|expires_at| expires_at <= now)
+                    .unwrap_or(false)
+            })
+            .map(|(client_id, _)| client_id.clone())
+            .collect()
+    }
+
+    /// Looks up every login token in the token database, tokens we fail to look up are kept
+    pub fn clients_with_revoked_login_token(&self) -> Vec<String> {
+        let mut is_valid: HashMap<&str, bool> = HashMap::new();
+        self.client_login_token
+            .iter()
+            .filter(|(_, login_token)| {
+                let token_hash = login_token.token_hash.as_str();
+                let is_valid = *is_valid.entry(token_hash).or_insert_with(|| {
+                    is_login_token_hash_valid(token_hash).unwrap_or_else(|e| {
+                        log::error!("Failed to look up login token: {}", e);
+                        true
+                    })
+                });
+                !is_valid
+            })
+            .map(|(client_id, _)| client_id.clone())
+            .collect()
+    }
+
+    /// An audit log entry describing this client, to be completed by the caller
+    pub fn audit_entry(&self, client_id: &str, event: AuditEvent) -> AuditEntry {
+        let audit_info = self
+            .client_audit_info
+            .get(client_id)
+            .cloned()
+            .unwrap_or_default();
+        AuditEntry::new(event)
+            .web_client_id(client_id)
+            .token_name(audit_info.token_name)
+            .remote_addr(audit_info.remote_addr)
+            .read_only(self.is_client_read_only(client_id))
+    }
+
     pub fn add_client_control_tx(
         &mut self,
         client_id: &str,
@@ -94,6 +171,9 @@ impl ConnectionTable {
         }
         self.client_read_only_status.remove(client_id);
         self.client_session_token_hash.remove(client_id);
+        self.client_session_scope.remove(client_id);
+        self.client_audit_info.remove(client_id);
+        self.client_login_token.remove(client_id);
     }
 
     pub fn get_should_not_reconnect_flag(&self, client_id: &str) -> Option<Arc<AtomicBool>> {
@@ -211,6 +291,59 @@ impl ClientConnectionBus {
     }
 }
 
+/// Detaches these clients from their sessions and closes their connections without letting them
+/// reconnect
+pub fn close_client_connections(
+    connection_table: &Arc<Mutex<ConnectionTable>>,
+    client_ids: Vec<String>,
+) {
+    for client_id in client_ids {
+        let os_api = connection_table
+            .lock()
+            .unwrap()
+            .get_client_os_api(&client_id)
+            .cloned();
+        if let Some(os_api) = os_api {
+            os_api.send_to_server(ClientToServerMsg::ClientExited);
+        }
+        ClientConnectionBus::new(&client_id, connection_table).close_connection_kicked();
+    }
+}
+
+pub async fn close_connections_with_expired_login_tokens(
+    connection_table: Arc<Mutex<ConnectionTable>>,
+) {
+    let mut interval = tokio::time::interval(LOGIN_TOKEN_EXPIRY_CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        let expired_clients = connection_table
+            .lock()
+            .unwrap()
+            .clients_with_expired_login_token(Instant::now());
+        if !expired_clients.is_empty() {
+            log::info!(
+                "Closing {} connections with an expired login token",
+                expired_clients.len()
+            );
+            close_client_connections(&connection_table, expired_clients);
+        }
+    }
+}
+
+pub fn close_connections_with_revoked_login_tokens(connection_table: &Arc<Mutex<ConnectionTable>>) {
+    let revoked_clients = connection_table
+        .lock()
+        .unwrap()
+        .clients_with_revoked_login_token();
+    if !revoked_clients.is_empty() {
+        log::info!(
+            "Closing {} connections with a revoked login token",
+            revoked_clients.len()
+        );
+        close_client_connections(connection_table, revoked_clients);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +412,7 @@ mod tests {
             Box::new(StubOsInput::default()),
             false,
             "token".to_owned(),
+            None,
         );
         connection_table
     }
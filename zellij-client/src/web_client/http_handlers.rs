@@ -1,22 +1,29 @@
-use crate::web_client::authentication::{IsReadOnly, SessionTokenHash};
+use crate::web_client::authentication::{IsReadOnly, SessionScope, SessionTokenHash};
 use crate::web_client::control_message::SetConfigPayload;
 use crate::web_client::types::{
-    record_pending_welcome_session, AppState, CreateClientIdResponse, LoginRequest, LoginResponse,
-    SessionListResponse, SessionQuery,
+    record_pending_welcome_session, AppState, ClientAuditInfo, ClientLoginToken,
+    CreateClientIdResponse, LoginRequest, LoginResponse, SessionListResponse, SessionQuery,
 };
 use crate::web_client::utils::get_mime_type;
 use axum::{
-    extract::{Path as AxumPath, Query, State},
+    extract::{ConnectInfo, Path as AxumPath, Query, State},
     http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
 use axum_extra::extract::cookie::{Cookie, SameSite};
 use include_dir;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use zellij_utils::{
-    consts::VERSION, sessions::generate_unique_session_name,
-    web_authentication_tokens::create_session_token,
+    consts::VERSION,
+    sessions::generate_unique_session_name,
+    web_audit_log::{AuditEntry, AuditEvent},
+    web_authentication_tokens::{
+        create_session_token, login_token_for_session_token_hash, token_name_for_auth_token,
+        token_name_for_session_token_hash,
+    },
 };
 
 const ASSETS_DIR: include_dir::Dir<'_> = include_dir::include_dir!("$CARGO_MANIFEST_DIR/assets");
@@ -36,15 +43,29 @@ pub async fn serve_html() -> impl IntoResponse {
     }
 }
 
+// recording blocks on file I/O, which we keep off the async runtime
+fn record_audit_entry(entry: AuditEntry) {
+    tokio::task::spawn_blocking(move || entry.record());
+}
+
 pub async fn login_handler(
     State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
     Json(login_request): Json<LoginRequest>,
 ) -> impl IntoResponse {
+    let token_name = token_name_for_auth_token(&login_request.auth_token)
+        .ok()
+        .flatten();
     match create_session_token(
         &login_request.auth_token,
         login_request.remember_me.unwrap_or(false),
     ) {
         Ok(session_token) => {
+            record_audit_entry(
+                AuditEntry::new(AuditEvent::LoginSucceeded)
+                    .token_name(token_name)
+                    .remote_addr(Some(remote_addr.ip().to_string())),
+            );
             let is_https = state.is_https;
             let cookie = if login_request.remember_me.unwrap_or(false) {
                 // Persistent cookie for remember_me
@@ -77,19 +98,29 @@ pub async fn login_handler(
 
             response
         },
-        Err(_) => (
-            StatusCode::UNAUTHORIZED,
-            Json(LoginResponse {
-                success: false,
-                message: "Invalid authentication token".to_string(),
-            }),
-        )
-            .into_response(),
+        Err(e) => {
+            // token_name is only known if the token exists but can no longer be used
+            record_audit_entry(
+                AuditEntry::new(AuditEvent::LoginFailed)
+                    .token_name(token_name)
+                    .remote_addr(Some(remote_addr.ip().to_string()))
+                    .detail(e.to_string()),
+            );
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(LoginResponse {
+                    success: false,
+                    message: "Invalid authentication token".to_string(),
+                }),
+            )
+                .into_response()
+        },
     }
 }
 
 pub async fn create_new_client(
     State(state): State<AppState>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
     Query(params): Query<SessionQuery>,
     request: axum::extract::Request,
 ) -> Result<Json<CreateClientIdResponse>, (StatusCode, impl IntoResponse)> {
@@ -108,6 +139,37 @@ pub async fn create_new_client(
             StatusCode::INTERNAL_SERVER_ERROR,
             Json("Missing session info".to_string()),
         ))?;
+    let session_scope = request
+        .extensions()
+        .get::<SessionScope>()
+        .cloned()
+        .unwrap_or(SessionScope(None));
+    let login_token = match login_token_for_session_token_hash(&session_token_hash.0) {
+        Ok(Some((token_hash, expires_in_seconds))) => ClientLoginToken {
+            token_hash,
+            expires_at: expires_in_seconds
+                .map(|seconds| Instant::now() + Duration::from_secs(seconds.max(0) as u64)),
+        },
+        Ok(None) => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json("Login token was revoked".to_string()),
+            ));
+        },
+        Err(e) => {
+            log::error!("Failed to look up login token: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json("Failed to look up login token".to_string()),
+            ));
+        },
+    };
+    let audit_info = ClientAuditInfo {
+        token_name: token_name_for_session_token_hash(&session_token_hash.0)
+            .ok()
+            .flatten(),
+        remote_addr: Some(remote_addr.ip().to_string()),
+    };
 
     let session_name = match params.session.filter(|name| !name.is_empty()) {
         Some(session_name) => session_name,
@@ -123,18 +185,40 @@ pub async fn create_new_client(
         },
     };
 
+    if !session_scope.allows(&session_name) {
+        record_audit_entry(
+            AuditEntry::new(AuditEvent::SessionScopeViolation)
+                .token_name(audit_info.token_name)
+                .remote_addr(audit_info.remote_addr)
+                .session(&session_name)
+                .read_only(is_read_only),
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(format!(
+                "This login token cannot access session \"{}\"",
+                session_name
+            )),
+        ));
+    }
+
     let web_client_id = String::from(Uuid::new_v4());
     let os_input = state
         .client_os_api_factory
         .create_client_os_api()
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(e.to_string())))?;
 
-    state.connection_table.lock().unwrap().add_new_client(
+    let mut connection_table = state.connection_table.lock().unwrap();
+    connection_table.add_new_client(
         web_client_id.to_owned(),
         os_input,
         is_read_only,
         session_token_hash.0,
+        session_scope.0,
     );
+    connection_table.add_client_audit_info(&web_client_id, audit_info);
+    connection_table.add_client_login_token(&web_client_id, login_token);
+    drop(connection_table);
 
     let config = SetConfigPayload::from(&*state.config.lock().unwrap());
 
@@ -146,8 +230,12 @@ pub async fn create_new_client(
     }))
 }
 
-pub async fn list_sessions_handler(State(state): State<AppState>) -> Json<SessionListResponse> {
+pub async fn list_sessions_handler(
+    State(state): State<AppState>,
+    axum::Extension(session_scope): axum::Extension<SessionScope>,
+) -> Json<SessionListResponse> {
     let mut sessions = state.session_manager.list_sessions();
+    sessions.retain(|session| session_scope.allows(&session.name));
     sessions.sort_by(|a, b| a.name.cmp(&b.name));
     Json(SessionListResponse { sessions })
 }
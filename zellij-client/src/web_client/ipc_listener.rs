@@ -1,6 +1,9 @@
+use crate::web_client::connection_manager::close_connections_with_revoked_login_tokens;
+use crate::web_client::types::ConnectionTable;
 use axum_server::Handle;
 use interprocess::local_socket::traits::tokio::Listener;
 use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use zellij_utils::consts::{ipc_bind_async, WEBSERVER_SOCKET_PATH};
 use zellij_utils::prost::Message;
@@ -62,6 +65,7 @@ pub async fn send_webserver_response(
 
 pub async fn listen_to_web_server_instructions(
     server_handle: Handle<SocketAddr>,
+    connection_table: Arc<Mutex<ConnectionTable>>,
     id: &str,
     web_server_ip: IpAddr,
     web_server_port: u16,
@@ -83,6 +87,9 @@ pub async fn listen_to_web_server_instructions(
                         });
                         let _ = send_webserver_response(&mut receiver, response).await;
                     },
+                    InstructionForWebServer::LoginTokensRevoked => {
+                        close_connections_with_revoked_login_tokens(&connection_table);
+                    },
                 },
                 Err(e) => {
                     log::error!("Failed to process web server instruction: {}", e);
@@ -13,7 +13,7 @@ mod utils;
 mod websocket_handlers;
 
 use std::{
-    net::{IpAddr, Ipv4Addr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
@@ -43,6 +43,7 @@ use std::io::{BufRead, BufReader};
 use zellij_utils::input::{config::Config, options::Options};
 
 use authentication::auth_middleware;
+use connection_manager::close_connections_with_expired_login_tokens;
 use http_handlers::{
     create_new_client, get_static_asset, list_sessions_handler, login_handler, serve_html,
     version_handler,
@@ -222,9 +223,11 @@ pub async fn serve_web_client(
 
     tokio::spawn({
         let server_handle = server_handle.clone();
+        let connection_table = connection_table.clone();
         async move {
             listen_to_web_server_instructions(
                 server_handle,
+                connection_table,
                 &format!("{}", id),
                 web_server_ip,
                 web_server_port,
@@ -233,6 +236,10 @@ pub async fn serve_web_client(
         }
     });
 
+    tokio::spawn(close_connections_with_expired_login_tokens(
+        connection_table.clone(),
+    ));
+
     let is_https = state.is_https;
     let app = Router::new()
         .route("/ws/control", any(ws_handler_control))
@@ -284,7 +291,7 @@ pub async fn serve_web_client(
             };
             let _ = server
                 .handle(server_handle)
-                .serve(app.into_make_service())
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
                 .await;
         },
         None => {
@@ -297,7 +304,7 @@ pub async fn serve_web_client(
             };
             let _ = server
                 .handle(server_handle)
-                .serve(app.into_make_service())
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
                 .await;
         },
     }
@@ -22,6 +22,7 @@ use zellij_utils::{
     pane_size::{Size, SizeInPixels},
     sessions::generate_unique_session_name,
     setup::Setup,
+    web_audit_log::AuditEvent,
 };
 
 pub fn zellij_server_listener(
@@ -40,6 +41,8 @@ pub fn zellij_server_listener(
 ) {
     let _server_listener_thread = std::thread::Builder::new()
         .name("server_listener".to_string())
+        // reloading the configuration needs more than the default thread stack in debug builds
+        .stack_size(8 * 1024 * 1024)
         .spawn({
             move || {
                 let mut client_connection_bus =
@@ -118,6 +121,30 @@ pub fn zellij_server_listener(
 
                     if is_read_only && !session_exists {
                         log::error!("Read only tokens cannot create new sessions.");
+                        connection_table
+                            .lock()
+                            .unwrap()
+                            .audit_entry(&web_client_id, AuditEvent::ReadOnlyViolation)
+                            .session(&session_name)
+                            .detail("attempted to create a new session")
+                            .record();
+                        client_connection_bus.close_connection();
+                        return;
+                    }
+
+                    // This also covers switching sessions from inside an attached one
+                    let is_in_scope = connection_table
+                        .lock()
+                        .unwrap()
+                        .is_session_in_client_scope(&web_client_id, &session_name);
+                    if !is_in_scope {
+                        log::error!("Login token is not allowed to access session {}.", session_name);
+                        connection_table
+                            .lock()
+                            .unwrap()
+                            .audit_entry(&web_client_id, AuditEvent::SessionScopeViolation)
+                            .session(&session_name)
+                            .record();
                         client_connection_bus.close_connection();
                         return;
                     }
@@ -134,6 +161,15 @@ pub fn zellij_server_listener(
                         first_message,
                     );
 
+                    // the client's audit info is gone by the time we learn it has detached, so we
+                    // keep the entry we attached with around
+                    let attached_entry = connection_table
+                        .lock()
+                        .unwrap()
+                        .audit_entry(&web_client_id, AuditEvent::Attached)
+                        .session(&session_name);
+                    attached_entry.clone().record();
+
                     if let Some(pixel_dims) = client_pixel_dims {
                         os_input.send_to_server(ClientToServerMsg::TerminalPixelDimensions {
                             pixel_dimensions: PixelDimensions {
@@ -199,6 +235,10 @@ pub fn zellij_server_listener(
                                 client_connection_bus.send_stdout(bytes);
                             },
                             Some(ServerToClientMsg::SwitchSession{connect_to_session}) => {
+                                attached_entry
+                                    .for_event(AuditEvent::Detached)
+                                    .detail("switched session")
+                                    .record();
                                 reconnect_to_session = Some(connect_to_session);
                                 switched_from_previous_session = true;
                                 continue 'reconnect_loop;
@@ -282,6 +322,7 @@ pub fn zellij_server_listener(
                             },
                         }
                     }
+                    attached_entry.for_event(AuditEvent::Detached).record();
                     if reconnect_to_session.is_none() {
                         break;
                     }
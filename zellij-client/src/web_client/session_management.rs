@@ -102,6 +102,7 @@ pub fn create_first_message(
             max_panes: None,
             force_run_layout_commands: false,
             cwd: None,
+            layout_args: Default::default(),
             host_terminal_env: Default::default(),
         };
 
@@ -122,6 +123,7 @@ pub fn create_first_message(
             max_panes: None,
             force_run_layout_commands: false,
             cwd: None,
+            layout_args: Default::default(),
             host_terminal_env: Default::default(),
         };
         let is_web_client = true;
@@ -100,6 +100,24 @@ pub struct ConnectionTable {
     pub client_id_to_channels: HashMap<String, ClientChannels>,
     pub client_read_only_status: HashMap<String, bool>,
     pub client_session_token_hash: HashMap<String, String>,
+    pub client_session_scope: HashMap<String, String>,
+    pub client_audit_info: HashMap<String, ClientAuditInfo>,
+    pub client_login_token: HashMap<String, ClientLoginToken>,
+}
+
+/// Who is behind a web client, as recorded in the web audit log
+#[derive(Debug, Default, Clone)]
+pub struct ClientAuditInfo {
+    pub token_name: Option<String>,
+    pub remote_addr: Option<String>,
+}
+
+/// The login token a web client was created with, its connection is closed once that token
+/// expires or is revoked
+#[derive(Debug, Clone)]
+pub struct ClientLoginToken {
+    pub token_hash: String,
+    pub expires_at: Option<Instant>,
 }
 
 const MAX_PENDING_CONTROL_MESSAGES: usize = 64;
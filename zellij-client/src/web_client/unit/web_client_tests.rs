@@ -22,7 +22,10 @@ use zellij_utils::{
     errors::ErrorContext,
     ipc::{ClientToServerMsg, ServerToClientMsg},
     pane_size::Size,
-    web_authentication_tokens::{create_token, delete_db, revoke_token},
+    web_audit_log::{read_audit_log, AuditEvent, AuditFilter},
+    web_authentication_tokens::{
+        create_restricted_token, create_token, delete_db, list_tokens, revoke_token,
+    },
 };
 
 use serial_test::serial;
@@ -301,6 +304,93 @@ mod web_client_tests {
         server_handle.abort();
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_token_lifecycle_and_logins_are_audited() {
+        let _ = delete_db();
+        // the audit log is shared between test runs, so only look at what this one wrote
+        let test_started = std::time::SystemTime::now() - Duration::from_secs(1);
+
+        let test_token_name = "test_token_audit";
+        let (auth_token, _) = create_token(Some(test_token_name.to_string()), false)
+            .expect("Failed to create test token");
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                listener,
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+                addr.ip(),
+                port,
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let login_url = format!("http://127.0.0.1:{}/command/login", port);
+        let login_payload = serde_json::json!({
+            "auth_token": auth_token,
+            "remember_me": false
+        });
+
+        let response = timeout(
+            Duration::from_secs(5),
+            tokio::task::spawn_blocking(move || {
+                isahc::Request::post(&login_url)
+                    .header("Content-Type", "application/json")
+                    .body(login_payload.to_string())
+                    .unwrap()
+                    .send()
+            }),
+        )
+        .await
+        .expect("Login request timed out")
+        .expect("Spawn blocking failed")
+        .expect("Login request failed");
+
+        assert!(response.status().is_success());
+
+        server_handle.abort();
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+
+        let audited_events: Vec<AuditEvent> = read_audit_log(&AuditFilter {
+            token_name: Some(test_token_name.to_owned()),
+            since: Some(test_started),
+            ..Default::default()
+        })
+        .expect("Failed to read the audit log")
+        .iter()
+        .map(|entry| entry.event)
+        .collect();
+        assert_eq!(
+            audited_events,
+            vec![
+                AuditEvent::TokenCreated,
+                AuditEvent::LoginSucceeded,
+                AuditEvent::TokenRevoked
+            ]
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_full_session_flow() {
@@ -3116,6 +3206,261 @@ mod web_client_tests {
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
+    async fn get_session_list(port: u16, session_token: &str) -> isahc::Response<isahc::Body> {
+        let url = format!("http://127.0.0.1:{}/session-list", port);
+        let session_token = session_token.to_owned();
+        timeout(
+            Duration::from_secs(5),
+            tokio::task::spawn_blocking(move || {
+                isahc::Request::get(&url)
+                    .header("Cookie", format!("session_token={}", session_token))
+                    .body(())
+                    .unwrap()
+                    .send()
+            }),
+        )
+        .await
+        .expect("Request timed out")
+        .expect("Spawn blocking failed")
+        .expect("Request failed")
+    }
+
+    fn listed_session(name: &str) -> WebSessionInfo {
+        WebSessionInfo {
+            name: name.to_owned(),
+            web_clients_allowed: true,
+            tab_count: 1,
+            pane_count: 1,
+            connected_clients: 0,
+            creation_secs_ago: 1,
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_session_scoped_token_only_reaches_matching_sessions() {
+        let _ = delete_db();
+
+        let (scoped_token, _) = create_restricted_token(
+            Some("contractor".to_string()),
+            false,
+            None,
+            Some("pairing-*".to_string()),
+        )
+        .unwrap();
+
+        let session_manager = Arc::new(MockSessionManager::with_listed_sessions(vec![
+            listed_session("pairing-backend"),
+            listed_session("secret-stuff"),
+        ]));
+        let (port, server_handle) = spawn_test_server_with_session_manager(session_manager).await;
+
+        let session_token = login_and_get_session_token(port, &scoped_token).await;
+
+        let mut response = get_session_list(port, &session_token).await;
+        assert!(response.status().is_success());
+        let body: SessionListResponse =
+            serde_json::from_str(&response.text().expect("Failed to read body"))
+                .expect("Failed to parse the session list response");
+        assert_eq!(
+            body.sessions
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pairing-backend"],
+            "sessions outside of the token scope must not be listed"
+        );
+
+        let create_client = |session_name: &'static str| {
+            let session_url = format!("http://127.0.0.1:{}/session?session={}", port, session_name);
+            let session_token = session_token.clone();
+            tokio::task::spawn_blocking(move || {
+                isahc::Request::post(&session_url)
+                    .header("Cookie", format!("session_token={}", session_token))
+                    .header("Content-Type", "application/json")
+                    .body("{}")
+                    .unwrap()
+                    .send()
+                    .unwrap()
+                    .status()
+            })
+        };
+        assert_eq!(create_client("secret-stuff").await.unwrap(), 403);
+        assert!(create_client("pairing-backend").await.unwrap().is_success());
+
+        let token_info = list_tokens().unwrap();
+        assert_eq!(token_info[0].session_scope.as_deref(), Some("pairing-*"));
+        assert!(
+            token_info[0].last_used_at.is_some(),
+            "logging in must record when the token was last used"
+        );
+
+        server_handle.abort();
+        let _ = delete_db();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_expired_token_and_its_sessions_are_rejected() {
+        let _ = delete_db();
+
+        let (expiring_token, _) = create_restricted_token(
+            Some("afternoon".to_string()),
+            false,
+            Some(Duration::from_secs(2)),
+            None,
+        )
+        .unwrap();
+
+        let (port, server_handle) =
+            spawn_test_server_with_session_manager(Arc::new(MockSessionManager::new())).await;
+
+        let session_token = login_and_get_session_token(port, &expiring_token).await;
+        assert!(get_session_list(port, &session_token)
+            .await
+            .status()
+            .is_success());
+
+        tokio::time::sleep(Duration::from_millis(3100)).await;
+
+        assert_eq!(
+            get_session_list(port, &session_token).await.status(),
+            401,
+            "sessions created from an expired token must stop working"
+        );
+
+        let login_url = format!("http://127.0.0.1:{}/command/login", port);
+        let login_payload = serde_json::json!({ "auth_token": expiring_token });
+        let login_status = tokio::task::spawn_blocking(move || {
+            isahc::Request::post(&login_url)
+                .header("Content-Type", "application/json")
+                .body(login_payload.to_string())
+                .unwrap()
+                .send()
+                .unwrap()
+                .status()
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            login_status, 401,
+            "expired tokens must not be able to log in"
+        );
+
+        server_handle.abort();
+        let _ = delete_db();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    async fn connect_control_websocket(
+        port: u16,
+        session_token: &str,
+    ) -> futures::stream::SplitStream<
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    > {
+        let web_client_id = create_client_session(port, session_token).await;
+        let control_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/control?web_client_id={}",
+            port, web_client_id
+        );
+        let (control_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&control_ws_url, session_token),
+        )
+        .await
+        .expect("Control WebSocket connection timed out")
+        .expect("Failed to connect to control WebSocket");
+        let (_control_sink, control_stream) = control_ws.split();
+        control_stream
+    }
+
+    async fn wait_for_close_code(
+        control_stream: &mut futures::stream::SplitStream<
+            tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+        >,
+        within: Duration,
+    ) -> Option<tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode> {
+        let deadline = tokio::time::Instant::now() + within;
+        while tokio::time::Instant::now() < deadline {
+            match timeout(Duration::from_millis(500), control_stream.next()).await {
+                Ok(Some(Ok(Message::Close(frame)))) => return frame.map(|frame| frame.code),
+                Ok(Some(Ok(_))) | Err(_) => continue,
+                Ok(Some(Err(_))) | Ok(None) => return None,
+            }
+        }
+        None
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_live_connection_is_closed_when_its_login_token_expires() {
+        let _ = delete_db();
+
+        let (expiring_token, _) = create_restricted_token(
+            Some("afternoon".to_string()),
+            false,
+            Some(Duration::from_secs(2)),
+            None,
+        )
+        .unwrap();
+
+        let (port, server_handle) =
+            spawn_test_server_with_session_manager(Arc::new(MockSessionManager::new())).await;
+
+        let session_token = login_and_get_session_token(port, &expiring_token).await;
+        let mut control_stream = connect_control_websocket(port, &session_token).await;
+
+        assert_eq!(
+            wait_for_close_code(&mut control_stream, Duration::from_secs(6)).await,
+            Some(tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode::Library(4001)),
+            "connections must be closed once the login token they were made with expires"
+        );
+
+        server_handle.abort();
+        let _ = delete_db();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_revoking_a_login_token_closes_its_live_connections() {
+        let _ = delete_db();
+
+        let (revoked_token, _) = create_token(Some("revoked".to_string()), false).unwrap();
+        let (kept_token, _) = create_token(Some("kept".to_string()), false).unwrap();
+
+        let (port, server_handle) =
+            spawn_test_server_with_session_manager(Arc::new(MockSessionManager::new())).await;
+
+        let revoked_session_token = login_and_get_session_token(port, &revoked_token).await;
+        let mut revoked_control_stream =
+            connect_control_websocket(port, &revoked_session_token).await;
+        let kept_session_token = login_and_get_session_token(port, &kept_token).await;
+        let mut kept_control_stream = connect_control_websocket(port, &kept_session_token).await;
+
+        revoke_token("revoked").expect("Failed to revoke test token");
+
+        assert_eq!(
+            wait_for_close_code(&mut revoked_control_stream, Duration::from_secs(5)).await,
+            Some(tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode::Library(4001)),
+            "revoking a login token must close the connections made with it"
+        );
+        assert_eq!(
+            wait_for_close_code(&mut kept_control_stream, Duration::from_secs(1)).await,
+            None,
+            "connections made with other login tokens must stay open"
+        );
+
+        server_handle.abort();
+        let _ = delete_db();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
     async fn create_client_session_with_query(
         port: u16,
         session_token: &str,
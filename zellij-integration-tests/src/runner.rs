@@ -157,6 +157,7 @@ fn new_pane_cli_action(
         command: command.iter().map(|part| part.to_string()).collect(),
         plugin: None,
         cwd: None,
+        env: vec![],
         floating,
         in_place: false,
         close_replaced_pane: false,
@@ -164,6 +165,8 @@ fn new_pane_cli_action(
         name: None,
         close_on_exit,
         start_suspended,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -12,6 +12,7 @@ fn no_focus_new_pane_action(command: &[&str]) -> CliAction {
         command: command.iter().map(|part| part.to_string()).collect(),
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -19,6 +20,8 @@ fn no_focus_new_pane_action(command: &[&str]) -> CliAction {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -45,6 +48,7 @@ fn no_focus_new_tab_action() -> CliAction {
         layout: None,
         layout_string: None,
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
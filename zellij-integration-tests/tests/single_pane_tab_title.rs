@@ -16,6 +16,7 @@ fn replace_pane_in_place_with_held_command(
         command: vec![command.to_string()],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: true,
         close_replaced_pane: true,
@@ -23,6 +24,8 @@ fn replace_pane_in_place_with_held_command(
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -4,9 +4,12 @@ use zellij_utils::consts::{
     VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
 };
 #[allow(unused_imports)]
-use zellij_utils::data::{Event, HttpVerb, LayoutInfo, SessionInfo, WebServerStatus};
+use zellij_utils::data::{
+    Event, HttpVerb, LayoutInfo, SessionInfo, WebServerStatus, WEB_REQUEST_BLOCKED_STATUS,
+};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
-use zellij_utils::input::layout::RunPlugin;
+use zellij_utils::input::layout::{ReadinessProbe, RunPlugin};
+use zellij_utils::input::permission::web_request_is_allowed;
 #[allow(unused_imports)]
 use zellij_utils::shared::parse_base_url;
 
@@ -40,11 +43,12 @@ use crate::{ClientId, ServerInstruction};
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
     DisplayPaneError(Vec<PaneId>, String),
-    AnimatePluginLoading(u32),                            // u32 - plugin_id
-    StopPluginLoadingAnimation(u32),                      // u32 - plugin_id
-    ReportSessionInfo(String, SessionInfo),               // String - session name
-    ReportPluginList(BTreeMap<PluginId, RunPlugin>),      // String - session name
-    ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
+    AnimatePluginLoading(u32),                       // u32 - plugin_id
+    StopPluginLoadingAnimation(u32),                 // u32 - plugin_id
+    ReportSessionInfo(String, SessionInfo),          // String - session name
+    ReportPluginList(BTreeMap<PluginId, RunPlugin>), // String - session name
+    ReportPluginCrash(PluginId, Option<String>),     // None - the plugin recovered
+    ReportLayoutInfo((String, BTreeMap<String, Vec<u8>>)), // BTreeMap<file_name, file_contents>
     RunCommand(
         PluginId,
         ClientId,
@@ -53,7 +57,8 @@ pub enum BackgroundJob {
         BTreeMap<String, String>,
         PathBuf,
         BTreeMap<String, String>,
-    ), // command, args, env_variables, cwd, context
+        Option<usize>,
+    ), // command, args, env_variables, cwd, context, max_pending_jobs
     WebRequest(
         PluginId,
         ClientId,
@@ -62,6 +67,8 @@ pub enum BackgroundJob {
         BTreeMap<String, String>, // headers
         Vec<u8>,                  // body
         BTreeMap<String, String>, // context
+        Option<usize>,            // max_pending_jobs
+        Vec<String>,              // allowed_hosts
     ),
     HighlightPanesWithMessage(Vec<PaneId>, String),
     RenderToClients,
@@ -78,6 +85,11 @@ pub enum BackgroundJob {
     StopFlashTabBell(usize), // usize = tab_id
     StartNestedGuestPing(PaneId),
     StopNestedGuestPing(PaneId),
+    StartPaneMonitorTicks,
+    StopPaneMonitorTicks,
+    RestartCommandPane(PaneId, u64, Duration), // u64 - restart id, Duration - how long to wait
+    StartReadinessProbe(PaneId, u64, ReadinessProbe), // PaneId - the dependent pane, u64 - probe id
+    StopReadinessProbes(PaneId),
     Exit,
 }
 
@@ -94,6 +106,7 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::ReportPluginList(..) => BackgroundJobContext::ReportPluginList,
+            BackgroundJob::ReportPluginCrash(..) => BackgroundJobContext::ReportPluginCrash,
             BackgroundJob::RenderToClients => BackgroundJobContext::ReportPluginList,
             BackgroundJob::HighlightPanesWithMessage(..) => {
                 BackgroundJobContext::HighlightPanesWithMessage
@@ -111,6 +124,11 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::StopFlashTabBell(..) => BackgroundJobContext::StopFlashTabBell,
             BackgroundJob::StartNestedGuestPing(..) => BackgroundJobContext::StartNestedGuestPing,
             BackgroundJob::StopNestedGuestPing(..) => BackgroundJobContext::StopNestedGuestPing,
+            BackgroundJob::StartPaneMonitorTicks => BackgroundJobContext::StartPaneMonitorTicks,
+            BackgroundJob::StopPaneMonitorTicks => BackgroundJobContext::StopPaneMonitorTicks,
+            BackgroundJob::RestartCommandPane(..) => BackgroundJobContext::RestartCommandPane,
+            BackgroundJob::StartReadinessProbe(..) => BackgroundJobContext::StartReadinessProbe,
+            BackgroundJob::StopReadinessProbes(..) => BackgroundJobContext::StopReadinessProbes,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -125,12 +143,63 @@ static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
 static REPAINT_DELAY_MS: u64 = 10;
 static HELP_TEXT_DEBOUNCE_DURATION: u64 = 5000;
 static COMMAND_OUTPUT_FLASH_DURATION_MS: u64 = 400;
+static PANE_MONITOR_TICK_INTERVAL_MS: u64 = 1000;
+static READINESS_PROBE_INTERVAL_MS: u64 = 500;
+
+/// Counts the `RunCommand` and `WebRequest` jobs each plugin has in flight, so that plugins can be
+/// held to their `max_pending_jobs`
+#[derive(Clone, Default)]
+struct PendingPluginJobs(Arc<Mutex<HashMap<PluginId, usize>>>);
+
+impl PendingPluginJobs {
+    fn try_reserve(
+        &self,
+        plugin_id: PluginId,
+        max_pending_jobs: Option<usize>,
+    ) -> Option<PendingPluginJob> {
+        let mut pending_jobs = self.0.lock().unwrap();
+        let pending_jobs_of_plugin = pending_jobs.entry(plugin_id).or_insert(0);
+        if max_pending_jobs
+            .map(|max_pending_jobs| *pending_jobs_of_plugin >= max_pending_jobs)
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        *pending_jobs_of_plugin += 1;
+        Some(PendingPluginJob {
+            plugin_id,
+            pending_plugin_jobs: self.clone(),
+        })
+    }
+    fn release(&self, plugin_id: PluginId) {
+        let mut pending_jobs = self.0.lock().unwrap();
+        if let Some(pending_jobs_of_plugin) = pending_jobs.get_mut(&plugin_id) {
+            *pending_jobs_of_plugin = pending_jobs_of_plugin.saturating_sub(1);
+            if *pending_jobs_of_plugin == 0 {
+                pending_jobs.remove(&plugin_id);
+            }
+        }
+    }
+}
+
+/// A slot of a plugin's `max_pending_jobs`, given back when dropped
+struct PendingPluginJob {
+    plugin_id: PluginId,
+    pending_plugin_jobs: PendingPluginJobs,
+}
+
+impl Drop for PendingPluginJob {
+    fn drop(&mut self) {
+        self.pending_plugin_jobs.release(self.plugin_id);
+    }
+}
 
 #[derive(Clone)]
 pub struct SessionScanState {
     pub current_session_name: Arc<Mutex<String>>,
     pub current_session_info: Arc<Mutex<SessionInfo>>,
     pub current_session_plugin_list: Arc<Mutex<BTreeMap<PluginId, RunPlugin>>>,
+    pub current_session_crashed_plugins: Arc<Mutex<BTreeMap<PluginId, String>>>,
 }
 
 static SESSION_SCAN_STATE: std::sync::OnceLock<SessionScanState> = std::sync::OnceLock::new();
@@ -153,12 +222,15 @@ pub(crate) fn background_jobs_main(
     let current_session_info = Arc::new(Mutex::new(SessionInfo::default()));
     let current_session_plugin_list: Arc<Mutex<BTreeMap<PluginId, RunPlugin>>> =
         Arc::new(Mutex::new(BTreeMap::new()));
+    let current_session_crashed_plugins: Arc<Mutex<BTreeMap<PluginId, String>>> =
+        Arc::new(Mutex::new(BTreeMap::new()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
 
     let _ = SESSION_SCAN_STATE.set(SessionScanState {
         current_session_name: current_session_name.clone(),
         current_session_info: current_session_info.clone(),
         current_session_plugin_list: current_session_plugin_list.clone(),
+        current_session_crashed_plugins: current_session_crashed_plugins.clone(),
     });
     let last_serialization_time = Arc::new(Mutex::new(Instant::now()));
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
@@ -171,6 +243,9 @@ pub(crate) fn background_jobs_main(
     let mut flashing_pane_bells: HashMap<PaneId, Arc<AtomicBool>> = HashMap::new();
     let mut flashing_tab_bells: HashMap<usize, Arc<AtomicBool>> = HashMap::new();
     let mut nested_guest_pings: HashMap<PaneId, Arc<AtomicBool>> = HashMap::new();
+    let mut pane_monitor_ticks: Option<Arc<AtomicBool>> = None;
+    let mut readiness_probes: HashMap<PaneId, Arc<AtomicBool>> = HashMap::new(); // by dependent pane
+    let pending_plugin_jobs = PendingPluginJobs::default();
 
     let http_client = HttpClient::builder()
         // TODO: timeout?
@@ -292,6 +367,17 @@ pub(crate) fn background_jobs_main(
             BackgroundJob::ReportPluginList(plugin_list) => {
                 *current_session_plugin_list.lock().unwrap() = plugin_list;
             },
+            BackgroundJob::ReportPluginCrash(plugin_id, crash_reason) => {
+                let mut crashed_plugins = current_session_crashed_plugins.lock().unwrap();
+                match crash_reason {
+                    Some(crash_reason) => {
+                        crashed_plugins.insert(plugin_id, crash_reason);
+                    },
+                    None => {
+                        crashed_plugins.remove(&plugin_id);
+                    },
+                }
+            },
             BackgroundJob::ReportLayoutInfo(session_layout) => {
                 *current_session_layout.lock().unwrap() = session_layout;
 
@@ -312,10 +398,23 @@ pub(crate) fn background_jobs_main(
                 env_variables,
                 cwd,
                 context,
+                max_pending_jobs,
             ) => {
+                let Some(pending_plugin_job) =
+                    pending_plugin_jobs.try_reserve(plugin_id, max_pending_jobs)
+                else {
+                    let stderr = too_many_pending_jobs_error(max_pending_jobs);
+                    let _ = bus.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::RunCommandResult(Some(2), vec![], stderr, context),
+                    )]));
+                    continue;
+                };
                 runtime.spawn({
                     let senders = bus.senders.clone();
                     async move {
+                        let _pending_plugin_job = pending_plugin_job;
                         let output = tokio::process::Command::new(&command)
                             .args(&args)
                             .envs(env_variables)
@@ -351,16 +450,58 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
-            BackgroundJob::WebRequest(plugin_id, client_id, url, verb, headers, body, context) => {
+            BackgroundJob::WebRequest(
+                plugin_id,
+                client_id,
+                url,
+                verb,
+                headers,
+                body,
+                context,
+                max_pending_jobs,
+                allowed_hosts,
+            ) => {
+                if !web_request_is_allowed(&allowed_hosts, &url) {
+                    log::warn!(
+                        "Blocked web request of plugin {} to {}: not in its allowed_hosts",
+                        plugin_id,
+                        url
+                    );
+                    let error_body = web_request_blocked_error(&url, &allowed_hosts);
+                    let _ = bus.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::WebRequestResult(
+                            WEB_REQUEST_BLOCKED_STATUS,
+                            BTreeMap::new(),
+                            error_body,
+                            context,
+                        ),
+                    )]));
+                    continue;
+                }
+                let Some(pending_plugin_job) =
+                    pending_plugin_jobs.try_reserve(plugin_id, max_pending_jobs)
+                else {
+                    let error_body = too_many_pending_jobs_error(max_pending_jobs);
+                    let _ = bus.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::WebRequestResult(429, BTreeMap::new(), error_body, context),
+                    )]));
+                    continue;
+                };
                 runtime.spawn({
                     let senders = bus.senders.clone();
                     let http_client = http_client.clone();
                     async move {
+                        let _pending_plugin_job = pending_plugin_job;
                         async fn web_request(
                             url: String,
                             verb: HttpVerb,
                             headers: BTreeMap<String, String>,
                             body: Vec<u8>,
+                            follow_redirects: bool,
                             http_client: HttpClient,
                         ) -> Result<
                             (u16, BTreeMap<String, String>, Vec<u8>), // status_code, headers, body
@@ -375,6 +516,9 @@ pub(crate) fn background_jobs_main(
                             for (header, value) in headers {
                                 request = request.header(header.as_str(), value);
                             }
+                            if !follow_redirects {
+                                request = request.redirect_policy(RedirectPolicy::None);
+                            }
                             let mut res = if !body.is_empty() {
                                 let req = request.body(body)?;
                                 http_client.send_async(req).await?
@@ -407,7 +551,12 @@ pub(crate) fn background_jobs_main(
                             return;
                         };
 
-                        match web_request(url, verb, headers, body, http_client).await {
+                        // a redirect could lead a plugin with allowed_hosts anywhere, so those
+                        // get the redirect response itself
+                        let follow_redirects = allowed_hosts.is_empty();
+                        match web_request(url, verb, headers, body, follow_redirects, http_client)
+                            .await
+                        {
                             Ok((status, headers, body)) => {
                                 let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
                                     Some(plugin_id),
@@ -711,6 +860,67 @@ pub(crate) fn background_jobs_main(
                     flag.store(false, Ordering::SeqCst);
                 }
             },
+            BackgroundJob::StartPaneMonitorTicks => {
+                if pane_monitor_ticks.is_none() {
+                    let is_ticking = Arc::new(AtomicBool::new(true));
+                    pane_monitor_ticks = Some(is_ticking.clone());
+                    runtime.spawn({
+                        let senders = bus.senders.clone();
+                        async move {
+                            while is_ticking.load(Ordering::SeqCst) {
+                                tokio::time::sleep(Duration::from_millis(
+                                    PANE_MONITOR_TICK_INTERVAL_MS,
+                                ))
+                                .await;
+                                let _ =
+                                    senders.send_to_screen(ScreenInstruction::CheckPaneMonitors);
+                            }
+                        }
+                    });
+                }
+            },
+            BackgroundJob::StopPaneMonitorTicks => {
+                if let Some(flag) = pane_monitor_ticks.take() {
+                    flag.store(false, Ordering::SeqCst);
+                }
+            },
+            BackgroundJob::RestartCommandPane(pane_id, restart_id, delay) => {
+                runtime.spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        tokio::time::sleep(delay).await;
+                        let _ = senders.send_to_screen(ScreenInstruction::RestartCommandPane(
+                            pane_id, restart_id,
+                        ));
+                    }
+                });
+            },
+            BackgroundJob::StartReadinessProbe(pane_id, probe_id, probe) => {
+                let is_probing = readiness_probes
+                    .entry(pane_id)
+                    .or_insert_with(|| Arc::new(AtomicBool::new(true)))
+                    .clone();
+                runtime.spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        while is_probing.load(Ordering::SeqCst) {
+                            if readiness_probe_succeeds(&probe).await {
+                                let _ = senders.send_to_screen(
+                                    ScreenInstruction::ReadinessProbeSucceeded(pane_id, probe_id),
+                                );
+                                break;
+                            }
+                            tokio::time::sleep(Duration::from_millis(READINESS_PROBE_INTERVAL_MS))
+                                .await;
+                        }
+                    }
+                });
+            },
+            BackgroundJob::StopReadinessProbes(pane_id) => {
+                if let Some(flag) = readiness_probes.remove(&pane_id) {
+                    flag.store(false, Ordering::SeqCst);
+                }
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
@@ -718,6 +928,12 @@ pub(crate) fn background_jobs_main(
                 for nested_guest_ping in nested_guest_pings.values() {
                     nested_guest_ping.store(false, Ordering::SeqCst);
                 }
+                if let Some(pane_monitor_ticks) = pane_monitor_ticks.take() {
+                    pane_monitor_ticks.store(false, Ordering::SeqCst);
+                }
+                for readiness_probe in readiness_probes.values() {
+                    readiness_probe.store(false, Ordering::SeqCst);
+                }
 
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
@@ -728,6 +944,21 @@ pub(crate) fn background_jobs_main(
     }
 }
 
+async fn readiness_probe_succeeds(probe: &ReadinessProbe) -> bool {
+    match probe {
+        ReadinessProbe::PortOpen(port) => tokio::time::timeout(
+            Duration::from_millis(READINESS_PROBE_INTERVAL_MS),
+            tokio::net::TcpStream::connect(("localhost", *port)),
+        )
+        .await
+        .map(|connected| connected.is_ok())
+        .unwrap_or(false),
+        ReadinessProbe::FileExists(path) => tokio::fs::try_exists(path).await.unwrap_or(false),
+        // these are tracked by the tab itself
+        ReadinessProbe::ExitSuccess | ReadinessProbe::OutputMatches(_) => false,
+    }
+}
+
 fn job_already_running(
     job: BackgroundJob,
     running_jobs: &mut HashMap<BackgroundJob, Instant>,
@@ -760,7 +991,7 @@ fn file_content_changed(path: &std::path::Path, new_content: &[u8]) -> bool {
 pub fn write_session_state_to_disk(
     current_session_name: String,
     current_session_info: SessionInfo,
-    current_session_layout: (String, BTreeMap<String, String>),
+    current_session_layout: (String, BTreeMap<String, Vec<u8>>),
 ) {
     let metadata_cache_file_name = session_info_cache_file_name(&current_session_name);
     let (current_session_layout, layout_files_to_write) = current_session_layout;
@@ -785,9 +1016,9 @@ pub fn write_session_state_to_disk(
         let session_info_folder = session_info_folder_for_session(&current_session_name);
         for (external_file_name, external_file_contents) in layout_files_to_write {
             let external_file_path = session_info_folder.join(&external_file_name);
-            if file_content_changed(&external_file_path, external_file_contents.as_bytes()) {
+            if file_content_changed(&external_file_path, &external_file_contents) {
                 std::fs::File::create(&external_file_path)
-                    .and_then(|mut f| write!(f, "{}", external_file_contents))
+                    .and_then(|mut f| f.write_all(&external_file_contents))
                     .unwrap_or_else(|e| {
                         log::error!("Failed to write layout metadata file: {:?}", e);
                     });
@@ -796,10 +1027,30 @@ pub fn write_session_state_to_disk(
     }
 }
 
+fn too_many_pending_jobs_error(max_pending_jobs: Option<usize>) -> Vec<u8> {
+    format!(
+        "Plugin has too many pending jobs (max_pending_jobs: {})",
+        max_pending_jobs.unwrap_or_default()
+    )
+    .as_bytes()
+    .to_vec()
+}
+
+fn web_request_blocked_error(url: &str, allowed_hosts: &[String]) -> Vec<u8> {
+    format!(
+        "Web request to {} blocked, the plugin can only reach: {}",
+        url,
+        allowed_hosts.join(", ")
+    )
+    .as_bytes()
+    .to_vec()
+}
+
 pub fn scan_session_list(
     current_session_name: &str,
     available_layouts: &[LayoutInfo],
     current_session_plugin_list: &BTreeMap<PluginId, RunPlugin>,
+    current_session_crashed_plugins: &BTreeMap<PluginId, String>,
     sock_dir: &Path,
     session_info_cache_dir: &Path,
 ) -> (BTreeMap<String, SessionInfo>, BTreeMap<String, Duration>) {
@@ -810,7 +1061,10 @@ pub fn scan_session_list(
     );
     for (name, info) in session_infos_on_machine.iter_mut() {
         if name == current_session_name {
-            info.populate_plugin_list(current_session_plugin_list.clone());
+            info.populate_plugin_list(
+                current_session_plugin_list.clone(),
+                current_session_crashed_plugins,
+            );
             info.available_layouts = available_layouts.to_vec();
         }
     }
@@ -823,11 +1077,13 @@ pub fn scan_session_list_default_dirs(
     current_session_name: &str,
     available_layouts: &[LayoutInfo],
     current_session_plugin_list: &BTreeMap<PluginId, RunPlugin>,
+    current_session_crashed_plugins: &BTreeMap<PluginId, String>,
 ) -> (BTreeMap<String, SessionInfo>, BTreeMap<String, Duration>) {
     scan_session_list(
         current_session_name,
         available_layouts,
         current_session_plugin_list,
+        current_session_crashed_plugins,
         &*ZELLIJ_SOCK_DIR,
         &*ZELLIJ_SESSION_INFO_CACHE_DIR,
     )
@@ -949,6 +1205,7 @@ mod tests {
             "me",
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             sock_dir.path(),
             info_dir.path(),
         );
@@ -968,6 +1225,7 @@ mod tests {
             "me",
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             sock_dir.path(),
             info_dir.path(),
         );
@@ -986,6 +1244,7 @@ mod tests {
             "me",
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             sock_dir.path(),
             info_dir.path(),
         );
@@ -1011,6 +1270,7 @@ mod tests {
             "me",
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             sock_dir.path(),
             info_dir.path(),
         );
@@ -1020,4 +1280,60 @@ mod tests {
             assert!(!resurrectable.contains_key(name));
         }
     }
+
+    #[test]
+    fn scan_session_list_reports_crashed_plugins_of_current_session() {
+        let sock_dir = tempdir().unwrap();
+        let info_dir = tempdir().unwrap();
+        let _listener = make_socket(sock_dir.path(), "me");
+        write_metadata(info_dir.path(), "me", &SessionInfo::new("me".to_string()));
+        let plugin_list = BTreeMap::from([(1, RunPlugin::default()), (2, RunPlugin::default())]);
+        let crashed_plugins = BTreeMap::from([(2, "out of fuel".to_owned())]);
+
+        let (live, _resurrectable) = scan_session_list(
+            "me",
+            &[],
+            &plugin_list,
+            &crashed_plugins,
+            sock_dir.path(),
+            info_dir.path(),
+        );
+        let plugins = &live.get("me").unwrap().plugins;
+        assert_eq!(plugins.get(&1).unwrap().crash_reason, None);
+        assert_eq!(
+            plugins.get(&2).unwrap().crash_reason,
+            Some("out of fuel".to_owned())
+        );
+    }
+
+    #[test]
+    fn pending_plugin_jobs_are_held_to_max_pending_jobs() {
+        let pending_plugin_jobs = PendingPluginJobs::default();
+        let first_job = pending_plugin_jobs.try_reserve(1, Some(2));
+        let second_job = pending_plugin_jobs.try_reserve(1, Some(2));
+        assert!(first_job.is_some());
+        assert!(second_job.is_some());
+        assert!(
+            pending_plugin_jobs.try_reserve(1, Some(2)).is_none(),
+            "third job is over the limit"
+        );
+        assert!(
+            pending_plugin_jobs.try_reserve(2, Some(2)).is_some(),
+            "other plugins are counted separately"
+        );
+        drop(first_job);
+        assert!(
+            pending_plugin_jobs.try_reserve(1, Some(2)).is_some(),
+            "finished jobs free up their slot"
+        );
+    }
+
+    #[test]
+    fn pending_plugin_jobs_are_unlimited_by_default() {
+        let pending_plugin_jobs = PendingPluginJobs::default();
+        let jobs: Vec<_> = (0..100)
+            .map(|_| pending_plugin_jobs.try_reserve(1, None))
+            .collect();
+        assert!(jobs.iter().all(|job| job.is_some()));
+    }
 }
@@ -24,6 +24,7 @@ mod pty_writer;
 mod route;
 mod screen;
 mod session_layout_metadata;
+mod session_recording;
 mod terminal_bytes;
 mod thread_bus;
 mod ui;
@@ -2548,7 +2549,10 @@ fn update_new_saved_config(
 
 pub fn get_engine() -> Engine {
     log::info!("Loading plugins using Wasmi interpreter");
-    Engine::default()
+    let mut config = wasmi::Config::default();
+    // metering fuel lets us stop plugins that hog the CPU (see the `fuel_budget` plugin option)
+    config.consume_fuel(true);
+    Engine::new(&config)
 }
 
 // TODO: move elsewhere
@@ -187,11 +187,19 @@ fn build_command_line(cmd: &RunCommand) -> Vec<u16> {
 }
 
 /// Build a UTF-16 environment block (each entry `KEY=VALUE\0`, terminated by
-/// an extra `\0`) from the current process environment, adding
-/// `ZELLIJ_PANE_ID`.
-fn build_environment_block(terminal_id: u32) -> Vec<u16> {
+/// an extra `\0`) from the current process environment, overridden by the
+/// pane's own `env` and adding `ZELLIJ_PANE_ID`.
+fn build_environment_block(terminal_id: u32, pane_env: &BTreeMap<String, String>) -> Vec<u16> {
     let mut block: Vec<u16> = Vec::new();
     for (key, value) in std::env::vars() {
+        if key == "ZELLIJ_PANE_ID" || pane_env.contains_key(&key) {
+            continue;
+        }
+        let entry = format!("{}={}", key, value);
+        block.extend(OsStr::new(&entry).encode_wide());
+        block.push(0);
+    }
+    for (key, value) in pane_env {
         if key == "ZELLIJ_PANE_ID" {
             continue;
         }
@@ -325,7 +333,7 @@ fn spawn_child_process(
 
     // --- command line & environment ---
     let mut cmd_line = build_command_line(cmd);
-    let env_block = build_environment_block(terminal_id);
+    let env_block = build_environment_block(terminal_id, &cmd.env);
 
     let cwd: Option<Vec<u16>> = cmd.cwd.as_ref().and_then(|p| {
         if p.exists() && p.is_dir() {
@@ -8,6 +8,8 @@ use crate::{panes::PaneId, pty::PtyInstruction, thread_bus::ThreadSenders, Clien
 
 pub struct PaneGroups {
     panes_in_group: HashMap<ClientId, Vec<PaneId>>,
+    // clients whose input is mirrored to all the panes in their group
+    input_synced_clients: HashSet<ClientId>,
     senders: ThreadSenders,
 }
 
@@ -15,6 +17,7 @@ impl std::fmt::Debug for PaneGroups {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PaneGroups")
             .field("panes_in_group", &self.panes_in_group)
+            .field("input_synced_clients", &self.input_synced_clients)
             .finish_non_exhaustive()
     }
 }
@@ -23,6 +26,7 @@ impl PaneGroups {
     pub fn new(senders: ThreadSenders) -> Self {
         PaneGroups {
             panes_in_group: HashMap::new(),
+            input_synced_clients: HashSet::new(),
             senders,
         }
     }
@@ -37,6 +41,27 @@ impl PaneGroups {
     }
     pub fn clear_pane_group(&mut self, client_id: &ClientId) {
         self.panes_in_group.get_mut(client_id).map(|p| p.clear());
+        self.input_synced_clients.remove(client_id);
+    }
+    /// Returns whether the input of this client is now synced to its pane group
+    pub fn toggle_input_sync(&mut self, client_id: &ClientId) -> bool {
+        if !self.input_synced_clients.remove(client_id) {
+            self.input_synced_clients.insert(*client_id);
+            true
+        } else {
+            false
+        }
+    }
+    /// Replaces the pane group of this client and syncs its input to it
+    pub fn sync_input_to_panes(&mut self, pane_ids: Vec<PaneId>, client_id: &ClientId) {
+        self.panes_in_group.insert(*client_id, pane_ids);
+        self.input_synced_clients.insert(*client_id);
+    }
+    pub fn input_is_synced(&self, client_id: &ClientId) -> bool {
+        self.input_synced_clients.contains(client_id)
+    }
+    pub fn input_synced_clients(&self) -> HashSet<ClientId> {
+        self.input_synced_clients.clone()
     }
     pub fn toggle_pane_id_in_group(
         &mut self,
@@ -498,4 +523,39 @@ mod tests {
 
         assert!(!pane_groups.should_launch_plugin(&previous_groups, &client_id));
     }
+
+    #[test]
+    fn input_sync_is_toggled_per_client() {
+        let mut pane_groups = create_test_pane_groups();
+        let client_id: ClientId = 1;
+        let other_client_id: ClientId = 2;
+
+        assert!(pane_groups.toggle_input_sync(&client_id));
+        assert!(pane_groups.input_is_synced(&client_id));
+        assert!(!pane_groups.input_is_synced(&other_client_id));
+
+        assert!(!pane_groups.toggle_input_sync(&client_id));
+        assert!(!pane_groups.input_is_synced(&client_id));
+    }
+
+    #[test]
+    fn sync_input_to_panes_replaces_the_group_and_clearing_it_stops_the_sync() {
+        let mut pane_groups = create_test_pane_groups();
+        let client_id: ClientId = 1;
+        let screen_size = create_test_screen_size();
+
+        pane_groups.add_pane_id_to_group(PaneId::Terminal(10), screen_size, &client_id);
+        pane_groups.sync_input_to_panes(vec![PaneId::Terminal(2), PaneId::Terminal(3)], &client_id);
+        assert_eq!(
+            pane_groups.clone_inner()[&client_id],
+            vec![PaneId::Terminal(2), PaneId::Terminal(3)]
+        );
+        assert_eq!(
+            pane_groups.input_synced_clients(),
+            HashSet::from([client_id])
+        );
+
+        pane_groups.clear_pane_group(&client_id);
+        assert!(!pane_groups.input_is_synced(&client_id));
+    }
 }
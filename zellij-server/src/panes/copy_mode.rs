@@ -0,0 +1,588 @@
+use super::Selection;
+use crate::panes::Grid;
+use zellij_utils::input::actions::{CopyModeMotion, CopyModeSelection};
+use zellij_utils::position::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharacterClass {
+    Whitespace,
+    Separator,
+    Word,
+}
+
+// The copy mode cursor and selection anchor are relative to the viewport, like all other
+// selections of the grid, and are moved along with it when it scrolls
+#[derive(Debug, Clone, Default)]
+pub struct CopyMode {
+    pub cursor: Position,
+    anchor: Position,
+    selection: Option<CopyModeSelection>,
+    pub block_selections: Vec<Selection>,
+}
+
+impl CopyMode {
+    pub fn new(cursor: Position) -> Self {
+        CopyMode {
+            cursor,
+            anchor: cursor,
+            selection: None,
+            block_selections: vec![],
+        }
+    }
+
+    pub fn move_up(&mut self, lines: usize) {
+        self.cursor.line.0 -= lines as isize;
+        self.anchor.line.0 -= lines as isize;
+        for block_selection in self.block_selections.iter_mut() {
+            block_selection.move_up(lines);
+        }
+    }
+
+    pub fn move_down(&mut self, lines: usize) {
+        self.cursor.line.0 += lines as isize;
+        self.anchor.line.0 += lines as isize;
+        for block_selection in self.block_selections.iter_mut() {
+            block_selection.move_down(lines);
+        }
+    }
+}
+
+impl Grid {
+    pub fn enter_copy_mode(&mut self) {
+        if self.copy_mode.is_some() {
+            return;
+        }
+        // Start on the terminal cursor if it is in view, otherwise at the bottom of the viewport
+        let cursor = if self.is_scrolled {
+            Position::new(self.last_copy_mode_line() as i32, 0)
+        } else {
+            Position::new(
+                self.cursor.y.min(self.last_copy_mode_line()) as i32,
+                self.cursor.x.min(self.width.saturating_sub(1)) as u16,
+            )
+        };
+        self.copy_mode = Some(CopyMode::new(cursor));
+        // coming from a search, the match we were looking at is a better place to start
+        self.move_copy_mode_cursor_to_active_search_result();
+        self.mark_for_rerender();
+    }
+
+    pub fn exit_copy_mode(&mut self) {
+        if let Some(copy_mode) = self.copy_mode.take() {
+            if copy_mode.selection.is_some() {
+                self.reset_selection();
+            }
+            self.output_buffer.update_all_lines();
+            self.mark_for_rerender();
+        }
+    }
+
+    pub fn copy_mode_cursor(&self) -> Option<Position> {
+        self.copy_mode.as_ref().map(|copy_mode| copy_mode.cursor)
+    }
+
+    pub fn copy_mode_move(&mut self, motion: CopyModeMotion) {
+        self.enter_copy_mode();
+        match motion {
+            CopyModeMotion::Left => self.copy_mode_column_left(),
+            CopyModeMotion::Right => self.copy_mode_column_right(),
+            CopyModeMotion::Up => {
+                self.copy_mode_line_up();
+            },
+            CopyModeMotion::Down => {
+                self.copy_mode_line_down();
+            },
+            CopyModeMotion::WordForward => self.copy_mode_word_forward(),
+            CopyModeMotion::WordBackward => self.copy_mode_word_backward(),
+            CopyModeMotion::WordEnd => self.copy_mode_word_end(),
+            CopyModeMotion::LineStart => self.set_copy_mode_column(0),
+            CopyModeMotion::LineEnd => {
+                let line_end = self
+                    .copy_mode_cells()
+                    .last()
+                    .map(|(column, _)| *column)
+                    .unwrap_or(0);
+                self.set_copy_mode_column(line_end);
+            },
+            CopyModeMotion::FirstNonBlank => {
+                let first_non_blank = self
+                    .copy_mode_cells()
+                    .iter()
+                    .find(|(_, character)| !character.is_whitespace())
+                    .map(|(column, _)| *column)
+                    .unwrap_or(0);
+                self.set_copy_mode_column(first_non_blank);
+            },
+            CopyModeMotion::ParagraphForward => self.copy_mode_paragraph(true),
+            CopyModeMotion::ParagraphBackward => self.copy_mode_paragraph(false),
+            CopyModeMotion::Top => {
+                while self.copy_mode_line_up() {}
+                self.set_copy_mode_column(0);
+            },
+            CopyModeMotion::Bottom => {
+                self.reset_viewport();
+                let line = self.cursor.y.min(self.last_copy_mode_line());
+                self.set_copy_mode_cursor(Position::new(line as i32, 0));
+            },
+            CopyModeMotion::PageUp => self.copy_mode_lines_up(self.height),
+            CopyModeMotion::PageDown => self.copy_mode_lines_down(self.height),
+            CopyModeMotion::HalfPageUp => self.copy_mode_lines_up(self.height / 2),
+            CopyModeMotion::HalfPageDown => self.copy_mode_lines_down(self.height / 2),
+            CopyModeMotion::PreviousPrompt => self.copy_mode_prompt(false),
+            CopyModeMotion::NextPrompt => self.copy_mode_prompt(true),
+            CopyModeMotion::NextSearchResult => {
+                self.search_down();
+                self.move_copy_mode_cursor_to_active_search_result();
+            },
+            CopyModeMotion::PreviousSearchResult => {
+                self.search_up();
+                self.move_copy_mode_cursor_to_active_search_result();
+            },
+        }
+        self.update_copy_mode_selection();
+        self.mark_for_rerender();
+    }
+
+    /// Starts a selection of the given type at the cursor. Selecting the type that is already
+    /// active clears the selection, selecting another one switches to it keeping the anchor.
+    pub fn copy_mode_select(&mut self, selection: CopyModeSelection) {
+        self.enter_copy_mode();
+        let had_selection = match self.copy_mode.as_mut() {
+            Some(copy_mode) => {
+                let had_selection = copy_mode.selection.is_some();
+                if copy_mode.selection == Some(selection) {
+                    copy_mode.selection = None;
+                } else {
+                    if !had_selection {
+                        copy_mode.anchor = copy_mode.cursor;
+                    }
+                    copy_mode.selection = Some(selection);
+                }
+                copy_mode.block_selections.clear();
+                had_selection
+            },
+            None => return,
+        };
+        if had_selection {
+            self.reset_selection();
+        }
+        self.update_copy_mode_selection();
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
+
+    pub fn copy_mode_selected_text(&self) -> Option<String> {
+        let copy_mode = self.copy_mode.as_ref()?;
+        match copy_mode.selection? {
+            CopyModeSelection::Block => {
+                let lines: Vec<String> = copy_mode
+                    .block_selections
+                    .iter()
+                    .map(|block_selection| {
+                        self.text_in_range(block_selection.start, block_selection.end)
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                Some(lines.join("\n"))
+            },
+            CopyModeSelection::Character | CopyModeSelection::Line => self.get_selected_text(),
+        }
+    }
+
+    fn update_copy_mode_selection(&mut self) {
+        let Some(copy_mode) = self.copy_mode.as_mut() else {
+            return;
+        };
+        let (start, end) = if copy_mode.anchor <= copy_mode.cursor {
+            (copy_mode.anchor, copy_mode.cursor)
+        } else {
+            (copy_mode.cursor, copy_mode.anchor)
+        };
+        let (start, end) = match copy_mode.selection {
+            None => return,
+            Some(CopyModeSelection::Character) => (
+                start,
+                Position::new(end.line.0 as i32, end.column.0 as u16 + 1),
+            ),
+            Some(CopyModeSelection::Line) => (
+                Position::new(start.line.0 as i32, 0),
+                Position::new(end.line.0 as i32, self.width as u16),
+            ),
+            Some(CopyModeSelection::Block) => {
+                let first_column = start.column.0.min(end.column.0);
+                let last_column = start.column.0.max(end.column.0);
+                copy_mode.block_selections = (start.line.0..=end.line.0)
+                    .map(|line| {
+                        let mut block_selection = Selection::default();
+                        block_selection.set_start_and_end_positions(
+                            Position::new(line as i32, first_column as u16),
+                            Position::new(line as i32, last_column as u16 + 1),
+                        );
+                        block_selection.finalize();
+                        block_selection
+                    })
+                    .collect();
+                self.output_buffer.update_all_lines();
+                return;
+            },
+        };
+        let old_selection = self.selection;
+        self.selection.set_start_and_end_positions(start, end);
+        self.selection.finalize();
+        let current_selection = self.selection;
+        self.update_selected_lines(&old_selection, &current_selection);
+    }
+
+    fn last_copy_mode_line(&self) -> usize {
+        self.viewport.len().min(self.height).saturating_sub(1)
+    }
+
+    fn set_copy_mode_cursor(&mut self, cursor: Position) {
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor = cursor;
+        }
+    }
+
+    fn set_copy_mode_column(&mut self, column: usize) {
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor.column.0 = column.min(self.width.saturating_sub(1));
+        }
+    }
+
+    // (display column, character) of every character on the line of the cursor
+    fn copy_mode_cells_at(&self, line: isize) -> Vec<(usize, char)> {
+        let mut cells = vec![];
+        if let Some(row) = self.row_at(line) {
+            let mut column = 0;
+            for terminal_character in row.columns.iter() {
+                cells.push((column, terminal_character.character));
+                column += terminal_character.width();
+            }
+        }
+        cells
+    }
+
+    fn copy_mode_cells(&self) -> Vec<(usize, char)> {
+        match self.copy_mode_cursor() {
+            Some(cursor) => self.copy_mode_cells_at(cursor.line.0),
+            None => vec![],
+        }
+    }
+
+    fn copy_mode_line_is_canonical(&self, line: isize) -> bool {
+        self.row_at(line)
+            .map(|row| row.is_canonical)
+            .unwrap_or(true)
+    }
+
+    fn character_class(&self, character: char) -> CharacterClass {
+        if character.is_whitespace() {
+            CharacterClass::Whitespace
+        } else if self.word_separators.contains(character) {
+            CharacterClass::Separator
+        } else {
+            CharacterClass::Word
+        }
+    }
+
+    fn copy_mode_class_at_cursor(&self) -> CharacterClass {
+        let Some(cursor) = self.copy_mode_cursor() else {
+            return CharacterClass::Whitespace;
+        };
+        self.copy_mode_cells()
+            .iter()
+            .find(|(column, _)| *column == cursor.column.0)
+            .map(|(_, character)| self.character_class(*character))
+            .unwrap_or(CharacterClass::Whitespace)
+    }
+
+    fn copy_mode_column_left(&mut self) {
+        let Some(cursor) = self.copy_mode_cursor() else {
+            return;
+        };
+        let previous_column = self
+            .copy_mode_cells()
+            .iter()
+            .rev()
+            .find(|(column, _)| *column < cursor.column.0)
+            .map(|(column, _)| *column)
+            .unwrap_or(cursor.column.0.saturating_sub(1));
+        self.set_copy_mode_column(previous_column);
+    }
+
+    fn copy_mode_column_right(&mut self) {
+        let Some(cursor) = self.copy_mode_cursor() else {
+            return;
+        };
+        let next_column = self
+            .copy_mode_cells()
+            .iter()
+            .find(|(column, _)| *column > cursor.column.0)
+            .map(|(column, _)| *column)
+            .unwrap_or(cursor.column.0 + 1);
+        self.set_copy_mode_column(next_column);
+    }
+
+    // Returns true if the cursor moved, scrolls the viewport if the cursor is at its edge
+    fn copy_mode_line_up(&mut self) -> bool {
+        let Some(cursor) = self.copy_mode_cursor() else {
+            return false;
+        };
+        if cursor.line.0 <= 0 {
+            // scrolling moves the cursor down along with the rest of the viewport
+            self.scroll_up_one_line();
+        }
+        match self.copy_mode.as_mut() {
+            Some(copy_mode) if copy_mode.cursor.line.0 > 0 => {
+                copy_mode.cursor.line.0 -= 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn copy_mode_line_down(&mut self) -> bool {
+        let Some(cursor) = self.copy_mode_cursor() else {
+            return false;
+        };
+        let last_line = self.last_copy_mode_line() as isize;
+        if cursor.line.0 >= last_line {
+            self.scroll_down_one_line();
+        }
+        match self.copy_mode.as_mut() {
+            Some(copy_mode) if copy_mode.cursor.line.0 < last_line => {
+                copy_mode.cursor.line.0 += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn copy_mode_lines_up(&mut self, count: usize) {
+        for _ in 0..count {
+            if !self.copy_mode_line_up() {
+                break;
+            }
+        }
+    }
+
+    fn copy_mode_lines_down(&mut self, count: usize) {
+        for _ in 0..count {
+            if !self.copy_mode_line_down() {
+                break;
+            }
+        }
+    }
+
+    // Moves the cursor one character forward, wrapping to the next line, and returns the class
+    // of the new character and whether we crossed into a new (non-wrapped) line to get there
+    fn copy_mode_step_forward(&mut self) -> Option<(CharacterClass, bool)> {
+        let cursor = self.copy_mode_cursor()?;
+        if let Some((column, character)) = self
+            .copy_mode_cells()
+            .into_iter()
+            .find(|(column, _)| *column > cursor.column.0)
+        {
+            self.set_copy_mode_column(column);
+            return Some((self.character_class(character), false));
+        }
+        if !self.copy_mode_line_down() {
+            return None;
+        }
+        self.set_copy_mode_column(0);
+        let line = self.copy_mode_cursor()?.line.0;
+        let crossed_line_break = self.copy_mode_line_is_canonical(line);
+        let class = self
+            .copy_mode_cells()
+            .first()
+            .map(|(_, character)| self.character_class(*character))
+            .unwrap_or(CharacterClass::Whitespace);
+        Some((class, crossed_line_break))
+    }
+
+    fn copy_mode_step_backward(&mut self) -> Option<CharacterClass> {
+        let cursor = self.copy_mode_cursor()?;
+        if let Some((column, character)) = self
+            .copy_mode_cells()
+            .into_iter()
+            .rev()
+            .find(|(column, _)| *column < cursor.column.0)
+        {
+            self.set_copy_mode_column(column);
+            return Some(self.character_class(character));
+        }
+        let was_wrapped = !self.copy_mode_line_is_canonical(cursor.line.0);
+        if !self.copy_mode_line_up() {
+            return None;
+        }
+        match self.copy_mode_cells().last() {
+            Some((column, character)) => {
+                let class = self.character_class(*character);
+                self.set_copy_mode_column(*column);
+                Some(if was_wrapped {
+                    class
+                } else {
+                    CharacterClass::Whitespace
+                })
+            },
+            None => {
+                self.set_copy_mode_column(0);
+                Some(CharacterClass::Whitespace)
+            },
+        }
+    }
+
+    // The class of the next character, without crossing into a new (non-wrapped) line
+    fn copy_mode_peek_forward(&self) -> Option<CharacterClass> {
+        let cursor = self.copy_mode_cursor()?;
+        let cells = self.copy_mode_cells();
+        match cells.iter().find(|(column, _)| *column > cursor.column.0) {
+            Some((_, character)) => Some(self.character_class(*character)),
+            None => {
+                let next_line = cursor.line.0 + 1;
+                if self.copy_mode_line_is_canonical(next_line) {
+                    None
+                } else {
+                    self.copy_mode_cells_at(next_line)
+                        .first()
+                        .map(|(_, character)| self.character_class(*character))
+                }
+            },
+        }
+    }
+
+    fn copy_mode_peek_backward(&self) -> Option<CharacterClass> {
+        let cursor = self.copy_mode_cursor()?;
+        let cells = self.copy_mode_cells();
+        match cells
+            .iter()
+            .rev()
+            .find(|(column, _)| *column < cursor.column.0)
+        {
+            Some((_, character)) => Some(self.character_class(*character)),
+            None => {
+                if self.copy_mode_line_is_canonical(cursor.line.0) {
+                    None
+                } else {
+                    self.copy_mode_cells_at(cursor.line.0 - 1)
+                        .last()
+                        .map(|(_, character)| self.character_class(*character))
+                }
+            },
+        }
+    }
+
+    fn copy_mode_word_forward(&mut self) {
+        let mut previous_class = self.copy_mode_class_at_cursor();
+        while let Some((class, crossed_line_break)) = self.copy_mode_step_forward() {
+            if class != CharacterClass::Whitespace
+                && (class != previous_class || crossed_line_break)
+            {
+                break;
+            }
+            previous_class = class;
+        }
+    }
+
+    fn copy_mode_word_end(&mut self) {
+        let class = loop {
+            match self.copy_mode_step_forward() {
+                Some((CharacterClass::Whitespace, _)) => continue,
+                Some((class, _)) => break class,
+                None => return,
+            }
+        };
+        while self.copy_mode_peek_forward() == Some(class) {
+            if self.copy_mode_step_forward().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn copy_mode_word_backward(&mut self) {
+        let class = loop {
+            match self.copy_mode_step_backward() {
+                Some(CharacterClass::Whitespace) => continue,
+                Some(class) => break class,
+                None => return,
+            }
+        };
+        while self.copy_mode_peek_backward() == Some(class) {
+            if self.copy_mode_step_backward().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn copy_mode_line_is_blank(&self) -> bool {
+        self.copy_mode_cells()
+            .iter()
+            .all(|(_, character)| character.is_whitespace())
+    }
+
+    // Moves to the next blank line after the current paragraph, skipping any blank lines the
+    // cursor is already on
+    fn copy_mode_paragraph(&mut self, forward: bool) {
+        let mut seen_text = !self.copy_mode_line_is_blank();
+        loop {
+            let moved = if forward {
+                self.copy_mode_line_down()
+            } else {
+                self.copy_mode_line_up()
+            };
+            if !moved {
+                break;
+            }
+            let is_blank = self.copy_mode_line_is_blank();
+            if is_blank && seen_text {
+                break;
+            }
+            seen_text |= !is_blank;
+        }
+        self.set_copy_mode_column(0);
+    }
+
+    // Prompts are found through the OSC 133 markers of the shell, if we don't find any we go
+    // back to where we started
+    fn copy_mode_prompt(&mut self, forward: bool) {
+        let Some(original_column) = self.copy_mode_cursor().map(|c| c.column.0) else {
+            return;
+        };
+        let mut lines_moved = 0;
+        loop {
+            let moved = if forward {
+                self.copy_mode_line_down()
+            } else {
+                self.copy_mode_line_up()
+            };
+            if !moved {
+                break;
+            }
+            lines_moved += 1;
+            let line = self.copy_mode_cursor().map(|c| c.line.0).unwrap_or(0);
+            if let Some(prompt_column) = self.prompt_column_at_line(line) {
+                self.set_copy_mode_column(prompt_column);
+                return;
+            }
+        }
+        if forward {
+            self.copy_mode_lines_up(lines_moved);
+        } else {
+            self.copy_mode_lines_down(lines_moved);
+        }
+        self.set_copy_mode_column(original_column);
+    }
+
+    fn move_copy_mode_cursor_to_active_search_result(&mut self) {
+        if let Some(active) = self.search_results.active {
+            let start = active.sorted().start;
+            if start.line.0 >= 0 && start.line.0 <= self.last_copy_mode_line() as isize {
+                self.set_copy_mode_cursor(start);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/copy_mode_tests.rs"]
+mod copy_mode_tests;
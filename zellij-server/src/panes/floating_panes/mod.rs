@@ -467,6 +467,7 @@ impl FloatingPanes {
         output: &mut Output,
         mouse_hover_pane_id: &HashMap<ClientId, PaneId>,
         current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+        input_synced_clients: &HashSet<ClientId>,
         client_id_override: Option<ClientId>,
         help_text_visible: &HashMap<ClientId, bool>,
         mouse_scroll_resize: bool,
@@ -588,6 +589,7 @@ impl FloatingPanes {
                 mouse_hover_tips,
                 self.dimmed_clients.clone(),
             );
+            pane_contents_and_ui.set_input_synced_clients(input_synced_clients.clone());
             for client_id in &connected_clients {
                 let client_mode = self
                     .mode_info
@@ -3,6 +3,7 @@ use super::kitty_graphics::{
     KittyError, KittyErrorCode, KittyGrid, KittyHostSupport, KittyImageChunk, KittyImageStore,
     KittyPlacement, KittyReplyData, KittyRowsBelowTheViewport, KittyVerticalAnchor,
 };
+use super::scrollback_snapshot::{decode_scrollback_snapshot, encode_scrollback_snapshot};
 use super::sixel::{PixelRect, SixelGrid, SixelImageStore};
 use base64::alphabet::STANDARD as BASE64_STANDARD_ALPHABET;
 use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
@@ -151,6 +152,7 @@ use zellij_utils::{consts::VERSION, shared::version_number};
 
 use crate::output::{CharacterChunk, HighlightSelection, OutputBuffer, SixelImageChunk};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
+use crate::panes::copy_mode::CopyMode;
 use crate::panes::hyperlink_tracker::HyperlinkTracker;
 use crate::panes::link_handler::LinkHandler;
 use crate::panes::search::SearchResult;
@@ -692,7 +694,7 @@ pub struct Grid {
     pub(crate) lines_below: VecDeque<Row>,
     horizontal_tabstops: BTreeSet<usize>,
     alternate_screen_state: Option<AlternateScreenState>,
-    cursor: Cursor,
+    pub(crate) cursor: Cursor,
     cursor_is_hidden: bool,
     saved_cursor_position: Option<Cursor>,
     scroll_region: (usize, usize),
@@ -779,7 +781,8 @@ pub struct Grid {
     osc133_markers_seen: bool,
     osc133_command_selection: bool,
     command_output_flash: Option<Selection>,
-    word_separators: String,
+    pub(crate) word_separators: String,
+    pub(crate) copy_mode: Option<CopyMode>,
 }
 
 impl Grid {
@@ -1152,6 +1155,7 @@ impl Grid {
             osc133_command_selection: true,
             command_output_flash: None,
             word_separators: DEFAULT_WORD_SEPARATORS.to_owned(),
+            copy_mode: None,
         }
     }
     pub fn set_selection_options(&mut self, osc133_command_selection: bool, word_separators: &str) {
@@ -1437,6 +1441,9 @@ impl Grid {
             if let Some(command_output_flash) = self.command_output_flash.as_mut() {
                 command_output_flash.move_down(1);
             }
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_down(1);
+            }
             // Move all search-selections down one line as well
             found_something = self
                 .search_results
@@ -1501,6 +1508,9 @@ impl Grid {
             if let Some(command_output_flash) = self.command_output_flash.as_mut() {
                 command_output_flash.move_up(1);
             }
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_up(1);
+            }
             // Move all search-selections up one line as well
             found_something =
                 self.search_results
@@ -1757,7 +1767,13 @@ impl Grid {
         self.set_scroll_region_to_viewport_size();
         self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
         self.search_results.selections.clear();
+        self.search_results.capture_groups.clear();
         self.search_viewport();
+        if self.copy_mode.is_some() {
+            // positions do not survive the reflow, so we start over from the terminal cursor
+            self.exit_copy_mode();
+            self.enter_copy_mode();
+        }
         // If we have thrown out the active element, set it to None
         self.search_results.unset_active_selection_if_nonexistent();
         self.output_buffer.update_all_lines();
@@ -1862,6 +1878,78 @@ impl Grid {
             },
         }
     }
+    pub fn scrollback_snapshot(
+        &self,
+        scrollback_lines_to_serialize: Option<usize>,
+    ) -> Option<Vec<u8>> {
+        // if an alternate screen is active (eg. an editor), we snapshot the primary screen behind it
+        let (lines_above, viewport, lines_below) = match &self.alternate_screen_state {
+            Some(alternate_screen_state) => (
+                &alternate_screen_state.lines_above,
+                &alternate_screen_state.viewport,
+                None,
+            ),
+            None => (&self.lines_above, &self.viewport, Some(&self.lines_below)),
+        };
+        let first_index = match scrollback_lines_to_serialize {
+            Some(0) | None => 0,
+            Some(scrollback_lines_to_serialize) => lines_above
+                .len()
+                .saturating_sub(scrollback_lines_to_serialize),
+        };
+        let mut lines: Vec<Row> = lines_above.iter().skip(first_index).cloned().collect();
+        let wrapped_rows = viewport
+            .iter()
+            .chain(lines_below.into_iter().flatten())
+            .cloned();
+        for mut row in wrapped_rows {
+            match lines.last_mut() {
+                Some(last_line) if !row.is_canonical => last_line.append(&mut row),
+                _ => lines.push(row),
+            }
+        }
+        while lines
+            .last()
+            .map(|line| line.columns.is_empty() && line.osc133_markers.is_empty())
+            .unwrap_or(false)
+        {
+            lines.pop();
+        }
+        match encode_scrollback_snapshot(&lines, &self.link_handler.borrow()) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                log::error!("Failed to create scrollback snapshot: {:?}", e);
+                None
+            },
+        }
+    }
+    pub fn restore_scrollback_snapshot(&mut self, snapshot: &[u8]) -> Result<()> {
+        let lines = decode_scrollback_snapshot(snapshot, &mut self.link_handler.borrow_mut())?;
+        if lines.iter().any(|line| !line.osc133_markers.is_empty()) {
+            self.osc133_markers_seen = true;
+        }
+        self.viewport.clear();
+        for mut line in lines {
+            self.viewport
+                .extend(line.split_to_rows_of_length(self.width));
+        }
+        // leave an empty line for the cursor, as a fresh shell prompt would
+        let overflow = (self.viewport.len() + 1).saturating_sub(self.height);
+        transfer_rows_from_viewport_to_lines_above(
+            &mut self.viewport,
+            &mut self.lines_above,
+            &mut self.sixel_grid,
+            &mut self.kitty_grid,
+            overflow,
+            self.width,
+        );
+        self.viewport.push_back(Row::new().canonical());
+        self.cursor.x = 0;
+        self.cursor.y = self.viewport.len() - 1;
+        self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        self.output_buffer.update_all_lines();
+        Ok(())
+    }
     pub fn render(
         &mut self,
         content_x: usize,
@@ -1896,6 +1984,33 @@ impl Grid {
                 self.pane_default_fg.map(AnsiCode::RgbCode),
                 self.pane_default_bg.map(AnsiCode::RgbCode),
             );
+            if let Some(copy_mode) = self.copy_mode.as_ref() {
+                for block_selection in copy_mode.block_selections.iter() {
+                    if block_selection.contains_row(character_chunk.y.saturating_sub(content_y)) {
+                        let background_color = match style.colors.text_selected.background {
+                            PaletteColor::Rgb(rgb) => AnsiCode::RgbCode(rgb),
+                            PaletteColor::EightBit(col) => AnsiCode::ColorIndex(col),
+                        };
+                        let foreground_color = match style.colors.text_selected.base {
+                            PaletteColor::Rgb(rgb) => AnsiCode::RgbCode(rgb),
+                            PaletteColor::EightBit(col) => AnsiCode::ColorIndex(col),
+                        };
+                        character_chunk.add_selection_and_colors(
+                            HighlightSelection {
+                                selection: *block_selection,
+                                bg: Some(background_color),
+                                fg: Some(foreground_color),
+                                bold: false,
+                                italic: false,
+                                underline: false,
+                                layer: HighlightLayer::ActionFeedback,
+                            },
+                            content_x,
+                            content_y,
+                        );
+                    }
+                }
+            }
             if self
                 .selection
                 .contains_row(character_chunk.y.saturating_sub(content_y))
@@ -1923,6 +2038,32 @@ impl Grid {
                     content_y,
                 );
             } else if !self.search_results.selections.is_empty() {
+                // Capture groups go first, so that they take precedence over the match containing them
+                for capture_group in self.search_results.capture_groups.iter() {
+                    if capture_group.contains_row(character_chunk.y.saturating_sub(content_y)) {
+                        let background_color = match style.colors.text_unselected.emphasis_1 {
+                            PaletteColor::Rgb(rgb) => AnsiCode::RgbCode(rgb),
+                            PaletteColor::EightBit(col) => AnsiCode::ColorIndex(col),
+                        };
+                        let foreground_color = match style.colors.text_unselected.background {
+                            PaletteColor::Rgb(rgb) => AnsiCode::RgbCode(rgb),
+                            PaletteColor::EightBit(col) => AnsiCode::ColorIndex(col),
+                        };
+                        character_chunk.add_selection_and_colors(
+                            HighlightSelection {
+                                selection: *capture_group,
+                                bg: Some(background_color),
+                                fg: Some(foreground_color),
+                                bold: false,
+                                italic: false,
+                                underline: false,
+                                layer: HighlightLayer::ActionFeedback,
+                            },
+                            content_x,
+                            content_y,
+                        );
+                    }
+                }
                 for res in self.search_results.selections.iter() {
                     if res.contains_row(character_chunk.y.saturating_sub(content_y)) {
                         let (select_background_palette, select_foreground_palette) =
@@ -2004,6 +2145,14 @@ impl Grid {
     /// bounds) so that the host terminal can position the cursor for IME even
     /// when the app has hidden it. The bool is true when the cursor is visible.
     pub fn cursor_coordinates(&self) -> Option<(usize, usize, bool)> {
+        if let Some(copy_mode_cursor) = self.copy_mode_cursor() {
+            let (x, y) = (copy_mode_cursor.column.0, copy_mode_cursor.line.0);
+            return if y < 0 || y as usize >= self.height || x >= self.width {
+                None
+            } else {
+                Some((x, y as usize, true))
+            };
+        }
         if self.cursor.x >= self.width || self.cursor.y >= self.height {
             None
         } else {
@@ -2142,6 +2291,9 @@ impl Grid {
         {
             self.transfer_rows_to_lines_above(1);
             self.selection.move_up(1);
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_up(1);
+            }
             RegionRowsScrolled::IntoScrollback
         } else if at < self.viewport.len() {
             self.viewport.remove(at);
@@ -2223,6 +2375,9 @@ impl Grid {
             }
             self.viewport.push_back(new_row);
             self.selection.move_up(1);
+            if let Some(copy_mode) = self.copy_mode.as_mut() {
+                copy_mode.move_up(1);
+            }
         } else {
             let outcome = self.scroll_region_content_up(
                 scroll_region_top,
@@ -3307,7 +3462,7 @@ impl Grid {
         Some((position_start, position_end))
     }
 
-    fn row_at(&self, line: isize) -> Option<&Row> {
+    pub(crate) fn row_at(&self, line: isize) -> Option<&Row> {
         if line < 0 {
             let offset_from_end = line.unsigned_abs();
             if self.lines_above.len() >= offset_from_end {
@@ -3440,6 +3595,22 @@ impl Grid {
         None
     }
 
+    pub(crate) fn prompt_column_at_line(&self, line: isize) -> Option<usize> {
+        if !self.osc133_markers_seen {
+            return None;
+        }
+        self.row_at(line)?
+            .osc133_markers
+            .iter()
+            .find(|marker| {
+                matches!(
+                    marker.kind,
+                    Osc133MarkerKind::Prompt | Osc133MarkerKind::Input
+                )
+            })
+            .map(|marker| marker.column)
+    }
+
     pub fn scroll_to_previous_prompt(&mut self) -> bool {
         let delta = self.previous_prompt_line_delta();
         match delta {
@@ -3572,7 +3743,11 @@ impl Grid {
         exit_codes
     }
 
-    fn update_selected_lines(&mut self, old_selection: &Selection, new_selection: &Selection) {
+    pub(crate) fn update_selected_lines(
+        &mut self,
+        old_selection: &Selection,
+        new_selection: &Selection,
+    ) {
         for l in old_selection.diff(new_selection, self.height) {
             self.output_buffer.update_line(l as usize);
         }
@@ -5592,11 +5767,11 @@ pub struct Row {
     pub is_canonical: bool,
     width: Option<usize>,
     pub bg_color: Option<AnsiCode>,
-    osc133_markers: Vec<Osc133Marker>,
+    pub(crate) osc133_markers: Vec<Osc133Marker>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Osc133MarkerKind {
+pub(crate) enum Osc133MarkerKind {
     Prompt,
     Input,
     Output,
@@ -5604,9 +5779,9 @@ enum Osc133MarkerKind {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct Osc133Marker {
-    column: usize,
-    kind: Osc133MarkerKind,
+pub(crate) struct Osc133Marker {
+    pub(crate) column: usize,
+    pub(crate) kind: Osc133MarkerKind,
 }
 
 impl Debug for Row {
@@ -62,6 +62,17 @@ impl LinkHandler {
         anchor
     }
 
+    pub fn get_link(&self, index: u16) -> Option<&Link> {
+        self.links.get(&index)
+    }
+
+    pub fn add_link(&mut self, link: Link) -> LinkAnchor {
+        let anchor = LinkAnchor::Start(self.link_index);
+        self.links.insert(self.link_index, link);
+        self.link_index += 1;
+        anchor
+    }
+
     pub fn output_osc8(&self, link_anchor: Option<LinkAnchor>) -> Option<String> {
         link_anchor.and_then(|link| match link {
             LinkAnchor::Start(index) => {
@@ -4,11 +4,15 @@ pub mod hyperlink_tracker;
 pub mod kitty_graphics;
 pub mod link_handler;
 pub mod nested_session_modal;
+pub mod output_log;
+pub mod pane_monitor;
+pub mod scrollback_snapshot;
 pub mod selection;
 pub mod sixel;
 pub mod terminal_character;
 
 mod active_panes;
+mod copy_mode;
 pub mod floating_panes;
 mod plugin_pane;
 mod search;
@@ -0,0 +1,224 @@
+//! Continuously appends the output of a terminal pane to a file (`log_output` in layouts, or the
+//! `start-pane-log` action).
+//!
+//! The log is fed the raw bytes the pane's process writes, before they are interpreted by the
+//! `Grid`, so it is not limited by the scrollback buffer. Optionally the ANSI escape sequences are
+//! stripped, each line is prefixed with the time it was received and the file is rotated once it
+//! grows beyond a certain size.
+//!
+//! The file is written to from a thread of its own, so that a slow or full disk does not hold up
+//! the screen thread.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use zellij_utils::channels::{self, Sender, TrySendError};
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::layout::LogOutput;
+
+const DEFAULT_MAX_FILES: usize = 5;
+// chunks of output waiting to be written, beyond these new output is dropped from the log
+const MAX_PENDING_WRITES: usize = 1024;
+const TIMESTAMP_FORMAT: &str = "[%Y-%m-%d %H:%M:%S%.3f] ";
+
+pub struct PaneOutputLog {
+    log_output: LogOutput,
+    file: File,
+    size: u64,
+    at_line_start: bool,
+    ansi_parser: Option<vte::Parser>,
+}
+
+impl PaneOutputLog {
+    pub fn open(log_output: LogOutput) -> Result<Self> {
+        let err_context = || format!("failed to open pane log {}", log_output.path.display());
+        if let Some(parent) = log_output.path.parent() {
+            fs::create_dir_all(parent).with_context(err_context)?;
+        }
+        let file = open_for_append(&log_output.path).with_context(err_context)?;
+        let size = file.metadata().with_context(err_context)?.len();
+        let ansi_parser = if log_output.strip_ansi {
+            Some(vte::Parser::new())
+        } else {
+            None
+        };
+        Ok(PaneOutputLog {
+            log_output,
+            file,
+            size,
+            at_line_start: true,
+            ansi_parser,
+        })
+    }
+    pub fn log_output(&self) -> &LogOutput {
+        &self.log_output
+    }
+    pub fn path(&self) -> &Path {
+        &self.log_output.path
+    }
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let result = self.append(bytes);
+        result.with_context(|| format!("failed to write to pane log {}", self.path().display()))
+    }
+    fn append(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let mut output = match self.ansi_parser.as_mut() {
+            Some(ansi_parser) => {
+                let mut stripper = AnsiStripper::default();
+                ansi_parser.advance(&mut stripper, bytes);
+                stripper.output
+            },
+            None => bytes.to_vec(),
+        };
+        if self.log_output.timestamps {
+            output = self.add_timestamps(&output);
+        }
+        if output.is_empty() {
+            return Ok(());
+        }
+        if let Some(max_size) = self.log_output.max_size {
+            if self.size > 0 && self.size + output.len() as u64 > max_size {
+                self.rotate()?;
+            }
+        }
+        self.file.write_all(&output)?;
+        self.size += output.len() as u64;
+        Ok(())
+    }
+    fn add_timestamps(&mut self, output: &[u8]) -> Vec<u8> {
+        let timestamp = chrono::Local::now().format(TIMESTAMP_FORMAT).to_string();
+        let mut timestamped = Vec::with_capacity(output.len() + timestamp.len());
+        for byte in output {
+            if self.at_line_start {
+                timestamped.extend_from_slice(timestamp.as_bytes());
+            }
+            timestamped.push(*byte);
+            self.at_line_start = *byte == b'\n';
+        }
+        timestamped
+    }
+    // path -> path.1 -> path.2 ... the oldest file falls off the end
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let max_files = self.log_output.max_files.unwrap_or(DEFAULT_MAX_FILES);
+        let path = self.log_output.path.clone();
+        if max_files == 0 {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+        } else {
+            for index in (1..max_files).rev() {
+                let from = rotated_path(&path, index);
+                if from.exists() {
+                    fs::rename(&from, rotated_path(&path, index + 1))?;
+                }
+            }
+            // the log might have been moved or removed from under us
+            if path.exists() {
+                fs::rename(&path, rotated_path(&path, 1))?;
+            }
+        }
+        self.file = open_for_append(&path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// A `PaneOutputLog` written to from its own thread
+pub struct BackgroundOutputLog {
+    log_output: LogOutput,
+    sender: Sender<Vec<u8>>,
+    is_dropping_output: bool,
+}
+
+impl BackgroundOutputLog {
+    pub fn start(log_output: LogOutput) -> Result<Self> {
+        let mut output_log = PaneOutputLog::open(log_output.clone())?;
+        let (sender, receiver) = channels::bounded::<Vec<u8>>(MAX_PENDING_WRITES);
+        thread::Builder::new()
+            .name("pane_output_log".to_owned())
+            .spawn(move || {
+                while let Ok(bytes) = receiver.recv() {
+                    if let Err(e) = output_log.write(&bytes) {
+                        // the receiver is dropped along with this thread, which stops the pane
+                        // from sending more output
+                        log::error!("{:?}", e);
+                        break;
+                    }
+                }
+            })
+            .with_context(|| {
+                format!(
+                    "failed to start writing to pane log {}",
+                    log_output.path.display()
+                )
+            })?;
+        Ok(BackgroundOutputLog {
+            log_output,
+            sender,
+            is_dropping_output: false,
+        })
+    }
+    pub fn log_output(&self) -> &LogOutput {
+        &self.log_output
+    }
+    pub fn path(&self) -> &Path {
+        &self.log_output.path
+    }
+    /// Queues `bytes` to be written, errors if the log was closed because writing to it failed
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        match self.sender.try_send(bytes.to_vec()) {
+            Ok(()) => {
+                self.is_dropping_output = false;
+                Ok(())
+            },
+            Err(TrySendError::Full(_)) => {
+                if !self.is_dropping_output {
+                    log::warn!(
+                        "Pane log {} is falling behind, dropping output",
+                        self.path().display()
+                    );
+                    self.is_dropping_output = true;
+                }
+                Ok(())
+            },
+            Err(TrySendError::Disconnected(_)) => Err(anyhow!(
+                "stopped writing to pane log {}",
+                self.path().display()
+            )),
+        }
+    }
+}
+
+fn open_for_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+pub fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    PathBuf::from(rotated)
+}
+
+#[derive(Default)]
+pub(crate) struct AnsiStripper {
+    pub(crate) output: Vec<u8>,
+}
+
+impl vte::Perform for AnsiStripper {
+    fn print(&mut self, c: char) {
+        let mut buf = [0; 4];
+        self.output
+            .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    fn execute(&mut self, byte: u8) {
+        // carriage returns are dropped so that CRLF line endings end up as plain newlines
+        if byte == b'\n' || byte == b'\t' {
+            self.output.push(byte);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/output_log_tests.rs"]
+mod output_log_tests;
@@ -0,0 +1,66 @@
+//! Activity and silence monitors of a terminal pane (`monitor_activity` and `monitor_silence` in
+//! layouts, or the `monitor-pane` action).
+//!
+//! The monitor is told whenever the pane receives output and is periodically checked by the
+//! screen, which raises the alerts it returns.
+
+use std::time::{Duration, Instant};
+
+use zellij_utils::data::{PaneMonitorAlert, PaneMonitors};
+
+/// How long a pane has to be quiet before new output counts as activity, so that a pane
+/// continuously printing does not alert over and over again
+pub const ACTIVITY_QUIET_PERIOD: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct PaneMonitor {
+    monitors: PaneMonitors,
+    last_output: Instant,
+    activity_pending: bool,
+    silence_alerted: bool,
+}
+
+impl PaneMonitor {
+    /// The pane is considered to have just produced output, so neither an activity nor a
+    /// silence alert can be raised right away
+    pub fn new(monitors: PaneMonitors, now: Instant) -> Self {
+        PaneMonitor {
+            monitors,
+            last_output: now,
+            activity_pending: false,
+            silence_alerted: false,
+        }
+    }
+    pub fn monitors(&self) -> PaneMonitors {
+        self.monitors
+    }
+    pub fn output(&mut self, now: Instant) {
+        if self.monitors.activity
+            && now.saturating_duration_since(self.last_output) >= ACTIVITY_QUIET_PERIOD
+        {
+            self.activity_pending = true;
+        }
+        self.last_output = now;
+        self.silence_alerted = false;
+    }
+    /// Activity is reported once per burst of output, silence once per silent stretch
+    pub fn check(&mut self, now: Instant) -> Vec<PaneMonitorAlert> {
+        let mut alerts = vec![];
+        if self.activity_pending {
+            self.activity_pending = false;
+            alerts.push(PaneMonitorAlert::Activity);
+        }
+        if let Some(silence_secs) = self.monitors.silence_secs {
+            let silent_for = now.saturating_duration_since(self.last_output);
+            if !self.silence_alerted && silent_for >= Duration::from_secs(silence_secs) {
+                self.silence_alerted = true;
+                alerts.push(PaneMonitorAlert::Silence(silence_secs));
+            }
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pane_monitor_tests.rs"]
+mod pane_monitor_tests;
@@ -22,7 +22,8 @@ use std::rc::Rc;
 use vte;
 use zellij_utils::data::PaneContents;
 use zellij_utils::data::{
-    BareKey, KeyWithModifier, PermissionStatus, PermissionType, PluginPermission,
+    BareKey, FilesystemPermission, KeyWithModifier, PermissionStatus, PermissionType,
+    PluginPermission,
 };
 use zellij_utils::pane_size::{Offset, SizeInPixels};
 use zellij_utils::position::Position;
@@ -291,17 +292,20 @@ impl Pane for PluginPane {
             None
         } else if let Some(requesting_permissions) = &self.requesting_permissions {
             let permissions = requesting_permissions.permissions.clone();
+            let filesystem_permissions = requesting_permissions.filesystem_permissions.clone();
             if let Some(key_with_modifier) = key_with_modifier {
                 match key_with_modifier.bare_key {
                     BareKey::Char('y') if key_with_modifier.has_no_modifiers() => {
                         Some(AdjustedInput::PermissionRequestResult(
                             permissions,
+                            filesystem_permissions,
                             PermissionStatus::Granted,
                         ))
                     },
                     BareKey::Char('n') if key_with_modifier.has_no_modifiers() => {
                         Some(AdjustedInput::PermissionRequestResult(
                             permissions,
+                            filesystem_permissions,
                             PermissionStatus::Denied,
                         ))
                     },
@@ -312,11 +316,13 @@ impl Pane for PluginPane {
                     // Y or y
                     &[89] | &[121] => Some(AdjustedInput::PermissionRequestResult(
                         permissions,
+                        filesystem_permissions,
                         PermissionStatus::Granted,
                     )),
                     // N or n
                     &[78] | &[110] => Some(AdjustedInput::PermissionRequestResult(
                         permissions,
+                        filesystem_permissions,
                         PermissionStatus::Denied,
                     )),
                     _ => None,
@@ -969,8 +975,10 @@ impl PluginPane {
         let mut messages = String::new();
         let permissions: BTreeSet<PermissionType> =
             plugin_permission.permissions.clone().into_iter().collect();
+        let filesystem_permissions: BTreeSet<&FilesystemPermission> =
+            plugin_permission.filesystem_permissions.iter().collect();
 
-        let min_row_count = permissions.len() + 4;
+        let min_row_count = permissions.len() + filesystem_permissions.len() + 4;
 
         if self.rows() >= min_row_count {
             messages.push_str(&format!(
@@ -979,13 +987,18 @@ impl PluginPane {
                 cyan.paint(&plugin_permission.name),
                 bold_white.paint("asks permission to:"),
             ));
-            permissions.iter().enumerate().for_each(|(i, p)| {
-                messages.push_str(&format!(
-                    "\n\r{}. {}",
-                    bold_white.paint(&format!("{}", i + 1)),
-                    orange.paint(p.display_name())
-                ));
-            });
+            permissions
+                .iter()
+                .map(|p| web_access_display_name(p, p.display_name(), plugin_permission))
+                .chain(filesystem_permissions.iter().map(|p| p.display_name()))
+                .enumerate()
+                .for_each(|(i, display_name)| {
+                    messages.push_str(&format!(
+                        "\n\r{}. {}",
+                        bold_white.paint(&format!("{}", i + 1)),
+                        orange.paint(display_name)
+                    ));
+                });
 
             messages.push_str(&format!(
                 "\n\n\r{} {}",
@@ -999,7 +1012,8 @@ impl PluginPane {
                 orange.paint(
                     permissions
                         .iter()
-                        .map(|p| p.to_string())
+                        .map(|p| web_access_display_name(p, p.to_string(), plugin_permission))
+                        .chain(filesystem_permissions.iter().map(|p| p.display_name()))
                         .collect::<Vec<_>>()
                         .join(", ")
                 ),
@@ -1011,3 +1025,20 @@ impl PluginPane {
         messages
     }
 }
+
+// a plugin restricted to some hosts only asks for web access to them
+fn web_access_display_name(
+    permission: &PermissionType,
+    display_name: String,
+    plugin_permission: &PluginPermission,
+) -> String {
+    if permission == &PermissionType::WebAccess && !plugin_permission.allowed_hosts.is_empty() {
+        format!(
+            "{} (only {})",
+            display_name,
+            plugin_permission.allowed_hosts.join(", ")
+        )
+    } else {
+        display_name
+    }
+}
@@ -0,0 +1,436 @@
+//! A compact binary format for the styled contents of a pane, used to carry its scrollback across
+//! session resurrection.
+//!
+//! A snapshot starts with a short uncompressed header (magic bytes and a format version) followed
+//! by a zlib compressed body holding the hyperlinks, a table of the distinct character styles and
+//! finally the lines themselves. Lines are stored unwrapped (the way they are kept above the
+//! viewport) so that they can be reflowed into a pane of any size when restored.
+
+use super::grid::{Osc133Marker, Osc133MarkerKind, Row};
+use super::link_handler::{Link, LinkHandler};
+use super::terminal_character::{
+    AnsiCode, AnsiStyledUnderline, CharacterStyles, LinkAnchor, NamedColor, RcCharacterStyles,
+    TerminalCharacter, DEFAULT_STYLES,
+};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io::{Read, Write};
+use unicode_width::UnicodeWidthChar;
+use zellij_utils::errors::prelude::*;
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"ZJSB";
+const SNAPSHOT_VERSION: u8 = 1;
+
+// snapshots are decompressed in full before being decoded, this bounds what a corrupt one can
+// make us allocate
+const MAX_SNAPSHOT_BODY_LEN: u64 = 256 * 1024 * 1024;
+
+// the smallest number of bytes each kind of entry can be encoded in, used to make sure a count
+// read from the snapshot fits in what is left of it before allocating anything for its entries
+const MIN_LINK_LEN: usize = 2 + 1 + 4; // index, optional id, uri
+const MIN_STYLE_LEN: usize = 12 + 1 + 1; // ansi codes, link anchor, styled underlines
+const MIN_LINE_LEN: usize = 1 + 1 + 4 + 4; // is_canonical, bg color, marker and column counts
+const MIN_MARKER_LEN: usize = 4 + 1; // column, kind
+const MIN_COLUMN_LEN: usize = 4 + 1 + 4; // character, width, style index
+
+const NAMED_COLORS: [NamedColor; 16] = [
+    NamedColor::Black,
+    NamedColor::Red,
+    NamedColor::Green,
+    NamedColor::Yellow,
+    NamedColor::Blue,
+    NamedColor::Magenta,
+    NamedColor::Cyan,
+    NamedColor::White,
+    NamedColor::BrightBlack,
+    NamedColor::BrightRed,
+    NamedColor::BrightGreen,
+    NamedColor::BrightYellow,
+    NamedColor::BrightBlue,
+    NamedColor::BrightMagenta,
+    NamedColor::BrightCyan,
+    NamedColor::BrightWhite,
+];
+
+pub fn encode_scrollback_snapshot(lines: &[Row], link_handler: &LinkHandler) -> Result<Vec<u8>> {
+    let err_context = || "failed to encode scrollback snapshot";
+
+    let mut style_indices: HashMap<Vec<u8>, u32> = HashMap::new();
+    let mut style_table: Vec<u8> = vec![];
+    let mut link_indices: BTreeSet<u16> = BTreeSet::new();
+    let mut encoded_lines: Vec<u8> = vec![];
+
+    encoded_lines
+        .write_u32::<LittleEndian>(lines.len() as u32)
+        .with_context(err_context)?;
+    for line in lines {
+        encoded_lines
+            .write_u8(line.is_canonical as u8)
+            .with_context(err_context)?;
+        write_ansi_code(&mut encoded_lines, line.bg_color).with_context(err_context)?;
+        encoded_lines
+            .write_u32::<LittleEndian>(line.osc133_markers.len() as u32)
+            .with_context(err_context)?;
+        for marker in &line.osc133_markers {
+            write_osc133_marker(&mut encoded_lines, marker).with_context(err_context)?;
+        }
+        encoded_lines
+            .write_u32::<LittleEndian>(line.columns.len() as u32)
+            .with_context(err_context)?;
+        for terminal_character in &line.columns {
+            let styles: &CharacterStyles = &terminal_character.styles;
+            if let Some(LinkAnchor::Start(link_index)) = styles.link_anchor {
+                link_indices.insert(link_index);
+            }
+            let mut encoded_styles = vec![];
+            write_character_styles(&mut encoded_styles, styles).with_context(err_context)?;
+            let next_style_index = style_indices.len() as u32;
+            let style_index =
+                *style_indices
+                    .entry(encoded_styles)
+                    .or_insert_with_key(|encoded_styles| {
+                        style_table.extend_from_slice(encoded_styles);
+                        next_style_index
+                    });
+            encoded_lines
+                .write_u32::<LittleEndian>(terminal_character.character as u32)
+                .with_context(err_context)?;
+            encoded_lines
+                .write_u8(terminal_character.width() as u8)
+                .with_context(err_context)?;
+            encoded_lines
+                .write_u32::<LittleEndian>(style_index)
+                .with_context(err_context)?;
+        }
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    let links: Vec<(u16, &Link)> = link_indices
+        .iter()
+        .filter_map(|index| link_handler.get_link(*index).map(|link| (*index, link)))
+        .collect();
+    encoder
+        .write_u32::<LittleEndian>(links.len() as u32)
+        .with_context(err_context)?;
+    for (index, link) in links {
+        encoder
+            .write_u16::<LittleEndian>(index)
+            .with_context(err_context)?;
+        write_optional_string(&mut encoder, link.id.as_deref()).with_context(err_context)?;
+        write_string(&mut encoder, &link.uri).with_context(err_context)?;
+    }
+    encoder
+        .write_u32::<LittleEndian>(style_indices.len() as u32)
+        .with_context(err_context)?;
+    encoder.write_all(&style_table).with_context(err_context)?;
+    encoder
+        .write_all(&encoded_lines)
+        .with_context(err_context)?;
+    let body = encoder.finish().with_context(err_context)?;
+
+    let mut snapshot = Vec::with_capacity(SNAPSHOT_MAGIC.len() + 1 + body.len());
+    snapshot.extend_from_slice(SNAPSHOT_MAGIC);
+    snapshot.push(SNAPSHOT_VERSION);
+    snapshot.extend_from_slice(&body);
+    Ok(snapshot)
+}
+
+/// Decodes a snapshot created with `encode_scrollback_snapshot`, registering the hyperlinks it
+/// contains with the given `LinkHandler` (their indices are remapped to the new ones).
+pub fn decode_scrollback_snapshot(
+    snapshot: &[u8],
+    link_handler: &mut LinkHandler,
+) -> Result<Vec<Row>> {
+    let err_context = || "failed to decode scrollback snapshot";
+
+    let header_len = SNAPSHOT_MAGIC.len() + 1;
+    if snapshot.len() < header_len || &snapshot[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC {
+        return Err(anyhow!("not a scrollback snapshot")).with_context(err_context);
+    }
+    let version = snapshot[SNAPSHOT_MAGIC.len()];
+    if version != SNAPSHOT_VERSION {
+        return Err(anyhow!("unsupported snapshot version {}", version)).with_context(err_context);
+    }
+    let mut decompressed = vec![];
+    ZlibDecoder::new(&snapshot[header_len..])
+        .take(MAX_SNAPSHOT_BODY_LEN + 1)
+        .read_to_end(&mut decompressed)
+        .with_context(err_context)?;
+    if decompressed.len() as u64 > MAX_SNAPSHOT_BODY_LEN {
+        return Err(anyhow!("snapshot is too large")).with_context(err_context);
+    }
+    let mut body: &[u8] = &decompressed;
+
+    let mut link_anchors: HashMap<u16, LinkAnchor> = HashMap::new();
+    let link_count = read_count(&mut body, MIN_LINK_LEN).with_context(err_context)?;
+    for _ in 0..link_count {
+        let index = body.read_u16::<LittleEndian>().with_context(err_context)?;
+        let id = read_optional_string(&mut body).with_context(err_context)?;
+        let uri = read_string(&mut body).with_context(err_context)?;
+        link_anchors.insert(index, link_handler.add_link(Link { id, uri }));
+    }
+
+    let style_count = read_count(&mut body, MIN_STYLE_LEN).with_context(err_context)?;
+    let mut styles: Vec<RcCharacterStyles> = Vec::with_capacity(style_count);
+    for _ in 0..style_count {
+        let mut character_styles = read_character_styles(&mut body).with_context(err_context)?;
+        if let Some(LinkAnchor::Start(index)) = character_styles.link_anchor {
+            // links we could not find when taking the snapshot are dropped
+            character_styles.link_anchor = link_anchors.get(&index).copied();
+        }
+        if character_styles == DEFAULT_STYLES {
+            styles.push(RcCharacterStyles::default());
+        } else {
+            styles.push(character_styles.into());
+        }
+    }
+
+    let line_count = read_count(&mut body, MIN_LINE_LEN).with_context(err_context)?;
+    let mut lines = Vec::with_capacity(line_count);
+    for _ in 0..line_count {
+        let is_canonical = body.read_u8().with_context(err_context)? != 0;
+        let bg_color = read_ansi_code(&mut body).with_context(err_context)?;
+        let marker_count = read_count(&mut body, MIN_MARKER_LEN).with_context(err_context)?;
+        let mut osc133_markers = Vec::with_capacity(marker_count);
+        for _ in 0..marker_count {
+            osc133_markers.push(read_osc133_marker(&mut body).with_context(err_context)?);
+        }
+        let column_count = read_count(&mut body, MIN_COLUMN_LEN).with_context(err_context)?;
+        let mut columns = VecDeque::with_capacity(column_count);
+        for _ in 0..column_count {
+            let character = char::from_u32(body.read_u32::<LittleEndian>()?)
+                .ok_or_else(|| anyhow!("invalid character"))
+                .with_context(err_context)?;
+            let width = body.read_u8().with_context(err_context)?;
+            let style_index = body.read_u32::<LittleEndian>().with_context(err_context)?;
+            let styles = styles
+                .get(style_index as usize)
+                .cloned()
+                .ok_or_else(|| anyhow!("invalid style index {}", style_index))
+                .with_context(err_context)?;
+            let terminal_character = if width == 1 && character.width().unwrap_or(0) != 1 {
+                TerminalCharacter::new_singlewidth_styled(character, styles)
+            } else {
+                TerminalCharacter::new_styled(character, styles)
+            };
+            columns.push_back(terminal_character);
+        }
+        let mut line = Row::from_columns(columns).with_bg_color(bg_color);
+        line.is_canonical = is_canonical;
+        line.osc133_markers = osc133_markers;
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+fn write_ansi_code(writer: &mut impl Write, ansi_code: Option<AnsiCode>) -> std::io::Result<()> {
+    match ansi_code {
+        None => writer.write_u8(0),
+        Some(AnsiCode::On) => writer.write_u8(1),
+        Some(AnsiCode::Reset) => writer.write_u8(2),
+        Some(AnsiCode::NamedColor(named_color)) => {
+            writer.write_u8(3)?;
+            let index = NAMED_COLORS
+                .iter()
+                .position(|c| *c == named_color)
+                .unwrap_or(0);
+            writer.write_u8(index as u8)
+        },
+        Some(AnsiCode::RgbCode((r, g, b))) => {
+            writer.write_u8(4)?;
+            writer.write_all(&[r, g, b])
+        },
+        Some(AnsiCode::ColorIndex(index)) => {
+            writer.write_u8(5)?;
+            writer.write_u8(index)
+        },
+        Some(AnsiCode::Underline(styled_underline)) => {
+            writer.write_u8(6)?;
+            writer.write_u8(match styled_underline {
+                None => 0,
+                Some(AnsiStyledUnderline::Double) => 1,
+                Some(AnsiStyledUnderline::Undercurl) => 2,
+                Some(AnsiStyledUnderline::Underdotted) => 3,
+                Some(AnsiStyledUnderline::Underdashed) => 4,
+            })
+        },
+    }
+}
+
+fn read_ansi_code(reader: &mut impl Read) -> Result<Option<AnsiCode>> {
+    let ansi_code = match reader.read_u8()? {
+        0 => None,
+        1 => Some(AnsiCode::On),
+        2 => Some(AnsiCode::Reset),
+        3 => {
+            let index = reader.read_u8()?;
+            let named_color = NAMED_COLORS
+                .get(index as usize)
+                .ok_or_else(|| anyhow!("invalid named color {}", index))?;
+            Some(AnsiCode::NamedColor(*named_color))
+        },
+        4 => {
+            let mut rgb = [0; 3];
+            reader.read_exact(&mut rgb)?;
+            Some(AnsiCode::RgbCode((rgb[0], rgb[1], rgb[2])))
+        },
+        5 => Some(AnsiCode::ColorIndex(reader.read_u8()?)),
+        6 => {
+            let styled_underline = match reader.read_u8()? {
+                0 => None,
+                1 => Some(AnsiStyledUnderline::Double),
+                2 => Some(AnsiStyledUnderline::Undercurl),
+                3 => Some(AnsiStyledUnderline::Underdotted),
+                4 => Some(AnsiStyledUnderline::Underdashed),
+                other => return Err(anyhow!("invalid styled underline {}", other)),
+            };
+            Some(AnsiCode::Underline(styled_underline))
+        },
+        other => return Err(anyhow!("invalid ansi code {}", other)),
+    };
+    Ok(ansi_code)
+}
+
+fn write_character_styles(
+    writer: &mut impl Write,
+    styles: &CharacterStyles,
+) -> std::io::Result<()> {
+    for ansi_code in [
+        styles.foreground,
+        styles.background,
+        styles.underline_color,
+        styles.strike,
+        styles.hidden,
+        styles.reverse,
+        styles.slow_blink,
+        styles.fast_blink,
+        styles.underline,
+        styles.bold,
+        styles.dim,
+        styles.italic,
+    ] {
+        write_ansi_code(writer, ansi_code)?;
+    }
+    match styles.link_anchor {
+        None => writer.write_u8(0)?,
+        Some(LinkAnchor::Start(index)) => {
+            writer.write_u8(1)?;
+            writer.write_u16::<LittleEndian>(index)?;
+        },
+        Some(LinkAnchor::End) => writer.write_u8(2)?,
+    }
+    writer.write_u8(styles.styled_underlines_enabled as u8)
+}
+
+fn read_character_styles(reader: &mut impl Read) -> Result<CharacterStyles> {
+    let foreground = read_ansi_code(reader)?;
+    let background = read_ansi_code(reader)?;
+    let underline_color = read_ansi_code(reader)?;
+    let strike = read_ansi_code(reader)?;
+    let hidden = read_ansi_code(reader)?;
+    let reverse = read_ansi_code(reader)?;
+    let slow_blink = read_ansi_code(reader)?;
+    let fast_blink = read_ansi_code(reader)?;
+    let underline = read_ansi_code(reader)?;
+    let bold = read_ansi_code(reader)?;
+    let dim = read_ansi_code(reader)?;
+    let italic = read_ansi_code(reader)?;
+    let link_anchor = match reader.read_u8()? {
+        0 => None,
+        1 => Some(LinkAnchor::Start(reader.read_u16::<LittleEndian>()?)),
+        2 => Some(LinkAnchor::End),
+        other => return Err(anyhow!("invalid link anchor {}", other)),
+    };
+    let styled_underlines_enabled = reader.read_u8()? != 0;
+    Ok(CharacterStyles {
+        foreground,
+        background,
+        underline_color,
+        strike,
+        hidden,
+        reverse,
+        slow_blink,
+        fast_blink,
+        underline,
+        bold,
+        dim,
+        italic,
+        link_anchor,
+        styled_underlines_enabled,
+    })
+}
+
+fn write_osc133_marker(writer: &mut impl Write, marker: &Osc133Marker) -> std::io::Result<()> {
+    writer.write_u32::<LittleEndian>(marker.column as u32)?;
+    match marker.kind {
+        Osc133MarkerKind::Prompt => writer.write_u8(0),
+        Osc133MarkerKind::Input => writer.write_u8(1),
+        Osc133MarkerKind::Output => writer.write_u8(2),
+        Osc133MarkerKind::End(None) => writer.write_u8(3),
+        Osc133MarkerKind::End(Some(exit_code)) => {
+            writer.write_u8(4)?;
+            writer.write_i32::<LittleEndian>(exit_code)
+        },
+    }
+}
+
+fn read_osc133_marker(reader: &mut impl Read) -> Result<Osc133Marker> {
+    let column = reader.read_u32::<LittleEndian>()? as usize;
+    let kind = match reader.read_u8()? {
+        0 => Osc133MarkerKind::Prompt,
+        1 => Osc133MarkerKind::Input,
+        2 => Osc133MarkerKind::Output,
+        3 => Osc133MarkerKind::End(None),
+        4 => Osc133MarkerKind::End(Some(reader.read_i32::<LittleEndian>()?)),
+        other => return Err(anyhow!("invalid prompt marker {}", other)),
+    };
+    Ok(Osc133Marker { column, kind })
+}
+
+fn write_string(writer: &mut impl Write, string: &str) -> std::io::Result<()> {
+    writer.write_u32::<LittleEndian>(string.len() as u32)?;
+    writer.write_all(string.as_bytes())
+}
+
+/// Reads a count of entries, making sure that that many entries of at least `min_entry_len` bytes
+/// fit in what is left of `reader`
+fn read_count(reader: &mut &[u8], min_entry_len: usize) -> Result<usize> {
+    let count = reader.read_u32::<LittleEndian>()? as usize;
+    if count.saturating_mul(min_entry_len) > reader.len() {
+        return Err(anyhow!(
+            "{} entries do not fit in the remaining {} bytes",
+            count,
+            reader.len()
+        ));
+    }
+    Ok(count)
+}
+
+fn read_string(reader: &mut &[u8]) -> Result<String> {
+    let len = read_count(reader, 1)?;
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn write_optional_string(writer: &mut impl Write, string: Option<&str>) -> std::io::Result<()> {
+    match string {
+        Some(string) => {
+            writer.write_u8(1)?;
+            write_string(writer, string)
+        },
+        None => writer.write_u8(0),
+    }
+}
+
+fn read_optional_string(reader: &mut &[u8]) -> Result<Option<String>> {
+    match reader.read_u8()? {
+        0 => Ok(None),
+        _ => read_string(reader).map(Some),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/scrollback_snapshot_tests.rs"]
+mod scrollback_snapshot_tests;
@@ -1,6 +1,7 @@
 use super::Selection;
 use crate::panes::terminal_character::TerminalCharacter;
 use crate::panes::{Grid, Row};
+use regex::{Regex, RegexBuilder};
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -12,6 +13,17 @@ fn is_word_boundary(x: &Option<char>) -> bool {
     x.map_or(true, |c| !c.is_ascii_alphanumeric() && c != '_')
 }
 
+// Positions in a not-yet-wrapped tail can lie beyond the width of the row, so move them down
+fn reflow_position(position: &mut Position, width: usize) {
+    if width == 0 {
+        return;
+    }
+    while position.column() > width {
+        position.column.0 -= width;
+        position.line.0 += 1;
+    }
+}
+
 #[derive(Debug)]
 enum SearchSource<'a> {
     Main(&'a Row),
@@ -99,6 +111,12 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Interpret the needle as a regular expression instead of a literal string
+    pub regex_search: bool,
+    // The capture groups inside the selections we found (only populated when searching with a regex)
+    pub capture_groups: Vec<Selection>,
+    // The needle compiled with the current search-options (None if not in regex mode or invalid)
+    regex: Option<Regex>,
 }
 
 impl SearchResult {
@@ -139,10 +157,173 @@ impl SearchResult {
                     .for_each(|x| *x = TerminalCharacter::new(replacement_char));
             }
         }
+        for s in &self.capture_groups {
+            if s.contains_row(ridx) {
+                let (skip, take) = if ridx as isize == s.start.line() {
+                    let take = if s.end.line() == s.start.line() {
+                        s.end.column() - s.start.column()
+                    } else {
+                        row.columns.len()
+                    };
+                    (s.start.column(), take)
+                } else if ridx as isize == s.end.line() {
+                    (0, s.end.column())
+                } else {
+                    (0, row.columns.len())
+                };
+                row.to_mut()
+                    .columns
+                    .iter_mut()
+                    .skip(skip)
+                    .take(take)
+                    .for_each(|x| *x = TerminalCharacter::new('='));
+            }
+        }
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search || self.whole_word_only || self.case_insensitive || self.regex_search
+    }
+
+    /// Recompile the needle, needs to be called whenever the needle or one of the
+    /// search-options changes while searching with a regex.
+    pub(crate) fn update_regex(&mut self) {
+        self.regex = if self.regex_search && !self.needle.is_empty() {
+            // Mimic the word-boundaries of the literal search, without adding a capture group
+            let pattern = if self.whole_word_only {
+                format!(r"\b(?:{})\b", self.needle)
+            } else {
+                self.needle.clone()
+            };
+            match RegexBuilder::new(&pattern)
+                .case_insensitive(self.case_insensitive)
+                .build()
+            {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    log::warn!("Invalid search regex {:?}: {}", self.needle, e);
+                    None
+                },
+            }
+        } else {
+            None
+        };
+    }
+
+    pub fn has_invalid_regex(&self) -> bool {
+        self.regex_search && !self.needle.is_empty() && self.regex.is_none()
+    }
+
+    /// Search a row and its tail with the current search-mode, returning every hit
+    /// together with the capture groups it contains.
+    fn find_in_row(
+        &self,
+        ridx: usize,
+        row: &Row,
+        tail: &[&Row],
+    ) -> Vec<(Selection, Vec<Selection>)> {
+        if self.regex_search {
+            self.regex_search_row(ridx, row, tail)
+        } else {
+            self.search_row(ridx, row, tail)
+                .into_iter()
+                .map(|s| (s, vec![]))
+                .collect()
+        }
+    }
+
+    /// Search a row and its tail with the compiled regex.
+    /// The row and its tail are joined into one haystack, so that a match can span wrapped lines.
+    /// Only matches starting in `row` itself are returned, the ones starting in the tail are
+    /// found when the tail-lines themselves get searched.
+    pub(crate) fn regex_search_row(
+        &self,
+        ridx: usize,
+        row: &Row,
+        tail: &[&Row],
+    ) -> Vec<(Selection, Vec<Selection>)> {
+        let mut res = Vec::new();
+        let regex = match self.regex.as_ref() {
+            Some(regex) => regex,
+            None => return res,
+        };
+        if row.columns.is_empty() {
+            return res;
+        }
+
+        // Remember for every char of the haystack its byte offset and where it lives in the grid
+        let mut haystack = String::new();
+        let mut positions: Vec<(usize, Position)> = Vec::new();
+        let mut main_row_end = 0;
+        for (line_offset, current_row) in
+            std::iter::once(row).chain(tail.iter().copied()).enumerate()
+        {
+            for (cidx, terminal_character) in current_row.columns.iter().enumerate() {
+                positions.push((
+                    haystack.len(),
+                    Position::new((ridx + line_offset) as i32, cidx as u16),
+                ));
+                haystack.push(terminal_character.character);
+            }
+            if line_offset == 0 {
+                main_row_end = haystack.len();
+            }
+        }
+
+        let char_index = |byte_offset: usize| {
+            positions
+                .binary_search_by_key(&byte_offset, |(offset, _)| *offset)
+                .unwrap_or_else(|idx| idx)
+        };
+        // Turns a byte-range of the haystack into a selection of the grid
+        let to_selection = |start: usize, end: usize| {
+            let start_position = positions[char_index(start)].1;
+            let last_position = positions[char_index(end) - 1].1;
+            let mut selection = Selection::default();
+            selection.start(start_position);
+            selection.end(Position::new(
+                last_position.line() as i32,
+                (last_position.column() + 1) as u16,
+            ));
+            selection
+        };
+
+        for captures in regex.captures_iter(&haystack) {
+            let whole_match = match captures.get(0) {
+                Some(whole_match) => whole_match,
+                None => continue,
+            };
+            if whole_match.start() >= main_row_end {
+                // Everything from here on starts in the tail
+                break;
+            }
+            if whole_match.is_empty() {
+                // Nothing to highlight
+                continue;
+            }
+            let capture_groups = captures
+                .iter()
+                .skip(1)
+                .flatten()
+                .filter(|group| !group.is_empty())
+                .map(|group| to_selection(group.start(), group.end()))
+                .collect();
+            res.push((
+                to_selection(whole_match.start(), whole_match.end()),
+                capture_groups,
+            ));
+        }
+
+        // Same as with the literal search, the tail may not have been wrapped yet
+        let width = row.width();
+        for (selection, capture_groups) in res.iter_mut() {
+            reflow_position(&mut selection.end, width);
+            for group in capture_groups.iter_mut() {
+                reflow_position(&mut group.start, width);
+                reflow_position(&mut group.end, width);
+            }
+        }
+        res
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -325,6 +506,7 @@ impl SearchResult {
         self.selections
             .iter_mut()
             .chain(self.active.iter_mut())
+            .chain(self.capture_groups.iter_mut())
             .for_each(|x| x.move_down(amount));
 
         // Throw out all search-results outside of the new viewport
@@ -342,9 +524,10 @@ impl SearchResult {
                         break;
                     }
                 }
-                let selections = self.search_row(0, row, &tail);
-                for selection in selections.iter().rev() {
-                    self.selections.insert(0, *selection);
+                let selections = self.find_in_row(0, row, &tail);
+                for (selection, capture_groups) in selections.into_iter().rev() {
+                    self.selections.insert(0, selection);
+                    self.capture_groups.extend(capture_groups);
                     found_something = true;
                 }
             }
@@ -363,6 +546,7 @@ impl SearchResult {
         self.selections
             .iter_mut()
             .chain(self.active.iter_mut())
+            .chain(self.capture_groups.iter_mut())
             .for_each(|x| x.move_up(amount));
         // Throw out all search-results outside of the new viewport
         self.adjust_selections_to_moved_viewport(grid_height);
@@ -371,11 +555,12 @@ impl SearchResult {
         if !self.needle.is_empty() {
             if let Some(row) = viewport.back() {
                 let tail: Vec<&Row> = lines_below.iter().take_while(|r| !r.is_canonical).collect();
-                let selections = self.search_row(viewport.len() - 1, row, &tail);
-                for selection in selections {
+                let selections = self.find_in_row(viewport.len() - 1, row, &tail);
+                for (selection, capture_groups) in selections {
                     // We are only interested in results that start in the this new row
                     if selection.start.line() as usize == viewport.len() - 1 {
                         self.selections.push(selection);
+                        self.capture_groups.extend(capture_groups);
                         found_something = true;
                     }
                 }
@@ -388,6 +573,8 @@ impl SearchResult {
         // Throw out all search-results outside of the new viewport
         self.selections
             .retain(|s| (s.start.line() as usize) < grid_height && s.end.line() >= 0);
+        self.capture_groups
+            .retain(|s| (s.start.line() as usize) < grid_height && s.end.line() >= 0);
         // If we have thrown out the active element, set it to None
         self.unset_active_selection_if_nonexistent();
     }
@@ -413,6 +600,7 @@ impl Grid {
 
     pub fn set_search_string(&mut self, needle: &str) {
         self.search_results.needle = needle.to_string();
+        self.search_results.update_regex();
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
@@ -440,25 +628,28 @@ impl Grid {
                     break;
                 }
             }
-            let selections = self.search_results.search_row(ridx, row, &tail);
-            for sel in &selections {
+            let selections = self.search_results.find_in_row(ridx, row, &tail);
+            for (sel, _capture_groups) in &selections {
                 // Cast works because we can' be negative here
                 self.output_buffer
                     .update_lines(sel.start.line() as usize, sel.end.line() as usize);
             }
 
-            for selection in selections {
+            for (selection, capture_groups) in selections {
                 self.search_results.selections.push(selection);
+                self.search_results.capture_groups.extend(capture_groups);
             }
         }
     }
 
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
         }
+        self.search_results.capture_groups.clear();
         self.search_viewport();
         // Maybe the selection we had is now gone
         self.search_results.unset_active_selection_if_nonexistent();
@@ -470,16 +661,30 @@ impl Grid {
 
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
         }
+        self.search_results.capture_groups.clear();
         self.search_results.active = None;
         self.search_viewport();
         // Maybe the selection we had is now gone
         self.search_results.unset_active_selection_if_nonexistent();
     }
 
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex_search = !self.search_results.regex_search;
+        self.search_results.update_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.capture_groups.clear();
+        self.search_results.active = None;
+        self.search_viewport();
+    }
+
     fn search_scrollbuffer(&mut self, dir: SearchDirection) {
         let first_sel = self.search_results.selections.first();
         let last_sel = self.search_results.selections.last();
@@ -7,6 +7,8 @@ use crate::panes::LinkHandler;
 use crate::panes::{
     grid::{Grid, PendingNotification},
     nested_session_modal::GuestModalShortcuts,
+    output_log::BackgroundOutputLog,
+    pane_monitor::PaneMonitor,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
 use crate::pty::VteBytes;
@@ -16,20 +18,22 @@ use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::time::{self, Instant};
+use std::time::{self, Duration, Instant};
 use vte;
 use zellij_utils::data::PaneContents;
+use zellij_utils::input::actions::{CopyModeMotion, CopyModeSelection};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::mouse::{MouseEvent, MouseEventType};
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
     data::{
         BareKey, InputMode, KeyWithModifier, Palette, PaletteColor, PaneId as ZellijUtilsPaneId,
-        RegexHighlight, Style, Styling,
+        PaneMonitorAlert, PaneMonitors, RegexHighlight, Style, Styling,
     },
     errors::prelude::*,
-    input::layout::Run,
+    input::layout::{LogOutput, PaneDependency, Run},
     nested_session::NestedSessionMessage,
     pane_size::PaneGeom,
     pane_size::SizeInPixels,
@@ -41,6 +45,12 @@ use crate::ui::pane_boundaries_frame::{FrameParams, PaneFrame};
 
 pub const SELECTION_SCROLL_INTERVAL_MS: u64 = 10;
 
+// the wait before a command pane is restarted by its restart policy doubles with every restart in
+// a row, up to the maximum - a command that stays up for longer than that no longer counts as
+// restarting in a row
+const RESTART_BACKOFF_INITIAL_MS: u64 = 1000;
+const RESTART_BACKOFF_MAX_MS: u64 = 30000;
+
 // Some keys in different formats but are used in the code
 const LEFT_ARROW: &[u8] = &[27, 91, 68];
 const RIGHT_ARROW: &[u8] = &[27, 91, 67];
@@ -177,6 +187,15 @@ pub struct TerminalPane {
     /// has been written.
     pending_pty_input: VecDeque<u8>,
     kitty_interceptor: KittyApcInterceptor,
+    output_log: Option<BackgroundOutputLog>,
+    monitor: Option<PaneMonitor>,
+    broadcast_group: Option<String>,
+    restart_count: usize, // restarts in a row by the restart policy, reset when re-run manually
+    last_started: Instant,
+    pending_restart: Option<u64>,
+    last_restart_id: u64,
+    pane_dependencies: Vec<PaneDependency>, // until they are handed over to the tab
+    waiting_for: Option<String>,
 }
 
 impl Pane for TerminalPane {
@@ -551,9 +570,15 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex_search {
+                    modifiers.push("r")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
+            if self.grid.search_results.has_invalid_regex() {
+                modifier_text.push_str(" (invalid regex)");
+            }
             format!("SEARCHING: {}{}", self.search_term, modifier_text)
         } else {
             self.current_title()
@@ -588,6 +613,8 @@ impl Pane for TerminalPane {
                 frame.add_exit_status(exit_status.as_ref().copied());
             }
         }
+        frame.add_restart_count(self.restart_count);
+        frame.add_waiting_for(self.waiting_for.clone());
         if let Some((frame_color_override, _text)) = self.pane_frame_color_override.as_ref() {
             frame.override_color(*frame_color_override);
         }
@@ -1081,10 +1108,33 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
     }
+    fn enter_copy_mode(&mut self) {
+        self.grid.enter_copy_mode();
+        self.set_should_render(true);
+    }
+    fn exit_copy_mode(&mut self) {
+        self.grid.exit_copy_mode();
+        self.set_should_render(true);
+    }
+    fn copy_mode_move(&mut self, motion: CopyModeMotion) {
+        self.grid.copy_mode_move(motion);
+        self.set_should_render(true);
+    }
+    fn copy_mode_select(&mut self, selection: CopyModeSelection) {
+        self.grid.copy_mode_select(selection);
+        self.set_should_render(true);
+    }
+    fn copy_mode_selected_text(&self) -> Option<String> {
+        self.grid.copy_mode_selected_text()
+    }
     fn is_alternate_mode_active(&self) -> bool {
         self.grid.is_alternate_mode_active()
     }
@@ -1195,18 +1245,130 @@ impl Pane for TerminalPane {
     fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         self.grid.serialize(scrollback_lines_to_serialize)
     }
+    fn scrollback_snapshot(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<Vec<u8>> {
+        self.grid.scrollback_snapshot(scrollback_lines_to_serialize)
+    }
+    fn start_output_log(&mut self, log_output: LogOutput) -> Result<()> {
+        self.output_log = Some(
+            BackgroundOutputLog::start(log_output)
+                .with_context(|| format!("failed to log output of pane {}", self.pid))?,
+        );
+        Ok(())
+    }
+    fn stop_output_log(&mut self) -> Option<PathBuf> {
+        self.output_log
+            .take()
+            .map(|output_log| output_log.path().to_path_buf())
+    }
+    fn output_log(&self) -> Option<LogOutput> {
+        self.output_log
+            .as_ref()
+            .map(|output_log| output_log.log_output().clone())
+    }
+    fn received_pty_bytes(&mut self, bytes: &[u8]) {
+        if let Some(output_log) = self.output_log.as_mut() {
+            if let Err(e) = output_log.write(bytes) {
+                // stop logging rather than reporting the same error for every chunk of output
+                log::error!("{:?}", e);
+                self.output_log = None;
+            }
+        }
+        if let Some(monitor) = self.monitor.as_mut() {
+            monitor.output(Instant::now());
+        }
+    }
+    fn set_monitors(&mut self, monitors: PaneMonitors) {
+        self.monitor = if monitors.is_empty() {
+            None
+        } else {
+            Some(PaneMonitor::new(monitors, Instant::now()))
+        };
+    }
+    fn monitors(&self) -> Option<PaneMonitors> {
+        self.monitor.as_ref().map(|monitor| monitor.monitors())
+    }
+    fn set_broadcast_group(&mut self, broadcast_group: Option<String>) {
+        self.broadcast_group = broadcast_group;
+    }
+    fn broadcast_group(&self) -> Option<String> {
+        self.broadcast_group.clone()
+    }
+    fn set_pane_dependencies(&mut self, dependencies: Vec<PaneDependency>) {
+        self.pane_dependencies = dependencies;
+    }
+    fn take_pane_dependencies(&mut self) -> Vec<PaneDependency> {
+        std::mem::take(&mut self.pane_dependencies)
+    }
+    fn set_waiting_for(&mut self, waiting_for: Option<String>) {
+        if self.waiting_for != waiting_for {
+            self.waiting_for = waiting_for;
+            self.set_should_render(true);
+        }
+    }
+    fn check_monitors(&mut self, now: Instant) -> Vec<PaneMonitorAlert> {
+        let alerts = self
+            .monitor
+            .as_mut()
+            .map(|monitor| monitor.check(now))
+            .unwrap_or_default();
+        if !alerts.is_empty() {
+            // alerts are indicated like a bell in the pane, so they get the same pane frame and
+            // tab bar treatment
+            self.grid.ring_bell = true;
+        }
+        alerts
+    }
     fn rerun(&mut self) -> Option<RunCommand> {
         // if this is a command pane that has exited or is waiting to be rerun, will return its
         // RunCommand, otherwise it is safe to assume this is not the right sort of pane or that it
         // is not in the right sort of state
         self.is_held.take().map(|(_, _, run_command)| {
             self.is_held = None;
+            self.reset_restarts();
             self.grid.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
             run_command.clone()
         })
     }
+    fn schedule_restart(&mut self) -> Option<(u64, Duration)> {
+        let (exit_status, is_first_run, run_command) = self.is_held.as_ref()?;
+        if *is_first_run || !run_command.restart.should_restart(*exit_status) {
+            return None;
+        }
+        if self.last_started.elapsed() >= Duration::from_millis(RESTART_BACKOFF_MAX_MS) {
+            self.restart_count = 0;
+        }
+        if let Some(max_restarts) = run_command.max_restarts {
+            if self.restart_count >= max_restarts {
+                return None;
+            }
+        }
+        self.last_restart_id += 1;
+        self.pending_restart = Some(self.last_restart_id);
+        let backoff_ms = RESTART_BACKOFF_INITIAL_MS
+            .saturating_mul(2_u64.saturating_pow(self.restart_count as u32))
+            .min(RESTART_BACKOFF_MAX_MS);
+        Some((self.last_restart_id, Duration::from_millis(backoff_ms)))
+    }
+    fn restart(&mut self, restart_id: u64) -> Option<RunCommand> {
+        // the restart might have been pre-empted (eg. the user re-ran the command themselves)
+        if self.pending_restart != Some(restart_id) {
+            return None;
+        }
+        self.pending_restart = None;
+        self.is_held.take().map(|(_, _, run_command)| {
+            self.restart_count += 1;
+            self.last_started = Instant::now();
+            self.grid.reset_terminal_state();
+            self.set_should_render(true);
+            self.remove_banner();
+            run_command
+        })
+    }
+    fn restart_count(&self) -> usize {
+        self.restart_count
+    }
     fn update_theme(&mut self, theme: Styling) {
         self.style.colors = theme.clone();
         self.grid.update_theme(theme);
@@ -1433,8 +1595,24 @@ impl TerminalPane {
             guest_modal_shortcuts: GuestModalShortcuts::default(),
             pending_pty_input: VecDeque::new(),
             kitty_interceptor: KittyApcInterceptor::new(),
+            output_log: None,
+            monitor: None,
+            broadcast_group: None,
+            restart_count: 0,
+            last_started: Instant::now(),
+            pending_restart: None,
+            last_restart_id: 0,
+            pane_dependencies: vec![],
+            waiting_for: None,
         }
     }
+    pub fn restore_scrollback_snapshot(&mut self, snapshot: &[u8]) -> Result<()> {
+        self.grid
+            .restore_scrollback_snapshot(snapshot)
+            .with_context(|| format!("failed to restore scrollback of pane {}", self.pid))?;
+        self.set_should_render(true);
+        Ok(())
+    }
     pub fn get_x(&self) -> usize {
         match self.geom_override {
             Some(position_and_size_override) => position_and_size_override.x,
@@ -1492,6 +1670,11 @@ impl TerminalPane {
         self.banner = Some(banner.clone());
         self.handle_pty_bytes(banner.as_bytes().to_vec());
     }
+    fn reset_restarts(&mut self) {
+        self.restart_count = 0;
+        self.last_started = Instant::now();
+        self.pending_restart = None;
+    }
     fn remove_banner(&mut self) {
         if self.banner.is_some() {
             self.grid.reset_terminal_state();
@@ -1607,6 +1790,7 @@ impl TerminalPane {
     fn handle_held_run(&mut self) -> Option<AdjustedInput> {
         self.is_held.take().map(|(_, _, run_command)| {
             self.is_held = None;
+            self.reset_restarts();
             self.grid.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
@@ -1618,6 +1802,7 @@ impl TerminalPane {
             // Drop to shell in the same working directory as the command was run
             let working_dir = run_command.cwd.clone();
             self.is_held = None;
+            self.reset_restarts();
             self.grid.reset_terminal_state();
             self.set_should_render(true);
             self.remove_banner();
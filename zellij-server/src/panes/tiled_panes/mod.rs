@@ -1129,6 +1129,7 @@ impl TiledPanes {
         floating_panes_are_visible: bool,
         mouse_hover_pane_id: &HashMap<ClientId, PaneId>,
         current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+        input_synced_clients: &HashSet<ClientId>,
         client_id_override: Option<ClientId>,
         help_text_visible: &HashMap<ClientId, bool>,
         mouse_scroll_resize: bool,
@@ -1261,6 +1262,7 @@ impl TiledPanes {
                     mouse_hover_tips,
                     self.dimmed_clients.clone(),
                 );
+                pane_contents_and_ui.set_input_synced_clients(input_synced_clients.clone());
                 pane_contents_and_ui.set_frame_geom_override(visible_member_frame_override);
                 pane_contents_and_ui.set_blank_title(reserved_rows_for_pane > 0);
                 for client_id in &connected_clients {
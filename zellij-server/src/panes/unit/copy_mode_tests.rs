@@ -0,0 +1,158 @@
+use super::super::Grid;
+use crate::panes::kitty_graphics::KittyImageStore;
+use crate::panes::link_handler::LinkHandler;
+use crate::panes::sixel::SixelImageStore;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use vte;
+use zellij_utils::{
+    data::{Palette, Style},
+    input::actions::{CopyModeMotion, CopyModeSelection},
+    position::Position,
+};
+
+fn create_grid_with_content(rows: usize, columns: usize, content: &str) -> Grid {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let mut grid = Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Rc::new(RefCell::new(KittyImageStore::default())),
+        Style::default(),
+        false,
+        true,
+        true,
+        true,
+        false,
+    );
+    vte_parser.advance(&mut grid, content.as_bytes());
+    grid
+}
+
+#[test]
+fn entering_copy_mode_starts_at_terminal_cursor() {
+    let mut grid = create_grid_with_content(4, 30, "first line\r\nsecond");
+    grid.enter_copy_mode();
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(1, 6)));
+    grid.exit_copy_mode();
+    assert_eq!(grid.copy_mode_cursor(), None);
+}
+
+#[test]
+fn copy_mode_word_motions() {
+    let mut grid = create_grid_with_content(4, 30, "foo bar(baz  qux\r\n");
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::Up);
+    grid.copy_mode_move(CopyModeMotion::LineStart);
+    grid.copy_mode_move(CopyModeMotion::WordForward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 4)));
+    grid.copy_mode_move(CopyModeMotion::WordForward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 7)));
+    grid.copy_mode_move(CopyModeMotion::WordEnd);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 10)));
+    grid.copy_mode_move(CopyModeMotion::WordBackward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 8)));
+    grid.copy_mode_move(CopyModeMotion::LineEnd);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 15)));
+}
+
+#[test]
+fn copy_mode_moving_past_the_top_scrolls_into_the_scrollback() {
+    let mut grid =
+        create_grid_with_content(3, 30, "line 1\r\nline 2\r\nline 3\r\nline 4\r\nline 5");
+    grid.enter_copy_mode();
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 6)));
+    grid.copy_mode_move(CopyModeMotion::Top);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 0)));
+    grid.copy_mode_select(CopyModeSelection::Line);
+    assert_eq!(grid.copy_mode_selected_text(), Some("line 1".to_owned()));
+    grid.copy_mode_move(CopyModeMotion::Bottom);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+    assert_eq!(
+        grid.copy_mode_selected_text(),
+        Some("line 1\nline 2\nline 3\nline 4\nline 5".to_owned())
+    );
+}
+
+#[test]
+fn copy_mode_character_selection() {
+    let mut grid = create_grid_with_content(4, 30, "hello world\r\nsecond line");
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::Up);
+    grid.copy_mode_move(CopyModeMotion::LineStart);
+    grid.copy_mode_move(CopyModeMotion::WordForward);
+    grid.copy_mode_select(CopyModeSelection::Character);
+    grid.copy_mode_move(CopyModeMotion::Down);
+    grid.copy_mode_move(CopyModeMotion::WordBackward);
+    grid.copy_mode_move(CopyModeMotion::WordEnd);
+    assert_eq!(
+        grid.copy_mode_selected_text(),
+        Some("world\nsecond".to_owned())
+    );
+    // selecting the same type again clears the selection
+    grid.copy_mode_select(CopyModeSelection::Character);
+    assert_eq!(grid.copy_mode_selected_text(), None);
+}
+
+#[test]
+fn copy_mode_block_selection() {
+    let mut grid = create_grid_with_content(4, 30, "abcdef\r\nghijkl\r\nmnopqr");
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::Top);
+    grid.copy_mode_move(CopyModeMotion::Right);
+    grid.copy_mode_select(CopyModeSelection::Block);
+    grid.copy_mode_move(CopyModeMotion::Down);
+    grid.copy_mode_move(CopyModeMotion::Down);
+    grid.copy_mode_move(CopyModeMotion::Right);
+    grid.copy_mode_move(CopyModeMotion::Right);
+    assert_eq!(
+        grid.copy_mode_selected_text(),
+        Some("bcd\nhij\nnop".to_owned())
+    );
+}
+
+#[test]
+fn copy_mode_paragraph_motions() {
+    let mut grid = create_grid_with_content(6, 30, "one\r\ntwo\r\n\r\nthree\r\nfour");
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::ParagraphBackward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+    grid.copy_mode_move(CopyModeMotion::ParagraphBackward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 0)));
+    grid.copy_mode_move(CopyModeMotion::ParagraphForward);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+}
+
+#[test]
+fn copy_mode_prompt_motions() {
+    let mut grid = create_grid_with_content(
+        6,
+        30,
+        "\x1b]133;A\x07$ \x1b]133;B\x07echo hi\r\n\x1b]133;C\x07hi\r\n\x1b]133;A\x07$ \x1b]133;B\x07ls\r\nfile\r\n",
+    );
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::PreviousPrompt);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+    grid.copy_mode_move(CopyModeMotion::PreviousPrompt);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 0)));
+    grid.copy_mode_move(CopyModeMotion::NextPrompt);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+}
+
+#[test]
+fn copy_mode_jumps_to_search_results() {
+    let mut grid = create_grid_with_content(4, 30, "needle one\r\nhay\r\nmore needle\r\n");
+    grid.set_search_string("needle");
+    grid.enter_copy_mode();
+    grid.copy_mode_move(CopyModeMotion::PreviousSearchResult);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(2, 5)));
+    grid.copy_mode_move(CopyModeMotion::PreviousSearchResult);
+    assert_eq!(grid.copy_mode_cursor(), Some(Position::new(0, 0)));
+}
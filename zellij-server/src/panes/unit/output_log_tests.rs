@@ -0,0 +1,162 @@
+use super::{rotated_path, BackgroundOutputLog, PaneOutputLog};
+use std::fs;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+use zellij_utils::input::layout::LogOutput;
+
+#[test]
+fn raw_output_is_appended_as_is() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    fs::write(&path, "existing\n").unwrap();
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"\x1b[31mred\x1b[m\r\n").unwrap();
+    log.write(b"more").unwrap();
+    assert_eq!(
+        fs::read(&path).unwrap(),
+        b"existing\n\x1b[31mred\x1b[m\r\nmore".to_vec()
+    );
+}
+
+#[test]
+fn missing_parent_folders_are_created() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("logs").join("ci").join("pane.log");
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"hi").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "hi");
+}
+
+#[test]
+fn ansi_sequences_are_stripped() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        strip_ansi: true,
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"\x1b[1;32mok\x1b[0m\tdone\r\n\x1b]0;title\x07")
+        .unwrap();
+    // a sequence split across two reads is still stripped
+    log.write(b"\x1b[3").unwrap();
+    log.write(b"4mnext\x1b[m\r\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "ok\tdone\nnext\n");
+}
+
+#[test]
+fn each_line_is_prefixed_with_a_timestamp() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        strip_ansi: true,
+        timestamps: true,
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"first line\r\nsecond ").unwrap();
+    log.write(b"line\r\n").unwrap();
+    let contents = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2);
+    for (line, expected) in lines.iter().zip(["first line", "second line"]) {
+        // eg. [2024-01-31 13:37:00.123] first line
+        assert!(line.starts_with('['), "line: {:?}", line);
+        let (timestamp, text) = line.split_once("] ").unwrap();
+        assert_eq!(timestamp.len(), "[2024-01-31 13:37:00.123".len());
+        assert_eq!(text, expected);
+    }
+}
+
+#[test]
+fn log_is_rotated_when_it_grows_beyond_max_size() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        max_size: Some(10),
+        max_files: Some(2),
+        ..Default::default()
+    })
+    .unwrap();
+    for chunk in ["aaaaaaaa", "bbbbbbbb", "cccccccc", "dddddddd"] {
+        log.write(chunk.as_bytes()).unwrap();
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), "dddddddd");
+    assert_eq!(
+        fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+        "cccccccc"
+    );
+    assert_eq!(
+        fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+        "bbbbbbbb"
+    );
+    assert!(!rotated_path(&path, 3).exists());
+}
+
+#[test]
+fn rotation_takes_the_existing_file_size_into_account() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    fs::write(&path, "123456789").unwrap();
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        max_size: Some(10),
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"abc").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "abc");
+    assert_eq!(
+        fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+        "123456789"
+    );
+}
+
+#[test]
+fn no_rotated_files_are_kept_when_max_files_is_zero() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    let mut log = PaneOutputLog::open(LogOutput {
+        path: path.clone(),
+        max_size: Some(4),
+        max_files: Some(0),
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"abc").unwrap();
+    log.write(b"def").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "def");
+    assert!(!rotated_path(&path, 1).exists());
+}
+
+#[test]
+fn background_log_writes_from_its_own_thread() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("pane.log");
+    let mut log = BackgroundOutputLog::start(LogOutput {
+        path: path.clone(),
+        ..Default::default()
+    })
+    .unwrap();
+    log.write(b"first ").unwrap();
+    log.write(b"second").unwrap();
+    let started_waiting = Instant::now();
+    while fs::read_to_string(&path).unwrap() != "first second" {
+        assert!(
+            started_waiting.elapsed() < Duration::from_secs(5),
+            "output was not written in time"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,74 @@
+use super::{PaneMonitor, ACTIVITY_QUIET_PERIOD};
+use std::time::{Duration, Instant};
+use zellij_utils::data::{PaneMonitorAlert, PaneMonitors};
+
+fn activity_monitor() -> PaneMonitors {
+    PaneMonitors {
+        activity: true,
+        silence_secs: None,
+    }
+}
+
+fn silence_monitor(silence_secs: u64) -> PaneMonitors {
+    PaneMonitors {
+        activity: false,
+        silence_secs: Some(silence_secs),
+    }
+}
+
+#[test]
+fn output_right_after_monitoring_starts_is_not_activity() {
+    let start = Instant::now();
+    let mut monitor = PaneMonitor::new(activity_monitor(), start);
+    monitor.output(start + Duration::from_secs(1));
+    assert_eq!(monitor.check(start + Duration::from_secs(2)), vec![]);
+}
+
+#[test]
+fn output_after_a_quiet_period_is_reported_once() {
+    let start = Instant::now();
+    let mut monitor = PaneMonitor::new(activity_monitor(), start);
+    let active_at = start + ACTIVITY_QUIET_PERIOD;
+    monitor.output(active_at);
+    monitor.output(active_at + Duration::from_millis(100));
+    assert_eq!(
+        monitor.check(active_at + Duration::from_secs(1)),
+        vec![PaneMonitorAlert::Activity]
+    );
+    // continuous output does not alert again
+    monitor.output(active_at + Duration::from_secs(2));
+    assert_eq!(monitor.check(active_at + Duration::from_secs(3)), vec![]);
+    // but output after another quiet period does
+    let active_again_at = active_at + Duration::from_secs(2) + ACTIVITY_QUIET_PERIOD;
+    monitor.output(active_again_at);
+    assert_eq!(
+        monitor.check(active_again_at),
+        vec![PaneMonitorAlert::Activity]
+    );
+}
+
+#[test]
+fn silence_is_reported_once_per_silent_stretch() {
+    let start = Instant::now();
+    let mut monitor = PaneMonitor::new(silence_monitor(5), start);
+    assert_eq!(monitor.check(start + Duration::from_secs(4)), vec![]);
+    assert_eq!(
+        monitor.check(start + Duration::from_secs(5)),
+        vec![PaneMonitorAlert::Silence(5)]
+    );
+    assert_eq!(monitor.check(start + Duration::from_secs(20)), vec![]);
+    monitor.output(start + Duration::from_secs(21));
+    assert_eq!(monitor.check(start + Duration::from_secs(25)), vec![]);
+    assert_eq!(
+        monitor.check(start + Duration::from_secs(26)),
+        vec![PaneMonitorAlert::Silence(5)]
+    );
+}
+
+#[test]
+fn activity_is_not_reported_when_only_silence_is_monitored() {
+    let start = Instant::now();
+    let mut monitor = PaneMonitor::new(silence_monitor(60), start);
+    monitor.output(start + ACTIVITY_QUIET_PERIOD * 2);
+    assert_eq!(monitor.check(start + ACTIVITY_QUIET_PERIOD * 2), vec![]);
+}
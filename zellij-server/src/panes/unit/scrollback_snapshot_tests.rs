@@ -0,0 +1,179 @@
+use super::super::Grid;
+use super::decode_scrollback_snapshot;
+use crate::panes::kitty_graphics::KittyImageStore;
+use crate::panes::link_handler::{Link, LinkHandler};
+use crate::panes::sixel::SixelImageStore;
+use crate::panes::terminal_character::LinkAnchor;
+use insta::assert_snapshot;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use vte;
+use zellij_utils::{
+    data::{Palette, Style},
+    input::actions::CopyModeMotion,
+    position::Position,
+};
+
+fn create_grid(rows: usize, columns: usize, link_handler: Rc<RefCell<LinkHandler>>) -> Grid {
+    Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        Rc::new(RefCell::new(HashMap::new())),
+        link_handler,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(SixelImageStore::default())),
+        Rc::new(RefCell::new(KittyImageStore::default())),
+        Style::default(),
+        false,
+        true,
+        true,
+        true,
+        false,
+    )
+}
+
+fn create_grid_with_content(rows: usize, columns: usize, content: &str) -> Grid {
+    let mut vte_parser = vte::Parser::new();
+    let mut grid = create_grid(rows, columns, Rc::new(RefCell::new(LinkHandler::new())));
+    vte_parser.advance(&mut grid, content.as_bytes());
+    grid
+}
+
+#[test]
+fn scrollback_snapshot_round_trips_styled_scrollback() {
+    let mut content = String::new();
+    for i in 0..20 {
+        content.push_str(&format!(
+            "\u{1b}[1;31mline {}\u{1b}[0m \u{1b}[38;2;10;20;30;48;5;200mstyled\u{1b}[m\r\n",
+            i
+        ));
+    }
+    let grid = create_grid_with_content(5, 30, &content);
+    let snapshot = grid.scrollback_snapshot(None).unwrap();
+
+    let mut restored_grid = create_grid(5, 30, Rc::new(RefCell::new(LinkHandler::new())));
+    restored_grid
+        .restore_scrollback_snapshot(&snapshot)
+        .unwrap();
+    assert_eq!(
+        restored_grid.serialize(Some(0)),
+        grid.serialize(Some(0)),
+        "restored grid has the same styled contents"
+    );
+    assert_eq!(
+        restored_grid.scrollback_position_and_length(),
+        grid.scrollback_position_and_length(),
+        "restored grid has the same scrollback"
+    );
+}
+
+#[test]
+fn scrollback_snapshot_respects_scrollback_lines_to_serialize() {
+    let mut content = String::new();
+    for i in 0..20 {
+        content.push_str(&format!("line {}\r\n", i));
+    }
+    let grid = create_grid_with_content(5, 30, &content);
+    let snapshot = grid.scrollback_snapshot(Some(3)).unwrap();
+
+    let mut restored_grid = create_grid(5, 30, Rc::new(RefCell::new(LinkHandler::new())));
+    restored_grid
+        .restore_scrollback_snapshot(&snapshot)
+        .unwrap();
+    assert_snapshot!(restored_grid.serialize(Some(0)).unwrap());
+}
+
+#[test]
+fn scrollback_snapshot_preserves_hyperlinks() {
+    let grid = create_grid_with_content(
+        5,
+        30,
+        "\u{1b}]8;;https://zellij.dev\u{1b}\\zellij\u{1b}]8;;\u{1b}\\ link\r\n",
+    );
+    let snapshot = grid.scrollback_snapshot(None).unwrap();
+
+    // a link handler that already has a link, so that the restored one has to be remapped
+    let mut link_handler = LinkHandler::new();
+    link_handler.add_link(Link {
+        id: None,
+        uri: "https://example.com".to_owned(),
+    });
+    let lines = decode_scrollback_snapshot(&snapshot, &mut link_handler).unwrap();
+    let link_anchor = lines[0].columns[0].styles.link_anchor;
+    assert_eq!(link_anchor, Some(LinkAnchor::Start(1)));
+    assert_eq!(
+        link_handler.get_link(1).map(|link| link.uri.as_str()),
+        Some("https://zellij.dev")
+    );
+    assert_eq!(
+        lines[0].columns[6].styles.link_anchor,
+        Some(LinkAnchor::End)
+    );
+}
+
+#[test]
+fn scrollback_snapshot_preserves_prompt_marks() {
+    let grid = create_grid_with_content(
+        6,
+        30,
+        "\x1b]133;A\x07$ \x1b]133;B\x07echo hi\r\n\x1b]133;C\x07hi\r\n\x1b]133;A\x07$ \x1b]133;B\x07ls\r\nfile\r\n",
+    );
+    let snapshot = grid.scrollback_snapshot(None).unwrap();
+
+    let mut restored_grid = create_grid(6, 30, Rc::new(RefCell::new(LinkHandler::new())));
+    restored_grid
+        .restore_scrollback_snapshot(&snapshot)
+        .unwrap();
+    restored_grid.enter_copy_mode();
+    restored_grid.copy_mode_move(CopyModeMotion::PreviousPrompt);
+    assert_eq!(restored_grid.copy_mode_cursor(), Some(Position::new(2, 0)));
+    restored_grid.copy_mode_move(CopyModeMotion::PreviousPrompt);
+    assert_eq!(restored_grid.copy_mode_cursor(), Some(Position::new(0, 0)));
+}
+
+#[test]
+fn restored_scrollback_snapshot_is_reflowed_to_the_pane_width() {
+    let grid = create_grid_with_content(
+        5,
+        40,
+        "a line that is longer than the new pane\r\nshort\r\n",
+    );
+    let snapshot = grid.scrollback_snapshot(None).unwrap();
+
+    let mut restored_grid = create_grid(5, 10, Rc::new(RefCell::new(LinkHandler::new())));
+    restored_grid
+        .restore_scrollback_snapshot(&snapshot)
+        .unwrap();
+    assert_snapshot!(format!("{:?}", restored_grid));
+}
+
+#[test]
+fn restoring_an_invalid_scrollback_snapshot_fails() {
+    let mut grid = create_grid(5, 30, Rc::new(RefCell::new(LinkHandler::new())));
+    assert!(grid.restore_scrollback_snapshot(b"not a snapshot").is_err());
+    assert!(grid
+        .restore_scrollback_snapshot(b"ZJSB\x01garbage")
+        .is_err());
+}
+
+#[test]
+fn restoring_a_scrollback_snapshot_with_corrupt_counts_fails() {
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::Write;
+    let snapshot_with_body = |body: &[u8]| {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        let mut snapshot = b"ZJSB\x01".to_vec();
+        snapshot.extend_from_slice(&encoder.finish().unwrap());
+        snapshot
+    };
+    let mut grid = create_grid(5, 30, Rc::new(RefCell::new(LinkHandler::new())));
+    // no links, then u32::MAX styles
+    let too_many_styles = snapshot_with_body(&[0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+    assert!(grid.restore_scrollback_snapshot(&too_many_styles).is_err());
+    // a single link whose uri is u32::MAX bytes long
+    let uri_too_long = snapshot_with_body(&[1, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+    assert!(grid.restore_scrollback_snapshot(&uri_too_long).is_err());
+}
@@ -426,3 +426,68 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn searching_with_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("t(o)rt(or)");
+    assert_snapshot!(
+        "grid_copy_regex_as_literal_not_found",
+        format!("{:?}", terminal_pane.grid)
+    );
+
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_regex_capture_groups_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+
+    terminal_pane.search_up();
+    assert_snapshot!(
+        "grid_copy_regex_cursor_at_bottom",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_across_line_wrap() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term(r"aliquam\s+sem\s+\w+");
+    terminal_pane.toggle_search_regex();
+    // Spread across 4 lines
+    terminal_pane.grid.change_size(40, 4);
+    assert_snapshot!(
+        "grid_copy_multiline_highlighted_narrow",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_regex_case_insensitive_and_whole_word() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("qua[m]");
+    terminal_pane.toggle_search_regex();
+    terminal_pane.toggle_search_whole_words();
+    assert_snapshot!(
+        "grid_copy_quam_whole_word_only",
+        format!("{:?}", terminal_pane.grid)
+    );
+
+    terminal_pane.toggle_search_case_sensitivity();
+    assert_snapshot!(
+        "grid_copy_quam_whole_word_case_insensitive",
+        format!("{:?}", terminal_pane.grid)
+    );
+}
+
+#[test]
+pub fn searching_with_invalid_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tortor(");
+    terminal_pane.toggle_search_regex();
+    assert!(terminal_pane.grid.search_results.has_invalid_regex());
+    assert!(terminal_pane.grid.search_results.selections.is_empty());
+
+    terminal_pane.toggle_search_regex();
+    assert!(!terminal_pane.grid.search_results.has_invalid_regex());
+}
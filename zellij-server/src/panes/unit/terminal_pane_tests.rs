@@ -1035,3 +1035,96 @@ pub fn guest_modal_no_modal_passes_input_through() {
         Some(crate::tab::AdjustedInput::WriteBytesToTerminal(_))
     ));
 }
+
+fn command_with_restart_policy(
+    restart: zellij_utils::input::command::RestartPolicy,
+    max_restarts: Option<usize>,
+) -> zellij_utils::input::command::RunCommand {
+    let mut run_command =
+        zellij_utils::input::command::RunCommand::new(std::path::PathBuf::from("./worker"));
+    run_command.restart = restart;
+    run_command.max_restarts = max_restarts;
+    run_command
+}
+
+#[test]
+pub fn restart_backs_off_until_max_restarts() {
+    use std::time::Duration;
+    use zellij_utils::input::command::RestartPolicy;
+    let mut pane = create_guest_modal_pane();
+    let run_command = command_with_restart_policy(RestartPolicy::OnFailure, Some(2));
+    let is_first_run = false;
+
+    pane.hold(Some(1), is_first_run, run_command.clone());
+    let (restart_id, delay) = pane.schedule_restart().unwrap();
+    assert_eq!(delay, Duration::from_secs(1));
+    assert_eq!(pane.restart(restart_id), Some(run_command.clone()));
+    assert_eq!(pane.restart_count(), 1);
+
+    pane.hold(Some(1), is_first_run, run_command.clone());
+    let (restart_id, delay) = pane.schedule_restart().unwrap();
+    assert_eq!(delay, Duration::from_secs(2));
+    assert!(pane.restart(restart_id).is_some());
+    assert_eq!(pane.restart_count(), 2);
+
+    pane.hold(Some(1), is_first_run, run_command);
+    assert_eq!(pane.schedule_restart(), None, "max restarts reached");
+    assert!(pane.is_held());
+}
+
+#[test]
+pub fn on_failure_restart_policy_ignores_successful_exit() {
+    use zellij_utils::input::command::RestartPolicy;
+    let mut pane = create_guest_modal_pane();
+    let is_first_run = false;
+    pane.hold(
+        Some(0),
+        is_first_run,
+        command_with_restart_policy(RestartPolicy::OnFailure, None),
+    );
+    assert_eq!(pane.schedule_restart(), None);
+    pane.hold(
+        Some(0),
+        is_first_run,
+        command_with_restart_policy(RestartPolicy::Always, None),
+    );
+    assert!(pane.schedule_restart().is_some());
+}
+
+#[test]
+pub fn manual_rerun_cancels_scheduled_restart_and_resets_count() {
+    use zellij_utils::input::command::RestartPolicy;
+    let mut pane = create_guest_modal_pane();
+    let run_command = command_with_restart_policy(RestartPolicy::Always, None);
+    let is_first_run = false;
+
+    pane.hold(Some(1), is_first_run, run_command.clone());
+    let (restart_id, _delay) = pane.schedule_restart().unwrap();
+    pane.restart(restart_id);
+    pane.hold(Some(1), is_first_run, run_command);
+    let (restart_id, _delay) = pane.schedule_restart().unwrap();
+    assert!(pane.rerun().is_some());
+    assert_eq!(pane.restart(restart_id), None, "restart was pre-empted");
+    assert_eq!(pane.restart_count(), 0);
+}
+
+#[test]
+pub fn restart_count_resets_once_the_command_ran_for_longer_than_the_max_backoff() {
+    use std::time::{Duration, Instant};
+    use zellij_utils::input::command::RestartPolicy;
+    let mut pane = create_guest_modal_pane();
+    let run_command = command_with_restart_policy(RestartPolicy::Always, Some(2));
+    let is_first_run = false;
+
+    for _ in 0..2 {
+        pane.hold(Some(1), is_first_run, run_command.clone());
+        let (restart_id, _delay) = pane.schedule_restart().unwrap();
+        pane.restart(restart_id);
+    }
+    pane.last_started = Instant::now() - Duration::from_secs(30);
+    pane.hold(Some(1), is_first_run, run_command);
+    let (restart_id, delay) = pane.schedule_restart().unwrap();
+    assert_eq!(delay, Duration::from_secs(1));
+    assert!(pane.restart(restart_id).is_some());
+    assert_eq!(pane.restart_count(), 1);
+}
@@ -2,7 +2,9 @@ mod pinned_executor;
 mod pipes;
 mod plugin_loader;
 mod plugin_map;
+mod plugin_storage;
 mod plugin_worker;
+mod read_only_dir;
 mod wasm_bridge;
 mod watch_filesystem;
 mod zellij_exports;
@@ -25,21 +27,26 @@ use crate::{pty::PtyInstruction, thread_bus::Bus, ClientId, ServerInstruction};
 use zellij_utils::data::PaneRenderReport;
 use zellij_utils::input::layout::TabLayoutInfo;
 
+use pipes::DEFAULT_PLUGIN_REQUEST_TIMEOUT;
 pub use wasm_bridge::PluginRenderAsset;
 use wasm_bridge::WasmBridge;
 
 use zellij_utils::{
     data::{
-        ClientInfo, CommandOrPlugin, Event, EventType, FloatingPaneCoordinates, InputMode,
-        LayoutInfo, LayoutWithError, MessageToPlugin, PermissionStatus, PermissionType,
-        PipeMessage, PipeSource, WebServerStatus,
+        ClientInfo, CommandOrPlugin, Event, EventType, FilesystemPermission,
+        FloatingPaneCoordinates, InputMode, LayoutInfo, LayoutWithError, MessageToPlugin,
+        PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginReply, StorageScope,
+        WebServerStatus,
     },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
         actions::Action,
         command::TerminalAction,
         keybinds::Keybinds,
-        layout::{FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginOrAlias, TiledPaneLayout},
+        layout::{
+            FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginLocation, RunPluginOrAlias,
+            TiledPaneLayout,
+        },
         plugins::PluginAliases,
     },
     pane_size::Size,
@@ -134,6 +141,7 @@ pub enum PluginInstruction {
         PluginId,
         Option<ClientId>,
         Vec<PermissionType>,
+        Vec<FilesystemPermission>,
         PermissionStatus,
         Option<PathBuf>,
     ),
@@ -181,6 +189,27 @@ pub enum PluginInstruction {
         source_plugin_id: u32,
         message: MessageToPlugin,
     },
+    PluginRequest {
+        source_plugin_id: u32,
+        source_client_id: ClientId,
+        message: MessageToPlugin,
+        timeout_ms: Option<u64>,
+        context: BTreeMap<String, String>,
+    },
+    PluginRequestReply {
+        replying_plugin_id: u32,
+        request_id: u64,
+        reply: PluginReply,
+    },
+    PluginRequestTimedOut(u64), // u64 - request_id
+    StorageValueChanged {
+        source_plugin_id: u32,
+        source_client_id: ClientId,
+        plugin_location: RunPluginLocation,
+        scope: StorageScope,
+        key: String,
+        value: Option<String>,
+    },
     UnblockCliPipes(Vec<PluginRenderAsset>),
     Reconfigure {
         client_id: ClientId,
@@ -194,6 +223,8 @@ pub enum PluginInstruction {
         file_path: Option<PathBuf>,
     },
     WatchFilesystem,
+    PluginFilesChanged(Vec<PathBuf>),
+    PermissionCacheChanged(Option<PathBuf>), // path to the cache, None for the default one
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
     ChangePluginHostDir(PathBuf, PluginId, ClientId),
     WebServerStarted(String), // String -> the base url of the web server
@@ -258,8 +289,14 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::CliPipe { .. } => PluginContext::CliPipe,
             PluginInstruction::CachePluginEvents { .. } => PluginContext::CachePluginEvents,
             PluginInstruction::MessageFromPlugin { .. } => PluginContext::MessageFromPlugin,
+            PluginInstruction::PluginRequest { .. } => PluginContext::PluginRequest,
+            PluginInstruction::PluginRequestReply { .. } => PluginContext::PluginRequestReply,
+            PluginInstruction::PluginRequestTimedOut(..) => PluginContext::PluginRequestTimedOut,
+            PluginInstruction::StorageValueChanged { .. } => PluginContext::StorageValueChanged,
             PluginInstruction::UnblockCliPipes { .. } => PluginContext::UnblockCliPipes,
             PluginInstruction::WatchFilesystem => PluginContext::WatchFilesystem,
+            PluginInstruction::PluginFilesChanged(..) => PluginContext::PluginFilesChanged,
+            PluginInstruction::PermissionCacheChanged(..) => PluginContext::PermissionCacheChanged,
             PluginInstruction::KeybindPipe { .. } => PluginContext::KeybindPipe,
             PluginInstruction::DumpLayoutToPlugin { .. } => PluginContext::DumpLayoutToPlugin,
             PluginInstruction::Reconfigure { .. } => PluginContext::Reconfigure,
@@ -772,6 +809,7 @@ pub(crate) fn plugin_thread_main(
                 plugin_id,
                 client_id,
                 permissions,
+                filesystem_permissions,
                 status,
                 cache_path,
             ) => {
@@ -779,6 +817,7 @@ pub(crate) fn plugin_thread_main(
                     plugin_id,
                     client_id,
                     permissions,
+                    filesystem_permissions,
                     status,
                     cache_path,
                 ) {
@@ -1056,92 +1095,115 @@ pub(crate) fn plugin_thread_main(
                 source_plugin_id,
                 message,
             } => {
-                let mut pipe_messages = vec![];
-                let skip_cache = message
-                    .new_plugin_args
-                    .as_ref()
-                    .map(|n| n.skip_cache)
-                    .unwrap_or(false);
-                let should_float = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.should_float)
-                    .unwrap_or(true);
-                let pane_title = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.pane_title.clone());
-                let pane_id_to_replace = message
-                    .new_plugin_args
-                    .as_ref()
-                    .and_then(|n| n.pane_id_to_replace);
-                let floating_pane_coordinates = message.floating_pane_coordinates;
-                match (message.plugin_url, message.destination_plugin_id) {
-                    (Some(plugin_url), None) => {
-                        // send to specific plugin(s)
-                        pipe_to_specific_plugins(
-                            PipeSource::Plugin(source_plugin_id),
-                            &plugin_url,
-                            &Some(message.plugin_config),
-                            &None,
-                            skip_cache,
-                            should_float,
-                            &pane_id_to_replace.map(|p| p.into()),
-                            &pane_title,
-                            None,
-                            &mut pipe_messages,
-                            &message.message_name,
-                            &message.message_payload,
-                            &Some(message.message_args),
+                let pipe_messages = route_message_from_plugin(
+                    source_plugin_id,
+                    message,
+                    &bus,
+                    &mut wasm_bridge,
+                    &plugin_aliases,
+                );
+                wasm_bridge.pipe_messages(pipe_messages, shutdown_send.clone(), None)?;
+            },
+            PluginInstruction::PluginRequest {
+                source_plugin_id,
+                source_client_id,
+                message,
+                timeout_ms,
+                context,
+            } => {
+                let request_id =
+                    wasm_bridge.add_plugin_request(source_plugin_id, source_client_id, context);
+                let mut pipe_messages =
+                    if message.plugin_url.is_none() && message.destination_plugin_id.is_none() {
+                        // requests are not broadcast, they need to be sent to a specific plugin
+                        vec![]
+                    } else {
+                        route_message_from_plugin(
+                            source_plugin_id,
+                            message,
                             &bus,
                             &mut wasm_bridge,
                             &plugin_aliases,
-                            floating_pane_coordinates,
-                            message.new_plugin_args.and_then(|n| n.should_focus),
-                        );
-                    },
-                    (None, Some(destination_plugin_id)) => {
-                        let is_private = true;
-                        pipe_messages.push((
-                            Some(destination_plugin_id),
-                            None,
-                            PipeMessage::new(
-                                PipeSource::Plugin(source_plugin_id),
-                                message.message_name,
-                                &message.message_payload,
-                                &Some(message.message_args),
-                                is_private,
-                            ),
-                        ));
-                    },
-                    (Some(plugin_url), Some(destination_plugin_id)) => {
-                        log::warn!("Message contains both a destination plugin url: {plugin_url} and a destination plugin id: {destination_plugin_id}, ignoring the url and prioritizing the id");
-                        let is_private = true;
-                        pipe_messages.push((
-                            Some(destination_plugin_id),
-                            None,
-                            PipeMessage::new(
-                                PipeSource::Plugin(source_plugin_id),
-                                message.message_name,
-                                &message.message_payload,
-                                &Some(message.message_args),
-                                is_private,
-                            ),
-                        ));
-                    },
-                    (None, None) => {
-                        // send to all plugins
-                        pipe_to_all_plugins(
-                            PipeSource::Plugin(source_plugin_id),
-                            &message.message_name,
-                            &message.message_payload,
-                            &Some(message.message_args),
-                            &mut wasm_bridge,
-                            &mut pipe_messages,
-                        );
-                    },
+                        )
+                    };
+                for (plugin_id, _client_id, pipe_message) in pipe_messages.iter_mut() {
+                    pipe_message.request_id = Some(request_id);
+                    if let Some(plugin_id) = plugin_id {
+                        wasm_bridge.add_plugin_request_recipient(request_id, *plugin_id);
+                    }
+                }
+                if pipe_messages.is_empty() {
+                    let failure = wasm_bridge.resolve_plugin_request(
+                        request_id,
+                        PluginReply::Failed("Found no plugin to send this request to".to_owned()),
+                    );
+                    if let Some((plugin_id, client_id, event)) = failure {
+                        wasm_bridge.update_plugins(
+                            vec![(Some(plugin_id), Some(client_id), event)],
+                            shutdown_send.clone(),
+                        )?;
+                    }
+                } else {
+                    wasm_bridge.pipe_messages(pipe_messages, shutdown_send.clone(), None)?;
+                    let timeout = timeout_ms
+                        .map(Duration::from_millis)
+                        .unwrap_or(DEFAULT_PLUGIN_REQUEST_TIMEOUT);
+                    let senders = bus.senders.clone();
+                    crate::global_async_runtime::get_tokio_runtime().spawn(async move {
+                        tokio::time::sleep(timeout).await;
+                        let _ = senders
+                            .send_to_plugin(PluginInstruction::PluginRequestTimedOut(request_id));
+                    });
                 }
-                wasm_bridge.pipe_messages(pipe_messages, shutdown_send.clone(), None)?;
+            },
+            PluginInstruction::PluginRequestReply {
+                replying_plugin_id,
+                request_id,
+                reply,
+            } => {
+                if let Some((plugin_id, client_id, event)) =
+                    wasm_bridge.reply_to_plugin_request(request_id, replying_plugin_id, reply)
+                {
+                    wasm_bridge.update_plugins(
+                        vec![(Some(plugin_id), Some(client_id), event)],
+                        shutdown_send.clone(),
+                    )?;
+                }
+            },
+            PluginInstruction::PluginRequestTimedOut(request_id) => {
+                if let Some((plugin_id, client_id, event)) =
+                    wasm_bridge.resolve_plugin_request(request_id, PluginReply::TimedOut)
+                {
+                    wasm_bridge.update_plugins(
+                        vec![(Some(plugin_id), Some(client_id), event)],
+                        shutdown_send.clone(),
+                    )?;
+                }
+            },
+            PluginInstruction::StorageValueChanged {
+                source_plugin_id,
+                source_client_id,
+                plugin_location,
+                scope,
+                key,
+                value,
+            } => {
+                // the plugin instance that changed the value already knows about it
+                let updates = wasm_bridge
+                    .all_plugin_and_client_ids_with_location(&plugin_location)
+                    .into_iter()
+                    .filter(|(plugin_id, client_id)| {
+                        (*plugin_id, *client_id) != (source_plugin_id, source_client_id)
+                    })
+                    .map(|(plugin_id, client_id)| {
+                        (
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::StorageValueChanged(scope, key.clone(), value.clone()),
+                        )
+                    })
+                    .collect();
+                wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
             PluginInstruction::UnblockCliPipes(pipes_to_unblock) => {
                 let pipes_to_unblock = wasm_bridge.update_cli_pipe_state(pipes_to_unblock);
@@ -1185,6 +1247,23 @@ pub(crate) fn plugin_thread_main(
             PluginInstruction::WatchFilesystem => {
                 wasm_bridge.start_fs_watcher_if_not_started();
             },
+            PluginInstruction::PluginFilesChanged(changed_paths) => {
+                wasm_bridge.reload_dev_mode_plugins(&changed_paths)?;
+            },
+            PluginInstruction::PermissionCacheChanged(cache_path) => {
+                let updates = wasm_bridge
+                    .revoke_uncached_permissions(cache_path)
+                    .into_iter()
+                    .map(|(plugin_id, client_id)| {
+                        (
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::PermissionRequestResult(PermissionStatus::Denied),
+                        )
+                    })
+                    .collect();
+                wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
+            },
             PluginInstruction::ChangePluginHostDir(new_host_folder, plugin_id, client_id) => {
                 if let Ok(_) = wasm_bridge.change_plugin_host_dir(
                     new_host_folder.clone(),
@@ -1328,6 +1407,101 @@ fn populate_session_layout_metadata(
     session_layout_metadata.update_plugin_aliases_in_default_layout(plugin_aliases);
 }
 
+fn route_message_from_plugin(
+    source_plugin_id: u32,
+    message: MessageToPlugin,
+    bus: &Bus<PluginInstruction>,
+    wasm_bridge: &mut WasmBridge,
+    plugin_aliases: &PluginAliases,
+) -> Vec<(Option<PluginId>, Option<ClientId>, PipeMessage)> {
+    let mut pipe_messages = vec![];
+    let skip_cache = message
+        .new_plugin_args
+        .as_ref()
+        .map(|n| n.skip_cache)
+        .unwrap_or(false);
+    let should_float = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.should_float)
+        .unwrap_or(true);
+    let pane_title = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.pane_title.clone());
+    let pane_id_to_replace = message
+        .new_plugin_args
+        .as_ref()
+        .and_then(|n| n.pane_id_to_replace);
+    let floating_pane_coordinates = message.floating_pane_coordinates;
+    match (message.plugin_url, message.destination_plugin_id) {
+        (Some(plugin_url), None) => {
+            // send to specific plugin(s)
+            pipe_to_specific_plugins(
+                PipeSource::Plugin(source_plugin_id),
+                &plugin_url,
+                &Some(message.plugin_config),
+                &None,
+                skip_cache,
+                should_float,
+                &pane_id_to_replace.map(|p| p.into()),
+                &pane_title,
+                None,
+                &mut pipe_messages,
+                &message.message_name,
+                &message.message_payload,
+                &Some(message.message_args),
+                bus,
+                wasm_bridge,
+                plugin_aliases,
+                floating_pane_coordinates,
+                message.new_plugin_args.and_then(|n| n.should_focus),
+            );
+        },
+        (None, Some(destination_plugin_id)) => {
+            let is_private = true;
+            pipe_messages.push((
+                Some(destination_plugin_id),
+                None,
+                PipeMessage::new(
+                    PipeSource::Plugin(source_plugin_id),
+                    message.message_name,
+                    &message.message_payload,
+                    &Some(message.message_args),
+                    is_private,
+                ),
+            ));
+        },
+        (Some(plugin_url), Some(destination_plugin_id)) => {
+            log::warn!("Message contains both a destination plugin url: {plugin_url} and a destination plugin id: {destination_plugin_id}, ignoring the url and prioritizing the id");
+            let is_private = true;
+            pipe_messages.push((
+                Some(destination_plugin_id),
+                None,
+                PipeMessage::new(
+                    PipeSource::Plugin(source_plugin_id),
+                    message.message_name,
+                    &message.message_payload,
+                    &Some(message.message_args),
+                    is_private,
+                ),
+            ));
+        },
+        (None, None) => {
+            // send to all plugins
+            pipe_to_all_plugins(
+                PipeSource::Plugin(source_plugin_id),
+                &message.message_name,
+                &message.message_payload,
+                &Some(message.message_args),
+                wasm_bridge,
+                &mut pipe_messages,
+            );
+        },
+    }
+    pipe_messages
+}
+
 fn pipe_to_all_plugins(
     pipe_source: PipeSource,
     name: &str,
@@ -2,8 +2,9 @@ use super::{PluginId, PluginInstruction};
 use crate::plugins::plugin_map::RunningPlugin;
 use crate::plugins::wasm_bridge::PluginRenderAsset;
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
-use std::collections::{HashMap, HashSet};
-use zellij_utils::data::{PipeMessage, PipeSource};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+use zellij_utils::data::{Event, PipeMessage, PipeSource, PluginReply};
 use zellij_utils::plugin_api::pipe_message::ProtobufPipeMessage;
 
 use prost::Message;
@@ -137,6 +138,111 @@ impl PendingPipeInfo {
     }
 }
 
+pub const DEFAULT_PLUGIN_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+// requests sent with send_plugin_request that did not yet get a reply, they are resolved either
+// by the first reply of one of their recipients, by a timeout or once all of their recipients
+// have been unloaded
+#[derive(Debug, Clone, Default)]
+pub struct PendingPluginRequests {
+    next_request_id: u64,
+    requests: HashMap<u64, PendingPluginRequest>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingPluginRequest {
+    source_plugin_id: PluginId,
+    source_client_id: ClientId,
+    context: BTreeMap<String, String>,
+    recipients: HashSet<PluginId>,
+}
+
+impl PendingPluginRequests {
+    // returns the id of the new request
+    pub fn add_request(
+        &mut self,
+        source_plugin_id: PluginId,
+        source_client_id: ClientId,
+        context: BTreeMap<String, String>,
+    ) -> u64 {
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        self.requests.insert(
+            request_id,
+            PendingPluginRequest {
+                source_plugin_id,
+                source_client_id,
+                context,
+                recipients: HashSet::new(),
+            },
+        );
+        request_id
+    }
+    pub fn add_recipient(&mut self, request_id: u64, plugin_id: PluginId) {
+        if let Some(pending_request) = self.requests.get_mut(&request_id) {
+            pending_request.recipients.insert(plugin_id);
+        }
+    }
+    // returns the event to send to the plugin that made the request, if it is still pending
+    pub fn resolve(
+        &mut self,
+        request_id: u64,
+        reply: PluginReply,
+    ) -> Option<(PluginId, ClientId, Event)> {
+        self.requests.remove(&request_id).map(|pending_request| {
+            (
+                pending_request.source_plugin_id,
+                pending_request.source_client_id,
+                Event::PluginRequestResult(reply, pending_request.context),
+            )
+        })
+    }
+    // like resolve, but only if the replying plugin is one of the recipients of this request
+    pub fn reply(
+        &mut self,
+        request_id: u64,
+        replying_plugin_id: PluginId,
+        reply: PluginReply,
+    ) -> Option<(PluginId, ClientId, Event)> {
+        let is_recipient = self
+            .requests
+            .get(&request_id)
+            .map(|pending_request| pending_request.recipients.contains(&replying_plugin_id))
+            .unwrap_or(false);
+        if is_recipient {
+            self.resolve(request_id, reply)
+        } else {
+            log::warn!(
+                "Plugin {} replied to request {} which is not pending for it, ignoring",
+                replying_plugin_id,
+                request_id
+            );
+            None
+        }
+    }
+    // returns the events to send to plugins whose requests can no longer be answered
+    pub fn unload_plugin(&mut self, plugin_id: &PluginId) -> Vec<(PluginId, ClientId, Event)> {
+        self.requests
+            .retain(|_, pending_request| pending_request.source_plugin_id != *plugin_id);
+        let mut orphaned_requests = vec![];
+        for (request_id, pending_request) in self.requests.iter_mut() {
+            if pending_request.recipients.remove(plugin_id) && pending_request.recipients.is_empty()
+            {
+                orphaned_requests.push(*request_id);
+            }
+        }
+        orphaned_requests
+            .into_iter()
+            .filter_map(|request_id| {
+                self.resolve(
+                    request_id,
+                    PluginReply::Failed("The plugin handling this request was unloaded".to_owned()),
+                )
+            })
+            .collect()
+    }
+}
+
 pub fn apply_pipe_message_to_plugin(
     plugin_id: PluginId,
     client_id: ClientId,
@@ -145,7 +251,7 @@ pub fn apply_pipe_message_to_plugin(
     plugin_render_assets: &mut Vec<PluginRenderAsset>,
     senders: &ThreadSenders,
 ) -> Result<()> {
-    let instance = &running_plugin.instance;
+    let instance = running_plugin.instance;
     let rows = running_plugin.rows;
     let columns = running_plugin.columns;
 
@@ -155,23 +261,26 @@ pub fn apply_pipe_message_to_plugin(
         .try_into()
         .map_err(|e| anyhow!("Failed to convert to protobuf: {:?}", e))?;
     match instance.get_typed_func::<(), i32>(&mut running_plugin.store, "pipe") {
-        Ok(pipe) => {
+        Ok(pipe) if !running_plugin.is_out_of_fuel() => {
             wasi_write_object(
                 running_plugin.store.data(),
                 &protobuf_pipe_message.encode_to_vec(),
             )
             .with_context(err_context)?;
+            running_plugin.refuel();
             let should_render = pipe
                 .call(&mut running_plugin.store, ())
+                .map_err(|e| running_plugin.handle_call_error(e))
                 .with_context(err_context)?;
             let should_render = should_render == 1;
             if rows > 0 && columns > 0 && should_render {
                 let rendered_bytes = instance
                     .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
                     .and_then(|render| {
+                        running_plugin.refuel();
                         render.call(&mut running_plugin.store, (rows as i32, columns as i32))
                     })
-                    .map_err(|e| anyhow!(e))
+                    .map_err(|e| running_plugin.handle_call_error(e))
                     .and_then(|_| {
                         wasi_read_string(running_plugin.store.data()).map_err(|e| anyhow!(e))
                     })
@@ -197,8 +306,9 @@ pub fn apply_pipe_message_to_plugin(
                     .context("failed to unblock input pipe");
             }
         },
-        Err(_e) => {
-            // no-op, this is probably an old plugin that does not have this interface
+        _ => {
+            // no-op, this is probably an old plugin that does not have this interface (or one
+            // that ran out of fuel and is waiting to be reloaded)
             // we don't log this error because if we do the logs will be super crowded
             let pipes_to_block_or_unblock =
                 pipes_to_block_or_unblock(running_plugin, Some(&pipe_message.source));
@@ -251,3 +361,7 @@ pub fn pipes_to_block_or_unblock(
     }
     pipe_state_changes
 }
+
+#[path = "./unit/pipes_tests.rs"]
+#[cfg(test)]
+mod pipes_tests;
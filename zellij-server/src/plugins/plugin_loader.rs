@@ -2,6 +2,7 @@ use crate::plugins::plugin_map::{
     PluginEnv, PluginMap, RunningPlugin, VecDequeInputStream, WriteOutputStream,
 };
 use crate::plugins::plugin_worker::{plugin_worker, RunningWorker};
+use crate::plugins::read_only_dir::ReadOnlyDir;
 use crate::plugins::wasm_bridge::{LoadingContext, PluginCache};
 use crate::plugins::zellij_exports::{wasi_write_object, zellij_exports};
 use crate::plugins::PluginId;
@@ -12,11 +13,12 @@ use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
 };
-use wasmi::{Engine, Instance, Linker, Module, Store, StoreLimits};
+use wasmi::{Engine, Instance, Linker, Module, Store, StoreLimits, TypedFunc};
 use wasmi_wasi::sync::WasiCtxBuilder;
 use wasmi_wasi::wasi_common::pipe::{ReadPipe, WritePipe};
+use wasmi_wasi::wasi_common::sync::dir::Dir as SyncDir;
 use wasmi_wasi::Dir;
-use wasmi_wasi::WasiCtx;
+use wasmi_wasi::{WasiCtx, WasiDir};
 
 use crate::{
     logging_pipe::LoggingPipe, thread_bus::ThreadSenders,
@@ -25,8 +27,15 @@ use crate::{
 
 use zellij_utils::plugin_api::action::ProtobufPluginConfiguration;
 use zellij_utils::{
-    consts::ZELLIJ_TMP_DIR, data::InputMode, errors::prelude::*, input::command::TerminalAction,
-    input::keybinds::Keybinds, input::plugins::PluginConfig, pane_size::Size,
+    consts::ZELLIJ_TMP_DIR,
+    data::{FilesystemPermission, InputMode},
+    errors::prelude::*,
+    input::command::TerminalAction,
+    input::keybinds::Keybinds,
+    input::layout::{PluginResourceLimits, MAX_PLUGIN_MEMORY_MB},
+    input::permission::host_folders_for_permission,
+    input::plugins::PluginConfig,
+    pane_size::Size,
 };
 
 /// Open a directory as a `File` handle for WASI pre-opening.
@@ -76,6 +85,7 @@ pub struct PluginLoader<'a> {
     keybinds: Keybinds,
     plugin_dir: PathBuf,
     size: Size,
+    saved_state: Option<String>,
     loading_indication: LoadingIndication,
     senders: ThreadSenders,
     engine: Engine,
@@ -117,6 +127,7 @@ impl<'a> PluginLoader<'a> {
             keybinds: loading_context.keybinds,
             plugin_dir: loading_context.plugin_dir,
             size: loading_context.size,
+            saved_state: loading_context.saved_state,
 
             skip_cache,
             senders,
@@ -189,6 +200,7 @@ impl<'a> PluginLoader<'a> {
                 let start_function_for_worker = instance
                     .get_typed_func::<(), ()>(&mut store, "_start")
                     .with_context(err_context)?;
+                refuel_store(&mut store, u64::MAX);
                 start_function_for_worker
                     .call(&mut store, ())
                     .with_context(err_context)?;
@@ -214,9 +226,7 @@ impl<'a> PluginLoader<'a> {
             workers,
         );
 
-        start_function
-            .call(&mut plugin.lock().unwrap().store, ())
-            .with_context(err_context)?;
+        call_plugin_while_loading(&plugin, &start_function, err_context)?;
 
         let protobuf_plugin_configuration: ProtobufPluginConfiguration = self
             .plugin_config
@@ -227,9 +237,11 @@ impl<'a> PluginLoader<'a> {
         let protobuf_bytes = protobuf_plugin_configuration.encode_to_vec();
         wasi_write_object(plugin.lock().unwrap().store.data(), &protobuf_bytes)
             .with_context(err_context)?;
-        load_function
-            .call(&mut plugin.lock().unwrap().store, ())
-            .with_context(err_context)?;
+        call_plugin_while_loading(&plugin, &load_function, err_context)?;
+
+        if let Some(saved_state) = self.saved_state.as_ref() {
+            restore_plugin_state(&plugin, saved_state).with_context(err_context)?;
+        }
 
         Ok(())
     }
@@ -251,6 +263,7 @@ impl<'a> PluginLoader<'a> {
             &self.plugin_own_data_dir,
             &self.plugin_own_cache_dir,
             &ZELLIJ_TMP_DIR,
+            &[],
             &self.plugin_config.location.to_string(),
             self.plugin_id,
             stdin_pipe.clone(),
@@ -262,6 +275,7 @@ impl<'a> PluginLoader<'a> {
             client_id: self.client_id,
             plugin: self.plugin_config.clone(), // TODO: change field name in PluginEnv to plugin_config
             permissions: Arc::new(Mutex::new(None)),
+            filesystem_permissions: vec![],
             senders: self.senders.clone(),
             wasi_ctx,
             plugin_own_data_dir: self.plugin_own_data_dir.clone(),
@@ -280,12 +294,14 @@ impl<'a> PluginLoader<'a> {
             intercepting_key_presses: false,
             stdin_pipe,
             stdout_pipe,
-            store_limits: create_optimized_store_limits(),
+            store_limits: create_optimized_store_limits(&self.plugin_config.resource_limits),
+            recent_timers: VecDeque::new(),
         };
         let mut store = Store::new(&self.engine, plugin_env);
 
         // Apply optimized resource limits for memory efficiency
         store.limiter(|plugin_env| &mut plugin_env.store_limits);
+        refuel_store(&mut store, plugin_fuel_budget(&self.plugin_config));
 
         let mut linker = Linker::new(&self.engine);
         wasmi_wasi::add_to_linker(&mut linker, |plugin_env: &mut PluginEnv| {
@@ -299,6 +315,7 @@ impl<'a> PluginLoader<'a> {
 
         if let Some(func) = instance.get_func(&mut store, "_initialize") {
             if let Ok(typed_func) = func.typed::<(), ()>(&store) {
+                refuel_store(&mut store, plugin_fuel_budget(&self.plugin_config));
                 let _ = typed_func.call(&mut store, ());
             }
         }
@@ -363,6 +380,7 @@ impl<'a> PluginLoader<'a> {
             &self.plugin_own_data_dir,
             &self.plugin_own_cache_dir,
             &ZELLIJ_TMP_DIR,
+            &[],
             &self.plugin_config.location.to_string(),
             self.plugin_id,
             stdin_pipe.clone(),
@@ -374,6 +392,7 @@ impl<'a> PluginLoader<'a> {
             client_id: self.client_id,
             plugin: plugin_config,
             permissions: Arc::new(Mutex::new(None)),
+            filesystem_permissions: vec![],
             senders: self.senders.clone(),
             wasi_ctx,
             plugin_own_data_dir: self.plugin_own_data_dir.clone(),
@@ -392,12 +411,15 @@ impl<'a> PluginLoader<'a> {
             intercepting_key_presses: false,
             stdin_pipe,
             stdout_pipe,
-            store_limits: create_optimized_store_limits(),
+            store_limits: create_optimized_store_limits(&self.plugin_config.resource_limits),
+            recent_timers: VecDeque::new(),
         };
         let mut store = Store::new(&self.engine, plugin_env);
 
         // Apply optimized resource limits for memory efficiency
         store.limiter(|plugin_env| &mut plugin_env.store_limits);
+        // workers are there to do long-running work in the background, so we don't meter them
+        refuel_store(&mut store, u64::MAX);
 
         let mut linker = Linker::new(&self.engine);
         wasmi_wasi::add_to_linker(&mut linker, |plugin_env: &mut PluginEnv| {
@@ -422,6 +444,7 @@ impl<'a> PluginLoader<'a> {
         data_dir: &PathBuf,
         cache_dir: &PathBuf,
         tmp_dir: &PathBuf,
+        filesystem_permissions: &[FilesystemPermission],
         plugin_url: &String,
         plugin_id: PluginId,
         stdin_pipe: Arc<Mutex<VecDeque<u8>>>,
@@ -461,6 +484,27 @@ impl<'a> PluginLoader<'a> {
 
         let ctx = builder.build();
 
+        // folders granted through filesystem permissions are mounted under their host path
+        for filesystem_permission in filesystem_permissions {
+            for host_path in host_folders_for_permission(filesystem_permission) {
+                match open_dir(&host_path) {
+                    Ok(dir_file) => {
+                        let dir: Box<dyn WasiDir> =
+                            Box::new(SyncDir::from_cap_std(Dir::from_std_file(dir_file)));
+                        let dir: Box<dyn WasiDir> = if filesystem_permission.read_only {
+                            Box::new(ReadOnlyDir(dir))
+                        } else {
+                            dir
+                        };
+                        ctx.push_preopened_dir(dir, &host_path)?;
+                    },
+                    Err(e) => {
+                        log::warn!("Failed to mount directory {:?}: {}", host_path, e);
+                    },
+                }
+            }
+        }
+
         // Set up custom stdin/stdout/stderr
         ctx.set_stdin(Box::new(ReadPipe::new(VecDequeInputStream(
             stdin_pipe.clone(),
@@ -476,13 +520,81 @@ impl<'a> PluginLoader<'a> {
     }
 }
 
-fn create_optimized_store_limits() -> StoreLimits {
+// pass the state saved by the previous instance of a plugin reloaded in `dev_mode` to the new one
+fn restore_plugin_state(plugin: &Arc<Mutex<RunningPlugin>>, saved_state: &str) -> Result<()> {
+    let restore_state = {
+        let mut plugin = plugin.lock().unwrap();
+        let instance = plugin.instance;
+        // plugins compiled against an older zellij-tile do not have this function
+        let Ok(restore_state) =
+            instance.get_typed_func::<(), ()>(&mut plugin.store, "restore_state")
+        else {
+            return Ok(());
+        };
+        wasi_write_object(plugin.store.data(), saved_state)?;
+        restore_state
+    };
+    call_plugin_while_loading(plugin, &restore_state, || {
+        "failed to restore plugin state".to_owned()
+    })
+}
+
+/// Running out of fuel while loading crashes the plugin the same way it does in any later callback
+fn call_plugin_while_loading(
+    plugin: &Arc<Mutex<RunningPlugin>>,
+    function: &TypedFunc<(), ()>,
+    err_context: impl FnOnce() -> String,
+) -> Result<()> {
+    let mut plugin = plugin.lock().unwrap();
+    plugin.refuel();
+    function.call(&mut plugin.store, ()).map_err(|e| {
+        let error = plugin.handle_call_error(e);
+        if plugin.is_out_of_fuel() {
+            error
+        } else {
+            error.context(err_context())
+        }
+    })
+}
+
+/// Fuel a plugin without a `fuel_budget` of its own can burn in a single callback, enough for any
+/// reasonable amount of work while still catching plugins stuck in a loop
+pub const DEFAULT_PLUGIN_FUEL_BUDGET: u64 = 1_000_000_000;
+
+/// The amount of fuel a plugin may burn in a single callback before it is trapped
+pub fn plugin_fuel_budget(plugin_config: &PluginConfig) -> u64 {
+    match plugin_config.resource_limits.fuel_budget {
+        Some(fuel_budget) => fuel_budget,
+        // built-in plugins are part of the application, there's no use stopping them
+        None if plugin_config.is_builtin() => u64::MAX,
+        None => DEFAULT_PLUGIN_FUEL_BUDGET,
+    }
+}
+
+pub fn refuel_store(store: &mut Store<PluginEnv>, fuel: u64) {
+    // this only fails if the engine does not meter fuel (eg. in tests), in which case there is
+    // nothing to refill
+    let _ = store.set_fuel(fuel);
+}
+
+/// Size each linear memory of a plugin can grow to unless it sets a `max_memory_mb` of its own
+const DEFAULT_PLUGIN_MAX_MEMORY_MB: usize = 16;
+
+fn create_optimized_store_limits(resource_limits: &PluginResourceLimits) -> StoreLimits {
     use wasmi::StoreLimitsBuilder;
-    StoreLimitsBuilder::new()
+    let max_memory_mb = resource_limits
+        .max_memory_mb
+        .unwrap_or(DEFAULT_PLUGIN_MAX_MEMORY_MB)
+        .min(MAX_PLUGIN_MEMORY_MB);
+    let mut store_limits = StoreLimitsBuilder::new()
         .instances(1) // One instance per plugin
         .memories(4) // Max 4 linear memories per plugin
-        .memory_size(16 * 1024 * 1024) // 16MB per memory maximum
-        .tables(16) // Small table element limit
+        .memory_size(max_memory_mb.saturating_mul(1024 * 1024)) // per memory maximum
+        .tables(16); // Small table element limit
+    if let Some(max_table_elements) = resource_limits.max_table_elements {
+        store_limits = store_limits.table_elements(max_table_elements);
+    }
+    store_limits
         .trap_on_grow_failure(true) // Fail fast on resource exhaustion
         .build()
 }
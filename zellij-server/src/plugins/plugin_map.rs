@@ -1,3 +1,5 @@
+use crate::background_jobs::BackgroundJob;
+use crate::plugins::plugin_loader::{plugin_fuel_budget, refuel_store};
 use crate::plugins::plugin_worker::MessageToWorker;
 use crate::plugins::PluginId;
 use std::io::Write;
@@ -5,8 +7,9 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::Instant,
 };
-use wasmi::{Instance, Store, StoreLimits};
+use wasmi::{Instance, Store, StoreLimits, TrapCode};
 use wasmi_wasi::WasiCtx;
 
 use crate::{thread_bus::ThreadSenders, ClientId};
@@ -20,7 +23,10 @@ use zellij_utils::{
     input::layout::{PluginUserConfiguration, RunPlugin, RunPluginLocation},
     input::plugins::PluginConfig,
 };
-use zellij_utils::{data::PermissionType, errors::prelude::*};
+use zellij_utils::{
+    data::{FilesystemPermission, PermissionType},
+    errors::prelude::*,
+};
 
 // the idea here is to provide atomicity when adding/removing plugins from the map (eg. when a new
 // client connects) but to also allow updates/renders not to block each other
@@ -241,11 +247,17 @@ impl PluginMap {
                     let run_plugin_configuration =
                         plugin_config.initial_userspace_configuration.clone();
                     let initial_cwd = plugin_config.initial_cwd.clone();
+                    let sha256 = plugin_config.sha256.clone();
+                    let resource_limits = plugin_config.resource_limits.clone();
+                    let allowed_hosts = plugin_config.allowed_hosts.clone();
                     Some(RunPlugin {
                         _allow_exec_host_cmd: false,
                         location: run_plugin_location,
                         configuration: run_plugin_configuration,
                         initial_cwd,
+                        sha256,
+                        resource_limits,
+                        allowed_hosts,
                     })
                 } else {
                     None
@@ -278,6 +290,7 @@ pub struct PluginEnv {
     pub plugin_id: PluginId,
     pub plugin: PluginConfig,
     pub permissions: Arc<Mutex<Option<HashSet<PermissionType>>>>,
+    pub filesystem_permissions: Vec<FilesystemPermission>, // mounted in wasi_ctx
     pub senders: ThreadSenders,
     pub wasi_ctx: WasiCtx,
     pub tab_index: Option<usize>,
@@ -299,6 +312,7 @@ pub struct PluginEnv {
     pub keybinds: Keybinds,
     pub intercepting_key_presses: bool,
     pub store_limits: StoreLimits,
+    pub recent_timers: VecDeque<Instant>, // when the timers the plugin set in the last second were set
 }
 
 #[derive(Clone)]
@@ -362,6 +376,7 @@ pub struct RunningPlugin {
     pub columns: usize,
     next_event_ids: HashMap<AtomicEvent, usize>,
     last_applied_event_ids: HashMap<AtomicEvent, usize>,
+    out_of_fuel: bool,
 }
 
 impl RunningPlugin {
@@ -373,6 +388,7 @@ impl RunningPlugin {
             columns,
             next_event_ids: HashMap::new(),
             last_applied_event_ids: HashMap::new(),
+            out_of_fuel: false,
         }
     }
     pub fn next_event_id(&mut self, atomic_event: AtomicEvent) -> usize {
@@ -412,4 +428,35 @@ impl RunningPlugin {
     pub fn intercepting_key_presses(&self) -> bool {
         self.store.data().intercepting_key_presses
     }
+    pub fn refuel(&mut self) {
+        let fuel_budget = plugin_fuel_budget(&self.store.data().plugin);
+        refuel_store(&mut self.store, fuel_budget);
+    }
+    pub fn is_out_of_fuel(&self) -> bool {
+        // a plugin that ran out of fuel is left in an unknown state, so we don't call into it
+        // again until it is reloaded
+        self.out_of_fuel
+    }
+    pub fn handle_call_error(&mut self, error: wasmi::Error) -> anyError {
+        if error.as_trap_code() != Some(TrapCode::OutOfFuel) {
+            return anyhow!(error);
+        }
+        self.out_of_fuel = true;
+        let plugin_env = self.store.data();
+        let crash_reason = format!(
+            "Used up its fuel budget of {} in a single callback",
+            plugin_fuel_budget(&plugin_env.plugin)
+        );
+        let _ = plugin_env
+            .senders
+            .send_to_background_jobs(BackgroundJob::ReportPluginCrash(
+                plugin_env.plugin_id,
+                Some(crash_reason.clone()),
+            ));
+        anyhow!(
+            "{} and was stopped.\n\nReload it with: zellij action start-or-reload-plugin {}\nIf it needs more time, raise the fuel_budget in its configuration.",
+            crash_reason,
+            plugin_env.plugin.location.display(),
+        )
+    }
 }
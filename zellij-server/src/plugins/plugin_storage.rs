@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+use super::wasm_bridge::make_plugin_url_path_safe;
+use zellij_utils::data::StorageScope;
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::layout::RunPluginLocation;
+
+/// The most a single key and its value can take up
+pub const MAX_STORAGE_ENTRY_LEN: usize = 64 * 1024;
+/// The most all the values of a plugin can take up, across scopes and sessions
+pub const MAX_PLUGIN_STORAGE_LEN: usize = 1024 * 1024;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredValues {
+    #[serde(default)]
+    plugin: BTreeMap<String, String>,
+    #[serde(default)]
+    sessions: BTreeMap<String, BTreeMap<String, String>>, // session name -> values
+}
+
+impl StoredValues {
+    fn scoped(&self, scope: StorageScope, session_name: &str) -> Option<&BTreeMap<String, String>> {
+        match scope {
+            StorageScope::Plugin => Some(&self.plugin),
+            StorageScope::Session => self.sessions.get(session_name),
+        }
+    }
+    fn scoped_mut(
+        &mut self,
+        scope: StorageScope,
+        session_name: &str,
+    ) -> &mut BTreeMap<String, String> {
+        match scope {
+            StorageScope::Plugin => &mut self.plugin,
+            StorageScope::Session => self.sessions.entry(session_name.to_owned()).or_default(),
+        }
+    }
+}
+
+/// The persistent key-value storage of a plugin, backed by a json file that is shared by all the
+/// instances of the plugin url in all sessions
+pub struct PluginStorage {
+    path: PathBuf,
+    session_name: String,
+}
+
+impl PluginStorage {
+    pub fn new(
+        storage_dir: &Path,
+        plugin_location: &RunPluginLocation,
+        session_name: impl Into<String>,
+    ) -> Self {
+        let plugin_url = Url::from(plugin_location).to_string();
+        PluginStorage {
+            path: storage_dir
+                .join(make_plugin_url_path_safe(plugin_url))
+                .join("storage.json"),
+            session_name: session_name.into(),
+        }
+    }
+    pub fn get(&self, scope: StorageScope, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .read()?
+            .scoped(scope, &self.session_name)
+            .and_then(|values| values.get(key).cloned()))
+    }
+    pub fn set(&self, scope: StorageScope, key: &str, value: String) -> Result<()> {
+        if key.is_empty() {
+            return Err(anyhow!("Storage keys cannot be empty"));
+        }
+        if key.len() + value.len() > MAX_STORAGE_ENTRY_LEN {
+            return Err(anyhow!(
+                "Storage value of \"{}\" is too big ({} bytes, the limit is {})",
+                key,
+                key.len() + value.len(),
+                MAX_STORAGE_ENTRY_LEN
+            ));
+        }
+        let _lock = self.lock()?;
+        let mut stored_values = self.read()?;
+        stored_values
+            .scoped_mut(scope, &self.session_name)
+            .insert(key.to_owned(), value);
+        let serialized = serde_json::to_string(&stored_values)?;
+        if serialized.len() > MAX_PLUGIN_STORAGE_LEN {
+            return Err(anyhow!(
+                "Plugin storage is full (it would take {} bytes, the limit is {})",
+                serialized.len(),
+                MAX_PLUGIN_STORAGE_LEN
+            ));
+        }
+        self.write(&serialized)
+    }
+    // returns true if the key existed
+    pub fn delete(&self, scope: StorageScope, key: &str) -> Result<bool> {
+        let _lock = self.lock()?;
+        let mut stored_values = self.read()?;
+        let values = stored_values.scoped_mut(scope, &self.session_name);
+        let existed = values.remove(key).is_some();
+        if values.is_empty() && scope == StorageScope::Session {
+            stored_values.sessions.remove(&self.session_name);
+        }
+        if existed {
+            self.write(&serde_json::to_string(&stored_values)?)?;
+        }
+        Ok(existed)
+    }
+    pub fn list(&self, scope: StorageScope, prefix: &str) -> Result<BTreeMap<String, String>> {
+        Ok(self
+            .read()?
+            .scoped(scope, &self.session_name)
+            .map(|values| {
+                values
+                    .range(prefix.to_owned()..)
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+    // the servers of all sessions share the storage file, so changes to it hold an exclusive lock
+    // on a file next to it (released when the returned file is dropped). Readers don't need it
+    // because the storage file is always replaced as a whole
+    fn lock(&self) -> Result<File> {
+        let err_context = || format!("Failed to lock plugin storage {:?}", self.path);
+        let storage_dir = self.storage_dir().with_context(err_context)?;
+        std::fs::create_dir_all(storage_dir).with_context(err_context)?;
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(storage_dir.join("storage.lock"))
+            .with_context(err_context)?;
+        lock_file.lock().with_context(err_context)?;
+        Ok(lock_file)
+    }
+    fn storage_dir(&self) -> Result<&Path> {
+        self.path
+            .parent()
+            .ok_or_else(|| anyhow!("Plugin storage has no parent folder"))
+    }
+    fn read(&self) -> Result<StoredValues> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(stored_values) => serde_json::from_str(&stored_values)
+                .with_context(|| format!("Failed to parse plugin storage {:?}", self.path)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(StoredValues::default()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to read plugin storage {:?}", self.path))
+            },
+        }
+    }
+    fn write(&self, serialized: &str) -> Result<()> {
+        let err_context = || format!("Failed to write plugin storage {:?}", self.path);
+        let storage_dir = self.storage_dir().with_context(err_context)?;
+        // write to a temporary file first so that a crash midway does not leave a corrupt file
+        let mut temp_file =
+            tempfile::NamedTempFile::new_in(storage_dir).with_context(err_context)?;
+        temp_file
+            .write_all(serialized.as_bytes())
+            .with_context(err_context)?;
+        temp_file
+            .persist(&self.path)
+            .map_err(|e| e.error)
+            .with_context(err_context)?;
+        Ok(())
+    }
+}
+
+#[path = "./unit/plugin_storage_tests.rs"]
+#[cfg(test)]
+mod plugin_storage_tests;
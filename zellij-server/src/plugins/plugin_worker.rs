@@ -1,3 +1,4 @@
+use crate::plugins::plugin_loader::refuel_store;
 use crate::plugins::plugin_map::PluginEnv;
 use crate::plugins::zellij_exports::wasi_write_object;
 use wasmi::{Instance, Store};
@@ -34,6 +35,7 @@ impl RunningWorker {
             .get_typed_func::<(), ()>(&mut self.store, &self.name)
             .with_context(err_context)?;
         wasi_write_object(self.store.data(), &protobuf_bytes).with_context(err_context)?;
+        refuel_store(&mut self.store, u64::MAX);
         work_function
             .call(&mut self.store, ())
             .with_context(err_context)?;
@@ -0,0 +1,105 @@
+use super::PendingPluginRequests;
+use std::collections::BTreeMap;
+use zellij_utils::data::{Event, PluginReply};
+
+fn context(request_name: &str) -> BTreeMap<String, String> {
+    let mut context = BTreeMap::new();
+    context.insert("request".to_owned(), request_name.to_owned());
+    context
+}
+
+#[test]
+fn reply_is_delivered_to_requesting_plugin_with_its_context() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let request_id = pending_requests.add_request(1, 10, context("list_projects"));
+    pending_requests.add_recipient(request_id, 2);
+    assert_eq!(
+        pending_requests.reply(
+            request_id,
+            2,
+            PluginReply::Replied(Some("zellij".to_owned()))
+        ),
+        Some((
+            1,
+            10,
+            Event::PluginRequestResult(
+                PluginReply::Replied(Some("zellij".to_owned())),
+                context("list_projects")
+            )
+        ))
+    );
+}
+
+#[test]
+fn only_the_first_reply_to_a_request_is_delivered() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let request_id = pending_requests.add_request(1, 10, context("list_projects"));
+    pending_requests.add_recipient(request_id, 2);
+    pending_requests.add_recipient(request_id, 3);
+    assert!(pending_requests
+        .reply(request_id, 3, PluginReply::Replied(None))
+        .is_some());
+    assert_eq!(
+        pending_requests.reply(request_id, 2, PluginReply::Replied(None)),
+        None
+    );
+    assert_eq!(
+        pending_requests.resolve(request_id, PluginReply::TimedOut),
+        None,
+        "request does not time out after having been replied to"
+    );
+}
+
+#[test]
+fn replies_from_plugins_the_request_was_not_sent_to_are_ignored() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let request_id = pending_requests.add_request(1, 10, context("list_projects"));
+    pending_requests.add_recipient(request_id, 2);
+    assert_eq!(
+        pending_requests.reply(request_id, 3, PluginReply::Replied(None)),
+        None
+    );
+    assert!(pending_requests
+        .reply(request_id, 2, PluginReply::Replied(None))
+        .is_some());
+}
+
+#[test]
+fn request_ids_are_unique() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let first_request_id = pending_requests.add_request(1, 10, BTreeMap::new());
+    let second_request_id = pending_requests.add_request(1, 10, BTreeMap::new());
+    assert_ne!(first_request_id, second_request_id);
+}
+
+#[test]
+fn request_fails_once_all_of_its_recipients_are_unloaded() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let request_id = pending_requests.add_request(1, 10, context("run_tests"));
+    pending_requests.add_recipient(request_id, 2);
+    pending_requests.add_recipient(request_id, 3);
+    assert_eq!(pending_requests.unload_plugin(&2), vec![]);
+    assert_eq!(
+        pending_requests.unload_plugin(&3),
+        vec![(
+            1,
+            10,
+            Event::PluginRequestResult(
+                PluginReply::Failed("The plugin handling this request was unloaded".to_owned()),
+                context("run_tests")
+            )
+        )]
+    );
+}
+
+#[test]
+fn requests_of_an_unloaded_plugin_are_dropped() {
+    let mut pending_requests = PendingPluginRequests::default();
+    let request_id = pending_requests.add_request(1, 10, context("run_tests"));
+    pending_requests.add_recipient(request_id, 2);
+    assert_eq!(pending_requests.unload_plugin(&1), vec![]);
+    assert_eq!(
+        pending_requests.reply(request_id, 2, PluginReply::Replied(None)),
+        None
+    );
+}
@@ -0,0 +1,192 @@
+use super::{PluginStorage, MAX_PLUGIN_STORAGE_LEN, MAX_STORAGE_ENTRY_LEN};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tempfile::tempdir;
+use zellij_utils::data::StorageScope;
+use zellij_utils::input::layout::RunPluginLocation;
+
+fn plugin_location(name: &str) -> RunPluginLocation {
+    RunPluginLocation::File(PathBuf::from(format!("/plugins/{}.wasm", name)))
+}
+
+#[test]
+fn values_are_persisted_across_storage_instances() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("switcher"), "session");
+    storage
+        .set(StorageScope::Plugin, "last_project", "zellij".to_owned())
+        .unwrap();
+    let storage = PluginStorage::new(
+        storage_dir.path(),
+        &plugin_location("switcher"),
+        "other-session",
+    );
+    assert_eq!(
+        storage.get(StorageScope::Plugin, "last_project").unwrap(),
+        Some("zellij".to_owned())
+    );
+}
+
+#[test]
+fn values_are_scoped_to_the_plugin_url() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("switcher"), "session");
+    storage
+        .set(StorageScope::Plugin, "last_project", "zellij".to_owned())
+        .unwrap();
+    let other_plugin_storage =
+        PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    assert_eq!(
+        other_plugin_storage
+            .get(StorageScope::Plugin, "last_project")
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn session_values_are_scoped_to_the_session() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("switcher"), "first");
+    storage
+        .set(StorageScope::Session, "selected", "3".to_owned())
+        .unwrap();
+    assert_eq!(storage.get(StorageScope::Plugin, "selected").unwrap(), None);
+    assert_eq!(
+        storage.get(StorageScope::Session, "selected").unwrap(),
+        Some("3".to_owned())
+    );
+    let other_session_storage =
+        PluginStorage::new(storage_dir.path(), &plugin_location("switcher"), "second");
+    assert_eq!(
+        other_session_storage
+            .get(StorageScope::Session, "selected")
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn deleting_a_value() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("switcher"), "session");
+    storage
+        .set(StorageScope::Plugin, "last_project", "zellij".to_owned())
+        .unwrap();
+    assert!(storage
+        .delete(StorageScope::Plugin, "last_project")
+        .unwrap());
+    assert!(!storage
+        .delete(StorageScope::Plugin, "last_project")
+        .unwrap());
+    assert_eq!(
+        storage.get(StorageScope::Plugin, "last_project").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn listing_values_with_a_prefix() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    for (key, value) in [
+        ("results/unit", "passed"),
+        ("results/e2e", "failed"),
+        ("resultset", "ignored"),
+        ("last_run", "yesterday"),
+    ] {
+        storage
+            .set(StorageScope::Plugin, key, value.to_owned())
+            .unwrap();
+    }
+    let mut expected = BTreeMap::new();
+    expected.insert("results/e2e".to_owned(), "failed".to_owned());
+    expected.insert("results/unit".to_owned(), "passed".to_owned());
+    assert_eq!(
+        storage.list(StorageScope::Plugin, "results/").unwrap(),
+        expected
+    );
+    assert_eq!(storage.list(StorageScope::Plugin, "").unwrap().len(), 4);
+    assert_eq!(
+        storage.list(StorageScope::Session, "").unwrap(),
+        BTreeMap::new()
+    );
+}
+
+#[test]
+fn empty_keys_are_rejected() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    assert!(storage
+        .set(StorageScope::Plugin, "", "value".to_owned())
+        .is_err());
+}
+
+#[test]
+fn values_over_the_entry_limit_are_rejected() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    assert!(storage
+        .set(
+            StorageScope::Plugin,
+            "big",
+            "a".repeat(MAX_STORAGE_ENTRY_LEN)
+        )
+        .is_err());
+    assert_eq!(storage.get(StorageScope::Plugin, "big").unwrap(), None);
+}
+
+#[test]
+fn values_that_would_fill_the_plugin_storage_are_rejected() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    let value = "a".repeat(MAX_STORAGE_ENTRY_LEN - 16);
+    let values_that_fit = MAX_PLUGIN_STORAGE_LEN / MAX_STORAGE_ENTRY_LEN;
+    for i in 0..values_that_fit {
+        storage
+            .set(StorageScope::Plugin, &format!("key_{}", i), value.clone())
+            .unwrap();
+    }
+    // the limit covers the values of all sessions together
+    let other_session_storage =
+        PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "other");
+    assert!(other_session_storage
+        .set(StorageScope::Session, "one_too_many", value.clone())
+        .is_err());
+    assert!(storage.delete(StorageScope::Plugin, "key_0").unwrap());
+    other_session_storage
+        .set(StorageScope::Session, "one_too_many", value)
+        .unwrap();
+}
+
+#[test]
+fn changes_wait_for_other_servers_to_release_the_storage() {
+    let storage_dir = tempdir().unwrap();
+    let storage = PluginStorage::new(storage_dir.path(), &plugin_location("runner"), "session");
+    storage
+        .set(StorageScope::Plugin, "counter", "1".to_owned())
+        .unwrap();
+    // stands in for the server of another session in the middle of a change
+    let lock_file = std::fs::File::open(storage.path.with_file_name("storage.lock")).unwrap();
+    lock_file.lock().unwrap();
+    let (done_sender, done_receiver) = std::sync::mpsc::channel();
+    let storage_path = storage_dir.path().to_owned();
+    std::thread::spawn(move || {
+        let storage = PluginStorage::new(&storage_path, &plugin_location("runner"), "session");
+        storage
+            .set(StorageScope::Plugin, "counter", "2".to_owned())
+            .unwrap();
+        done_sender.send(()).unwrap();
+    });
+    assert!(done_receiver
+        .recv_timeout(std::time::Duration::from_millis(200))
+        .is_err());
+    drop(lock_file);
+    done_receiver
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(
+        storage.get(StorageScope::Plugin, "counter").unwrap(),
+        Some("2".to_owned())
+    );
+}
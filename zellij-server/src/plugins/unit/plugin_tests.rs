@@ -8,13 +8,15 @@ use std::path::PathBuf;
 use tempfile::tempdir;
 use wasmi::Engine;
 use zellij_utils::data::{
-    BareKey, Event, InputMode, KeyWithModifier, ModeInfo, PermissionStatus, PermissionType,
+    BareKey, Event, FilesystemPermission, InputMode, KeyWithModifier, ModeInfo, PermissionStatus,
+    PermissionType,
 };
 use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::actions::Action;
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::layout::{
-    Layout, PluginAlias, PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias,
+    Layout, PluginAlias, PluginResourceLimits, PluginUserConfiguration, RunPlugin,
+    RunPluginLocation, RunPluginOrAlias,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::input::plugins::PluginAliases;
@@ -115,6 +117,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -125,6 +128,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -168,6 +172,7 @@ macro_rules! deny_permissions_and_log_actions_in_thread {
                                     0,
                                     Some($client_id),
                                     plugin_permission.permissions,
+                                    plugin_permission.filesystem_permissions,
                                     PermissionStatus::Denied,
                                     Some(cache_path.clone()),
                                 ),
@@ -212,6 +217,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_naked_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -222,6 +228,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_naked_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -266,6 +273,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_struct_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Granted,
                                         Some(cache_path.clone()),
                                     ),
@@ -276,6 +284,7 @@ macro_rules! grant_permissions_and_log_actions_in_thread_struct_variant {
                                         0,
                                         Some($client_id),
                                         plugin_permission.permissions,
+                                        plugin_permission.filesystem_permissions,
                                         PermissionStatus::Denied,
                                         Some(cache_path.clone()),
                                     ),
@@ -584,6 +593,26 @@ fn create_plugin_thread_with_background_jobs_receiver(
     Receiver<(BackgroundJob, ErrorContext)>,
     Receiver<(ScreenInstruction, ErrorContext)>,
     Box<dyn FnOnce()>,
+) {
+    let mut config = wasmi::Config::default();
+    config.set_max_stack_height(1024 * 1024);
+    config.set_max_recursion_depth(1000);
+    create_plugin_thread_with_engine_and_background_jobs_receiver(
+        Engine::new(&config),
+        zellij_cwd,
+        session_env_vars,
+    )
+}
+
+fn create_plugin_thread_with_engine_and_background_jobs_receiver(
+    engine: Engine,
+    zellij_cwd: Option<PathBuf>,
+    session_env_vars: Option<std::collections::BTreeMap<String, String>>,
+) -> (
+    SenderWithContext<PluginInstruction>,
+    Receiver<(BackgroundJob, ErrorContext)>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    Box<dyn FnOnce()>,
 ) {
     let zellij_cwd = zellij_cwd.unwrap_or_else(|| PathBuf::from("."));
     let session_env_vars = session_env_vars.unwrap_or_else(|| std::env::vars().collect());
@@ -618,10 +647,6 @@ fn create_plugin_thread_with_background_jobs_receiver(
         None,
     )
     .should_silently_fail();
-    let mut config = wasmi::Config::default();
-    config.set_max_stack_height(1024 * 1024);
-    config.set_max_recursion_depth(1000);
-    let engine = Engine::new(&config);
     let data_dir = PathBuf::from(tempdir().unwrap().path());
     let default_shell = PathBuf::from(".");
     let default_shell_action = None; // TODO: change me
@@ -6223,6 +6248,7 @@ pub fn granted_permission_request_result() {
                                 0,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
                                 PermissionStatus::Granted,
                                 Some(cache_path.clone()),
                             ));
@@ -6277,6 +6303,117 @@ pub fn granted_permission_request_result() {
     assert_snapshot!(format!("{:#?}", permissions));
 }
 
+#[test]
+#[ignore]
+pub fn revoked_permissions_are_denied_to_running_plugin() {
+    let temp_folder = tempdir().unwrap();
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder), None);
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: PluginUserConfiguration::new(BTreeMap::from([(
+            "subscribe_permission_request_result".to_owned(),
+            "true".to_owned(),
+        )])),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+
+    // here we create a fake screen thread that grants every permission request it gets and
+    // exits once the plugin rendered that its permissions were revoked
+    let received_plugin_bytes = Arc::new(Mutex::new(vec![]));
+    let screen_thread = std::thread::Builder::new()
+        .name("fake_screen_thread".to_string())
+        .spawn({
+            let cache_path = cache_path.clone();
+            let plugin_thread_sender = plugin_thread_sender.clone();
+            let received_plugin_bytes = received_plugin_bytes.clone();
+            move || loop {
+                let (event, _err_ctx) = screen_receiver
+                    .recv()
+                    .expect("failed to receive event on channel");
+                match event {
+                    ScreenInstruction::RequestPluginPermissions(_, plugin_permission) => {
+                        let _ =
+                            plugin_thread_sender.send(PluginInstruction::PermissionRequestResult(
+                                0,
+                                Some(client_id),
+                                plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
+                                PermissionStatus::Granted,
+                                Some(cache_path.clone()),
+                            ));
+                    },
+                    ScreenInstruction::PluginBytes(plugin_render_assets) => {
+                        for plugin_render_asset in plugin_render_assets {
+                            let bytes = String::from_utf8_lossy(&plugin_render_asset.bytes);
+                            if bytes.contains("PermissionRequestResult(Denied)") {
+                                received_plugin_bytes
+                                    .lock()
+                                    .unwrap()
+                                    .push(bytes.to_string());
+                            }
+                        }
+                        if !received_plugin_bytes.lock().unwrap().is_empty() {
+                            break;
+                        }
+                    },
+                    ScreenInstruction::Exit => {
+                        break;
+                    },
+                    _ => {},
+                }
+            }
+        })
+        .unwrap();
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin.clone(),
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let plugin_url = PathBuf::from(&*PLUGIN_FIXTURE).display().to_string();
+    let mut permission_cache = PermissionCache::from_path_or_default(Some(cache_path.clone()));
+    assert!(permission_cache.revoke(plugin_url.clone(), &[PermissionType::RunCommands]));
+    permission_cache.write_to_file().unwrap();
+    let _ = plugin_thread_sender.send(PluginInstruction::PermissionCacheChanged(Some(
+        cache_path.clone(),
+    )));
+    screen_thread.join().unwrap();
+    teardown();
+
+    let permission_cache = PermissionCache::from_path_or_default(Some(cache_path));
+    let remaining_permissions = permission_cache.get_permissions(plugin_url).unwrap();
+    assert!(!remaining_permissions.contains(&PermissionType::RunCommands));
+    assert!(remaining_permissions.contains(&PermissionType::ReadApplicationState));
+    assert!(!received_plugin_bytes.lock().unwrap().is_empty());
+}
+
 #[test]
 #[ignore]
 pub fn denied_permission_request_result() {
@@ -6319,6 +6456,7 @@ pub fn denied_permission_request_result() {
                                 0,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
                                 PermissionStatus::Denied,
                                 Some(cache_path.clone()),
                             ));
@@ -6367,6 +6505,143 @@ pub fn denied_permission_request_result() {
     assert_snapshot!(format!("{:#?}", permissions));
 }
 
+#[test]
+#[ignore]
+pub fn granted_read_only_filesystem_permission() {
+    let temp_folder = tempdir().unwrap();
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let notes_folder = plugin_host_folder.join("notes");
+    std::fs::create_dir(&notes_folder).unwrap();
+    std::fs::write(notes_folder.join("todo.md"), "buy milk").unwrap();
+
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder), None);
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+
+    // here we create a fake screen thread that grants every permission request it gets and
+    // exits once the plugin rendered what it found in the notes folder
+    let received_plugin_bytes = Arc::new(Mutex::new(vec![]));
+    let screen_thread = std::thread::Builder::new()
+        .name("fake_screen_thread".to_string())
+        .spawn({
+            let cache_path = cache_path.clone();
+            let plugin_thread_sender = plugin_thread_sender.clone();
+            let received_plugin_bytes = received_plugin_bytes.clone();
+            move || loop {
+                let (event, _err_ctx) = screen_receiver
+                    .recv()
+                    .expect("failed to receive event on channel");
+                match event {
+                    ScreenInstruction::RequestPluginPermissions(_, plugin_permission) => {
+                        let _ =
+                            plugin_thread_sender.send(PluginInstruction::PermissionRequestResult(
+                                0,
+                                Some(client_id),
+                                plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
+                                PermissionStatus::Granted,
+                                Some(cache_path.clone()),
+                            ));
+                    },
+                    ScreenInstruction::PluginBytes(plugin_render_assets) => {
+                        for plugin_render_asset in plugin_render_assets {
+                            let bytes = String::from_utf8_lossy(&plugin_render_asset.bytes);
+                            if bytes.contains("entries:") {
+                                received_plugin_bytes
+                                    .lock()
+                                    .unwrap()
+                                    .push(bytes.to_string());
+                            }
+                        }
+                        if !received_plugin_bytes.lock().unwrap().is_empty() {
+                            break;
+                        }
+                    },
+                    ScreenInstruction::Exit => {
+                        break;
+                    },
+                    _ => {},
+                }
+            }
+        })
+        .unwrap();
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin.clone(),
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    for (name, payload) in [
+        (
+            "request_read_only_folder",
+            notes_folder.display().to_string(),
+        ),
+        ("read_and_write_folder", notes_folder.display().to_string()),
+    ] {
+        let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+            pipe_id: "input_pipe_id".to_owned(),
+            name: name.to_owned(),
+            payload: Some(payload),
+            plugin: None, // broadcast
+            args: None,
+            configuration: None,
+            floating: None,
+            pane_id_to_replace: None,
+            pane_title: None,
+            cwd: None,
+            skip_cache: false,
+            cli_client_id: client_id,
+        });
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+    screen_thread.join().unwrap();
+    teardown();
+
+    let received_plugin_bytes = received_plugin_bytes.lock().unwrap();
+    assert!(
+        received_plugin_bytes[0].contains("entries: [\"todo.md\"], written: false"),
+        "plugin can list the granted folder but not write to it: {:?}",
+        received_plugin_bytes
+    );
+    assert!(!notes_folder.join("new_file").exists());
+    let permission_cache = PermissionCache::from_path_or_default(Some(cache_path));
+    assert_eq!(
+        permission_cache
+            .get_filesystem_permissions(PathBuf::from(&*PLUGIN_FIXTURE).display().to_string()),
+        Some(&vec![FilesystemPermission::read_only(
+            notes_folder.display().to_string()
+        )])
+    );
+}
+
 #[test]
 #[ignore]
 pub fn run_command_plugin_command() {
@@ -6616,6 +6891,96 @@ pub fn web_request_plugin_command() {
     assert_snapshot!(format!("{:#?}", new_tab_event));
 }
 
+#[test]
+#[ignore]
+pub fn web_request_with_allowed_hosts_plugin_command() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, background_jobs_receiver, screen_receiver, teardown) =
+        create_plugin_thread_with_background_jobs_receiver(Some(plugin_host_folder), None);
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        allowed_hosts: vec!["api.open-meteo.com".to_owned()],
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_background_jobs_instructions = Arc::new(Mutex::new(vec![]));
+    let background_jobs_thread = log_actions_in_thread!(
+        received_background_jobs_instructions,
+        BackgroundJob::WebRequest,
+        background_jobs_receiver,
+        1
+    );
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let _screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::WebAccess,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+        None,
+        Some(client_id),
+        Event::Key(KeyWithModifier::new(BareKey::Char('4')).with_ctrl_modifier()), // this triggers the enent in the fixture plugin
+    )]));
+    background_jobs_thread.join().unwrap(); // this might take a while if the cache is cold
+    teardown();
+    let new_tab_event = received_background_jobs_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let BackgroundJob::WebRequest(..) = i {
+                Some(i.clone())
+            } else {
+                None
+            }
+        })
+        .clone();
+    match new_tab_event {
+        Some(BackgroundJob::WebRequest(_, _, url, _, _, _, _, _, allowed_hosts)) => {
+            assert_eq!(url, "https://example.com/foo?arg1=val1&arg2=val2");
+            assert_eq!(allowed_hosts, vec!["api.open-meteo.com".to_owned()]);
+        },
+        _ => panic!("Expected a web request, got: {:?}", new_tab_event),
+    }
+}
+
 #[test]
 #[ignore]
 pub fn unblock_input_plugin_command() {
@@ -6978,6 +7343,98 @@ pub fn pipe_message_to_plugin_plugin_command() {
     assert_snapshot!(format!("{:#?}", plugin_bytes_event));
 }
 
+#[test]
+#[ignore]
+pub fn send_plugin_request_plugin_command() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder), None);
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::PluginBytes,
+        screen_receiver,
+        4,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "plugin_request_to_self".to_owned(),
+        payload: None,
+        plugin: None, // broadcast
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_bytes_event = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::PluginBytes(plugin_render_assets) = i {
+                for plugin_render_asset in plugin_render_assets {
+                    let plugin_id = plugin_render_asset.plugin_id;
+                    let client_id = plugin_render_asset.client_id;
+                    let plugin_bytes = plugin_render_asset.bytes.clone();
+                    let plugin_bytes = String::from_utf8_lossy(plugin_bytes.as_slice()).to_string();
+                    if plugin_bytes.contains("Reply from plugin:") {
+                        return Some((plugin_id, client_id, plugin_bytes));
+                    }
+                }
+            }
+            None
+        });
+    assert_snapshot!(format!("{:#?}", plugin_bytes_event));
+}
+
 #[test]
 #[ignore]
 pub fn switch_session_plugin_command() {
@@ -7434,6 +7891,9 @@ pub fn run_plugin_in_specific_cwd() {
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
         initial_cwd: Some(plugin_initial_cwd.clone()),
+        sha256: None,
+        resource_limits: Default::default(),
+        allowed_hosts: vec![],
     });
     let tab_index = 1;
     let client_id = 1;
@@ -9263,6 +9723,81 @@ pub fn reload_plugin_plugin_command() {
     assert_eq!(request_state_update_requests, 2);
 }
 
+#[test]
+#[ignore]
+pub fn dev_mode_plugin_is_reloaded_when_its_file_changes() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let plugin_path = plugin_host_folder.join("fixture-plugin-for-tests.wasm");
+    std::fs::copy(&*PLUGIN_FIXTURE, &plugin_path).unwrap();
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder), None);
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let mut configuration = BTreeMap::new();
+    configuration.insert("dev_mode".to_owned(), "true".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(plugin_path.clone()),
+        configuration: PluginUserConfiguration::new(configuration),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::RequestStateUpdateForPlugins, // happens on successful plugin (re)load
+        screen_receiver,
+        2,
+        &PermissionType::ChangeApplicationState,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    std::fs::copy(&*PLUGIN_FIXTURE, &plugin_path).unwrap(); // as if the plugin was recompiled
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    teardown();
+    let received_screen_instructions = received_screen_instructions.lock().unwrap();
+    let request_state_update_requests = received_screen_instructions
+        .iter()
+        .filter(|i| matches!(i, ScreenInstruction::RequestStateUpdateForPlugins))
+        .count();
+    assert_eq!(request_state_update_requests, 2);
+    let state_was_restored = received_screen_instructions
+        .iter()
+        .any(|i| matches!(i, ScreenInstruction::SetSelectable(_, false))); // this is what the fixture
+                                                                           // plugin does when its state
+                                                                           // is restored
+    assert!(state_was_restored);
+}
+
 #[test]
 #[ignore]
 pub fn load_new_plugin_plugin_command() {
@@ -12953,6 +13488,7 @@ pub fn mode_update_payload_is_lightweight_for_opted_in_plugins() {
                                 plugin_id,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
                                 PermissionStatus::Granted,
                                 Some(cache_path.clone()),
                             ));
@@ -13123,6 +13659,7 @@ pub fn reconfiguration_resends_keybinds_to_opted_in_plugins() {
                                 plugin_id,
                                 Some(client_id),
                                 plugin_permission.permissions,
+                                plugin_permission.filesystem_permissions,
                                 PermissionStatus::Granted,
                                 Some(cache_path.clone()),
                             ));
@@ -13211,3 +13748,104 @@ pub fn reconfiguration_resends_keybinds_to_opted_in_plugins() {
         last_render
     );
 }
+
+#[test]
+#[ignore]
+pub fn plugin_running_out_of_fuel_while_loading_is_reported_as_crashed() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let (plugin_thread_sender, background_jobs_receiver, screen_receiver, teardown) =
+        create_plugin_thread_with_engine_and_background_jobs_receiver(
+            crate::get_engine(),
+            Some(plugin_host_folder),
+            None,
+        );
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        resource_limits: PluginResourceLimits {
+            fuel_budget: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_background_jobs_instructions = Arc::new(Mutex::new(vec![]));
+    let background_jobs_thread = log_actions_in_thread!(
+        received_background_jobs_instructions,
+        BackgroundJob::ReportPluginCrash,
+        background_jobs_receiver,
+        1
+    );
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::UpdatePluginLoadingStage,
+        screen_receiver,
+        1
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        false, // close_replaced_pane
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    background_jobs_thread.join().unwrap(); // this might take a while if the cache is cold
+    screen_thread.join().unwrap();
+    teardown();
+    let crash_reason = received_background_jobs_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let BackgroundJob::ReportPluginCrash(_plugin_id, crash_reason) = i {
+                Some(crash_reason.clone())
+            } else {
+                None
+            }
+        })
+        .flatten();
+    assert_eq!(
+        crash_reason.as_deref(),
+        Some("Used up its fuel budget of 1 in a single callback")
+    );
+    let loading_error = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::UpdatePluginLoadingStage(_plugin_id, loading_indication) = i {
+                Some(format!("{:?}", loading_indication))
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    assert!(
+        loading_error.contains("Used up its fuel budget of 1 in a single callback and was stopped"),
+        "the loading error should be the crash message, got: {}",
+        loading_error
+    );
+}
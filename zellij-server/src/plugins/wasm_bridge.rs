@@ -1,20 +1,26 @@
 use super::{PinnedExecutor, PluginId, PluginInstruction};
 use crate::global_async_runtime::get_tokio_runtime;
 use crate::plugins::pipes::{
-    apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PipeStateChange,
+    apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PendingPluginRequests,
+    PipeStateChange,
 };
 use crate::plugins::plugin_loader::PluginLoader;
 use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions};
 
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::watch_filesystem::watch_filesystem;
+use crate::plugins::watch_filesystem::{
+    watch_filesystem, watch_permission_cache, watch_plugin_files,
+};
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use highway::{HighwayHash, PortableHash};
 use log::info;
-use notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, RecommendedCache};
+use notify_debouncer_full::{
+    notify::{RecommendedWatcher, RecursiveMode},
+    Debouncer, RecommendedCache,
+};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, Mutex},
 };
@@ -23,10 +29,11 @@ use url::Url;
 use wasmi::{Engine, Module};
 use zellij_utils::consts::{ZELLIJ_CACHE_DIR, ZELLIJ_SESSION_CACHE_DIR, ZELLIJ_TMP_DIR};
 use zellij_utils::data::{
-    FloatingPaneCoordinates, InputMode, KeybindsVec, LayoutInfo, LayoutWithError, PaneContents,
-    PaneRenderReport, PermissionStatus, PermissionType, PipeMessage, PipeSource,
+    FilesystemPermission, FloatingPaneCoordinates, InputMode, KeybindsVec, LayoutInfo,
+    LayoutWithError, PaneContents, PaneRenderReport, PermissionStatus, PermissionType, PipeMessage,
+    PipeSource, PluginReply,
 };
-use zellij_utils::downloader::Downloader;
+use zellij_utils::downloader::{Downloader, DownloaderError};
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::plugin_api::event::ProtobufEvent;
@@ -53,12 +60,12 @@ use zellij_utils::{
 /// On Windows, colons in URL strings (e.g. `zellij:tab-bar`, `file:///...`)
 /// are illegal in path components. Replace them with underscores.
 #[cfg(windows)]
-fn make_plugin_url_path_safe(url: String) -> String {
+pub(super) fn make_plugin_url_path_safe(url: String) -> String {
     url.replace(':', "_")
 }
 
 #[cfg(not(windows))]
-fn make_plugin_url_path_safe(url: String) -> String {
+pub(super) fn make_plugin_url_path_safe(url: String) -> String {
     url
 }
 
@@ -109,6 +116,9 @@ pub struct LoadingContext {
     pub keybinds: Keybinds,
     pub plugin_dir: PathBuf,
     pub size: Size,
+    /// state saved by the previous instance of a plugin reloaded in `dev_mode`, to be restored
+    /// once it is loaded
+    pub saved_state: Option<String>,
 }
 
 impl LoadingContext {
@@ -158,6 +168,7 @@ impl LoadingContext {
             tab_index,
             plugin_dir: wasm_bridge.plugin_dir.clone(),
             size,
+            saved_state: None,
         }
     }
     pub fn update_plugin_path(&mut self, new_path: PathBuf) {
@@ -185,12 +196,16 @@ pub struct WasmBridge {
     pending_plugin_reloads: HashSet<RunPlugin>,
     path_to_default_shell: PathBuf,
     watcher: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
+    dev_mode_watcher: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
+    permission_cache_watcher: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
+    dev_mode_watched_folders: HashSet<PathBuf>,
     zellij_cwd: PathBuf,
     session_env_vars: std::collections::BTreeMap<String, String>,
     default_shell: Option<TerminalAction>,
     cached_plugin_map:
         HashMap<RunPluginLocation, HashMap<PluginUserConfiguration, Vec<(PluginId, ClientId)>>>,
     pending_pipes: PendingPipes,
+    pending_plugin_requests: PendingPluginRequests,
     layout_dir: Option<PathBuf>,
     available_layouts: Vec<LayoutInfo>,
     available_layout_errors: Vec<LayoutWithError>,
@@ -241,6 +256,9 @@ impl WasmBridge {
             plugin_executor,
             path_to_default_shell,
             watcher,
+            dev_mode_watcher: None,
+            permission_cache_watcher: None,
+            dev_mode_watched_folders: HashSet::new(),
             next_plugin_id: 0,
             cached_events_for_pending_plugins: HashMap::new(),
             plugin_ids_waiting_for_permission_request: HashSet::new(),
@@ -253,6 +271,7 @@ impl WasmBridge {
             default_shell,
             cached_plugin_map: HashMap::new(),
             pending_pipes: Default::default(),
+            pending_plugin_requests: Default::default(),
             layout_dir,
             available_layouts,
             available_layout_errors,
@@ -325,6 +344,9 @@ impl WasmBridge {
                     },
                 };
                 let plugin_name = run.location.to_string();
+                if plugin.is_in_dev_mode() {
+                    self.watch_dev_mode_plugin(&plugin.path);
+                }
 
                 self.cached_events_for_pending_plugins
                     .insert(plugin_id, vec![]);
@@ -365,9 +387,30 @@ impl WasmBridge {
                                 .map(ToString::to_string)
                                 .collect();
 
-                            match downloader.download(url, Some(&file_name)).await {
+                            match downloader
+                                .download(url, Some(&file_name), plugin.sha256.as_deref())
+                                .await
+                            {
                                 Ok(_) => loading_context
                                     .update_plugin_path(ZELLIJ_CACHE_DIR.join(&file_name)),
+                                Err(e @ DownloaderError::IntegrityMismatch { .. }) => {
+                                    let _ = senders.send_to_background_jobs(
+                                        BackgroundJob::DisplayPaneError(
+                                            vec![PaneId::Plugin(plugin_id)],
+                                            "SHA256 MISMATCH!".into(),
+                                        ),
+                                    );
+                                    // the url, both digests and how to fix the pin, rather than
+                                    // the debug representation of the error
+                                    handle_plugin_loading_failure(
+                                        &senders,
+                                        plugin_id,
+                                        &mut loading_indication,
+                                        anyError::new(e),
+                                        Some(client_id),
+                                    );
+                                    return;
+                                },
                                 Err(e) => {
                                     handle_plugin_loading_failure(
                                         &senders,
@@ -573,14 +616,33 @@ impl WasmBridge {
                 .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_name))
                 .context("failed to unblock input pipe");
         }
+        let failed_plugin_requests = self.pending_plugin_requests.unload_plugin(&pid);
+        if !failed_plugin_requests.is_empty() {
+            let _ = self.senders.send_to_plugin(PluginInstruction::Update(
+                failed_plugin_requests
+                    .into_iter()
+                    .map(|(plugin_id, client_id, event)| (Some(plugin_id), Some(client_id), event))
+                    .collect(),
+            ));
+        }
         let plugin_list = self.plugin_map.lock().unwrap().list_plugins();
         let _ = self
             .senders
             .send_to_background_jobs(BackgroundJob::ReportPluginList(plugin_list));
+        let _ = self
+            .senders
+            .send_to_background_jobs(BackgroundJob::ReportPluginCrash(pid, None));
 
         Ok(())
     }
     pub fn reload_plugin_with_id(&mut self, plugin_id: u32) -> Result<()> {
+        self.reload_plugin_with_id_and_state(plugin_id, None)
+    }
+    fn reload_plugin_with_id_and_state(
+        &mut self,
+        plugin_id: u32,
+        saved_state: Option<String>,
+    ) -> Result<()> {
         let Some(run_plugin) = self.run_plugin_of_plugin_id(plugin_id).map(|r| r.clone()) else {
             log::error!("Failed to find plugin with id: {}", plugin_id);
             return Ok(());
@@ -621,7 +683,7 @@ impl WasmBridge {
 
         let cwd = self.cwd_of_plugin_id(plugin_id);
 
-        let loading_context = LoadingContext::new(
+        let mut loading_context = LoadingContext::new(
             &self,
             cwd,
             plugin_config,
@@ -630,6 +692,7 @@ impl WasmBridge {
             tab_index,
             size,
         );
+        loading_context.saved_state = saved_state;
 
         plugin_executor.execute_for_plugin(
             plugin_id,
@@ -667,6 +730,91 @@ impl WasmBridge {
         );
         Ok(())
     }
+    pub fn watch_dev_mode_plugin(&mut self, plugin_path: &Path) {
+        // we watch the folder rather than the file itself, because build tools often replace the
+        // file rather than write to it
+        let Some(plugin_folder) = plugin_path
+            .parent()
+            .and_then(|folder| std::fs::canonicalize(folder).ok())
+        else {
+            log::error!(
+                "Cannot watch plugin {} for changes, its folder does not exist",
+                plugin_path.display()
+            );
+            return;
+        };
+        if self.dev_mode_watched_folders.contains(&plugin_folder) {
+            return;
+        }
+        if self.dev_mode_watcher.is_none() {
+            self.dev_mode_watcher = match watch_plugin_files(self.senders.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    log::error!("Failed to watch plugin files: {:?}", e);
+                    return;
+                },
+            };
+        }
+        if let Some(watcher) = self.dev_mode_watcher.as_mut() {
+            match watcher.watch(&plugin_folder, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.dev_mode_watched_folders.insert(plugin_folder);
+                },
+                Err(e) => log::error!("Failed to watch {}: {:?}", plugin_folder.display(), e),
+            }
+        }
+    }
+    pub fn reload_dev_mode_plugins(&mut self, changed_paths: &[PathBuf]) -> Result<()> {
+        let plugin_ids = self.plugin_map.lock().unwrap().plugin_ids();
+        for plugin_id in plugin_ids {
+            let Some(plugin_config) = self.plugin_config_of_plugin_id(plugin_id) else {
+                continue;
+            };
+            let plugin_file_changed = plugin_config.is_in_dev_mode()
+                && canonical_path(&plugin_config.path)
+                    .map(|plugin_path| changed_paths.contains(&plugin_path))
+                    .unwrap_or(false);
+            if plugin_file_changed
+                && !self.plugin_is_currently_being_loaded(&plugin_config.location)
+            {
+                log::info!(
+                    "Reloading plugin {} after its wasm file changed",
+                    plugin_config.location
+                );
+                let saved_state = self.save_plugin_state(plugin_id);
+                self.reload_plugin_with_id_and_state(plugin_id, saved_state)?;
+            }
+        }
+        Ok(())
+    }
+    fn save_plugin_state(&self, plugin_id: PluginId) -> Option<String> {
+        let running_plugin = self
+            .plugin_map
+            .lock()
+            .unwrap()
+            .get_running_plugin(plugin_id, None)?;
+        let mut running_plugin = running_plugin.lock().unwrap();
+        let instance = running_plugin.instance;
+        // plugins compiled against an older zellij-tile do not have this function
+        let save_state = instance
+            .get_typed_func::<(), ()>(&mut running_plugin.store, "save_state")
+            .ok()?;
+        running_plugin.refuel();
+        let saved_state = save_state
+            .call(&mut running_plugin.store, ())
+            .map_err(anyError::new)
+            .and_then(|_| wasi_read_string(running_plugin.store.data()))
+            .and_then(|saved_state| {
+                serde_json::from_str::<Option<String>>(&saved_state).map_err(anyError::new)
+            });
+        match saved_state {
+            Ok(saved_state) => saved_state,
+            Err(e) => {
+                log::error!("Failed to save state of plugin {}: {:?}", plugin_id, e);
+                None
+            },
+        }
+    }
     pub fn reload_plugin(&mut self, run_plugin: &RunPlugin) -> Result<()> {
         if self.plugin_is_currently_being_loaded(&run_plugin.location) {
             self.pending_plugin_reloads.insert(run_plugin.clone());
@@ -819,7 +967,9 @@ impl WasmBridge {
                     move |senders, _plugin_map, _connected_clients, _plugin_cache, _engine| {
                         let mut running_plugin = running_plugin.lock().unwrap();
                         let _s = _s; // guard to allow the task to complete before cleanup/shutdown
-                        if running_plugin.apply_event_id(AtomicEvent::Resize, event_id) {
+                        if running_plugin.apply_event_id(AtomicEvent::Resize, event_id)
+                            && !running_plugin.is_out_of_fuel()
+                        {
                             let old_rows = running_plugin.rows;
                             let old_columns = running_plugin.columns;
                             running_plugin.rows = new_rows;
@@ -836,12 +986,13 @@ impl WasmBridge {
                                         "render",
                                     )
                                     .and_then(|render| {
+                                        running_plugin.refuel();
                                         render.call(
                                             &mut running_plugin.store,
                                             (new_rows as i32, new_columns as i32),
                                         )
                                     })
-                                    .map_err(|e| anyhow!(e))
+                                    .map_err(|e| running_plugin.handle_call_error(e))
                                     .and_then(|_| {
                                         wasi_read_string(running_plugin.store.data())
                                             .map_err(|e| anyhow!(e))
@@ -860,7 +1011,18 @@ impl WasmBridge {
                                             ]))
                                             .unwrap();
                                     },
-                                    Err(e) => log::error!("{}", e),
+                                    Err(e) => {
+                                        log::error!("{}", e);
+                                        if running_plugin.is_out_of_fuel() {
+                                            let stringified_error =
+                                                format!("{:?}", e).replace("\n", "\n\r");
+                                            handle_plugin_crash(
+                                                plugin_id,
+                                                stringified_error,
+                                                senders.clone(),
+                                            );
+                                        }
+                                    },
                                 }
                             }
                         }
@@ -1062,6 +1224,7 @@ impl WasmBridge {
                                 &plugin_env.plugin_own_data_dir,
                                 &plugin_env.plugin_own_cache_dir,
                                 &ZELLIJ_TMP_DIR,
+                                &plugin_env.filesystem_permissions,
                                 &plugin_env.plugin.location.to_string(),
                                 plugin_env.plugin_id,
                                 stdin_pipe.clone(),
@@ -1377,6 +1540,12 @@ impl WasmBridge {
         if let Some(watcher) = self.watcher.take() {
             watcher.stop_nonblocking();
         }
+        if let Some(watcher) = self.dev_mode_watcher.take() {
+            watcher.stop_nonblocking();
+        }
+        if let Some(watcher) = self.permission_cache_watcher.take() {
+            watcher.stop_nonblocking();
+        }
     }
     pub fn run_plugin_of_loading_plugin_id(&self, plugin_id: PluginId) -> Option<&RunPlugin> {
         self.loading_plugins
@@ -1532,6 +1701,16 @@ impl WasmBridge {
                                                     },
                                                     Err(e) => {
                                                         log::error!("{}", e);
+                                                        if running_plugin.is_out_of_fuel() {
+                                                            let stringified_error =
+                                                                format!("{:?}", e)
+                                                                    .replace("\n", "\n\r");
+                                                            handle_plugin_crash(
+                                                                plugin_id,
+                                                                stringified_error,
+                                                                senders.clone(),
+                                                            );
+                                                        }
                                                     },
                                                 }
                                             },
@@ -1656,6 +1835,24 @@ impl WasmBridge {
             None => vec![],
         }
     }
+    pub fn all_plugin_and_client_ids_with_location(
+        &mut self,
+        plugin_location: &RunPluginLocation,
+    ) -> Vec<(PluginId, ClientId)> {
+        if self.cached_plugin_map.is_empty() {
+            self.cached_plugin_map = self.plugin_map.lock().unwrap().clone_plugin_assets();
+        }
+        self.cached_plugin_map
+            .get(plugin_location)
+            .map(|plugins_by_configuration| {
+                plugins_by_configuration
+                    .values()
+                    .flatten()
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
     pub fn all_plugin_ids(&self) -> Vec<(PluginId, ClientId)> {
         self.plugin_map.lock().unwrap().all_plugin_ids()
     }
@@ -1765,6 +1962,7 @@ impl WasmBridge {
         plugin_id: PluginId,
         client_id: Option<ClientId>,
         permissions: Vec<PermissionType>,
+        filesystem_permissions: Vec<FilesystemPermission>,
         status: PermissionStatus,
         cache_path: Option<PathBuf>,
     ) -> Result<()> {
@@ -1779,25 +1977,106 @@ impl WasmBridge {
 
         let mut running_plugin = running_plugin.lock().unwrap();
 
+        let plugin_env = running_plugin.store.data_mut();
+        let plugin_name = plugin_env.plugin.location.to_string();
+        if cache_path.is_none() {
+            self.watch_permission_cache();
+        }
+        let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
+
         let permissions = if status == PermissionStatus::Granted {
+            // folders granted to earlier requests stay granted, the plugin's preopens are built
+            // from all of them when it is next loaded
+            permission_cache
+                .grant_filesystem_permissions(plugin_name.clone(), filesystem_permissions);
             permissions
         } else {
+            permission_cache.cache_filesystem_permissions(plugin_name.clone(), vec![]);
             vec![]
         };
+        permission_cache.cache(plugin_name.clone(), permissions.clone());
+        if permissions.contains(&PermissionType::WebAccess) {
+            permission_cache
+                .grant_web_access(plugin_name.clone(), plugin_env.plugin.allowed_hosts.clone());
+        }
+        let filesystem_permissions = permission_cache
+            .get_filesystem_permissions(plugin_name)
+            .cloned()
+            .unwrap_or_default();
 
-        running_plugin
-            .store
-            .data_mut()
-            .set_permissions(HashSet::from_iter(permissions.clone()));
-
-        let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
-        permission_cache.cache(
-            running_plugin.store.data().plugin.location.to_string(),
-            permissions,
-        );
+        plugin_env.set_permissions(HashSet::from_iter(permissions));
+        mount_filesystem_permissions(plugin_env, filesystem_permissions)
+            .with_context(err_context)?;
 
         permission_cache.write_to_file().with_context(err_context)
     }
+    // brings the permissions of running plugins in line with the permission cache after it was
+    // changed (eg. with `zellij plugin permissions revoke`) so that revoking a permission applies
+    // immediately, returns the plugins that lost permissions
+    //
+    // granting permissions only applies the next time the plugin asks for them
+    pub fn revoke_uncached_permissions(
+        &mut self,
+        cache_path: Option<PathBuf>,
+    ) -> Vec<(PluginId, ClientId)> {
+        let permission_cache = PermissionCache::from_path_or_default(cache_path);
+        let mut plugins_with_revoked_permissions = vec![];
+        let running_plugins = self.plugin_map.lock().unwrap().running_plugins();
+        for (plugin_id, client_id, running_plugin) in running_plugins {
+            let mut running_plugin = running_plugin.lock().unwrap();
+            let plugin_env = running_plugin.store.data_mut();
+            let plugin_name = plugin_env.plugin.location.to_string();
+            let Some(granted) = plugin_env.permissions.lock().unwrap().clone() else {
+                continue; // this plugin never asked for permissions
+            };
+            let cached = permission_cache
+                .get_permissions(plugin_name.clone())
+                .cloned()
+                .unwrap_or_default();
+            let cached_filesystem = permission_cache
+                .get_filesystem_permissions(plugin_name)
+                .cloned()
+                .unwrap_or_default();
+            let remaining: HashSet<PermissionType> = granted
+                .iter()
+                .filter(|p| cached.contains(p))
+                .copied()
+                .collect();
+            let remaining_filesystem: Vec<FilesystemPermission> = plugin_env
+                .filesystem_permissions
+                .iter()
+                .filter(|p| cached_filesystem.iter().any(|c| c.covers(p)))
+                .cloned()
+                .collect();
+            if remaining.len() == granted.len()
+                && remaining_filesystem.len() == plugin_env.filesystem_permissions.len()
+            {
+                continue;
+            }
+            log::info!(
+                "Revoking permissions of plugin {}: {:?}",
+                plugin_env.name(),
+                granted.difference(&remaining).collect::<Vec<_>>()
+            );
+            plugin_env.set_permissions(remaining);
+            if let Err(e) = mount_filesystem_permissions(plugin_env, remaining_filesystem) {
+                log::error!("Failed to unmount revoked folders: {:?}", e);
+            }
+            plugins_with_revoked_permissions.push((plugin_id, client_id));
+        }
+        plugins_with_revoked_permissions
+    }
+    pub fn watch_permission_cache(&mut self) {
+        if self.permission_cache_watcher.is_none() {
+            self.permission_cache_watcher = match watch_permission_cache(self.senders.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    log::error!("Failed to watch the plugin permission cache: {}", e);
+                    None
+                },
+            };
+        }
+    }
     pub fn cache_plugin_events(&mut self, plugin_id: PluginId) {
         self.plugin_ids_waiting_for_permission_request
             .insert(plugin_id);
@@ -1886,6 +2165,38 @@ impl WasmBridge {
             },
         }
     }
+    // returns the id of the new request
+    pub fn add_plugin_request(
+        &mut self,
+        source_plugin_id: PluginId,
+        source_client_id: ClientId,
+        context: BTreeMap<String, String>,
+    ) -> u64 {
+        self.pending_plugin_requests
+            .add_request(source_plugin_id, source_client_id, context)
+    }
+    pub fn add_plugin_request_recipient(&mut self, request_id: u64, plugin_id: PluginId) {
+        self.pending_plugin_requests
+            .add_recipient(request_id, plugin_id);
+    }
+    // returns the update to send to the plugin that made the request, if it is still pending
+    pub fn resolve_plugin_request(
+        &mut self,
+        request_id: u64,
+        reply: PluginReply,
+    ) -> Option<(PluginId, ClientId, Event)> {
+        self.pending_plugin_requests.resolve(request_id, reply)
+    }
+    // returns the update to send to the plugin that made the request, if it is still pending
+    pub fn reply_to_plugin_request(
+        &mut self,
+        request_id: u64,
+        replying_plugin_id: PluginId,
+        reply: PluginReply,
+    ) -> Option<(PluginId, ClientId, Event)> {
+        self.pending_plugin_requests
+            .reply(request_id, replying_plugin_id, reply)
+    }
     pub fn clear_plugin_map_cache(&mut self) {
         self.cached_plugin_map.clear();
     }
@@ -2016,6 +2327,7 @@ fn handle_plugin_successful_loading(
     let _ = senders.send_to_background_jobs(BackgroundJob::StopPluginLoadingAnimation(plugin_id));
     let _ = senders.send_to_screen(ScreenInstruction::RequestStateUpdateForPlugins);
     let _ = senders.send_to_background_jobs(BackgroundJob::ReportPluginList(plugin_list));
+    let _ = senders.send_to_background_jobs(BackgroundJob::ReportPluginCrash(plugin_id, None));
     let _ = senders.send_to_plugin(PluginInstruction::RequestStateUpdateForPlugin(plugin_id));
 }
 
@@ -2066,6 +2378,7 @@ fn check_event_permission(
         | Event::EditPaneExited(..)
         | Event::FailedToWriteConfigToDisk(..)
         | Event::CommandPaneReRun(..)
+        | Event::CommandPaneRestarted(..)
         | Event::CwdChanged(..)
         | Event::CommandChanged(..)
         | Event::AvailableLayoutInfo(..)
@@ -2074,6 +2387,7 @@ fn check_event_permission(
         | Event::SoftKeyboardVisibilityChanged(..)
         | Event::HintText(..)
         | Event::ActivePaneScroll(..)
+        | Event::PaneMonitorAlert(..)
         | Event::InputReceived => PermissionType::ReadApplicationState,
         Event::WebServerStatus(..) => PermissionType::StartWebServer,
         Event::PaneRenderReport(..) => PermissionType::ReadPaneContents,
@@ -2099,7 +2413,10 @@ pub fn apply_event_to_plugin(
     senders: ThreadSenders,
     plugin_subscriptions: &HashSet<EventType>,
 ) -> Result<()> {
-    let instance = &running_plugin.instance;
+    if running_plugin.is_out_of_fuel() {
+        return Ok(());
+    }
+    let instance = running_plugin.instance;
     let rows = running_plugin.rows;
     let columns = running_plugin.columns;
 
@@ -2127,8 +2444,10 @@ pub fn apply_event_to_plugin(
                         .with_context(err_context)?;
                     wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
                         .with_context(err_context)?;
+                    running_plugin.refuel();
                     let should_render = update
                         .call(&mut running_plugin.store, ())
+                        .map_err(|e| running_plugin.handle_call_error(e))
                         .with_context(err_context)?;
                     let mut should_render = should_render == 1;
                     if let Event::PermissionRequestResult(..) = event {
@@ -2140,10 +2459,11 @@ pub fn apply_event_to_plugin(
                         let rendered_bytes = instance
                             .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
                             .and_then(|render| {
+                                running_plugin.refuel();
                                 render
                                     .call(&mut running_plugin.store, (rows as i32, columns as i32))
                             })
-                            .map_err(|e| anyhow!(e))
+                            .map_err(|e| running_plugin.handle_call_error(e))
                             .and_then(|_| {
                                 wasi_read_string(running_plugin.store.data())
                                     .map_err(|e| anyhow!(e))
@@ -2207,7 +2527,10 @@ pub fn apply_before_close_event_to_plugin(
     running_plugin: &mut RunningPlugin,
     senders: ThreadSenders,
 ) -> Result<()> {
-    let instance = &running_plugin.instance;
+    if running_plugin.is_out_of_fuel() {
+        return Ok(());
+    }
+    let instance = running_plugin.instance;
 
     let err_context = || format!("Failed to apply event to plugin {plugin_id}");
     let event = Event::BeforeClose;
@@ -2220,8 +2543,10 @@ pub fn apply_before_close_event_to_plugin(
         .with_context(err_context)?;
     wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
         .with_context(err_context)?;
+    running_plugin.refuel();
     let _should_render = update
         .call(&mut running_plugin.store, ())
+        .map_err(|e| running_plugin.handle_call_error(e))
         .with_context(err_context)?;
     let pipes_to_block_or_unblock = pipes_to_block_or_unblock(running_plugin, None);
     let plugin_render_asset =
@@ -2233,3 +2558,33 @@ pub fn apply_before_close_event_to_plugin(
         .context("failed to unblock input pipe");
     Ok(())
 }
+
+// the granted folders are mounted by recreating the plugin's wasi context
+fn mount_filesystem_permissions(
+    plugin_env: &mut PluginEnv,
+    filesystem_permissions: Vec<FilesystemPermission>,
+) -> Result<()> {
+    if plugin_env.filesystem_permissions == filesystem_permissions {
+        return Ok(());
+    }
+    let wasi_ctx = PluginLoader::create_wasi_ctx(
+        &plugin_env.plugin_cwd,
+        &plugin_env.plugin_own_data_dir,
+        &plugin_env.plugin_own_cache_dir,
+        &ZELLIJ_TMP_DIR,
+        &filesystem_permissions,
+        &plugin_env.plugin.location.to_string(),
+        plugin_env.plugin_id,
+        plugin_env.stdin_pipe.clone(),
+        plugin_env.stdout_pipe.clone(),
+    )?;
+    drop(std::mem::replace(&mut plugin_env.wasi_ctx, wasi_ctx));
+    plugin_env.filesystem_permissions = filesystem_permissions;
+    Ok(())
+}
+
+fn canonical_path(path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?;
+    let folder = std::fs::canonicalize(path.parent()?).ok()?;
+    Some(folder.join(file_name))
+}
@@ -10,7 +10,7 @@ use notify_debouncer_full::{
     notify::{EventKind, RecommendedWatcher, RecursiveMode},
     DebounceEventResult, Debouncer, RecommendedCache,
 };
-use zellij_utils::{data::Event, errors::prelude::Result};
+use zellij_utils::{consts::ZELLIJ_PLUGIN_PERMISSIONS_CACHE, data::Event, errors::prelude::Result};
 
 const DEBOUNCE_DURATION_MS: u64 = 400;
 
@@ -141,3 +141,66 @@ pub fn watch_filesystem(
     debouncer.watch(zellij_cwd, RecursiveMode::Recursive)?;
     Ok(debouncer)
 }
+
+/// Watch the folders of plugins in `dev_mode` (see `WasmBridge::watch_dev_mode_plugin`), reporting
+/// the files in them that were created or changed so that the matching plugins can be reloaded
+pub fn watch_plugin_files(
+    senders: ThreadSenders,
+) -> Result<Debouncer<RecommendedWatcher, RecommendedCache>> {
+    let debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let mut changed_paths: Vec<PathBuf> = events
+                    .into_iter()
+                    .filter(|event| {
+                        matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    })
+                    .flat_map(|event| event.event.paths)
+                    .collect();
+                changed_paths.dedup();
+                if !changed_paths.is_empty() {
+                    let _ = senders
+                        .send_to_plugin(PluginInstruction::PluginFilesChanged(changed_paths));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("plugin file watch error: {error:?}")),
+        },
+    )?;
+    Ok(debouncer)
+}
+
+/// Watch the plugin permission cache, so that permissions revoked by editing it (eg. with
+/// `zellij plugin permissions revoke`) are taken away from running plugins
+pub fn watch_permission_cache(
+    senders: ThreadSenders,
+) -> Result<Debouncer<RecommendedWatcher, RecommendedCache>> {
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let permission_cache_changed = events.iter().any(|event| {
+                    event
+                        .paths
+                        .iter()
+                        .any(|path| path == &*ZELLIJ_PLUGIN_PERMISSIONS_CACHE)
+                });
+                if permission_cache_changed {
+                    let _ = senders.send_to_plugin(PluginInstruction::PermissionCacheChanged(None));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("permission cache watch error: {error:?}")),
+        },
+    )?;
+    // the file itself is replaced rather than modified by some editors
+    if let Some(cache_folder) = ZELLIJ_PLUGIN_PERMISSIONS_CACHE.parent() {
+        debouncer.watch(cache_folder, RecursiveMode::NonRecursive)?;
+    }
+    Ok(debouncer)
+}
@@ -2,6 +2,7 @@ use super::PluginInstruction;
 use crate::background_jobs::BackgroundJob;
 use crate::global_async_runtime::get_tokio_runtime;
 use crate::plugins::plugin_map::PluginEnv;
+use crate::plugins::plugin_storage::PluginStorage;
 use crate::plugins::wasm_bridge::handle_plugin_crash;
 use crate::pty::{ClientTabIndexOrPaneId, PtyInstruction};
 use crate::route::{route_action, wait_for_action_completion, NotificationEnd};
@@ -23,7 +24,7 @@ use zellij_utils::consts::ipc_connect;
 use zellij_utils::data::{
     BreakPanesToNewTabResponse, BreakPanesToTabWithIdResponse, BreakPanesToTabWithIndexResponse,
     CommandType, ConnectToSession, DeleteAllDeadSessionsResponse, DeleteDeadSessionResponse,
-    DeleteLayoutResponse, EditLayoutResponse, Event, FloatingPaneCoordinates,
+    DeleteLayoutResponse, EditLayoutResponse, Event, FilesystemPermission, FloatingPaneCoordinates,
     FocusOrCreateTabResponse, GetFocusedPaneInfoResponse, GetPaneCwdResponse, GetPanePidResponse,
     GetPaneRunningCommandResponse, HttpVerb, KeyWithModifier, KillSessionsResponse, LayoutInfo,
     LayoutMetadata, LayoutParsingError, MessageToPlugin, NewPanePlacement, NewTabResponse,
@@ -37,8 +38,9 @@ use zellij_utils::data::{
     OpenPluginPaneFloatingResponse, OpenTerminalFloatingNearPluginResponse,
     OpenTerminalFloatingResponse, OpenTerminalInPlaceOfPluginResponse, OpenTerminalInPlaceResponse,
     OpenTerminalNearPluginResponse, OpenTerminalPaneInPlaceOfPaneIdResponse, OpenTerminalResponse,
-    OriginatingPlugin, PaneFrameStyle, PaneScrollbackResponse, PermissionStatus, PermissionType,
-    PluginPermission, RegexHighlight, RenameLayoutResponse, SaveLayoutResponse, TabMetadata,
+    OriginatingPlugin, PaneFrameStyle, PaneMonitors, PaneScrollbackResponse, PermissionStatus,
+    PermissionType, PluginPermission, PluginReply, RecordingTarget, RegexHighlight,
+    RenameLayoutResponse, SaveLayoutResponse, TabMetadata,
 };
 use zellij_utils::home::default_layout_dir;
 use zellij_utils::input::permission::PermissionCache;
@@ -46,7 +48,7 @@ use zellij_utils::ipc::{ClientToServerMsg, IpcSenderWithContext};
 use zellij_utils::sessions::generate_random_name as generate_random_name_impl;
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
-    create_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
+    create_restricted_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
 };
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
@@ -56,11 +58,15 @@ use kdl::KdlDocument;
 
 use prost::Message;
 use zellij_utils::{
-    consts::{VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR, ZELLIJ_TMP_DIR},
+    consts::{
+        VERSION, ZELLIJ_PLUGIN_STORAGE_DIR, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        ZELLIJ_TMP_DIR,
+    },
     data::{
         CommandOrPlugin, CommandToRun, Direction, EventType, FileToOpen, InputMode, PluginCommand,
-        PluginIds, PluginMessage, Resize, ResizeStrategy,
+        PluginIds, PluginMessage, Resize, ResizeStrategy, StorageScope,
     },
+    envs,
     errors::prelude::*,
     input::{
         actions::Action,
@@ -76,7 +82,7 @@ use zellij_utils::{
         plugin_command::{
             dump_layout_response, dump_session_layout_response, hide_floating_panes_response,
             parse_layout_response, save_session_response, show_floating_panes_response,
-            ProtobufBreakPanesToNewTabResponse, ProtobufBreakPanesToTabWithIdResponse,
+            ContextItem, ProtobufBreakPanesToNewTabResponse, ProtobufBreakPanesToTabWithIdResponse,
             ProtobufBreakPanesToTabWithIndexResponse, ProtobufDeleteAllDeadSessionsResponse,
             ProtobufDeleteDeadSessionResponse, ProtobufDeleteLayoutResponse,
             ProtobufDumpLayoutResponse, ProtobufDumpSessionLayoutResponse,
@@ -86,9 +92,10 @@ use zellij_utils::{
             ProtobufGetPanePidResponse, ProtobufGetPaneRunningCommandResponse,
             ProtobufGetSessionEnvironmentVariablesResponse, ProtobufGetSessionListResponse,
             ProtobufGetTabInfoResponse, ProtobufHideFloatingPanesResponse,
-            ProtobufKillSessionsResponse, ProtobufNewTabResponse, ProtobufNewTabUnfocusedResponse,
-            ProtobufNewTabsResponse, ProtobufNewTiledPaneInTabResponse,
-            ProtobufOpenCommandPaneBackgroundResponse,
+            ProtobufKillSessionsResponse, ProtobufListGrantedPermissionsResponse,
+            ProtobufListStorageValuesResponse, ProtobufNewTabResponse,
+            ProtobufNewTabUnfocusedResponse, ProtobufNewTabsResponse,
+            ProtobufNewTiledPaneInTabResponse, ProtobufOpenCommandPaneBackgroundResponse,
             ProtobufOpenCommandPaneFloatingNearPluginResponse,
             ProtobufOpenCommandPaneFloatingResponse,
             ProtobufOpenCommandPaneInPlaceOfPaneIdResponse,
@@ -105,7 +112,7 @@ use zellij_utils::{
             ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse, ProtobufOpenTerminalResponse,
             ProtobufParseLayoutResponse, ProtobufPluginCommand, ProtobufRenameLayoutResponse,
             ProtobufSaveLayoutResponse, ProtobufSaveSessionResponse,
-            ProtobufShowFloatingPanesResponse,
+            ProtobufShowFloatingPanesResponse, ProtobufStorageValueResponse,
         },
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
@@ -266,8 +273,12 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         new_tabs_with_layout(env, &raw_layout)?
                     },
                     PluginCommand::NewTabsWithLayoutInfo(layout_info) => {
-                        new_tabs_with_layout_info(env, layout_info)?
+                        new_tabs_with_layout_info(env, layout_info, BTreeMap::new())?
                     },
+                    PluginCommand::NewTabsWithLayoutAndArgs {
+                        layout_info,
+                        layout_args,
+                    } => new_tabs_with_layout_info(env, layout_info, layout_args)?,
                     PluginCommand::OverrideLayout(
                         layout_info,
                         retain_existing_terminal_panes,
@@ -280,6 +291,23 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         retain_existing_terminal_panes,
                         retain_existing_plugin_panes,
                         apply_only_to_active_tab,
+                        BTreeMap::new(),
+                        context,
+                    )?,
+                    PluginCommand::OverrideLayoutWithArgs {
+                        layout_info,
+                        retain_existing_terminal_panes,
+                        retain_existing_plugin_panes,
+                        apply_only_to_active_tab,
+                        layout_args,
+                        context,
+                    } => override_layout(
+                        env,
+                        layout_info,
+                        retain_existing_terminal_panes,
+                        retain_existing_plugin_panes,
+                        apply_only_to_active_tab,
+                        layout_args,
                         context,
                     )?,
                     PluginCommand::SaveLayout {
@@ -306,6 +334,13 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::ToggleFloatingPanes { tab_id } => {
                         toggle_floating_panes(env, tab_id)
                     },
+                    PluginCommand::StartRecording { path, target } => {
+                        start_recording(env, path, target)
+                    },
+                    PluginCommand::StopRecording { path } => stop_recording(env, path),
+                    PluginCommand::SetPaneMonitors { pane_id, monitors } => {
+                        set_pane_monitors(env, pane_id, monitors)
+                    },
                     PluginCommand::NewPane => new_pane(env),
                     PluginCommand::GoToNextTab => go_to_next_tab(env),
                     PluginCommand::GoToPreviousTab => go_to_previous_tab(env),
@@ -345,6 +380,7 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::TogglePaneEmbedOrEject => toggle_pane_embed_or_eject(env),
                     PluginCommand::UndoRenamePane => undo_rename_pane(env),
                     PluginCommand::CloseFocus => close_focus(env),
+                    PluginCommand::ReopenClosedPane => reopen_closed_pane(env),
                     PluginCommand::ToggleActiveTabSync => toggle_active_tab_sync(env),
                     PluginCommand::CloseFocusedTab => close_focused_tab(env),
                     PluginCommand::UndoRenameTab => undo_rename_tab(env),
@@ -394,8 +430,17 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     },
                     PluginCommand::ReportPanic(crash_payload) => report_panic(env, &crash_payload),
                     PluginCommand::RequestPluginPermissions(permissions) => {
-                        request_permission(env, permissions)?
+                        request_permission(env, permissions, vec![])?
                     },
+                    PluginCommand::RequestPluginPermissionsWithFilesystemAccess(
+                        permissions,
+                        filesystem_permissions,
+                    ) => request_permission(env, permissions, filesystem_permissions)?,
+                    PluginCommand::ListGrantedPermissions => list_granted_permissions(env),
+                    PluginCommand::RevokePluginPermissions {
+                        plugin_url,
+                        permissions,
+                    } => revoke_plugin_permissions(env, plugin_url, permissions)?,
                     PluginCommand::SwitchSession(connect_to_session) => switch_session(
                         env,
                         connect_to_session.name,
@@ -447,6 +492,26 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         cli_pipe_output(env, pipe_name, output)?
                     },
                     PluginCommand::MessageToPlugin(message) => message_to_plugin(env, message)?,
+                    PluginCommand::SendPluginRequest {
+                        message,
+                        timeout_ms,
+                        context,
+                    } => send_plugin_request(env, message, timeout_ms, context)?,
+                    PluginCommand::ReplyToPluginRequest { request_id, reply } => {
+                        reply_to_plugin_request(env, request_id, reply)?
+                    },
+                    PluginCommand::GetStorageValue { key, scope } => {
+                        get_storage_value(env, key, scope)
+                    },
+                    PluginCommand::SetStorageValue { key, value, scope } => {
+                        set_storage_value(env, key, value, scope)?
+                    },
+                    PluginCommand::DeleteStorageValue { key, scope } => {
+                        delete_storage_value(env, key, scope)?
+                    },
+                    PluginCommand::ListStorageValues { prefix, scope } => {
+                        list_storage_values(env, prefix, scope)
+                    },
                     PluginCommand::DisconnectOtherClients => disconnect_other_clients(env),
                     PluginCommand::KillSessions(session_list) => kill_sessions(session_list),
                     PluginCommand::KillSessionsAndReply(session_list) => {
@@ -686,8 +751,19 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::SetSelfMouseSelectionSupport(selection_support) => {
                         set_self_mouse_selection_support(env, selection_support);
                     },
-                    PluginCommand::GenerateWebLoginToken(token_label, read_only) => {
-                        generate_web_login_token(env, token_label, read_only);
+                    PluginCommand::GenerateWebLoginToken(
+                        token_label,
+                        read_only,
+                        expires_in_secs,
+                        session_scope,
+                    ) => {
+                        generate_web_login_token(
+                            env,
+                            token_label,
+                            read_only,
+                            expires_in_secs,
+                            session_scope,
+                        );
                     },
                     PluginCommand::RevokeWebLoginToken(label) => {
                         revoke_web_login_token(env, label);
@@ -790,14 +866,17 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     },
                 },
                 (PermissionStatus::Denied, permission) => {
-                    log::error!(
-                        "Plugin '{}' permission '{}' denied - Command '{:?}' denied",
+                    let permission = permission
+                        .map(|p| p.to_string())
+                        .unwrap_or("UNKNOWN".to_owned());
+                    return Err(anyhow!(
+                        "command '{:?}' denied: plugin '{}' does not have the '{}' permission (it \
+                         was either never granted or has since been revoked, see `zellij plugin \
+                         permissions list`)",
+                        CommandType::from_str(&command.to_string()).with_context(err_context)?,
                         env.name(),
-                        permission
-                            .map(|p| p.to_string())
-                            .unwrap_or("UNKNOWN".to_owned()),
-                        CommandType::from_str(&command.to_string()).with_context(err_context)?
-                    );
+                        permission,
+                    ));
                 },
             };
             Ok(())
@@ -833,7 +912,149 @@ fn cli_pipe_output(env: &PluginEnv, pipe_name: String, output: String) -> Result
         .context("failed to send pipe output")
 }
 
-fn message_to_plugin(env: &PluginEnv, mut message_to_plugin: MessageToPlugin) -> Result<()> {
+fn message_to_plugin(env: &PluginEnv, message_to_plugin: MessageToPlugin) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::MessageFromPlugin {
+            source_plugin_id: env.plugin_id,
+            message: with_caller_defaults(env, message_to_plugin),
+        })
+        .context("failed to send message to plugin")
+}
+
+fn send_plugin_request(
+    env: &PluginEnv,
+    message_to_plugin: MessageToPlugin,
+    timeout_ms: Option<u64>,
+    context: BTreeMap<String, String>,
+) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::PluginRequest {
+            source_plugin_id: env.plugin_id,
+            source_client_id: env.client_id,
+            message: with_caller_defaults(env, message_to_plugin),
+            timeout_ms,
+            context,
+        })
+        .context("failed to send request to plugin")
+}
+
+fn reply_to_plugin_request(env: &PluginEnv, request_id: u64, reply: PluginReply) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::PluginRequestReply {
+            replying_plugin_id: env.plugin_id,
+            request_id,
+            reply,
+        })
+        .context("failed to reply to plugin request")
+}
+
+fn plugin_storage(env: &PluginEnv) -> PluginStorage {
+    PluginStorage::new(
+        &ZELLIJ_PLUGIN_STORAGE_DIR,
+        &env.plugin.location,
+        envs::get_session_name().unwrap_or_default(),
+    )
+}
+
+fn write_storage_value_response(env: &PluginEnv, response: Result<Option<String>>) {
+    let protobuf_response = match response {
+        Ok(value) => ProtobufStorageValueResponse { value, error: None },
+        Err(e) => {
+            log::error!("Plugin storage error: {:#}", e);
+            ProtobufStorageValueResponse {
+                value: None,
+                error: Some(format!("{:#}", e)),
+            }
+        },
+    };
+    wasi_write_object(env, &protobuf_response.encode_to_vec())
+        .context("failed to write plugin storage response")
+        .non_fatal();
+}
+
+fn notify_storage_value_changed(
+    env: &PluginEnv,
+    scope: StorageScope,
+    key: String,
+    value: Option<String>,
+) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::StorageValueChanged {
+            source_plugin_id: env.plugin_id,
+            source_client_id: env.client_id,
+            plugin_location: env.plugin.location.clone(),
+            scope,
+            key,
+            value,
+        })
+        .context("failed to notify plugins of storage change")
+}
+
+fn get_storage_value(env: &PluginEnv, key: String, scope: StorageScope) {
+    let response = plugin_storage(env).get(scope, &key);
+    write_storage_value_response(env, response);
+}
+
+fn set_storage_value(
+    env: &PluginEnv,
+    key: String,
+    value: String,
+    scope: StorageScope,
+) -> Result<()> {
+    match plugin_storage(env).set(scope, &key, value.clone()) {
+        Ok(()) => {
+            write_storage_value_response(env, Ok(None));
+            notify_storage_value_changed(env, scope, key, Some(value))
+        },
+        Err(e) => {
+            write_storage_value_response(env, Err(e));
+            Ok(())
+        },
+    }
+}
+
+fn delete_storage_value(env: &PluginEnv, key: String, scope: StorageScope) -> Result<()> {
+    match plugin_storage(env).delete(scope, &key) {
+        Ok(existed) => {
+            write_storage_value_response(env, Ok(None));
+            if existed {
+                notify_storage_value_changed(env, scope, key, None)?;
+            }
+            Ok(())
+        },
+        Err(e) => {
+            write_storage_value_response(env, Err(e));
+            Ok(())
+        },
+    }
+}
+
+fn list_storage_values(env: &PluginEnv, prefix: String, scope: StorageScope) {
+    let protobuf_response = match plugin_storage(env).list(scope, &prefix) {
+        Ok(values) => ProtobufListStorageValuesResponse {
+            values: values
+                .into_iter()
+                .map(|(name, value)| ContextItem { name, value })
+                .collect(),
+            error: None,
+        },
+        Err(e) => {
+            log::error!("Plugin storage error: {:#}", e);
+            ProtobufListStorageValuesResponse {
+                values: vec![],
+                error: Some(format!("{:#}", e)),
+            }
+        },
+    };
+    wasi_write_object(env, &protobuf_response.encode_to_vec())
+        .context("failed to write plugin storage response")
+        .non_fatal();
+}
+
+fn with_caller_defaults(
+    env: &PluginEnv,
+    mut message_to_plugin: MessageToPlugin,
+) -> MessageToPlugin {
     if message_to_plugin.plugin_url.as_ref().map(|s| s.as_str()) == Some("zellij:OWN_URL") {
         message_to_plugin.plugin_url = Some(env.plugin.location.display());
     }
@@ -847,12 +1068,7 @@ fn message_to_plugin(env: &PluginEnv, mut message_to_plugin: MessageToPlugin) ->
             env.plugin_cwd.display().to_string(),
         );
     }
-    env.senders
-        .send_to_plugin(PluginInstruction::MessageFromPlugin {
-            source_plugin_id: env.plugin_id,
-            message: message_to_plugin,
-        })
-        .context("failed to send message to plugin")
+    message_to_plugin
 }
 
 fn unsubscribe(env: &PluginEnv, event_list: HashSet<EventType>) -> Result<()> {
@@ -900,9 +1116,19 @@ fn show_cursor(env: &PluginEnv, cursor_position: Option<(usize, usize)>) {
         .non_fatal();
 }
 
-fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Result<()> {
-    if PermissionCache::from_path_or_default(None)
-        .check_permissions(env.plugin.location.to_string(), &permissions)
+fn request_permission(
+    env: &PluginEnv,
+    permissions: Vec<PermissionType>,
+    filesystem_permissions: Vec<FilesystemPermission>,
+) -> Result<()> {
+    let permission_cache = PermissionCache::from_path_or_default(None);
+    let plugin_name = env.plugin.location.to_string();
+    // web access granted with a narrower `allowed_hosts` than the plugin now has is asked for again
+    if permission_cache.check_permissions(plugin_name.clone(), &permissions)
+        && permission_cache
+            .check_filesystem_permissions(plugin_name.clone(), &filesystem_permissions)
+        && (!permissions.contains(&PermissionType::WebAccess)
+            || permission_cache.check_web_access(plugin_name, &env.plugin.allowed_hosts))
     {
         return env
             .senders
@@ -910,6 +1136,7 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
                 env.plugin_id,
                 Some(env.client_id),
                 permissions.to_vec(),
+                filesystem_permissions,
                 PermissionStatus::Granted,
                 None,
             ));
@@ -926,10 +1153,41 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
     env.senders
         .send_to_screen(ScreenInstruction::RequestPluginPermissions(
             env.plugin_id,
-            PluginPermission::new(env.plugin.location.to_string(), permissions),
+            PluginPermission::new(env.plugin.location.to_string(), permissions)
+                .with_filesystem_permissions(filesystem_permissions)
+                .with_allowed_hosts(env.plugin.allowed_hosts.clone()),
         ))
 }
 
+fn list_granted_permissions(env: &PluginEnv) {
+    let protobuf_response = ProtobufListGrantedPermissionsResponse {
+        granted: PermissionCache::from_path_or_default(None)
+            .list()
+            .into_iter()
+            .map(|granted| granted.into())
+            .collect(),
+    };
+    wasi_write_object(env, &protobuf_response.encode_to_vec())
+        .context("failed to write granted permissions")
+        .non_fatal();
+}
+
+fn revoke_plugin_permissions(
+    env: &PluginEnv,
+    plugin_url: String,
+    permissions: Vec<PermissionType>,
+) -> Result<()> {
+    let mut permission_cache = PermissionCache::from_path_or_default(None);
+    if permission_cache.revoke(plugin_url, &permissions) {
+        permission_cache
+            .write_to_file()
+            .context("failed to write permission cache")?;
+    }
+    // applied here as well as by the cache watcher so it does not depend on the latter
+    env.senders
+        .send_to_plugin(PluginInstruction::PermissionCacheChanged(None))
+}
+
 fn get_plugin_ids(env: &PluginEnv) {
     let ids = PluginIds {
         plugin_id: env.plugin_id,
@@ -1065,6 +1323,8 @@ fn open_command_pane_in_new_tab(
             context,
         )),
         use_terminal_title: false,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let initial_panes = Some(vec![CommandOrPlugin::Command(run_command_action)]);
     let action = Action::NewTab {
@@ -1335,6 +1595,7 @@ fn parse_layout(env: &PluginEnv, layout_string: String) {
                 tabs,
                 creation_time: current_time.clone(),
                 update_time: current_time,
+                parameters: Layout::parameters_from_kdl(&layout_string).unwrap_or_default(),
             };
 
             // Convert LayoutMetadata to protobuf
@@ -1962,6 +2223,8 @@ fn open_command_pane_in_place_of_plugin(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
 
@@ -2061,6 +2324,8 @@ fn open_command_pane_in_place_of_pane_id(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
 
@@ -2156,6 +2421,8 @@ fn open_command_pane(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let action = Action::NewTiledPane {
         direction,
@@ -2207,6 +2474,8 @@ fn open_command_pane_near_plugin(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
 
@@ -2266,6 +2535,8 @@ fn open_command_pane_floating(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let action = Action::NewFloatingPane {
         command: Some(run_command_action),
@@ -2317,6 +2588,8 @@ fn open_command_pane_floating_near_plugin(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
 
@@ -2377,6 +2650,8 @@ fn open_command_pane_in_place(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let action = Action::NewInPlacePane {
         command: Some(run_command_action),
@@ -2430,6 +2705,8 @@ fn open_command_pane_background(
             context,
         )),
         use_terminal_title,
+        env: command_to_run.env,
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
 
@@ -2478,7 +2755,27 @@ fn switch_tab_to(env: &PluginEnv, tab_idx: u32) {
         .non_fatal();
 }
 
-fn set_timeout(env: &PluginEnv, secs: f64) {
+fn set_timeout(env: &mut PluginEnv, secs: f64) {
+    if let Some(max_timers_per_second) = env.plugin.resource_limits.max_timers_per_second {
+        let now = Instant::now();
+        while env
+            .recent_timers
+            .front()
+            .map(|set_at| now.duration_since(*set_at) >= Duration::from_secs(1))
+            .unwrap_or(false)
+        {
+            env.recent_timers.pop_front();
+        }
+        if env.recent_timers.len() >= max_timers_per_second {
+            log::warn!(
+                "Plugin {} set more than {} timers in a second, dropping timer",
+                env.name(),
+                max_timers_per_second
+            );
+            return;
+        }
+        env.recent_timers.push_back(now);
+    }
     let send_plugin_instructions = env.senders.to_plugin.clone();
     let update_target = Some(env.plugin_id);
     let client_id = env.client_id;
@@ -2559,6 +2856,7 @@ fn run_command(
                 env_variables,
                 cwd,
                 context,
+                env.plugin.resource_limits.max_pending_jobs,
             ));
     }
 }
@@ -2581,6 +2879,8 @@ fn web_request(
             headers,
             body,
             context,
+            env.plugin.resource_limits.max_pending_jobs,
+            env.plugin.allowed_hosts.clone(),
         ));
 }
 
@@ -2720,21 +3020,29 @@ fn switch_to_mode(env: &PluginEnv, input_mode: InputMode) {
 
 fn new_tabs_with_layout(env: &PluginEnv, raw_layout: &str) -> Result<()> {
     // TODO: cwd
-    let layout = Layout::from_str(
+    let mut layout = Layout::from_str(
         &raw_layout,
         format!("Layout from plugin: {}", env.name()),
         None,
         None,
     )
     .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
+    // like layouts loaded from a URL, layouts from plugins cannot lift plugin resource limits
+    layout.clear_plugin_resource_limits();
     apply_layout(env, layout);
     Ok(())
 }
 
-fn new_tabs_with_layout_info(env: &PluginEnv, layout_info: LayoutInfo) -> Result<()> {
+fn new_tabs_with_layout_info(
+    env: &PluginEnv,
+    layout_info: LayoutInfo,
+    layout_args: BTreeMap<String, String>,
+) -> Result<()> {
     // TODO: cwd
-    let layout = Layout::from_layout_info(&env.layout_dir, layout_info)
+    let mut layout = Layout::from_layout_info(&env.layout_dir, layout_info, layout_args)
         .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
+    // this includes layout files, which plugins can write with `save_layout`
+    layout.clear_plugin_resource_limits();
     apply_layout(env, layout);
     Ok(())
 }
@@ -3193,6 +3501,53 @@ fn toggle_floating_panes(env: &PluginEnv, tab_id: Option<u64>) {
     apply_action!(action, error_msg, env);
 }
 
+fn start_recording(env: &PluginEnv, path: Option<PathBuf>, target: RecordingTarget) {
+    let error_msg = || format!("failed to start recording in plugin {}", env.name());
+    let path = match path.map(|path| translate_plugin_recording_path(env, path)) {
+        Some(Ok(path)) => Some(path),
+        Some(Err(e)) => {
+            log::error!("{}: {:?}", error_msg(), e);
+            return;
+        },
+        None => None,
+    };
+    let action = Action::StartRecording { path, target };
+    apply_action!(action, error_msg, env);
+}
+
+// recordings are created by the server with the user's privileges, so plugins are kept to their
+// own folders lest they use them to overwrite arbitrary files
+fn translate_plugin_recording_path(env: &PluginEnv, path: PathBuf) -> Result<PathBuf> {
+    let is_in_plugin_folder = path.starts_with("/data") || path.starts_with("/tmp");
+    let leaves_its_folder = path
+        .components()
+        .any(|component| component == std::path::Component::ParentDir);
+    if !is_in_plugin_folder || leaves_its_folder {
+        return Err(anyhow!(
+            "plugins can only record to files in /data or /tmp, not to {}",
+            path.display()
+        ));
+    }
+    Ok(translate_plugin_path(env, path))
+}
+
+fn stop_recording(env: &PluginEnv, path: Option<PathBuf>) {
+    let error_msg = || format!("failed to stop recording in plugin {}", env.name());
+    let action = Action::StopRecording {
+        path: path.map(|path| translate_plugin_path(env, path)),
+    };
+    apply_action!(action, error_msg, env);
+}
+
+fn set_pane_monitors(env: &PluginEnv, pane_id: zellij_utils::data::PaneId, monitors: PaneMonitors) {
+    let error_msg = || format!("failed to set pane monitors in plugin {}", env.name());
+    let action = Action::MonitorPane {
+        pane_id: Some(pane_id),
+        monitors,
+    };
+    apply_action!(action, error_msg, env);
+}
+
 fn new_pane(env: &PluginEnv) {
     let error_msg = || format!("failed to open new pane in plugin {}", env.name());
     let action = Action::NewPane {
@@ -3215,6 +3570,12 @@ fn close_focus(env: &PluginEnv) {
     apply_action!(action, error_msg, env);
 }
 
+fn reopen_closed_pane(env: &PluginEnv) {
+    let error_msg = || format!("failed to reopen closed pane in plugin {}", env.name());
+    let action = Action::ReopenClosedPane;
+    apply_action!(action, error_msg, env);
+}
+
 fn toggle_active_tab_sync(env: &PluginEnv) {
     let error_msg = || format!("failed to toggle active tab sync in plugin {}", env.name());
     let action = Action::ToggleActiveSyncTab;
@@ -4323,15 +4684,24 @@ fn get_session_list(env: &PluginEnv) {
 
     let response = match session_scan_state() {
         Some(state) => {
-            let (session_name, available_layouts, plugin_list) = {
+            let (session_name, available_layouts, plugin_list, crashed_plugins) = {
                 let name = state.current_session_name.lock().unwrap().clone();
                 let info = state.current_session_info.lock().unwrap().clone();
                 let plugins = state.current_session_plugin_list.lock().unwrap().clone();
-                (name, info.available_layouts, plugins)
+                let crashed_plugins = state
+                    .current_session_crashed_plugins
+                    .lock()
+                    .unwrap()
+                    .clone();
+                (name, info.available_layouts, plugins, crashed_plugins)
             };
 
-            let (live_sessions_map, resurrectable_sessions_map) =
-                scan_session_list_default_dirs(&session_name, &available_layouts, &plugin_list);
+            let (live_sessions_map, resurrectable_sessions_map) = scan_session_list_default_dirs(
+                &session_name,
+                &available_layouts,
+                &plugin_list,
+                &crashed_plugins,
+            );
 
             let _ = env
                 .senders
@@ -5150,24 +5520,38 @@ fn embed_multiple_panes(env: &PluginEnv, pane_ids: Vec<PaneId>) {
 }
 
 #[cfg(feature = "web_server_capability")]
-fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>, read_only: bool) {
-    let serialized = match create_token(token_label, read_only) {
-        Ok((token, token_label)) => CreateTokenResponse {
-            token: Some(token),
-            token_label: Some(token_label),
-            error: None,
-        },
-        Err(e) => CreateTokenResponse {
-            token: None,
-            token_label: None,
-            error: Some(e.to_string()),
-        },
-    };
+fn generate_web_login_token(
+    env: &PluginEnv,
+    token_label: Option<String>,
+    read_only: bool,
+    expires_in_secs: Option<u64>,
+    session_scope: Option<String>,
+) {
+    let expires_in = expires_in_secs.map(Duration::from_secs);
+    let serialized =
+        match create_restricted_token(token_label, read_only, expires_in, session_scope) {
+            Ok((token, token_label)) => CreateTokenResponse {
+                token: Some(token),
+                token_label: Some(token_label),
+                error: None,
+            },
+            Err(e) => CreateTokenResponse {
+                token: None,
+                token_label: None,
+                error: Some(e.to_string()),
+            },
+        };
     let _ = wasi_write_object(env, &serialized.encode_to_vec());
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-fn generate_web_login_token(env: &PluginEnv, _token_label: Option<String>, _read_only: bool) {
+fn generate_web_login_token(
+    env: &PluginEnv,
+    _token_label: Option<String>,
+    _read_only: bool,
+    _expires_in_secs: Option<u64>,
+    _session_scope: Option<String>,
+) {
     log::error!("This version of Zellij was compiled without the web server capabilities!");
     let empty_vec: Vec<&str> = vec![];
     let _ = wasi_write_object(env, &empty_vec);
@@ -5250,12 +5634,27 @@ fn list_web_login_tokens(env: &PluginEnv) {
             tokens: token_list.iter().map(|t| t.name.clone()).collect(),
             creation_times: token_list.iter().map(|t| t.created_at.clone()).collect(),
             read_only_flags: token_list.iter().map(|t| t.read_only).collect(),
+            expiration_times: token_list
+                .iter()
+                .map(|t| t.expires_at.clone().unwrap_or_default())
+                .collect(),
+            session_scopes: token_list
+                .iter()
+                .map(|t| t.session_scope.clone().unwrap_or_default())
+                .collect(),
+            last_used_times: token_list
+                .iter()
+                .map(|t| t.last_used_at.clone().unwrap_or_default())
+                .collect(),
             error: None,
         },
         Err(e) => ListTokensResponse {
             tokens: vec![],
             creation_times: vec![],
             read_only_flags: vec![],
+            expiration_times: vec![],
+            session_scopes: vec![],
+            last_used_times: vec![],
             error: Some(e.to_string()),
         },
     };
@@ -5326,10 +5725,12 @@ fn override_layout(
     retain_existing_terminal_panes: bool,
     retain_existing_plugin_panes: bool,
     apply_only_to_active_tab: bool,
+    layout_args: BTreeMap<String, String>,
     context: BTreeMap<String, String>,
 ) -> Result<()> {
-    let layout = Layout::from_layout_info(&env.layout_dir, layout_info)
+    let mut layout = Layout::from_layout_info(&env.layout_dir, layout_info, layout_args)
         .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
+    layout.clear_plugin_resource_limits();
 
     // Convert all tabs to Vec<TabLayoutInfo>
     let tabs: Vec<TabLayoutInfo> = layout
@@ -5471,6 +5872,7 @@ fn check_command_permission(
         | PluginCommand::SwitchToMode(..)
         | PluginCommand::NewTabsWithLayout(..)
         | PluginCommand::NewTabsWithLayoutInfo(..)
+        | PluginCommand::NewTabsWithLayoutAndArgs { .. }
         | PluginCommand::NewTab { .. }
         | PluginCommand::NewTabUnfocused { .. }
         | PluginCommand::GoToNextTab
@@ -5514,6 +5916,7 @@ fn check_command_permission(
         | PluginCommand::TogglePaneEmbedOrEjectForPaneId(..)
         | PluginCommand::UndoRenamePane
         | PluginCommand::CloseFocus
+        | PluginCommand::ReopenClosedPane
         | PluginCommand::ToggleActiveTabSync
         | PluginCommand::CloseFocusedTab
         | PluginCommand::UndoRenameTab
@@ -5568,6 +5971,7 @@ fn check_command_permission(
         | PluginCommand::SendSigintToPaneId(..)
         | PluginCommand::SendSigkillToPaneId(..)
         | PluginCommand::OverrideLayout(..)
+        | PluginCommand::OverrideLayoutWithArgs { .. }
         | PluginCommand::SaveLayout { .. }
         | PluginCommand::DeleteLayout { .. }
         | PluginCommand::RenameLayout { .. }
@@ -5576,11 +5980,14 @@ fn check_command_permission(
         | PluginCommand::HideFloatingPanes { .. }
         | PluginCommand::SetPaneRegexHighlights(..)
         | PluginCommand::ClearPaneHighlights(..)
-        | PluginCommand::SetSoftKeyboard(..) => PermissionType::ChangeApplicationState,
+        | PluginCommand::SetSoftKeyboard(..)
+        | PluginCommand::SetPaneMonitors { .. } => PermissionType::ChangeApplicationState,
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
-        PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
+        PluginCommand::MessageToPlugin(..) | PluginCommand::SendPluginRequest { .. } => {
+            PermissionType::MessageAndLaunchOtherPlugins
+        },
         PluginCommand::ListClients
         | PluginCommand::DumpSessionLayout { .. }
         | PluginCommand::GetPanePid { .. }
@@ -5602,6 +6009,8 @@ fn check_command_permission(
         PluginCommand::ChangeHostFolder(..) | PluginCommand::ListWindowsVolumes => {
             PermissionType::FullHdAccess
         },
+        PluginCommand::ListGrantedPermissions => PermissionType::ReadApplicationState,
+        PluginCommand::RevokePluginPermissions { .. } => PermissionType::Reconfigure,
         PluginCommand::ShareCurrentSession
         | PluginCommand::StopSharingCurrentSession
         | PluginCommand::StopWebServer
@@ -5620,7 +6029,9 @@ fn check_command_permission(
         PluginCommand::GetSessionEnvironmentVariables => {
             PermissionType::ReadSessionEnvironmentVariables
         },
-        PluginCommand::OpenCommandPaneInNewTab(..) => PermissionType::RunCommands,
+        PluginCommand::OpenCommandPaneInNewTab(..)
+        | PluginCommand::StartRecording { .. }
+        | PluginCommand::StopRecording { .. } => PermissionType::RunCommands,
         PluginCommand::OpenEditorPaneInNewTab(..) => PermissionType::OpenFiles,
         _ => return (PermissionStatus::Granted, None),
     };
@@ -1077,7 +1077,7 @@ impl Pty {
         let originating_edit_plugin = Arc::new(originating_edit_plugin.clone());
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
-            move |pane_id, exit_status, command| {
+            move |pane_id, exit_status, command: RunCommand| {
                 // if this command originated in a plugin, we send the plugin an event letting it
                 // know the command exited and some other useful information
                 if let PaneId::Terminal(pane_id) = pane_id {
@@ -1107,7 +1107,8 @@ impl Pty {
                     }
                 }
 
-                if hold_on_close {
+                if hold_on_close || command.restart.should_restart(exit_status) {
+                    // panes that will be restarted are held until their restart is due
                     let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                         pane_id,
                         exit_status,
@@ -1616,7 +1617,7 @@ impl Pty {
                 let hold_on_close = command.hold_on_close;
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
+                    move |pane_id, exit_status, command: RunCommand| {
                         if let PaneId::Terminal(terminal_pane_id) = pane_id {
                             if let Some(originating_plugin) = originating_plugin.as_ref() {
                                 let update_event = Event::CommandPaneExited(
@@ -1632,7 +1633,7 @@ impl Pty {
                             }
                         }
 
-                        if hold_on_close {
+                        if hold_on_close || command.restart.should_restart(exit_status) {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
                                 exit_status,
@@ -1798,6 +1799,29 @@ impl Pty {
             Some(Run::Plugin(_)) => Ok(None),
         }
     }
+    // the cwd has to be queried before the child process is killed, so that the pane can later
+    // be reopened in the same folder
+    fn report_closed_pane_cwd(&self, terminal_id: u32) {
+        let cwd = self
+            .id_to_child_pid
+            .get(&terminal_id)
+            .and_then(|&pid| {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .and_then(|input| input.get_cwd(pid))
+            })
+            .or_else(|| self.terminal_cwds.get(&terminal_id).cloned());
+        if let Some(cwd) = cwd {
+            let _ = self
+                .bus
+                .senders
+                .send_to_screen(ScreenInstruction::UpdateClosedPaneCwd(
+                    PaneId::Terminal(terminal_id),
+                    cwd,
+                ));
+        }
+    }
     pub fn close_pane(&mut self, id: PaneId) -> Result<()> {
         let err_context = || format!("failed to close for pane {id:?}");
         match id {
@@ -1805,6 +1829,7 @@ impl Pty {
                 if let Some(handle) = self.task_handles.remove(&id) {
                     handle.abort();
                 }
+                self.report_closed_pane_cwd(id);
                 if let Some(child_pid) = self.id_to_child_pid.remove(&id) {
                     let err_context = || format!("failed to kill child processes for pane {id}");
                     self.bus
@@ -1866,7 +1891,7 @@ impl Pty {
                 let originating_plugin = Arc::new(run_command.originating_plugin.clone());
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
+                    move |pane_id, exit_status, command: RunCommand| {
                         if let PaneId::Terminal(pane_id) = pane_id {
                             if let Some(originating_plugin) = originating_plugin.as_ref() {
                                 let update_event = Event::CommandPaneExited(
@@ -1881,7 +1906,7 @@ impl Pty {
                                 )]));
                             }
                         }
-                        if hold_on_close {
+                        if hold_on_close || command.restart.should_restart(exit_status) {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
                                 exit_status,
@@ -513,6 +513,61 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::StartRecording { path, target } => {
+            senders
+                .send_to_screen(ScreenInstruction::StartRecording {
+                    path,
+                    target,
+                    client_id,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
+        Action::StopRecording { path } => {
+            senders
+                .send_to_screen(ScreenInstruction::StopRecording {
+                    path,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
+        Action::StartPaneLog {
+            pane_id,
+            log_output,
+        } => {
+            senders
+                .send_to_screen(ScreenInstruction::StartPaneLog {
+                    pane_id: pane_id.map(|p| p.into()),
+                    log_output,
+                    client_id,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
+        Action::StopPaneLog { pane_id } => {
+            senders
+                .send_to_screen(ScreenInstruction::StopPaneLog {
+                    pane_id: pane_id.map(|p| p.into()),
+                    client_id,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
+        Action::MonitorPane { pane_id, monitors } => {
+            senders
+                .send_to_screen(ScreenInstruction::MonitorPane {
+                    pane_id: pane_id.map(|p| p.into()),
+                    monitors,
+                    client_id,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
         Action::EditScrollback { ansi } => {
             senders
                 .send_to_screen(ScreenInstruction::EditScrollback(
@@ -1024,6 +1079,14 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::ReopenClosedPane => {
+            senders
+                .send_to_screen(ScreenInstruction::ReopenClosedPane(
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         Action::NewTab {
             tiled_layout: tab_layout,
             floating_layouts: floating_panes_layout,
@@ -1089,6 +1152,24 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::TogglePaneGroupSync => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneGroupSync(
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SyncBroadcastGroup { name } => {
+            senders
+                .send_to_screen(ScreenInstruction::SyncBroadcastGroup {
+                    name,
+                    client_id,
+                    cli_client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
         Action::CloseTab => {
             senders
                 .send_to_screen(ScreenInstruction::CloseTab(
@@ -1315,11 +1396,40 @@ pub(crate) fn route_action(
                 SearchOption::Wrap => {
                     ScreenInstruction::SearchToggleWrap(client_id, notification_end)
                 },
+                SearchOption::Regex => {
+                    ScreenInstruction::SearchToggleRegex(client_id, notification_end)
+                },
             };
             senders
                 .send_to_screen(instruction)
                 .with_context(err_context)?;
         },
+        Action::CopyModeMove { motion } => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyModeMove(
+                    motion,
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::CopyModeSelect { selection } => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyModeSelect(
+                    selection,
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::CopyModeYank => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyModeYank(
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         Action::ToggleMouseMode => {}, // Handled client side
         Action::PreviousSwapLayout => {
             senders
@@ -2839,7 +2949,12 @@ pub(crate) fn route_thread_main(
                                 if let Some(scan_state) =
                                     crate::background_jobs::session_scan_state()
                                 {
-                                    let (session_name, available_layouts, plugin_list) = {
+                                    let (
+                                        session_name,
+                                        available_layouts,
+                                        plugin_list,
+                                        crashed_plugins,
+                                    ) = {
                                         let name =
                                             scan_state.current_session_name.lock().unwrap().clone();
                                         let info =
@@ -2849,13 +2964,19 @@ pub(crate) fn route_thread_main(
                                             .lock()
                                             .unwrap()
                                             .clone();
-                                        (name, info.available_layouts, plugins)
+                                        let crashed_plugins = scan_state
+                                            .current_session_crashed_plugins
+                                            .lock()
+                                            .unwrap()
+                                            .clone();
+                                        (name, info.available_layouts, plugins, crashed_plugins)
                                     };
                                     let (live_sessions_map, resurrectable_sessions_map) =
                                         crate::background_jobs::scan_session_list_default_dirs(
                                             &session_name,
                                             &available_layouts,
                                             &plugin_list,
+                                            &crashed_plugins,
                                         );
                                     let _ = senders.send_to_screen(
                                         ScreenInstruction::UpdateSessionInfos(
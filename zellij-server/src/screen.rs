@@ -43,11 +43,12 @@ use zellij_utils::data::{
     CommandOrPlugin, Direction, EventType, FloatingPaneCoordinates, GetFocusedPaneInfoResponse,
     HostTerminalThemeMode, KeyWithModifier, LayoutInfo, LayoutWithError, ListPanesResponse,
     ListTabsResponse, NewPanePlacement, PaneContents, PaneInfo, PaneListEntry, PaneManifest,
-    PaneRenderReport, PaneScrollbackResponse, PluginPermission, RegexHighlight, Resize,
-    ResizeStrategy, SessionInfo, Styling, TabInfo, ThemeHue, WebSharing,
+    PaneMonitorAlert, PaneMonitors, PaneRenderReport, PaneScrollbackResponse, PluginPermission,
+    RecordingTarget, RegexHighlight, Resize, ResizeStrategy, SessionInfo, Styling, TabInfo,
+    ThemeHue, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
-use zellij_utils::input::actions::Action;
+use zellij_utils::input::actions::{Action, CopyModeMotion, CopyModeSelection};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::config::Config;
 use zellij_utils::input::keybinds::{shortcut_for_action, Keybinds};
@@ -67,8 +68,8 @@ use zellij_utils::{
     envs::set_session_name,
     input::command::TerminalAction,
     input::layout::{
-        FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginOrAlias, SwapFloatingLayout,
-        SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+        FloatingPaneLayout, Layout, LogOutput, PercentOrFixed, Run, RunPluginOrAlias,
+        SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
     },
     position::Position,
 };
@@ -83,6 +84,7 @@ use crate::panes::nested_session_modal::GuestModalShortcuts;
 use crate::panes::terminal_character::AnsiCode;
 use crate::panes::terminal_pane::{BRACKETED_PASTE_BEGIN, BRACKETED_PASTE_END};
 use crate::session_layout_metadata::{PaneLayoutMetadata, SessionLayoutMetadata};
+use crate::session_recording::{RecordedOutput, SessionRecordings};
 
 use crate::{
     nested_guest::NestedGuestTracker,
@@ -94,7 +96,7 @@ use crate::{
     plugins::{DumpSessionLayoutResponse, PluginId, PluginInstruction, PluginRenderAsset},
     pty::{get_default_shell, ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
     pty_writer::PtyWriteInstruction,
-    tab::{GuestChoiceIndicator, SuppressedPanes, Tab},
+    tab::{GuestChoiceIndicator, Pane, SuppressedPanes, Tab},
     thread_bus::Bus,
     ui::loading_indication::LoadingIndication,
     ClientId, ServerInstruction,
@@ -406,6 +408,39 @@ pub enum ScreenInstruction {
     DumpLayout(Option<PathBuf>, ClientId, Option<NotificationEnd>), // PathBuf is the default configured
     // shell
     SaveSession(ClientId, Option<NotificationEnd>),
+    StartRecording {
+        path: Option<PathBuf>,
+        target: RecordingTarget,
+        client_id: ClientId,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
+    StopRecording {
+        path: Option<PathBuf>,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
+    StartPaneLog {
+        pane_id: Option<PaneId>,
+        log_output: LogOutput,
+        client_id: ClientId,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
+    StopPaneLog {
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
+    MonitorPane {
+        pane_id: Option<PaneId>,
+        monitors: PaneMonitors,
+        client_id: ClientId,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
+    CheckPaneMonitors,
     DumpLayoutToPlugin {
         plugin_id: PluginId,
         tab_index: Option<usize>,
@@ -441,12 +476,16 @@ pub enum ScreenInstruction {
     SelectCommandAtScrollPosition(ClientId, Option<NotificationEnd>),
     CopyLastCommandOutput(ClientId, Option<NotificationEnd>),
     ClearCommandOutputFlash(PaneId),
+    RestartCommandPane(PaneId, u64), // u64 - the id of the scheduled restart
+    ReadinessProbeSucceeded(PaneId, u64), // PaneId - the dependent pane, u64 - the probe id
     PageScrollUp(ClientId, Option<NotificationEnd>),
     PageScrollDown(ClientId, Option<NotificationEnd>),
     HalfPageScrollUp(ClientId, Option<NotificationEnd>),
     HalfPageScrollDown(ClientId, Option<NotificationEnd>),
     ClearScroll(ClientId),
     CloseFocusedPane(ClientId, Option<NotificationEnd>),
+    ReopenClosedPane(ClientId, Option<NotificationEnd>),
+    UpdateClosedPaneCwd(PaneId, PathBuf),
     ToggleActiveTerminalFullscreen(ClientId, Option<NotificationEnd>),
     ToggleActiveTerminalNoUiFullscreen(ClientId, Option<NotificationEnd>),
     TogglePaneFrames(Option<NotificationEnd>),
@@ -499,6 +538,13 @@ pub enum ScreenInstruction {
     SwitchTabNext(ClientId, Option<NotificationEnd>),
     SwitchTabPrev(ClientId, Option<NotificationEnd>),
     ToggleActiveSyncTab(ClientId, Option<NotificationEnd>),
+    TogglePaneGroupSync(ClientId, Option<NotificationEnd>),
+    SyncBroadcastGroup {
+        name: String,
+        client_id: ClientId,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    },
     CloseTab(ClientId, Option<NotificationEnd>),
     GoToTab(u32, Option<ClientId>, Option<NotificationEnd>), // this Option is a hacky workaround, please do not copy this behaviour
     GoToTabName(
@@ -618,6 +664,10 @@ pub enum ScreenInstruction {
     SearchToggleCaseSensitivity(ClientId, Option<NotificationEnd>),
     SearchToggleWholeWord(ClientId, Option<NotificationEnd>),
     SearchToggleWrap(ClientId, Option<NotificationEnd>),
+    SearchToggleRegex(ClientId, Option<NotificationEnd>),
+    CopyModeMove(CopyModeMotion, ClientId, Option<NotificationEnd>),
+    CopyModeSelect(CopyModeSelection, ClientId, Option<NotificationEnd>),
+    CopyModeYank(ClientId, Option<NotificationEnd>),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
     SetTabBellFlash(usize, bool), // tab_id, is_flashing
@@ -1017,6 +1067,12 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::DumpScreen(..) => ScreenContext::DumpScreen,
             ScreenInstruction::DumpLayout(..) => ScreenContext::DumpLayout,
             ScreenInstruction::SaveSession(..) => ScreenContext::SaveSession,
+            ScreenInstruction::StartRecording { .. } => ScreenContext::StartRecording,
+            ScreenInstruction::StopRecording { .. } => ScreenContext::StopRecording,
+            ScreenInstruction::StartPaneLog { .. } => ScreenContext::StartPaneLog,
+            ScreenInstruction::StopPaneLog { .. } => ScreenContext::StopPaneLog,
+            ScreenInstruction::MonitorPane { .. } => ScreenContext::MonitorPane,
+            ScreenInstruction::CheckPaneMonitors => ScreenContext::CheckPaneMonitors,
             ScreenInstruction::DumpLayoutToPlugin { .. } => ScreenContext::DumpLayoutToPlugin,
             ScreenInstruction::GetFocusedPaneInfo { .. } => ScreenContext::GetFocusedPaneInfo,
             ScreenInstruction::GetPaneInfo { .. } => ScreenContext::GetPaneInfo,
@@ -1036,12 +1092,18 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ClearCommandOutputFlash(..) => {
                 ScreenContext::ClearCommandOutputFlash
             },
+            ScreenInstruction::RestartCommandPane(..) => ScreenContext::RestartCommandPane,
+            ScreenInstruction::ReadinessProbeSucceeded(..) => {
+                ScreenContext::ReadinessProbeSucceeded
+            },
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
             ScreenInstruction::HalfPageScrollDown(..) => ScreenContext::HalfPageScrollDown,
             ScreenInstruction::ClearScroll(..) => ScreenContext::ClearScroll,
             ScreenInstruction::CloseFocusedPane(..) => ScreenContext::CloseFocusedPane,
+            ScreenInstruction::ReopenClosedPane(..) => ScreenContext::ReopenClosedPane,
+            ScreenInstruction::UpdateClosedPaneCwd(..) => ScreenContext::UpdateClosedPaneCwd,
             ScreenInstruction::ToggleActiveTerminalFullscreen(..) => {
                 ScreenContext::ToggleActiveTerminalFullscreen
             },
@@ -1112,6 +1174,8 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::ChangeModeForAllClients
             },
             ScreenInstruction::ToggleActiveSyncTab(..) => ScreenContext::ToggleActiveSyncTab,
+            ScreenInstruction::TogglePaneGroupSync(..) => ScreenContext::TogglePaneGroupSync,
+            ScreenInstruction::SyncBroadcastGroup { .. } => ScreenContext::SyncBroadcastGroup,
             ScreenInstruction::ScrollUpAt(..) => ScreenContext::ScrollUpAt,
             ScreenInstruction::ScrollDownAt(..) => ScreenContext::ScrollDownAt,
             ScreenInstruction::MouseEvent(..) => ScreenContext::MouseEvent,
@@ -1127,6 +1191,10 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
+            ScreenInstruction::CopyModeMove(..) => ScreenContext::CopyModeMove,
+            ScreenInstruction::CopyModeSelect(..) => ScreenContext::CopyModeSelect,
+            ScreenInstruction::CopyModeYank(..) => ScreenContext::CopyModeYank,
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
             },
@@ -1531,6 +1599,7 @@ pub(crate) struct Screen {
     auto_layout: bool,
     session_serialization: bool,
     serialize_pane_viewport: bool,
+    serialize_pane_scrollback: bool,
     scrollback_lines_to_serialize: Option<usize>,
     session_is_mirrored: bool,
     copy_options: CopyOptions,
@@ -1607,6 +1676,7 @@ pub(crate) struct Screen {
     client_notification_protocols: HashMap<ClientId, NotificationProtocol>,
     host_notification_protocol: HostNotificationProtocol,
     client_host_terminal_env: HashMap<ClientId, BTreeMap<String, String>>,
+    session_recordings: SessionRecordings,
 }
 
 /// A pending forward waiting to be dispatched once the current in-flight
@@ -1673,6 +1743,7 @@ impl Screen {
         default_shell: PathBuf,
         session_serialization: bool,
         serialize_pane_viewport: bool,
+        serialize_pane_scrollback: bool,
         scrollback_lines_to_serialize: Option<usize>,
         styled_underlines: bool,
         osc8_hyperlinks: bool,
@@ -1741,6 +1812,7 @@ impl Screen {
             default_shell,
             session_serialization,
             serialize_pane_viewport,
+            serialize_pane_scrollback,
             scrollback_lines_to_serialize,
             styled_underlines,
             osc8_hyperlinks,
@@ -1803,6 +1875,7 @@ impl Screen {
             client_notification_protocols: HashMap::new(),
             host_notification_protocol: HostNotificationProtocol::default(),
             client_host_terminal_env: HashMap::new(),
+            session_recordings: SessionRecordings::default(),
         }
     }
 
@@ -2222,6 +2295,7 @@ impl Screen {
         // Notify pane render subscribers of each closed pane
         for p_id in &pane_ids {
             self.notify_pane_closed_to_subscribers((*p_id).into());
+            self.stop_recordings_of_closed_pane(*p_id);
         }
 
         // below we don't check the result of sending the CloseTab instruction to the pty thread
@@ -4214,6 +4288,7 @@ impl Screen {
 
             if non_watcher_output_was_dirty || has_bell {
                 let serialized_output = output.serialize().context(err_context)?;
+                self.record_client_output(&serialized_output);
                 if !serialized_output.is_empty() {
                     let _ = self
                         .bus
@@ -4376,6 +4451,287 @@ impl Screen {
         ));
     }
 
+    pub fn start_recording(
+        &mut self,
+        path: PathBuf,
+        target: RecordingTarget,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to start recording to {}", path.display());
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            client_id
+        } else {
+            self.get_first_client_id()
+                .ok_or_else(|| anyhow!("no client to record for"))
+                .with_context(err_context)?
+        };
+        let pane_id = match target {
+            RecordingTarget::Pane(pane_id) => Some(PaneId::from(pane_id)),
+            RecordingTarget::FocusedPane => Some(
+                self.get_active_tab(client_id)
+                    .ok()
+                    .and_then(|tab| tab.get_active_pane_id(client_id))
+                    .ok_or_else(|| anyhow!("no focused pane to record"))
+                    .with_context(err_context)?,
+            ),
+            _ => None,
+        };
+        match pane_id {
+            Some(pane_id) => {
+                if let PaneId::Plugin(_) = pane_id {
+                    return Err(anyhow!(
+                        "plugin panes can only be recorded as part of a tab or client recording"
+                    ))
+                    .with_context(err_context);
+                }
+                let pane = self
+                    .tabs
+                    .values()
+                    .find_map(|tab| tab.get_pane_with_id(pane_id))
+                    .ok_or_else(|| anyhow!("pane {:?} not found", pane_id))
+                    .with_context(err_context)?;
+                let size = Size {
+                    rows: pane.get_content_rows(),
+                    cols: pane.get_content_columns(),
+                };
+                // start the recording with what the pane currently shows, so that it makes sense
+                // on its own
+                let mut initial_contents = String::from("\u{1b}[H\u{1b}[2J");
+                if let Some(contents) = pane.serialize(None) {
+                    initial_contents.push_str(contents.strip_prefix("\n\r").unwrap_or(&contents));
+                }
+                initial_contents.push_str("\u{1b}[m");
+                if let Some((x, y, visible)) = pane.cursor_coordinates(None) {
+                    initial_contents.push_str(&format!("\u{1b}[{};{}H", y + 1, x + 1));
+                    if !visible {
+                        initial_contents.push_str("\u{1b}[?25l");
+                    }
+                }
+                let title = Some(pane.current_title());
+                self.session_recordings.start(
+                    path,
+                    RecordedOutput::Pane(pane_id),
+                    size,
+                    title,
+                    Some(initial_contents),
+                )
+            },
+            None => {
+                let (output, title) = match target {
+                    RecordingTarget::Tab(tab_id) => {
+                        let tab = self
+                            .get_tab_by_id(tab_id)
+                            .ok_or_else(|| anyhow!("tab with id {} not found", tab_id))
+                            .with_context(err_context)?;
+                        (RecordedOutput::Tab(tab_id, client_id), tab.name.clone())
+                    },
+                    RecordingTarget::FocusedTab => {
+                        let tab = self.get_active_tab(client_id).with_context(err_context)?;
+                        (RecordedOutput::Tab(tab.id, client_id), tab.name.clone())
+                    },
+                    _ => (RecordedOutput::Client(client_id), self.session_name.clone()),
+                };
+                self.session_recordings.start(
+                    path,
+                    output,
+                    self.size_for_client(Some(client_id)),
+                    Some(title),
+                    Some(String::from("\u{1b}[H\u{1b}[2J")),
+                )?;
+                // the client only receives changed lines, so we need to render everything once
+                // for the recording to start with the full picture
+                for tab in self.tabs.values_mut() {
+                    tab.set_force_render();
+                }
+                self.render(None)
+            },
+        }
+    }
+
+    fn terminal_pane_or_focused_pane(
+        &mut self,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+    ) -> Result<&mut Box<dyn Pane>> {
+        let pane_id = match pane_id {
+            Some(pane_id) => pane_id,
+            None => {
+                let client_id = if self.get_active_tab(client_id).is_ok() {
+                    client_id
+                } else {
+                    self.get_first_client_id()
+                        .ok_or_else(|| anyhow!("no client to find the focused pane for"))?
+                };
+                self.get_active_tab(client_id)
+                    .ok()
+                    .and_then(|tab| tab.get_active_pane_id(client_id))
+                    .ok_or_else(|| anyhow!("no focused pane"))?
+            },
+        };
+        if let PaneId::Plugin(_) = pane_id {
+            return Err(anyhow!("pane {:?} is not a terminal pane", pane_id));
+        }
+        self.tabs
+            .values_mut()
+            .find_map(|tab| tab.get_pane_with_id_mut(pane_id))
+            .ok_or_else(|| anyhow!("pane {:?} not found", pane_id))
+    }
+    pub fn start_pane_log(
+        &mut self,
+        pane_id: Option<PaneId>,
+        log_output: LogOutput,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to log pane output to {}", log_output.path.display());
+        self.terminal_pane_or_focused_pane(pane_id, client_id)
+            .with_context(err_context)?
+            .start_output_log(log_output.clone())
+            .with_context(err_context)
+    }
+    pub fn stop_pane_log(
+        &mut self,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+    ) -> Result<PathBuf> {
+        let err_context = || "failed to stop logging pane output";
+        self.terminal_pane_or_focused_pane(pane_id, client_id)
+            .with_context(err_context)?
+            .stop_output_log()
+            .ok_or_else(|| anyhow!("pane output is not being logged"))
+            .with_context(err_context)
+    }
+    pub fn monitor_pane(
+        &mut self,
+        pane_id: Option<PaneId>,
+        monitors: PaneMonitors,
+        client_id: ClientId,
+    ) -> Result<()> {
+        self.terminal_pane_or_focused_pane(pane_id, client_id)
+            .context("failed to monitor pane")?
+            .set_monitors(monitors);
+        if !monitors.is_empty() {
+            let _ = self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::StartPaneMonitorTicks);
+        }
+        Ok(())
+    }
+    /// Raises the alerts of all monitored panes: their bell rings (see
+    /// `check_and_handle_bell_notifications`), the host terminal gets a desktop notification and
+    /// plugins get a `PaneMonitorAlert` event
+    pub fn check_pane_monitors(&mut self) -> Result<()> {
+        let now = Instant::now();
+        let mut alerts = vec![];
+        for tab in self.tabs.values_mut() {
+            for (pane_id, alert) in tab.check_pane_monitors(now) {
+                let title = tab
+                    .get_pane_with_id(pane_id)
+                    .map(|pane| pane.current_title())
+                    .unwrap_or_default();
+                alerts.push((pane_id, title, alert));
+            }
+        }
+        if !self.tabs.values().any(|tab| tab.has_pane_monitors()) {
+            let _ = self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::StopPaneMonitorTicks);
+        }
+        if alerts.is_empty() {
+            return Ok(());
+        }
+        for (pane_id, title, alert) in &alerts {
+            if let PaneId::Terminal(terminal_id) = pane_id {
+                let body = match alert {
+                    PaneMonitorAlert::Activity => "Activity in pane".to_owned(),
+                    PaneMonitorAlert::Silence(silence_secs) => {
+                        format!("No output for {} seconds", silence_secs)
+                    },
+                };
+                self.forward_desktop_notifications(
+                    vec![PendingNotification::Osc777 {
+                        title: title.clone(),
+                        body,
+                    }],
+                    *terminal_id,
+                );
+            }
+        }
+        let _ = self.bus.senders.send_to_plugin(PluginInstruction::Update(
+            alerts
+                .into_iter()
+                .map(|(pane_id, _title, alert)| {
+                    (None, None, Event::PaneMonitorAlert(pane_id.into(), alert))
+                })
+                .collect(),
+        ));
+        self.render(None).context("failed to check pane monitors")
+    }
+    fn report_result_to_cli(
+        &self,
+        result: Result<Vec<String>>,
+        cli_client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    ) {
+        // actions triggered from a keybinding or a plugin have nowhere to print to
+        let instruction = match (result, cli_client_id) {
+            (Ok(paths), Some(cli_client_id)) => {
+                ServerInstruction::Log(paths, cli_client_id, completion_tx)
+            },
+            (Err(e), Some(cli_client_id)) => {
+                log::error!("{:?}", e);
+                ServerInstruction::LogError(vec![format!("{:#}", e)], cli_client_id, completion_tx)
+            },
+            (Ok(_), None) => return,
+            (Err(e), None) => {
+                log::error!("{:?}", e);
+                return;
+            },
+        };
+        let _ = self.bus.senders.send_to_server(instruction);
+    }
+
+    fn stop_recordings_of_closed_pane(&mut self, pane_id: PaneId) {
+        for path in self.session_recordings.stop_pane(pane_id) {
+            log::info!(
+                "Stopped recording to {} because its pane was closed",
+                path.display()
+            );
+        }
+    }
+    fn record_pane_bytes(&mut self, pane_id: PaneId, bytes: &[u8]) {
+        if !self.session_recordings.is_recording_pane(pane_id) {
+            return;
+        }
+        let size = self
+            .tabs
+            .values()
+            .find_map(|tab| tab.get_pane_with_id(pane_id))
+            .map(|pane| Size {
+                rows: pane.get_content_rows(),
+                cols: pane.get_content_columns(),
+            });
+        if let Some(size) = size {
+            self.session_recordings
+                .record_pane_bytes(pane_id, bytes, size);
+        }
+    }
+
+    fn record_client_output(&mut self, serialized_output: &HashMap<ClientId, String>) {
+        if !self.session_recordings.is_recording_client_output() {
+            return;
+        }
+        for (client_id, output) in serialized_output {
+            self.session_recordings.record_client_output(
+                *client_id,
+                self.active_tab_ids.get(client_id).copied(),
+                output,
+                self.size_for_client(Some(*client_id)),
+            );
+        }
+    }
+
     /// Returns an immutable reference to this [`Screen`]'s previous active [`Tab`].
     /// Consumes the last entry in tab history.
     pub fn get_previous_tab(&mut self, client_id: ClientId) -> Result<Option<&Tab>> {
@@ -5195,6 +5551,7 @@ impl Screen {
                 selectable_tiled_panes_count,
                 selectable_floating_panes_count,
                 tab_id: tab.id,
+                recently_closed_panes: tab.recently_closed_panes(),
                 has_bell_notification: tab.tab_has_pending_bell
                     && !self.active_tab_ids.values().any(|i| i == &tab.id),
                 is_flashing_bell: tab.tab_bell_flash
@@ -5242,6 +5599,7 @@ impl Screen {
                     selectable_tiled_panes_count,
                     selectable_floating_panes_count,
                     tab_id: tab.id,
+                    recently_closed_panes: tab.recently_closed_panes(),
                     has_bell_notification: tab.tab_has_pending_bell && *active_tab_index != tab.id,
                     is_flashing_bell: tab.tab_bell_flash && *active_tab_index != tab.id,
                 };
@@ -5919,6 +6277,27 @@ impl Screen {
             .cloned()
             .unwrap_or_else(|| self.default_mode_info.clone());
         let previous_mode = mode_info.mode;
+
+        // a search started from copy mode returns to it, so that its results can be jumped to with
+        // the copy mode motions
+        let searching_from_copy_mode = previous_mode == InputMode::EnterSearch
+            && self
+                .get_active_tab(client_id)
+                .map(|tab| tab.is_in_copy_mode(client_id))
+                .unwrap_or(false);
+        if searching_from_copy_mode && matches!(new_mode, InputMode::Search | InputMode::Scroll) {
+            // Route through the server like SwitchToMode does, so that keys resolve in copy mode
+            return self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::ChangeMode(
+                    client_id,
+                    InputMode::Copy,
+                    None,
+                ))
+                .with_context(|| format!("failed to return to copy mode for client {client_id}"));
+        }
+
         mode_info.mode = new_mode;
         mode_info.base_mode = base_mode;
         if mode_info.session_name.as_ref() != Some(&self.session_name) {
@@ -5926,13 +6305,28 @@ impl Screen {
         }
 
         // If we leave the Search-related modes, we need to clear all previous searches
-        let search_related_modes = [InputMode::EnterSearch, InputMode::Search, InputMode::Scroll];
+        let search_related_modes = [
+            InputMode::EnterSearch,
+            InputMode::Search,
+            InputMode::Scroll,
+            InputMode::Copy,
+        ];
         if search_related_modes.contains(&previous_mode)
             && !search_related_modes.contains(&mode_info.mode)
         {
             active_tab!(self, client_id, |tab: &mut Tab| tab.clear_search(client_id));
         }
 
+        // copy mode stays active while searching from it
+        if mode_info.mode == InputMode::Copy {
+            active_tab!(self, client_id, |tab: &mut Tab| tab
+                .enter_copy_mode(client_id));
+        } else if mode_info.mode != InputMode::EnterSearch {
+            for tab in self.tabs.values_mut() {
+                tab.exit_copy_mode(client_id);
+            }
+        }
+
         if mode_info.mode == InputMode::RenameTab {
             if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
                 active_tab.prev_name = active_tab.name.clone();
@@ -7344,9 +7738,17 @@ impl Screen {
                         } else {
                             None
                         },
+                        if self.serialize_pane_scrollback {
+                            p.scrollback_snapshot(self.scrollback_lines_to_serialize)
+                        } else {
+                            None
+                        },
                         focused_clients,
                         default_fg,
                         default_bg,
+                        p.output_log(),
+                        p.monitors(),
+                        p.broadcast_group(),
                     )
                 })
                 .collect();
@@ -7385,9 +7787,17 @@ impl Screen {
                         } else {
                             None
                         },
+                        if self.serialize_pane_scrollback {
+                            p.scrollback_snapshot(self.scrollback_lines_to_serialize)
+                        } else {
+                            None
+                        },
                         focused_clients,
                         default_fg,
                         default_bg,
+                        p.output_log(),
+                        p.monitors(),
+                        p.broadcast_group(),
                     )
                 })
                 .collect();
@@ -7433,6 +7843,83 @@ impl Screen {
             .borrow_mut()
             .remove(client_id);
     }
+    fn toggle_pane_group_sync(&mut self, client_id: &ClientId) {
+        self.current_pane_group
+            .borrow_mut()
+            .toggle_input_sync(client_id);
+    }
+    /// Makes the panes of this broadcast group (in all tabs) the pane group of the client, and
+    /// syncs its input to them
+    fn sync_broadcast_group(&mut self, broadcast_group: &str, client_id: ClientId) -> Result<()> {
+        let pane_ids: Vec<PaneId> = self
+            .tabs
+            .values()
+            .flat_map(|tab| tab.panes_in_broadcast_group(broadcast_group))
+            .collect();
+        if pane_ids.is_empty() {
+            return Err(anyhow!(
+                "No panes in broadcast group \"{}\"",
+                broadcast_group
+            ));
+        }
+        self.current_pane_group
+            .borrow_mut()
+            .sync_input_to_panes(pane_ids, &client_id);
+        let _ = self.log_and_report_session_state();
+        Ok(())
+    }
+    /// Writes to the focused pane of the client and to all the panes in its pane group, wherever
+    /// they are. Returns true if a UI update should be triggered.
+    fn write_to_synced_pane_group(
+        &mut self,
+        key_with_modifier: &Option<KeyWithModifier>,
+        raw_bytes: Vec<u8>,
+        is_kitty_keyboard_protocol: bool,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let pane_ids: Vec<PaneId> = self
+            .current_pane_group
+            .borrow()
+            .clone_inner()
+            .remove(&client_id)
+            .unwrap_or_default();
+        let active_pane_id = self.get_active_pane_id(&client_id);
+        let mut should_update_ui = false;
+        if active_pane_id.is_some() {
+            // this also clears the client's search and the mouse help, as typing normally does
+            should_update_ui |= self
+                .get_active_tab_mut(client_id)?
+                .write_to_active_terminal(
+                    key_with_modifier,
+                    raw_bytes.clone(),
+                    is_kitty_keyboard_protocol,
+                    client_id,
+                )?;
+        }
+        for pane_id in pane_ids
+            .into_iter()
+            .filter(|pane_id| Some(*pane_id) != active_pane_id)
+        {
+            let Some(tab) = self
+                .tabs
+                .values_mut()
+                .find(|tab| tab.has_pane_with_pid(&pane_id))
+            else {
+                continue;
+            };
+            should_update_ui |= tab
+                .write_to_pane_id(
+                    key_with_modifier,
+                    raw_bytes.clone(),
+                    is_kitty_keyboard_protocol,
+                    pane_id,
+                    Some(client_id),
+                    None,
+                )
+                .with_context(|| format!("failed to write to synced pane {:?}", pane_id))?;
+        }
+        Ok(should_update_ui)
+    }
     fn toggle_pane_id_in_group(&mut self, pane_id: PaneId, client_id: &ClientId) {
         let surface_size = self.size_of_tab_of_client(*client_id);
         {
@@ -7515,6 +8002,7 @@ impl Screen {
                 selectable_tiled_panes_count,
                 selectable_floating_panes_count,
                 tab_id: tab.id,
+                recently_closed_panes: tab.recently_closed_panes(),
                 has_bell_notification: tab.tab_has_pending_bell
                     && !self.active_tab_ids.values().any(|i| i == &tab.id),
                 is_flashing_bell: tab.tab_bell_flash
@@ -7583,6 +8071,10 @@ impl Screen {
             self.currently_marking_pane_group
                 .borrow_mut()
                 .remove(client_id);
+            // also stops syncing input to the (now empty) group
+            self.current_pane_group
+                .borrow_mut()
+                .clear_pane_group(client_id);
         }
         if !clients_with_empty_group.is_empty() {
             let all_tabs = self.get_tabs_mut();
@@ -7940,6 +8432,7 @@ pub(crate) fn screen_thread_main(
     let auto_layout = config_options.auto_layout.unwrap_or(true);
     let session_serialization = config_options.session_serialization.unwrap_or(true);
     let serialize_pane_viewport = config_options.serialize_pane_viewport.unwrap_or(false);
+    let serialize_pane_scrollback = config_options.serialize_pane_scrollback.unwrap_or(false);
     let scrollback_lines_to_serialize = config_options.scrollback_lines_to_serialize;
     let session_is_mirrored = config_options.mirror_session.unwrap_or(false);
     let layout_dir = config_options.layout_dir;
@@ -8030,6 +8523,7 @@ pub(crate) fn screen_thread_main(
         default_shell,
         session_serialization,
         serialize_pane_viewport,
+        serialize_pane_scrollback,
         scrollback_lines_to_serialize,
         styled_underlines,
         osc8_hyperlinks,
@@ -8084,6 +8578,7 @@ pub(crate) fn screen_thread_main(
                 screen
                     .pane_output_activity
                     .insert(PaneId::Terminal(pid), Instant::now());
+                screen.record_pane_bytes(PaneId::Terminal(pid), &vte_bytes);
                 let all_tabs = screen.get_tabs_mut();
                 let mut vte_bytes = Some(vte_bytes);
                 for tab in all_tabs.values_mut() {
@@ -8411,6 +8906,13 @@ pub(crate) fn screen_thread_main(
                 }
                 let mut state_changed = false;
                 let client_input_mode = screen.get_client_input_mode(client_id);
+                let input_is_synced_to_pane_group = !matches!(
+                    client_input_mode,
+                    Some(InputMode::EnterSearch) | Some(InputMode::RenamePane)
+                ) && screen
+                    .current_pane_group
+                    .borrow()
+                    .input_is_synced(&client_id);
                 match client_input_mode {
                     Some(InputMode::RenameTab) => {
                         if !(raw_bytes == BRACKETED_PASTE_BEGIN || raw_bytes == BRACKETED_PASTE_END)
@@ -8419,6 +8921,18 @@ pub(crate) fn screen_thread_main(
                             state_changed = true;
                         }
                     },
+                    _ if input_is_synced_to_pane_group => {
+                        match screen.write_to_synced_pane_group(
+                            &key_with_modifier,
+                            raw_bytes,
+                            is_kitty_keyboard_protocol,
+                            client_id,
+                        ) {
+                            Ok(true) => state_changed = true,
+                            Ok(false) => {},
+                            Err(e) => log::error!("{:?}", e),
+                        }
+                    },
                     _ => {
                         active_tab_and_connected_client_id!(
                             screen,
@@ -8900,6 +9414,73 @@ pub(crate) fn screen_thread_main(
                     },
                 }
             },
+            ScreenInstruction::StartRecording {
+                path,
+                target,
+                client_id,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let path = path.unwrap_or_else(|| {
+                    screen
+                        .session_recordings
+                        .default_recording_path(&screen.session_name)
+                });
+                let result = screen
+                    .start_recording(path.clone(), target, client_id)
+                    .map(|_| vec![path.display().to_string()]);
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
+            ScreenInstruction::StopRecording {
+                path,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let result = screen
+                    .session_recordings
+                    .stop(path.as_deref())
+                    .map(|paths| paths.iter().map(|p| p.display().to_string()).collect());
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
+            ScreenInstruction::StartPaneLog {
+                pane_id,
+                log_output,
+                client_id,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let path = log_output.path.display().to_string();
+                let result = screen
+                    .start_pane_log(pane_id, log_output, client_id)
+                    .map(|_| vec![path]);
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
+            ScreenInstruction::StopPaneLog {
+                pane_id,
+                client_id,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let result = screen
+                    .stop_pane_log(pane_id, client_id)
+                    .map(|path| vec![path.display().to_string()]);
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
+            ScreenInstruction::MonitorPane {
+                pane_id,
+                monitors,
+                client_id,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let result = screen
+                    .monitor_pane(pane_id, monitors, client_id)
+                    .map(|_| vec![]);
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
+            ScreenInstruction::CheckPaneMonitors => {
+                screen.check_pane_monitors()?;
+            },
             ScreenInstruction::DumpLayout(default_shell, client_id, completion_tx) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell, None);
@@ -9135,6 +9716,40 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
+            ScreenInstruction::RestartCommandPane(pane_id, restart_id) => {
+                let restarted = screen
+                    .get_tabs_mut()
+                    .values_mut()
+                    .find(|tab| tab.get_all_pane_ids().contains(&pane_id))
+                    .and_then(|tab| tab.restart_command_pane(pane_id, restart_id));
+                if let (PaneId::Terminal(terminal_pane_id), Some((exit_status, restart_count))) =
+                    (pane_id, restarted)
+                {
+                    let _ = screen
+                        .bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::Update(vec![(
+                            None,
+                            None,
+                            Event::CommandPaneRestarted(
+                                terminal_pane_id,
+                                exit_status,
+                                restart_count,
+                            ),
+                        )]));
+                    screen.render(None)?;
+                }
+            },
+            ScreenInstruction::ReadinessProbeSucceeded(pane_id, probe_id) => {
+                if let Some(tab) = screen
+                    .get_tabs_mut()
+                    .values_mut()
+                    .find(|tab| tab.has_pane_waiting_for_dependencies(pane_id))
+                {
+                    tab.readiness_probe_succeeded(pane_id, probe_id);
+                    screen.render(None)?;
+                }
+            },
             ScreenInstruction::MovePane(
                 client_id,
                 _completion_tx, // the action ends here, dropping this will release anything
@@ -9375,6 +9990,26 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::ReopenClosedPane(client_id, completion_tx) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.reopen_closed_pane(client_id, completion_tx), ?
+                );
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::UpdateClosedPaneCwd(pane_id, cwd) => {
+                let mut updated = false;
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.update_closed_pane_cwd(pane_id, cwd.clone()) {
+                        updated = true;
+                        break;
+                    }
+                }
+                if updated {
+                    screen.log_and_report_session_state()?;
+                }
+            },
             ScreenInstruction::SetSelectable(pid, selectable) => {
                 let all_tabs = screen.get_tabs_mut();
                 let mut found_plugin = false;
@@ -10022,6 +10657,26 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
                 screen.render(None)?;
             },
+            ScreenInstruction::TogglePaneGroupSync(
+                client_id,
+                _completion_tx, // the action ends here, dropping this will release anything
+                                // waiting for it
+            ) => {
+                screen.toggle_pane_group_sync(&client_id);
+                screen.render(None)?;
+            },
+            ScreenInstruction::SyncBroadcastGroup {
+                name,
+                client_id,
+                cli_client_id,
+                completion_tx,
+            } => {
+                let result = screen
+                    .sync_broadcast_group(&name, client_id)
+                    .map(|_| vec![]);
+                screen.render(None)?;
+                screen.report_result_to_cli(result, cli_client_id, completion_tx);
+            },
             ScreenInstruction::MouseEvent(
                 event,
                 client_id,
@@ -10163,6 +10818,43 @@ pub(crate) fn screen_thread_main(
                 );
                 screen.render(None)?;
             },
+            ScreenInstruction::SearchToggleRegex(
+                client_id,
+                _completion_tx, // the action ends here, dropping this will release anything
+                                // waiting for it
+            ) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render(None)?;
+            },
+            ScreenInstruction::CopyModeMove(motion, client_id, _completion_tx) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.copy_mode_move(motion, client_id)
+                );
+                screen.render(None)?;
+            },
+            ScreenInstruction::CopyModeSelect(selection, client_id, _completion_tx) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.copy_mode_select(selection, client_id)
+                );
+                screen.render(None)?;
+            },
+            ScreenInstruction::CopyModeYank(client_id, _completion_tx) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.copy_mode_yank(client_id),
+                    ?
+                );
+                screen.render(None)?;
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -12125,6 +12817,7 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::NotifyPaneClosedToSubscribers { pane_id } => {
                 screen.notify_pane_closed_to_subscribers(pane_id);
+                screen.stop_recordings_of_closed_pane(pane_id.into());
             },
             ScreenInstruction::UpdateBackgroundPluginSubscriptions(
                 plugin_id,
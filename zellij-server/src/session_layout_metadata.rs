@@ -6,9 +6,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use zellij_utils::common_path::common_path_all;
 use zellij_utils::pane_size::PaneGeom;
 use zellij_utils::{
-    data::{LayoutMetadata, PaneMetadata, TabMetadata},
+    data::{LayoutMetadata, PaneMetadata, PaneMonitors, TabMetadata},
     input::command::RunCommand,
-    input::layout::{Layout, Run, RunPlugin, RunPluginOrAlias},
+    input::layout::{Layout, LogOutput, Run, RunPlugin, RunPluginOrAlias},
     input::plugins::PluginAliases,
     session_serialization::{
         extract_command_and_args, extract_edit_and_line_number, extract_plugin_and_config,
@@ -288,6 +288,13 @@ impl SessionLayoutMetadata {
                         } else {
                             let mut run_command = RunCommand::new(PathBuf::from(command_name));
                             run_command.args = args;
+                            // the env and restart policy the pane was started with are not
+                            // visible in its command line
+                            if let Some(Run::Command(invoked_with)) = &pane_layout_metadata.run {
+                                run_command.env = invoked_with.env.clone();
+                                run_command.restart = invoked_with.restart;
+                                run_command.max_restarts = invoked_with.max_restarts;
+                            }
                             pane_layout_metadata.run = Some(Run::Command(run_command));
                         }
                     }
@@ -472,6 +479,7 @@ impl SessionLayoutMetadata {
             tabs,
             creation_time: current_time.clone(),
             update_time: current_time,
+            parameters: Default::default(),
         }
     }
 }
@@ -533,8 +541,12 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
+            scrollback_snapshot: self.scrollback_snapshot,
             default_fg: self.default_fg,
             default_bg: self.default_bg,
+            log_output: self.log_output,
+            monitors: self.monitors,
+            broadcast_group: self.broadcast_group,
         }
     }
 }
@@ -558,9 +570,13 @@ pub struct PaneLayoutMetadata {
     title: Option<String>,
     is_focused: bool,
     pane_contents: Option<String>,
+    scrollback_snapshot: Option<Vec<u8>>,
     focused_clients: Vec<ClientId>,
     default_fg: Option<String>,
     default_bg: Option<String>,
+    log_output: Option<LogOutput>,
+    monitors: Option<PaneMonitors>,
+    broadcast_group: Option<String>,
 }
 
 impl PaneLayoutMetadata {
@@ -572,9 +588,13 @@ impl PaneLayoutMetadata {
         title: Option<String>,
         is_focused: bool,
         pane_contents: Option<String>,
+        scrollback_snapshot: Option<Vec<u8>>,
         focused_clients: Vec<ClientId>,
         default_fg: Option<String>,
         default_bg: Option<String>,
+        log_output: Option<LogOutput>,
+        monitors: Option<PaneMonitors>,
+        broadcast_group: Option<String>,
     ) -> Self {
         PaneLayoutMetadata {
             id,
@@ -585,9 +605,13 @@ impl PaneLayoutMetadata {
             title,
             is_focused,
             pane_contents,
+            scrollback_snapshot,
             focused_clients,
             default_fg,
             default_bg,
+            log_output,
+            monitors,
+            broadcast_group,
         }
     }
     fn to_pane_metadata(&self) -> PaneMetadata {
@@ -698,9 +722,13 @@ mod tests {
             None,
             false,
             None,
+            None,
             vec![],
             None,
             None,
+            None,
+            None,
+            None,
         )
     }
 
@@ -717,9 +745,13 @@ mod tests {
             None,
             false,
             None,
+            None,
             vec![],
             None,
             None,
+            None,
+            None,
+            None,
         )
     }
 
@@ -904,4 +936,34 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn updated_terminal_command_keeps_the_env_it_was_started_with() {
+        let mut pane = make_command_pane(1, "./server", vec![]);
+        if let Some(Run::Command(run_command)) = pane.run.as_mut() {
+            run_command.env.insert(
+                "DATABASE_URL".to_owned(),
+                "postgres://localhost/staging".to_owned(),
+            );
+        }
+        let mut meta = session_with_editor("nvim", vec![pane]);
+        meta.update_terminal_commands(HashMap::from([(
+            1,
+            vec![
+                "./server".to_owned(),
+                "--port".to_owned(),
+                "8081".to_owned(),
+            ],
+        )]));
+        match get_first_tiled_run(&meta) {
+            Some(Run::Command(rc)) => {
+                assert_eq!(rc.args, vec!["--port".to_owned(), "8081".to_owned()]);
+                assert_eq!(
+                    rc.env.get("DATABASE_URL").map(|v| v.as_str()),
+                    Some("postgres://localhost/staging")
+                );
+            },
+            other => panic!("expected Command, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,373 @@
+//! Session recordings (`zellij action start-recording`), written as asciicast v2 files.
+//!
+//! A recording either taps the pty output of a single pane, or the composed output that is sent
+//! to a client (for a whole tab or the whole screen).
+//!
+//! Each recording is written to from a thread of its own, so that a slow or full disk does not
+//! hold up the screen thread.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::panes::PaneId;
+use zellij_utils::{
+    asciicast::{AsciicastEvent, AsciicastHeader, AsciicastWriter, ASCIICAST_FILE_EXTENSION},
+    channels::{self, RecvTimeoutError, Sender, TrySendError},
+    consts::ZELLIJ_CACHE_DIR,
+    data::ClientId,
+    errors::prelude::*,
+    pane_size::Size,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedOutput {
+    Pane(PaneId),
+    /// The output of this client, only while it is displaying the tab with this id
+    Tab(usize, ClientId),
+    Client(ClientId),
+}
+
+// events waiting for the writer thread, beyond these they are held back and coalesced
+const MAX_QUEUED_EVENTS: usize = 1024;
+// output held back while the writer thread is falling behind, beyond this it is dropped
+const MAX_BACKLOG_BYTES: usize = 16 * 1024 * 1024;
+const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+type TimedEvent = (Duration, AsciicastEvent);
+
+struct Recording {
+    output: RecordedOutput,
+    sender: Sender<TimedEvent>,
+    writer_thread: JoinHandle<()>,
+    // events that did not fit in the channel, sent before any newer ones
+    backlog: VecDeque<TimedEvent>,
+    backlog_bytes: usize,
+    is_dropping_output: bool,
+    started_at: Instant,
+    size: Size,
+    // the start of a utf8 character whose remaining bytes have not arrived yet
+    incomplete_utf8: Vec<u8>,
+}
+
+impl Recording {
+    fn write_output(&mut self, bytes: &[u8], size: Size) -> Result<()> {
+        if size != self.size {
+            self.size = size;
+            self.write_event(AsciicastEvent::Resize {
+                width: size.cols,
+                height: size.rows,
+            })?;
+        }
+        self.incomplete_utf8.extend_from_slice(bytes);
+        let (output, incomplete_utf8) = decode_utf8(&self.incomplete_utf8);
+        self.incomplete_utf8 = incomplete_utf8;
+        if output.is_empty() {
+            return Ok(());
+        }
+        self.write_event(AsciicastEvent::Output(output))
+    }
+    fn write_event(&mut self, event: AsciicastEvent) -> Result<()> {
+        let time = self.started_at.elapsed();
+        self.send_backlog()?;
+        if !self.backlog.is_empty() {
+            self.hold_back((time, event));
+            return Ok(());
+        }
+        match self.sender.try_send((time, event)) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(event)) => {
+                self.hold_back(event);
+                Ok(())
+            },
+            Err(TrySendError::Disconnected(_)) => Err(anyhow!("stopped writing to recording")),
+        }
+    }
+    fn send_backlog(&mut self) -> Result<()> {
+        while let Some(event) = self.backlog.pop_front() {
+            let event_bytes = event_len(&event.1);
+            match self.sender.try_send(event) {
+                Ok(()) => {
+                    self.backlog_bytes -= event_bytes;
+                },
+                Err(TrySendError::Full(event)) => {
+                    self.backlog.push_front(event);
+                    return Ok(());
+                },
+                Err(TrySendError::Disconnected(_)) => {
+                    return Err(anyhow!("stopped writing to recording"));
+                },
+            }
+        }
+        self.is_dropping_output = false;
+        Ok(())
+    }
+    // consecutive output is merged into a single event, keeping the time of the first one
+    fn hold_back(&mut self, event: TimedEvent) {
+        let event_bytes = event_len(&event.1);
+        if self.backlog_bytes + event_bytes > MAX_BACKLOG_BYTES {
+            if !self.is_dropping_output {
+                log::warn!("Recording is falling behind, dropping output");
+                self.is_dropping_output = true;
+            }
+            return;
+        }
+        self.backlog_bytes += event_bytes;
+        match (self.backlog.back_mut(), event) {
+            (Some((_, AsciicastEvent::Output(held_back))), (_, AsciicastEvent::Output(output))) => {
+                held_back.push_str(&output);
+            },
+            (_, event) => self.backlog.push_back(event),
+        }
+    }
+    /// Waits for everything still queued to be written to the file
+    fn finish(self) {
+        for event in self.backlog {
+            if self.sender.send(event).is_err() {
+                break;
+            }
+        }
+        drop(self.sender);
+        let _ = self.writer_thread.join();
+    }
+}
+
+fn event_len(event: &AsciicastEvent) -> usize {
+    match event {
+        AsciicastEvent::Output(data)
+        | AsciicastEvent::Input(data)
+        | AsciicastEvent::Marker(data) => data.len(),
+        AsciicastEvent::Resize { .. } => 0,
+    }
+}
+
+fn start_writer_thread(
+    mut writer: AsciicastWriter<BufWriter<File>>,
+    path: &Path,
+) -> Result<(Sender<TimedEvent>, JoinHandle<()>)> {
+    let (sender, receiver) = channels::bounded::<TimedEvent>(MAX_QUEUED_EVENTS);
+    let path = path.to_path_buf();
+    let writer_thread = thread::Builder::new()
+        .name("session_recording".to_owned())
+        .spawn(move || {
+            let mut has_unflushed_events = false;
+            loop {
+                // the receiver is dropped along with this thread if writing fails, which stops
+                // the recording on its next event
+                let written = match receiver.recv_timeout(FLUSH_INTERVAL) {
+                    Ok((time, event)) => {
+                        has_unflushed_events = true;
+                        writer.write_event(time, &event)
+                    },
+                    Err(RecvTimeoutError::Timeout) if has_unflushed_events => {
+                        has_unflushed_events = false;
+                        writer.flush()
+                    },
+                    Err(RecvTimeoutError::Timeout) => Ok(()),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        if let Err(e) = writer.flush() {
+                            log::error!("Failed to write recording {}: {:?}", path.display(), e);
+                        }
+                        break;
+                    },
+                };
+                if let Err(e) = written {
+                    log::error!("Failed to write recording {}: {:?}", path.display(), e);
+                    break;
+                }
+            }
+        })?;
+    Ok((sender, writer_thread))
+}
+
+/// Decodes as much of `bytes` as possible, replacing invalid sequences. Returns the decoded
+/// string and the bytes of a character that was cut off at the end.
+fn decode_utf8(mut bytes: &[u8]) -> (String, Vec<u8>) {
+    let mut decoded = String::new();
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                decoded.push_str(valid);
+                return (decoded, vec![]);
+            },
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                decoded.push_str(&String::from_utf8_lossy(valid));
+                match e.error_len() {
+                    Some(invalid_len) => {
+                        decoded.push(char::REPLACEMENT_CHARACTER);
+                        bytes = &rest[invalid_len..];
+                    },
+                    None => return (decoded, rest.to_vec()),
+                }
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SessionRecordings {
+    recordings: BTreeMap<PathBuf, Recording>,
+}
+
+impl SessionRecordings {
+    /// A path in the cache folder that is not taken by any existing file
+    pub fn default_recording_path(&self, session_name: &str) -> PathBuf {
+        let recordings_dir = ZELLIJ_CACHE_DIR.join("recordings");
+        let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let mut path = recordings_dir.join(format!(
+            "{}_{}.{}",
+            session_name, timestamp, ASCIICAST_FILE_EXTENSION
+        ));
+        let mut suffix = 1;
+        while path.exists() || self.recordings.contains_key(&path) {
+            path = recordings_dir.join(format!(
+                "{}_{}_{}.{}",
+                session_name, timestamp, suffix, ASCIICAST_FILE_EXTENSION
+            ));
+            suffix += 1;
+        }
+        path
+    }
+    pub fn start(
+        &mut self,
+        path: PathBuf,
+        output: RecordedOutput,
+        size: Size,
+        title: Option<String>,
+        initial_contents: Option<String>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to start recording to {}", path.display());
+        if self.recordings.contains_key(&path) {
+            return Err(anyhow!("{} is already being recorded to", path.display()))
+                .with_context(err_context);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(err_context)?;
+        }
+        // never truncate an existing file, a recording always starts a new one
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(err_context)?;
+        let header = AsciicastHeader::new(size.cols, size.rows, title);
+        let writer =
+            AsciicastWriter::new(BufWriter::new(file), &header).with_context(err_context)?;
+        let (sender, writer_thread) =
+            start_writer_thread(writer, &path).with_context(err_context)?;
+        let mut recording = Recording {
+            output,
+            sender,
+            writer_thread,
+            backlog: VecDeque::new(),
+            backlog_bytes: 0,
+            is_dropping_output: false,
+            started_at: Instant::now(),
+            size,
+            incomplete_utf8: vec![],
+        };
+        if let Some(initial_contents) = initial_contents {
+            recording
+                .write_output(initial_contents.as_bytes(), size)
+                .with_context(err_context)?;
+        }
+        self.recordings.insert(path, recording);
+        Ok(())
+    }
+    /// Stops the recording to `path`, or all recordings if it is None. Returns the paths of the
+    /// stopped recordings once everything they had queued is written.
+    pub fn stop(&mut self, path: Option<&Path>) -> Result<Vec<PathBuf>> {
+        match path {
+            Some(path) => match self.recordings.remove(path) {
+                Some(recording) => {
+                    recording.finish();
+                    Ok(vec![path.to_path_buf()])
+                },
+                None => Err(anyhow!("{} is not being recorded to", path.display())),
+            },
+            None if self.recordings.is_empty() => Err(anyhow!("there are no active recordings")),
+            None => Ok(finish_all(std::mem::take(&mut self.recordings))),
+        }
+    }
+    /// Stops the recordings of a pane that is closed. Returns the paths of the stopped recordings.
+    pub fn stop_pane(&mut self, pane_id: PaneId) -> Vec<PathBuf> {
+        let (stopped, recordings) = std::mem::take(&mut self.recordings)
+            .into_iter()
+            .partition(|(_, recording)| recording.output == RecordedOutput::Pane(pane_id));
+        self.recordings = recordings;
+        finish_all(stopped)
+    }
+    pub fn is_recording_pane(&self, pane_id: PaneId) -> bool {
+        self.recordings
+            .values()
+            .any(|r| r.output == RecordedOutput::Pane(pane_id))
+    }
+    pub fn is_recording_client_output(&self) -> bool {
+        self.recordings
+            .values()
+            .any(|r| !matches!(r.output, RecordedOutput::Pane(_)))
+    }
+    pub fn record_pane_bytes(&mut self, pane_id: PaneId, bytes: &[u8], size: Size) {
+        self.write_to_recordings(bytes, size, |output| {
+            output == RecordedOutput::Pane(pane_id)
+        });
+    }
+    pub fn record_client_output(
+        &mut self,
+        client_id: ClientId,
+        displayed_tab_id: Option<usize>,
+        output: &str,
+        size: Size,
+    ) {
+        self.write_to_recordings(
+            output.as_bytes(),
+            size,
+            |recorded_output| match recorded_output {
+                RecordedOutput::Client(recorded_client_id) => recorded_client_id == client_id,
+                RecordedOutput::Tab(tab_id, recorded_client_id) => {
+                    recorded_client_id == client_id && displayed_tab_id == Some(tab_id)
+                },
+                RecordedOutput::Pane(_) => false,
+            },
+        );
+    }
+    fn write_to_recordings(
+        &mut self,
+        bytes: &[u8],
+        size: Size,
+        should_record: impl Fn(RecordedOutput) -> bool,
+    ) {
+        // a recording we fail to write to (eg. its disk is full) is stopped rather than retried
+        // on every render
+        self.recordings.retain(|path, recording| {
+            if !should_record(recording.output) {
+                return true;
+            }
+            match recording.write_output(bytes, size) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::error!("Stopping recording to {}: {:?}", path.display(), e);
+                    false
+                },
+            }
+        });
+    }
+}
+
+fn finish_all(recordings: BTreeMap<PathBuf, Recording>) -> Vec<PathBuf> {
+    recordings
+        .into_iter()
+        .map(|(path, recording)| {
+            recording.finish();
+            path
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "./unit/session_recording_tests.rs"]
+mod session_recording_tests;
@@ -0,0 +1,154 @@
+use crate::panes::PaneId;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use zellij_utils::{
+    data::{ClosedPaneInfo, FloatingPaneCoordinates, NewPanePlacement},
+    input::{
+        command::{OpenFilePayload, RunCommand, TerminalAction},
+        layout::{PercentOrFixed, Run},
+    },
+    pane_size::PaneGeom,
+};
+
+const MAX_CLOSED_PANES: usize = 10;
+
+#[derive(Debug, Clone)]
+pub enum ClosedPanePlacement {
+    Tiled,
+    Floating(PaneGeom),
+    // the pane was stacked along with this one, if it had any stack siblings left
+    Stacked(Option<PaneId>),
+}
+
+#[derive(Debug, Clone)]
+pub struct ClosedPane {
+    pub pane_id: PaneId,
+    pub run: Option<Run>,
+    pub title: String,
+    pub custom_title: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub placement: ClosedPanePlacement,
+    pub borderless: bool,
+}
+
+impl ClosedPane {
+    /// What to spawn in order to bring this pane back, defaulting to a shell in the pane's cwd
+    pub fn terminal_action(&self, default_shell: &Path) -> TerminalAction {
+        match &self.run {
+            Some(Run::Command(run_command)) => TerminalAction::RunCommand(RunCommand {
+                cwd: self.cwd.clone().or_else(|| run_command.cwd.clone()),
+                originating_plugin: None,
+                ..run_command.clone()
+            }),
+            Some(Run::EditFile(path, line_number, cwd)) => {
+                TerminalAction::OpenFile(OpenFilePayload {
+                    path: path.clone(),
+                    line_number: *line_number,
+                    cwd: cwd.clone().or_else(|| self.cwd.clone()),
+                    originating_plugin: None,
+                })
+            },
+            Some(Run::Cwd(cwd)) => {
+                self.shell(default_shell, self.cwd.clone().or(Some(cwd.clone())))
+            },
+            Some(Run::Plugin(_)) | None => self.shell(default_shell, self.cwd.clone()),
+        }
+    }
+    /// Where to place the reopened pane, `stack_sibling_exists` tells whether the pane this one
+    /// was stacked with is still around and stacked
+    pub fn new_pane_placement(&self, stack_sibling_exists: bool) -> NewPanePlacement {
+        let borderless = Some(self.borderless);
+        match &self.placement {
+            ClosedPanePlacement::Floating(geom) => {
+                NewPanePlacement::Floating(Some(FloatingPaneCoordinates {
+                    x: Some(PercentOrFixed::Fixed(geom.x)),
+                    y: Some(PercentOrFixed::Fixed(geom.y)),
+                    width: Some(PercentOrFixed::Fixed(geom.cols.as_usize())),
+                    height: Some(PercentOrFixed::Fixed(geom.rows.as_usize())),
+                    pinned: Some(geom.is_pinned),
+                    borderless,
+                }))
+            },
+            ClosedPanePlacement::Stacked(Some(stacked_with)) if stack_sibling_exists => {
+                NewPanePlacement::Stacked {
+                    pane_id_to_stack_under: Some((*stacked_with).into()),
+                    borderless,
+                }
+            },
+            ClosedPanePlacement::Stacked(_) | ClosedPanePlacement::Tiled => {
+                NewPanePlacement::Tiled {
+                    direction: None,
+                    borderless,
+                }
+            },
+        }
+    }
+    pub fn stacked_with(&self) -> Option<PaneId> {
+        match self.placement {
+            ClosedPanePlacement::Stacked(stacked_with) => stacked_with,
+            _ => None,
+        }
+    }
+    fn shell(&self, default_shell: &Path, cwd: Option<PathBuf>) -> TerminalAction {
+        TerminalAction::RunCommand(RunCommand {
+            command: default_shell.to_path_buf(),
+            cwd,
+            use_terminal_title: true,
+            ..Default::default()
+        })
+    }
+}
+
+impl From<&ClosedPane> for ClosedPaneInfo {
+    fn from(closed_pane: &ClosedPane) -> Self {
+        let command = match &closed_pane.run {
+            Some(Run::Command(run_command)) => {
+                let mut command = vec![run_command.command.display().to_string()];
+                command.extend(run_command.args.iter().cloned());
+                command
+            },
+            _ => vec![],
+        };
+        ClosedPaneInfo {
+            title: closed_pane.title.clone(),
+            command,
+            cwd: closed_pane.cwd.clone(),
+            is_floating: matches!(closed_pane.placement, ClosedPanePlacement::Floating(_)),
+            is_stacked: matches!(closed_pane.placement, ClosedPanePlacement::Stacked(_)),
+        }
+    }
+}
+
+/// The most recently closed panes of a tab, newest first
+#[derive(Debug, Default)]
+pub struct ClosedPanes {
+    closed_panes: VecDeque<ClosedPane>,
+}
+
+impl ClosedPanes {
+    pub fn push(&mut self, closed_pane: ClosedPane) {
+        self.closed_panes.push_front(closed_pane);
+        self.closed_panes.truncate(MAX_CLOSED_PANES);
+    }
+    pub fn pop(&mut self) -> Option<ClosedPane> {
+        self.closed_panes.pop_front()
+    }
+    /// The cwd of a pane is only known to the pty thread, which reports it once the pane is
+    /// closed
+    pub fn update_cwd(&mut self, pane_id: PaneId, cwd: PathBuf) -> bool {
+        match self
+            .closed_panes
+            .iter_mut()
+            .find(|closed_pane| closed_pane.pane_id == pane_id)
+        {
+            Some(closed_pane) => {
+                closed_pane.cwd = Some(cwd);
+                true
+            },
+            None => false,
+        }
+    }
+    pub fn infos(&self) -> Vec<ClosedPaneInfo> {
+        self.closed_panes.iter().map(|c| c.into()).collect()
+    }
+}
@@ -4,6 +4,7 @@ use crate::resize_pty;
 use crate::tab::{get_next_terminal_position, HoldForCommand, Pane};
 
 use crate::{
+    background_jobs::BackgroundJob,
     os_input_output::ServerOsApi,
     panes::kitty_graphics::KittyImageStore,
     panes::sixel::SixelImageStore,
@@ -19,7 +20,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 use zellij_utils::{
     data::{Palette, Style},
-    input::layout::{FloatingPaneLayout, Run, RunPluginOrAlias, TiledPaneLayout},
+    input::layout::{FloatingPaneLayout, PaneDependency, Run, RunPluginOrAlias, TiledPaneLayout},
     input::options::PaneFrameStyle,
     pane_size::{PaneGeom, Size, SizeInPixels, Viewport},
 };
@@ -653,9 +654,19 @@ impl<'a> LayoutApplier<'a> {
             self.explicitly_disable_kitty_keyboard_protocol,
             None,
         );
+        let restored_scrollback = match &floating_pane_layout.pane_scrollback_snapshot {
+            Some(snapshot) => new_pane
+                .restore_scrollback_snapshot(snapshot)
+                .map_err(|e| log::error!("{:?}", e))
+                .is_ok(),
+            None => false,
+        };
         if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
-            new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
-            new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            // the snapshot already contains these contents, with their styles
+            if !restored_scrollback {
+                new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
+                new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            }
         }
         if floating_pane_layout.borderless.unwrap_or(false) {
             new_pane.set_borderless(true);
@@ -668,6 +679,24 @@ impl<'a> LayoutApplier<'a> {
                 floating_pane_layout.default_bg.clone(),
             );
         }
+        if let Some(log_output) = &floating_pane_layout.log_output {
+            if let Err(e) = new_pane.start_output_log(log_output.clone()) {
+                log::error!("{:?}", e);
+            }
+        }
+        if let Some(broadcast_group) = &floating_pane_layout.broadcast_group {
+            new_pane.set_broadcast_group(Some(broadcast_group.clone()));
+        }
+        if !floating_pane_layout.depends_on.is_empty() {
+            new_pane.set_waiting_for(Some(waiting_for(&floating_pane_layout.depends_on)));
+            new_pane.set_pane_dependencies(floating_pane_layout.depends_on.clone());
+        }
+        if let Some(monitors) = floating_pane_layout.monitors {
+            new_pane.set_monitors(monitors);
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::StartPaneMonitorTicks);
+        }
         if let Some(held_command) = hold_for_command {
             new_pane.hold(None, true, held_command.clone());
         }
@@ -730,9 +759,19 @@ impl<'a> LayoutApplier<'a> {
             self.explicitly_disable_kitty_keyboard_protocol,
             notification_end,
         );
+        let restored_scrollback = match &layout.pane_scrollback_snapshot {
+            Some(snapshot) => new_pane
+                .restore_scrollback_snapshot(snapshot)
+                .map_err(|e| log::error!("{:?}", e))
+                .is_ok(),
+            None => false,
+        };
         if let Some(pane_initial_contents) = &layout.pane_initial_contents {
-            new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
-            new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            // the snapshot already contains these contents, with their styles
+            if !restored_scrollback {
+                new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
+                new_pane.handle_pty_bytes("\n\r".as_bytes().into());
+            }
         }
         new_pane.set_borderless(layout.borderless.unwrap_or(false));
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
@@ -741,6 +780,24 @@ impl<'a> LayoutApplier<'a> {
         if layout.default_fg.is_some() || layout.default_bg.is_some() {
             new_pane.set_pane_default_colors(layout.default_fg.clone(), layout.default_bg.clone());
         }
+        if let Some(log_output) = &layout.log_output {
+            if let Err(e) = new_pane.start_output_log(log_output.clone()) {
+                log::error!("{:?}", e);
+            }
+        }
+        if let Some(broadcast_group) = &layout.broadcast_group {
+            new_pane.set_broadcast_group(Some(broadcast_group.clone()));
+        }
+        if !layout.depends_on.is_empty() {
+            new_pane.set_waiting_for(Some(waiting_for(&layout.depends_on)));
+            new_pane.set_pane_dependencies(layout.depends_on.clone());
+        }
+        if let Some(monitors) = layout.monitors {
+            new_pane.set_monitors(monitors);
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::StartPaneMonitorTicks);
+        }
         if let Some(held_command) = hold_for_command {
             new_pane.hold(None, true, held_command.clone());
         }
@@ -1415,3 +1472,12 @@ impl<'a> PaneApplier<'a> {
         }
     }
 }
+
+// the names of the panes a pane depends on, as displayed in its frame
+fn waiting_for(depends_on: &[PaneDependency]) -> String {
+    depends_on
+        .iter()
+        .map(|dependency| dependency.pane_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
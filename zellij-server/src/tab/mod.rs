@@ -2,9 +2,11 @@
 //! as well as how they should be resized
 
 mod clipboard;
+mod closed_panes;
 mod copy_command;
 mod layout_applier;
 mod mouse_handler;
+mod pane_dependencies;
 mod swap_layouts;
 
 use crate::plugins::PluginId;
@@ -17,10 +19,12 @@ use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 use zellij_utils::data::PaneContents;
 use zellij_utils::data::{
-    Direction, KeyWithModifier, NewPanePlacement, PaneInfo, PermissionStatus, PermissionType,
-    PluginPermission, RegexHighlight, ResizeStrategy, Style, StyledText, WebSharing,
+    ClosedPaneInfo, Direction, FilesystemPermission, KeyWithModifier, NewPanePlacement, PaneInfo,
+    PaneMonitorAlert, PaneMonitors, PermissionStatus, PermissionType, PluginPermission,
+    RegexHighlight, ResizeStrategy, Style, StyledText, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::{CopyModeMotion, CopyModeSelection};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::DEFAULT_WORD_SEPARATORS;
@@ -39,7 +43,9 @@ use crate::ui::{
     loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams,
     pane_contents_and_ui::PaneContentsAndUi,
 };
+use closed_panes::{ClosedPane, ClosedPanePlacement, ClosedPanes};
 use layout_applier::LayoutApplier;
+use pane_dependencies::PaneDependencies;
 use swap_layouts::SwapLayouts;
 
 use self::clipboard::ClipboardProvider;
@@ -61,7 +67,7 @@ use crate::{
 };
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     str,
@@ -71,8 +77,8 @@ use zellij_utils::{
     input::{
         command::TerminalAction,
         layout::{
-            FloatingPaneLayout, Run, RunPluginOrAlias, SplitDirection, SwapFloatingLayout,
-            SwapTiledLayout, TiledPaneLayout,
+            FloatingPaneLayout, LogOutput, PaneDependency, Run, RunPluginOrAlias, SplitDirection,
+            SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
         },
         options::PaneFrameStyle,
         parse_keys,
@@ -237,6 +243,8 @@ pub(crate) struct Tab {
     pending_instructions: Vec<BufferedTabInstruction>, // instructions that came while the tab was
     // pending and need to be re-applied
     swap_layouts: SwapLayouts,
+    closed_panes: ClosedPanes,
+    pane_dependencies: PaneDependencies,
     default_shell: PathBuf,
     default_editor: Option<PathBuf>,
     debug: bool,
@@ -252,6 +260,7 @@ pub(crate) struct Tab {
     dimmed_clients: HashSet<ClientId>,
     plugin_hover_pane_id: HashMap<ClientId, PaneId>,
     mouse_last_pane_id: HashMap<ClientId, PaneId>,
+    copy_mode_pane_id: HashMap<ClientId, PaneId>,
     mouse_help_text_visible: HashMap<ClientId, bool>,
     last_mouse_activity_time: HashMap<ClientId, Instant>,
     last_hint_text: HashMap<ClientId, BTreeMap<usize, StyledText>>,
@@ -685,9 +694,27 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn enter_copy_mode(&mut self) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn exit_copy_mode(&mut self) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn copy_mode_move(&mut self, _motion: CopyModeMotion) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn copy_mode_select(&mut self, _selection: CopyModeSelection) {
+        // No-op by default (only terminal-panes currently have copy mode)
+    }
+    fn copy_mode_selected_text(&self) -> Option<String> {
+        None
+    }
     fn is_alternate_mode_active(&self) -> bool {
         // False by default (only terminal-panes support alternate mode)
         false
@@ -741,9 +768,64 @@ pub trait Pane {
     fn serialize(&self, _scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         None
     }
+    fn scrollback_snapshot(
+        &self,
+        _scrollback_lines_to_serialize: Option<usize>,
+    ) -> Option<Vec<u8>> {
+        None
+    }
+    /// Start appending the output of this pane to a file, replacing any previous log.
+    /// Only terminal panes can be logged.
+    fn start_output_log(&mut self, _log_output: LogOutput) -> Result<()> {
+        Err(anyhow!("only terminal panes can be logged"))
+    }
+    /// Returns the path of the log that was stopped, if any
+    fn stop_output_log(&mut self) -> Option<PathBuf> {
+        None
+    }
+    fn output_log(&self) -> Option<LogOutput> {
+        None
+    }
+    /// Called with the bytes coming from the pty, before they are buffered or interpreted
+    fn received_pty_bytes(&mut self, _bytes: &[u8]) {}
+    /// Replaces the activity and silence monitors of this pane, empty monitors remove them.
+    /// Only terminal panes can be monitored.
+    fn set_monitors(&mut self, _monitors: PaneMonitors) {}
+    fn monitors(&self) -> Option<PaneMonitors> {
+        None
+    }
+    /// Returns the alerts raised by the monitors of this pane since it was last checked
+    fn check_monitors(&mut self, _now: Instant) -> Vec<PaneMonitorAlert> {
+        vec![]
+    }
+    /// The named broadcast group (from the layout) this pane belongs to, if any. Only terminal
+    /// panes can be part of one.
+    fn set_broadcast_group(&mut self, _broadcast_group: Option<String>) {}
+    fn broadcast_group(&self) -> Option<String> {
+        None
+    }
+    /// The panes (`depends_on` in the layout) this pane waits for before running its command,
+    /// handed over to the tab once the layout is applied. Only terminal panes can have them.
+    fn set_pane_dependencies(&mut self, _dependencies: Vec<PaneDependency>) {}
+    fn take_pane_dependencies(&mut self) -> Vec<PaneDependency> {
+        vec![]
+    }
+    /// The names of the panes this suspended pane is still waiting for, shown in its frame
+    fn set_waiting_for(&mut self, _waiting_for: Option<String>) {}
     fn rerun(&mut self) -> Option<RunCommand> {
         None
     } // only relevant to terminal panes
+    /// If the restart policy of this held command pane wants it restarted, returns the id of the
+    /// scheduled restart and how long to wait before it
+    fn schedule_restart(&mut self) -> Option<(u64, Duration)> {
+        None
+    } // only relevant to terminal panes
+    fn restart(&mut self, _restart_id: u64) -> Option<RunCommand> {
+        None
+    } // only relevant to terminal panes
+    fn restart_count(&self) -> usize {
+        0
+    }
     fn update_theme(&mut self, _theme: Styling) {}
     fn set_selection_options(&mut self, _osc133_command_selection: bool, _word_separators: &str) {}
     fn update_arrow_fonts(&mut self, _should_support_arrow_fonts: bool) {}
@@ -811,9 +893,15 @@ pub enum GuestChoiceIndicator {
 pub enum AdjustedInput {
     WriteBytesToTerminal(Vec<u8>),
     ReRunCommandInThisPane(RunCommand),
-    PermissionRequestResult(Vec<PermissionType>, PermissionStatus),
+    PermissionRequestResult(
+        Vec<PermissionType>,
+        Vec<FilesystemPermission>,
+        PermissionStatus,
+    ),
     CloseThisPane,
-    DropToShellInThisPane { working_dir: Option<PathBuf> },
+    DropToShellInThisPane {
+        working_dir: Option<PathBuf>,
+    },
     WriteKeyToPlugin(KeyWithModifier),
     GuestModalSelectionChanged,
     GuestModalZoom,
@@ -998,6 +1086,8 @@ impl Tab {
             is_pending: true, // will be switched to false once the layout is applied
             pending_instructions: vec![],
             swap_layouts,
+            closed_panes: ClosedPanes::default(),
+            pane_dependencies: PaneDependencies::default(),
             default_shell,
             debug,
             arrow_fonts,
@@ -1012,6 +1102,7 @@ impl Tab {
             mouse_hover_pane_id: HashMap::new(),
             plugin_hover_pane_id: HashMap::new(),
             mouse_last_pane_id: HashMap::new(),
+            copy_mode_pane_id: HashMap::new(),
             mouse_help_text_visible: HashMap::new(),
             last_mouse_activity_time: HashMap::new(),
             last_hint_text: HashMap::new(),
@@ -1764,6 +1855,7 @@ impl Tab {
                         .non_fatal();
                 }
                 self.tiled_panes.reapply_pane_frames();
+                self.register_pane_dependencies();
                 self.is_pending = false;
                 self.apply_buffered_instructions().non_fatal();
             },
@@ -1866,6 +1958,7 @@ impl Tab {
                 let _ = self.relayout_floating_panes(false);
 
                 self.tiled_panes.reapply_pane_frames();
+                self.register_pane_dependencies();
                 self.is_pending = false;
 
                 LayoutApplier::offset_viewport(
@@ -2284,6 +2377,7 @@ impl Tab {
             .get_mut(&client_id)
             .map(|c| c.change_to_default_mode()); // TODO: no races?
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.exit_copy_mode(client_id);
         self.mouse_help_text_visible.remove(&client_id);
         self.mouse_last_pane_id.remove(&client_id);
         self.last_mouse_activity_time.remove(&client_id);
@@ -2455,15 +2549,23 @@ impl Tab {
         Ok(())
     }
     fn normalize_invoked_with_for_default_shell(&self, invoked_with: Option<Run>) -> Option<Run> {
-        let default_shell_run_command = Run::Command(RunCommand {
+        let default_shell_run_command = RunCommand {
             command: self.default_shell.clone(),
             use_terminal_title: true,
             ..Default::default()
-        });
-        if invoked_with == Some(default_shell_run_command) {
-            None
-        } else {
-            invoked_with
+        };
+        match invoked_with {
+            // a shell that was started in a specific folder (eg. a reopened pane) is still the
+            // default shell
+            Some(Run::Command(run_command))
+                if RunCommand {
+                    cwd: None,
+                    ..run_command.clone()
+                } == default_shell_run_command =>
+            {
+                None
+            },
+            invoked_with => invoked_with,
         }
     }
     pub fn new_pane(
@@ -3841,6 +3943,34 @@ impl Tab {
         }
         (newly_notified_panes, tab_bell_newly_set)
     }
+    /// Returns the alerts raised by the monitors of all the panes in this tab. The alerting panes
+    /// ring their bell, so they are marked on the next render.
+    pub fn check_pane_monitors(&mut self, now: Instant) -> Vec<(PaneId, PaneMonitorAlert)> {
+        let monitored_panes: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .chain(self.suppressed_stack_list_members())
+            .filter(|(_, pane)| pane.monitors().is_some())
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        let mut alerts = vec![];
+        for pane_id in monitored_panes {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                for alert in pane.check_monitors(now) {
+                    alerts.push((pane_id, alert));
+                }
+            }
+        }
+        alerts
+    }
+    pub fn has_pane_monitors(&self) -> bool {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .chain(self.suppressed_stack_list_members())
+            .any(|(_, pane)| pane.monitors().is_some())
+    }
     pub fn clear_bell_notification_for_pane(&mut self, pane_id: PaneId) {
         self.panes_with_pending_bell.remove(&pane_id);
         if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
@@ -3917,6 +4047,16 @@ impl Tab {
                 .values()
                 .any(|s_p| s_p.1.pid() == *pid)
     }
+    pub fn panes_in_broadcast_group(&self, broadcast_group: &str) -> Vec<PaneId> {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .map(|(_, pane)| pane)
+            .chain(self.suppressed_panes.values().map(|(_, pane)| pane))
+            .filter(|pane| pane.broadcast_group().as_deref() == Some(broadcast_group))
+            .map(|pane| pane.pid())
+            .collect()
+    }
     pub fn has_non_suppressed_pane_with_pid(&self, pid: &PaneId) -> bool {
         self.tiled_panes.panes_contain(pid) || self.floating_panes.panes_contain(pid)
     }
@@ -4110,6 +4250,10 @@ impl Tab {
             return Ok(());
         }
         let err_context = || format!("failed to handle pty bytes from fd {pid}");
+        let dependents = self
+            .pane_dependencies
+            .output_received(PaneId::Terminal(pid), &bytes);
+        self.update_dependent_panes(dependents);
         if let Some(terminal_output) = self
             .tiled_panes
             .get_pane_mut(PaneId::Terminal(pid))
@@ -4121,6 +4265,7 @@ impl Tab {
                     .map(|s_p| &mut s_p.1)
             })
         {
+            terminal_output.received_pty_bytes(&bytes);
             // If the pane is scrolled buffer the vte events
             if terminal_output.is_scrolled() {
                 self.pending_vte_events.entry(pid).or_default().push(bytes);
@@ -4484,6 +4629,7 @@ impl Tab {
                             .with_context(err_context)?;
                     },
                     Some(AdjustedInput::ReRunCommandInThisPane(command)) => {
+                        self.forget_pane_dependencies(PaneId::Terminal(active_terminal_id));
                         self.pids_waiting_resize.insert(active_terminal_id);
                         self.senders
                             .send_to_pty(PtyInstruction::ReRunCommandInPane(
@@ -4499,6 +4645,7 @@ impl Tab {
                         should_update_ui = true;
                     },
                     Some(AdjustedInput::DropToShellInThisPane { working_dir }) => {
+                        self.forget_pane_dependencies(PaneId::Terminal(active_terminal_id));
                         self.pids_waiting_resize.insert(active_terminal_id);
                         self.senders
                             .send_to_pty(PtyInstruction::DropToShellInPane {
@@ -4563,7 +4710,11 @@ impl Tab {
                         .send_to_plugin(PluginInstruction::Update(plugin_updates))
                         .with_context(err_context)?;
                 },
-                Some(AdjustedInput::PermissionRequestResult(permissions, status)) => {
+                Some(AdjustedInput::PermissionRequestResult(
+                    permissions,
+                    filesystem_permissions,
+                    status,
+                )) => {
                     if active_pane.query_should_be_suppressed() {
                         active_pane.set_should_be_suppressed(false);
                         self.suppress_pane(PaneId::Plugin(pid), client_id);
@@ -4574,6 +4725,7 @@ impl Tab {
                             pid,
                             client_id,
                             permissions,
+                            filesystem_permissions,
                             status,
                             None,
                         ))
@@ -4865,12 +5017,14 @@ impl Tab {
 
         let current_pane_group: HashMap<ClientId, Vec<PaneId>> =
             { self.current_pane_group.borrow().clone_inner() };
+        let input_synced_clients = self.current_pane_group.borrow().input_synced_clients();
         self.tiled_panes
             .render(
                 output,
                 self.floating_panes.panes_are_visible(),
                 &self.mouse_hover_pane_id,
                 current_pane_group.clone(),
+                &input_synced_clients,
                 client_id_override,
                 &self.mouse_help_text_visible,
                 self.mouse_scroll_resize,
@@ -4889,6 +5043,7 @@ impl Tab {
                     output,
                     &self.mouse_hover_pane_id,
                     current_pane_group,
+                    &input_synced_clients,
                     client_id_override,
                     &self.mouse_help_text_visible,
                     self.mouse_scroll_resize,
@@ -5645,6 +5800,25 @@ impl Tab {
         ignore_suppressed_panes: bool,
         exit_status: Option<i32>,
     ) {
+        self.forget_pane_dependencies(id);
+        let dependents = self.pane_dependencies.exited(id, exit_status);
+        self.update_dependent_panes(dependents);
+        for dependent in self.pane_dependencies.closed(id) {
+            log::warn!(
+                "Pane {:?} was closed before it was ready, pane {:?} will not be started",
+                id,
+                dependent
+            );
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::StopReadinessProbes(dependent));
+            if let Some(pane) = self.get_pane_with_id_mut(dependent) {
+                pane.set_waiting_for(None);
+            }
+        }
+        if !ignore_suppressed_panes {
+            self.remember_closed_pane(id);
+        }
         let id_parks_a_different_pane = self.pane_parked_by(&id).is_some();
         if !ignore_suppressed_panes
             && !id_parks_a_different_pane
@@ -5732,6 +5906,45 @@ impl Tab {
                 pane_id: id.into(),
             });
     }
+    fn remember_closed_pane(&mut self, id: PaneId) {
+        let is_scrollback_editor = self
+            .suppressed_panes
+            .get(&id)
+            .map(|(is_scrollback_editor, _)| *is_scrollback_editor)
+            .unwrap_or(false);
+        if !matches!(id, PaneId::Terminal(_)) || is_scrollback_editor {
+            return;
+        }
+        let is_floating = self.floating_panes.panes_contain(&id);
+        let Some(pane) = self.get_pane_with_id(id) else {
+            return;
+        };
+        let geom = pane.position_and_size();
+        let placement = if is_floating {
+            ClosedPanePlacement::Floating(geom)
+        } else if let Some(stack_id) = geom.stacked {
+            let stacked_with = self
+                .tiled_panes
+                .get_panes()
+                .find(|(pane_id, pane)| {
+                    **pane_id != id && pane.position_and_size().stacked == Some(stack_id)
+                })
+                .map(|(pane_id, _)| *pane_id);
+            ClosedPanePlacement::Stacked(stacked_with)
+        } else {
+            ClosedPanePlacement::Tiled
+        };
+        let closed_pane = ClosedPane {
+            pane_id: id,
+            run: pane.invoked_with().clone(),
+            title: pane.current_title(),
+            custom_title: pane.custom_title(),
+            cwd: None, // reported by the pty thread once it closes the pane
+            placement,
+            borderless: pane.borderless(),
+        };
+        self.closed_panes.push(closed_pane);
+    }
     pub fn extract_pane(
         &mut self,
         id: PaneId,
@@ -5841,6 +6054,10 @@ impl Tab {
                 ));
             return;
         }
+        // panes that are not held on exit are only held to be restarted
+        let held_for_restart = !is_first_run
+            && !run_command.hold_on_close
+            && run_command.restart.should_restart(exit_status);
         if self.floating_panes.panes_contain(&id) {
             self.floating_panes
                 .hold_pane(id, exit_status, is_first_run, run_command);
@@ -5850,6 +6067,146 @@ impl Tab {
         } else if let Some(pane) = self.suppressed_panes.values_mut().find(|p| p.1.pid() == id) {
             pane.1.hold(exit_status, is_first_run, run_command);
         }
+        let scheduled_restart = self
+            .floating_panes
+            .get_mut(&id)
+            .or_else(|| self.tiled_panes.get_pane_mut(id))
+            .or_else(|| self.suppressed_panes.get_mut(&id).map(|p| &mut p.1))
+            .and_then(|pane| pane.schedule_restart());
+        match scheduled_restart {
+            Some((restart_id, delay)) => {
+                let _ = self
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::RestartCommandPane(
+                        id, restart_id, delay,
+                    ));
+            },
+            None if held_for_restart => {
+                // out of restarts, this pane closes like it would have without a restart policy
+                let _ = self.senders.send_to_screen(ScreenInstruction::ClosePane(
+                    id,
+                    None,
+                    None,
+                    exit_status,
+                ));
+                return;
+            },
+            None => {},
+        }
+        let dependents = self.pane_dependencies.exited(id, exit_status);
+        self.update_dependent_panes(dependents);
+    }
+    pub fn restart_command_pane(
+        &mut self,
+        pane_id: PaneId,
+        restart_id: u64,
+    ) -> Option<(Option<i32>, usize)> {
+        // returns the exit status that triggered the restart and the restart count if the
+        // pane was restarted
+        let PaneId::Terminal(terminal_pane_id) = pane_id else {
+            return None;
+        };
+        let pane = self
+            .floating_panes
+            .get_mut(&pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| self.suppressed_panes.get_mut(&pane_id).map(|p| &mut p.1))?;
+        let exit_status = pane.exit_status();
+        let command_to_restart = pane.restart(restart_id)?;
+        let restart_count = pane.restart_count();
+        self.pids_waiting_resize.insert(terminal_pane_id);
+        let _ = self.senders.send_to_pty(PtyInstruction::ReRunCommandInPane(
+            pane_id,
+            command_to_restart,
+            None,
+        ));
+        Some((exit_status, restart_count))
+    }
+    // hands the dependencies of the panes created by the layout over to self.pane_dependencies,
+    // the panes they refer to are looked up by name in this tab
+    fn register_pane_dependencies(&mut self) {
+        let pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        let mut pane_ids_by_name = HashMap::new();
+        let mut dependents = vec![];
+        for pane_id in pane_ids {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane_ids_by_name
+                    .entry(pane.current_title())
+                    .or_insert(pane_id);
+                let dependencies = pane.take_pane_dependencies();
+                if !dependencies.is_empty() {
+                    dependents.push((pane_id, dependencies));
+                }
+            }
+        }
+        let mut registered_dependents = vec![];
+        for (dependent, dependencies) in dependents {
+            let mut resolved_dependencies = vec![];
+            for dependency in dependencies {
+                match pane_ids_by_name.get(&dependency.pane_name) {
+                    Some(pane_id) => resolved_dependencies.push((*pane_id, dependency)),
+                    None => log::warn!(
+                        "Pane {:?} depends on {:?}, but there is no pane with this name in its tab",
+                        dependent,
+                        dependency.pane_name
+                    ),
+                }
+            }
+            let background_probes = self.pane_dependencies.add(dependent, resolved_dependencies);
+            for (probe_id, probe) in background_probes {
+                let _ = self
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::StartReadinessProbe(
+                        dependent, probe_id, probe,
+                    ));
+            }
+            registered_dependents.push(dependent);
+        }
+        // panes left without any dependency to wait for are started right away
+        self.update_dependent_panes(registered_dependents);
+    }
+    // starts the panes that are no longer waiting for anything and updates what the others are
+    // shown to be waiting for
+    fn update_dependent_panes(&mut self, dependents: Vec<PaneId>) {
+        for dependent in dependents {
+            if self.pane_dependencies.is_waiting(dependent) {
+                let waiting_for = self.pane_dependencies.waiting_for(dependent);
+                if let Some(pane) = self.get_pane_with_id_mut(dependent) {
+                    pane.set_waiting_for(waiting_for);
+                }
+            } else if let PaneId::Terminal(terminal_pane_id) = dependent {
+                let _ = self
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::StopReadinessProbes(dependent));
+                if let Some(pane) = self.get_pane_with_id_mut(dependent) {
+                    pane.set_waiting_for(None);
+                }
+                self.rerun_terminal_pane_with_id(terminal_pane_id, None);
+            }
+        }
+    }
+    // the pane no longer waits for its dependencies, eg. because it was run by the user
+    fn forget_pane_dependencies(&mut self, pane_id: PaneId) {
+        if self.pane_dependencies.remove(pane_id) {
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::StopReadinessProbes(pane_id));
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.set_waiting_for(None);
+            }
+        }
+    }
+    pub fn has_pane_waiting_for_dependencies(&self, pane_id: PaneId) -> bool {
+        self.pane_dependencies.is_waiting(pane_id)
+    }
+    pub fn readiness_probe_succeeded(&mut self, dependent: PaneId, probe_id: u64) {
+        let dependents = self.pane_dependencies.probe_succeeded(dependent, probe_id);
+        self.update_dependent_panes(dependents);
     }
     pub fn replace_pane_with_suppressed_pane(
         &mut self,
@@ -6755,12 +7112,70 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
         }
     }
 
+    pub fn enter_copy_mode(&mut self, client_id: ClientId) {
+        if self.copy_mode_pane_id.contains_key(&client_id) {
+            return;
+        }
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let pane_id = active_pane.pid();
+            active_pane.enter_copy_mode();
+            self.copy_mode_pane_id.insert(client_id, pane_id);
+        }
+    }
+
+    pub fn is_in_copy_mode(&self, client_id: ClientId) -> bool {
+        self.copy_mode_pane_id.contains_key(&client_id)
+    }
+
+    // focus might have moved while in copy mode, so we leave it in the pane it was entered in
+    pub fn exit_copy_mode(&mut self, client_id: ClientId) {
+        let Some(pane_id) = self.copy_mode_pane_id.remove(&client_id) else {
+            return;
+        };
+        let pane_is_in_copy_mode_for_other_clients =
+            self.copy_mode_pane_id.values().any(|p| *p == pane_id);
+        if !pane_is_in_copy_mode_for_other_clients {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.exit_copy_mode();
+            }
+        }
+    }
+
+    pub fn copy_mode_move(&mut self, motion: CopyModeMotion, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.copy_mode_move(motion);
+        }
+    }
+
+    pub fn copy_mode_select(&mut self, selection: CopyModeSelection, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.copy_mode_select(selection);
+        }
+    }
+
+    pub fn copy_mode_yank(&mut self, client_id: ClientId) -> Result<()> {
+        let selected_text = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .and_then(|p| p.copy_mode_selected_text());
+        if let Some(selected_text) = selected_text {
+            self.copy_text_to_clipboard(&selected_text)
+                .with_context(|| format!("failed to yank copy mode selection for {client_id}"))?;
+        }
+        Ok(())
+    }
+
     pub fn is_pending(&self) -> bool {
         self.is_pending
     }
@@ -7399,6 +7814,7 @@ impl Tab {
         completion_tx: Option<NotificationEnd>,
     ) {
         let pane_id = PaneId::Terminal(terminal_pane_id);
+        self.forget_pane_dependencies(pane_id);
         match self
             .floating_panes
             .get_mut(&pane_id)
@@ -8013,6 +8429,38 @@ impl Tab {
             .send_to_pty(PtyInstruction::ClosePane(pane_id, completion_tx))?;
         Ok(())
     }
+    /// Spawns the most recently closed pane of this tab again, does nothing if there isn't one
+    pub fn reopen_closed_pane(
+        &mut self,
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    ) -> Result<()> {
+        let Some(closed_pane) = self.closed_panes.pop() else {
+            return Ok(());
+        };
+        let stack_sibling_exists = closed_pane
+            .stacked_with()
+            .and_then(|pane_id| self.tiled_panes.get_pane(pane_id))
+            .map(|pane| pane.position_and_size().stacked.is_some())
+            .unwrap_or(false);
+        self.senders
+            .send_to_pty(PtyInstruction::SpawnTerminal(
+                Some(closed_pane.terminal_action(&self.default_shell)),
+                closed_pane.custom_title.clone(),
+                closed_pane.new_pane_placement(stack_sibling_exists),
+                false, // start suppressed
+                ClientTabIndexOrPaneId::ClientId(client_id),
+                completion_tx,
+                false, // set_blocking
+            ))
+            .with_context(|| format!("failed to reopen closed pane {:?}", closed_pane.pane_id))
+    }
+    pub fn update_closed_pane_cwd(&mut self, pane_id: PaneId, cwd: PathBuf) -> bool {
+        self.closed_panes.update_cwd(pane_id, cwd)
+    }
+    pub fn recently_closed_panes(&self) -> Vec<ClosedPaneInfo> {
+        self.closed_panes.infos()
+    }
     pub fn rename_pane_by_pane_id(&mut self, pane_id: PaneId, name: Vec<u8>) -> Result<()> {
         if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
             pane.rename(name);
@@ -0,0 +1,222 @@
+//! Command panes from a layout that wait for other panes in their tab to be ready (`depends_on`)
+//! before running their command.
+//!
+//! The tab feeds this the output and exit statuses of its panes, while port and file probes are
+//! polled by the background jobs thread. Each of these returns the dependent panes whose state
+//! changed, so the tab can either update what they are shown to be waiting for or start them.
+
+use crate::panes::output_log::AnsiStripper;
+use crate::panes::PaneId;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use zellij_utils::input::layout::{PaneDependency, ReadinessProbe};
+
+// a line of output without newlines is dropped rather than buffered forever
+const MAX_LINE_LENGTH: usize = 4096;
+
+#[derive(Debug)]
+struct Dependency {
+    pane_name: String,
+    pane_id: PaneId,
+    readiness: ReadinessProbe,
+    output_regex: Option<Regex>,
+    probe_id: Option<u64>, // for probes polled by the background jobs thread
+    is_ready: bool,
+}
+
+#[derive(Default)]
+struct OutputWatch {
+    ansi_parser: vte::Parser,
+    current_line: String,
+}
+
+#[derive(Default)]
+pub struct PaneDependencies {
+    waiting: BTreeMap<PaneId, Vec<Dependency>>, // dependent pane -> what it is waiting for
+    output_watches: HashMap<PaneId, OutputWatch>,
+    next_probe_id: u64,
+}
+
+impl PaneDependencies {
+    /// Starts tracking the dependencies of a suspended pane, each with the id of the pane it
+    /// refers to. Returns the probes that should be polled in the background.
+    pub fn add(
+        &mut self,
+        dependent: PaneId,
+        dependencies: Vec<(PaneId, PaneDependency)>,
+    ) -> Vec<(u64, ReadinessProbe)> {
+        let mut background_probes = vec![];
+        let mut tracked_dependencies = vec![];
+        for (pane_id, dependency) in dependencies {
+            let mut output_regex = None;
+            let mut probe_id = None;
+            match &dependency.readiness {
+                ReadinessProbe::ExitSuccess => {},
+                ReadinessProbe::OutputMatches(regex) => match Regex::new(regex) {
+                    Ok(regex) => {
+                        output_regex = Some(regex);
+                        self.output_watches.entry(pane_id).or_default();
+                    },
+                    Err(e) => {
+                        // layouts are validated when parsed, so this should not happen
+                        log::error!("Invalid output regex for depends_on: {}", e);
+                        continue;
+                    },
+                },
+                ReadinessProbe::PortOpen(_) | ReadinessProbe::FileExists(_) => {
+                    self.next_probe_id += 1;
+                    probe_id = Some(self.next_probe_id);
+                    background_probes.push((self.next_probe_id, dependency.readiness.clone()));
+                },
+            }
+            tracked_dependencies.push(Dependency {
+                pane_name: dependency.pane_name,
+                pane_id,
+                readiness: dependency.readiness,
+                output_regex,
+                probe_id,
+                is_ready: false,
+            });
+        }
+        if !tracked_dependencies.is_empty() {
+            self.waiting.insert(dependent, tracked_dependencies);
+        }
+        background_probes
+    }
+    /// Stops tracking a pane, returns true if it was waiting for its dependencies
+    pub fn remove(&mut self, dependent: PaneId) -> bool {
+        let was_waiting = self.waiting.remove(&dependent).is_some();
+        if was_waiting {
+            self.prune_output_watches();
+        }
+        was_waiting
+    }
+    pub fn is_waiting(&self, dependent: PaneId) -> bool {
+        self.waiting.contains_key(&dependent)
+    }
+    /// The names of the panes this pane is still waiting for, to be displayed to the user
+    pub fn waiting_for(&self, dependent: PaneId) -> Option<String> {
+        let pane_names: Vec<&str> = self
+            .waiting
+            .get(&dependent)?
+            .iter()
+            .filter(|dependency| !dependency.is_ready)
+            .map(|dependency| dependency.pane_name.as_str())
+            .collect();
+        Some(pane_names.join(", "))
+    }
+    pub fn output_received(&mut self, pane_id: PaneId, bytes: &[u8]) -> Vec<PaneId> {
+        let Some(output_watch) = self.output_watches.get_mut(&pane_id) else {
+            return vec![];
+        };
+        let mut stripper = AnsiStripper::default();
+        output_watch.ansi_parser.advance(&mut stripper, bytes);
+        let output = String::from_utf8_lossy(&stripper.output);
+        let mut lines = vec![];
+        for character in output.chars() {
+            if character == '\n' {
+                lines.push(std::mem::take(&mut output_watch.current_line));
+            } else {
+                output_watch.current_line.push(character);
+            }
+        }
+        if output_watch.current_line.len() > MAX_LINE_LENGTH {
+            output_watch.current_line.clear();
+        }
+        // a partial line is also matched, since eg. prompts do not end with a newline
+        if !output_watch.current_line.is_empty() {
+            lines.push(output_watch.current_line.clone());
+        }
+        let affected = self.mark_ready(|dependency| {
+            dependency.pane_id == pane_id
+                && dependency
+                    .output_regex
+                    .as_ref()
+                    .map(|regex| lines.iter().any(|line| regex.is_match(line)))
+                    .unwrap_or(false)
+        });
+        if !affected.is_empty() {
+            self.prune_output_watches();
+        }
+        affected
+    }
+    pub fn exited(&mut self, pane_id: PaneId, exit_status: Option<i32>) -> Vec<PaneId> {
+        if exit_status != Some(0) {
+            return vec![];
+        }
+        self.mark_ready(|dependency| {
+            dependency.pane_id == pane_id && dependency.readiness == ReadinessProbe::ExitSuccess
+        })
+    }
+    /// A pane that was closed before it was ready can no longer become ready, the panes waiting
+    /// for it stop waiting and are left for the user to run. Returns these panes.
+    pub fn closed(&mut self, pane_id: PaneId) -> Vec<PaneId> {
+        let failed: Vec<PaneId> = self
+            .waiting
+            .iter()
+            .filter(|(_, dependencies)| {
+                dependencies
+                    .iter()
+                    .any(|dependency| !dependency.is_ready && dependency.pane_id == pane_id)
+            })
+            .map(|(dependent, _)| *dependent)
+            .collect();
+        for dependent in &failed {
+            self.waiting.remove(dependent);
+        }
+        if !failed.is_empty() {
+            self.prune_output_watches();
+        }
+        failed
+    }
+    pub fn probe_succeeded(&mut self, dependent: PaneId, probe_id: u64) -> Vec<PaneId> {
+        let is_probe = |dependency: &Dependency| dependency.probe_id == Some(probe_id);
+        match self.waiting.get(&dependent) {
+            Some(dependencies) if dependencies.iter().any(is_probe) => self.mark_ready(is_probe),
+            _ => vec![],
+        }
+    }
+    // returns the dependent panes with newly ready dependencies, those with no more dependencies
+    // to wait for are no longer tracked
+    fn mark_ready(&mut self, is_ready: impl Fn(&Dependency) -> bool) -> Vec<PaneId> {
+        let mut affected = vec![];
+        for (dependent, dependencies) in self.waiting.iter_mut() {
+            let mut became_ready = false;
+            for dependency in dependencies
+                .iter_mut()
+                .filter(|dependency| !dependency.is_ready)
+            {
+                if is_ready(dependency) {
+                    log::info!(
+                        "Pane {:?} is ready ({}), as needed by pane {:?}",
+                        dependency.pane_name,
+                        dependency.readiness,
+                        dependent
+                    );
+                    dependency.is_ready = true;
+                    became_ready = true;
+                }
+            }
+            if became_ready {
+                affected.push(*dependent);
+            }
+        }
+        self.waiting
+            .retain(|_, dependencies| dependencies.iter().any(|d| !d.is_ready));
+        affected
+    }
+    fn prune_output_watches(&mut self) {
+        let waiting = &self.waiting;
+        self.output_watches.retain(|pane_id, _| {
+            waiting.values().flatten().any(|dependency| {
+                !dependency.is_ready
+                    && dependency.pane_id == *pane_id
+                    && dependency.output_regex.is_some()
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pane_dependencies_tests.rs"]
+mod pane_dependencies_tests;
@@ -0,0 +1,184 @@
+use super::PaneDependencies;
+use crate::panes::PaneId;
+use std::path::PathBuf;
+use zellij_utils::input::layout::{PaneDependency, ReadinessProbe};
+
+fn dependency(pane_name: &str, readiness: ReadinessProbe) -> PaneDependency {
+    PaneDependency {
+        pane_name: pane_name.to_owned(),
+        readiness,
+    }
+}
+
+#[test]
+fn pane_is_ready_once_its_dependency_exits_successfully() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let db = PaneId::Terminal(1);
+    let server = PaneId::Terminal(2);
+    let background_probes = pane_dependencies.add(
+        server,
+        vec![(db, dependency("db", ReadinessProbe::ExitSuccess))],
+    );
+    assert!(background_probes.is_empty());
+    assert_eq!(pane_dependencies.waiting_for(server), Some("db".to_owned()));
+    assert_eq!(pane_dependencies.exited(db, Some(1)), vec![]);
+    assert_eq!(pane_dependencies.exited(db, None), vec![]);
+    assert!(pane_dependencies.is_waiting(server));
+    assert_eq!(pane_dependencies.exited(db, Some(0)), vec![server]);
+    assert!(!pane_dependencies.is_waiting(server));
+}
+
+#[test]
+fn output_is_matched_without_ansi_sequences_across_chunks() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let db = PaneId::Terminal(1);
+    let server = PaneId::Terminal(2);
+    pane_dependencies.add(
+        server,
+        vec![(
+            db,
+            dependency(
+                "db",
+                ReadinessProbe::OutputMatches("ready to accept connections$".to_owned()),
+            ),
+        )],
+    );
+    assert_eq!(
+        pane_dependencies.output_received(db, b"starting up\r\n\x1b[32mready to"),
+        vec![]
+    );
+    assert_eq!(
+        pane_dependencies.output_received(server, b" accept connections"),
+        vec![],
+        "output of other panes is ignored"
+    );
+    assert_eq!(
+        pane_dependencies.output_received(db, b" accept \x1b[1mconnections\x1b[m\r\n"),
+        vec![server]
+    );
+    assert!(!pane_dependencies.is_waiting(server));
+}
+
+#[test]
+fn partial_lines_are_matched() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let shell = PaneId::Terminal(1);
+    let server = PaneId::Terminal(2);
+    pane_dependencies.add(
+        server,
+        vec![(
+            shell,
+            dependency("shell", ReadinessProbe::OutputMatches("^> $".to_owned())),
+        )],
+    );
+    assert_eq!(
+        pane_dependencies.output_received(shell, b"loading\n> "),
+        vec![server]
+    );
+}
+
+#[test]
+fn pane_waits_for_all_of_its_dependencies() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let db = PaneId::Terminal(1);
+    let cache = PaneId::Terminal(2);
+    let migrations = PaneId::Terminal(3);
+    let server = PaneId::Terminal(4);
+    let background_probes = pane_dependencies.add(
+        server,
+        vec![
+            (db, dependency("db", ReadinessProbe::PortOpen(5432))),
+            (
+                cache,
+                dependency(
+                    "cache",
+                    ReadinessProbe::FileExists(PathBuf::from("/tmp/cache.ready")),
+                ),
+            ),
+            (
+                migrations,
+                dependency("migrations", ReadinessProbe::ExitSuccess),
+            ),
+        ],
+    );
+    assert_eq!(
+        background_probes,
+        vec![
+            (1, ReadinessProbe::PortOpen(5432)),
+            (
+                2,
+                ReadinessProbe::FileExists(PathBuf::from("/tmp/cache.ready"))
+            ),
+        ]
+    );
+    assert_eq!(
+        pane_dependencies.waiting_for(server),
+        Some("db, cache, migrations".to_owned())
+    );
+    assert_eq!(pane_dependencies.probe_succeeded(server, 1), vec![server]);
+    assert_eq!(
+        pane_dependencies.waiting_for(server),
+        Some("cache, migrations".to_owned())
+    );
+    assert_eq!(
+        pane_dependencies.probe_succeeded(migrations, 2),
+        vec![],
+        "probes belong to the pane that depends on them"
+    );
+    assert_eq!(pane_dependencies.exited(migrations, Some(0)), vec![server]);
+    assert_eq!(pane_dependencies.probe_succeeded(server, 2), vec![server]);
+    assert!(!pane_dependencies.is_waiting(server));
+    assert_eq!(pane_dependencies.waiting_for(server), None);
+}
+
+#[test]
+fn removed_panes_are_no_longer_waiting() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let db = PaneId::Terminal(1);
+    let server = PaneId::Terminal(2);
+    pane_dependencies.add(
+        server,
+        vec![(
+            db,
+            dependency("db", ReadinessProbe::OutputMatches("ready".to_owned())),
+        )],
+    );
+    assert!(pane_dependencies.remove(server));
+    assert!(!pane_dependencies.remove(server));
+    assert_eq!(pane_dependencies.output_received(db, b"ready\n"), vec![]);
+}
+
+#[test]
+fn panes_stop_waiting_for_a_dependency_that_was_closed_before_it_was_ready() {
+    let mut pane_dependencies = PaneDependencies::default();
+    let db = PaneId::Terminal(1);
+    let cache = PaneId::Terminal(2);
+    let server = PaneId::Terminal(3);
+    let worker = PaneId::Terminal(4);
+    pane_dependencies.add(
+        server,
+        vec![(db, dependency("db", ReadinessProbe::ExitSuccess))],
+    );
+    pane_dependencies.add(
+        worker,
+        vec![
+            (cache, dependency("cache", ReadinessProbe::ExitSuccess)),
+            (
+                db,
+                dependency("db", ReadinessProbe::OutputMatches("ready".to_owned())),
+            ),
+        ],
+    );
+    assert_eq!(
+        pane_dependencies.output_received(db, b"ready\n"),
+        vec![worker]
+    );
+    assert_eq!(pane_dependencies.closed(db), vec![server]);
+    assert!(!pane_dependencies.is_waiting(server));
+    assert!(
+        pane_dependencies.is_waiting(worker),
+        "db was ready before it was closed"
+    );
+    assert_eq!(pane_dependencies.closed(cache), vec![worker]);
+    assert!(!pane_dependencies.is_waiting(worker));
+}
@@ -16197,3 +16197,95 @@ fn host_focus_events_only_reach_panes_that_asked_for_them() {
         .map(|bytes| String::from_utf8_lossy(bytes).to_string());
     assert_eq!(written, Some("\u{1b}[O\u{1b}[I".to_owned()));
 }
+
+#[test]
+fn pane_waiting_for_its_dependency_starts_once_it_is_ready() {
+    let layout = r#"
+        layout {
+            pane name="db" command="./db"
+            pane command="./server" {
+                depends_on "db" output="ready to accept connections"
+            }
+        }
+    "#;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab_with_layout(size, ModeInfo::default(), layout);
+    let server = PaneId::Terminal(1);
+    tab.hold_pane(
+        server,
+        None,
+        true,
+        RunCommand {
+            command: PathBuf::from("./server"),
+            hold_on_start: true,
+            ..Default::default()
+        },
+    );
+    let mut output = Output::default();
+    tab.render(&mut output, None).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+    tab.handle_pty_bytes(0, "starting up\r\n".as_bytes().to_vec())
+        .unwrap();
+    assert!(tab.has_pane_waiting_for_dependencies(server));
+    tab.handle_pty_bytes(0, "ready to accept connections\r\n".as_bytes().to_vec())
+        .unwrap();
+    assert!(!tab.has_pane_waiting_for_dependencies(server));
+    assert!(!tab.get_pane_with_id(server).unwrap().is_held());
+}
+
+#[test]
+fn exit_copy_mode_only_leaves_it_for_the_requesting_client() {
+    use zellij_utils::input::actions::CopyModeSelection;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let other_client_id = 2;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.add_client(other_client_id, None).unwrap();
+    tab.handle_pty_bytes(1, read_fixture("grid_copy")).unwrap();
+    let first_pane = PaneId::Terminal(1);
+    let selected_text = |tab: &Tab, pane_id| {
+        tab.get_pane_with_id(pane_id)
+            .unwrap()
+            .copy_mode_selected_text()
+    };
+
+    tab.enter_copy_mode(client_id);
+    tab.enter_copy_mode(other_client_id);
+    tab.copy_mode_select(CopyModeSelection::Line, client_id);
+    tab.exit_copy_mode(other_client_id);
+    assert!(tab.is_in_copy_mode(client_id));
+    assert!(!tab.is_in_copy_mode(other_client_id));
+    assert!(
+        selected_text(&tab, first_pane).is_some(),
+        "pane stays in copy mode while another client is using it"
+    );
+
+    // focus moving while in copy mode does not strand the pane copy mode was entered in
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        None,
+        false,
+        true,
+        NewPanePlacement::default(),
+        Some(client_id),
+        None,
+    )
+    .unwrap();
+    tab.exit_copy_mode(client_id);
+    assert!(!tab.is_in_copy_mode(client_id));
+    assert!(selected_text(&tab, first_pane).is_none());
+}
@@ -17631,3 +17631,211 @@ pub fn scroll_terminal_down_nonexistent_pane_id_is_a_noop() {
     let writes = drain_pty_writer(&rx);
     assert!(writes.is_empty());
 }
+
+#[test]
+pub fn pane_output_is_logged_once_until_the_log_is_stopped() {
+    use zellij_utils::input::layout::LogOutput;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    let client_id = 1;
+    let log_dir = tempfile::tempdir().unwrap();
+    let log_path = log_dir.path().join("pane.log");
+    tab.get_pane_with_id_mut(PaneId::Terminal(1))
+        .unwrap()
+        .start_output_log(LogOutput {
+            path: log_path.clone(),
+            strip_ansi: true,
+            ..Default::default()
+        })
+        .unwrap();
+    for i in 0..30 {
+        tab.handle_pty_bytes(1, format!("line {}\r\n", i).into_bytes())
+            .unwrap();
+    }
+    // the log is written in the background, so we wait for it to catch up
+    let wait_for_log_to_end_with = |ending: &str| {
+        let started_waiting = std::time::Instant::now();
+        loop {
+            let logged = std::fs::read_to_string(&log_path).unwrap_or_default();
+            if logged.ends_with(ending) {
+                return logged;
+            }
+            assert!(
+                started_waiting.elapsed() < std::time::Duration::from_secs(5),
+                "log should end with {:?}, found: {:?}",
+                ending,
+                logged
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    };
+    // while the pane is scrolled its output is buffered, but should be logged right away
+    tab.scroll_active_terminal_up(client_id);
+    tab.handle_pty_bytes(1, b"\x1b[32mwhile scrolled\x1b[m\r\n".to_vec())
+        .unwrap();
+    let logged = wait_for_log_to_end_with("line 29\nwhile scrolled\n");
+    tab.clear_active_terminal_scroll(client_id).unwrap();
+    tab.handle_pty_bytes(1, b"after scrolling\r\n".to_vec())
+        .unwrap();
+    assert_eq!(
+        wait_for_log_to_end_with("after scrolling\n"),
+        format!("{}after scrolling\n", logged),
+        "buffered output should not be logged again"
+    );
+    let logged = std::fs::read_to_string(&log_path).unwrap();
+    assert_eq!(
+        tab.get_pane_with_id_mut(PaneId::Terminal(1))
+            .unwrap()
+            .stop_output_log(),
+        Some(log_path.clone())
+    );
+    tab.handle_pty_bytes(1, b"after\r\n".to_vec()).unwrap();
+    assert_eq!(std::fs::read_to_string(&log_path).unwrap(), logged);
+}
+
+#[test]
+pub fn pane_monitor_alerts_ring_the_pane_bell() {
+    use std::time::{Duration, Instant};
+    use zellij_utils::data::{PaneMonitorAlert, PaneMonitors};
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    assert!(!tab.has_pane_monitors());
+    let monitored_at = Instant::now();
+    tab.get_pane_with_id_mut(PaneId::Terminal(1))
+        .unwrap()
+        .set_monitors(PaneMonitors {
+            activity: false,
+            silence_secs: Some(5),
+        });
+    assert!(tab.has_pane_monitors());
+    assert_eq!(tab.check_pane_monitors(monitored_at), vec![]);
+    assert_eq!(
+        tab.check_pane_monitors(monitored_at + Duration::from_secs(6)),
+        vec![(PaneId::Terminal(1), PaneMonitorAlert::Silence(5))]
+    );
+    // the pane is focused, so only the tab rings
+    assert_eq!(
+        tab.check_and_handle_bell_notifications(true),
+        (vec![], true)
+    );
+    tab.get_pane_with_id_mut(PaneId::Terminal(1))
+        .unwrap()
+        .set_monitors(PaneMonitors::default());
+    assert!(!tab.has_pane_monitors());
+}
+
+#[test]
+fn closed_panes_can_be_reopened_with_their_command_and_placement() {
+    use crate::pty::{ClientTabIndexOrPaneId, PtyInstruction};
+    use zellij_utils::input::layout::{PercentOrFixed, Run};
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, true);
+    let (tx, rx) = unbounded();
+    tab.senders.replace_to_pty(SenderWithContext::new(tx));
+    let dev_server = RunCommand {
+        command: PathBuf::from("cargo"),
+        args: vec!["watch".to_owned()],
+        ..Default::default()
+    };
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(Run::Command(dev_server.clone())),
+        false,
+        true,
+        NewPanePlacement::Floating(None),
+        Some(client_id),
+        None,
+    )
+    .unwrap();
+    let floating_geom = tab
+        .get_pane_with_id(PaneId::Terminal(2))
+        .unwrap()
+        .position_and_size();
+    tab.close_pane(PaneId::Terminal(2), false, None);
+    assert!(tab.update_closed_pane_cwd(PaneId::Terminal(2), PathBuf::from("/tmp/project")));
+
+    let recently_closed = tab.recently_closed_panes();
+    assert_eq!(recently_closed.len(), 1);
+    assert_eq!(recently_closed[0].command, vec!["cargo", "watch"]);
+    assert_eq!(recently_closed[0].cwd, Some(PathBuf::from("/tmp/project")));
+    assert!(recently_closed[0].is_floating);
+
+    // moving a pane elsewhere is not closing it
+    tab.close_pane(PaneId::Terminal(1), true, None);
+    assert_eq!(tab.recently_closed_panes().len(), 1);
+
+    tab.reopen_closed_pane(client_id, None).unwrap();
+    assert!(tab.recently_closed_panes().is_empty());
+    match rx.try_recv().map(|(instruction, _)| instruction) {
+        Ok(PtyInstruction::SpawnTerminal(
+            Some(TerminalAction::RunCommand(run_command)),
+            _,
+            NewPanePlacement::Floating(Some(coordinates)),
+            _,
+            ClientTabIndexOrPaneId::ClientId(reopened_for),
+            _,
+            _,
+        )) => {
+            assert_eq!(run_command.command, dev_server.command);
+            assert_eq!(run_command.args, dev_server.args);
+            assert_eq!(run_command.cwd, Some(PathBuf::from("/tmp/project")));
+            assert_eq!(
+                coordinates.width,
+                Some(PercentOrFixed::Fixed(floating_geom.cols.as_usize()))
+            );
+            assert_eq!(reopened_for, client_id);
+        },
+        other => panic!("expected the pane to be spawned again, got: {:?}", other),
+    }
+
+    // nothing left to reopen
+    tab.reopen_closed_pane(client_id, None).unwrap();
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+pub fn pane_that_is_not_held_on_exit_closes_once_out_of_restarts() {
+    use crate::screen::ScreenInstruction;
+    use zellij_utils::input::command::{RestartPolicy, RunCommand};
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size, true);
+    let (tx, rx) = unbounded();
+    tab.senders.replace_to_screen(SenderWithContext::new(tx));
+    let mut run_command = RunCommand::new(PathBuf::from("./worker"));
+    run_command.restart = RestartPolicy::OnFailure;
+    run_command.max_restarts = Some(0);
+    let is_first_run = false;
+
+    run_command.hold_on_close = true;
+    tab.hold_pane(
+        PaneId::Terminal(1),
+        Some(1),
+        is_first_run,
+        run_command.clone(),
+    );
+    assert!(rx.try_recv().is_err(), "pane held on exit stays held");
+
+    run_command.hold_on_close = false;
+    tab.hold_pane(PaneId::Terminal(1), Some(1), is_first_run, run_command);
+    let (instruction, _ctx) = rx.try_recv().unwrap();
+    assert!(matches!(
+        instruction,
+        ScreenInstruction::ClosePane(PaneId::Terminal(1), None, None, Some(1))
+    ));
+}
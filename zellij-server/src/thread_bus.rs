@@ -154,6 +154,12 @@ impl ThreadSenders {
         // this is mostly used for the tests, see struct
         self.to_plugin.replace(new_to_plugin);
     }
+
+    #[allow(unused)]
+    pub fn replace_to_screen(&mut self, new_to_screen: SenderWithContext<ScreenInstruction>) {
+        // this is mostly used for the tests, see struct
+        self.to_screen.replace(new_to_screen);
+    }
 }
 
 /// A container for a receiver, OS input and the senders to a given thread
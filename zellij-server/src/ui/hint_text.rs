@@ -156,6 +156,24 @@ pub fn exit_code_segments(exit_status: HintExitStatus) -> Vec<HintSegment> {
     }
 }
 
+pub fn restart_count_segments(restart_count: usize) -> Vec<HintSegment> {
+    vec![
+        HintSegment::plain(" [ "),
+        HintSegment::plain("RESTARTS: "),
+        HintSegment::emphasis(&restart_count.to_string()),
+        HintSegment::plain(" ] "),
+    ]
+}
+
+pub fn waiting_for_segments(pane_names: &str) -> Vec<HintSegment> {
+    vec![
+        HintSegment::plain(" [ "),
+        HintSegment::plain("WAITING FOR: "),
+        HintSegment::emphasis(pane_names),
+        HintSegment::plain(" ] "),
+    ]
+}
+
 pub fn hover_segments(tier: HintTier) -> Vec<HintSegment> {
     match tier {
         HintTier::Full => vec![
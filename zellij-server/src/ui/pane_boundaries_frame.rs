@@ -5,8 +5,8 @@ use crate::panes::{
 use crate::tab::GuestChoiceIndicator;
 use crate::ui::boundaries::boundary_type;
 use crate::ui::hint_text::{
-    exit_code_segments, hover_segments, rerun_segments, resize_segments, HintExitStatus, HintLevel,
-    HintSegment, HintTier,
+    exit_code_segments, hover_segments, rerun_segments, resize_segments, restart_count_segments,
+    waiting_for_segments, HintExitStatus, HintLevel, HintSegment, HintTier,
 };
 use crate::ClientId;
 use zellij_utils::data::{client_id_to_colors, PaletteColor, Style};
@@ -100,6 +100,7 @@ pub struct FrameParams {
     pub mouse_hover_tips: bool,
     pub dimmed: bool,
     pub guest_choice_indicator: Option<GuestChoiceIndicator>,
+    pub pane_input_is_synced: bool,
 }
 
 #[derive(Default, PartialEq)]
@@ -115,6 +116,8 @@ pub struct PaneFrame {
     pub other_focused_clients: Vec<ClientId>,
     exit_status: Option<ExitStatus>,
     is_first_run: bool,
+    restart_count: usize,
+    waiting_for: Option<String>,
     pane_is_stacked_over: bool,
     pane_is_stacked_under: bool,
     pane_is_stacked: bool,
@@ -133,6 +136,7 @@ pub struct PaneFrame {
     mouse_hover_tips: bool,
     dimmed: bool,
     guest_choice_indicator: Option<GuestChoiceIndicator>,
+    input_is_synced: bool,
 }
 
 impl PaneFrame {
@@ -154,6 +158,8 @@ impl PaneFrame {
             other_cursors_exist_in_session: frame_params.other_cursors_exist_in_session,
             exit_status: None,
             is_first_run: false,
+            restart_count: 0,
+            waiting_for: None,
             pane_is_stacked_over: frame_params.pane_is_stacked_over,
             pane_is_stacked_under: frame_params.pane_is_stacked_under,
             pane_is_stacked: frame_params.pane_is_stacked,
@@ -172,6 +178,7 @@ impl PaneFrame {
             mouse_hover_tips: frame_params.mouse_hover_tips,
             dimmed: frame_params.dimmed,
             guest_choice_indicator: frame_params.guest_choice_indicator,
+            input_is_synced: frame_params.pane_input_is_synced,
         }
     }
     pub fn is_pinned(mut self, is_pinned: bool) -> Self {
@@ -187,6 +194,12 @@ impl PaneFrame {
     pub fn indicate_first_run(&mut self) {
         self.is_first_run = true;
     }
+    pub fn add_restart_count(&mut self, restart_count: usize) {
+        self.restart_count = restart_count;
+    }
+    pub fn add_waiting_for(&mut self, waiting_for: Option<String>) {
+        self.waiting_for = waiting_for;
+    }
     pub fn override_color(&mut self, color: PaletteColor) {
         self.color = Some(color);
         self.color_override = Some(color);
@@ -223,7 +236,40 @@ impl PaneFrame {
         &self,
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
-        self.render_title_right_side_inner(max_length)
+        let sync_indication = if self.input_is_synced {
+            self.render_sync_indication(max_length)
+        } else {
+            None
+        };
+        let space_for_rest = sync_indication
+            .as_ref()
+            .map(|(_, length)| max_length.saturating_sub(*length + 1))
+            .unwrap_or(max_length);
+        match (
+            sync_indication,
+            self.render_title_right_side_inner(space_for_rest),
+        ) {
+            (Some((mut sync_indication, sync_indication_len)), Some((mut rest, rest_len))) => {
+                let mut separator = foreground_color("|", self.color);
+                rest.append(&mut separator);
+                rest.append(&mut sync_indication);
+                Some((rest, rest_len + sync_indication_len + 1))
+            },
+            (Some(sync_indication), None) => Some(sync_indication),
+            (None, rest) => rest,
+        }
+    }
+    fn render_sync_indication(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let full_indication = " SYNC ";
+        let full_indication_len = full_indication.chars().count();
+        if full_indication_len <= max_length {
+            Some((
+                foreground_color(full_indication, self.color),
+                full_indication_len,
+            ))
+        } else {
+            None
+        }
     }
     fn render_title_right_side_inner(
         &self,
@@ -1359,6 +1405,14 @@ impl PaneFrame {
                             x,
                             y,
                         ));
+                    } else if self.restart_count > 0 {
+                        let x = self.geom.x;
+                        let y = self.geom.y + row;
+                        character_chunks.push(CharacterChunk::new(
+                            self.render_restart_count_undertitle(),
+                            x,
+                            y,
+                        ));
                     } else {
                         let mut bottom_row = vec![];
                         for col in 0..self.geom.cols {
@@ -1414,15 +1468,40 @@ impl PaneFrame {
         (characters, length)
     }
     fn first_exited_held_title_part_full(&self) -> (Vec<TerminalCharacter>, usize) {
-        match self.exit_status {
+        let mut segments = match self.exit_status {
             Some(ExitStatus::Code(exit_code)) => {
-                self.render_hint_segments(&exit_code_segments(HintExitStatus::Code(exit_code)))
+                exit_code_segments(HintExitStatus::Code(exit_code))
             },
-            Some(ExitStatus::Exited) => {
-                self.render_hint_segments(&exit_code_segments(HintExitStatus::Exited))
+            Some(ExitStatus::Exited) => exit_code_segments(HintExitStatus::Exited),
+            None => match &self.waiting_for {
+                Some(waiting_for) if self.is_first_run => waiting_for_segments(waiting_for),
+                _ => return (foreground_color(boundary_type::HORIZONTAL, self.color), 1),
             },
-            None => (foreground_color(boundary_type::HORIZONTAL, self.color), 1),
+        };
+        if self.restart_count > 0 {
+            segments.append(&mut restart_count_segments(self.restart_count));
         }
+        self.render_hint_segments(&segments)
+    }
+    fn render_restart_count_undertitle(&self) -> Vec<TerminalCharacter> {
+        let max_undertitle_length = self.geom.cols.saturating_sub(2); // 2 for the left and right corners
+        let (mut restart_part, restart_part_len) =
+            self.render_hint_segments(&restart_count_segments(self.restart_count));
+        if restart_part_len > max_undertitle_length {
+            return self.empty_undertitle(max_undertitle_length);
+        }
+        let mut padding = String::new();
+        for _ in restart_part_len..max_undertitle_length {
+            padding.push_str(boundary_type::HORIZONTAL);
+        }
+        let mut ret = foreground_color(self.get_corner(boundary_type::BOTTOM_LEFT), self.color);
+        ret.append(&mut restart_part);
+        ret.append(&mut foreground_color(&padding, self.color));
+        ret.append(&mut foreground_color(
+            self.get_corner(boundary_type::BOTTOM_RIGHT),
+            self.color,
+        ));
+        ret
     }
     fn second_held_title_part_full(&self) -> (Vec<TerminalCharacter>, usize) {
         self.render_hint_segments(&rerun_segments(self.is_first_run, HintTier::Full))
@@ -1488,6 +1567,7 @@ mod tests {
                 mouse_hover_tips: true,
                 dimmed: false,
                 guest_choice_indicator: None,
+                pane_input_is_synced: false,
             },
         )
     }
@@ -1549,4 +1629,19 @@ mod tests {
         assert!(!text.contains("MouseScroll"));
         assert!(text.contains("<drag borders>"));
     }
+
+    #[test]
+    fn synced_input_is_indicated_next_to_the_pin_indication() {
+        let mut frame = pane_frame_with(false, true, 80);
+        frame.input_is_synced = true;
+        let (chars, len) = frame.render_title_right_side(80).unwrap();
+        assert_eq!(characters_to_string(&chars), " PIN [ ] | SYNC ");
+        assert_eq!(len, 16);
+        let (chars, _) = frame.render_title_right_side(7).unwrap();
+        assert_eq!(
+            characters_to_string(&chars),
+            " SYNC ",
+            "the sync indication takes precedence when space is short"
+        );
+    }
 }
@@ -42,6 +42,7 @@ pub struct PaneContentsAndUi<'a> {
     mouse_scroll_resize: bool,
     mouse_hover_tips: bool,
     dimmed_for_clients: HashSet<ClientId>,
+    input_synced_clients: HashSet<ClientId>,
 }
 
 impl<'a> PaneContentsAndUi<'a> {
@@ -101,11 +102,15 @@ impl<'a> PaneContentsAndUi<'a> {
             mouse_scroll_resize,
             mouse_hover_tips,
             dimmed_for_clients,
+            input_synced_clients: HashSet::new(),
         }
     }
     fn frame_is_dimmed_for_client(&self, client_id: ClientId) -> bool {
         self.dimmed_for_clients.contains(&client_id) && !self.focused_clients.contains(&client_id)
     }
+    pub fn set_input_synced_clients(&mut self, input_synced_clients: HashSet<ClientId>) {
+        self.input_synced_clients = input_synced_clients;
+    }
     pub fn set_frame_geom_override(&mut self, frame_geom_override: Option<PaneGeom>) {
         self.frame_geom_override = frame_geom_override;
     }
@@ -345,6 +350,8 @@ impl<'a> PaneContentsAndUi<'a> {
             .get(&client_id)
             .map(|p| p.contains(&self.pane.pid()))
             .unwrap_or(false);
+        let pane_input_is_synced =
+            pane_is_in_group && self.input_synced_clients.contains(&client_id);
         let stack_list_entry = self.stack_list_entry_width.map(|width| StackListEntry {
             width,
             label: self.pane.stack_list_entry_label(),
@@ -386,6 +393,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 mouse_hover_tips: self.mouse_hover_tips,
                 dimmed: frame_is_dimmed,
                 guest_choice_indicator,
+                pane_input_is_synced,
             }
         } else {
             FrameParams {
@@ -415,6 +423,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 mouse_hover_tips: self.mouse_hover_tips,
                 dimmed: frame_is_dimmed,
                 guest_choice_indicator,
+                pane_input_is_synced,
             }
         };
 
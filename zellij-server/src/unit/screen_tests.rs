@@ -340,6 +340,7 @@ fn create_new_screen_with_capture(
     let default_shell = PathBuf::from("my_default_shell");
     let session_serialization = true;
     let serialize_pane_viewport = false;
+    let serialize_pane_scrollback = false;
     let scrollback_lines_to_serialize = None;
     let layout_dir = None;
 
@@ -369,6 +370,7 @@ fn create_new_screen_with_capture(
         default_shell,
         session_serialization,
         serialize_pane_viewport,
+        serialize_pane_scrollback,
         scrollback_lines_to_serialize,
         styled_underlines,
         osc8_hyperlinks,
@@ -3522,6 +3524,63 @@ pub fn send_cli_toggle_active_tab_sync_action() {
     assert_snapshot!(format!("{:?}", received_write_instructions));
 }
 
+#[test]
+pub fn send_cli_sync_broadcast_group_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let node_pane = TiledPaneLayout {
+        broadcast_group: Some("nodes".to_owned()),
+        ..Default::default()
+    };
+    initial_layout.children = vec![node_pane.clone(), TiledPaneLayout::default(), node_pane];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_sync_broadcast_group_action = CliAction::SyncBroadcastGroup {
+        name: "nodes".to_owned(),
+    };
+    let cli_write_action = CliAction::Write {
+        bytes: vec![102, 111, 111],
+        pane_id: None,
+    };
+    send_cli_action_to_server(
+        &session_metadata,
+        cli_sync_broadcast_group_action,
+        client_id,
+    );
+    send_cli_action_to_server(&session_metadata, cli_write_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let mut written_to_terminals: Vec<u32> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|i| match i {
+            PtyWriteInstruction::Write(bytes, terminal_id, _) => {
+                assert_eq!(bytes, &vec![102, 111, 111]);
+                Some(*terminal_id)
+            },
+            _ => None,
+        })
+        .collect();
+    written_to_terminals.sort();
+    // the focused pane (the first one) and the other pane in the broadcast group, but not the
+    // pane in between them
+    assert_eq!(written_to_terminals, vec![0, 2]);
+}
+
 #[test]
 pub fn send_cli_new_pane_action_with_default_parameters() {
     let size = Size {
@@ -3547,6 +3606,7 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -3554,6 +3614,8 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -3657,6 +3719,7 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -3664,6 +3727,8 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -3713,6 +3778,7 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         command: vec!["htop".into()],
         plugin: None,
         cwd: Some("/some/folder".into()),
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -3720,6 +3786,8 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -3780,6 +3848,7 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
         command: vec!["htop".into()],
         plugin: None,
         cwd: Some("/some/folder".into()),
+        env: vec![],
         floating: true,
         in_place: false,
         close_replaced_pane: false,
@@ -3787,6 +3856,8 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: Some("10".to_owned()),
@@ -4128,6 +4199,7 @@ pub fn send_cli_new_tab_action_default_params() {
         layout: None,
         layout_string: None,
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -4177,6 +4249,7 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
         ))),
         layout_string: None,
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -5608,6 +5681,7 @@ pub fn send_cli_new_pane_in_place_with_close_replaced_pane() {
         command: vec!["bash".into()],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: true,
         close_replaced_pane: true,
@@ -5615,6 +5689,8 @@ pub fn send_cli_new_pane_in_place_with_close_replaced_pane() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -5774,6 +5850,7 @@ fn create_new_screen_with_message_capture(
     let default_shell = PathBuf::from("my_default_shell");
     let session_serialization = true;
     let serialize_pane_viewport = false;
+    let serialize_pane_scrollback = false;
     let scrollback_lines_to_serialize = None;
     let layout_dir = None;
     let debug = false;
@@ -5801,6 +5878,7 @@ fn create_new_screen_with_message_capture(
         default_shell,
         session_serialization,
         serialize_pane_viewport,
+        serialize_pane_scrollback,
         scrollback_lines_to_serialize,
         styled_underlines,
         osc8_hyperlinks,
@@ -8232,6 +8310,7 @@ pub fn send_cli_new_tab_action_with_layout_string() {
         layout: None,
         layout_string: Some("layout {\n    pane\n    pane\n    pane\n}\n".into()),
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -8287,6 +8366,7 @@ pub fn send_cli_new_tab_action_with_layout_string_and_name() {
         layout: None,
         layout_string: Some("layout {\n    pane\n    pane\n    pane\n}\n".into()),
         layout_dir: None,
+        layout_args: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -8345,6 +8425,7 @@ pub fn send_cli_new_pane_action_with_tab_id() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -8352,6 +8433,8 @@ pub fn send_cli_new_pane_action_with_tab_id() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -8408,6 +8491,7 @@ pub fn send_cli_new_floating_pane_action_with_tab_id() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: true,
         in_place: false,
         close_replaced_pane: false,
@@ -8415,6 +8499,8 @@ pub fn send_cli_new_floating_pane_action_with_tab_id() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -8520,6 +8606,7 @@ pub fn send_cli_new_pane_action_with_tab_id_and_direction() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -8527,6 +8614,8 @@ pub fn send_cli_new_pane_action_with_tab_id_and_direction() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -8582,6 +8671,7 @@ pub fn send_cli_new_pane_action_with_tab_id_and_stacked() {
         command: vec!["ls".into()],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         close_replaced_pane: false,
@@ -8589,6 +8679,8 @@ pub fn send_cli_new_pane_action_with_tab_id_and_stacked() {
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        restart: None,
+        max_restarts: None,
         configuration: None,
         skip_plugin_cache: false,
         x: None,
@@ -8885,6 +8977,7 @@ fn create_new_screen_with_forward_capture(size: Size) -> (Screen, ForwardCapture
     let default_shell = PathBuf::from("my_default_shell");
     let session_serialization = true;
     let serialize_pane_viewport = false;
+    let serialize_pane_scrollback = false;
     let scrollback_lines_to_serialize = None;
     let layout_dir = None;
     let debug = false;
@@ -8912,6 +9005,7 @@ fn create_new_screen_with_forward_capture(size: Size) -> (Screen, ForwardCapture
         default_shell,
         session_serialization,
         serialize_pane_viewport,
+        serialize_pane_scrollback,
         scrollback_lines_to_serialize,
         styled_underlines,
         osc8_hyperlinks,
@@ -9763,6 +9857,7 @@ fn create_new_screen_with_theme_capture(size: Size) -> (Screen, ThemeCapture) {
         default_shell,
         true,
         false,
+        false,
         None,
         true,
         true,
@@ -10296,6 +10391,7 @@ fn create_non_mirrored_screen(size: Size) -> Screen {
         PathBuf::from("my_default_shell"),
         true,  // session_serialization
         false, // serialize_pane_viewport
+        false, // serialize_pane_scrollback
         None,  // scrollback_lines_to_serialize
         true,  // styled_underlines
         true,  // osc8_hyperlinks
@@ -12112,6 +12208,53 @@ fn assert_focus_change_syncs_scroll_mode(default_mode: InputMode) {
     mock_screen.teardown(vec![server_thread, screen_thread]);
 }
 
+#[test]
+pub fn searching_from_copy_mode_returns_to_copy_mode() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let client_id = mock_screen.main_client_id;
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let change_modes = || -> Vec<InputMode> {
+        received_server_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|instruction| match instruction {
+                ServerInstruction::ChangeMode(cid, mode, _) if *cid == client_id => Some(*mode),
+                _ => None,
+            })
+            .collect()
+    };
+    for mode in [InputMode::EnterSearch, InputMode::Search] {
+        let _ = mock_screen
+            .to_screen
+            .send(ScreenInstruction::ChangeMode(mode, None, client_id, None));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(
+        change_modes(),
+        vec![],
+        "a search not started from copy mode stays in search mode"
+    );
+
+    for mode in [InputMode::Copy, InputMode::EnterSearch, InputMode::Search] {
+        let _ = mock_screen
+            .to_screen
+            .send(ScreenInstruction::ChangeMode(mode, None, client_id, None));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(change_modes(), vec![InputMode::Copy]);
+
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+}
+
 #[test]
 pub fn focusing_a_scrolled_pane_with_the_mouse_enters_scroll_mode() {
     // Same sync as the keyboard path, but the focus change comes from a mouse click. Any
@@ -13137,3 +13280,57 @@ fn a_client_whose_host_focus_was_never_reported_counts_as_focused() {
         "a client is assumed focused until told otherwise, so this is not a transition"
     );
 }
+
+#[test]
+pub fn monitoring_a_pane_ticks_until_no_monitors_are_left() {
+    use zellij_utils::data::PaneMonitors;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut mock_screen = MockScreen::new(size);
+    let received_background_jobs = mock_screen.received_background_jobs.clone();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let monitor_pane = |monitors| ScreenInstruction::MonitorPane {
+        pane_id: None,
+        monitors,
+        client_id,
+        cli_client_id: None,
+        completion_tx: None,
+    };
+    let _ = mock_screen.to_screen.send(monitor_pane(PaneMonitors {
+        activity: true,
+        silence_secs: None,
+    }));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::CheckPaneMonitors);
+    let _ = mock_screen
+        .to_screen
+        .send(monitor_pane(PaneMonitors::default()));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::CheckPaneMonitors);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![screen_thread]);
+    let pane_monitor_jobs: Vec<BackgroundJob> = received_background_jobs
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|job| {
+            matches!(
+                job,
+                BackgroundJob::StartPaneMonitorTicks | BackgroundJob::StopPaneMonitorTicks
+            )
+        })
+        .cloned()
+        .collect();
+    assert_eq!(
+        pane_monitor_jobs,
+        vec![
+            BackgroundJob::StartPaneMonitorTicks,
+            BackgroundJob::StopPaneMonitorTicks
+        ]
+    );
+}
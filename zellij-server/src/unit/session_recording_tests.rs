@@ -0,0 +1,277 @@
+use super::{RecordedOutput, SessionRecordings};
+use crate::panes::PaneId;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use zellij_utils::{
+    asciicast::{Asciicast, AsciicastEvent},
+    pane_size::Size,
+};
+
+fn read_recording(path: &Path) -> Asciicast {
+    Asciicast::from_reader(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+fn events(path: &Path) -> Vec<AsciicastEvent> {
+    read_recording(path)
+        .events
+        .into_iter()
+        .map(|(_time, event)| event)
+        .collect()
+}
+
+#[test]
+fn recording_a_pane() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("pane.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            path.clone(),
+            RecordedOutput::Pane(PaneId::Terminal(1)),
+            size,
+            Some("my pane".to_owned()),
+            Some("initial".to_owned()),
+        )
+        .unwrap();
+    assert!(recordings.is_recording_pane(PaneId::Terminal(1)));
+    assert!(!recordings.is_recording_pane(PaneId::Terminal(2)));
+    assert!(!recordings.is_recording_client_output());
+
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"hello", size);
+    recordings.record_pane_bytes(PaneId::Terminal(2), b"not recorded", size);
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"resized", Size { rows: 5, cols: 8 });
+    assert_eq!(recordings.stop(None).unwrap(), vec![path.clone()]);
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"after stop", size);
+
+    let recording = read_recording(&path);
+    assert_eq!(recording.header.width, 20);
+    assert_eq!(recording.header.height, 10);
+    assert_eq!(recording.header.title.as_deref(), Some("my pane"));
+    assert_eq!(
+        events(&path),
+        vec![
+            AsciicastEvent::Output("initial".to_owned()),
+            AsciicastEvent::Output("hello".to_owned()),
+            AsciicastEvent::Resize {
+                width: 8,
+                height: 5
+            },
+            AsciicastEvent::Output("resized".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn utf8_characters_split_between_reads_are_kept_whole() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pane.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            path.clone(),
+            RecordedOutput::Pane(PaneId::Terminal(1)),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    let bytes = "a😀b".as_bytes();
+    recordings.record_pane_bytes(PaneId::Terminal(1), &bytes[..3], size);
+    recordings.record_pane_bytes(PaneId::Terminal(1), &bytes[3..], size);
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"\xffc", size);
+    recordings.stop(Some(&path)).unwrap();
+    assert_eq!(
+        events(&path),
+        vec![
+            AsciicastEvent::Output("a".to_owned()),
+            AsciicastEvent::Output("😀b".to_owned()),
+            AsciicastEvent::Output("\u{fffd}c".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn recording_a_tab_only_records_while_it_is_displayed() {
+    let dir = tempfile::tempdir().unwrap();
+    let tab_path = dir.path().join("tab.cast");
+    let client_path = dir.path().join("client.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            tab_path.clone(),
+            RecordedOutput::Tab(0, 1),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    recordings
+        .start(
+            client_path.clone(),
+            RecordedOutput::Client(1),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(recordings.is_recording_client_output());
+
+    recordings.record_client_output(1, Some(0), "first tab", size);
+    recordings.record_client_output(1, Some(1), "second tab", size);
+    recordings.record_client_output(2, Some(0), "other client", size);
+    recordings.stop(None).unwrap();
+
+    assert_eq!(
+        events(&tab_path),
+        vec![AsciicastEvent::Output("first tab".to_owned())]
+    );
+    assert_eq!(
+        events(&client_path),
+        vec![
+            AsciicastEvent::Output("first tab".to_owned()),
+            AsciicastEvent::Output("second tab".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn stopping_recordings() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pane.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    assert!(recordings.stop(None).is_err(), "nothing to stop");
+    recordings
+        .start(path.clone(), RecordedOutput::Client(1), size, None, None)
+        .unwrap();
+    assert!(
+        recordings
+            .start(path.clone(), RecordedOutput::Client(2), size, None, None)
+            .is_err(),
+        "path is already being recorded to"
+    );
+    assert!(recordings
+        .stop(Some(&dir.path().join("other.cast")))
+        .is_err());
+    assert_eq!(recordings.stop(Some(&path)).unwrap(), vec![path.clone()]);
+    assert!(recordings.stop(Some(&path)).is_err(), "already stopped");
+}
+
+#[test]
+fn recording_never_overwrites_an_existing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("existing.cast");
+    std::fs::write(&path, "important").unwrap();
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    assert!(recordings
+        .start(path.clone(), RecordedOutput::Client(1), size, None, None)
+        .is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "important");
+}
+
+#[test]
+fn closing_a_pane_stops_its_recordings() {
+    let dir = tempfile::tempdir().unwrap();
+    let pane_path = dir.path().join("pane.cast");
+    let client_path = dir.path().join("client.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            pane_path.clone(),
+            RecordedOutput::Pane(PaneId::Terminal(1)),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    recordings
+        .start(
+            client_path.clone(),
+            RecordedOutput::Client(1),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"before close", size);
+    assert_eq!(
+        recordings.stop_pane(PaneId::Terminal(2)),
+        Vec::<std::path::PathBuf>::new()
+    );
+    assert_eq!(
+        recordings.stop_pane(PaneId::Terminal(1)),
+        vec![pane_path.clone()]
+    );
+    assert!(!recordings.is_recording_pane(PaneId::Terminal(1)));
+    assert!(recordings.is_recording_client_output());
+    assert_eq!(
+        events(&pane_path),
+        vec![AsciicastEvent::Output("before close".to_owned())]
+    );
+}
+
+#[test]
+fn stopping_a_recording_writes_everything_still_queued() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pane.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            path.clone(),
+            RecordedOutput::Pane(PaneId::Terminal(1)),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    // more chunks than fit in the writer's queue, the rest are held back until there is room
+    let mut expected_output = String::new();
+    for i in 0..5000 {
+        let chunk = format!("line {}\n", i);
+        recordings.record_pane_bytes(PaneId::Terminal(1), chunk.as_bytes(), size);
+        expected_output.push_str(&chunk);
+    }
+    recordings.stop(Some(&path)).unwrap();
+    let recorded_output: String = events(&path)
+        .into_iter()
+        .map(|event| match event {
+            AsciicastEvent::Output(output) => output,
+            event => panic!("unexpected event: {:?}", event),
+        })
+        .collect();
+    assert_eq!(recorded_output, expected_output);
+}
+
+#[test]
+fn active_recordings_are_flushed_periodically() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pane.cast");
+    let size = Size { rows: 10, cols: 20 };
+    let mut recordings = SessionRecordings::default();
+    recordings
+        .start(
+            path.clone(),
+            RecordedOutput::Pane(PaneId::Terminal(1)),
+            size,
+            None,
+            None,
+        )
+        .unwrap();
+    recordings.record_pane_bytes(PaneId::Terminal(1), b"still recording", size);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while events(&path).is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(
+        events(&path),
+        vec![AsciicastEvent::Output("still recording".to_owned())]
+    );
+}
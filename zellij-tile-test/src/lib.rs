@@ -0,0 +1,322 @@
+//! A headless test harness for Zellij plugins.
+//!
+//! The harness loads a plugin compiled to `wasm32-wasip1` (the same `.wasm` file Zellij would
+//! load), and drives the [`ZellijPlugin`] methods registered with `register_plugin!` without
+//! starting Zellij. Tests can send it synthetic [`Event`]s and [`PipeMessage`]s, assert on the
+//! [`PluginCommand`]s it emits and snapshot what it renders at a given size.
+//!
+//! Nothing is done on behalf of the commands besides recording them (and answering the ones that
+//! expect a response, see [`PluginTestHarness::respond_to_commands`]), so plugin workers do not
+//! run and timers do not fire: send the resulting events explicitly instead.
+//!
+//! ```no_run
+//! use std::collections::BTreeMap;
+//! use zellij_tile_test::PluginTestHarness;
+//! use zellij_utils::data::{BareKey, Event, KeyWithModifier, PluginCommand};
+//!
+//! let mut plugin =
+//!     PluginTestHarness::new("target/wasm32-wasip1/debug/my-plugin.wasm").unwrap();
+//! plugin.load(BTreeMap::new()).unwrap();
+//! let should_render = plugin
+//!     .update(Event::Key(KeyWithModifier::new(BareKey::Enter)))
+//!     .unwrap();
+//! assert!(should_render);
+//! assert!(matches!(
+//!     plugin.take_commands().last(),
+//!     Some(PluginCommand::HideSelf)
+//! ));
+//! assert_eq!(plugin.render_text(10, 80).unwrap(), "Hello from my plugin\n");
+//! ```
+//!
+//! [`ZellijPlugin`]: https://docs.rs/zellij-tile/latest/zellij_tile/trait.ZellijPlugin.html
+
+use prost::Message;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use wasmi::{Caller, Engine, Instance, Linker, Module, Store};
+use wasmi_wasi::sync::WasiCtxBuilder;
+use wasmi_wasi::wasi_common::pipe::{ReadPipe, WritePipe};
+use wasmi_wasi::WasiCtx;
+use zellij_utils::{
+    consts::VERSION,
+    data::{Event, EventType, PipeMessage, PluginCommand, PluginIds},
+    errors::prelude::*,
+    input::layout::PluginUserConfiguration,
+    plugin_api::{
+        action::ProtobufPluginConfiguration,
+        event::ProtobufEvent,
+        pipe_message::ProtobufPipeMessage,
+        plugin_command::ProtobufPluginCommand,
+        plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
+    },
+};
+
+/// Answers the commands that expect a response from Zellij (eg. `GetPluginIds`), returning the
+/// protobuf encoded response or `None` to fall back to the harness defaults
+type CommandResponder = Box<dyn FnMut(&PluginCommand) -> Option<Vec<u8>> + Send>;
+
+struct HarnessEnv {
+    wasi_ctx: WasiCtx,
+    stdin_pipe: Arc<Mutex<VecDeque<u8>>>,
+    stdout_pipe: Arc<Mutex<VecDeque<u8>>>,
+    plugin_ids: PluginIds,
+    subscriptions: HashSet<EventType>,
+    commands: Vec<PluginCommand>,
+    responder: Option<CommandResponder>,
+    panic_message: Option<String>,
+}
+
+impl HarnessEnv {
+    fn read_stdout(&self) -> String {
+        let mut buf = vec![];
+        // reading from a VecDeque cannot fail
+        let _ = self.stdout_pipe.lock().unwrap().read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).to_string()
+    }
+    fn write_stdin(&self, bytes: &[u8]) -> Result<()> {
+        let serialized = serde_json::to_string(bytes)?;
+        writeln!(self.stdin_pipe.lock().unwrap(), "{}", serialized)
+            .context("failed to write to plugin stdin")
+    }
+    fn default_response(&self, command: &PluginCommand) -> Result<Option<Vec<u8>>> {
+        match command {
+            PluginCommand::GetPluginIds => ProtobufPluginIds::try_from(self.plugin_ids.clone())
+                .map(|plugin_ids| Some(plugin_ids.encode_to_vec()))
+                .map_err(|e| anyhow!("Failed to serialize plugin ids: {}", e)),
+            PluginCommand::GetZellijVersion => Ok(Some(
+                ProtobufZellijVersion {
+                    version: VERSION.to_owned(),
+                }
+                .encode_to_vec(),
+            )),
+            _ => Ok(None),
+        }
+    }
+    fn run_plugin_command(&mut self) -> Result<()> {
+        let serialized_command = self.read_stdout();
+        let bytes: Vec<u8> = serde_json::from_str(serialized_command.trim())
+            .with_context(|| format!("failed to read plugin command: {}", serialized_command))?;
+        let command: PluginCommand = ProtobufPluginCommand::decode(bytes.as_slice())?
+            .try_into()
+            .map_err(|e| anyhow!("failed to convert serialized command: {}", e))?;
+        match &command {
+            PluginCommand::Subscribe(event_types) => {
+                self.subscriptions.extend(event_types.iter().cloned())
+            },
+            PluginCommand::Unsubscribe(event_types) => self
+                .subscriptions
+                .retain(|event_type| !event_types.contains(event_type)),
+            PluginCommand::ReportPanic(panic_message) => {
+                self.panic_message = Some(panic_message.clone())
+            },
+            _ => {},
+        }
+        let response = match self.responder.as_mut().and_then(|r| r(&command)) {
+            Some(response) => Some(response),
+            None => self.default_response(&command)?,
+        };
+        if let Some(response) = response {
+            self.write_stdin(&response)?;
+        }
+        self.commands.push(command);
+        Ok(())
+    }
+}
+
+/// A plugin loaded outside of Zellij, see the [crate level documentation](crate) for an example
+pub struct PluginTestHarness {
+    store: Store<HarnessEnv>,
+    instance: Instance,
+}
+
+impl PluginTestHarness {
+    /// Load a plugin compiled to `wasm32-wasip1` from the given path
+    ///
+    /// This does not call the plugin's `load` method, see [`PluginTestHarness::load`].
+    pub fn new(wasm_path: impl AsRef<Path>) -> Result<Self> {
+        let wasm_path = wasm_path.as_ref();
+        let wasm_bytes = std::fs::read(wasm_path)
+            .with_context(|| format!("failed to read plugin from {}", wasm_path.display()))?;
+        Self::from_wasm_bytes(&wasm_bytes)
+    }
+    /// Load a plugin from the bytes of its compiled `.wasm` file
+    pub fn from_wasm_bytes(wasm_bytes: &[u8]) -> Result<Self> {
+        let err_context = || "failed to instantiate plugin";
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm_bytes).with_context(err_context)?;
+
+        let stdin_pipe = Arc::new(Mutex::new(VecDeque::new()));
+        let stdout_pipe = Arc::new(Mutex::new(VecDeque::new()));
+        let wasi_ctx = WasiCtxBuilder::new().build();
+        wasi_ctx.set_stdin(Box::new(ReadPipe::new(VecDequeInputStream(
+            stdin_pipe.clone(),
+        ))));
+        wasi_ctx.set_stdout(Box::new(WritePipe::new(VecDequeOutputStream(
+            stdout_pipe.clone(),
+        ))));
+        wasi_ctx.set_stderr(Box::new(WritePipe::new(std::io::stderr())));
+
+        let env = HarnessEnv {
+            wasi_ctx,
+            stdin_pipe,
+            stdout_pipe,
+            plugin_ids: PluginIds {
+                plugin_id: 1,
+                zellij_pid: std::process::id(),
+                initial_cwd: PathBuf::from("/"),
+                client_id: 1,
+            },
+            subscriptions: HashSet::new(),
+            commands: vec![],
+            responder: None,
+            panic_message: None,
+        };
+        let mut store = Store::new(&engine, env);
+        let mut linker = Linker::new(&engine);
+        wasmi_wasi::add_to_linker(&mut linker, |env: &mut HarnessEnv| &mut env.wasi_ctx)
+            .with_context(err_context)?;
+        linker
+            .func_wrap(
+                "zellij",
+                "host_run_plugin_command",
+                |mut caller: Caller<'_, HarnessEnv>| {
+                    caller
+                        .data_mut()
+                        .run_plugin_command()
+                        .map_err(|e| wasmi::Error::new(format!("{:#}", e)))
+                },
+            )
+            .with_context(err_context)?;
+        let instance = linker
+            .instantiate_and_start(&mut store, &module)
+            .with_context(err_context)?;
+        if let Some(func) = instance.get_func(&mut store, "_initialize") {
+            if let Ok(typed_func) = func.typed::<(), ()>(&store) {
+                typed_func.call(&mut store, ()).with_context(err_context)?;
+            }
+        }
+        let mut harness = PluginTestHarness { store, instance };
+        harness.call::<(), ()>("_start", ())?;
+        Ok(harness)
+    }
+    /// Replace the ids (eg. plugin id, initial cwd) reported to the plugin when it asks for them
+    pub fn with_plugin_ids(mut self, plugin_ids: PluginIds) -> Self {
+        self.store.data_mut().plugin_ids = plugin_ids;
+        self
+    }
+    /// Answer the commands that expect a response from Zellij with protobuf encoded responses
+    /// (eg. a `ProtobufGetPaneCwdResponse` for `PluginCommand::GetPaneCwd`)
+    ///
+    /// Commands the responder returns `None` for get the harness defaults, which only cover
+    /// `GetPluginIds` and `GetZellijVersion`.
+    pub fn respond_to_commands(
+        &mut self,
+        responder: impl FnMut(&PluginCommand) -> Option<Vec<u8>> + Send + 'static,
+    ) {
+        self.store.data_mut().responder = Some(Box::new(responder));
+    }
+    /// Call the plugin's `load` method with the given configuration
+    pub fn load(&mut self, configuration: BTreeMap<String, String>) -> Result<()> {
+        let protobuf_configuration =
+            ProtobufPluginConfiguration::try_from(PluginUserConfiguration::new(configuration))
+                .map_err(|e| anyhow!("failed to serialize plugin configuration: {}", e))?;
+        self.store
+            .data()
+            .write_stdin(&protobuf_configuration.encode_to_vec())?;
+        self.call::<(), ()>("load", ())
+    }
+    /// Send an event to the plugin's `update` method, returning whether the plugin asked to be
+    /// rendered
+    ///
+    /// Like in Zellij, events the plugin is not subscribed to are not delivered.
+    pub fn update(&mut self, event: Event) -> Result<bool> {
+        let event_type = EventType::from_str(&event.to_string())
+            .map_err(|e| anyhow!("unknown event type: {}", e))?;
+        if !self.is_subscribed_to(event_type) {
+            return Ok(false);
+        }
+        let protobuf_event = ProtobufEvent::try_from(event)
+            .map_err(|e| anyhow!("failed to serialize event: {}", e))?;
+        self.store
+            .data()
+            .write_stdin(&protobuf_event.encode_to_vec())?;
+        self.call::<(), i32>("update", ())
+            .map(|should_render| should_render == 1)
+    }
+    /// Send a message to the plugin's `pipe` method, returning whether the plugin asked to be
+    /// rendered
+    pub fn pipe(&mut self, pipe_message: PipeMessage) -> Result<bool> {
+        let protobuf_pipe_message = ProtobufPipeMessage::try_from(pipe_message)
+            .map_err(|e| anyhow!("failed to serialize pipe message: {}", e))?;
+        self.store
+            .data()
+            .write_stdin(&protobuf_pipe_message.encode_to_vec())?;
+        self.call::<(), i32>("pipe", ())
+            .map(|should_render| should_render == 1)
+    }
+    /// Render the plugin at the given size, returning everything it printed (including ANSI
+    /// escape sequences)
+    pub fn render(&mut self, rows: usize, cols: usize) -> Result<String> {
+        self.store.data().read_stdout(); // discard anything printed outside of render
+        self.call::<(i32, i32), ()>("render", (rows as i32, cols as i32))?;
+        Ok(self.store.data().read_stdout())
+    }
+    /// Render the plugin at the given size, stripping ANSI escape sequences from the output so
+    /// that it can be easily compared or snapshotted
+    pub fn render_text(&mut self, rows: usize, cols: usize) -> Result<String> {
+        self.render(rows, cols).map(strip_ansi_escapes::strip_str)
+    }
+    /// All the commands the plugin emitted so far
+    pub fn commands(&self) -> &[PluginCommand] {
+        &self.store.data().commands
+    }
+    /// Take the commands the plugin emitted so far, so that the next assertions only see new ones
+    pub fn take_commands(&mut self) -> Vec<PluginCommand> {
+        std::mem::take(&mut self.store.data_mut().commands)
+    }
+    pub fn is_subscribed_to(&self, event_type: EventType) -> bool {
+        self.store.data().subscriptions.contains(&event_type)
+    }
+    fn call<Params, Results>(&mut self, function_name: &str, params: Params) -> Result<Results>
+    where
+        Params: wasmi::WasmParams,
+        Results: wasmi::WasmResults,
+    {
+        let result = self
+            .instance
+            .get_typed_func::<Params, Results>(&mut self.store, function_name)
+            .and_then(|func| func.call(&mut self.store, params));
+        result.map_err(|e| match self.store.data_mut().panic_message.take() {
+            Some(panic_message) => {
+                anyhow!("plugin panicked in {}: {}", function_name, panic_message)
+            },
+            None => anyhow!("failed to call plugin {}: {}", function_name, e),
+        })
+    }
+}
+
+struct VecDequeInputStream(Arc<Mutex<VecDeque<u8>>>);
+
+impl Read for VecDequeInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().read(buf)
+    }
+}
+
+struct VecDequeOutputStream(Arc<Mutex<VecDeque<u8>>>);
+
+impl Write for VecDequeOutputStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[path = "./unit/harness_tests.rs"]
+#[cfg(test)]
+mod harness_tests;
@@ -0,0 +1,118 @@
+use super::PluginTestHarness;
+use insta::assert_snapshot;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use zellij_utils::data::{
+    BareKey, Event, EventType, InputMode, KeyWithModifier, PipeMessage, PipeSource, PluginCommand,
+    PluginIds,
+};
+
+fn loaded_fixture_plugin() -> PluginTestHarness {
+    // to populate this file, make sure to run the build-e2e CI job
+    // (or compile the fixture plugin and copy the resulting .wasm blob to the below location)
+    let plugin_fixture = format!(
+        "{}/../target/e2e-data/plugins/fixture-plugin-for-tests.wasm",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let mut plugin = PluginTestHarness::new(plugin_fixture).unwrap();
+    plugin.load(BTreeMap::new()).unwrap();
+    plugin
+}
+
+#[test]
+#[ignore]
+pub fn load_records_subscriptions_and_commands() {
+    let plugin = loaded_fixture_plugin();
+    assert!(plugin.is_subscribed_to(EventType::Key));
+    assert!(!plugin.is_subscribed_to(EventType::ModeUpdate));
+    assert!(plugin
+        .commands()
+        .iter()
+        .any(|command| matches!(command, PluginCommand::RequestPluginPermissions(..))));
+    assert!(plugin
+        .commands()
+        .iter()
+        .any(|command| matches!(command, PluginCommand::WatchFilesystem)));
+}
+
+#[test]
+#[ignore]
+pub fn update_emits_plugin_commands() {
+    let mut plugin = loaded_fixture_plugin();
+    plugin.take_commands();
+    let should_render = plugin
+        .update(Event::Key(KeyWithModifier::new(BareKey::Char('a'))))
+        .unwrap();
+    assert!(should_render);
+    assert!(matches!(
+        plugin.take_commands().as_slice(),
+        [PluginCommand::SwitchToMode(InputMode::Tab)]
+    ));
+}
+
+#[test]
+#[ignore]
+pub fn events_the_plugin_is_not_subscribed_to_are_not_delivered() {
+    let mut plugin = loaded_fixture_plugin();
+    let should_render = plugin.update(Event::Visible(true)).unwrap();
+    assert!(!should_render);
+    assert_snapshot!(plugin.render_text(10, 80).unwrap());
+}
+
+#[test]
+#[ignore]
+pub fn render_at_a_given_size() {
+    let mut plugin = loaded_fixture_plugin();
+    plugin.update(Event::InputReceived).unwrap();
+    assert_snapshot!(plugin.render_text(20, 120).unwrap());
+}
+
+#[test]
+#[ignore]
+pub fn pipe_messages_and_command_responses() {
+    let mut plugin = loaded_fixture_plugin().with_plugin_ids(PluginIds {
+        plugin_id: 42,
+        zellij_pid: 1,
+        initial_cwd: PathBuf::from("/tmp"),
+        client_id: 1,
+    });
+    plugin.take_commands();
+    let should_render = plugin
+        .pipe(PipeMessage::new(
+            PipeSource::Keybind,
+            "plugin_request_to_self",
+            &None,
+            &None,
+            false,
+        ))
+        .unwrap();
+    assert!(should_render);
+    let commands = plugin.take_commands();
+    assert!(matches!(commands[0], PluginCommand::GetPluginIds));
+    match &commands[1] {
+        PluginCommand::SendPluginRequest { message, .. } => {
+            assert_eq!(message.destination_plugin_id, Some(42));
+        },
+        command => panic!("unexpected command: {:?}", command),
+    }
+}
+
+#[test]
+#[ignore]
+pub fn plugin_panics_are_reported() {
+    let mut plugin = loaded_fixture_plugin();
+    plugin.respond_to_commands(|command| match command {
+        PluginCommand::GetPluginIds => Some(vec![0xff, 0xff]), // not a valid protobuf message
+        _ => None,
+    });
+    let error = plugin
+        .pipe(PipeMessage::new(
+            PipeSource::Keybind,
+            "plugin_request_to_self",
+            &None,
+            &None,
+            false,
+        ))
+        .unwrap_err();
+    assert!(error.to_string().starts_with("plugin panicked in pipe"));
+}
@@ -46,6 +46,13 @@ pub trait ZellijPlugin: Default {
     /// Will be called either after an `update` that requested it, or when the plugin otherwise needs to be re-rendered (eg. on startup, or when the plugin is resized).
     /// The `rows` and `cols` values represent the "content size" of the plugin (this will not include its surrounding frame if the user has pane frames enabled).
     fn render(&mut self, rows: usize, cols: usize) {}
+    /// Will be called right before the plugin is reloaded because its `.wasm` file changed (only when it is loaded with `dev_mode true` in its configuration).
+    /// The returned state will be passed to [`restore_state`](ZellijPlugin::restore_state) of the new instance of the plugin.
+    fn save_state(&mut self) -> Option<String> {
+        None
+    }
+    /// Will be called right after `load` with the state returned from [`save_state`](ZellijPlugin::save_state) of the previous instance of the plugin, when it was reloaded in `dev_mode`.
+    fn restore_state(&mut self, state: String) {}
 }
 
 /// This trait is used to create workers. Workers can be used by plugins to run longer running
@@ -164,6 +171,21 @@ macro_rules! register_plugin {
             });
         }
 
+        #[no_mangle]
+        pub fn save_state() {
+            STATE.with(|state| {
+                $crate::shim::object_to_stdout(&state.borrow_mut().save_state());
+            });
+        }
+
+        #[no_mangle]
+        pub fn restore_state() {
+            STATE.with(|state| {
+                let saved_state: String = $crate::shim::object_from_stdin().unwrap();
+                state.borrow_mut().restore_state(saved_state);
+            });
+        }
+
         #[no_mangle]
         pub fn plugin_version() {
             println!("{}", $crate::prelude::VERSION);
@@ -26,6 +26,7 @@ use zellij_utils::plugin_api::plugin_command::{
     ProtobufGetPanePidResponse, ProtobufGetPaneRunningCommandResponse,
     ProtobufGetSessionEnvironmentVariablesResponse, ProtobufGetSessionListResponse,
     ProtobufGetTabInfoResponse, ProtobufHideFloatingPanesResponse, ProtobufKillSessionsResponse,
+    ProtobufListGrantedPermissionsResponse, ProtobufListStorageValuesResponse,
     ProtobufNewTabResponse, ProtobufNewTabUnfocusedResponse, ProtobufNewTabsResponse,
     ProtobufNewTiledPaneInTabResponse, ProtobufOpenCommandPaneBackgroundResponse,
     ProtobufOpenCommandPaneFloatingNearPluginResponse, ProtobufOpenCommandPaneFloatingResponse,
@@ -41,7 +42,8 @@ use zellij_utils::plugin_api::plugin_command::{
     ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse, ProtobufOpenTerminalResponse,
     ProtobufParseLayoutResponse, ProtobufPluginCommand, ProtobufRenameLayoutResponse,
     ProtobufSaveLayoutResponse, ProtobufSaveSessionResponse, ProtobufShowFloatingPanesResponse,
-    RenameWebTokenResponse, RevokeAllWebTokensResponse, RevokeTokenResponse,
+    ProtobufStorageValueResponse, RenameWebTokenResponse, RevokeAllWebTokensResponse,
+    RevokeTokenResponse,
 };
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
 
@@ -96,6 +98,52 @@ pub fn request_permission(permissions: &[PermissionType]) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Like `request_permission`, but also asks for access to specific host folders (see
+/// `FilesystemPermission`). Once granted, each folder is available to the plugin under its
+/// absolute host path.
+pub fn request_permission_with_filesystem_access(
+    permissions: &[PermissionType],
+    filesystem_permissions: &[FilesystemPermission],
+) {
+    let plugin_command = PluginCommand::RequestPluginPermissionsWithFilesystemAccess(
+        permissions.into(),
+        filesystem_permissions.into(),
+    );
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// List the permissions granted to every plugin URL (requires the `ReadApplicationState`
+/// permission)
+pub fn list_granted_permissions() -> Vec<GrantedPluginPermissions> {
+    let plugin_command = PluginCommand::ListGrantedPermissions;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let protobuf_response =
+        ProtobufListGrantedPermissionsResponse::decode(bytes_from_stdin().unwrap().as_slice())
+            .unwrap();
+    protobuf_response
+        .granted
+        .into_iter()
+        .map(|granted| granted.into())
+        .collect()
+}
+
+/// Revoke permissions from a plugin URL, all of them if `permissions` is empty (requires the
+/// `Reconfigure` permission). Running instances of that plugin lose them immediately and get a
+/// `PermissionRequestResult` event.
+pub fn revoke_plugin_permissions(plugin_url: &str, permissions: &[PermissionType]) {
+    let plugin_command = PluginCommand::RevokePluginPermissions {
+        plugin_url: plugin_url.to_owned(),
+        permissions: permissions.into(),
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Query Functions
 /// Returns the unique Zellij pane ID for the plugin as well as the Zellij process id.
 pub fn get_plugin_ids() -> PluginIds {
@@ -946,6 +994,31 @@ pub fn new_tabs_with_layout_info<L: AsRef<LayoutInfo>>(layout_info: L) -> Vec<us
     NewTabsResponse::try_from(response).unwrap()
 }
 
+/// Provide a stringified [`layout`](https://zellij.dev/documentation/layouts.html) to be applied to the current session, with values for the parameters it declares. If the layout has multiple tabs, they will all be opened.
+pub fn new_tabs_with_layout_and_args(
+    layout: &str,
+    layout_args: BTreeMap<String, String>,
+) -> Vec<usize> {
+    new_tabs_with_layout_info_and_args(LayoutInfo::Stringified(layout.to_owned()), layout_args)
+}
+
+/// Provide a LayoutInfo to be applied to the current session in a new tab, with values for the parameters the layout declares. If the layout has multiple tabs, they will all be opened.
+pub fn new_tabs_with_layout_info_and_args<L: AsRef<LayoutInfo>>(
+    layout_info: L,
+    layout_args: BTreeMap<String, String>,
+) -> Vec<usize> {
+    let plugin_command = PluginCommand::NewTabsWithLayoutAndArgs {
+        layout_info: layout_info.as_ref().clone(),
+        layout_args,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+
+    let response = ProtobufNewTabsResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    NewTabsResponse::try_from(response).unwrap()
+}
+
 /// Open a new tab with the default layout
 pub fn new_tab<S: AsRef<str>>(name: Option<S>, cwd: Option<S>) -> Option<usize>
 where
@@ -1343,6 +1416,15 @@ pub fn close_focus() {
     unsafe { host_run_plugin_command() };
 }
 
+/// Reopen the most recently closed pane of the focused tab, listed in
+/// `TabInfo::recently_closed_panes`
+pub fn reopen_closed_pane() {
+    let plugin_command = PluginCommand::ReopenClosedPane;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn new_pane() {
     let plugin_command = PluginCommand::NewPane;
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1357,6 +1439,33 @@ pub fn toggle_floating_panes(tab_id: Option<u64>) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Start recording the output of a pane, a tab or the whole screen to an asciicast file (by
+/// default in the cache folder). A `path` must be a new file in the plugin's `/data` or `/tmp`
+/// folder.
+pub fn start_recording(path: Option<PathBuf>, target: RecordingTarget) {
+    let plugin_command = PluginCommand::StartRecording { path, target };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stop the recording to `path`, or all active recordings if it is `None`
+pub fn stop_recording(path: Option<PathBuf>) {
+    let plugin_command = PluginCommand::StopRecording { path };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Set the activity and silence monitors of a terminal pane, alerts are sent as
+/// `Event::PaneMonitorAlert`. Empty monitors stop monitoring the pane.
+pub fn set_pane_monitors(pane_id: PaneId, monitors: PaneMonitors) {
+    let plugin_command = PluginCommand::SetPaneMonitors { pane_id, monitors };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Turn the `STDIN` synchronization of the current tab on or off
 pub fn toggle_active_tab_sync() {
     let plugin_command = PluginCommand::ToggleActiveTabSync;
@@ -1665,6 +1774,139 @@ pub fn pipe_message_to_plugin(message_to_plugin: MessageToPlugin) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Send a request to a plugin (by url, alias or id in `message_to_plugin`), launching it if it is
+/// not already running. The plugin receives it in its `pipe` method with a `request_id` and
+/// answers it with [`reply_to_plugin_request`] or [`fail_plugin_request`].
+///
+/// Subscribe to the `PluginRequestResult` Event to receive the reply. The `context` is returned
+/// verbatim in this event and can be used to tell requests apart. If no reply arrives within
+/// `timeout_ms` (5 seconds if not specified), the event will carry `PluginReply::TimedOut`.
+pub fn send_plugin_request(
+    message_to_plugin: MessageToPlugin,
+    timeout_ms: Option<u64>,
+    context: BTreeMap<String, String>,
+) {
+    let plugin_command = PluginCommand::SendPluginRequest {
+        message: message_to_plugin,
+        timeout_ms,
+        context,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Reply to a request received in the `pipe` method (see `PipeMessage::request_id`), only the
+/// first answer to a request is delivered
+pub fn reply_to_plugin_request(request_id: u64, payload: Option<String>) {
+    let plugin_command = PluginCommand::ReplyToPluginRequest {
+        request_id,
+        reply: PluginReply::Replied(payload),
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Fail a request received in the `pipe` method (see `PipeMessage::request_id`) with an error
+pub fn fail_plugin_request(request_id: u64, error: impl Into<String>) {
+    let plugin_command = PluginCommand::ReplyToPluginRequest {
+        request_id,
+        reply: PluginReply::Failed(error.into()),
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Read a value from the persistent storage of this plugin
+///
+/// Values stored with `StorageScope::Plugin` are shared by all instances of this plugin url across
+/// sessions, while values stored with `StorageScope::Session` only live in the current session.
+pub fn get_storage_value(key: &str, scope: StorageScope) -> Result<Option<String>, String> {
+    let plugin_command = PluginCommand::GetStorageValue {
+        key: key.to_owned(),
+        scope,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let protobuf_response =
+        ProtobufStorageValueResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    match protobuf_response.error {
+        Some(error) => Err(error),
+        None => Ok(protobuf_response.value),
+    }
+}
+
+/// Write a value to the persistent storage of this plugin, other instances of this plugin in the
+/// current session will receive an `Event::StorageValueChanged` if they are subscribed to it
+/// (instances in other sessions see the new value the next time they read it)
+///
+/// A single key and its value can take up to 64KB, and all the values of a plugin up to 1MB.
+pub fn set_storage_value(
+    key: impl Into<String>,
+    value: impl Into<String>,
+    scope: StorageScope,
+) -> Result<(), String> {
+    let plugin_command = PluginCommand::SetStorageValue {
+        key: key.into(),
+        value: value.into(),
+        scope,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let protobuf_response =
+        ProtobufStorageValueResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    match protobuf_response.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Remove a value from the persistent storage of this plugin
+pub fn delete_storage_value(key: &str, scope: StorageScope) -> Result<(), String> {
+    let plugin_command = PluginCommand::DeleteStorageValue {
+        key: key.to_owned(),
+        scope,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let protobuf_response =
+        ProtobufStorageValueResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    match protobuf_response.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// List all the values in the persistent storage of this plugin whose keys start with `prefix`
+/// (an empty prefix lists all of them)
+pub fn list_storage_values(
+    prefix: &str,
+    scope: StorageScope,
+) -> Result<BTreeMap<String, String>, String> {
+    let plugin_command = PluginCommand::ListStorageValues {
+        prefix: prefix.to_owned(),
+        scope,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let protobuf_response =
+        ProtobufListStorageValuesResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    match protobuf_response.error {
+        Some(error) => Err(error),
+        None => Ok(protobuf_response
+            .values
+            .into_iter()
+            .map(|item| (item.name, item.value))
+            .collect()),
+    }
+}
+
 /// Disconnect all other clients from the current session
 pub fn disconnect_other_clients() {
     let plugin_command = PluginCommand::DisconnectOtherClients;
@@ -2691,7 +2933,23 @@ pub fn generate_web_login_token(
     token_label: Option<String>,
     read_only: bool,
 ) -> Result<String, String> {
-    let plugin_command = PluginCommand::GenerateWebLoginToken(token_label, read_only);
+    generate_restricted_web_login_token(token_label, read_only, None, None)
+}
+
+/// Generate a web login token that expires after `expires_in` (if given) and can only attach to
+/// sessions whose name matches the `session_scope` glob (if given)
+pub fn generate_restricted_web_login_token(
+    token_label: Option<String>,
+    read_only: bool,
+    expires_in: Option<std::time::Duration>,
+    session_scope: Option<String>,
+) -> Result<String, String> {
+    let plugin_command = PluginCommand::GenerateWebLoginToken(
+        token_label,
+        read_only,
+        expires_in.map(|d| d.as_secs()),
+        session_scope,
+    );
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -2743,6 +3001,44 @@ pub fn list_web_login_tokens() -> Result<Vec<(String, String, bool)>, String> {
     }
 }
 
+/// Like `list_web_login_tokens`, also including the expiry, session scope and last use of each
+/// token
+pub fn list_web_login_token_details() -> Result<Vec<WebLoginTokenInfo>, String> {
+    let plugin_command = PluginCommand::ListWebLoginTokens;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let list_tokens_response =
+        ListTokensResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+
+    if let Some(error) = list_tokens_response.error {
+        return Err(error);
+    }
+    let not_empty = |s: Option<&String>| s.filter(|s| !s.is_empty()).cloned();
+    let tokens = list_tokens_response
+        .tokens
+        .iter()
+        .enumerate()
+        .map(|(i, name)| WebLoginTokenInfo {
+            name: name.clone(),
+            created_at: list_tokens_response
+                .creation_times
+                .get(i)
+                .cloned()
+                .unwrap_or_default(),
+            read_only: list_tokens_response
+                .read_only_flags
+                .get(i)
+                .copied()
+                .unwrap_or(false),
+            expires_at: not_empty(list_tokens_response.expiration_times.get(i)),
+            session_scope: not_empty(list_tokens_response.session_scopes.get(i)),
+            last_used_at: not_empty(list_tokens_response.last_used_times.get(i)),
+        })
+        .collect();
+    Ok(tokens)
+}
+
 pub fn revoke_all_web_tokens() -> Result<(), String> {
     let plugin_command = PluginCommand::RevokeAllWebLoginTokens;
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -2847,6 +3143,28 @@ pub fn override_layout<L: AsRef<LayoutInfo>>(
     unsafe { host_run_plugin_command() };
 }
 
+/// Like `override_layout`, with values for the parameters the layout declares
+pub fn override_layout_with_args<L: AsRef<LayoutInfo>>(
+    layout_info: L,
+    retain_existing_terminal_panes: bool,
+    retain_existing_plugin_panes: bool,
+    apply_only_to_active_tab: bool,
+    layout_args: BTreeMap<String, String>,
+    context: BTreeMap<String, String>,
+) {
+    let plugin_command = PluginCommand::OverrideLayoutWithArgs {
+        layout_info: layout_info.as_ref().clone(),
+        retain_existing_terminal_panes,
+        retain_existing_plugin_panes,
+        apply_only_to_active_tab,
+        layout_args,
+        context,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Internal Functions
 
 #[doc(hidden)]
@@ -68,6 +68,26 @@ pub struct RunPlugin {
     pub configuration: ::core::option::Option<PluginUserConfiguration>,
     #[prost(string, optional, tag="4")]
     pub initial_cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub sha256: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="6")]
+    pub resource_limits: ::core::option::Option<PluginResourceLimits>,
+    #[prost(string, repeated, tag="7")]
+    pub allowed_hosts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginResourceLimits {
+    #[prost(uint64, optional, tag="1")]
+    pub fuel_budget: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="2")]
+    pub max_memory_mb: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="3")]
+    pub max_table_elements: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="4")]
+    pub max_pending_jobs: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="5")]
+    pub max_timers_per_second: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -303,7 +323,7 @@ pub struct OverrideLayoutPayload {
 pub struct Action {
     #[prost(enumeration="ActionName", tag="1")]
     pub name: i32,
-    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61")]
+    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63")]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
 /// Nested message and enum types in `Action`.
@@ -425,6 +445,10 @@ pub mod action {
         AreFloatingPanesVisiblePayload(super::AreFloatingPanesVisiblePayload),
         #[prost(message, tag="61")]
         SetPaneFrameStylePayload(super::SetPaneFrameStylePayload),
+        #[prost(enumeration="super::CopyModeMotion", tag="62")]
+        CopyModeMovePayload(i32),
+        #[prost(enumeration="super::CopyModeSelection", tag="63")]
+        CopyModeSelectPayload(i32),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -824,6 +848,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag="7")]
     pub hold_on_start: bool,
+    #[prost(map="string, string", tag="8")]
+    pub env: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -980,6 +1006,7 @@ pub enum SearchOption {
     CaseSensitivity = 0,
     WholeWord = 1,
     Wrap = 2,
+    Regex = 3,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -991,6 +1018,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "CaseSensitivity",
             SearchOption::WholeWord => "WholeWord",
             SearchOption::Wrap => "Wrap",
+            SearchOption::Regex => "Regex",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -999,6 +1027,128 @@ impl SearchOption {
             "CaseSensitivity" => Some(Self::CaseSensitivity),
             "WholeWord" => Some(Self::WholeWord),
             "Wrap" => Some(Self::Wrap),
+            "Regex" => Some(Self::Regex),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeMotion {
+    Unspecified = 0,
+    Left = 1,
+    Right = 2,
+    Up = 3,
+    Down = 4,
+    WordForward = 5,
+    WordBackward = 6,
+    WordEnd = 7,
+    LineStart = 8,
+    LineEnd = 9,
+    FirstNonBlank = 10,
+    ParagraphForward = 11,
+    ParagraphBackward = 12,
+    Top = 13,
+    Bottom = 14,
+    PageUp = 15,
+    PageDown = 16,
+    HalfPageUp = 17,
+    HalfPageDown = 18,
+    PreviousPrompt = 19,
+    NextPrompt = 20,
+    NextSearchResult = 21,
+    PreviousSearchResult = 22,
+}
+impl CopyModeMotion {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeMotion::Unspecified => "COPY_MODE_MOTION_UNSPECIFIED",
+            CopyModeMotion::Left => "COPY_MODE_MOTION_LEFT",
+            CopyModeMotion::Right => "COPY_MODE_MOTION_RIGHT",
+            CopyModeMotion::Up => "COPY_MODE_MOTION_UP",
+            CopyModeMotion::Down => "COPY_MODE_MOTION_DOWN",
+            CopyModeMotion::WordForward => "COPY_MODE_MOTION_WORD_FORWARD",
+            CopyModeMotion::WordBackward => "COPY_MODE_MOTION_WORD_BACKWARD",
+            CopyModeMotion::WordEnd => "COPY_MODE_MOTION_WORD_END",
+            CopyModeMotion::LineStart => "COPY_MODE_MOTION_LINE_START",
+            CopyModeMotion::LineEnd => "COPY_MODE_MOTION_LINE_END",
+            CopyModeMotion::FirstNonBlank => "COPY_MODE_MOTION_FIRST_NON_BLANK",
+            CopyModeMotion::ParagraphForward => "COPY_MODE_MOTION_PARAGRAPH_FORWARD",
+            CopyModeMotion::ParagraphBackward => "COPY_MODE_MOTION_PARAGRAPH_BACKWARD",
+            CopyModeMotion::Top => "COPY_MODE_MOTION_TOP",
+            CopyModeMotion::Bottom => "COPY_MODE_MOTION_BOTTOM",
+            CopyModeMotion::PageUp => "COPY_MODE_MOTION_PAGE_UP",
+            CopyModeMotion::PageDown => "COPY_MODE_MOTION_PAGE_DOWN",
+            CopyModeMotion::HalfPageUp => "COPY_MODE_MOTION_HALF_PAGE_UP",
+            CopyModeMotion::HalfPageDown => "COPY_MODE_MOTION_HALF_PAGE_DOWN",
+            CopyModeMotion::PreviousPrompt => "COPY_MODE_MOTION_PREVIOUS_PROMPT",
+            CopyModeMotion::NextPrompt => "COPY_MODE_MOTION_NEXT_PROMPT",
+            CopyModeMotion::NextSearchResult => "COPY_MODE_MOTION_NEXT_SEARCH_RESULT",
+            CopyModeMotion::PreviousSearchResult => "COPY_MODE_MOTION_PREVIOUS_SEARCH_RESULT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COPY_MODE_MOTION_UNSPECIFIED" => Some(Self::Unspecified),
+            "COPY_MODE_MOTION_LEFT" => Some(Self::Left),
+            "COPY_MODE_MOTION_RIGHT" => Some(Self::Right),
+            "COPY_MODE_MOTION_UP" => Some(Self::Up),
+            "COPY_MODE_MOTION_DOWN" => Some(Self::Down),
+            "COPY_MODE_MOTION_WORD_FORWARD" => Some(Self::WordForward),
+            "COPY_MODE_MOTION_WORD_BACKWARD" => Some(Self::WordBackward),
+            "COPY_MODE_MOTION_WORD_END" => Some(Self::WordEnd),
+            "COPY_MODE_MOTION_LINE_START" => Some(Self::LineStart),
+            "COPY_MODE_MOTION_LINE_END" => Some(Self::LineEnd),
+            "COPY_MODE_MOTION_FIRST_NON_BLANK" => Some(Self::FirstNonBlank),
+            "COPY_MODE_MOTION_PARAGRAPH_FORWARD" => Some(Self::ParagraphForward),
+            "COPY_MODE_MOTION_PARAGRAPH_BACKWARD" => Some(Self::ParagraphBackward),
+            "COPY_MODE_MOTION_TOP" => Some(Self::Top),
+            "COPY_MODE_MOTION_BOTTOM" => Some(Self::Bottom),
+            "COPY_MODE_MOTION_PAGE_UP" => Some(Self::PageUp),
+            "COPY_MODE_MOTION_PAGE_DOWN" => Some(Self::PageDown),
+            "COPY_MODE_MOTION_HALF_PAGE_UP" => Some(Self::HalfPageUp),
+            "COPY_MODE_MOTION_HALF_PAGE_DOWN" => Some(Self::HalfPageDown),
+            "COPY_MODE_MOTION_PREVIOUS_PROMPT" => Some(Self::PreviousPrompt),
+            "COPY_MODE_MOTION_NEXT_PROMPT" => Some(Self::NextPrompt),
+            "COPY_MODE_MOTION_NEXT_SEARCH_RESULT" => Some(Self::NextSearchResult),
+            "COPY_MODE_MOTION_PREVIOUS_SEARCH_RESULT" => Some(Self::PreviousSearchResult),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeSelection {
+    Unspecified = 0,
+    Character = 1,
+    Line = 2,
+    Block = 3,
+}
+impl CopyModeSelection {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeSelection::Unspecified => "COPY_MODE_SELECTION_UNSPECIFIED",
+            CopyModeSelection::Character => "COPY_MODE_SELECTION_CHARACTER",
+            CopyModeSelection::Line => "COPY_MODE_SELECTION_LINE",
+            CopyModeSelection::Block => "COPY_MODE_SELECTION_BLOCK",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COPY_MODE_SELECTION_UNSPECIFIED" => Some(Self::Unspecified),
+            "COPY_MODE_SELECTION_CHARACTER" => Some(Self::Character),
+            "COPY_MODE_SELECTION_LINE" => Some(Self::Line),
+            "COPY_MODE_SELECTION_BLOCK" => Some(Self::Block),
             _ => None,
         }
     }
@@ -1143,6 +1293,9 @@ pub enum ActionName {
     ScrollToNextPrompt = 111,
     SelectCommandAtScrollPosition = 112,
     CopyLastCommandOutput = 113,
+    CopyModeMove = 114,
+    CopyModeSelect = 115,
+    CopyModeYank = 116,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1262,6 +1415,9 @@ impl ActionName {
             ActionName::ScrollToNextPrompt => "ScrollToNextPrompt",
             ActionName::SelectCommandAtScrollPosition => "SelectCommandAtScrollPosition",
             ActionName::CopyLastCommandOutput => "CopyLastCommandOutput",
+            ActionName::CopyModeMove => "CopyModeMove",
+            ActionName::CopyModeSelect => "CopyModeSelect",
+            ActionName::CopyModeYank => "CopyModeYank",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1378,6 +1534,9 @@ impl ActionName {
             "ScrollToNextPrompt" => Some(Self::ScrollToNextPrompt),
             "SelectCommandAtScrollPosition" => Some(Self::SelectCommandAtScrollPosition),
             "CopyLastCommandOutput" => Some(Self::CopyLastCommandOutput),
+            "CopyModeMove" => Some(Self::CopyModeMove),
+            "CopyModeSelect" => Some(Self::CopyModeSelect),
+            "CopyModeYank" => Some(Self::CopyModeYank),
             _ => None,
         }
     }
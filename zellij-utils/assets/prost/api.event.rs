@@ -10,7 +10,7 @@ pub struct EventNameList {
 pub struct Event {
     #[prost(enumeration="EventType", tag="1")]
     pub name: i32,
-    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 38, 39, 40, 41, 42, 43")]
+    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47")]
     pub payload: ::core::option::Option<event::Payload>,
 }
 /// Nested message and enum types in `Event`.
@@ -100,6 +100,14 @@ pub mod event {
         HintTextPayload(super::HintTextPayload),
         #[prost(message, tag="43")]
         ActivePaneScrollPayload(super::ActivePaneScrollPayload),
+        #[prost(message, tag="44")]
+        PaneMonitorAlertPayload(super::PaneMonitorAlertPayload),
+        #[prost(message, tag="45")]
+        PluginRequestResultPayload(super::PluginRequestResultPayload),
+        #[prost(message, tag="46")]
+        StorageValueChangedPayload(super::StorageValueChangedPayload),
+        #[prost(message, tag="47")]
+        CommandPaneRestartedPayload(super::CommandPaneRestartedPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -118,6 +126,15 @@ pub struct ActivePaneScrollPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneMonitorAlertPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    /// set for silence alerts, activity alerts leave it empty
+    #[prost(uint64, optional, tag="2")]
+    pub silence_secs: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StyledText {
     #[prost(string, tag="1")]
     pub text: ::prost::alloc::string::String,
@@ -321,6 +338,45 @@ pub struct WebRequestResultPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandPaneRestartedPayload {
+    #[prost(uint32, tag="1")]
+    pub terminal_pane_id: u32,
+    /// the exit status of the run that was restarted
+    #[prost(int32, optional, tag="2")]
+    pub exit_code: ::core::option::Option<i32>,
+    #[prost(uint32, tag="3")]
+    pub restart_count: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StorageValueChangedPayload {
+    #[prost(enumeration="StorageScope", tag="1")]
+    pub scope: i32,
+    #[prost(string, tag="2")]
+    pub key: ::prost::alloc::string::String,
+    /// not set if the value was deleted
+    #[prost(string, optional, tag="3")]
+    pub value: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginRequestResultPayload {
+    #[prost(message, optional, tag="1")]
+    pub reply: ::core::option::Option<PluginReply>,
+    #[prost(message, repeated, tag="2")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginReply {
+    #[prost(enumeration="PluginReplyStatus", tag="1")]
+    pub status: i32,
+    /// the reply payload when replied, the error message when failed
+    #[prost(string, optional, tag="2")]
+    pub payload: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ContextItem {
     #[prost(string, tag="1")]
     pub name: ::prost::alloc::string::String,
@@ -464,6 +520,8 @@ pub struct PluginInfo {
     pub plugin_url: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="3")]
     pub plugin_config: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(string, optional, tag="4")]
+    pub crash_reason: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -536,6 +594,8 @@ pub struct LayoutMetadata {
     pub creation_time: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub update_time: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="4")]
+    pub parameters: ::prost::alloc::vec::Vec<ContextItem>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -666,6 +726,22 @@ pub struct TabInfo {
     pub has_bell_notification: bool,
     #[prost(bool, tag="19")]
     pub is_flashing_bell: bool,
+    #[prost(message, repeated, tag="20")]
+    pub recently_closed_panes: ::prost::alloc::vec::Vec<ClosedPaneInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClosedPaneInfo {
+    #[prost(string, tag="1")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="2")]
+    pub command: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag="4")]
+    pub is_floating: bool,
+    #[prost(bool, tag="5")]
+    pub is_stacked: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -896,6 +972,13 @@ pub enum EventType {
     SoftKeyboardVisibilityChanged = 47,
     HintText = 48,
     ActivePaneScroll = 49,
+    PaneMonitorAlert = 50,
+    /// / A plugin replied to a request sent with SendPluginRequest, or the request timed out
+    PluginRequestResult = 51,
+    /// / A value in the storage of this plugin was set or deleted by another instance of it
+    StorageValueChanged = 52,
+    /// / A command pane was restarted by its restart policy
+    CommandPaneRestarted = 53,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -952,6 +1035,10 @@ impl EventType {
             EventType::SoftKeyboardVisibilityChanged => "SoftKeyboardVisibilityChanged",
             EventType::HintText => "HintText",
             EventType::ActivePaneScroll => "ActivePaneScroll",
+            EventType::PaneMonitorAlert => "PaneMonitorAlert",
+            EventType::PluginRequestResult => "PluginRequestResult",
+            EventType::StorageValueChanged => "StorageValueChanged",
+            EventType::CommandPaneRestarted => "CommandPaneRestarted",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1005,6 +1092,10 @@ impl EventType {
             "SoftKeyboardVisibilityChanged" => Some(Self::SoftKeyboardVisibilityChanged),
             "HintText" => Some(Self::HintText),
             "ActivePaneScroll" => Some(Self::ActivePaneScroll),
+            "PaneMonitorAlert" => Some(Self::PaneMonitorAlert),
+            "PluginRequestResult" => Some(Self::PluginRequestResult),
+            "StorageValueChanged" => Some(Self::StorageValueChanged),
+            "CommandPaneRestarted" => Some(Self::CommandPaneRestarted),
             _ => None,
         }
     }
@@ -1093,6 +1184,61 @@ impl PaneType {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum StorageScope {
+    PluginStorage = 0,
+    SessionStorage = 1,
+}
+impl StorageScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            StorageScope::PluginStorage => "PluginStorage",
+            StorageScope::SessionStorage => "SessionStorage",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PluginStorage" => Some(Self::PluginStorage),
+            "SessionStorage" => Some(Self::SessionStorage),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PluginReplyStatus {
+    Replied = 0,
+    Failed = 1,
+    TimedOut = 2,
+}
+impl PluginReplyStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PluginReplyStatus::Replied => "Replied",
+            PluginReplyStatus::Failed => "Failed",
+            PluginReplyStatus::TimedOut => "TimedOut",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Replied" => Some(Self::Replied),
+            "Failed" => Some(Self::Failed),
+            "TimedOut" => Some(Self::TimedOut),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum CopyDestination {
     Command = 0,
     Primary = 1,
@@ -38,6 +38,9 @@ pub enum InputMode {
     Prompt = 12,
     /// / `Tmux` mode allows for basic tmux keybindings functionality
     Tmux = 13,
+    /// / `Copy` mode allows moving a cursor over the scrollback of a pane and selecting text with
+    /// / the keyboard.
+    Copy = 14,
 }
 impl InputMode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -60,6 +63,7 @@ impl InputMode {
             InputMode::Move => "Move",
             InputMode::Prompt => "Prompt",
             InputMode::Tmux => "Tmux",
+            InputMode::Copy => "Copy",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -79,6 +83,7 @@ impl InputMode {
             "Move" => Some(Self::Move),
             "Prompt" => Some(Self::Prompt),
             "Tmux" => Some(Self::Tmux),
+            "Copy" => Some(Self::Copy),
             _ => None,
         }
     }
@@ -4,7 +4,7 @@
 pub struct PluginCommand {
     #[prost(enumeration="CommandName", tag="1")]
     pub name: i32,
-    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162, 163, 164, 168, 169, 170, 171")]
+    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162, 163, 164, 168, 169, 170, 171, 172, 173, 174, 175, 176, 177, 178, 179, 180, 181, 182, 183, 184")]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
 /// Nested message and enum types in `PluginCommand`.
@@ -320,6 +320,32 @@ pub mod plugin_command {
         SetPaneFrameStylePayload(super::SetPaneFrameStylePayload),
         #[prost(message, tag="171")]
         ToggleFloatingPanesPayload(super::ToggleFloatingPanesPayload),
+        #[prost(message, tag="172")]
+        StartRecordingPayload(super::StartRecordingPayload),
+        #[prost(message, tag="173")]
+        StopRecordingPayload(super::StopRecordingPayload),
+        #[prost(message, tag="174")]
+        SetPaneMonitorsPayload(super::SetPaneMonitorsPayload),
+        #[prost(message, tag="175")]
+        SendPluginRequestPayload(super::SendPluginRequestPayload),
+        #[prost(message, tag="176")]
+        ReplyToPluginRequestPayload(super::ReplyToPluginRequestPayload),
+        #[prost(message, tag="177")]
+        GetStorageValuePayload(super::StorageKeyPayload),
+        #[prost(message, tag="178")]
+        SetStorageValuePayload(super::SetStorageValuePayload),
+        #[prost(message, tag="179")]
+        DeleteStorageValuePayload(super::StorageKeyPayload),
+        #[prost(message, tag="180")]
+        ListStorageValuesPayload(super::ListStorageValuesPayload),
+        #[prost(message, tag="181")]
+        RequestPluginPermissionsWithFilesystemAccessPayload(super::RequestPluginPermissionPayload),
+        #[prost(message, tag="182")]
+        RevokePluginPermissionsPayload(super::RevokePluginPermissionsPayload),
+        #[prost(message, tag="183")]
+        NewTabsWithLayoutAndArgsPayload(super::NewTabsWithLayoutAndArgsPayload),
+        #[prost(message, tag="184")]
+        OverrideLayoutWithArgsPayload(super::OverrideLayoutPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -336,6 +362,53 @@ pub struct ToggleFloatingPanesPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartRecordingPayload {
+    #[prost(string, optional, tag="1")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="2")]
+    pub target: ::core::option::Option<RecordingTarget>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopRecordingPayload {
+    #[prost(string, optional, tag="1")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPaneMonitorsPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(bool, tag="2")]
+    pub monitor_activity: bool,
+    #[prost(uint64, optional, tag="3")]
+    pub monitor_silence_secs: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecordingTarget {
+    #[prost(oneof="recording_target::TargetType", tags="1, 2, 3, 4, 5")]
+    pub target_type: ::core::option::Option<recording_target::TargetType>,
+}
+/// Nested message and enum types in `RecordingTarget`.
+pub mod recording_target {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum TargetType {
+        #[prost(bool, tag="1")]
+        FocusedPane(bool),
+        #[prost(message, tag="2")]
+        PaneId(super::PaneId),
+        #[prost(bool, tag="3")]
+        FocusedTab(bool),
+        #[prost(uint32, tag="4")]
+        TabId(u32),
+        #[prost(bool, tag="5")]
+        Client(bool),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewTabPayload {
     #[prost(string, optional, tag="1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -401,6 +474,10 @@ pub struct GenerateWebLoginTokenPayload {
     pub token_label: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, optional, tag="2")]
     pub read_only: ::core::option::Option<bool>,
+    #[prost(uint64, optional, tag="3")]
+    pub expires_in_secs: ::core::option::Option<u64>,
+    #[prost(string, optional, tag="4")]
+    pub session_scope: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -782,6 +859,14 @@ pub struct NewTabsWithLayoutInfoPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NewTabsWithLayoutAndArgsPayload {
+    #[prost(message, optional, tag="1")]
+    pub layout_info: ::core::option::Option<super::event::LayoutInfo>,
+    #[prost(message, repeated, tag="2")]
+    pub layout_args: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct KillSessionsPayload {
     #[prost(string, repeated, tag="1")]
     pub session_names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
@@ -816,6 +901,24 @@ pub struct MessageToPluginPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPluginRequestPayload {
+    #[prost(message, optional, tag="1")]
+    pub message: ::core::option::Option<MessageToPluginPayload>,
+    #[prost(uint64, optional, tag="2")]
+    pub timeout_ms: ::core::option::Option<u64>,
+    #[prost(message, repeated, tag="3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplyToPluginRequestPayload {
+    #[prost(uint64, tag="1")]
+    pub request_id: u64,
+    #[prost(message, optional, tag="2")]
+    pub reply: ::core::option::Option<super::event::PluginReply>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewPluginArgs {
     #[prost(bool, optional, tag="1")]
     pub should_float: ::core::option::Option<bool>,
@@ -859,6 +962,8 @@ pub struct SwitchSessionPayload {
 pub struct RequestPluginPermissionPayload {
     #[prost(enumeration="super::plugin_permission::PermissionType", repeated, tag="1")]
     pub permissions: ::prost::alloc::vec::Vec<i32>,
+    #[prost(message, repeated, tag="2")]
+    pub filesystem_permissions: ::prost::alloc::vec::Vec<super::plugin_permission::FilesystemPermission>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1085,7 +1190,8 @@ pub struct RevokeTokenResponse {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListTokensResponse {
-    /// tokens/creation_times/read_only_flags should be synchronized
+    /// tokens/creation_times/read_only_flags/expiration_times/session_scopes/last_used_times should
+    /// be synchronized, an empty string in the latter three means "not set"
     #[prost(string, repeated, tag="1")]
     pub tokens: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag="2")]
@@ -1094,6 +1200,12 @@ pub struct ListTokensResponse {
     pub read_only_flags: ::prost::alloc::vec::Vec<bool>,
     #[prost(string, optional, tag="4")]
     pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="5")]
+    pub expiration_times: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="6")]
+    pub session_scopes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="7")]
+    pub last_used_times: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1199,6 +1311,62 @@ pub struct GetPaneCwdPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StorageKeyPayload {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(enumeration="super::event::StorageScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetStorageValuePayload {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(enumeration="super::event::StorageScope", tag="3")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListStorageValuesPayload {
+    #[prost(string, tag="1")]
+    pub prefix: ::prost::alloc::string::String,
+    #[prost(enumeration="super::event::StorageScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StorageValueResponse {
+    #[prost(string, optional, tag="1")]
+    pub value: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListStorageValuesResponse {
+    #[prost(message, repeated, tag="1")]
+    pub values: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokePluginPermissionsPayload {
+    #[prost(string, tag="1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(enumeration="super::plugin_permission::PermissionType", repeated, tag="2")]
+    pub permissions: ::prost::alloc::vec::Vec<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListGrantedPermissionsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub granted: ::prost::alloc::vec::Vec<super::plugin_permission::GrantedPluginPermissions>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetPaneCwdResponse {
     #[prost(oneof="get_pane_cwd_response::Result", tags="1, 2")]
     pub result: ::core::option::Option<get_pane_cwd_response::Result>,
@@ -1284,6 +1452,8 @@ pub struct OverrideLayoutPayload {
     pub retain_existing_plugin_panes: bool,
     #[prost(bool, tag="5")]
     pub apply_only_to_active_tab: bool,
+    #[prost(message, repeated, tag="6")]
+    pub layout_args: ::prost::alloc::vec::Vec<ContextItem>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2227,6 +2397,21 @@ pub enum CommandName {
     NewPane = 226,
     ToggleFocusNoUiFullscreen = 227,
     FocusHostSession = 228,
+    StartRecording = 229,
+    StopRecording = 230,
+    SetPaneMonitors = 231,
+    ReopenClosedPane = 232,
+    SendPluginRequest = 233,
+    ReplyToPluginRequest = 234,
+    GetStorageValue = 235,
+    SetStorageValue = 236,
+    DeleteStorageValue = 237,
+    ListStorageValues = 238,
+    RequestPluginPermissionsWithFilesystemAccess = 239,
+    ListGrantedPermissions = 240,
+    RevokePluginPermissions = 241,
+    NewTabsWithLayoutAndArgs = 242,
+    OverrideLayoutWithArgs = 243,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2438,6 +2623,21 @@ impl CommandName {
             CommandName::NewPane => "NewPane",
             CommandName::ToggleFocusNoUiFullscreen => "ToggleFocusNoUiFullscreen",
             CommandName::FocusHostSession => "FocusHostSession",
+            CommandName::StartRecording => "StartRecording",
+            CommandName::StopRecording => "StopRecording",
+            CommandName::SetPaneMonitors => "SetPaneMonitors",
+            CommandName::ReopenClosedPane => "ReopenClosedPane",
+            CommandName::SendPluginRequest => "SendPluginRequest",
+            CommandName::ReplyToPluginRequest => "ReplyToPluginRequest",
+            CommandName::GetStorageValue => "GetStorageValue",
+            CommandName::SetStorageValue => "SetStorageValue",
+            CommandName::DeleteStorageValue => "DeleteStorageValue",
+            CommandName::ListStorageValues => "ListStorageValues",
+            CommandName::RequestPluginPermissionsWithFilesystemAccess => "RequestPluginPermissionsWithFilesystemAccess",
+            CommandName::ListGrantedPermissions => "ListGrantedPermissions",
+            CommandName::RevokePluginPermissions => "RevokePluginPermissions",
+            CommandName::NewTabsWithLayoutAndArgs => "NewTabsWithLayoutAndArgs",
+            CommandName::OverrideLayoutWithArgs => "OverrideLayoutWithArgs",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2646,6 +2846,21 @@ impl CommandName {
             "NewPane" => Some(Self::NewPane),
             "ToggleFocusNoUiFullscreen" => Some(Self::ToggleFocusNoUiFullscreen),
             "FocusHostSession" => Some(Self::FocusHostSession),
+            "StartRecording" => Some(Self::StartRecording),
+            "StopRecording" => Some(Self::StopRecording),
+            "SetPaneMonitors" => Some(Self::SetPaneMonitors),
+            "ReopenClosedPane" => Some(Self::ReopenClosedPane),
+            "SendPluginRequest" => Some(Self::SendPluginRequest),
+            "ReplyToPluginRequest" => Some(Self::ReplyToPluginRequest),
+            "GetStorageValue" => Some(Self::GetStorageValue),
+            "SetStorageValue" => Some(Self::SetStorageValue),
+            "DeleteStorageValue" => Some(Self::DeleteStorageValue),
+            "ListStorageValues" => Some(Self::ListStorageValues),
+            "RequestPluginPermissionsWithFilesystemAccess" => Some(Self::RequestPluginPermissionsWithFilesystemAccess),
+            "ListGrantedPermissions" => Some(Self::ListGrantedPermissions),
+            "RevokePluginPermissions" => Some(Self::RevokePluginPermissions),
+            "NewTabsWithLayoutAndArgs" => Some(Self::NewTabsWithLayoutAndArgs),
+            "OverrideLayoutWithArgs" => Some(Self::OverrideLayoutWithArgs),
             _ => None,
         }
     }
@@ -1,4 +1,22 @@
 // This file is @generated by prost-build.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilesystemPermission {
+    #[prost(string, tag="1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub read_only: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GrantedPluginPermissions {
+    #[prost(string, tag="1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(enumeration="PermissionType", repeated, tag="2")]
+    pub permissions: ::prost::alloc::vec::Vec<i32>,
+    #[prost(message, repeated, tag="3")]
+    pub filesystem_permissions: ::prost::alloc::vec::Vec<FilesystemPermission>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum PermissionType {
@@ -120,7 +120,7 @@ pub struct RgbColor {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Action {
-    #[prost(oneof="action::ActionType", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151")]
+    #[prost(oneof="action::ActionType", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162")]
     pub action_type: ::core::option::Option<action::ActionType>,
 }
 /// Nested message and enum types in `Action`.
@@ -430,6 +430,28 @@ pub mod action {
         SelectCommandAtScrollPosition(super::SelectCommandAtScrollPositionAction),
         #[prost(message, tag="151")]
         CopyLastCommandOutput(super::CopyLastCommandOutputAction),
+        #[prost(message, tag="152")]
+        CopyModeMove(super::CopyModeMoveAction),
+        #[prost(message, tag="153")]
+        CopyModeSelect(super::CopyModeSelectAction),
+        #[prost(message, tag="154")]
+        CopyModeYank(super::CopyModeYankAction),
+        #[prost(message, tag="155")]
+        StartRecording(super::StartRecordingAction),
+        #[prost(message, tag="156")]
+        StopRecording(super::StopRecordingAction),
+        #[prost(message, tag="157")]
+        StartPaneLog(super::StartPaneLogAction),
+        #[prost(message, tag="158")]
+        StopPaneLog(super::StopPaneLogAction),
+        #[prost(message, tag="159")]
+        MonitorPane(super::MonitorPaneAction),
+        #[prost(message, tag="160")]
+        ReopenClosedPane(super::ReopenClosedPaneAction),
+        #[prost(message, tag="161")]
+        TogglePaneGroupSync(super::TogglePaneGroupSyncAction),
+        #[prost(message, tag="162")]
+        SyncBroadcastGroup(super::SyncBroadcastGroupAction),
     }
 }
 // Action message definitions (all 92 variants)
@@ -523,6 +545,10 @@ pub struct CopyLastCommandOutputAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CopyModeYankAction {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PageScrollUpAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -850,6 +876,79 @@ pub struct DumpScreenAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartRecordingAction {
+    #[prost(string, optional, tag="1")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="2")]
+    pub target: ::core::option::Option<RecordingTarget>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopRecordingAction {
+    #[prost(string, optional, tag="1")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartPaneLogAction {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag="2")]
+    pub log_output: ::core::option::Option<LogOutput>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopPaneLogAction {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MonitorPaneAction {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag="2")]
+    pub monitors: ::core::option::Option<PaneMonitors>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReopenClosedPaneAction {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TogglePaneGroupSyncAction {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncBroadcastGroupAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecordingTarget {
+    #[prost(oneof="recording_target::TargetType", tags="1, 2, 3, 4, 5")]
+    pub target_type: ::core::option::Option<recording_target::TargetType>,
+}
+/// Nested message and enum types in `RecordingTarget`.
+pub mod recording_target {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum TargetType {
+        #[prost(bool, tag="1")]
+        FocusedPane(bool),
+        #[prost(message, tag="2")]
+        PaneId(super::PaneId),
+        #[prost(bool, tag="3")]
+        FocusedTab(bool),
+        #[prost(uint32, tag="4")]
+        TabId(u32),
+        #[prost(bool, tag="5")]
+        Client(bool),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScrollUpAtAction {
     #[prost(message, optional, tag="1")]
     pub position: ::core::option::Option<Position>,
@@ -1114,6 +1213,18 @@ pub struct SearchToggleOptionAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CopyModeMoveAction {
+    #[prost(enumeration="CopyModeMotion", tag="1")]
+    pub motion: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CopyModeSelectAction {
+    #[prost(enumeration="CopyModeSelection", tag="1")]
+    pub selection: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewTiledPluginPaneAction {
     #[prost(message, optional, tag="1")]
     pub plugin: ::core::option::Option<RunPluginOrAlias>,
@@ -1381,6 +1492,8 @@ pub struct CliAssets {
     pub cwd: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(map="string, string", tag="12")]
     pub host_terminal_env: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(map="string, string", tag="13")]
+    pub layout_args: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1414,6 +1527,8 @@ pub struct LayoutMetadata {
     pub creation_time: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub update_time: ::prost::alloc::string::String,
+    #[prost(map="string, string", tag="4")]
+    pub parameters: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1602,6 +1717,12 @@ pub struct RunCommandAction {
     /// Added missing use_terminal_title field
     #[prost(bool, tag="8")]
     pub use_terminal_title: bool,
+    #[prost(map="string, string", tag="9")]
+    pub env: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    #[prost(enumeration="RestartPolicy", tag="10")]
+    pub restart: i32,
+    #[prost(uint32, optional, tag="11")]
+    pub max_restarts: ::core::option::Option<u32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1634,9 +1755,19 @@ pub struct TiledPaneLayout {
     pub pane_initial_contents: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag="14")]
     pub default_fg: ::core::option::Option<::prost::alloc::string::String>,
-    /// NOTE: run_instructions_to_ignore is not represented here because it's a field used only inside the server itself and not part of the server/client contract
     #[prost(string, optional, tag="15")]
     pub default_bg: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bytes="vec", optional, tag="16")]
+    pub pane_scrollback_snapshot: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="17")]
+    pub log_output: ::core::option::Option<LogOutput>,
+    #[prost(message, optional, tag="18")]
+    pub monitors: ::core::option::Option<PaneMonitors>,
+    #[prost(string, optional, tag="19")]
+    pub broadcast_group: ::core::option::Option<::prost::alloc::string::String>,
+    /// NOTE: run_instructions_to_ignore is not represented here because it's a field used only inside the server itself and not part of the server/client contract
+    #[prost(message, repeated, tag="20")]
+    pub depends_on: ::prost::alloc::vec::Vec<PaneDependency>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1669,6 +1800,61 @@ pub struct FloatingPaneLayout {
     pub default_fg: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag="14")]
     pub default_bg: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bytes="vec", optional, tag="15")]
+    pub pane_scrollback_snapshot: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag="16")]
+    pub log_output: ::core::option::Option<LogOutput>,
+    #[prost(message, optional, tag="17")]
+    pub monitors: ::core::option::Option<PaneMonitors>,
+    #[prost(string, optional, tag="18")]
+    pub broadcast_group: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="19")]
+    pub depends_on: ::prost::alloc::vec::Vec<PaneDependency>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneDependency {
+    #[prost(string, tag="1")]
+    pub pane_name: ::prost::alloc::string::String,
+    /// none of these means waiting for the pane to exit successfully
+    #[prost(oneof="pane_dependency::Readiness", tags="2, 3, 4")]
+    pub readiness: ::core::option::Option<pane_dependency::Readiness>,
+}
+/// Nested message and enum types in `PaneDependency`.
+pub mod pane_dependency {
+    /// none of these means waiting for the pane to exit successfully
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Readiness {
+        #[prost(string, tag="2")]
+        OutputMatches(::prost::alloc::string::String),
+        #[prost(uint32, tag="3")]
+        PortOpen(u32),
+        #[prost(string, tag="4")]
+        FileExists(::prost::alloc::string::String),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneMonitors {
+    #[prost(bool, tag="1")]
+    pub activity: bool,
+    #[prost(uint64, optional, tag="2")]
+    pub silence_secs: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LogOutput {
+    #[prost(string, tag="1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub strip_ansi: bool,
+    #[prost(bool, tag="3")]
+    pub timestamps: bool,
+    #[prost(uint64, optional, tag="4")]
+    pub max_size: ::core::option::Option<u64>,
+    #[prost(uint32, optional, tag="5")]
+    pub max_files: ::core::option::Option<u32>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1730,6 +1916,26 @@ pub struct RunPlugin {
     pub configuration: ::core::option::Option<PluginUserConfiguration>,
     #[prost(string, optional, tag="4")]
     pub initial_cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub sha256: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="6")]
+    pub resource_limits: ::core::option::Option<PluginResourceLimits>,
+    #[prost(string, repeated, tag="7")]
+    pub allowed_hosts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginResourceLimits {
+    #[prost(uint64, optional, tag="1")]
+    pub fuel_budget: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="2")]
+    pub max_memory_mb: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="3")]
+    pub max_table_elements: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="4")]
+    pub max_pending_jobs: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag="5")]
+    pub max_timers_per_second: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2062,6 +2268,8 @@ pub struct Options {
     pub mouse_hover_tips: ::core::option::Option<bool>,
     #[prost(string, optional, tag="67")]
     pub host_notification_protocol: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag="68")]
+    pub serialize_pane_scrollback: ::core::option::Option<bool>,
 }
 /// Pane-targeting action messages
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2484,6 +2692,7 @@ pub enum InputMode {
     Move = 12,
     Prompt = 13,
     Tmux = 14,
+    Copy = 15,
 }
 impl InputMode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2507,6 +2716,7 @@ impl InputMode {
             InputMode::Move => "INPUT_MODE_MOVE",
             InputMode::Prompt => "INPUT_MODE_PROMPT",
             InputMode::Tmux => "INPUT_MODE_TMUX",
+            InputMode::Copy => "INPUT_MODE_COPY",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2527,6 +2737,7 @@ impl InputMode {
             "INPUT_MODE_MOVE" => Some(Self::Move),
             "INPUT_MODE_PROMPT" => Some(Self::Prompt),
             "INPUT_MODE_TMUX" => Some(Self::Tmux),
+            "INPUT_MODE_COPY" => Some(Self::Copy),
             _ => None,
         }
     }
@@ -2600,6 +2811,38 @@ impl UnblockCondition {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum RestartPolicy {
+    Unspecified = 0,
+    Never = 1,
+    OnFailure = 2,
+    Always = 3,
+}
+impl RestartPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RestartPolicy::Unspecified => "RESTART_POLICY_UNSPECIFIED",
+            RestartPolicy::Never => "RESTART_POLICY_NEVER",
+            RestartPolicy::OnFailure => "RESTART_POLICY_ON_FAILURE",
+            RestartPolicy::Always => "RESTART_POLICY_ALWAYS",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESTART_POLICY_UNSPECIFIED" => Some(Self::Unspecified),
+            "RESTART_POLICY_NEVER" => Some(Self::Never),
+            "RESTART_POLICY_ON_FAILURE" => Some(Self::OnFailure),
+            "RESTART_POLICY_ALWAYS" => Some(Self::Always),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ResizeType {
     Unspecified = 0,
     Increase = 1,
@@ -2753,6 +2996,7 @@ pub enum SearchOption {
     WholeWord = 2,
     /// Unchanged - already matches
     Wrap = 3,
+    Regex = 4,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2765,6 +3009,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "SEARCH_OPTION_CASE_SENSITIVITY",
             SearchOption::WholeWord => "SEARCH_OPTION_WHOLE_WORD",
             SearchOption::Wrap => "SEARCH_OPTION_WRAP",
+            SearchOption::Regex => "SEARCH_OPTION_REGEX",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2774,6 +3019,128 @@ impl SearchOption {
             "SEARCH_OPTION_CASE_SENSITIVITY" => Some(Self::CaseSensitivity),
             "SEARCH_OPTION_WHOLE_WORD" => Some(Self::WholeWord),
             "SEARCH_OPTION_WRAP" => Some(Self::Wrap),
+            "SEARCH_OPTION_REGEX" => Some(Self::Regex),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeMotion {
+    Unspecified = 0,
+    Left = 1,
+    Right = 2,
+    Up = 3,
+    Down = 4,
+    WordForward = 5,
+    WordBackward = 6,
+    WordEnd = 7,
+    LineStart = 8,
+    LineEnd = 9,
+    FirstNonBlank = 10,
+    ParagraphForward = 11,
+    ParagraphBackward = 12,
+    Top = 13,
+    Bottom = 14,
+    PageUp = 15,
+    PageDown = 16,
+    HalfPageUp = 17,
+    HalfPageDown = 18,
+    PreviousPrompt = 19,
+    NextPrompt = 20,
+    NextSearchResult = 21,
+    PreviousSearchResult = 22,
+}
+impl CopyModeMotion {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeMotion::Unspecified => "COPY_MODE_MOTION_UNSPECIFIED",
+            CopyModeMotion::Left => "COPY_MODE_MOTION_LEFT",
+            CopyModeMotion::Right => "COPY_MODE_MOTION_RIGHT",
+            CopyModeMotion::Up => "COPY_MODE_MOTION_UP",
+            CopyModeMotion::Down => "COPY_MODE_MOTION_DOWN",
+            CopyModeMotion::WordForward => "COPY_MODE_MOTION_WORD_FORWARD",
+            CopyModeMotion::WordBackward => "COPY_MODE_MOTION_WORD_BACKWARD",
+            CopyModeMotion::WordEnd => "COPY_MODE_MOTION_WORD_END",
+            CopyModeMotion::LineStart => "COPY_MODE_MOTION_LINE_START",
+            CopyModeMotion::LineEnd => "COPY_MODE_MOTION_LINE_END",
+            CopyModeMotion::FirstNonBlank => "COPY_MODE_MOTION_FIRST_NON_BLANK",
+            CopyModeMotion::ParagraphForward => "COPY_MODE_MOTION_PARAGRAPH_FORWARD",
+            CopyModeMotion::ParagraphBackward => "COPY_MODE_MOTION_PARAGRAPH_BACKWARD",
+            CopyModeMotion::Top => "COPY_MODE_MOTION_TOP",
+            CopyModeMotion::Bottom => "COPY_MODE_MOTION_BOTTOM",
+            CopyModeMotion::PageUp => "COPY_MODE_MOTION_PAGE_UP",
+            CopyModeMotion::PageDown => "COPY_MODE_MOTION_PAGE_DOWN",
+            CopyModeMotion::HalfPageUp => "COPY_MODE_MOTION_HALF_PAGE_UP",
+            CopyModeMotion::HalfPageDown => "COPY_MODE_MOTION_HALF_PAGE_DOWN",
+            CopyModeMotion::PreviousPrompt => "COPY_MODE_MOTION_PREVIOUS_PROMPT",
+            CopyModeMotion::NextPrompt => "COPY_MODE_MOTION_NEXT_PROMPT",
+            CopyModeMotion::NextSearchResult => "COPY_MODE_MOTION_NEXT_SEARCH_RESULT",
+            CopyModeMotion::PreviousSearchResult => "COPY_MODE_MOTION_PREVIOUS_SEARCH_RESULT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COPY_MODE_MOTION_UNSPECIFIED" => Some(Self::Unspecified),
+            "COPY_MODE_MOTION_LEFT" => Some(Self::Left),
+            "COPY_MODE_MOTION_RIGHT" => Some(Self::Right),
+            "COPY_MODE_MOTION_UP" => Some(Self::Up),
+            "COPY_MODE_MOTION_DOWN" => Some(Self::Down),
+            "COPY_MODE_MOTION_WORD_FORWARD" => Some(Self::WordForward),
+            "COPY_MODE_MOTION_WORD_BACKWARD" => Some(Self::WordBackward),
+            "COPY_MODE_MOTION_WORD_END" => Some(Self::WordEnd),
+            "COPY_MODE_MOTION_LINE_START" => Some(Self::LineStart),
+            "COPY_MODE_MOTION_LINE_END" => Some(Self::LineEnd),
+            "COPY_MODE_MOTION_FIRST_NON_BLANK" => Some(Self::FirstNonBlank),
+            "COPY_MODE_MOTION_PARAGRAPH_FORWARD" => Some(Self::ParagraphForward),
+            "COPY_MODE_MOTION_PARAGRAPH_BACKWARD" => Some(Self::ParagraphBackward),
+            "COPY_MODE_MOTION_TOP" => Some(Self::Top),
+            "COPY_MODE_MOTION_BOTTOM" => Some(Self::Bottom),
+            "COPY_MODE_MOTION_PAGE_UP" => Some(Self::PageUp),
+            "COPY_MODE_MOTION_PAGE_DOWN" => Some(Self::PageDown),
+            "COPY_MODE_MOTION_HALF_PAGE_UP" => Some(Self::HalfPageUp),
+            "COPY_MODE_MOTION_HALF_PAGE_DOWN" => Some(Self::HalfPageDown),
+            "COPY_MODE_MOTION_PREVIOUS_PROMPT" => Some(Self::PreviousPrompt),
+            "COPY_MODE_MOTION_NEXT_PROMPT" => Some(Self::NextPrompt),
+            "COPY_MODE_MOTION_NEXT_SEARCH_RESULT" => Some(Self::NextSearchResult),
+            "COPY_MODE_MOTION_PREVIOUS_SEARCH_RESULT" => Some(Self::PreviousSearchResult),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CopyModeSelection {
+    Unspecified = 0,
+    Character = 1,
+    Line = 2,
+    Block = 3,
+}
+impl CopyModeSelection {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CopyModeSelection::Unspecified => "COPY_MODE_SELECTION_UNSPECIFIED",
+            CopyModeSelection::Character => "COPY_MODE_SELECTION_CHARACTER",
+            CopyModeSelection::Line => "COPY_MODE_SELECTION_LINE",
+            CopyModeSelection::Block => "COPY_MODE_SELECTION_BLOCK",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COPY_MODE_SELECTION_UNSPECIFIED" => Some(Self::Unspecified),
+            "COPY_MODE_SELECTION_CHARACTER" => Some(Self::Character),
+            "COPY_MODE_SELECTION_LINE" => Some(Self::Line),
+            "COPY_MODE_SELECTION_BLOCK" => Some(Self::Block),
             _ => None,
         }
     }
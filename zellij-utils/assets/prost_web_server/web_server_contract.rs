@@ -2,7 +2,7 @@
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct InstructionForWebServer {
-    #[prost(oneof="instruction_for_web_server::Instruction", tags="1, 2")]
+    #[prost(oneof="instruction_for_web_server::Instruction", tags="1, 2, 3")]
     pub instruction: ::core::option::Option<instruction_for_web_server::Instruction>,
 }
 /// Nested message and enum types in `InstructionForWebServer`.
@@ -12,11 +12,13 @@ pub mod instruction_for_web_server {
     pub enum Instruction {
         #[prost(message, tag="1")]
         ShutdownWebServer(super::ShutdownWebServerMsg),
-        /// Future commands can be added here
-        /// RestartWebServerMsg restart_web_server = 3;
-        /// ReloadConfigMsg reload_config = 4;
         #[prost(message, tag="2")]
         QueryVersion(super::QueryVersionMsg),
+        /// Future commands can be added here
+        /// RestartWebServerMsg restart_web_server = 4;
+        /// ReloadConfigMsg reload_config = 5;
+        #[prost(message, tag="3")]
+        LoginTokensRevoked(super::LoginTokensRevokedMsg),
     }
 }
 /// Empty for now, but allows for future parameters like graceful timeout
@@ -30,6 +32,10 @@ pub struct QueryVersionMsg {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoginTokensRevokedMsg {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WebServerResponse {
     #[prost(oneof="web_server_response::Response", tags="1")]
     pub response: ::core::option::Option<web_server_response::Response>,
@@ -0,0 +1,251 @@
+//! Reading and writing of [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) files.
+//!
+//! These are used by the server to record sessions and by `zellij replay` to play them back.
+//! Each file is made of a JSON header line followed by one JSON line per event, in the form of
+//! `[time, code, data]`.
+
+use crate::errors::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const ASCIICAST_VERSION: u8 = 2;
+pub const ASCIICAST_FILE_EXTENSION: &str = "cast";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsciicastHeader {
+    pub version: u8,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl AsciicastHeader {
+    pub fn new(width: usize, height: usize, title: Option<String>) -> Self {
+        AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            title,
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsciicastEvent {
+    Output(String),
+    Input(String),
+    Resize { width: usize, height: usize },
+    Marker(String),
+}
+
+impl AsciicastEvent {
+    fn code(&self) -> &'static str {
+        match self {
+            AsciicastEvent::Output(_) => "o",
+            AsciicastEvent::Input(_) => "i",
+            AsciicastEvent::Resize { .. } => "r",
+            AsciicastEvent::Marker(_) => "m",
+        }
+    }
+    fn data(&self) -> String {
+        match self {
+            AsciicastEvent::Output(data)
+            | AsciicastEvent::Input(data)
+            | AsciicastEvent::Marker(data) => data.clone(),
+            AsciicastEvent::Resize { width, height } => format!("{}x{}", width, height),
+        }
+    }
+    // returns None for event codes we do not know, these should be ignored as per the spec
+    fn from_code_and_data(code: &str, data: String) -> Result<Option<Self>> {
+        match code {
+            "o" => Ok(Some(AsciicastEvent::Output(data))),
+            "i" => Ok(Some(AsciicastEvent::Input(data))),
+            "m" => Ok(Some(AsciicastEvent::Marker(data))),
+            "r" => {
+                let (width, height) = data
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                    .ok_or_else(|| anyhow!("malformed resize event: {:?}", data))?;
+                Ok(Some(AsciicastEvent::Resize { width, height }))
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Writes an asciicast file event by event. Events are not flushed on their own, callers wrapping
+/// a buffered writer should `flush` from time to time so that recordings survive the session
+/// crashing mid-way
+pub struct AsciicastWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> AsciicastWriter<W> {
+    pub fn new(mut writer: W, header: &AsciicastHeader) -> Result<Self> {
+        let header = serde_json::to_string(header).context("failed to serialize header")?;
+        writeln!(writer, "{}", header).context("failed to write header")?;
+        writer.flush().context("failed to flush header")?;
+        Ok(AsciicastWriter { writer })
+    }
+    pub fn write_event(&mut self, time: Duration, event: &AsciicastEvent) -> Result<()> {
+        // microsecond precision is what asciinema itself writes
+        let time = time.as_micros() as f64 / 1_000_000.0;
+        let line = serde_json::to_string(&(time, event.code(), event.data()))
+            .context("failed to serialize event")?;
+        writeln!(self.writer, "{}", line).context("failed to write event")?;
+        Ok(())
+    }
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().context("failed to flush events")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asciicast {
+    pub header: AsciicastHeader,
+    pub events: Vec<(Duration, AsciicastEvent)>,
+}
+
+impl Asciicast {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut lines = reader.lines().enumerate();
+        let header_line = match lines.next() {
+            Some((_, line)) => line.context("failed to read header")?,
+            None => bail!("empty recording"),
+        };
+        let header: AsciicastHeader =
+            serde_json::from_str(&header_line).context("malformed asciicast header")?;
+        if header.version != ASCIICAST_VERSION {
+            bail!("unsupported asciicast version: {}", header.version);
+        }
+        let mut events = vec![];
+        for (line_index, line) in lines {
+            let line = line.with_context(|| format!("failed to read line {}", line_index + 1))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (time, code, data): (f64, String, String) = serde_json::from_str(&line)
+                .with_context(|| format!("malformed event on line {}", line_index + 1))?;
+            if !time.is_finite() || time < 0.0 {
+                bail!("invalid event time on line {}: {}", line_index + 1, time);
+            }
+            if let Some(event) = AsciicastEvent::from_code_and_data(&code, data)
+                .with_context(|| format!("malformed event on line {}", line_index + 1))?
+            {
+                events.push((Duration::from_secs_f64(time), event));
+            }
+        }
+        Ok(Asciicast { header, events })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings_round_trip() {
+        let header = AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width: 80,
+            height: 24,
+            timestamp: Some(1700000000),
+            title: Some("my session".to_owned()),
+            env: BTreeMap::new(),
+        };
+        let events = vec![
+            (
+                Duration::from_millis(0),
+                AsciicastEvent::Output("\u{1b}[31mhi\u{1b}[m\r\n".to_owned()),
+            ),
+            (
+                Duration::from_millis(1500),
+                AsciicastEvent::Resize {
+                    width: 100,
+                    height: 30,
+                },
+            ),
+            (
+                Duration::from_micros(2_000_001),
+                AsciicastEvent::Input("q".to_owned()),
+            ),
+            (
+                Duration::from_secs(3),
+                AsciicastEvent::Marker("done".to_owned()),
+            ),
+        ];
+        let mut file = vec![];
+        let mut writer = AsciicastWriter::new(&mut file, &header).unwrap();
+        for (time, event) in &events {
+            writer.write_event(*time, event).unwrap();
+        }
+        let recording = Asciicast::from_reader(file.as_slice()).unwrap();
+        assert_eq!(recording, Asciicast { header, events });
+    }
+
+    #[test]
+    fn writes_asciicast_v2_lines() {
+        let header = AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width: 10,
+            height: 5,
+            timestamp: None,
+            title: None,
+            env: BTreeMap::new(),
+        };
+        let mut file = vec![];
+        let mut writer = AsciicastWriter::new(&mut file, &header).unwrap();
+        writer
+            .write_event(
+                Duration::from_millis(250),
+                &AsciicastEvent::Output("a\"b".to_owned()),
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(file).unwrap(),
+            "{\"version\":2,\"width\":10,\"height\":5}\n[0.25,\"o\",\"a\\\"b\"]\n"
+        );
+    }
+
+    #[test]
+    fn unknown_event_codes_are_ignored() {
+        let file = "{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.5, \"x\", \"whatever\"]\n[1.0, \"o\", \"hi\"]\n";
+        let recording = Asciicast::from_reader(file.as_bytes()).unwrap();
+        assert_eq!(
+            recording.events,
+            vec![(
+                Duration::from_secs(1),
+                AsciicastEvent::Output("hi".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn malformed_recordings_are_rejected() {
+        assert!(Asciicast::from_reader("".as_bytes()).is_err());
+        assert!(Asciicast::from_reader(
+            "{\"version\": 1, \"width\": 80, \"height\": 24}\n".as_bytes()
+        )
+        .is_err());
+        assert!(Asciicast::from_reader(
+            "{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.5, \"r\", \"80\"]\n".as_bytes()
+        )
+        .is_err());
+        assert!(Asciicast::from_reader(
+            "{\"version\": 2, \"width\": 80, \"height\": 24}\nnot json\n".as_bytes()
+        )
+        .is_err());
+    }
+}
@@ -1,9 +1,11 @@
-use crate::data::{Direction, InputMode, Resize, UnblockCondition};
+use crate::data::{Direction, InputMode, PermissionType, Resize, UnblockCondition};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
     input::{
-        layout::PluginUserConfiguration,
+        actions::SearchOption,
+        command::RestartPolicy,
+        layout::{parse_log_size, PluginUserConfiguration},
         options::{Options, PaneFrameStyle},
     },
 };
@@ -12,6 +14,8 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 use url::Url;
 
 const fn ansi(color: AnsiColor) -> Style {
@@ -27,6 +31,18 @@ const CLI_STYLES: Styles = Styles::styled()
     .valid(ansi(AnsiColor::Green))
     .invalid(ansi(AnsiColor::Yellow));
 
+fn parse_positive_duration(duration: &str) -> Result<Duration, String> {
+    humantime::parse_duration(duration)
+        .ok()
+        .filter(|duration| duration.as_secs() > 0)
+        .ok_or_else(|| {
+            format!(
+                "Invalid duration: \"{}\" (expected eg. 30m, 4h or 7days)",
+                duration
+            )
+        })
+}
+
 fn validate_session(name: &str) -> Result<String, String> {
     #[cfg(unix)]
     {
@@ -51,6 +67,26 @@ fn validate_session(name: &str) -> Result<String, String> {
     Ok(name.to_owned())
 }
 
+fn parse_env_var(env_var: &str) -> Result<(String, String), String> {
+    match env_var.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "environment variables should be in the form KEY=VALUE, found: {}",
+            env_var
+        )),
+    }
+}
+
+fn parse_layout_arg(layout_arg: &str) -> Result<(String, String), String> {
+    match layout_arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "layout arguments should be in the form KEY=VALUE, found: {}",
+            layout_arg
+        )),
+    }
+}
+
 #[derive(Parser, Default, Debug, Clone, Serialize, Deserialize)]
 #[clap(
     version,
@@ -93,6 +129,10 @@ pub struct CliArgs {
     #[clap(short, long, value_parser, overrides_with = "new_session_with_layout")]
     pub new_session_with_layout: Option<PathBuf>,
 
+    /// Set a parameter declared by the layout, in the form KEY=VALUE (can be given more than once)
+    #[clap(long = "layout-arg", value_parser = parse_layout_arg)]
+    pub layout_args: Vec<(String, String)>,
+
     /// Change where zellij looks for the configuration file
     #[clap(short, long, overrides_with = "config", env = ZELLIJ_CONFIG_FILE_ENV, value_parser)]
     pub config: Option<PathBuf>,
@@ -226,14 +266,36 @@ pub struct WebCli {
     pub server_startup_timeout: Option<u64>,
     /// Create a login token for the web interface, will only be displayed once and cannot later be
     /// retrieved. Returns the token name and the token.
-    #[clap(long, value_parser, exclusive(true), display_order = 7)]
+    #[clap(
+        long,
+        value_parser,
+        conflicts_with_all(&["start", "stop", "status", "daemonize", "create_read_only_token", "revoke_token", "revoke_all_tokens", "list_tokens"]),
+        display_order = 7
+    )]
     pub create_token: bool,
     /// Optional name for the token
     #[clap(long, value_parser, value_name = "TOKEN_NAME", display_order = 8)]
     pub token_name: Option<String>,
     /// Create a read-only login token (can only attach to existing sessions as watcher)
-    #[clap(long, value_parser, exclusive(true), display_order = 9)]
+    #[clap(
+        long,
+        value_parser,
+        conflicts_with_all(&["start", "stop", "status", "daemonize", "create_token", "revoke_token", "revoke_all_tokens", "list_tokens"]),
+        display_order = 9
+    )]
     pub create_read_only_token: bool,
+    /// Make the created token expire after this long (eg. "30m", "4h", "7days")
+    #[clap(
+        long,
+        value_parser = parse_positive_duration,
+        value_name = "DURATION",
+        display_order = 9
+    )]
+    pub token_expires_in: Option<Duration>,
+    /// Only allow the created token to attach to sessions matching this name or glob (eg.
+    /// "pairing-*")
+    #[clap(long, value_parser, value_name = "SESSION_GLOB", display_order = 9)]
+    pub token_session: Option<String>,
     /// Revoke a login token by its name
     #[clap(
         long,
@@ -249,6 +311,44 @@ pub struct WebCli {
     /// List token names and their creation dates (cannot show actual tokens)
     #[clap(long, value_parser, exclusive(true), display_order = 12)]
     pub list_tokens: bool,
+    /// Show the audit log of logins, attaches, detaches and token changes, oldest first
+    #[clap(
+        long,
+        value_parser,
+        conflicts_with_all(&["start", "stop", "status", "daemonize", "create_token", "create_read_only_token", "revoke_token", "revoke_all_tokens", "list_tokens"]),
+        display_order = 13
+    )]
+    pub audit: bool,
+    /// Only show audit entries for this session
+    #[clap(
+        long,
+        value_parser,
+        requires = "audit",
+        value_name = "SESSION_NAME",
+        display_order = 13
+    )]
+    pub audit_session: Option<String>,
+    /// Only show audit entries for this token
+    #[clap(
+        long,
+        value_parser,
+        requires = "audit",
+        value_name = "TOKEN_NAME",
+        display_order = 13
+    )]
+    pub audit_token: Option<String>,
+    /// Only show audit entries from the last DURATION (eg. "30m", "4h", "7days")
+    #[clap(
+        long,
+        value_parser = parse_positive_duration,
+        requires = "audit",
+        value_name = "DURATION",
+        display_order = 13
+    )]
+    pub audit_since: Option<Duration>,
+    /// Print audit entries as JSON lines
+    #[clap(long, value_parser, requires = "audit", display_order = 13)]
+    pub audit_json: bool,
     /// The ip address to listen on locally for connections (defaults to 127.0.0.1)
     #[clap(
         long,
@@ -292,10 +392,42 @@ impl WebCli {
                 || self.create_read_only_token
                 || self.revoke_token.is_some()
                 || self.revoke_all_tokens
-                || self.list_tokens)
+                || self.list_tokens
+                || self.audit)
     }
 }
 
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum PluginSubcommand {
+    /// Review and change the permissions granted to plugins
+    Permissions {
+        #[clap(subcommand)]
+        command: PluginPermissionsCommand,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum PluginPermissionsCommand {
+    /// List every plugin URL with the permissions granted to it
+    #[clap(visible_alias = "ls")]
+    List,
+    /// Grant permissions to a plugin, so that it is not prompted for them
+    Grant {
+        /// Plugin URL, as shown by `zellij plugin permissions list`
+        url: String,
+        /// The permissions to grant (eg. ReadApplicationState RunCommands)
+        #[clap(required(true))]
+        permissions: Vec<PermissionType>,
+    },
+    /// Revoke permissions from a plugin, running instances of it lose them immediately
+    Revoke {
+        /// Plugin URL, as shown by `zellij plugin permissions list`
+        url: String,
+        /// The permissions to revoke, all of them (including filesystem access) if none are given
+        permissions: Vec<PermissionType>,
+    },
+}
+
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
 pub enum SessionCommand {
     /// Change the behaviour of zellij
@@ -379,6 +511,27 @@ pub enum Sessions {
         session_name: Option<String>,
     },
 
+    /// Play back a recording made with `zellij action start-recording`
+    ///
+    /// Inside a session, the recording is played in a new pane.
+    Replay {
+        /// Path to the asciicast (.cast) file
+        #[clap(value_parser)]
+        file: PathBuf,
+        /// Playback speed multiplier
+        #[clap(short, long, value_parser, default_value = "1.0")]
+        speed: f64,
+        /// Shorten pauses longer than this many seconds
+        #[clap(short, long, value_parser)]
+        idle_time_limit: Option<f64>,
+        /// Play in the current terminal, even inside a session
+        #[clap(long, value_parser)]
+        inline: bool,
+        /// Open the replay pane as a floating pane
+        #[clap(short, long, value_parser, conflicts_with("inline"))]
+        floating: bool,
+    },
+
     /// Kill a specific session
     #[clap(visible_alias = "k")]
     KillSession {
@@ -433,6 +586,11 @@ pub enum Sessions {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
 
+        /// Set an environment variable for the command, in the form KEY=VALUE (can be given
+        /// more than once)
+        #[clap(long = "env", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+
         /// Open the new pane in floating mode
         #[clap(short, long)]
         floating: bool,
@@ -457,6 +615,14 @@ pub enum Sessions {
         #[clap(short, long)]
         start_suspended: bool,
 
+        /// Restart the command when it exits: never (default), on-failure or always
+        #[clap(long, value_parser = RestartPolicy::from_str)]
+        restart: Option<RestartPolicy>,
+
+        /// The maximum number of times in a row the command will be restarted
+        #[clap(long, requires("restart"))]
+        max_restarts: Option<usize>,
+
         /// The x coordinates if the pane is floating as a bare integer (eg. 1) or percent (eg. 10%)
         #[clap(short, long, requires("floating"))]
         x: Option<String>,
@@ -528,11 +694,18 @@ pub enum Sessions {
     },
     /// Load a plugin
     /// Returns: Created pane ID (format: plugin_<id>)
-    #[clap(visible_alias = "p")]
+    #[clap(
+        visible_alias = "p",
+        subcommand_negates_reqs(true),
+        args_conflicts_with_subcommands(true)
+    )]
     Plugin {
         /// Plugin URL, can either start with http(s), file: or zellij:
         #[clap(last(true), required(true))]
-        url: String,
+        url: Option<String>,
+
+        #[clap(subcommand)]
+        subcommand: Option<PluginSubcommand>,
 
         /// Plugin configuration
         #[clap(short, long, value_parser)]
@@ -788,6 +961,85 @@ pub enum CliAction {
     DumpLayout,
     /// Save the current session state to disk immediately
     SaveSession,
+    /// Start recording to an asciicast v2 file, which can be played back with `zellij replay`.
+    /// Records the focused pane unless told otherwise.
+    ///
+    /// Returns: The path of the recording
+    StartRecording {
+        /// File to record to, it must not exist yet. If omitted, a new file is created in the zellij
+        /// cache folder.
+        #[clap(value_parser)]
+        path: Option<PathBuf>,
+
+        /// Record a specific pane by ID (eg. terminal_1, plugin_2, or 3)
+        #[clap(short, long, value_parser, conflicts_with_all(&["tab", "tab_id", "client"]))]
+        pane_id: Option<String>,
+
+        /// Record the focused tab, including its frames and floating panes
+        #[clap(short, long, conflicts_with_all(&["tab_id", "client"]))]
+        tab: bool,
+
+        /// Record the tab with this ID, including its frames and floating panes
+        #[clap(long, value_parser, conflicts_with("client"))]
+        tab_id: Option<usize>,
+
+        /// Record the whole screen as this client sees it, including tab switches
+        #[clap(short, long)]
+        client: bool,
+    },
+    /// Stop a recording, or all recordings if no path is given
+    StopRecording {
+        /// Path of the recording to stop
+        #[clap(value_parser)]
+        path: Option<PathBuf>,
+    },
+    /// Continuously append the output of a terminal pane to a file
+    StartPaneLog {
+        /// File to append the pane output to
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Log a specific pane by ID (eg. terminal_1 or 3). If not specified, logs the focused pane.
+        #[clap(short, long, value_parser)]
+        pane_id: Option<String>,
+
+        /// Remove ANSI escape sequences from the logged output
+        #[clap(long)]
+        strip_ansi: bool,
+
+        /// Prefix each logged line with the time it was received
+        #[clap(long)]
+        timestamps: bool,
+
+        /// Rotate the log file once it grows beyond this size (eg. 4096, 512K or 10M)
+        #[clap(long, value_parser = parse_log_size)]
+        max_size: Option<u64>,
+
+        /// How many rotated log files to keep around (default: 5)
+        #[clap(long, value_parser, requires("max_size"))]
+        max_files: Option<usize>,
+    },
+    /// Stop logging the output of a pane
+    StopPaneLog {
+        /// Stop logging a specific pane by ID (eg. terminal_1 or 3). If not specified, stops logging the focused pane.
+        #[clap(short, long, value_parser)]
+        pane_id: Option<String>,
+    },
+    /// Get notified when a terminal pane becomes active or goes silent. Without any flags,
+    /// clears the monitors of the pane.
+    MonitorPane {
+        /// Monitor a specific pane by ID (eg. terminal_1 or 3). If not specified, monitors the focused pane.
+        #[clap(short, long, value_parser)]
+        pane_id: Option<String>,
+
+        /// Alert when the pane produces output after being idle
+        #[clap(long)]
+        activity: bool,
+
+        /// Alert when the pane produces no output for this many seconds
+        #[clap(long, value_parser)]
+        silence: Option<u64>,
+    },
     /// Open the pane scrollback in your default editor
     EditScrollback {
         /// Target a specific pane by ID (eg. terminal_1, plugin_2, or 3)
@@ -846,6 +1098,11 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         pane_id: Option<String>,
     },
+    /// Toggle a search option of the focused pane [casesensitivity|wholeword|wrap|regex]
+    SearchToggleOption {
+        #[clap(value_parser)]
+        option: SearchOption,
+    },
     /// Toggle between fullscreen focus pane and normal layout.
     ToggleFullscreen {
         /// Target a specific pane by ID (eg. terminal_1, plugin_2, or 3)
@@ -876,6 +1133,14 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         tab_id: Option<usize>,
     },
+    /// Toggle between sending text commands to all the panes in the pane group (also across tabs)
+    /// and normal mode.
+    TogglePaneGroupSync,
+    /// Make the panes with this broadcast_group (set in the layout) the pane group and send text
+    /// commands to all of them
+    SyncBroadcastGroup {
+        name: String,
+    },
     /// Open a new pane in the specified direction [right|down]
     /// If no direction is specified, will try to use the biggest available space.
     /// Returns: Created pane ID (format: terminal_<id> or plugin_<id>)
@@ -894,6 +1159,11 @@ pub enum CliAction {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
 
+        /// Set an environment variable for the command, in the form KEY=VALUE (can be given
+        /// more than once)
+        #[clap(long = "env", value_parser = parse_env_var, requires("command"))]
+        env: Vec<(String, String)>,
+
         /// Open the new pane in floating mode
         #[clap(short, long)]
         floating: bool,
@@ -926,6 +1196,12 @@ pub enum CliAction {
         /// Start the command suspended, only running it after the you first press ENTER
         #[clap(short, long, requires("command"))]
         start_suspended: bool,
+        /// Restart the command when it exits: never (default), on-failure or always
+        #[clap(long, value_parser = RestartPolicy::from_str, requires("command"))]
+        restart: Option<RestartPolicy>,
+        /// The maximum number of times in a row the command will be restarted
+        #[clap(long, requires("restart"))]
+        max_restarts: Option<usize>,
         #[clap(long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
         #[clap(long, value_parser)]
@@ -1111,6 +1387,9 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         pane_id: Option<String>,
     },
+    /// Reopen the most recently closed terminal pane of the focused tab, with its command, cwd
+    /// and placement
+    ReopenClosedPane,
     /// Renames the focused pane
     RenamePane {
         name: String,
@@ -1189,6 +1468,11 @@ pub enum CliAction {
         #[clap(long, value_parser, requires("layout"))]
         layout_dir: Option<PathBuf>,
 
+        /// Set a parameter declared by the layout, in the form KEY=VALUE (can be given more than
+        /// once)
+        #[clap(long = "layout-arg", value_parser = parse_layout_arg)]
+        layout_args: Vec<(String, String)>,
+
         /// Name of the new tab
         #[clap(short, long, value_parser)]
         name: Option<String>,
@@ -1584,7 +1868,7 @@ mod tests {
     fn parse_subscribe(args: &[&str]) -> SubscribeCli {
         let mut full_args = vec!["zellij"];
         full_args.extend_from_slice(args);
-        let cli = CliArgs::try_parse_from(full_args).unwrap();
+        let cli = crate::run_with_large_stack(|| CliArgs::try_parse_from(full_args)).unwrap();
         match cli.command {
             Some(Command::Subscribe(s)) => s,
             other => panic!("Expected Subscribe, got {:?}", other),
@@ -1644,7 +1928,8 @@ mod tests {
 
     #[test]
     fn subscribe_requires_pane_id() {
-        let result = CliArgs::try_parse_from(["zellij", "subscribe"]);
+        let result =
+            crate::run_with_large_stack(|| CliArgs::try_parse_from(["zellij", "subscribe"]));
         assert!(result.is_err());
     }
 }
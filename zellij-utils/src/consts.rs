@@ -109,6 +109,8 @@ lazy_static! {
     pub static ref ZELLIJ_PLUGIN_ARTIFACT_DIR: PathBuf = ZELLIJ_CACHE_DIR.join(VERSION);
     pub static ref ZELLIJ_SEEN_RELEASE_NOTES_CACHE_FILE: PathBuf =
         ZELLIJ_CACHE_DIR.join(VERSION).join("seen_release_notes");
+    pub static ref ZELLIJ_PLUGIN_STORAGE_DIR: PathBuf =
+        ZELLIJ_PROJ_DIR.data_dir().join("plugin_storage");
 }
 
 pub const FEATURES: &[&str] = &[
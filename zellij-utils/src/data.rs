@@ -51,6 +51,68 @@ impl UnblockCondition {
     }
 }
 
+/// What a session recording (see `StartRecording`) captures
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordingTarget {
+    /// The output of the focused pane
+    #[default]
+    FocusedPane,
+    /// The output of a specific pane
+    Pane(PaneId),
+    /// The composed output of the focused tab, recorded only while it is displayed
+    FocusedTab,
+    /// The composed output of the tab with this id, recorded only while it is displayed
+    Tab(usize),
+    /// The composed output of the whole screen, as the client sees it
+    Client,
+}
+
+/// Alerts to raise about a terminal pane (`monitor_activity` and `monitor_silence` in layouts)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PaneMonitors {
+    /// Alert when the pane prints output after having been idle
+    pub activity: bool,
+    /// Alert when the pane has not printed anything for this many seconds
+    pub silence_secs: Option<u64>,
+}
+
+impl PaneMonitors {
+    pub fn is_empty(&self) -> bool {
+        !self.activity && self.silence_secs.is_none()
+    }
+}
+
+/// The scope of a value in the persistent storage of a plugin, values are always scoped to the
+/// plugin url
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StorageScope {
+    /// Shared by all instances of the plugin, in all sessions
+    #[default]
+    Plugin,
+    /// Shared by the instances of the plugin in the current session
+    Session,
+}
+
+/// The answer to a request sent to another plugin with `send_plugin_request`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PluginReply {
+    /// The plugin replied to the request, optionally with a payload
+    Replied(Option<String>),
+    /// The plugin failed the request (or could not be reached) with this error
+    Failed(String),
+    /// No reply arrived before the request timed out
+    TimedOut,
+}
+
+/// An alert raised by one of the `PaneMonitors` of a pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PaneMonitorAlert {
+    /// The pane printed output after having been idle
+    Activity,
+    /// The pane has been silent for this many seconds
+    Silence(u64),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CommandOrPlugin {
     Command(RunCommandAction),
@@ -946,6 +1008,10 @@ pub struct StyledText {
     pub indices: Vec<Vec<usize>>,
 }
 
+/// The status of a `WebRequestResult` for a request that was never sent, because its URL is not
+/// covered by the `allowed_hosts` of the plugin (no HTTP response has this status)
+pub const WEB_REQUEST_BLOCKED_STATUS: u16 = 0;
+
 /// These events can be subscribed to with subscribe method exported by `zellij-tile`.
 /// Once subscribed to, they will trigger the `update` method of the `ZellijPlugin` trait.
 #[derive(Debug, Clone, PartialEq, EnumDiscriminants, Display, Serialize, Deserialize)]
@@ -1040,6 +1106,15 @@ pub enum Event {
     SoftKeyboardVisibilityChanged(bool),
     HintText(BTreeMap<usize, StyledText>),
     ActivePaneScroll(Option<(usize, usize)>),
+    /// One of the monitors set on a pane (see `set_pane_monitors`) raised an alert
+    PaneMonitorAlert(PaneId, PaneMonitorAlert),
+    /// A reply to a request sent with `send_plugin_request`, along with the context given to it
+    PluginRequestResult(PluginReply, BTreeMap<String, String>),
+    /// Another instance of this plugin in this session set (`Some`) or deleted (`None`) a value in
+    /// its storage
+    StorageValueChanged(StorageScope, String, Option<String>), // scope, key, new value
+    /// A command pane was restarted by its restart policy
+    CommandPaneRestarted(u32, Option<i32>, usize), // terminal_pane_id, exit status, restart count
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -1128,15 +1203,78 @@ impl PermissionType {
     }
 }
 
+/// Access to part of the host filesystem, as an alternative to `PermissionType::FullHdAccess`
+///
+/// `path` is a host folder, optionally starting with `~` and containing `*` or `?` wildcards (eg.
+/// `~/notes` or `~/projects/*/docs`). Once granted, every folder it matches is mounted in the
+/// plugin's filesystem under its absolute host path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct FilesystemPermission {
+    pub path: String,
+    pub read_only: bool,
+}
+
+impl FilesystemPermission {
+    pub fn read_only(path: impl Into<String>) -> Self {
+        FilesystemPermission {
+            path: path.into(),
+            read_only: true,
+        }
+    }
+    pub fn read_write(path: impl Into<String>) -> Self {
+        FilesystemPermission {
+            path: path.into(),
+            read_only: false,
+        }
+    }
+    pub fn display_name(&self) -> String {
+        if self.read_only {
+            format!("Read files in {}", self.path)
+        } else {
+            format!("Read and write files in {}", self.path)
+        }
+    }
+    /// Whether granting `self` also covers a request for `other`
+    pub fn covers(&self, other: &FilesystemPermission) -> bool {
+        self.path == other.path && (other.read_only || !self.read_only)
+    }
+}
+
+/// The permissions cached for a plugin URL, as reviewed with `zellij plugin permissions list`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrantedPluginPermissions {
+    pub plugin_url: String,
+    pub permissions: Vec<PermissionType>,
+    pub filesystem_permissions: Vec<FilesystemPermission>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PluginPermission {
     pub name: String,
     pub permissions: Vec<PermissionType>,
+    pub filesystem_permissions: Vec<FilesystemPermission>,
+    pub allowed_hosts: Vec<String>,
 }
 
 impl PluginPermission {
     pub fn new(name: String, permissions: Vec<PermissionType>) -> Self {
-        PluginPermission { name, permissions }
+        PluginPermission {
+            name,
+            permissions,
+            filesystem_permissions: vec![],
+            allowed_hosts: vec![],
+        }
+    }
+    pub fn with_filesystem_permissions(
+        mut self,
+        filesystem_permissions: Vec<FilesystemPermission>,
+    ) -> Self {
+        self.filesystem_permissions = filesystem_permissions;
+        self
+    }
+    pub fn with_allowed_hosts(mut self, allowed_hosts: Vec<String>) -> Self {
+        self.allowed_hosts = allowed_hosts;
+        self
     }
 }
 
@@ -1200,6 +1338,10 @@ pub enum InputMode {
     /// `Tmux` mode allows for basic tmux keybindings functionality
     #[serde(alias = "tmux")]
     Tmux,
+    /// `Copy` mode allows moving a cursor over the scrollback of a pane and selecting text with
+    /// the keyboard.
+    #[serde(alias = "copy")]
+    Copy,
 }
 
 impl Default for InputMode {
@@ -1344,6 +1486,7 @@ impl FromStr for InputMode {
             "move" | "Move" => Ok(InputMode::Move),
             "prompt" | "Prompt" => Ok(InputMode::Prompt),
             "tmux" | "Tmux" => Ok(InputMode::Tmux),
+            "copy" | "Copy" => Ok(InputMode::Copy),
             "entersearch" | "Entersearch" | "EnterSearch" => Ok(InputMode::EnterSearch),
             e => Err(ConversionError::UnknownInputMode(e.into())),
         }
@@ -1819,6 +1962,9 @@ pub struct SessionInfo {
 pub struct PluginInfo {
     pub location: String,
     pub configuration: BTreeMap<String, String>,
+    /// Set if the plugin was stopped by Zellij (eg. for exceeding its fuel budget), the plugin
+    /// stays stopped until it is reloaded
+    pub crash_reason: Option<String>,
 }
 
 impl From<RunPlugin> for PluginInfo {
@@ -1826,6 +1972,7 @@ impl From<RunPlugin> for PluginInfo {
         PluginInfo {
             location: run_plugin.location.display(),
             configuration: run_plugin.configuration.inner().clone(),
+            crash_reason: None,
         }
     }
 }
@@ -1865,6 +2012,7 @@ pub struct LayoutMetadata {
     pub tabs: Vec<TabMetadata>,
     pub creation_time: String,
     pub update_time: String,
+    pub parameters: BTreeMap<String, String>, // parameter name -> default value
 }
 
 impl From<&PathBuf> for LayoutMetadata {
@@ -1893,10 +2041,14 @@ impl From<&PathBuf> for LayoutMetadata {
                         let (creation_time, update_time) =
                             LayoutMetadata::creation_and_update_times(&path);
 
+                        let parameters =
+                            Layout::parameters_from_kdl(&stringified_layout).unwrap_or_default();
+
                         LayoutMetadata {
                             tabs,
                             creation_time,
                             update_time,
+                            parameters,
                         }
                     },
                     Err(e) => {
@@ -2241,11 +2393,17 @@ impl SessionInfo {
     pub fn update_connected_clients(&mut self, new_connected_clients: usize) {
         self.connected_clients = new_connected_clients;
     }
-    pub fn populate_plugin_list(&mut self, plugins: BTreeMap<u32, RunPlugin>) {
+    pub fn populate_plugin_list(
+        &mut self,
+        plugins: BTreeMap<u32, RunPlugin>,
+        crashed_plugins: &BTreeMap<u32, String>,
+    ) {
         // u32 - plugin_id
         let mut plugin_list = BTreeMap::new();
         for (plugin_id, run_plugin) in plugins {
-            plugin_list.insert(plugin_id, run_plugin.into());
+            let mut plugin_info: PluginInfo = run_plugin.into();
+            plugin_info.crash_reason = crashed_plugins.get(&plugin_id).cloned();
+            plugin_list.insert(plugin_id, plugin_info);
         }
         self.plugins = plugin_list;
     }
@@ -2291,6 +2449,21 @@ pub struct TabInfo {
     pub has_bell_notification: bool,
     /// Whether this tab is currently flashing its bell (transient 400ms state)
     pub is_flashing_bell: bool,
+    /// Terminal panes that were recently closed in this tab, most recently closed first
+    pub recently_closed_panes: Vec<ClosedPaneInfo>,
+}
+
+/// A recently closed terminal pane, which can be brought back with `reopen_closed_pane`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ClosedPaneInfo {
+    /// The title of the pane when it was closed
+    pub title: String,
+    /// The command (and its arguments) the pane was running, empty for the default shell
+    pub command: Vec<String>,
+    /// The working directory the pane had when it was closed, if known
+    pub cwd: Option<PathBuf>,
+    pub is_floating: bool,
+    pub is_stacked: bool,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -2802,6 +2975,7 @@ pub struct CommandToRun {
     pub path: PathBuf,
     pub args: Vec<String>,
     pub cwd: Option<PathBuf>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl CommandToRun {
@@ -2818,6 +2992,10 @@ impl CommandToRun {
             ..Default::default()
         }
     }
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -3024,6 +3202,9 @@ pub struct PipeMessage {
     pub payload: Option<String>,
     pub args: BTreeMap<String, String>,
     pub is_private: bool,
+    /// Set when this message is a request sent with `send_plugin_request`, the plugin should
+    /// answer it with `reply_to_plugin_request` using this id
+    pub request_id: Option<u64>,
 }
 
 impl PipeMessage {
@@ -3040,6 +3221,7 @@ impl PipeMessage {
             payload: payload.clone(),
             args: args.clone().unwrap_or_else(|| Default::default()),
             is_private,
+            request_id: None,
         }
     }
 }
@@ -3186,6 +3368,18 @@ impl OriginatingPlugin {
     }
 }
 
+/// A web login token as listed by `list_web_login_token_details`. The token itself is never
+/// retrievable after creation, only its metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebLoginTokenInfo {
+    pub name: String,
+    pub created_at: String,
+    pub read_only: bool,
+    pub expires_at: Option<String>,
+    pub session_scope: Option<String>, // glob of session names this token can attach to
+    pub last_used_at: Option<String>,
+}
+
 #[derive(ValueEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WebSharing {
     #[serde(alias = "on")]
@@ -3447,6 +3641,16 @@ pub enum PluginCommand {
     RenameTab(u32, String),           // tab index, new name
     ReportPanic(String),              // stringified panic
     RequestPluginPermissions(Vec<PermissionType>),
+    RequestPluginPermissionsWithFilesystemAccess(Vec<PermissionType>, Vec<FilesystemPermission>),
+    ListGrantedPermissions,
+    RevokePluginPermissions {
+        plugin_url: String,
+        permissions: Vec<PermissionType>, // empty to revoke all of them
+    },
+    NewTabsWithLayoutAndArgs {
+        layout_info: LayoutInfo, // LayoutInfo::Stringified for a raw kdl layout
+        layout_args: BTreeMap<String, String>, // values for the parameters declared in the layout
+    },
     SwitchSession(ConnectToSession),
     DeleteDeadSession(String),       // String -> session name
     DeleteAllDeadSessions,           // String -> session name
@@ -3571,7 +3775,8 @@ pub enum PluginCommand {
     EmbedMultiplePanes(Vec<PaneId>),
     QueryWebServerStatus,
     SetSelfMouseSelectionSupport(bool),
-    GenerateWebLoginToken(Option<String>, bool), // (token_label, read_only)
+    GenerateWebLoginToken(Option<String>, bool, Option<u64>, Option<String>), // (token_label,
+    // read_only, expires_in_secs, session_scope)
     RevokeWebLoginToken(String), // String -> token id (provided name or generated id)
     ListWebLoginTokens,
     RevokeAllWebLoginTokens,
@@ -3589,6 +3794,14 @@ pub enum PluginCommand {
         bool,                     // apply_only_to_active_tab,
         BTreeMap<String, String>, // context
     ),
+    OverrideLayoutWithArgs {
+        layout_info: LayoutInfo,
+        retain_existing_terminal_panes: bool,
+        retain_existing_plugin_panes: bool,
+        apply_only_to_active_tab: bool,
+        layout_args: BTreeMap<String, String>, // values for the parameters declared in the layout
+        context: BTreeMap<String, String>,
+    },
     SaveLayout {
         layout_name: String,
         layout_kdl: String,
@@ -3647,6 +3860,44 @@ pub enum PluginCommand {
     DeleteAllDeadSessionsAndReply,     // no payload; sends a response back
     SetSoftKeyboard(bool),
     FocusHostSession,
+    StartRecording {
+        path: Option<PathBuf>,
+        target: RecordingTarget,
+    },
+    StopRecording {
+        path: Option<PathBuf>,
+    },
+    SetPaneMonitors {
+        pane_id: PaneId,
+        monitors: PaneMonitors,
+    },
+    ReopenClosedPane,
+    SendPluginRequest {
+        message: MessageToPlugin,
+        timeout_ms: Option<u64>,
+        context: BTreeMap<String, String>,
+    },
+    ReplyToPluginRequest {
+        request_id: u64,
+        reply: PluginReply,
+    },
+    GetStorageValue {
+        key: String,
+        scope: StorageScope,
+    },
+    SetStorageValue {
+        key: String,
+        value: String,
+        scope: StorageScope,
+    },
+    DeleteStorageValue {
+        key: String,
+        scope: StorageScope,
+    },
+    ListStorageValues {
+        prefix: String,
+        scope: StorageScope,
+    },
 }
 
 // Response type for plugin API methods that open a pane in a new tab
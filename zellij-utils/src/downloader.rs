@@ -1,7 +1,8 @@
 use isahc::prelude::*;
 use isahc::{config::RedirectPolicy, HttpClient, Request};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::{io::AsyncWriteExt as _, sync::Mutex};
@@ -26,6 +27,15 @@ pub enum DownloaderError {
     NotFoundFileName(String),
     #[error("Failed to parse URL body: {0}")]
     InvalidUrlBody(String),
+    #[error(
+        "Integrity check failed for {url}: expected sha256 {expected}, got {actual}. If the plugin \
+         was updated on purpose, update its sha256 pin to the new digest or remove the pin"
+    )]
+    IntegrityMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -65,10 +75,16 @@ impl Downloader {
         }
     }
 
+    /// Download `url` into the cache directory, unless it's already there.
+    ///
+    /// If `expected_sha256` is given, both a cached file and a freshly downloaded one are
+    /// verified against it. A cached file that does not match is discarded and downloaded again,
+    /// a download that does not match is discarded and an error is returned.
     pub async fn download(
         &self,
         url: &str,
         file_name: Option<&str>,
+        expected_sha256: Option<&str>,
     ) -> Result<(), DownloaderError> {
         let Some(client) = &self.client else {
             log::error!("No Http client found, cannot perform requests - this is likely a misconfiguration of isahc::HttpClient");
@@ -88,8 +104,26 @@ impl Downloader {
 
         let file_path = self.location.join(file_name.as_str());
         if file_path.exists() {
-            log::debug!("File already exists: {:?}", file_path);
-            return Ok(());
+            match expected_sha256 {
+                None => {
+                    log::debug!("File already exists: {:?}", file_path);
+                    return Ok(());
+                },
+                Some(expected_sha256) => {
+                    match verify_sha256(url, &file_path, expected_sha256).await {
+                        Ok(()) => {
+                            log::debug!("File already exists and was verified: {:?}", file_path);
+                            return Ok(());
+                        },
+                        Err(e) => {
+                            log::warn!("Discarding cached file {:?}: {}", file_path, e);
+                            tokio::fs::remove_file(&file_path)
+                                .await
+                                .map_err(DownloaderError::Io)?;
+                        },
+                    }
+                },
+            }
         }
         let file_part_path = self.location.join(format!("{}.part", file_name));
         let (mut target, file_part_size) = {
@@ -135,6 +169,15 @@ impl Downloader {
 
         log::debug!("Download complete: {:?}", file_part_path);
 
+        if let Some(expected_sha256) = expected_sha256 {
+            if let Err(e) = verify_sha256(url, &file_part_path, expected_sha256).await {
+                // we remove the file so that the next attempt starts from scratch rather than
+                // resuming a bad download
+                let _ = tokio::fs::remove_file(&file_part_path).await;
+                return Err(e);
+            }
+        }
+
         tokio::fs::rename(file_part_path, file_path)
             .await
             .map_err(|e| DownloaderError::Io(e))?;
@@ -223,12 +266,102 @@ impl Downloader {
     }
 }
 
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+async fn verify_sha256(
+    url: &str,
+    file_path: &Path,
+    expected_sha256: &str,
+) -> Result<(), DownloaderError> {
+    let bytes = tokio::fs::read(file_path)
+        .await
+        .map_err(DownloaderError::Io)?;
+    let actual = sha256_hex(&bytes);
+    if actual.eq_ignore_ascii_case(expected_sha256) {
+        Ok(())
+    } else {
+        Err(DownloaderError::IntegrityMismatch {
+            url: url.to_owned(),
+            expected: expected_sha256.to_owned(),
+            actual,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn cached_file_matching_sha256_is_used() {
+        let location = tempdir().expect("Failed to create temp directory");
+        let location_path = location.path();
+        let contents = b"not really wasm";
+        std::fs::write(location_path.join("cached.wasm"), contents).unwrap();
+
+        let downloader = Downloader::new(location_path.to_path_buf());
+        // the url is never requested because the cached file is verified
+        let result = downloader
+            .download(
+                "https://localhost:0/cached.wasm",
+                Some("cached.wasm"),
+                Some(&sha256_hex(contents)),
+            )
+            .await;
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(
+            std::fs::read(location_path.join("cached.wasm")).unwrap(),
+            contents
+        );
+    }
+
+    #[tokio::test]
+    async fn sha256_mismatch_is_an_integrity_error() {
+        let location = tempdir().expect("Failed to create temp directory");
+        let file_path = location.path().join("plugin.wasm");
+        std::fs::write(&file_path, b"tampered").unwrap();
+
+        let result = verify_sha256(
+            "https://example.com/plugin.wasm",
+            &file_path,
+            &sha256_hex(b"original"),
+        )
+        .await;
+
+        let error = result.expect_err("expected an integrity mismatch");
+        match &error {
+            DownloaderError::IntegrityMismatch {
+                url,
+                expected,
+                actual,
+            } => {
+                assert_eq!(url, "https://example.com/plugin.wasm");
+                assert_eq!(expected, &sha256_hex(b"original"));
+                assert_eq!(actual, &sha256_hex(b"tampered"));
+            },
+            other => panic!("expected an integrity mismatch, got: {:?}", other),
+        }
+        // shown in the plugin pane when it fails to load
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Integrity check failed for https://example.com/plugin.wasm: expected sha256 {}, \
+                 got {}. If the plugin was updated on purpose, update its sha256 pin to the new \
+                 digest or remove the pin",
+                sha256_hex(b"original"),
+                sha256_hex(b"tampered")
+            )
+        );
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_download_ok() {
@@ -240,6 +373,7 @@ mod tests {
             .download(
                 "https://github.com/imsnif/monocle/releases/download/0.39.0/monocle.wasm",
                 Some("monocle.wasm"),
+                None,
             )
             .await
             .is_ok();
@@ -261,6 +395,7 @@ mod tests {
             .download(
                 "https://github.com/imsnif/multitask/releases/download/0.38.2v2/multitask.wasm",
                 None,
+                None,
             )
             .await
             .is_ok();
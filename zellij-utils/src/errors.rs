@@ -265,6 +265,12 @@ pub enum ScreenContext {
     DumpScreen,
     DumpLayout,
     SaveSession,
+    StartRecording,
+    StopRecording,
+    StartPaneLog,
+    StopPaneLog,
+    MonitorPane,
+    CheckPaneMonitors,
     EditScrollback,
     GetPaneScrollback,
     ScrollUp,
@@ -278,13 +284,19 @@ pub enum ScreenContext {
     SelectCommandAtScrollPosition,
     CopyLastCommandOutput,
     ClearCommandOutputFlash,
+    RestartCommandPane,
+    ReadinessProbeSucceeded,
     PageScrollUp,
     PageScrollDown,
     HalfPageScrollUp,
     HalfPageScrollDown,
     ClearScroll,
     CloseFocusedPane,
+    ReopenClosedPane,
+    UpdateClosedPaneCwd,
     ToggleActiveSyncTab,
+    TogglePaneGroupSync,
+    SyncBroadcastGroup,
     ToggleActiveTerminalFullscreen,
     ToggleActiveTerminalNoUiFullscreen,
     TogglePaneFrames,
@@ -350,6 +362,10 @@ pub enum ScreenContext {
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
     SearchToggleWrap,
+    SearchToggleRegex,
+    CopyModeMove,
+    CopyModeSelect,
+    CopyModeYank,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
     SetTabBellFlash,
@@ -549,8 +565,14 @@ pub enum PluginContext {
     Message,
     CachePluginEvents,
     MessageFromPlugin,
+    PluginRequest,
+    PluginRequestReply,
+    PluginRequestTimedOut,
+    StorageValueChanged,
     UnblockCliPipes,
     WatchFilesystem,
+    PluginFilesChanged,
+    PermissionCacheChanged,
     KeybindPipe,
     DumpLayoutToPlugin,
     ListClientsMetadata,
@@ -654,6 +676,7 @@ pub enum BackgroundJobContext {
     RunCommand,
     WebRequest,
     ReportPluginList,
+    ReportPluginCrash,
     ListWebSessions,
     RenderToClients,
     HighlightPanesWithMessage,
@@ -666,6 +689,11 @@ pub enum BackgroundJobContext {
     StopFlashTabBell,
     StartNestedGuestPing,
     StopNestedGuestPing,
+    StartPaneMonitorTicks,
+    StopPaneMonitorTicks,
+    RestartCommandPane,
+    StartReadinessProbe,
+    StopReadinessProbes,
     Exit,
 }
 
@@ -2,13 +2,13 @@
 
 pub use super::command::{OpenFilePayload, RunCommandAction};
 use super::layout::{
-    FloatingPaneLayout, Layout, PluginAlias, RunPlugin, RunPluginLocation, RunPluginOrAlias,
-    SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+    FloatingPaneLayout, Layout, LogOutput, PluginAlias, RunPlugin, RunPluginLocation,
+    RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
 };
 use crate::cli::CliAction;
 use crate::data::{
     CommandOrPlugin, Direction, KeyWithModifier, LayoutInfo, NewPanePlacement, OriginatingPlugin,
-    PaneId, Resize, UnblockCondition,
+    PaneId, PaneMonitors, RecordingTarget, Resize, UnblockCondition,
 };
 use crate::data::{FloatingPaneCoordinates, InputMode};
 use crate::home::{find_default_config_dir, get_layout_dir};
@@ -78,6 +78,7 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
 }
 
 impl FromStr for SearchOption {
@@ -89,6 +90,7 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" => Ok(SearchOption::Regex),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -97,6 +99,90 @@ impl FromStr for SearchOption {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CopyModeMotion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordBackward,
+    WordEnd,
+    LineStart,
+    LineEnd,
+    FirstNonBlank,
+    ParagraphForward,
+    ParagraphBackward,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    PreviousPrompt,
+    NextPrompt,
+    NextSearchResult,
+    PreviousSearchResult,
+}
+
+impl FromStr for CopyModeMotion {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Left" | "left" => Ok(CopyModeMotion::Left),
+            "Right" | "right" => Ok(CopyModeMotion::Right),
+            "Up" | "up" => Ok(CopyModeMotion::Up),
+            "Down" | "down" => Ok(CopyModeMotion::Down),
+            "WordForward" | "wordforward" => Ok(CopyModeMotion::WordForward),
+            "WordBackward" | "wordbackward" => Ok(CopyModeMotion::WordBackward),
+            "WordEnd" | "wordend" => Ok(CopyModeMotion::WordEnd),
+            "LineStart" | "linestart" => Ok(CopyModeMotion::LineStart),
+            "LineEnd" | "lineend" => Ok(CopyModeMotion::LineEnd),
+            "FirstNonBlank" | "firstnonblank" => Ok(CopyModeMotion::FirstNonBlank),
+            "ParagraphForward" | "paragraphforward" => Ok(CopyModeMotion::ParagraphForward),
+            "ParagraphBackward" | "paragraphbackward" => Ok(CopyModeMotion::ParagraphBackward),
+            "Top" | "top" => Ok(CopyModeMotion::Top),
+            "Bottom" | "bottom" => Ok(CopyModeMotion::Bottom),
+            "PageUp" | "pageup" => Ok(CopyModeMotion::PageUp),
+            "PageDown" | "pagedown" => Ok(CopyModeMotion::PageDown),
+            "HalfPageUp" | "halfpageup" => Ok(CopyModeMotion::HalfPageUp),
+            "HalfPageDown" | "halfpagedown" => Ok(CopyModeMotion::HalfPageDown),
+            "PreviousPrompt" | "previousprompt" => Ok(CopyModeMotion::PreviousPrompt),
+            "NextPrompt" | "nextprompt" => Ok(CopyModeMotion::NextPrompt),
+            "NextSearchResult" | "nextsearchresult" => Ok(CopyModeMotion::NextSearchResult),
+            "PreviousSearchResult" | "previoussearchresult" => {
+                Ok(CopyModeMotion::PreviousSearchResult)
+            },
+            _ => Err(format!(
+                "Failed to parse CopyModeMotion. Unknown CopyModeMotion: {}",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CopyModeSelection {
+    Character,
+    Line,
+    Block,
+}
+
+impl FromStr for CopyModeSelection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Character" | "character" => Ok(CopyModeSelection::Character),
+            "Line" | "line" => Ok(CopyModeSelection::Line),
+            "Block" | "block" => Ok(CopyModeSelection::Block),
+            _ => Err(format!(
+                "Failed to parse CopyModeSelection. Unknown CopyModeSelection: {}",
+                s
+            )),
+        }
+    }
+}
+
 // As these actions are bound to the default config, please
 // do take care when refactoring - or renaming.
 // They might need to be adjusted in the default config
@@ -187,6 +273,30 @@ pub enum Action {
     DumpLayout,
     /// Save the current session state to disk
     SaveSession,
+    /// Start recording a pane, a tab or the whole screen to an asciicast file
+    StartRecording {
+        path: Option<PathBuf>,
+        target: RecordingTarget,
+    },
+    /// Stop a recording, or all recordings if no path is given
+    StopRecording {
+        path: Option<PathBuf>,
+    },
+    /// Start appending the output of a terminal pane (the focused one if not specified) to a file
+    StartPaneLog {
+        pane_id: Option<PaneId>,
+        log_output: LogOutput,
+    },
+    /// Stop logging the output of a pane (the focused one if not specified)
+    StopPaneLog {
+        pane_id: Option<PaneId>,
+    },
+    /// Set the activity and silence monitors of a terminal pane (the focused one if not
+    /// specified), empty monitors clear them
+    MonitorPane {
+        pane_id: Option<PaneId>,
+        monitors: PaneMonitors,
+    },
     EditScrollback {
         ansi: bool,
     },
@@ -226,6 +336,13 @@ pub enum Action {
     SetPaneFrameStyle(PaneFrameStyle),
     /// Toggle between sending text commands to all panes on the current tab and normal mode.
     ToggleActiveSyncTab,
+    /// Toggle between sending input to all the panes in the pane group (across tabs) and normal
+    /// mode.
+    TogglePaneGroupSync,
+    /// Make the panes of a broadcast group (from the layout) the pane group and sync input to it
+    SyncBroadcastGroup {
+        name: String,
+    },
     /// Open a new pane in the specified direction (relative to focus).
     /// If no direction is specified, will try to use the biggest available space.
     NewPane {
@@ -315,6 +432,8 @@ pub enum Action {
     },
     /// Close the focus pane.
     CloseFocus,
+    /// Reopen the most recently closed terminal pane of the focused tab
+    ReopenClosedPane,
     PaneNameInput {
         input: Vec<u8>,
     },
@@ -427,6 +546,16 @@ pub enum Action {
     SearchToggleOption {
         option: SearchOption,
     },
+    /// Move the copy mode cursor of the focused pane
+    CopyModeMove {
+        motion: CopyModeMotion,
+    },
+    /// Start, change or clear the copy mode selection of the focused pane
+    CopyModeSelect {
+        selection: CopyModeSelection,
+    },
+    /// Copy the copy mode selection of the focused pane to the clipboard
+    CopyModeYank,
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
@@ -692,6 +821,18 @@ impl Default for SearchOption {
     }
 }
 
+impl Default for CopyModeMotion {
+    fn default() -> Self {
+        CopyModeMotion::Left
+    }
+}
+
+impl Default for CopyModeSelection {
+    fn default() -> Self {
+        CopyModeSelection::Character
+    }
+}
+
 impl Action {
     /// Checks that two Action are match except their mutable attributes.
     pub fn shallow_eq(&self, other_action: &Action) -> bool {
@@ -928,6 +1069,93 @@ impl Action {
             },
             CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
             CliAction::SaveSession => Ok(vec![Action::SaveSession]),
+            CliAction::StartRecording {
+                path,
+                pane_id,
+                tab,
+                tab_id,
+                client,
+            } => {
+                let target = match (pane_id, tab_id) {
+                    (Some(pane_id_str), _) => {
+                        let pane_id = PaneId::from_str(&pane_id_str)
+                            .map_err(|_| format!(
+                                "Malformed pane id: {pane_id_str}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)"
+                            ))?;
+                        RecordingTarget::Pane(pane_id)
+                    },
+                    (None, Some(tab_id)) => RecordingTarget::Tab(tab_id),
+                    (None, None) if tab => RecordingTarget::FocusedTab,
+                    (None, None) if client => RecordingTarget::Client,
+                    (None, None) => RecordingTarget::FocusedPane,
+                };
+                Ok(vec![Action::StartRecording {
+                    path: path.map(|path| get_current_dir().join(path)),
+                    target,
+                }])
+            },
+            CliAction::StopRecording { path } => Ok(vec![Action::StopRecording {
+                path: path.map(|path| get_current_dir().join(path)),
+            }]),
+            CliAction::StartPaneLog {
+                path,
+                pane_id,
+                strip_ansi,
+                timestamps,
+                max_size,
+                max_files,
+            } => {
+                let pane_id = pane_id
+                    .map(|pane_id_str| {
+                        PaneId::from_str(&pane_id_str).map_err(|_| format!(
+                            "Malformed pane id: {pane_id_str}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)"
+                        ))
+                    })
+                    .transpose()?;
+                Ok(vec![Action::StartPaneLog {
+                    pane_id,
+                    log_output: LogOutput {
+                        path: get_current_dir().join(path),
+                        strip_ansi,
+                        timestamps,
+                        max_size,
+                        max_files,
+                    },
+                }])
+            },
+            CliAction::StopPaneLog { pane_id } => {
+                let pane_id = pane_id
+                    .map(|pane_id_str| {
+                        PaneId::from_str(&pane_id_str).map_err(|_| format!(
+                            "Malformed pane id: {pane_id_str}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)"
+                        ))
+                    })
+                    .transpose()?;
+                Ok(vec![Action::StopPaneLog { pane_id }])
+            },
+            CliAction::MonitorPane {
+                pane_id,
+                activity,
+                silence,
+            } => {
+                let pane_id = pane_id
+                    .map(|pane_id_str| {
+                        PaneId::from_str(&pane_id_str).map_err(|_| format!(
+                            "Malformed pane id: {pane_id_str}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)"
+                        ))
+                    })
+                    .transpose()?;
+                if silence == Some(0) {
+                    return Err("--silence must be a positive number of seconds".to_owned());
+                }
+                Ok(vec![Action::MonitorPane {
+                    pane_id,
+                    monitors: PaneMonitors {
+                        activity,
+                        silence_secs: silence,
+                    },
+                }])
+            },
             CliAction::EditScrollback { pane_id, ansi } => match pane_id {
                 Some(pane_id_str) => {
                     let pane_id = PaneId::from_str(&pane_id_str)
@@ -1018,6 +1246,9 @@ impl Action {
                 },
                 None => Ok(vec![Action::HalfPageScrollDown]),
             },
+            CliAction::SearchToggleOption { option } => {
+                Ok(vec![Action::SearchToggleOption { option }])
+            },
             CliAction::ToggleFullscreen { pane_id } => match pane_id {
                 Some(pane_id_str) => {
                     let pane_id = PaneId::from_str(&pane_id_str)
@@ -1044,11 +1275,14 @@ impl Action {
                 Some(id) => Ok(vec![Action::ToggleActiveSyncTabByTabId { id: id as u64 }]),
                 None => Ok(vec![Action::ToggleActiveSyncTab]),
             },
+            CliAction::TogglePaneGroupSync => Ok(vec![Action::TogglePaneGroupSync]),
+            CliAction::SyncBroadcastGroup { name } => Ok(vec![Action::SyncBroadcastGroup { name }]),
             CliAction::NewPane {
                 direction,
                 command,
                 plugin,
                 cwd,
+                env,
                 floating,
                 in_place,
                 close_replaced_pane,
@@ -1056,6 +1290,8 @@ impl Action {
                 name,
                 close_on_exit,
                 start_suspended,
+                restart,
+                max_restarts,
                 configuration,
                 skip_plugin_cache,
                 x,
@@ -1122,6 +1358,9 @@ impl Action {
                             direction,
                             hold_on_close,
                             hold_on_start,
+                            env: env.into_iter().collect(),
+                            restart: restart.unwrap_or_default(),
+                            max_restarts,
                             ..Default::default()
                         })
                     } else {
@@ -1168,6 +1407,9 @@ impl Action {
                                 location,
                                 configuration: user_configuration,
                                 initial_cwd: cwd.clone(),
+                                sha256: None,
+                                resource_limits: Default::default(),
+                                allowed_hosts: vec![],
                             })
                         },
                         Err(_) => {
@@ -1231,6 +1473,9 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        env: env.into_iter().collect(),
+                        restart: restart.unwrap_or_default(),
+                        max_restarts,
                         ..Default::default()
                     };
                     if floating {
@@ -1394,6 +1639,7 @@ impl Action {
                 },
                 None => Ok(vec![Action::CloseFocus]),
             },
+            CliAction::ReopenClosedPane => Ok(vec![Action::ReopenClosedPane]),
             CliAction::RenamePane { name, pane_id } => {
                 let pane_id = match pane_id {
                     Some(pane_id_str) => Some(
@@ -1452,6 +1698,7 @@ impl Action {
                 layout,
                 layout_string,
                 layout_dir,
+                layout_args,
                 cwd,
                 initial_command,
                 initial_plugin,
@@ -1486,6 +1733,9 @@ impl Action {
                             location,
                             configuration: Default::default(),
                             initial_cwd: cwd.clone(),
+                            sha256: None,
+                            resource_limits: Default::default(),
+                            allowed_hosts: vec![],
                         }),
                         Err(_) => {
                             let mut plugin_alias =
@@ -1516,13 +1766,17 @@ impl Action {
                 } else {
                     None
                 };
+                let layout_args: BTreeMap<String, String> = layout_args.into_iter().collect();
+                if !layout_args.is_empty() && layout.is_none() && layout_string.is_none() {
+                    return Err("Layout arguments can only be given together with a layout".into());
+                }
                 if let Some(raw_layout) = layout_string {
                     let layout_source_name = "layout-string".to_owned();
                     let path_to_raw_layout = layout_source_name.clone();
                     let swap_layouts: Option<(String, String)> = None;
                     let should_start_layout_commands_suspended = false;
                     let raw_layout_for_error = raw_layout.clone();
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let mut layout = Layout::from_kdl_with_layout_args(&raw_layout, Some(path_to_raw_layout), swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, layout_args.clone()).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_source_name.clone(), raw_layout_for_error);
@@ -1638,7 +1892,7 @@ impl Action {
                         Layout::stringified_from_path_or_default(Some(&layout_path), layout_dir)
                             .map_err(|e| format!("Failed to load layout: {}", e))?
                     };
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let mut layout = Layout::from_kdl_with_layout_args(&raw_layout, Some(path_to_raw_layout), swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, layout_args.clone()).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_source_name.clone(), String::from(raw_layout));
@@ -2986,6 +3240,30 @@ mod tests {
         assert!(matches!(actions[0], Action::CloseFocus));
     }
 
+    #[test]
+    fn test_reopen_closed_pane() {
+        let cli_action = CliAction::ReopenClosedPane;
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], Action::ReopenClosedPane));
+    }
+
+    #[test]
+    fn test_sync_broadcast_group() {
+        let cli_action = CliAction::SyncBroadcastGroup {
+            name: "nodes".to_string(),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::SyncBroadcastGroup {
+                name: "nodes".to_string()
+            }]
+        );
+    }
+
     // 17. RenamePane
     #[test]
     fn test_rename_pane_with_pane_id() {
@@ -3475,6 +3753,141 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_start_recording_defaults_to_focused_pane() {
+        let cli_action = CliAction::StartRecording {
+            path: Some(PathBuf::from("incident.cast")),
+            pane_id: None,
+            tab: false,
+            tab_id: None,
+            client: false,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::StartRecording {
+                path: Some(PathBuf::from("/tmp/incident.cast")),
+                target: RecordingTarget::FocusedPane,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_start_recording_targets() {
+        let start_recording = |pane_id: Option<&str>, tab, tab_id, client| {
+            let cli_action = CliAction::StartRecording {
+                path: None,
+                pane_id: pane_id.map(|p| p.to_owned()),
+                tab,
+                tab_id,
+                client,
+            };
+            match Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None)
+                .unwrap()
+                .remove(0)
+            {
+                Action::StartRecording { path, target } => {
+                    assert_eq!(path, None);
+                    target
+                },
+                _ => panic!("Expected StartRecording action"),
+            }
+        };
+        assert_eq!(
+            start_recording(Some("plugin_2"), false, None, false),
+            RecordingTarget::Pane(PaneId::Plugin(2))
+        );
+        assert_eq!(
+            start_recording(None, true, None, false),
+            RecordingTarget::FocusedTab
+        );
+        assert_eq!(
+            start_recording(None, false, Some(3), false),
+            RecordingTarget::Tab(3)
+        );
+        assert_eq!(
+            start_recording(None, false, None, true),
+            RecordingTarget::Client
+        );
+        let cli_action = CliAction::StartRecording {
+            path: None,
+            pane_id: Some("not_a_pane".to_owned()),
+            tab: false,
+            tab_id: None,
+            client: false,
+        };
+        assert!(
+            Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_start_pane_log() {
+        let cli_action = CliAction::StartPaneLog {
+            path: PathBuf::from("logs/ci.log"),
+            pane_id: Some("terminal_4".to_owned()),
+            strip_ansi: true,
+            timestamps: true,
+            max_size: Some(1024),
+            max_files: Some(2),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::StartPaneLog {
+                pane_id: Some(PaneId::Terminal(4)),
+                log_output: LogOutput {
+                    path: PathBuf::from("/tmp/logs/ci.log"),
+                    strip_ansi: true,
+                    timestamps: true,
+                    max_size: Some(1024),
+                    max_files: Some(2),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_stop_pane_log() {
+        let cli_action = CliAction::StopPaneLog { pane_id: None };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(result.unwrap(), vec![Action::StopPaneLog { pane_id: None }]);
+        let cli_action = CliAction::StopPaneLog {
+            pane_id: Some("not_a_pane".to_owned()),
+        };
+        assert!(
+            Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_monitor_pane() {
+        let cli_action = CliAction::MonitorPane {
+            pane_id: Some("2".to_owned()),
+            activity: true,
+            silence: Some(30),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::MonitorPane {
+                pane_id: Some(PaneId::Terminal(2)),
+                monitors: PaneMonitors {
+                    activity: true,
+                    silence_secs: Some(30),
+                },
+            }]
+        );
+        let cli_action = CliAction::MonitorPane {
+            pane_id: None,
+            activity: false,
+            silence: Some(0),
+        };
+        assert!(
+            Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None).is_err()
+        );
+    }
+
     #[test]
     fn test_focus_pane_id() {
         let cli_action = CliAction::FocusPaneId {
@@ -3542,6 +3955,7 @@ mod tests {
             layout: None,
             layout_string: Some("layout {\n    pane\n    pane\n}\n".into()),
             layout_dir: None,
+            layout_args: vec![],
             cwd: None,
             initial_command: vec![],
             initial_plugin: None,
@@ -3579,6 +3993,7 @@ mod tests {
             layout: None,
             layout_string: Some("invalid { kdl".into()),
             layout_dir: None,
+            layout_args: vec![],
             cwd: None,
             initial_command: vec![],
             initial_plugin: None,
@@ -3665,6 +4080,7 @@ mod tests {
             command: vec![],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: false,
             close_replaced_pane: false,
@@ -3672,6 +4088,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3702,6 +4120,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_new_pane_with_env() {
+        let cli_action = CliAction::NewPane {
+            direction: None,
+            command: vec!["./server".to_owned()],
+            plugin: None,
+            cwd: None,
+            env: vec![(
+                "DATABASE_URL".to_owned(),
+                "postgres://localhost/staging".to_owned(),
+            )],
+            floating: false,
+            in_place: false,
+            close_replaced_pane: false,
+            pane_id: None,
+            name: None,
+            close_on_exit: false,
+            start_suspended: false,
+            restart: None,
+            max_restarts: None,
+            configuration: None,
+            skip_plugin_cache: false,
+            x: None,
+            y: None,
+            width: None,
+            height: None,
+            pinned: None,
+            stacked: false,
+            blocking: false,
+            block_until_exit_success: false,
+            block_until_exit_failure: false,
+            block_until_exit: false,
+            unblock_condition: None,
+            near_current_pane: false,
+            no_focus: false,
+            borderless: None,
+            tab_id: None,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        let actions = result.unwrap();
+        match &actions[0] {
+            Action::NewTiledPane {
+                command: Some(run_command_action),
+                ..
+            } => {
+                assert_eq!(
+                    run_command_action.env.get("DATABASE_URL"),
+                    Some(&"postgres://localhost/staging".to_owned())
+                );
+            },
+            _ => panic!("Expected NewTiledPane action with a command"),
+        }
+    }
+
     #[test]
     fn test_new_pane_tiled_without_tab_id() {
         let cli_action = CliAction::NewPane {
@@ -3709,6 +4181,7 @@ mod tests {
             command: vec![],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: false,
             close_replaced_pane: false,
@@ -3716,6 +4189,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3753,6 +4228,7 @@ mod tests {
             command: vec![],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: true,
             close_replaced_pane: true,
@@ -3760,6 +4236,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3799,6 +4277,7 @@ mod tests {
             command: vec![],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: true,
             close_replaced_pane: false,
@@ -3806,6 +4285,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3836,6 +4317,7 @@ mod tests {
             command: vec![],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: true,
             in_place: false,
             close_replaced_pane: false,
@@ -3843,6 +4325,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3880,6 +4364,7 @@ mod tests {
             command: vec!["ls".into()],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: false,
             close_replaced_pane: false,
@@ -3887,6 +4372,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -3924,6 +4411,7 @@ mod tests {
             command: vec!["ls".into()],
             plugin: None,
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: false,
             close_replaced_pane: false,
@@ -3931,6 +4419,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -4032,6 +4522,7 @@ mod tests {
             command: vec![],
             plugin: Some("zellij:strider".into()),
             cwd: None,
+            env: vec![],
             floating: false,
             in_place: false,
             close_replaced_pane: false,
@@ -4039,6 +4530,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -4076,6 +4569,7 @@ mod tests {
             command: vec![],
             plugin: Some("zellij:strider".into()),
             cwd: None,
+            env: vec![],
             floating: true,
             in_place: false,
             close_replaced_pane: false,
@@ -4083,6 +4577,8 @@ mod tests {
             name: None,
             close_on_exit: false,
             start_suspended: false,
+            restart: None,
+            max_restarts: None,
             configuration: None,
             skip_plugin_cache: false,
             x: None,
@@ -4112,4 +4608,21 @@ mod tests {
             _ => panic!("Expected NewFloatingPluginPane action"),
         }
     }
+
+    #[test]
+    fn test_search_toggle_option_regex() {
+        let cli_action = CliAction::SearchToggleOption {
+            option: SearchOption::Regex,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0],
+            Action::SearchToggleOption {
+                option: SearchOption::Regex
+            }
+        );
+    }
 }
@@ -40,6 +40,7 @@ pub struct CliAssets {
     pub should_ignore_config: bool,
     pub configuration_options: Option<Options>, // merged from everywhere: there are the source of truth
     pub layout: Option<LayoutInfo>,
+    pub layout_args: BTreeMap<String, String>, // values for the parameters declared in the layout
     pub terminal_window_size: Size,
     pub data_dir: Option<PathBuf>,
     pub is_debug: bool,
@@ -77,8 +78,13 @@ impl CliAssets {
                         .map(|dir| dir.join("layouts"))
                 });
             self.layout.as_ref().and_then(|layout_info| {
-                Layout::from_layout_info_with_config(&layout_dir, layout_info, Some(config.clone()))
-                    .ok()
+                Layout::from_layout_info_with_config(
+                    &layout_dir,
+                    layout_info,
+                    self.layout_args.clone(),
+                    Some(config.clone()),
+                )
+                .ok()
             })
         }
         .map(|(layout, config)| (layout, config))
@@ -1,7 +1,10 @@
 //! Trigger a command
 use crate::data::{Direction, OriginatingPlugin};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub enum TerminalAction {
@@ -56,6 +59,51 @@ impl OpenFilePayload {
     }
 }
 
+/// When the command of a command pane should be started again after it exits
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    pub fn should_restart(&self, exit_status: Option<i32>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            // a command killed by a signal has no exit status, we count this as a failure
+            RestartPolicy::OnFailure => exit_status != Some(0),
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+impl FromStr for RestartPolicy {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(RestartPolicy::Never),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "always" => Ok(RestartPolicy::Always),
+            _ => Err(format!(
+                "Unknown restart policy: {}, expected one of: never, on-failure, always",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestartPolicy::Never => write!(f, "never"),
+            RestartPolicy::OnFailure => write!(f, "on-failure"),
+            RestartPolicy::Always => write!(f, "always"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Default, Serialize, PartialEq, Eq)]
 pub struct RunCommand {
     #[serde(alias = "cmd")]
@@ -72,6 +120,16 @@ pub struct RunCommand {
     pub originating_plugin: Option<OriginatingPlugin>,
     #[serde(default)]
     pub use_terminal_title: bool,
+    /// Environment variables set for this command only, on top of the ones the server
+    /// itself was started with
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// How many times in a row the command can be restarted by its restart policy, unlimited if
+    /// None
+    #[serde(default)]
+    pub max_restarts: Option<usize>,
 }
 
 impl std::fmt::Display for RunCommand {
@@ -109,6 +167,12 @@ pub struct RunCommandAction {
     pub originating_plugin: Option<OriginatingPlugin>,
     #[serde(default)]
     pub use_terminal_title: bool,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    #[serde(default)]
+    pub max_restarts: Option<usize>,
 }
 
 impl From<RunCommandAction> for RunCommand {
@@ -121,6 +185,9 @@ impl From<RunCommandAction> for RunCommand {
             hold_on_start: action.hold_on_start,
             originating_plugin: action.originating_plugin,
             use_terminal_title: action.use_terminal_title,
+            env: action.env,
+            restart: action.restart,
+            max_restarts: action.max_restarts,
         }
     }
 }
@@ -136,6 +203,9 @@ impl From<RunCommand> for RunCommandAction {
             hold_on_start: run_command.hold_on_start,
             originating_plugin: run_command.originating_plugin,
             use_terminal_title: run_command.use_terminal_title,
+            env: run_command.env,
+            restart: run_command.restart,
+            max_restarts: run_command.max_restarts,
         }
     }
 }
@@ -168,4 +238,8 @@ impl RunCommand {
         self.cwd = Some(cwd);
         self
     }
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
 }
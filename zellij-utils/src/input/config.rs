@@ -224,7 +224,7 @@ impl Config {
                 let mut kdl_config = String::new();
                 file.read_to_string(&mut kdl_config)
                     .map_err(|e| ConfigError::IoPath(e, path.to_path_buf()))?;
-                match Config::from_kdl(&kdl_config, default_config) {
+                match Config::from_kdl_file(&kdl_config, default_config, path) {
                     Ok(config) => Ok(config),
                     Err(ConfigError::KdlDeserializationError(kdl_error)) => {
                         let error_message = match kdl_error.kind {
@@ -251,7 +251,8 @@ impl Config {
                         };
                         Err(ConfigError::KdlError(kdl_error))
                     },
-                    Err(ConfigError::KdlError(kdl_error)) => {
+                    // errors in included files already carry the source of those files
+                    Err(ConfigError::KdlError(kdl_error)) if kdl_error.src.is_none() => {
                         Err(ConfigError::KdlError(kdl_error.add_src(
                             path.as_path().as_os_str().to_string_lossy().to_string(),
                             kdl_config,
@@ -263,6 +264,40 @@ impl Config {
             Err(e) => Err(ConfigError::IoPath(e, path.into())),
         }
     }
+    /// Gives the plugins this configuration defines or loads the resource limits they have in
+    /// `trusted_config` (or the default ones), so that a configuration that comes with a layout
+    /// loaded from a URL cannot lift the limits of the plugins it starts
+    pub fn keep_plugin_resource_limits_of(&mut self, trusted_config: &Config) {
+        for (alias, run_plugin) in self.plugins.aliases.iter_mut() {
+            run_plugin.resource_limits = trusted_config
+                .plugins
+                .aliases
+                .get(alias)
+                .map(|trusted_plugin| trusted_plugin.resource_limits.clone())
+                .unwrap_or_default();
+        }
+        self.background_plugins = std::mem::take(&mut self.background_plugins)
+            .into_iter()
+            .map(|plugin| match plugin {
+                RunPluginOrAlias::RunPlugin(mut run_plugin) => {
+                    run_plugin.resource_limits = trusted_config
+                        .background_plugins
+                        .iter()
+                        .find_map(|trusted_plugin| match trusted_plugin {
+                            RunPluginOrAlias::RunPlugin(trusted_plugin)
+                                if trusted_plugin.location == run_plugin.location =>
+                            {
+                                Some(trusted_plugin.resource_limits.clone())
+                            },
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+                    RunPluginOrAlias::RunPlugin(run_plugin)
+                },
+                alias => alias,
+            })
+            .collect();
+    }
     pub fn merge(&mut self, other: Config) -> Result<(), ConfigError> {
         self.options = self.options.merge(other.options);
         self.keybinds.merge(other.keybinds.clone());
@@ -292,6 +327,15 @@ impl Config {
     ) -> Result<Config, Option<PathBuf>> {
         // if we fail, try to return the PathBuf of the file we were not able to write to
         let config_file_path = config_file_path.clone();
+        // the serialized config is what all the included files and matching overlays resolve to,
+        // writing it would drop them and bake this host's overlays into the file
+        if Config::file_uses_includes_or_overlays(&config_file_path) {
+            log::error!(
+                "Not overwriting {} because it uses include or overlay",
+                config_file_path.display()
+            );
+            return Err(Some(config_file_path));
+        }
         Config::from_kdl(&config, None)
             .map_err(|e| {
                 log::error!("Failed to parse config: {}", e);
@@ -450,7 +494,8 @@ pub async fn watch_config_file_changes<F, Fut>(
     Fut: std::future::Future<Output = ()> + Send,
 {
     // in a gist, what we do here is fire the `on_config_change` function whenever there is a
-    // change in the config file or the configured theme directory, we do this by:
+    // change in the config file, the files it includes or the configured theme directory, we do
+    // this by:
     // 1. Trying to watch the provided config file for changes
     // 2. If the file is deleted or does not exist, we periodically poll for it (manually, not
     //    through filesystem events)
@@ -493,6 +538,10 @@ pub async fn watch_config_file_changes<F, Fut>(
         event.paths.iter().any(|path| path == config_file_path)
     }
 
+    fn event_is_for_included_file(event: &Event, included_files: &[PathBuf]) -> bool {
+        event.paths.iter().any(|path| included_files.contains(path))
+    }
+
     fn event_is_in_theme_dir(event: &Event, theme_dir: Option<&Path>) -> bool {
         theme_dir.map_or(false, |theme_dir| {
             event.paths.iter().any(|path| path.starts_with(theme_dir))
@@ -503,6 +552,7 @@ pub async fn watch_config_file_changes<F, Fut>(
         config_file_path: &Path,
         config_dir: Option<&Path>,
         watched_theme_dir: Option<&Path>,
+        watched_included_files: &[PathBuf],
         on_config_change: &F,
     ) -> Option<bool>
     where
@@ -524,7 +574,10 @@ pub async fn watch_config_file_changes<F, Fut>(
                 },
             };
         on_config_change(new_config).await;
-        Some(new_theme_dir.as_deref() != watched_theme_dir)
+        Some(
+            new_theme_dir.as_deref() != watched_theme_dir
+                || Config::included_files(config_file_path) != watched_included_files,
+        )
     }
 
     loop {
@@ -532,6 +585,7 @@ pub async fn watch_config_file_changes<F, Fut>(
             let watched_theme_dir =
                 load_config_and_theme_dir(config_file_path.as_path(), config_dir)
                     .and_then(|(_, theme_dir)| theme_dir);
+            let watched_included_files = Config::included_files(&config_file_path);
             let (tx, mut rx) = mpsc::unbounded_channel();
 
             let mut watcher = match PollWatcher::new(
@@ -562,6 +616,16 @@ pub async fn watch_config_file_changes<F, Fut>(
                 }
             }
 
+            for included_file in &watched_included_files {
+                if let Err(e) = watcher.watch(included_file, RecursiveMode::NonRecursive) {
+                    log::error!(
+                        "Failed to watch included config file {:?}, continuing without it: {}",
+                        included_file,
+                        e,
+                    );
+                }
+            }
+
             while let Some(event_result) = rx.recv().await {
                 let event = match event_result {
                     Ok(event) => event,
@@ -581,6 +645,7 @@ pub async fn watch_config_file_changes<F, Fut>(
                             config_file_path.as_path(),
                             config_dir,
                             watched_theme_dir.as_deref(),
+                            &watched_included_files,
                             &on_config_change,
                         )
                         .await
@@ -589,13 +654,15 @@ pub async fn watch_config_file_changes<F, Fut>(
                             break;
                         }
                     }
-                } else if event_is_in_theme_dir(&event, watched_theme_dir.as_deref())
+                } else if (event_is_in_theme_dir(&event, watched_theme_dir.as_deref())
+                    || event_is_for_included_file(&event, &watched_included_files))
                     && (event.kind.is_remove() || event.kind.is_create() || event.kind.is_modify())
                 {
                     let should_restart_watcher = reload_config_after_change(
                         config_file_path.as_path(),
                         config_dir,
                         watched_theme_dir.as_deref(),
+                        &watched_included_files,
                         &on_config_change,
                     )
                     .await
@@ -1432,4 +1499,306 @@ mod config_test {
             "Env variables defined in config"
         );
     }
+
+    #[test]
+    fn can_include_config_files() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("team")).unwrap();
+        std::fs::write(
+            tmp.path().join("team").join("base.kdl"),
+            r#"
+                include "colors.kdl"
+                theme "team theme"
+                default_shell "fish"
+                env {
+                    EDITOR "vim"
+                }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("team").join("colors.kdl"),
+            r#"
+                theme "colors theme"
+                pane_frames false
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("local.kdl"),
+            r#"
+                default_shell "zsh"
+            "#,
+        )
+        .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        std::fs::write(
+            &config_file_path,
+            r#"
+                include "team/base.kdl"
+                include "local.kdl"
+                default_mode "locked"
+            "#,
+        )
+        .unwrap();
+        let config = Config::from_path(&config_file_path, None).unwrap();
+        assert_eq!(
+            config.options.theme,
+            Some("team theme".to_string()),
+            "Including file overrides the files it includes"
+        );
+        assert_eq!(
+            config.options.pane_frames,
+            Some(false),
+            "Options from nested includes are merged"
+        );
+        assert_eq!(
+            config.options.default_shell,
+            Some(PathBuf::from("zsh")),
+            "Later includes override earlier ones"
+        );
+        assert_eq!(config.options.default_mode, Some(InputMode::Locked));
+        let mut expected_env_config = HashMap::new();
+        expected_env_config.insert("EDITOR".into(), "vim".into());
+        assert_eq!(
+            config.env,
+            EnvironmentVariables::from_data(expected_env_config),
+            "Env variables from included files are merged"
+        );
+    }
+
+    #[test]
+    fn error_on_config_file_including_itself() {
+        let tmp = tempdir().unwrap();
+        std::fs::write(tmp.path().join("base.kdl"), r#"include "config.kdl""#).unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        std::fs::write(&config_file_path, r#"include "base.kdl""#).unwrap();
+        let result = Config::from_path(&config_file_path, None);
+        assert!(result.is_err(), "error provided for include cycle");
+    }
+
+    #[test]
+    fn errors_in_included_config_files_point_to_the_included_file() {
+        let tmp = tempdir().unwrap();
+        std::fs::write(tmp.path().join("base.kdl"), r#"default_mode "not a mode""#).unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        std::fs::write(&config_file_path, r#"include "base.kdl""#).unwrap();
+        let Err(ConfigError::KdlError(kdl_error)) = Config::from_path(&config_file_path, None)
+        else {
+            panic!("expected a kdl error");
+        };
+        assert!(
+            kdl_error.src.unwrap().name().ends_with("base.kdl"),
+            "error source is the included file"
+        );
+    }
+
+    #[test]
+    fn error_on_missing_included_config_file() {
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        std::fs::write(&config_file_path, r#"include "no-such-file.kdl""#).unwrap();
+        let Err(ConfigError::KdlError(kdl_error)) = Config::from_path(&config_file_path, None)
+        else {
+            panic!("expected a kdl error");
+        };
+        assert!(
+            kdl_error
+                .src
+                .unwrap()
+                .name()
+                .ends_with(DEFAULT_CONFIG_FILE_NAME),
+            "error source is the including file"
+        );
+    }
+
+    #[test]
+    fn can_define_overlays_in_configfile() {
+        let config_contents = format!(
+            r#"
+            theme "default theme"
+            overlay os="{}" {{
+                theme "os theme"
+                env {{
+                    FROM_OS_OVERLAY "1"
+                }}
+            }}
+            overlay hostname="no-such-host-for-zellij-tests" {{
+                theme "hostname theme"
+            }}
+            overlay env="PATH" {{
+                default_shell "fish"
+            }}
+            overlay env="PATH=not-the-path" {{
+                default_shell "zsh"
+            }}
+            overlay os="{}" env="PATH" {{
+                overlay env="NO_SUCH_VARIABLE_FOR_ZELLIJ_TESTS" {{
+                    default_mode "locked"
+                }}
+            }}
+        "#,
+            std::env::consts::OS,
+            std::env::consts::OS,
+        );
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        let config = Config::from_kdl_file(&config_contents, None, &config_file_path).unwrap();
+        assert_eq!(config.options.theme, Some("os theme".to_string()));
+        assert_eq!(config.options.default_shell, Some(PathBuf::from("fish")));
+        assert_eq!(config.options.default_mode, None);
+        let mut expected_env_config = HashMap::new();
+        expected_env_config.insert("FROM_OS_OVERLAY".into(), "1".into());
+        assert_eq!(
+            config.env,
+            EnvironmentVariables::from_data(expected_env_config),
+            "Env variables defined in overlay"
+        );
+    }
+
+    #[test]
+    fn error_on_unknown_overlay_condition() {
+        let config_contents = r#"
+            overlay shell="fish" {
+                theme "fish theme"
+            }
+        "#;
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        let result = Config::from_kdl_file(config_contents, None, &config_file_path);
+        assert!(
+            result.is_err(),
+            "error provided for unknown overlay condition"
+        );
+    }
+
+    #[test]
+    fn includes_and_overlays_are_only_allowed_in_the_configuration_file() {
+        let tmp = tempdir().unwrap();
+        let included_file_path = tmp.path().join("included.kdl");
+        std::fs::write(&included_file_path, r#"default_shell "fish""#).unwrap();
+        for config_contents in [
+            format!(r#"include "{}""#, included_file_path.display()),
+            format!(
+                r#"overlay os="{}" {{ default_shell "zsh"; }}"#,
+                std::env::consts::OS
+            ),
+        ] {
+            let result = Config::from_kdl(&config_contents, None);
+            assert!(result.is_err(), "{} should be rejected", config_contents);
+        }
+    }
+
+    #[test]
+    fn included_files_are_listed_for_watching() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("team")).unwrap();
+        std::fs::write(
+            tmp.path().join("team").join("base.kdl"),
+            r#"include "colors.kdl""#,
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("team").join("colors.kdl"), "").unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        std::fs::write(
+            &config_file_path,
+            r#"
+                include "team/base.kdl"
+                overlay hostname="no-such-host-for-zellij-tests" {
+                    include "work.kdl"
+                }
+            "#,
+        )
+        .unwrap();
+        let tmp_path = tmp.path().canonicalize().unwrap();
+        assert_eq!(
+            Config::included_files(&config_file_path),
+            vec![
+                tmp_path.join("team").join("base.kdl"),
+                tmp_path.join("team").join("colors.kdl"),
+                tmp_path.join("work.kdl"),
+            ],
+            "Nested includes and includes of overlays that do not apply are listed"
+        );
+    }
+
+    #[test]
+    fn config_file_with_includes_or_overlays_is_not_overwritten() {
+        let tmp = tempdir().unwrap();
+        std::fs::write(tmp.path().join("base.kdl"), r#"default_shell "fish""#).unwrap();
+        for config_contents in [
+            r#"include "base.kdl""#,
+            r#"overlay os="linux" { default_shell "zsh"; }"#,
+        ] {
+            let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+            std::fs::write(&config_file_path, config_contents).unwrap();
+            let result =
+                Config::write_config_to_disk(r#"default_mode "locked""#.into(), &config_file_path);
+            assert_eq!(result, Err(Some(config_file_path.clone())));
+            assert_eq!(
+                std::fs::read_to_string(&config_file_path).unwrap(),
+                config_contents,
+                "Config file left untouched"
+            );
+            assert!(
+                !tmp.path().join("config.kdl.bak").exists(),
+                "Config file not backed up"
+            );
+        }
+    }
+
+    #[test]
+    fn plugin_resource_limits_are_kept_from_the_trusted_config() {
+        let trusted_config = Config::from_kdl(
+            r#"
+            plugins {
+                limited location="file:/path/to/limited.wasm" max_memory_mb=32
+            }
+        "#,
+            None,
+        )
+        .unwrap();
+        let mut config = Config::from_kdl(
+            r#"
+            plugins {
+                limited location="file:/path/to/limited.wasm" max_memory_mb=4096
+                added location="file:/path/to/added.wasm" fuel_budget=999999999999
+            }
+            load_plugins {
+                "file:/path/to/background.wasm" {
+                    max_table_elements 999999999
+                }
+            }
+        "#,
+            Some(trusted_config.clone()),
+        )
+        .unwrap();
+        config.keep_plugin_resource_limits_of(&trusted_config);
+        assert_eq!(
+            config
+                .plugins
+                .aliases
+                .get("limited")
+                .unwrap()
+                .resource_limits,
+            trusted_config
+                .plugins
+                .aliases
+                .get("limited")
+                .unwrap()
+                .resource_limits
+        );
+        assert!(config
+            .plugins
+            .aliases
+            .get("added")
+            .unwrap()
+            .resource_limits
+            .is_empty());
+        assert!(config.background_plugins.iter().all(|p| p
+            .get_run_plugin()
+            .unwrap()
+            .resource_limits
+            .is_empty()));
+    }
 }
@@ -11,10 +11,12 @@
 #[cfg(not(target_family = "wasm"))]
 use crate::downloader::Downloader;
 use crate::{
-    data::{Direction, LayoutInfo, LayoutMetadata, LayoutParsingError, LayoutWithError},
+    data::{
+        Direction, LayoutInfo, LayoutMetadata, LayoutParsingError, LayoutWithError, PaneMonitors,
+    },
     home::{default_layout_dir, find_default_config_dir},
     input::{
-        command::RunCommand,
+        command::{RestartPolicy, RunCommand},
         config::{Config, ConfigError},
     },
     pane_size::{Constraint, Dimension, PaneGeom},
@@ -235,6 +237,27 @@ impl RunPluginOrAlias {
         }
         self
     }
+    pub fn with_sha256(mut self, sha256: Option<String>) -> Self {
+        // aliases are pinned in their definition rather than where they are used
+        if let RunPluginOrAlias::RunPlugin(ref mut run_plugin) = self {
+            run_plugin.sha256 = sha256;
+        }
+        self
+    }
+    pub fn with_resource_limits(mut self, resource_limits: PluginResourceLimits) -> Self {
+        // like pinning, the limits of an alias are set in its definition
+        if let RunPluginOrAlias::RunPlugin(ref mut run_plugin) = self {
+            run_plugin.resource_limits = resource_limits;
+        }
+        self
+    }
+    pub fn with_allowed_hosts(mut self, allowed_hosts: Vec<String>) -> Self {
+        // like pinning, the allowed hosts of an alias are set in its definition
+        if let RunPluginOrAlias::RunPlugin(ref mut run_plugin) = self {
+            run_plugin.allowed_hosts = allowed_hosts;
+        }
+        self
+    }
     pub fn add_initial_cwd(&mut self, initial_cwd: &PathBuf) {
         match self {
             RunPluginOrAlias::RunPlugin(ref mut run_plugin) => {
@@ -277,6 +300,15 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                let mut env = base_run_command.env.clone();
+                env.extend(merged.env);
+                merged.env = env;
+                if merged.restart == RestartPolicy::Never {
+                    merged.restart = base_run_command.restart;
+                }
+                if merged.max_restarts.is_none() {
+                    merged.max_restarts = base_run_command.max_restarts;
+                }
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -360,6 +392,14 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        // adds to (and overrides) the env of a Run::Command if it is Some
+        if let Some(env) = env {
+            if let Run::Command(run_command) = self {
+                run_command.env.extend(env);
+            }
+        }
+    }
     pub fn add_close_on_exit(&mut self, close_on_exit: Option<bool>) {
         // overrides the hold_on_close of a Run::Command if it is Some
         // and not empty
@@ -378,6 +418,22 @@ impl Run {
             }
         }
     }
+    pub fn clear_plugin_resource_limits(&mut self) {
+        if let Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin)) = self {
+            run_plugin.resource_limits = PluginResourceLimits::default();
+        }
+    }
+    pub fn add_restart(&mut self, restart: Option<RestartPolicy>, max_restarts: Option<usize>) {
+        // overrides the restart policy of a Run::Command with the parts that are Some
+        if let Run::Command(run_command) = self {
+            if let Some(restart) = restart {
+                run_command.restart = restart;
+            }
+            if let Some(max_restarts) = max_restarts {
+                run_command.max_restarts = Some(max_restarts);
+            }
+        }
+    }
     pub fn is_same_category(first: &Option<Run>, second: &Option<Run>) -> bool {
         match (first, second) {
             (Some(Run::Plugin(..)), Some(Run::Plugin(..))) => true,
@@ -428,6 +484,41 @@ pub struct RunPlugin {
     pub location: RunPluginLocation,
     pub configuration: PluginUserConfiguration,
     pub initial_cwd: Option<PathBuf>,
+    /// the expected sha256 digest (lowercase hex) of a remote plugin's wasm file, downloads
+    /// and cached copies that don't match it are rejected
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub resource_limits: PluginResourceLimits,
+    /// the hosts (eg. `api.example.com` or `*.example.com`) and URL prefixes (eg.
+    /// `https://example.com/api/`) the plugin can send web requests to, empty for any
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+/// The most `max_memory_mb` can be set to, the size of the 32 bit wasm address space
+pub const MAX_PLUGIN_MEMORY_MB: usize = 4096;
+
+/// Limits on the resources a single plugin can use, set where the plugin is declared
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct PluginResourceLimits {
+    /// the amount of wasm fuel (roughly, executed instructions) a single callback of the plugin
+    /// (eg. `update` or `render`) can consume before it is stopped
+    pub fuel_budget: Option<u64>,
+    /// the maximum size of each of the plugin's linear memories, in megabytes
+    pub max_memory_mb: Option<usize>,
+    /// the maximum number of elements in each of the plugin's tables
+    pub max_table_elements: Option<usize>,
+    /// how many `RunCommand` and `WebRequest` jobs of the plugin can be in flight at once
+    pub max_pending_jobs: Option<usize>,
+    /// how many timers the plugin can set in the span of a second
+    pub max_timers_per_second: Option<usize>,
+}
+
+impl PluginResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self == &PluginResourceLimits::default()
+    }
 }
 
 impl RunPlugin {
@@ -446,6 +537,18 @@ impl RunPlugin {
         self.initial_cwd = initial_cwd;
         self
     }
+    pub fn with_sha256(mut self, sha256: Option<String>) -> Self {
+        self.sha256 = sha256;
+        self
+    }
+    pub fn with_resource_limits(mut self, resource_limits: PluginResourceLimits) -> Self {
+        self.resource_limits = resource_limits;
+        self
+    }
+    pub fn with_allowed_hosts(mut self, allowed_hosts: Vec<String>) -> Self {
+        self.allowed_hosts = allowed_hosts;
+        self
+    }
     pub fn merge_configuration(mut self, configuration: &Option<BTreeMap<String, String>>) -> Self {
         if let Some(configuration) = configuration {
             self.configuration.merge(configuration);
@@ -544,6 +647,13 @@ impl PluginUserConfiguration {
         configuration.remove("title");
         configuration.remove("in_place");
         configuration.remove("skip_plugin_cache");
+        configuration.remove("sha256");
+        configuration.remove("fuel_budget");
+        configuration.remove("max_memory_mb");
+        configuration.remove("max_table_elements");
+        configuration.remove("max_pending_jobs");
+        configuration.remove("max_timers_per_second");
+        configuration.remove("allowed_hosts");
 
         PluginUserConfiguration(configuration)
     }
@@ -780,6 +890,76 @@ impl FromStr for PercentOrFixed {
     }
 }
 
+/// Continuously append the output of a pane to a file (`log_output` in layouts)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct LogOutput {
+    pub path: PathBuf,
+    /// Log plain text rather than the raw output with its ANSI escape sequences
+    pub strip_ansi: bool,
+    /// Prefix every line with the time it was written
+    pub timestamps: bool,
+    /// Rotate the log once it grows beyond this many bytes
+    pub max_size: Option<u64>,
+    /// How many rotated logs to keep (as `<path>.1`, `<path>.2`, ...)
+    pub max_files: Option<usize>,
+}
+
+/// A pane this pane waits for before running its command (`depends_on` in layouts)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct PaneDependency {
+    /// The name of the pane being waited for, in the same tab
+    pub pane_name: String,
+    pub readiness: ReadinessProbe,
+}
+
+/// How to tell that the pane being depended on is ready
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Default)]
+pub enum ReadinessProbe {
+    /// The pane's command exited successfully
+    #[default]
+    ExitSuccess,
+    /// A line of the pane's output (without its ANSI escape sequences) matches this regex
+    OutputMatches(String),
+    /// A TCP port on localhost accepts connections
+    PortOpen(u16),
+    /// A file exists at this path
+    FileExists(PathBuf),
+}
+
+impl fmt::Display for ReadinessProbe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadinessProbe::ExitSuccess => write!(f, "exit"),
+            ReadinessProbe::OutputMatches(regex) => write!(f, "output \"{}\"", regex),
+            ReadinessProbe::PortOpen(port) => write!(f, "port {}", port),
+            ReadinessProbe::FileExists(path) => write!(f, "file {}", path.display()),
+        }
+    }
+}
+
+/// Parses a size in bytes, optionally with a K, M or G (binary) suffix - eg. "512K" or "10M"
+pub fn parse_log_size(size: &str) -> Result<u64, String> {
+    let size = size.trim();
+    let (number, multiplier) = match size.char_indices().last() {
+        Some((i, 'k' | 'K')) => (&size[..i], 1024),
+        Some((i, 'm' | 'M')) => (&size[..i], 1024 * 1024),
+        Some((i, 'g' | 'G')) => (&size[..i], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .filter(|size| *size > 0)
+        .ok_or_else(|| {
+            format!(
+                "Invalid size: \"{}\" (expected eg. 4096, 512K or 10M)",
+                size
+            )
+        })
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct FloatingPaneLayout {
     pub name: Option<String>,
@@ -796,6 +976,11 @@ pub struct FloatingPaneLayout {
     pub logical_position: Option<usize>,
     pub default_fg: Option<String>,
     pub default_bg: Option<String>,
+    pub pane_scrollback_snapshot: Option<Vec<u8>>,
+    pub log_output: Option<LogOutput>,
+    pub monitors: Option<PaneMonitors>,
+    pub broadcast_group: Option<String>,
+    pub depends_on: Vec<PaneDependency>,
 }
 
 impl FloatingPaneLayout {
@@ -815,6 +1000,11 @@ impl FloatingPaneLayout {
             logical_position: None,
             default_fg: None,
             default_bg: None,
+            pane_scrollback_snapshot: None,
+            log_output: None,
+            monitors: None,
+            broadcast_group: None,
+            depends_on: vec![],
         }
     }
     pub fn add_cwd_to_layout(&mut self, cwd: &PathBuf) {
@@ -830,6 +1020,11 @@ impl FloatingPaneLayout {
             run.add_start_suspended(start_suspended);
         }
     }
+    pub fn clear_plugin_resource_limits(&mut self) {
+        if let Some(run) = self.run.as_mut() {
+            run.clear_plugin_resource_limits();
+        }
+    }
 }
 
 impl From<&TiledPaneLayout> for FloatingPaneLayout {
@@ -838,6 +1033,10 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            log_output: pane_layout.log_output.clone(),
+            monitors: pane_layout.monitors,
+            broadcast_group: pane_layout.broadcast_group.clone(),
+            depends_on: pane_layout.depends_on.clone(),
             ..Default::default()
         }
     }
@@ -861,6 +1060,11 @@ pub struct TiledPaneLayout {
     pub pane_initial_contents: Option<String>,
     pub default_fg: Option<String>,
     pub default_bg: Option<String>,
+    pub pane_scrollback_snapshot: Option<Vec<u8>>,
+    pub log_output: Option<LogOutput>,
+    pub monitors: Option<PaneMonitors>,
+    pub broadcast_group: Option<String>,
+    pub depends_on: Vec<PaneDependency>,
 }
 
 impl TiledPaneLayout {
@@ -1197,6 +1401,14 @@ impl TiledPaneLayout {
             child.recursively_add_start_suspended(start_suspended);
         }
     }
+    pub fn recursively_clear_plugin_resource_limits(&mut self) {
+        if let Some(run) = self.run.as_mut() {
+            run.clear_plugin_resource_limits();
+        }
+        for child in self.children.iter_mut() {
+            child.recursively_clear_plugin_resource_limits();
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -1326,6 +1538,7 @@ impl Layout {
     pub fn from_layout_info(
         layout_dir: &Option<PathBuf>,
         layout_info: LayoutInfo,
+        layout_args: BTreeMap<String, String>,
     ) -> Result<Layout, ConfigError> {
         let mut should_start_layout_commands_suspended = false;
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) = match layout_info {
@@ -1349,13 +1562,14 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout, None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_layout_args(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_args,
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1368,6 +1582,7 @@ impl Layout {
     pub fn from_layout_info_with_config(
         layout_dir: &Option<PathBuf>,
         layout_info: &LayoutInfo,
+        layout_args: BTreeMap<String, String>,
         config: Option<Config>,
     ) -> Result<(Layout, Config), ConfigError> {
         let mut should_start_layout_commands_suspended = false;
@@ -1392,13 +1607,14 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout.clone(), None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_layout_args(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_args,
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1406,7 +1622,11 @@ impl Layout {
                 .next()
                 .map(|l| l.recursively_add_start_suspended_including_template(Some(true)));
         }
-        let config = Config::from_kdl(&raw_layout, config)?; // this merges the two config, with
+        let base_config = config.clone().unwrap_or_default();
+        let mut config = Config::from_kdl(&raw_layout, config)?; // this merges the two config, with
+        if let LayoutInfo::Url(_) = layout_info {
+            config.keep_plugin_resource_limits_of(&base_config);
+        }
         layout.map(|l| (l, config))
     }
     pub fn stringified_from_path_or_default(
@@ -1460,40 +1680,60 @@ impl Layout {
     pub fn from_path_or_default(
         layout_path: Option<&PathBuf>,
         layout_dir: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>,
         config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
             Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+        let layout = Layout::from_kdl_with_layout_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_args,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(not(target_family = "wasm"))]
-    pub fn from_url(url: &str, config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        url: &str,
+        layout_args: BTreeMap<String, String>,
+        config: Config,
+    ) -> Result<(Layout, Config), ConfigError> {
         let raw_layout = Downloader::download_without_cache_blocking(url)
             .map_err(|e| ConfigError::DownloadError(format!("{}", e)))?;
-        let mut layout = Layout::from_kdl(&raw_layout, Some(url.into()), None, None)?;
+        let mut layout = Layout::from_kdl_with_layout_args(
+            &raw_layout,
+            Some(url.into()),
+            None,
+            None,
+            layout_args,
+        )?;
         layout.recursively_add_start_suspended_including_template(Some(true));
-        let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
+        let base_config = config.clone();
+        let mut config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
+        config.keep_plugin_resource_limits_of(&base_config);
         Ok((layout, config))
     }
     pub fn from_stringified_layout(
         stringified_layout: &str,
+        layout_args: BTreeMap<String, String>,
         config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
-        let layout = Layout::from_kdl(&stringified_layout, None, None, None)?;
+        let layout =
+            Layout::from_kdl_with_layout_args(&stringified_layout, None, None, None, layout_args)?;
         let config = Config::from_kdl(&stringified_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(target_family = "wasm")]
-    pub fn from_url(_url: &str, _config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        _url: &str,
+        _layout_args: BTreeMap<String, String>,
+        _config: Config,
+    ) -> Result<(Layout, Config), ConfigError> {
         Err(ConfigError::DownloadError(format!(
             "Unsupported platform, cannot download layout from the web"
         )))
@@ -1738,6 +1978,51 @@ impl Layout {
             }
         }
     }
+    /// Resets the resource limits of every plugin in the layout (including its template and swap
+    /// layouts) to the defaults, for layouts that do not come from the user (eg. ones supplied by
+    /// a plugin) and so should not be able to lift the limits of the plugins they start
+    pub fn clear_plugin_resource_limits(&mut self) {
+        let tiled_layouts = self
+            .template
+            .iter_mut()
+            .map(|(tiled_panes, _)| tiled_panes)
+            .chain(self.tabs.iter_mut().map(|(_, tiled_panes, _)| tiled_panes))
+            .chain(
+                self.swap_layouts
+                    .iter_mut()
+                    .map(|(tiled_panes, _)| tiled_panes),
+            )
+            .chain(
+                self.swap_tiled_layouts
+                    .iter_mut()
+                    .flat_map(|(swap_layouts, _)| swap_layouts.values_mut()),
+            );
+        for tiled_panes in tiled_layouts {
+            tiled_panes.recursively_clear_plugin_resource_limits();
+        }
+        let floating_layouts = self
+            .template
+            .iter_mut()
+            .map(|(_, floating_panes)| floating_panes)
+            .chain(
+                self.tabs
+                    .iter_mut()
+                    .map(|(_, _, floating_panes)| floating_panes),
+            )
+            .chain(
+                self.swap_layouts
+                    .iter_mut()
+                    .map(|(_, floating_panes)| floating_panes),
+            )
+            .chain(
+                self.swap_floating_layouts
+                    .iter_mut()
+                    .flat_map(|(swap_layouts, _)| swap_layouts.values_mut()),
+            );
+        for floating_pane in floating_layouts.flatten() {
+            floating_pane.clear_plugin_resource_limits();
+        }
+    }
     fn swap_layout_and_path(path: &Path) -> Option<(String, String)> {
         // Option<path, stringified_swap_layout>
         let mut swap_layout_path = PathBuf::from(path);
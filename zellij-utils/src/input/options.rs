@@ -246,6 +246,12 @@ pub struct Options {
     #[serde(default)]
     pub scrollback_lines_to_serialize: Option<usize>,
 
+    /// Whether the full styled scrollback of terminal panes is saved in a binary snapshot along
+    /// with the session and restored when it is resurrected, default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub serialize_pane_scrollback: Option<bool>,
+
     /// Whether to use ANSI styled underlines
     #[clap(long, value_parser)]
     #[serde(default)]
@@ -534,6 +540,9 @@ impl Options {
         let scrollback_lines_to_serialize = other
             .scrollback_lines_to_serialize
             .or(self.scrollback_lines_to_serialize);
+        let serialize_pane_scrollback = other
+            .serialize_pane_scrollback
+            .or(self.serialize_pane_scrollback);
         let styled_underlines = other.styled_underlines.or(self.styled_underlines);
         let serialization_interval = other.serialization_interval.or(self.serialization_interval);
         let disable_session_metadata = other
@@ -617,6 +626,7 @@ impl Options {
             session_serialization,
             serialize_pane_viewport,
             scrollback_lines_to_serialize,
+            serialize_pane_scrollback,
             styled_underlines,
             serialization_interval,
             disable_session_metadata,
@@ -675,6 +685,10 @@ impl Options {
             merge_bool(other.session_serialization, self.session_serialization);
         let serialize_pane_viewport =
             merge_bool(other.serialize_pane_viewport, self.serialize_pane_viewport);
+        let serialize_pane_scrollback = merge_bool(
+            other.serialize_pane_scrollback,
+            self.serialize_pane_scrollback,
+        );
 
         let default_mode = other.default_mode.or(self.default_mode);
         let default_shell = other.default_shell.or_else(|| self.default_shell.clone());
@@ -784,6 +798,7 @@ impl Options {
             session_serialization,
             serialize_pane_viewport,
             scrollback_lines_to_serialize,
+            serialize_pane_scrollback,
             styled_underlines,
             serialization_interval,
             disable_session_metadata,
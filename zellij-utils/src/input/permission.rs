@@ -2,28 +2,61 @@ use std::{
     collections::HashMap,
     fs::{self, File},
     io::Write,
-    path::PathBuf,
+    path::{Component, Path, PathBuf},
 };
 
-use crate::{consts::ZELLIJ_PLUGIN_PERMISSIONS_CACHE, data::PermissionType};
+use url::Url;
+
+use crate::{
+    consts::ZELLIJ_PLUGIN_PERMISSIONS_CACHE,
+    data::{FilesystemPermission, GrantedPluginPermissions, PermissionType},
+};
 
 pub type GrantedPermission = HashMap<String, Vec<PermissionType>>;
+pub type GrantedFilesystemPermission = HashMap<String, Vec<FilesystemPermission>>;
+// the `allowed_hosts` a plugin was granted web access with, an empty list is unrestricted access
+pub type GrantedWebAccess = HashMap<String, Vec<String>>;
 
 #[derive(Default, Debug)]
 pub struct PermissionCache {
     path: PathBuf,
     granted: GrantedPermission,
+    granted_filesystem: GrantedFilesystemPermission,
+    granted_web_access: GrantedWebAccess,
 }
 
 impl PermissionCache {
     pub fn cache(&mut self, plugin_name: String, permissions: Vec<PermissionType>) {
+        if !permissions.contains(&PermissionType::WebAccess) {
+            self.granted_web_access.remove(&plugin_name);
+        }
         self.granted.insert(plugin_name, permissions);
     }
 
+    pub fn cache_filesystem_permissions(
+        &mut self,
+        plugin_name: String,
+        filesystem_permissions: Vec<FilesystemPermission>,
+    ) {
+        if filesystem_permissions.is_empty() {
+            self.granted_filesystem.remove(&plugin_name);
+        } else {
+            self.granted_filesystem
+                .insert(plugin_name, filesystem_permissions);
+        }
+    }
+
     pub fn get_permissions(&self, plugin_name: String) -> Option<&Vec<PermissionType>> {
         self.granted.get(&plugin_name)
     }
 
+    pub fn get_filesystem_permissions(
+        &self,
+        plugin_name: String,
+    ) -> Option<&Vec<FilesystemPermission>> {
+        self.granted_filesystem.get(&plugin_name)
+    }
+
     pub fn check_permissions(
         &self,
         plugin_name: String,
@@ -42,26 +75,563 @@ impl PermissionCache {
         false
     }
 
+    pub fn check_filesystem_permissions(
+        &self,
+        plugin_name: String,
+        filesystem_permissions_to_check: &[FilesystemPermission],
+    ) -> bool {
+        if filesystem_permissions_to_check.is_empty() {
+            return true;
+        }
+        match self.granted_filesystem.get(&plugin_name) {
+            Some(granted) => filesystem_permissions_to_check
+                .iter()
+                .all(|requested| granted.iter().any(|g| g.covers(requested))),
+            None => false,
+        }
+    }
+
+    /// Whether the plugin was granted web access for at least the hosts in `allowed_hosts`
+    pub fn check_web_access(&self, plugin_name: String, allowed_hosts: &[String]) -> bool {
+        match self.granted_web_access.get(&plugin_name) {
+            Some(granted) if granted.is_empty() => true,
+            Some(granted) => {
+                !allowed_hosts.is_empty() && allowed_hosts.iter().all(|h| granted.contains(h))
+            },
+            None => false,
+        }
+    }
+
+    /// Records that the plugin was granted web access with `allowed_hosts`, adding them to the
+    /// hosts it was granted before
+    pub fn grant_web_access(&mut self, plugin_name: String, allowed_hosts: Vec<String>) {
+        match self.granted_web_access.get_mut(&plugin_name) {
+            Some(granted) if granted.is_empty() => {},
+            Some(granted) if !allowed_hosts.is_empty() => {
+                for allowed_host in allowed_hosts {
+                    if !granted.contains(&allowed_host) {
+                        granted.push(allowed_host);
+                    }
+                }
+            },
+            _ => {
+                self.granted_web_access.insert(plugin_name, allowed_hosts);
+            },
+        }
+    }
+
+    /// Adds `permissions` to those already granted to the plugin, web access granted this way is
+    /// not restricted to any hosts
+    pub fn grant(&mut self, plugin_name: String, permissions: Vec<PermissionType>) {
+        if permissions.contains(&PermissionType::WebAccess) {
+            self.grant_web_access(plugin_name.clone(), vec![]);
+        }
+        let granted = self.granted.entry(plugin_name).or_default();
+        for permission in permissions {
+            if !granted.contains(&permission) {
+                granted.push(permission);
+            }
+        }
+    }
+
+    /// Adds `filesystem_permissions` to the folders already granted to the plugin, a folder granted
+    /// both read-only and read-write keeps the read-write grant
+    pub fn grant_filesystem_permissions(
+        &mut self,
+        plugin_name: String,
+        filesystem_permissions: Vec<FilesystemPermission>,
+    ) {
+        if filesystem_permissions.is_empty() {
+            return;
+        }
+        let granted = self.granted_filesystem.entry(plugin_name).or_default();
+        for permission in filesystem_permissions {
+            if granted.iter().any(|g| g.covers(&permission)) {
+                continue;
+            }
+            granted.retain(|g| !permission.covers(g));
+            granted.push(permission);
+        }
+    }
+
+    /// Removes `permissions` from those granted to the plugin, or forgets the plugin entirely
+    /// (including its filesystem permissions) if `permissions` is empty. Returns false if the
+    /// plugin had none of them.
+    pub fn revoke(&mut self, plugin_name: String, permissions: &[PermissionType]) -> bool {
+        if permissions.is_empty() || permissions.contains(&PermissionType::WebAccess) {
+            self.granted_web_access.remove(&plugin_name);
+        }
+        if permissions.is_empty() {
+            let had_filesystem_permissions = self.granted_filesystem.remove(&plugin_name).is_some();
+            return self.granted.remove(&plugin_name).is_some() || had_filesystem_permissions;
+        }
+        match self.granted.get_mut(&plugin_name) {
+            Some(granted) => {
+                let count_before = granted.len();
+                granted.retain(|p| !permissions.contains(p));
+                granted.len() != count_before
+            },
+            None => false,
+        }
+    }
+
+    /// Everything granted, sorted by plugin URL
+    pub fn list(&self) -> Vec<GrantedPluginPermissions> {
+        let mut plugin_urls: Vec<&String> = self
+            .granted
+            .keys()
+            .chain(self.granted_filesystem.keys())
+            .collect();
+        plugin_urls.sort();
+        plugin_urls.dedup();
+        plugin_urls
+            .into_iter()
+            .map(|plugin_url| {
+                let mut permissions = self.granted.get(plugin_url).cloned().unwrap_or_default();
+                permissions.sort();
+                let mut filesystem_permissions = self
+                    .granted_filesystem
+                    .get(plugin_url)
+                    .cloned()
+                    .unwrap_or_default();
+                filesystem_permissions.sort();
+                GrantedPluginPermissions {
+                    plugin_url: plugin_url.clone(),
+                    permissions,
+                    filesystem_permissions,
+                }
+            })
+            .collect()
+    }
+
     pub fn from_path_or_default(cache_path: Option<PathBuf>) -> Self {
         let cache_path = cache_path.unwrap_or(ZELLIJ_PLUGIN_PERMISSIONS_CACHE.to_path_buf());
 
-        let granted = match fs::read_to_string(cache_path.clone()) {
-            Ok(raw_string) => PermissionCache::from_string(raw_string).unwrap_or_default(),
-            Err(e) => {
-                log::error!("Failed to read permission cache file: {}", e);
-                GrantedPermission::default()
-            },
-        };
+        let (granted, granted_filesystem, granted_web_access) =
+            match fs::read_to_string(cache_path.clone()) {
+                Ok(raw_string) => PermissionCache::from_string(raw_string).unwrap_or_default(),
+                Err(e) => {
+                    log::error!("Failed to read permission cache file: {}", e);
+                    Default::default()
+                },
+            };
 
         PermissionCache {
             path: cache_path,
             granted,
+            granted_filesystem,
+            granted_web_access,
         }
     }
 
     pub fn write_to_file(&self) -> std::io::Result<()> {
         let mut f = File::create(&self.path)?;
-        write!(f, "{}", PermissionCache::to_string(&self.granted))?;
+        write!(
+            f,
+            "{}",
+            PermissionCache::to_string(
+                &self.granted,
+                &self.granted_filesystem,
+                &self.granted_web_access
+            )
+        )?;
         Ok(())
     }
 }
+
+/// The existing host folders matched by the path of a `FilesystemPermission`, after expanding a
+/// leading `~` and any `*` or `?` wildcards in its components
+pub fn host_folders_for_permission(filesystem_permission: &FilesystemPermission) -> Vec<PathBuf> {
+    let expanded = shellexpand::tilde(&filesystem_permission.path).to_string();
+    let expanded = PathBuf::from(expanded);
+    if !expanded.is_absolute() {
+        log::error!(
+            "Filesystem permissions must name absolute paths, ignoring: {}",
+            filesystem_permission.path
+        );
+        return vec![];
+    }
+    let mut candidates = vec![PathBuf::new()];
+    for component in expanded.components() {
+        match component {
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                if name.contains(['*', '?']) {
+                    candidates = candidates
+                        .iter()
+                        .flat_map(|candidate| matching_children(candidate, &name))
+                        .collect();
+                } else {
+                    for candidate in candidates.iter_mut() {
+                        candidate.push(name.as_ref());
+                    }
+                }
+            },
+            // ".." would allow a wildcard to escape the folder it was meant to be limited to
+            Component::ParentDir => return vec![],
+            Component::CurDir => {},
+            component => {
+                for candidate in candidates.iter_mut() {
+                    candidate.push(component.as_os_str());
+                }
+            },
+        }
+    }
+    candidates.retain(|candidate| candidate.is_dir());
+    candidates
+}
+
+fn matching_children(folder: &Path, pattern: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+    let mut children: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| wildcard_match(pattern, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+    children
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            last_star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = last_star {
+            // let the last star swallow one more character and try again
+            p = star_p + 1;
+            n = star_n + 1;
+            last_star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// an entry of a plugin's `allowed_hosts`: `example.com` is that exact host, `*.example.com` any
+// of its subdomains and `https://example.com/api/` any URL under that scheme, host, port and path
+enum AllowedHost {
+    Host(String),
+    Subdomains(String),
+    UrlPrefix(Url),
+}
+
+impl AllowedHost {
+    fn parse(entry: &str) -> Option<Self> {
+        if entry.contains("://") {
+            Url::parse(entry)
+                .ok()
+                .filter(|url| url.host_str().is_some())
+                .map(AllowedHost::UrlPrefix)
+        } else if entry.contains(|c: char| c == '/' || c == '@' || c.is_whitespace()) {
+            None
+        } else if let Some(domain) = entry.strip_prefix("*.") {
+            (!domain.is_empty() && !domain.contains('*'))
+                .then(|| AllowedHost::Subdomains(domain.to_ascii_lowercase()))
+        } else {
+            (!entry.is_empty() && !entry.contains('*'))
+                .then(|| AllowedHost::Host(entry.to_ascii_lowercase()))
+        }
+    }
+    fn allows(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        match self {
+            AllowedHost::Host(allowed_host) => host == allowed_host,
+            AllowedHost::Subdomains(domain) => host
+                .strip_suffix(domain.as_str())
+                .map(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.'))
+                .unwrap_or(false),
+            AllowedHost::UrlPrefix(prefix) => {
+                url.scheme() == prefix.scheme()
+                    && Some(host) == prefix.host_str()
+                    && url.port_or_known_default() == prefix.port_or_known_default()
+                    && url
+                        .path()
+                        .strip_prefix(prefix.path())
+                        .map(|rest| {
+                            // `https://example.com/v1` should not allow `https://example.com/v1-admin`
+                            prefix.path().ends_with('/') || rest.is_empty() || rest.starts_with('/')
+                        })
+                        .unwrap_or(false)
+            },
+        }
+    }
+}
+
+/// Whether `entry` can be used in a plugin's `allowed_hosts`
+pub fn is_valid_allowed_host(entry: &str) -> bool {
+    AllowedHost::parse(entry).is_some()
+}
+
+/// Whether a plugin restricted to `allowed_hosts` can send a web request to `url`, an empty list
+/// places no restriction
+pub fn web_request_is_allowed(allowed_hosts: &[String], url: &str) -> bool {
+    if allowed_hosts.is_empty() {
+        return true;
+    }
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    allowed_hosts
+        .iter()
+        .filter_map(|entry| AllowedHost::parse(entry))
+        .any(|allowed_host| allowed_host.allows(&url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_can_be_granted_revoked_and_listed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut cache =
+            PermissionCache::from_path_or_default(Some(temp_dir.path().join("permissions.kdl")));
+        cache.grant(
+            "file:/b.wasm".to_owned(),
+            vec![PermissionType::RunCommands, PermissionType::WebAccess],
+        );
+        cache.grant(
+            "file:/a.wasm".to_owned(),
+            vec![PermissionType::ReadApplicationState],
+        );
+        cache.grant(
+            "file:/a.wasm".to_owned(),
+            vec![
+                PermissionType::ReadApplicationState,
+                PermissionType::OpenFiles,
+            ],
+        );
+        cache.cache_filesystem_permissions(
+            "file:/c.wasm".to_owned(),
+            vec![FilesystemPermission::read_only("~/notes")],
+        );
+
+        assert!(cache.revoke("file:/b.wasm".to_owned(), &[PermissionType::WebAccess]));
+        assert!(!cache.revoke("file:/b.wasm".to_owned(), &[PermissionType::WebAccess]));
+        assert!(cache.revoke("file:/c.wasm".to_owned(), &[]));
+        assert_eq!(
+            cache.list(),
+            vec![
+                GrantedPluginPermissions {
+                    plugin_url: "file:/a.wasm".to_owned(),
+                    permissions: vec![
+                        PermissionType::ReadApplicationState,
+                        PermissionType::OpenFiles
+                    ],
+                    filesystem_permissions: vec![],
+                },
+                GrantedPluginPermissions {
+                    plugin_url: "file:/b.wasm".to_owned(),
+                    permissions: vec![PermissionType::RunCommands],
+                    filesystem_permissions: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn web_access_is_only_cached_for_the_hosts_it_was_granted_for() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("permissions.kdl");
+        let mut cache = PermissionCache::from_path_or_default(Some(cache_path.clone()));
+        let plugin_name = "file:/plugin.wasm".to_owned();
+        cache.cache(plugin_name.clone(), vec![PermissionType::WebAccess]);
+        cache.grant_web_access(plugin_name.clone(), vec!["api.example.com".to_owned()]);
+        cache.grant_web_access(plugin_name.clone(), vec!["*.wttr.in".to_owned()]);
+        cache.write_to_file().unwrap();
+
+        let mut cache = PermissionCache::from_path_or_default(Some(cache_path));
+        assert!(cache.check_web_access(
+            plugin_name.clone(),
+            &["*.wttr.in".to_owned(), "api.example.com".to_owned()]
+        ));
+        assert!(!cache.check_web_access(plugin_name.clone(), &["example.com".to_owned()]));
+        assert!(!cache.check_web_access(plugin_name.clone(), &[]));
+
+        cache.grant_web_access(plugin_name.clone(), vec![]);
+        assert!(cache.check_web_access(plugin_name.clone(), &[]));
+        cache.grant_web_access(plugin_name.clone(), vec!["example.com".to_owned()]);
+        assert!(cache.check_web_access(plugin_name.clone(), &[]));
+
+        cache.cache(
+            plugin_name.clone(),
+            vec![PermissionType::ReadApplicationState],
+        );
+        assert!(!cache.check_web_access(plugin_name, &["api.example.com".to_owned()]));
+    }
+
+    #[test]
+    fn filesystem_permissions_are_added_to_earlier_grants() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut cache =
+            PermissionCache::from_path_or_default(Some(temp_dir.path().join("permissions.kdl")));
+        let plugin_name = "file:/plugin.wasm".to_owned();
+        cache.grant_filesystem_permissions(
+            plugin_name.clone(),
+            vec![
+                FilesystemPermission::read_only("~/notes"),
+                FilesystemPermission::read_only("/tmp/scratch"),
+            ],
+        );
+        cache.grant_filesystem_permissions(plugin_name.clone(), vec![]);
+        cache.grant_filesystem_permissions(
+            plugin_name.clone(),
+            vec![
+                FilesystemPermission::read_write("/tmp/scratch"),
+                FilesystemPermission::read_only("~/todo"),
+            ],
+        );
+        cache.grant_filesystem_permissions(
+            plugin_name.clone(),
+            vec![FilesystemPermission::read_only("/tmp/scratch")],
+        );
+        assert_eq!(
+            cache.get_filesystem_permissions(plugin_name),
+            Some(&vec![
+                FilesystemPermission::read_only("~/notes"),
+                FilesystemPermission::read_write("/tmp/scratch"),
+                FilesystemPermission::read_only("~/todo"),
+            ])
+        );
+    }
+
+    #[test]
+    fn wildcards_match_within_a_single_path_component() {
+        assert!(wildcard_match("*", "notes"));
+        assert!(wildcard_match("no*s", "notes"));
+        assert!(wildcard_match("n?tes", "notes"));
+        assert!(wildcard_match("*.md", "todo.md"));
+        assert!(!wildcard_match("*.md", "todo.txt"));
+        assert!(!wildcard_match("n?tes", "nootes"));
+    }
+
+    #[test]
+    fn host_folders_are_expanded_from_globs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for folder in ["a/docs", "b/docs", "c/src"] {
+            fs::create_dir_all(temp_dir.path().join(folder)).unwrap();
+        }
+        fs::write(temp_dir.path().join("d"), "not a folder").unwrap();
+        let glob = format!("{}/*/docs", temp_dir.path().display());
+        assert_eq!(
+            host_folders_for_permission(&FilesystemPermission::read_only(glob)),
+            vec![
+                temp_dir.path().join("a/docs"),
+                temp_dir.path().join("b/docs")
+            ]
+        );
+        let missing = format!("{}/missing", temp_dir.path().display());
+        assert!(host_folders_for_permission(&FilesystemPermission::read_only(missing)).is_empty());
+        let escaping = format!("{}/*/..", temp_dir.path().display());
+        assert!(host_folders_for_permission(&FilesystemPermission::read_only(escaping)).is_empty());
+    }
+
+    #[test]
+    fn filesystem_permissions_survive_a_round_trip_through_the_cache_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_path = temp_dir.path().join("permissions.kdl");
+        let mut cache = PermissionCache::from_path_or_default(Some(cache_path.clone()));
+        cache.cache(
+            "file:/plugin.wasm".to_owned(),
+            vec![PermissionType::ReadApplicationState],
+        );
+        cache.cache_filesystem_permissions(
+            "file:/plugin.wasm".to_owned(),
+            vec![
+                FilesystemPermission::read_only("~/notes"),
+                FilesystemPermission::read_write("/tmp/scratch"),
+            ],
+        );
+        cache.write_to_file().unwrap();
+
+        let cache = PermissionCache::from_path_or_default(Some(cache_path));
+        assert!(cache.check_permissions(
+            "file:/plugin.wasm".to_owned(),
+            &vec![PermissionType::ReadApplicationState]
+        ));
+        assert!(cache.check_filesystem_permissions(
+            "file:/plugin.wasm".to_owned(),
+            &[
+                FilesystemPermission::read_only("~/notes"),
+                FilesystemPermission::read_only("/tmp/scratch")
+            ]
+        ));
+        assert!(!cache.check_filesystem_permissions(
+            "file:/plugin.wasm".to_owned(),
+            &[FilesystemPermission::read_write("~/notes")]
+        ));
+    }
+
+    #[test]
+    fn web_requests_are_restricted_to_allowed_hosts() {
+        let allowed_hosts = vec![
+            "api.open-meteo.com".to_owned(),
+            "*.example.com".to_owned(),
+            "https://wttr.in/api/".to_owned(),
+            "https://api.example.org/v1".to_owned(),
+        ];
+        assert!(web_request_is_allowed(&[], "https://anywhere.io/upload"));
+        assert!(web_request_is_allowed(
+            &allowed_hosts,
+            "https://API.open-meteo.com/v1/forecast?latitude=52"
+        ));
+        assert!(web_request_is_allowed(
+            &allowed_hosts,
+            "http://eu.cdn.example.com/"
+        ));
+        assert!(web_request_is_allowed(
+            &allowed_hosts,
+            "https://wttr.in/api/berlin"
+        ));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "https://example.com"
+        ));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "https://notexample.com"
+        ));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "https://api.open-meteo.com.evil.io/"
+        ));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "https://api.open-meteo.com@evil.io/"
+        ));
+        assert!(!web_request_is_allowed(&allowed_hosts, "https://wttr.in/"));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "http://wttr.in/api/berlin"
+        ));
+        assert!(web_request_is_allowed(
+            &allowed_hosts,
+            "https://api.example.org/v1"
+        ));
+        assert!(web_request_is_allowed(
+            &allowed_hosts,
+            "https://api.example.org/v1/users"
+        ));
+        assert!(!web_request_is_allowed(
+            &allowed_hosts,
+            "https://api.example.org/v1-admin/users"
+        ));
+        assert!(!web_request_is_allowed(&allowed_hosts, "not a url"));
+        assert!(!is_valid_allowed_host("example.com/path"));
+        assert!(!is_valid_allowed_host("*."));
+        assert!(!is_valid_allowed_host("https://"));
+    }
+}
@@ -7,7 +7,7 @@ use thiserror::Error;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use super::layout::{PluginUserConfiguration, RunPlugin, RunPluginLocation};
+use super::layout::{PluginResourceLimits, PluginUserConfiguration, RunPlugin, RunPluginLocation};
 #[cfg(not(target_family = "wasm"))]
 use crate::consts::ASSET_MAP;
 use crate::consts::BUILTIN_PLUGIN_NAMES;
@@ -44,6 +44,12 @@ pub struct PluginConfig {
     pub initial_userspace_configuration: PluginUserConfiguration,
     /// plugin initial working directory
     pub initial_cwd: Option<PathBuf>,
+    /// Expected sha256 digest of the plugin's wasm, only used for remote plugins
+    pub sha256: Option<String>,
+    /// Limits on the resources this plugin can use
+    pub resource_limits: PluginResourceLimits,
+    /// Hosts and URL prefixes this plugin can send web requests to, empty for any
+    pub allowed_hosts: Vec<String>,
 }
 
 impl PluginConfig {
@@ -55,6 +61,9 @@ impl PluginConfig {
                 location: run_plugin.location.clone(),
                 initial_userspace_configuration: run_plugin.configuration.clone(),
                 initial_cwd: run_plugin.initial_cwd.clone(),
+                sha256: run_plugin.sha256.clone(),
+                resource_limits: run_plugin.resource_limits.clone(),
+                allowed_hosts: run_plugin.allowed_hosts.clone(),
             }),
             RunPluginLocation::Zellij(tag) => {
                 let tag = tag.to_string();
@@ -66,6 +75,9 @@ impl PluginConfig {
                             .ok()?,
                         initial_userspace_configuration: run_plugin.configuration.clone(),
                         initial_cwd: run_plugin.initial_cwd.clone(),
+                        sha256: run_plugin.sha256.clone(),
+                        resource_limits: run_plugin.resource_limits.clone(),
+                        allowed_hosts: run_plugin.allowed_hosts.clone(),
                     })
                 } else {
                     None
@@ -77,9 +89,23 @@ impl PluginConfig {
                 location: run_plugin.location.clone(),
                 initial_userspace_configuration: run_plugin.configuration.clone(),
                 initial_cwd: run_plugin.initial_cwd.clone(),
+                sha256: run_plugin.sha256.clone(),
+                resource_limits: run_plugin.resource_limits.clone(),
+                allowed_hosts: run_plugin.allowed_hosts.clone(),
             }),
         }
     }
+    /// Plugins loaded from a file with `dev_mode true` in their configuration are reloaded
+    /// whenever their wasm file changes on disk
+    pub fn is_in_dev_mode(&self) -> bool {
+        matches!(self.location, RunPluginLocation::File(_))
+            && self
+                .initial_userspace_configuration
+                .inner()
+                .get("dev_mode")
+                .map(|dev_mode| dev_mode == "true")
+                .unwrap_or(false)
+    }
     /// Resolve wasm plugin bytes for the plugin path and given plugin directory.
     ///
     /// If zellij was built without the 'disable_automatic_asset_installation' feature, builtin
@@ -1,4 +1,5 @@
 use super::super::layout::*;
+use crate::home::xdg_data_dir;
 use insta::assert_snapshot;
 
 #[cfg(not(windows))]
@@ -2535,3 +2536,1217 @@ fn tiled_pane_still_rejects_zero_percent() {
     let result = SplitSize::from_str("1%");
     assert!(result.is_ok());
 }
+
+#[test]
+fn layout_with_pane_output_log() {
+    let kdl_layout = r#"
+        layout {
+            pane log_output="/home/user/layouts/pane.log"
+            pane command="cargo" {
+                log_output "/home/user/layouts/ci.log" strip_ansi=true timestamps=true max_size="10M" max_files=3
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("/home/user/layouts/ci.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    let panes = &layout.template.unwrap().0.children;
+    assert_eq!(
+        panes[0].log_output,
+        Some(LogOutput {
+            path: PathBuf::from("/home/user/layouts/pane.log"),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        panes[1].log_output,
+        Some(LogOutput {
+            path: PathBuf::from("/home/user/layouts/ci.log"),
+            strip_ansi: true,
+            timestamps: true,
+            max_size: Some(10 * 1024 * 1024),
+            max_files: Some(3),
+        })
+    );
+}
+
+#[test]
+fn layout_with_floating_pane_output_log() {
+    let kdl_layout = r#"
+        layout {
+            floating_panes {
+                pane {
+                    log_output "floating.log" max_size=4096
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("/home/user/layouts/ci.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        layout.template.unwrap().1[0].log_output,
+        Some(LogOutput {
+            path: PathBuf::from("/home/user/layouts/floating.log"),
+            max_size: Some(4096),
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn relative_pane_output_log_is_relative_to_the_layout_file() {
+    let kdl_layout = r#"
+        layout {
+            pane log_output="logs/pane.log"
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("/home/user/layouts/ci.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        layout.template.unwrap().0.children[0]
+            .log_output
+            .as_ref()
+            .map(|l| l.path.clone()),
+        Some(PathBuf::from("/home/user/layouts/logs/pane.log"))
+    );
+}
+
+#[test]
+fn pane_output_log_with_invalid_max_size_errors() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                log_output "pane.log" max_size="lots"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("/home/user/layouts/ci.kdl".into()),
+        None,
+        None,
+    );
+    assert!(layout.is_err(), "invalid max_size should error");
+}
+
+#[test]
+fn remote_layout_cannot_load_a_scrollback_snapshot() {
+    let kdl_layout = r#"
+        layout {
+            pane scrollback_snapshot_file="/etc/passwd"
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("https://example.com/layout.kdl".into()),
+        None,
+        None,
+    );
+    assert!(
+        layout.is_err(),
+        "scrollback_snapshot_file should be rejected in remote layouts"
+    );
+}
+
+#[test]
+fn pane_output_log_outside_of_the_layout_folder_errors() {
+    for log_path in ["/home/user/.bashrc", "../.bashrc", "logs/../../.bashrc"] {
+        let kdl_layout = format!(
+            r#"
+            layout {{
+                pane log_output="{}"
+            }}
+        "#,
+            log_path
+        );
+        let layout = Layout::from_kdl(
+            &kdl_layout,
+            Some("/home/user/layouts/ci.kdl".into()),
+            None,
+            None,
+        );
+        assert!(layout.is_err(), "{} should not be allowed", log_path);
+    }
+}
+
+#[test]
+fn pane_output_log_can_be_in_the_zellij_data_folder() {
+    let log_path = xdg_data_dir().join("logs").join("ci.log");
+    let kdl_layout = format!(
+        r#"
+        layout {{
+            pane log_output="{}"
+        }}
+    "#,
+        log_path.display()
+    );
+    let layout = Layout::from_kdl(
+        &kdl_layout,
+        Some("/home/user/layouts/ci.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        layout.template.unwrap().0.children[0]
+            .log_output
+            .as_ref()
+            .map(|l| l.path.clone()),
+        Some(log_path)
+    );
+}
+
+#[test]
+fn remote_layout_cannot_log_pane_output() {
+    let kdl_layout = r#"
+        layout {
+            pane log_output="pane.log"
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("https://example.com/layout.kdl".into()),
+        None,
+        None,
+    );
+    assert!(
+        layout.is_err(),
+        "log_output should be rejected in remote layouts"
+    );
+}
+
+#[test]
+fn layout_cannot_include_configuration_files() {
+    let kdl_layout = r#"
+        include "/etc/passwd"
+        layout {
+            pane
+        }
+    "#;
+    let layout_and_config =
+        Layout::from_stringified_layout(kdl_layout, BTreeMap::new(), Config::default());
+    assert!(
+        layout_and_config.is_err(),
+        "include should be rejected in layouts"
+    );
+}
+
+#[test]
+fn log_sizes_accept_binary_suffixes() {
+    assert_eq!(parse_log_size("4096"), Ok(4096));
+    assert_eq!(parse_log_size("512K"), Ok(512 * 1024));
+    assert_eq!(parse_log_size("10m"), Ok(10 * 1024 * 1024));
+    assert_eq!(parse_log_size("1G"), Ok(1024 * 1024 * 1024));
+    assert!(parse_log_size("0").is_err());
+    assert!(parse_log_size("10MB").is_err());
+    assert!(parse_log_size("").is_err());
+}
+
+#[test]
+fn layout_with_pane_monitors() {
+    use crate::data::PaneMonitors;
+    let kdl_layout = r#"
+        layout {
+            pane monitor_activity=true
+            pane command="cargo" monitor_silence=30
+            floating_panes {
+                pane monitor_activity=true monitor_silence=5
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, floating_panes) = layout.template.unwrap();
+    assert_eq!(
+        tiled_panes.children[0].monitors,
+        Some(PaneMonitors {
+            activity: true,
+            silence_secs: None,
+        })
+    );
+    assert_eq!(
+        tiled_panes.children[1].monitors,
+        Some(PaneMonitors {
+            activity: false,
+            silence_secs: Some(30),
+        })
+    );
+    assert_eq!(
+        floating_panes[0].monitors,
+        Some(PaneMonitors {
+            activity: true,
+            silence_secs: Some(5),
+        })
+    );
+}
+
+#[test]
+fn pane_monitors_are_merged_with_pane_template_monitors() {
+    use crate::data::PaneMonitors;
+    let kdl_layout = r#"
+        layout {
+            pane_template name="watched" monitor_activity=true
+            watched monitor_silence=60
+            watched
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let panes = layout.template.unwrap().0.children;
+    assert_eq!(
+        panes[0].monitors,
+        Some(PaneMonitors {
+            activity: true,
+            silence_secs: Some(60),
+        })
+    );
+    assert_eq!(
+        panes[1].monitors,
+        Some(PaneMonitors {
+            activity: true,
+            silence_secs: None,
+        })
+    );
+}
+
+#[test]
+fn pane_monitor_silence_must_be_positive() {
+    let kdl_layout = r#"
+        layout {
+            pane monitor_silence=0
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "zero monitor_silence should error");
+}
+
+#[test]
+fn layout_with_pane_broadcast_groups() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="node" broadcast_group="nodes"
+            node
+            pane
+            node broadcast_group="workers"
+            floating_panes {
+                pane broadcast_group="nodes"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, floating_panes) = layout.template.unwrap();
+    assert_eq!(
+        tiled_panes.children[0].broadcast_group,
+        Some("nodes".to_owned())
+    );
+    assert_eq!(tiled_panes.children[1].broadcast_group, None);
+    assert_eq!(
+        tiled_panes.children[2].broadcast_group,
+        Some("workers".to_owned())
+    );
+    assert_eq!(floating_panes[0].broadcast_group, Some("nodes".to_owned()));
+}
+
+#[test]
+fn layout_with_pinned_remote_plugin() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="https://example.com/plugin.wasm" sha256="2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae" {
+                    some_config "value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|r| r.get_run_plugin())
+        .unwrap();
+    assert_eq!(
+        run_plugin.sha256.as_deref(),
+        Some("2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae")
+    );
+    assert!(run_plugin.configuration.inner().get("sha256").is_none());
+}
+
+#[test]
+fn plugin_sha256_must_be_a_sha256_digest() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="https://example.com/plugin.wasm" sha256="not-a-digest"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "malformed sha256 should error");
+}
+
+#[test]
+fn plugin_sha256_is_only_allowed_for_remote_plugins() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="zellij:tab-bar" sha256="2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "sha256 on a builtin plugin should error");
+}
+
+#[test]
+fn layout_with_plugin_fuel_budget() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" fuel_budget=5000 {
+                    some_config "value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|r| r.get_run_plugin())
+        .unwrap();
+    assert_eq!(run_plugin.resource_limits.fuel_budget, Some(5000));
+    assert!(run_plugin
+        .configuration
+        .inner()
+        .get("fuel_budget")
+        .is_none());
+}
+
+#[test]
+fn layout_with_plugin_resource_limits() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" max_memory_mb=64 {
+                    max_table_elements 10000
+                    max_pending_jobs 4
+                    max_timers_per_second 10
+                    some_config "value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|r| r.get_run_plugin())
+        .unwrap();
+    assert_eq!(
+        run_plugin.resource_limits,
+        PluginResourceLimits {
+            fuel_budget: None,
+            max_memory_mb: Some(64),
+            max_table_elements: Some(10000),
+            max_pending_jobs: Some(4),
+            max_timers_per_second: Some(10),
+        }
+    );
+    assert_eq!(run_plugin.configuration.inner().len(), 1);
+    assert_eq!(
+        run_plugin.configuration.inner().get("some_config"),
+        Some(&"value".to_owned())
+    );
+}
+
+#[test]
+fn layout_with_plugin_allowed_hosts() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" {
+                    allowed_hosts "api.open-meteo.com *.wttr.in https://example.com/api/"
+                    some_config "value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|r| r.get_run_plugin())
+        .unwrap();
+    assert_eq!(
+        run_plugin.allowed_hosts,
+        vec![
+            "api.open-meteo.com".to_owned(),
+            "*.wttr.in".to_owned(),
+            "https://example.com/api/".to_owned()
+        ]
+    );
+    assert_eq!(run_plugin.configuration.inner().len(), 1);
+}
+
+#[test]
+fn plugin_allowed_hosts_must_be_hosts_or_url_prefixes() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" allowed_hosts="example.com/api"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "a host with a path but no scheme should error"
+    );
+}
+
+#[test]
+fn plugin_max_memory_must_be_a_positive_integer() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" max_memory_mb="a lot"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "a non numeric max_memory_mb should error");
+}
+
+#[test]
+fn plugin_max_memory_is_capped() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" max_memory_mb=1000000
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "a max_memory_mb beyond the wasm address space should error"
+    );
+}
+
+#[test]
+fn remote_layout_cannot_set_plugin_resource_limits() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" max_memory_mb=4096 {
+                    fuel_budget 999999999999
+                    max_table_elements 999999999
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("https://example.com/layout.kdl".into()),
+        None,
+        None,
+    )
+    .unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|r| r.get_run_plugin())
+        .unwrap();
+    assert!(run_plugin.resource_limits.is_empty());
+}
+
+fn plugin_resource_limits_in(layout: &Layout) -> Vec<PluginResourceLimits> {
+    fn tiled(pane: &TiledPaneLayout, limits: &mut Vec<PluginResourceLimits>) {
+        if let Some(run_plugin) = pane.run.as_ref().and_then(|r| r.get_run_plugin()) {
+            limits.push(run_plugin.resource_limits);
+        }
+        for child in &pane.children {
+            tiled(child, limits);
+        }
+    }
+    fn floating(panes: &[FloatingPaneLayout], limits: &mut Vec<PluginResourceLimits>) {
+        for pane in panes {
+            if let Some(run_plugin) = pane.run.as_ref().and_then(|r| r.get_run_plugin()) {
+                limits.push(run_plugin.resource_limits);
+            }
+        }
+    }
+    let mut limits = vec![];
+    for (_, tiled_panes, floating_panes) in &layout.tabs {
+        tiled(tiled_panes, &mut limits);
+        floating(floating_panes, &mut limits);
+    }
+    for (swap_layouts, _) in &layout.swap_tiled_layouts {
+        for tiled_panes in swap_layouts.values() {
+            tiled(tiled_panes, &mut limits);
+        }
+    }
+    for (swap_layouts, _) in &layout.swap_floating_layouts {
+        for floating_panes in swap_layouts.values() {
+            floating(floating_panes, &mut limits);
+        }
+    }
+    limits
+}
+
+#[test]
+fn layout_from_plugin_cannot_set_plugin_resource_limits() {
+    let kdl_layout = r#"
+        layout {
+            tab {
+                pane {
+                    plugin location="file:/path/to/my/plugin.wasm" max_memory_mb=4096
+                }
+                floating_panes {
+                    pane {
+                        plugin location="file:/path/to/my/plugin.wasm" fuel_budget=999999999999
+                    }
+                }
+            }
+            swap_tiled_layout name="tiled" {
+                tab max_panes=2 {
+                    pane {
+                        plugin location="file:/path/to/my/plugin.wasm" max_table_elements=999999999
+                    }
+                }
+            }
+            swap_floating_layout name="floating" {
+                floating_panes max_panes=2 {
+                    pane {
+                        plugin location="file:/path/to/my/plugin.wasm" max_pending_jobs=999999
+                    }
+                }
+            }
+        }
+    "#;
+    // parsed the way the `new_tabs_with_layout` plugin command parses it
+    let mut layout = Layout::from_str(
+        kdl_layout,
+        "Layout from plugin: my-plugin".to_owned(),
+        None,
+        None,
+    )
+    .unwrap();
+    let limits = plugin_resource_limits_in(&layout);
+    assert_eq!(limits.len(), 4);
+    assert!(limits.iter().all(|l| !l.is_empty()));
+
+    layout.clear_plugin_resource_limits();
+    let limits = plugin_resource_limits_in(&layout);
+    assert_eq!(limits.len(), 4);
+    assert!(limits.iter().all(|l| l.is_empty()));
+}
+
+#[test]
+fn plugin_fuel_budget_must_be_a_positive_integer() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my/plugin.wasm" fuel_budget=0
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "a zero fuel_budget should error");
+}
+
+#[test]
+fn plugin_fuel_budget_cannot_be_set_on_an_alias() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="filepicker" fuel_budget=5000
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "fuel_budget on an alias should error");
+}
+
+#[test]
+fn layout_with_command_pane_env() {
+    let kdl_layout = r#"
+        layout {
+            pane command="./server" {
+                env {
+                    DATABASE_URL "postgres://localhost/staging"
+                    PORT 8081
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run = layout.template.unwrap().0.children[0].run.clone();
+    let Some(Run::Command(run_command)) = run else {
+        panic!("expected a command pane, got: {:?}", run);
+    };
+    let expected_env: BTreeMap<String, String> = [
+        (
+            "DATABASE_URL".to_owned(),
+            "postgres://localhost/staging".to_owned(),
+        ),
+        ("PORT".to_owned(), "8081".to_owned()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(run_command.env, expected_env);
+}
+
+#[test]
+fn env_added_to_env_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="server" {
+                command "./server"
+                env {
+                    DATABASE_URL "postgres://localhost/staging"
+                    LOG_LEVEL "debug"
+                }
+            }
+            server {
+                env {
+                    DATABASE_URL "postgres://localhost/production"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run = layout.template.unwrap().0.children[0].run.clone();
+    let Some(Run::Command(run_command)) = run else {
+        panic!("expected a command pane, got: {:?}", run);
+    };
+    let expected_env: BTreeMap<String, String> = [
+        (
+            "DATABASE_URL".to_owned(),
+            "postgres://localhost/production".to_owned(),
+        ),
+        ("LOG_LEVEL".to_owned(), "debug".to_owned()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(run_command.command, PathBuf::from("./server"));
+    assert_eq!(run_command.env, expected_env);
+}
+
+#[test]
+fn error_on_bare_env_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    DATABASE_URL "postgres://localhost/staging"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_with_command_pane_restart_policy() {
+    let kdl_layout = r#"
+        layout {
+            pane command="./worker" restart="on-failure" max_restarts=5
+            pane command="./server" {
+                restart "always"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let children = layout.template.unwrap().0.children;
+    let Some(Run::Command(worker)) = children[0].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[0].run);
+    };
+    let Some(Run::Command(server)) = children[1].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[1].run);
+    };
+    assert_eq!(worker.restart, RestartPolicy::OnFailure);
+    assert_eq!(worker.max_restarts, Some(5));
+    assert_eq!(server.restart, RestartPolicy::Always);
+    assert_eq!(server.max_restarts, None);
+}
+
+#[test]
+fn restart_policy_added_to_command_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="service" {
+                command "./server"
+                restart "on-failure"
+                max_restarts 3
+            }
+            service max_restarts=10
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run = layout.template.unwrap().0.children[0].run.clone();
+    let Some(Run::Command(run_command)) = run else {
+        panic!("expected a command pane, got: {:?}", run);
+    };
+    assert_eq!(run_command.restart, RestartPolicy::OnFailure);
+    assert_eq!(run_command.max_restarts, Some(10));
+}
+
+#[test]
+fn error_on_unknown_restart_policy() {
+    let kdl_layout = r#"
+        layout {
+            pane command="./worker" restart="sometimes"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_bare_restart_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane restart="always"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_with_pane_dependencies() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db"
+            pane name="migrations" command="./migrate" depends_on="db"
+            pane command="./server" {
+                depends_on "db" output="ready to accept connections"
+                depends_on "cache" port=6379
+            }
+            pane name="cache" command="./cache"
+            floating_panes {
+                pane command="./worker" {
+                    depends_on "db" file="/tmp/db.ready"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, floating_panes) = layout.template.unwrap();
+    assert!(tiled_panes.children[0].depends_on.is_empty());
+    assert_eq!(
+        tiled_panes.children[1].depends_on,
+        vec![PaneDependency {
+            pane_name: "db".to_owned(),
+            readiness: ReadinessProbe::ExitSuccess,
+        }]
+    );
+    assert_eq!(
+        tiled_panes.children[2].depends_on,
+        vec![
+            PaneDependency {
+                pane_name: "db".to_owned(),
+                readiness: ReadinessProbe::OutputMatches("ready to accept connections".to_owned()),
+            },
+            PaneDependency {
+                pane_name: "cache".to_owned(),
+                readiness: ReadinessProbe::PortOpen(6379),
+            },
+        ]
+    );
+    assert_eq!(
+        floating_panes[0].depends_on,
+        vec![PaneDependency {
+            pane_name: "db".to_owned(),
+            readiness: ReadinessProbe::FileExists(PathBuf::from("/tmp/db.ready")),
+        }]
+    );
+}
+
+#[test]
+fn panes_with_dependencies_start_suspended() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="service" depends_on="db"
+            pane name="db" command="./db"
+            service command="./server"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let children = layout.template.unwrap().0.children;
+    let Some(Run::Command(db)) = children[0].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[0].run);
+    };
+    let Some(Run::Command(server)) = children[1].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[1].run);
+    };
+    assert!(!db.hold_on_start);
+    assert!(server.hold_on_start);
+    assert_eq!(children[1].depends_on[0].pane_name, "db");
+}
+
+#[test]
+fn error_on_pane_dependency_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db"
+            pane depends_on="db"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_pane_dependency_with_more_than_one_probe() {
+    let kdl_layout = r#"
+        layout {
+            pane command="./server" {
+                depends_on "db" port=5432 file="/tmp/db.ready"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_pane_dependency_with_invalid_output_regex() {
+    let kdl_layout = r#"
+        layout {
+            pane command="./server" {
+                depends_on "db" output="ready ("
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn pane_dependencies_are_resolved_within_their_tab() {
+    let kdl_layout = r#"
+        layout {
+            default_tab_template {
+                pane name="db" command="./db"
+                children
+            }
+            tab {
+                pane command="./server" depends_on="db"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_ok(), "dependency on a pane of the tab template");
+    let kdl_layout = r#"
+        layout {
+            tab {
+                pane name="db" command="./db"
+            }
+            tab {
+                pane command="./server" depends_on="db"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_pane_dependency_on_missing_pane() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db"
+            pane command="./server" depends_on="dbb"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_pane_dependency_on_ambiguous_pane_name() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db"
+            floating_panes {
+                pane name="db" command="./other-db"
+            }
+            pane command="./server" depends_on="db"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_pane_dependency_cycle() {
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db" depends_on="migrations"
+            pane name="migrations" command="./migrate" depends_on="server"
+            pane name="server" command="./server" depends_on="db"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        format!("{:?}", layout.unwrap_err()).contains("db -> migrations -> server -> db"),
+        "cycle is reported"
+    );
+    let kdl_layout = r#"
+        layout {
+            pane name="db" command="./db" depends_on="db"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn layout_parameters_are_substituted_with_their_default_values() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+                port 8080
+            }
+            pane_template name="logs" {
+                command "tail"
+                args "-f" "/var/log/{{ service }}.log"
+            }
+            tab name="debugging {{service}}" {
+                pane name="{{service}}" command="./run-{{service}}" cwd="/projects/{{service}}" {
+                    args "--port" "{{port}}"
+                }
+                logs
+                pane {
+                    plugin location="zellij:strider" {
+                        service "{{service}}"
+                    }
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tab_name, tiled_panes, _floating_panes) = layout.tabs[0].clone();
+    assert_eq!(tab_name, Some("debugging api".to_owned()));
+    assert_eq!(tiled_panes.children[0].name, Some("api".to_owned()));
+    let Some(Run::Command(service)) = tiled_panes.children[0].run.clone() else {
+        panic!(
+            "expected a command pane, got: {:?}",
+            tiled_panes.children[0].run
+        );
+    };
+    assert_eq!(service.command, PathBuf::from("./run-api"));
+    assert_eq!(service.args, vec!["--port".to_owned(), "8080".to_owned()]);
+    assert_eq!(service.cwd, Some(PathBuf::from("/projects/api")));
+    let Some(Run::Command(logs)) = tiled_panes.children[1].run.clone() else {
+        panic!(
+            "expected a command pane, got: {:?}",
+            tiled_panes.children[1].run
+        );
+    };
+    assert_eq!(
+        logs.args,
+        vec!["-f".to_owned(), "/var/log/api.log".to_owned()]
+    );
+    let Some(Run::Plugin(plugin)) = tiled_panes.children[2].run.clone() else {
+        panic!(
+            "expected a plugin pane, got: {:?}",
+            tiled_panes.children[2].run
+        );
+    };
+    assert_eq!(
+        plugin
+            .get_configuration()
+            .and_then(|c| c.inner().get("service").cloned()),
+        Some("api".to_owned())
+    );
+}
+
+#[test]
+fn layout_args_override_parameter_defaults() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+                host "localhost"
+            }
+            pane command="ssh" {
+                args "{{host}}" "journalctl -fu {{service}}"
+            }
+        }
+    "#;
+    let layout_args = [("host".to_owned(), "staging".to_owned())]
+        .into_iter()
+        .collect();
+    let layout = Layout::from_kdl_with_layout_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+    )
+    .unwrap();
+    let children = layout.template.unwrap().0.children;
+    let Some(Run::Command(ssh)) = children[0].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[0].run);
+    };
+    assert_eq!(
+        ssh.args,
+        vec!["staging".to_owned(), "journalctl -fu api".to_owned()]
+    );
+}
+
+#[test]
+fn layout_parameters_are_substituted_in_swap_layouts() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+            }
+            pane
+        }
+    "#;
+    let kdl_swap_layout = r#"
+        swap_tiled_layout name="{{service}}" {
+            tab {
+                pane command="./run-{{service}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        Some(("swap_layout_file_name", kdl_swap_layout)),
+        None,
+    )
+    .unwrap();
+    let (swap_tiled_layout, swap_layout_name) = &layout.swap_tiled_layouts[0];
+    assert_eq!(swap_layout_name, &Some("api".to_owned()));
+    let swap_layout_panes = swap_tiled_layout.values().next().unwrap();
+    let Some(Run::Command(service)) = swap_layout_panes.children[0].run.clone() else {
+        panic!(
+            "expected a command pane, got: {:?}",
+            swap_layout_panes.children[0].run
+        );
+    };
+    assert_eq!(service.command, PathBuf::from("./run-api"));
+}
+
+#[test]
+fn references_to_undeclared_layout_parameters_are_left_as_is() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+            }
+            pane command="docker" {
+                args "ps" "--format" "{{.Names}} {{service}} {{unknown}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let children = layout.template.unwrap().0.children;
+    let Some(Run::Command(docker)) = children[0].run.clone() else {
+        panic!("expected a command pane, got: {:?}", children[0].run);
+    };
+    assert_eq!(
+        docker.args,
+        vec![
+            "ps".to_owned(),
+            "--format".to_owned(),
+            "{{.Names}} api {{unknown}}".to_owned()
+        ]
+    );
+}
+
+#[test]
+fn layout_parameters_are_listed_with_their_default_values() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+                port 8080
+            }
+            pane
+        }
+    "#;
+    assert_eq!(
+        Layout::parameters_from_kdl(kdl_layout).unwrap(),
+        [
+            ("port".to_owned(), "8080".to_owned()),
+            ("service".to_owned(), "api".to_owned())
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert!(Layout::parameters_from_kdl("layout { pane; }")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn error_on_layout_arg_for_undeclared_parameter() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service "api"
+            }
+            pane
+        }
+    "#;
+    let layout_args = [("srevice".to_owned(), "db".to_owned())]
+        .into_iter()
+        .collect();
+    let layout = Layout::from_kdl_with_layout_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+    );
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_layout_parameter_without_default_value() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service
+            }
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
+#[test]
+fn error_on_invalid_layout_parameter_name() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                "my service" "api"
+            }
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided");
+}
@@ -838,6 +838,7 @@ impl From<crate::input::cli_assets::CliAssets>
             should_ignore_config: cli_assets.should_ignore_config,
             configuration_options: cli_assets.configuration_options.map(|o| o.into()),
             layout: cli_assets.layout.map(|l| l.into()),
+            layout_args: cli_assets.layout_args.into_iter().collect(),
             terminal_window_size: Some(cli_assets.terminal_window_size.into()),
             data_dir: cli_assets.data_dir.map(|p| p.to_string_lossy().to_string()),
             is_debug: cli_assets.is_debug,
@@ -865,6 +866,7 @@ impl TryFrom<crate::client_server_contract::client_server_contract::CliAssets>
                 .map(|o| o.try_into())
                 .transpose()?,
             layout: cli_assets.layout.map(|l| l.try_into()).transpose()?,
+            layout_args: cli_assets.layout_args.into_iter().collect(),
             terminal_window_size: cli_assets
                 .terminal_window_size
                 .ok_or_else(|| anyhow!("CliAssets missing terminal_window_size"))?
@@ -927,6 +929,7 @@ impl From<crate::input::options::Options>
             session_serialization: options.session_serialization,
             serialize_pane_viewport: options.serialize_pane_viewport,
             scrollback_lines_to_serialize: options.scrollback_lines_to_serialize.map(|s| s as u32),
+            serialize_pane_scrollback: options.serialize_pane_scrollback,
             styled_underlines: options.styled_underlines,
             serialization_interval: options.serialization_interval,
             disable_session_metadata: options.disable_session_metadata,
@@ -1053,6 +1056,7 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Options>
             scrollback_lines_to_serialize: options
                 .scrollback_lines_to_serialize
                 .map(|s| s as usize),
+            serialize_pane_scrollback: options.serialize_pane_scrollback,
             styled_underlines: options.styled_underlines,
             serialization_interval: options.serialization_interval,
             disable_session_metadata: options.disable_session_metadata,
@@ -1146,6 +1150,9 @@ impl From<crate::input::actions::Action>
             ConfirmAction,
             CopyAction,
             CopyLastCommandOutputAction,
+            CopyModeMoveAction,
+            CopyModeSelectAction,
+            CopyModeYankAction,
             CurrentTabInfoAction,
             DenyAction,
             DetachAction,
@@ -1178,6 +1185,7 @@ impl From<crate::input::actions::Action>
             ListClientsAction,
             ListPanesAction,
             ListTabsAction,
+            MonitorPaneAction,
             MouseEventAction,
             MoveFocusAction,
             MoveFocusOrTabAction,
@@ -1218,6 +1226,7 @@ impl From<crate::input::actions::Action>
             RenameTabAction,
             RenameTabByIdAction,
             RenameTerminalPaneAction,
+            ReopenClosedPaneAction,
             ResizeAction,
             ResizeByPaneIdAction,
             RunAction,
@@ -1248,10 +1257,15 @@ impl From<crate::input::actions::Action>
             SkipConfirmAction,
             StackPanesAction,
             StartOrReloadPluginAction,
+            StartPaneLogAction,
+            StartRecordingAction,
+            StopPaneLogAction,
+            StopRecordingAction,
             SwitchFocusAction,
             SwitchModeForAllClientsAction,
             SwitchSessionAction,
             SwitchToModeAction,
+            SyncBroadcastGroupAction,
             TabNameInputAction,
             ToggleActiveSyncTabAction,
             ToggleActiveSyncTabByTabIdAction,
@@ -1268,6 +1282,7 @@ impl From<crate::input::actions::Action>
             TogglePaneEmbedOrFloatingAction,
             TogglePaneEmbedOrFloatingByPaneIdAction,
             TogglePaneFramesAction,
+            TogglePaneGroupSyncAction,
             TogglePaneInGroupAction,
             TogglePanePinnedAction,
             TogglePanePinnedByPaneIdAction,
@@ -1604,6 +1619,15 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::CloseFocus => {
                 ActionType::CloseFocus(CloseFocusAction {})
             },
+            crate::input::actions::Action::ReopenClosedPane => {
+                ActionType::ReopenClosedPane(ReopenClosedPaneAction {})
+            },
+            crate::input::actions::Action::TogglePaneGroupSync => {
+                ActionType::TogglePaneGroupSync(TogglePaneGroupSyncAction {})
+            },
+            crate::input::actions::Action::SyncBroadcastGroup { name } => {
+                ActionType::SyncBroadcastGroup(SyncBroadcastGroupAction { name })
+            },
             crate::input::actions::Action::PaneNameInput { input } => {
                 ActionType::PaneNameInput(PaneNameInputAction {
                     input: input.into_iter().map(|b| b as u32).collect(),
@@ -1769,6 +1793,19 @@ impl From<crate::input::actions::Action>
                     option: search_option_to_proto_i32(option),
                 })
             },
+            crate::input::actions::Action::CopyModeMove { motion } => {
+                ActionType::CopyModeMove(CopyModeMoveAction {
+                    motion: copy_mode_motion_to_proto_i32(motion),
+                })
+            },
+            crate::input::actions::Action::CopyModeSelect { selection } => {
+                ActionType::CopyModeSelect(CopyModeSelectAction {
+                    selection: copy_mode_selection_to_proto_i32(selection),
+                })
+            },
+            crate::input::actions::Action::CopyModeYank => {
+                ActionType::CopyModeYank(CopyModeYankAction {})
+            },
             crate::input::actions::Action::ToggleMouseMode => {
                 ActionType::ToggleMouseMode(ToggleMouseModeAction {})
             },
@@ -2024,6 +2061,35 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::SaveSession => {
                 ActionType::SaveSession(SaveSessionAction {})
             },
+            crate::input::actions::Action::StartRecording { path, target } => {
+                ActionType::StartRecording(StartRecordingAction {
+                    path: path.map(|p| p.to_string_lossy().to_string()),
+                    target: Some(target.into()),
+                })
+            },
+            crate::input::actions::Action::StopRecording { path } => {
+                ActionType::StopRecording(StopRecordingAction {
+                    path: path.map(|p| p.to_string_lossy().to_string()),
+                })
+            },
+            crate::input::actions::Action::StartPaneLog {
+                pane_id,
+                log_output,
+            } => ActionType::StartPaneLog(StartPaneLogAction {
+                pane_id: pane_id.map(|p| p.into()),
+                log_output: Some(log_output.into()),
+            }),
+            crate::input::actions::Action::StopPaneLog { pane_id } => {
+                ActionType::StopPaneLog(StopPaneLogAction {
+                    pane_id: pane_id.map(|p| p.into()),
+                })
+            },
+            crate::input::actions::Action::MonitorPane { pane_id, monitors } => {
+                ActionType::MonitorPane(MonitorPaneAction {
+                    pane_id: pane_id.map(|p| p.into()),
+                    monitors: Some(monitors.into()),
+                })
+            },
             crate::input::actions::Action::ListTabs {
                 show_state,
                 show_dimensions,
@@ -2308,6 +2374,45 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
             },
             ActionType::DumpLayout(_) => Ok(crate::input::actions::Action::DumpLayout),
             ActionType::SaveSession(_) => Ok(crate::input::actions::Action::SaveSession),
+            ActionType::StartRecording(start_recording_action) => {
+                Ok(crate::input::actions::Action::StartRecording {
+                    path: start_recording_action.path.map(PathBuf::from),
+                    target: start_recording_action
+                        .target
+                        .ok_or_else(|| anyhow!("StartRecording missing target"))?
+                        .try_into()?,
+                })
+            },
+            ActionType::StopRecording(stop_recording_action) => {
+                Ok(crate::input::actions::Action::StopRecording {
+                    path: stop_recording_action.path.map(PathBuf::from),
+                })
+            },
+            ActionType::StartPaneLog(start_pane_log_action) => {
+                Ok(crate::input::actions::Action::StartPaneLog {
+                    pane_id: start_pane_log_action
+                        .pane_id
+                        .and_then(|p| p.try_into().ok()),
+                    log_output: start_pane_log_action
+                        .log_output
+                        .ok_or_else(|| anyhow!("StartPaneLog missing log_output"))?
+                        .into(),
+                })
+            },
+            ActionType::StopPaneLog(stop_pane_log_action) => {
+                Ok(crate::input::actions::Action::StopPaneLog {
+                    pane_id: stop_pane_log_action.pane_id.and_then(|p| p.try_into().ok()),
+                })
+            },
+            ActionType::MonitorPane(monitor_pane_action) => {
+                Ok(crate::input::actions::Action::MonitorPane {
+                    pane_id: monitor_pane_action.pane_id.and_then(|p| p.try_into().ok()),
+                    monitors: monitor_pane_action
+                        .monitors
+                        .map(|m| m.into())
+                        .unwrap_or_default(),
+                })
+            },
             ActionType::EditScrollback(edit_scrollback_action) => {
                 Ok(crate::input::actions::Action::EditScrollback {
                     ansi: edit_scrollback_action.ansi,
@@ -2497,6 +2602,15 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
                 })
             },
             ActionType::CloseFocus(_) => Ok(crate::input::actions::Action::CloseFocus),
+            ActionType::ReopenClosedPane(_) => Ok(crate::input::actions::Action::ReopenClosedPane),
+            ActionType::TogglePaneGroupSync(_) => {
+                Ok(crate::input::actions::Action::TogglePaneGroupSync)
+            },
+            ActionType::SyncBroadcastGroup(sync_broadcast_group_action) => {
+                Ok(crate::input::actions::Action::SyncBroadcastGroup {
+                    name: sync_broadcast_group_action.name,
+                })
+            },
             ActionType::PaneNameInput(pane_name_action) => {
                 Ok(crate::input::actions::Action::PaneNameInput {
                     input: pane_name_action
@@ -2678,6 +2792,17 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
                     option: proto_i32_to_search_option(search_toggle_action.option)?,
                 })
             },
+            ActionType::CopyModeMove(copy_mode_move_action) => {
+                Ok(crate::input::actions::Action::CopyModeMove {
+                    motion: proto_i32_to_copy_mode_motion(copy_mode_move_action.motion)?,
+                })
+            },
+            ActionType::CopyModeSelect(copy_mode_select_action) => {
+                Ok(crate::input::actions::Action::CopyModeSelect {
+                    selection: proto_i32_to_copy_mode_selection(copy_mode_select_action.selection)?,
+                })
+            },
+            ActionType::CopyModeYank(_) => Ok(crate::input::actions::Action::CopyModeYank),
             ActionType::ToggleMouseMode(_) => Ok(crate::input::actions::Action::ToggleMouseMode),
             ActionType::PreviousSwapLayout(_) => {
                 Ok(crate::input::actions::Action::PreviousSwapLayout)
@@ -3327,6 +3452,7 @@ impl From<crate::data::LayoutMetadata> for ProtoLayoutMetadata {
             tabs: metadata.tabs.into_iter().map(|t| t.into()).collect(),
             creation_time: metadata.creation_time,
             update_time: metadata.update_time,
+            parameters: metadata.parameters.into_iter().collect(),
         }
     }
 }
@@ -3343,6 +3469,7 @@ impl TryFrom<ProtoLayoutMetadata> for crate::data::LayoutMetadata {
             tabs,
             creation_time: proto_metadata.creation_time,
             update_time: proto_metadata.update_time,
+            parameters: proto_metadata.parameters.into_iter().collect(),
         })
     }
 }
@@ -3461,6 +3588,7 @@ fn input_mode_to_proto_i32(mode: InputMode) -> i32 {
         InputMode::Move => ProtoInputMode::Move as i32,
         InputMode::Prompt => ProtoInputMode::Prompt as i32,
         InputMode::Tmux => ProtoInputMode::Tmux as i32,
+        InputMode::Copy => ProtoInputMode::Copy as i32,
     }
 }
 
@@ -3480,6 +3608,7 @@ fn proto_i32_to_input_mode(i: i32) -> Result<InputMode> {
         Some(ProtoInputMode::Move) => Ok(InputMode::Move),
         Some(ProtoInputMode::Prompt) => Ok(InputMode::Prompt),
         Some(ProtoInputMode::Tmux) => Ok(InputMode::Tmux),
+        Some(ProtoInputMode::Copy) => Ok(InputMode::Copy),
         _ => Err(anyhow!("Invalid InputMode value: {}", i)),
     }
 }
@@ -3503,6 +3632,15 @@ fn direction_to_proto_i32(direction: crate::data::Direction) -> i32 {
     }
 }
 
+fn restart_policy_to_proto_i32(restart_policy: crate::input::command::RestartPolicy) -> i32 {
+    use crate::client_server_contract::client_server_contract::RestartPolicy as ProtoRestartPolicy;
+    match restart_policy {
+        crate::input::command::RestartPolicy::Never => ProtoRestartPolicy::Never as i32,
+        crate::input::command::RestartPolicy::OnFailure => ProtoRestartPolicy::OnFailure as i32,
+        crate::input::command::RestartPolicy::Always => ProtoRestartPolicy::Always as i32,
+    }
+}
+
 fn search_direction_to_proto_i32(direction: crate::input::actions::SearchDirection) -> i32 {
     use crate::client_server_contract::client_server_contract::SearchDirection as ProtoSearchDirection;
     match direction {
@@ -3519,6 +3657,46 @@ fn search_option_to_proto_i32(option: crate::input::actions::SearchOption) -> i3
         },
         crate::input::actions::SearchOption::Wrap => ProtoSearchOption::Wrap as i32,
         crate::input::actions::SearchOption::WholeWord => ProtoSearchOption::WholeWord as i32,
+        crate::input::actions::SearchOption::Regex => ProtoSearchOption::Regex as i32,
+    }
+}
+
+fn copy_mode_motion_to_proto_i32(motion: crate::input::actions::CopyModeMotion) -> i32 {
+    use crate::client_server_contract::client_server_contract::CopyModeMotion as ProtoCopyModeMotion;
+    use crate::input::actions::CopyModeMotion;
+    match motion {
+        CopyModeMotion::Left => ProtoCopyModeMotion::Left as i32,
+        CopyModeMotion::Right => ProtoCopyModeMotion::Right as i32,
+        CopyModeMotion::Up => ProtoCopyModeMotion::Up as i32,
+        CopyModeMotion::Down => ProtoCopyModeMotion::Down as i32,
+        CopyModeMotion::WordForward => ProtoCopyModeMotion::WordForward as i32,
+        CopyModeMotion::WordBackward => ProtoCopyModeMotion::WordBackward as i32,
+        CopyModeMotion::WordEnd => ProtoCopyModeMotion::WordEnd as i32,
+        CopyModeMotion::LineStart => ProtoCopyModeMotion::LineStart as i32,
+        CopyModeMotion::LineEnd => ProtoCopyModeMotion::LineEnd as i32,
+        CopyModeMotion::FirstNonBlank => ProtoCopyModeMotion::FirstNonBlank as i32,
+        CopyModeMotion::ParagraphForward => ProtoCopyModeMotion::ParagraphForward as i32,
+        CopyModeMotion::ParagraphBackward => ProtoCopyModeMotion::ParagraphBackward as i32,
+        CopyModeMotion::Top => ProtoCopyModeMotion::Top as i32,
+        CopyModeMotion::Bottom => ProtoCopyModeMotion::Bottom as i32,
+        CopyModeMotion::PageUp => ProtoCopyModeMotion::PageUp as i32,
+        CopyModeMotion::PageDown => ProtoCopyModeMotion::PageDown as i32,
+        CopyModeMotion::HalfPageUp => ProtoCopyModeMotion::HalfPageUp as i32,
+        CopyModeMotion::HalfPageDown => ProtoCopyModeMotion::HalfPageDown as i32,
+        CopyModeMotion::PreviousPrompt => ProtoCopyModeMotion::PreviousPrompt as i32,
+        CopyModeMotion::NextPrompt => ProtoCopyModeMotion::NextPrompt as i32,
+        CopyModeMotion::NextSearchResult => ProtoCopyModeMotion::NextSearchResult as i32,
+        CopyModeMotion::PreviousSearchResult => ProtoCopyModeMotion::PreviousSearchResult as i32,
+    }
+}
+
+fn copy_mode_selection_to_proto_i32(selection: crate::input::actions::CopyModeSelection) -> i32 {
+    use crate::client_server_contract::client_server_contract::CopyModeSelection as ProtoCopyModeSelection;
+    use crate::input::actions::CopyModeSelection;
+    match selection {
+        CopyModeSelection::Character => ProtoCopyModeSelection::Character as i32,
+        CopyModeSelection::Line => ProtoCopyModeSelection::Line as i32,
+        CopyModeSelection::Block => ProtoCopyModeSelection::Block as i32,
     }
 }
 
@@ -3585,6 +3763,7 @@ fn proto_i32_to_search_option(option: i32) -> Result<crate::input::actions::Sear
         x if x == ProtoSearchOption::CaseSensitivity as i32 => ProtoSearchOption::CaseSensitivity,
         x if x == ProtoSearchOption::WholeWord as i32 => ProtoSearchOption::WholeWord,
         x if x == ProtoSearchOption::Wrap as i32 => ProtoSearchOption::Wrap,
+        x if x == ProtoSearchOption::Regex as i32 => ProtoSearchOption::Regex,
         _ => return Err(anyhow!("Invalid SearchOption: {}", option)),
     };
     match proto_option {
@@ -3593,10 +3772,76 @@ fn proto_i32_to_search_option(option: i32) -> Result<crate::input::actions::Sear
         },
         ProtoSearchOption::Wrap => Ok(crate::input::actions::SearchOption::Wrap),
         ProtoSearchOption::WholeWord => Ok(crate::input::actions::SearchOption::WholeWord),
+        ProtoSearchOption::Regex => Ok(crate::input::actions::SearchOption::Regex),
         ProtoSearchOption::Unspecified => Err(anyhow!("Unspecified search option")),
     }
 }
 
+fn proto_i32_to_copy_mode_motion(motion: i32) -> Result<crate::input::actions::CopyModeMotion> {
+    use crate::client_server_contract::client_server_contract::CopyModeMotion as ProtoCopyModeMotion;
+    use crate::input::actions::CopyModeMotion;
+    match ProtoCopyModeMotion::try_from(motion).ok() {
+        Some(ProtoCopyModeMotion::Left) => Ok(CopyModeMotion::Left),
+        Some(ProtoCopyModeMotion::Right) => Ok(CopyModeMotion::Right),
+        Some(ProtoCopyModeMotion::Up) => Ok(CopyModeMotion::Up),
+        Some(ProtoCopyModeMotion::Down) => Ok(CopyModeMotion::Down),
+        Some(ProtoCopyModeMotion::WordForward) => Ok(CopyModeMotion::WordForward),
+        Some(ProtoCopyModeMotion::WordBackward) => Ok(CopyModeMotion::WordBackward),
+        Some(ProtoCopyModeMotion::WordEnd) => Ok(CopyModeMotion::WordEnd),
+        Some(ProtoCopyModeMotion::LineStart) => Ok(CopyModeMotion::LineStart),
+        Some(ProtoCopyModeMotion::LineEnd) => Ok(CopyModeMotion::LineEnd),
+        Some(ProtoCopyModeMotion::FirstNonBlank) => Ok(CopyModeMotion::FirstNonBlank),
+        Some(ProtoCopyModeMotion::ParagraphForward) => Ok(CopyModeMotion::ParagraphForward),
+        Some(ProtoCopyModeMotion::ParagraphBackward) => Ok(CopyModeMotion::ParagraphBackward),
+        Some(ProtoCopyModeMotion::Top) => Ok(CopyModeMotion::Top),
+        Some(ProtoCopyModeMotion::Bottom) => Ok(CopyModeMotion::Bottom),
+        Some(ProtoCopyModeMotion::PageUp) => Ok(CopyModeMotion::PageUp),
+        Some(ProtoCopyModeMotion::PageDown) => Ok(CopyModeMotion::PageDown),
+        Some(ProtoCopyModeMotion::HalfPageUp) => Ok(CopyModeMotion::HalfPageUp),
+        Some(ProtoCopyModeMotion::HalfPageDown) => Ok(CopyModeMotion::HalfPageDown),
+        Some(ProtoCopyModeMotion::PreviousPrompt) => Ok(CopyModeMotion::PreviousPrompt),
+        Some(ProtoCopyModeMotion::NextPrompt) => Ok(CopyModeMotion::NextPrompt),
+        Some(ProtoCopyModeMotion::NextSearchResult) => Ok(CopyModeMotion::NextSearchResult),
+        Some(ProtoCopyModeMotion::PreviousSearchResult) => Ok(CopyModeMotion::PreviousSearchResult),
+        _ => Err(anyhow!("Invalid CopyModeMotion: {}", motion)),
+    }
+}
+
+fn proto_i32_to_copy_mode_selection(
+    selection: i32,
+) -> Result<crate::input::actions::CopyModeSelection> {
+    use crate::client_server_contract::client_server_contract::CopyModeSelection as ProtoCopyModeSelection;
+    use crate::input::actions::CopyModeSelection;
+    match ProtoCopyModeSelection::try_from(selection).ok() {
+        Some(ProtoCopyModeSelection::Character) => Ok(CopyModeSelection::Character),
+        Some(ProtoCopyModeSelection::Line) => Ok(CopyModeSelection::Line),
+        Some(ProtoCopyModeSelection::Block) => Ok(CopyModeSelection::Block),
+        _ => Err(anyhow!("Invalid CopyModeSelection: {}", selection)),
+    }
+}
+
+fn proto_i32_to_restart_policy(
+    restart_policy: i32,
+) -> Result<crate::input::command::RestartPolicy> {
+    use crate::client_server_contract::client_server_contract::RestartPolicy as ProtoRestartPolicy;
+    match restart_policy {
+        // messages from older clients do not have a restart policy
+        x if x == ProtoRestartPolicy::Unspecified as i32 => {
+            Ok(crate::input::command::RestartPolicy::Never)
+        },
+        x if x == ProtoRestartPolicy::Never as i32 => {
+            Ok(crate::input::command::RestartPolicy::Never)
+        },
+        x if x == ProtoRestartPolicy::OnFailure as i32 => {
+            Ok(crate::input::command::RestartPolicy::OnFailure)
+        },
+        x if x == ProtoRestartPolicy::Always as i32 => {
+            Ok(crate::input::command::RestartPolicy::Always)
+        },
+        _ => Err(anyhow!("Invalid RestartPolicy: {}", restart_policy)),
+    }
+}
+
 fn proto_i32_to_unblock_condition(condition: i32) -> Result<crate::data::UnblockCondition> {
     use crate::client_server_contract::client_server_contract::UnblockCondition as ProtoUnblockCondition;
     let proto_condition = match condition {
@@ -3712,6 +3957,49 @@ impl TryFrom<crate::client_server_contract::client_server_contract::PaneId>
     }
 }
 
+// RecordingTarget conversion
+impl From<crate::data::RecordingTarget>
+    for crate::client_server_contract::client_server_contract::RecordingTarget
+{
+    fn from(target: crate::data::RecordingTarget) -> Self {
+        use crate::client_server_contract::client_server_contract::recording_target::TargetType;
+        let target_type = match target {
+            crate::data::RecordingTarget::FocusedPane => TargetType::FocusedPane(true),
+            crate::data::RecordingTarget::Pane(pane_id) => TargetType::PaneId(pane_id.into()),
+            crate::data::RecordingTarget::FocusedTab => TargetType::FocusedTab(true),
+            crate::data::RecordingTarget::Tab(tab_id) => TargetType::TabId(tab_id as u32),
+            crate::data::RecordingTarget::Client => TargetType::Client(true),
+        };
+        Self {
+            target_type: Some(target_type),
+        }
+    }
+}
+
+// Reverse RecordingTarget conversion
+impl TryFrom<crate::client_server_contract::client_server_contract::RecordingTarget>
+    for crate::data::RecordingTarget
+{
+    type Error = anyhow::Error;
+    fn try_from(
+        target: crate::client_server_contract::client_server_contract::RecordingTarget,
+    ) -> Result<Self> {
+        use crate::client_server_contract::client_server_contract::recording_target::TargetType;
+        match target
+            .target_type
+            .ok_or_else(|| anyhow!("RecordingTarget missing target_type"))?
+        {
+            TargetType::FocusedPane(_) => Ok(crate::data::RecordingTarget::FocusedPane),
+            TargetType::PaneId(pane_id) => {
+                Ok(crate::data::RecordingTarget::Pane(pane_id.try_into()?))
+            },
+            TargetType::FocusedTab(_) => Ok(crate::data::RecordingTarget::FocusedTab),
+            TargetType::TabId(tab_id) => Ok(crate::data::RecordingTarget::Tab(tab_id as usize)),
+            TargetType::Client(_) => Ok(crate::data::RecordingTarget::Client),
+        }
+    }
+}
+
 // FloatingCoordinate conversion - SplitSize to FloatingCoordinate
 impl From<crate::input::layout::SplitSize>
     for crate::client_server_contract::client_server_contract::FloatingCoordinate
@@ -4017,6 +4305,9 @@ impl From<crate::input::command::RunCommandAction>
             hold_on_start: action.hold_on_start,
             originating_plugin: action.originating_plugin.map(|op| op.into()),
             use_terminal_title: action.use_terminal_title,
+            env: action.env.into_iter().collect(),
+            restart: restart_policy_to_proto_i32(action.restart),
+            max_restarts: action.max_restarts.map(|m| m as u32),
         }
     }
 }
@@ -4119,6 +4410,9 @@ impl From<crate::input::layout::Run>
                         hold_on_start: cmd.hold_on_start,
                         originating_plugin: cmd.originating_plugin.map(|op| op.into()),
                         use_terminal_title: cmd.use_terminal_title,
+                        env: cmd.env.into_iter().collect(),
+                        restart: restart_policy_to_proto_i32(cmd.restart),
+                        max_restarts: cmd.max_restarts.map(|m| m as u32),
                     },
                 )),
             },
@@ -4217,6 +4511,105 @@ impl TryFrom<crate::client_server_contract::client_server_contract::TabLayoutInf
     }
 }
 
+// LogOutput conversion
+impl From<crate::input::layout::LogOutput>
+    for crate::client_server_contract::client_server_contract::LogOutput
+{
+    fn from(log_output: crate::input::layout::LogOutput) -> Self {
+        Self {
+            path: log_output.path.to_string_lossy().to_string(),
+            strip_ansi: log_output.strip_ansi,
+            timestamps: log_output.timestamps,
+            max_size: log_output.max_size,
+            max_files: log_output.max_files.map(|m| m as u32),
+        }
+    }
+}
+
+impl From<crate::client_server_contract::client_server_contract::LogOutput>
+    for crate::input::layout::LogOutput
+{
+    fn from(log_output: crate::client_server_contract::client_server_contract::LogOutput) -> Self {
+        Self {
+            path: PathBuf::from(log_output.path),
+            strip_ansi: log_output.strip_ansi,
+            timestamps: log_output.timestamps,
+            max_size: log_output.max_size,
+            max_files: log_output.max_files.map(|m| m as usize),
+        }
+    }
+}
+
+// PaneDependency conversion
+impl From<crate::input::layout::PaneDependency>
+    for crate::client_server_contract::client_server_contract::PaneDependency
+{
+    fn from(dependency: crate::input::layout::PaneDependency) -> Self {
+        use crate::client_server_contract::client_server_contract::pane_dependency::Readiness;
+        use crate::input::layout::ReadinessProbe;
+        let readiness = match dependency.readiness {
+            ReadinessProbe::ExitSuccess => None,
+            ReadinessProbe::OutputMatches(regex) => Some(Readiness::OutputMatches(regex)),
+            ReadinessProbe::PortOpen(port) => Some(Readiness::PortOpen(port as u32)),
+            ReadinessProbe::FileExists(path) => {
+                Some(Readiness::FileExists(path.to_string_lossy().to_string()))
+            },
+        };
+        Self {
+            pane_name: dependency.pane_name,
+            readiness,
+        }
+    }
+}
+
+impl TryFrom<crate::client_server_contract::client_server_contract::PaneDependency>
+    for crate::input::layout::PaneDependency
+{
+    type Error = anyhow::Error;
+
+    fn try_from(
+        dependency: crate::client_server_contract::client_server_contract::PaneDependency,
+    ) -> Result<Self> {
+        use crate::client_server_contract::client_server_contract::pane_dependency::Readiness;
+        use crate::input::layout::ReadinessProbe;
+        let readiness = match dependency.readiness {
+            None => ReadinessProbe::ExitSuccess,
+            Some(Readiness::OutputMatches(regex)) => ReadinessProbe::OutputMatches(regex),
+            Some(Readiness::PortOpen(port)) => ReadinessProbe::PortOpen(
+                u16::try_from(port).map_err(|_| anyhow!("Invalid port: {}", port))?,
+            ),
+            Some(Readiness::FileExists(path)) => ReadinessProbe::FileExists(PathBuf::from(path)),
+        };
+        Ok(Self {
+            pane_name: dependency.pane_name,
+            readiness,
+        })
+    }
+}
+
+// PaneMonitors conversion
+impl From<crate::data::PaneMonitors>
+    for crate::client_server_contract::client_server_contract::PaneMonitors
+{
+    fn from(monitors: crate::data::PaneMonitors) -> Self {
+        Self {
+            activity: monitors.activity,
+            silence_secs: monitors.silence_secs,
+        }
+    }
+}
+
+impl From<crate::client_server_contract::client_server_contract::PaneMonitors>
+    for crate::data::PaneMonitors
+{
+    fn from(monitors: crate::client_server_contract::client_server_contract::PaneMonitors) -> Self {
+        Self {
+            activity: monitors.activity,
+            silence_secs: monitors.silence_secs,
+        }
+    }
+}
+
 // TiledPaneLayout conversion
 impl From<crate::input::layout::TiledPaneLayout>
     for crate::client_server_contract::client_server_contract::TiledPaneLayout
@@ -4238,6 +4631,11 @@ impl From<crate::input::layout::TiledPaneLayout>
             pane_initial_contents: layout.pane_initial_contents,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
+            pane_scrollback_snapshot: layout.pane_scrollback_snapshot,
+            log_output: layout.log_output.map(|l| l.into()),
+            monitors: layout.monitors.map(|m| m.into()),
+            broadcast_group: layout.broadcast_group,
+            depends_on: layout.depends_on.into_iter().map(|d| d.into()).collect(),
         }
     }
 }
@@ -4261,6 +4659,11 @@ impl From<crate::input::layout::FloatingPaneLayout>
             borderless: layout.borderless,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
+            pane_scrollback_snapshot: layout.pane_scrollback_snapshot,
+            log_output: layout.log_output.map(|l| l.into()),
+            monitors: layout.monitors.map(|m| m.into()),
+            broadcast_group: layout.broadcast_group,
+            depends_on: layout.depends_on.into_iter().map(|d| d.into()).collect(),
         }
     }
 }
@@ -4359,6 +4762,40 @@ impl From<crate::input::layout::RunPlugin>
             location: Some(plugin.location.into()),
             configuration: Some(plugin.configuration.into()),
             initial_cwd: plugin.initial_cwd.map(|p| p.display().to_string()),
+            sha256: plugin.sha256,
+            resource_limits: Some(plugin.resource_limits.into()),
+            allowed_hosts: plugin.allowed_hosts,
+        }
+    }
+}
+
+// PluginResourceLimits conversion
+impl From<crate::input::layout::PluginResourceLimits>
+    for crate::client_server_contract::client_server_contract::PluginResourceLimits
+{
+    fn from(resource_limits: crate::input::layout::PluginResourceLimits) -> Self {
+        Self {
+            fuel_budget: resource_limits.fuel_budget,
+            max_memory_mb: resource_limits.max_memory_mb.map(|m| m as u64),
+            max_table_elements: resource_limits.max_table_elements.map(|t| t as u64),
+            max_pending_jobs: resource_limits.max_pending_jobs.map(|j| j as u64),
+            max_timers_per_second: resource_limits.max_timers_per_second.map(|t| t as u64),
+        }
+    }
+}
+
+impl From<crate::client_server_contract::client_server_contract::PluginResourceLimits>
+    for crate::input::layout::PluginResourceLimits
+{
+    fn from(
+        resource_limits: crate::client_server_contract::client_server_contract::PluginResourceLimits,
+    ) -> Self {
+        Self {
+            fuel_budget: resource_limits.fuel_budget,
+            max_memory_mb: resource_limits.max_memory_mb.map(|m| m as usize),
+            max_table_elements: resource_limits.max_table_elements.map(|t| t as usize),
+            max_pending_jobs: resource_limits.max_pending_jobs.map(|j| j as usize),
+            max_timers_per_second: resource_limits.max_timers_per_second.map(|t| t as usize),
         }
     }
 }
@@ -4504,6 +4941,9 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Run>
                         .map(|op| op.try_into())
                         .transpose()?,
                     use_terminal_title: cmd.use_terminal_title,
+                    env: cmd.env.into_iter().collect(),
+                    restart: proto_i32_to_restart_policy(cmd.restart)?,
+                    max_restarts: cmd.max_restarts.map(|m| m as usize),
                 },
             )),
             RunType::EditFile(edit) => Ok(crate::input::layout::Run::EditFile(
@@ -4613,6 +5053,9 @@ impl TryFrom<crate::client_server_contract::client_server_contract::RunCommandAc
                 .map(|op| op.try_into())
                 .transpose()?,
             use_terminal_title: action.use_terminal_title,
+            env: action.env.into_iter().collect(),
+            restart: proto_i32_to_restart_policy(action.restart)?,
+            max_restarts: action.max_restarts.map(|m| m as usize),
         })
     }
 }
@@ -4673,6 +5116,15 @@ impl TryFrom<crate::client_server_contract::client_server_contract::TiledPaneLay
             pane_initial_contents: layout.pane_initial_contents,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
+            pane_scrollback_snapshot: layout.pane_scrollback_snapshot,
+            log_output: layout.log_output.map(|l| l.into()),
+            monitors: layout.monitors.map(|m| m.into()),
+            broadcast_group: layout.broadcast_group,
+            depends_on: layout
+                .depends_on
+                .into_iter()
+                .map(|d| d.try_into())
+                .collect::<Result<_>>()?,
         })
     }
 }
@@ -4707,6 +5159,15 @@ impl TryFrom<crate::client_server_contract::client_server_contract::FloatingPane
             borderless: layout.borderless,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
+            pane_scrollback_snapshot: layout.pane_scrollback_snapshot,
+            log_output: layout.log_output.map(|l| l.into()),
+            monitors: layout.monitors.map(|m| m.into()),
+            broadcast_group: layout.broadcast_group,
+            depends_on: layout
+                .depends_on
+                .into_iter()
+                .map(|d| d.try_into())
+                .collect::<Result<_>>()?,
         })
     }
 }
@@ -4849,6 +5310,9 @@ impl TryFrom<crate::client_server_contract::client_server_contract::RunPlugin>
             location,
             configuration,
             initial_cwd,
+            sha256: plugin.sha256,
+            resource_limits: plugin.resource_limits.map(|r| r.into()).unwrap_or_default(),
+            allowed_hosts: plugin.allowed_hosts,
         })
     }
 }
@@ -2,15 +2,18 @@ use super::test_framework::*;
 use crate::data::{
     BareKey, CommandOrPlugin, ConnectToSession, Direction, FloatingPaneCoordinates,
     HostTerminalThemeMode, InputMode, KeyModifier, KeyWithModifier, LayoutInfo, LayoutMetadata,
-    NewPanePlacement, OriginatingPlugin, PaneId, PluginTag, Resize, WebSharing,
+    NewPanePlacement, OriginatingPlugin, PaneId, PaneMonitors, PluginTag, RecordingTarget, Resize,
+    WebSharing,
+};
+use crate::input::actions::{
+    Action, CopyModeMotion, CopyModeSelection, SearchDirection, SearchOption,
 };
-use crate::input::actions::{Action, SearchDirection, SearchOption};
 use crate::input::cli_assets::CliAssets;
-use crate::input::command::{OpenFilePayload, RunCommand, RunCommandAction};
+use crate::input::command::{OpenFilePayload, RestartPolicy, RunCommand, RunCommandAction};
 use crate::input::layout::{
-    FloatingPaneLayout, LayoutConstraint, PercentOrFixed, PluginAlias, PluginUserConfiguration,
-    Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection, SplitSize,
-    TiledPaneLayout,
+    FloatingPaneLayout, LayoutConstraint, LogOutput, PaneDependency, PercentOrFixed, PluginAlias,
+    PluginUserConfiguration, ReadinessProbe, Run, RunPlugin, RunPluginLocation, RunPluginOrAlias,
+    SplitDirection, SplitSize, TiledPaneLayout,
 };
 use crate::input::mouse::{MouseEvent, MouseEventType};
 use crate::input::options::{
@@ -415,6 +418,9 @@ fn test_client_messages() {
             should_ignore_config: true,
             configuration_options: None,
             layout: None,
+            layout_args: [("service".to_owned(), "api".to_owned())]
+                .into_iter()
+                .collect(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -434,6 +440,7 @@ fn test_client_messages() {
             should_ignore_config: true,
             configuration_options: Some(Options::default()),
             layout: None,
+            layout_args: Default::default(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -479,6 +486,7 @@ fn test_client_messages() {
                 session_serialization: Some(true),
                 serialize_pane_viewport: Some(true),
                 scrollback_lines_to_serialize: Some(10000),
+                serialize_pane_scrollback: Some(true),
                 styled_underlines: Some(true),
                 serialization_interval: Some(1),
                 disable_session_metadata: Some(true),
@@ -511,6 +519,7 @@ fn test_client_messages() {
                 dangerously_enable_paste_buffer_read: Some(true),
             }),
             layout: None,
+            layout_args: Default::default(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -1000,6 +1009,74 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StartRecording {
+            path: Some(PathBuf::from("/path/to/recording.cast")),
+            target: RecordingTarget::Pane(PaneId::Terminal(5)),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StartRecording {
+            path: None,
+            target: RecordingTarget::Tab(2),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StartRecording {
+            path: None,
+            target: RecordingTarget::Client,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StopRecording {
+            path: Some(PathBuf::from("/path/to/recording.cast")),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StartPaneLog {
+            pane_id: Some(PaneId::Terminal(5)),
+            log_output: LogOutput {
+                path: PathBuf::from("/path/to/pane.log"),
+                strip_ansi: true,
+                timestamps: false,
+                max_size: Some(10 * 1024 * 1024),
+                max_files: None,
+            },
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::StopPaneLog { pane_id: None },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::MonitorPane {
+            pane_id: Some(PaneId::Terminal(5)),
+            monitors: PaneMonitors {
+                activity: true,
+                silence_secs: Some(30),
+            },
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::DumpLayout,
         terminal_id: Some(1),
@@ -1132,6 +1209,20 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::TogglePaneGroupSync,
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SyncBroadcastGroup {
+            name: "nodes".to_owned(),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::NewPane {
             direction: None,
@@ -1408,6 +1499,9 @@ fn test_client_messages() {
                 hold_on_start: false,
                 originating_plugin: None,
                 use_terminal_title: false,
+                env: BTreeMap::new(),
+                restart: RestartPolicy::Never,
+                max_restarts: None,
             }),
             pane_name: Some("my_pane_name".to_owned()),
             coordinates: FloatingPaneCoordinates::new(
@@ -1441,6 +1535,9 @@ fn test_client_messages() {
                     context: demo_context.clone(),
                 }),
                 use_terminal_title: false,
+                env: demo_context.clone(),
+                restart: RestartPolicy::OnFailure,
+                max_restarts: Some(3),
             }),
             pane_name: Some("my_pane_name".to_owned()),
             coordinates: FloatingPaneCoordinates::new(
@@ -1488,6 +1585,9 @@ fn test_client_messages() {
                     context: demo_context.clone(),
                 }),
                 use_terminal_title: false,
+                env: BTreeMap::new(),
+                restart: RestartPolicy::Never,
+                max_restarts: None,
             }),
             direction: Some(Direction::Right),
             pane_name: Some("my_pane_name".to_owned()),
@@ -1515,6 +1615,9 @@ fn test_client_messages() {
                     context: demo_context.clone(),
                 }),
                 use_terminal_title: false,
+                env: BTreeMap::new(),
+                restart: RestartPolicy::Never,
+                max_restarts: None,
             }),
             pane_name: Some("my_pane_name".to_owned()),
             near_current_pane: false,
@@ -1556,6 +1659,9 @@ fn test_client_messages() {
                     context: demo_context.clone(),
                 }),
                 use_terminal_title: false,
+                env: BTreeMap::new(),
+                restart: RestartPolicy::Never,
+                max_restarts: None,
             }),
             pane_name: Some("my_pane_name".to_owned()),
             near_current_pane: false,
@@ -2040,6 +2146,9 @@ fn test_client_messages() {
                     hold_on_start: false,
                     originating_plugin: None,
                     use_terminal_title: true,
+                    env: BTreeMap::new(),
+                    restart: RestartPolicy::Never,
+                    max_restarts: None,
                 })),
                 ..Default::default()
             }),
@@ -2071,6 +2180,9 @@ fn test_client_messages() {
                         context: empty_context.clone(),
                     }),
                     use_terminal_title: true,
+                    env: demo_context.clone(),
+                    restart: RestartPolicy::OnFailure,
+                    max_restarts: Some(3),
                 })),
                 ..Default::default()
             }),
@@ -2168,9 +2280,32 @@ fn test_client_messages() {
                     focus: Some(true),
                     already_running: true,
                     pane_initial_contents: Some("pane_initial_contents".to_owned()),
+                    pane_scrollback_snapshot: Some(vec![1, 2, 3]),
                     logical_position: Some(15),
                     default_fg: None,
                     default_bg: None,
+                    log_output: Some(LogOutput {
+                        path: PathBuf::from("/path/to/pane.log"),
+                        strip_ansi: true,
+                        timestamps: true,
+                        max_size: Some(1024),
+                        max_files: Some(3),
+                    }),
+                    monitors: Some(PaneMonitors {
+                        activity: false,
+                        silence_secs: Some(60),
+                    }),
+                    broadcast_group: Some("nodes".to_owned()),
+                    depends_on: vec![
+                        PaneDependency {
+                            pane_name: "db".to_owned(),
+                            readiness: ReadinessProbe::PortOpen(5432),
+                        },
+                        PaneDependency {
+                            pane_name: "migrations".to_owned(),
+                            readiness: ReadinessProbe::ExitSuccess,
+                        },
+                    ],
                 },
                 FloatingPaneLayout {
                     name: Some("third floating layout".to_owned()),
@@ -2265,6 +2400,9 @@ fn test_client_messages() {
                     hold_on_start: false,
                     originating_plugin: None,
                     use_terminal_title: false,
+                    env: BTreeMap::new(),
+                    restart: RestartPolicy::Never,
+                    max_restarts: None,
                 }),
                 CommandOrPlugin::Plugin(RunPluginOrAlias::RunPlugin(RunPlugin::default())),
             ]),
@@ -2372,6 +2510,9 @@ fn test_client_messages() {
                 hold_on_start: false,
                 originating_plugin: None,
                 use_terminal_title: false,
+                env: BTreeMap::new(),
+                restart: RestartPolicy::Never,
+                max_restarts: None,
             },
             near_current_pane: false,
             no_focus: false,
@@ -2599,6 +2740,36 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SearchToggleOption {
+            option: SearchOption::Regex,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::CopyModeMove {
+            motion: CopyModeMotion::ParagraphBackward,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::CopyModeSelect {
+            selection: CopyModeSelection::Line,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::CopyModeYank,
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::ToggleMouseMode,
         terminal_id: Some(1),
@@ -3857,7 +4028,10 @@ fn test_server_messages() {
                 LayoutMetadata {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
-                    update_time: "0".to_owned()
+                    update_time: "0".to_owned(),
+                    parameters: [("service".to_owned(), "api".to_owned())]
+                        .into_iter()
+                        .collect(),
                 }
             )),
             cwd: Some(PathBuf::from("/path/to/cwd")),
@@ -1,11 +1,16 @@
+use crate::consts::ZELLIJ_SESSION_INFO_CACHE_DIR;
+use crate::data::PaneMonitors;
+use crate::home::xdg_data_dir;
 use crate::input::{
-    command::RunCommand,
+    command::{RestartPolicy, RunCommand},
     config::ConfigError,
     layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
-        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        parse_log_size, FloatingPaneLayout, Layout, LayoutConstraint, LogOutput, PaneDependency,
+        PercentOrFixed, PluginResourceLimits, PluginUserConfiguration, ReadinessProbe, Run,
+        RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection, SplitSize,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout, MAX_PLUGIN_MEMORY_MB,
     },
+    permission::is_valid_allowed_host,
 };
 
 use kdl::*;
@@ -23,9 +28,22 @@ use crate::{
     kdl_string_arguments,
 };
 
-use std::path::PathBuf;
+use regex::Regex;
+use std::path::{Component, Path, PathBuf};
 use std::vec::Vec;
 
+// eg. "{{service}}", the parameter name is the first capture group
+const PARAMETER_REFERENCE: &str = r"\{\{\s*([A-Za-z_][A-Za-z0-9_-]*)\s*\}\}";
+
+fn is_a_valid_parameter_name(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && characters.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PaneOrFloatingPane {
     Pane(TiledPaneLayout),
@@ -41,6 +59,8 @@ pub struct KdlLayoutParser<'a> {
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     file_name: Option<PathBuf>,
+    layout_args: BTreeMap<String, String>, // values given by the user for the layout's parameters
+    parameters: BTreeMap<String, String>,  // parameter name -> value to substitute
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -57,8 +77,14 @@ impl<'a> KdlLayoutParser<'a> {
             new_tab_template: None,
             global_cwd,
             file_name: file_name.map(|f| PathBuf::from(f)),
+            layout_args: BTreeMap::new(),
+            parameters: BTreeMap::new(),
         }
     }
+    pub fn with_layout_args(mut self, layout_args: BTreeMap<String, String>) -> Self {
+        self.layout_args = layout_args;
+        self
+    }
     fn is_a_reserved_word(&self, word: &str) -> bool {
         // note that it's important that none of these words happens to also be a config property,
         // otherwise they might collide
@@ -76,6 +102,8 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "args"
             || word == "close_on_exit"
             || word == "start_suspended"
+            || word == "restart"
+            || word == "max_restarts"
             || word == "borderless"
             || word == "focus"
             || word == "name"
@@ -86,6 +114,13 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "scrollback_snapshot_file"
+            || word == "log_output"
+            || word == "monitor_activity"
+            || word == "monitor_silence"
+            || word == "broadcast_group"
+            || word == "depends_on"
+            || word == "params"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -97,8 +132,11 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
+            || property_name == "restart"
+            || property_name == "max_restarts"
             || property_name == "split_direction"
             || property_name == "pane"
             || property_name == "children"
@@ -106,6 +144,12 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "scrollback_snapshot_file"
+            || property_name == "log_output"
+            || property_name == "monitor_activity"
+            || property_name == "monitor_silence"
+            || property_name == "broadcast_group"
+            || property_name == "depends_on"
             || property_name == "default_fg"
             || property_name == "default_bg"
     }
@@ -118,14 +162,23 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
+            || property_name == "restart"
+            || property_name == "max_restarts"
             || property_name == "x"
             || property_name == "y"
             || property_name == "width"
             || property_name == "height"
             || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "scrollback_snapshot_file"
+            || property_name == "log_output"
+            || property_name == "monitor_activity"
+            || property_name == "monitor_silence"
+            || property_name == "broadcast_group"
+            || property_name == "depends_on"
             || property_name == "default_fg"
             || property_name == "default_bg"
     }
@@ -145,6 +198,151 @@ impl<'a> KdlLayoutParser<'a> {
         property_name == "location"
             || property_name == "_allow_exec_host_cmd"
             || property_name == "path"
+            || property_name == "sha256"
+            || property_name == "fuel_budget"
+            || property_name == "max_memory_mb"
+            || property_name == "max_table_elements"
+            || property_name == "max_pending_jobs"
+            || property_name == "max_timers_per_second"
+            || property_name == "allowed_hosts"
+    }
+    fn parse_positive_integer_property(
+        plugin_block: &KdlNode,
+        property_name: &str,
+    ) -> Result<Option<u64>, ConfigError> {
+        let Some(property) = kdl_get_property_or_child!(plugin_block, property_name) else {
+            return Ok(None);
+        };
+        let parsed_property =
+            property
+                .value()
+                .as_i64()
+                .filter(|p| *p > 0)
+                .ok_or(ConfigError::new_kdl_error(
+                    format!("{} must be a positive integer", property_name),
+                    property.span().offset(),
+                    property.span().len(),
+                ))?;
+        Ok(Some(parsed_property as u64))
+    }
+    fn parse_max_memory_mb(plugin_block: &KdlNode) -> Result<Option<usize>, ConfigError> {
+        match KdlLayoutParser::parse_positive_integer_property(plugin_block, "max_memory_mb")? {
+            Some(max_memory_mb) if max_memory_mb > MAX_PLUGIN_MEMORY_MB as u64 => {
+                Err(kdl_parsing_error!(
+                    format!(
+                        "max_memory_mb can be at most {} (the size of the wasm address space)",
+                        MAX_PLUGIN_MEMORY_MB
+                    ),
+                    plugin_block
+                ))
+            },
+            max_memory_mb => Ok(max_memory_mb.map(|m| m as usize)),
+        }
+    }
+    pub fn parse_plugin_resource_limits(
+        plugin_block: &KdlNode,
+        run_plugin_or_alias: &RunPluginOrAlias,
+    ) -> Result<PluginResourceLimits, ConfigError> {
+        let resource_limits = PluginResourceLimits {
+            fuel_budget: KdlLayoutParser::parse_positive_integer_property(
+                plugin_block,
+                "fuel_budget",
+            )?,
+            max_memory_mb: KdlLayoutParser::parse_max_memory_mb(plugin_block)?,
+            max_table_elements: KdlLayoutParser::parse_positive_integer_property(
+                plugin_block,
+                "max_table_elements",
+            )?
+            .map(|t| t as usize),
+            max_pending_jobs: KdlLayoutParser::parse_positive_integer_property(
+                plugin_block,
+                "max_pending_jobs",
+            )?
+            .map(|j| j as usize),
+            max_timers_per_second: KdlLayoutParser::parse_positive_integer_property(
+                plugin_block,
+                "max_timers_per_second",
+            )?
+            .map(|t| t as usize),
+        };
+        if !resource_limits.is_empty() {
+            if let RunPluginOrAlias::Alias(..) = run_plugin_or_alias {
+                return Err(ConfigError::new_kdl_error(
+                    "Resource limits of a plugin alias should be set where the alias is defined"
+                        .into(),
+                    plugin_block.span().offset(),
+                    plugin_block.span().len(),
+                ));
+            }
+        }
+        Ok(resource_limits)
+    }
+    pub fn parse_plugin_allowed_hosts(
+        plugin_block: &KdlNode,
+        run_plugin_or_alias: &RunPluginOrAlias,
+    ) -> Result<Vec<String>, ConfigError> {
+        let Some(allowed_hosts_node) = kdl_get_property_or_child!(plugin_block, "allowed_hosts")
+        else {
+            return Ok(vec![]);
+        };
+        let allowed_hosts: Vec<String> =
+            kdl_get_string_property_or_child_value_with_error!(plugin_block, "allowed_hosts")
+                .map(|s| s.split_whitespace().map(|h| h.to_owned()).collect())
+                .unwrap_or_default();
+        if allowed_hosts.is_empty() {
+            return Err(ConfigError::new_kdl_error(
+                "allowed_hosts must list at least one host or URL prefix".into(),
+                allowed_hosts_node.span().offset(),
+                allowed_hosts_node.span().len(),
+            ));
+        }
+        if let Some(invalid_host) = allowed_hosts.iter().find(|h| !is_valid_allowed_host(h)) {
+            return Err(ConfigError::new_kdl_error(
+                format!("Invalid allowed host '{}', expected a host (eg. api.example.com), a wildcard subdomain (eg. *.example.com) or a URL prefix (eg. https://example.com/api/)", invalid_host),
+                allowed_hosts_node.span().offset(),
+                allowed_hosts_node.span().len(),
+            ));
+        }
+        if let RunPluginOrAlias::Alias(..) = run_plugin_or_alias {
+            return Err(ConfigError::new_kdl_error(
+                "The allowed hosts of a plugin alias should be set where the alias is defined"
+                    .into(),
+                allowed_hosts_node.span().offset(),
+                allowed_hosts_node.span().len(),
+            ));
+        }
+        Ok(allowed_hosts)
+    }
+    pub fn parse_plugin_sha256(
+        plugin_block: &KdlNode,
+        run_plugin_or_alias: &RunPluginOrAlias,
+    ) -> Result<Option<String>, ConfigError> {
+        let Some(sha256_node) = kdl_get_property_or_child!(plugin_block, "sha256") else {
+            return Ok(None);
+        };
+        let sha256 = kdl_get_string_property_or_child_value_with_error!(plugin_block, "sha256")
+            .map(|s| s.to_ascii_lowercase())
+            .filter(|s| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or(ConfigError::new_kdl_error(
+                "sha256 must be a hex encoded sha256 digest (64 characters)".into(),
+                sha256_node.span().offset(),
+                sha256_node.span().len(),
+            ))?;
+        let is_remote = matches!(
+            run_plugin_or_alias,
+            RunPluginOrAlias::RunPlugin(RunPlugin {
+                location: RunPluginLocation::Remote(_),
+                ..
+            })
+        );
+        if !is_remote {
+            return Err(ConfigError::new_kdl_error(
+                "sha256 can only be specified for plugins loaded from a remote (http/https) location, aliases should be pinned where they are defined".into(),
+                sha256_node.span().offset(),
+                sha256_node.span().len(),
+            ));
+        }
+        Ok(Some(sha256))
     }
     fn assert_legal_node_name(&self, name: &str, kdl_node: &KdlNode) -> Result<(), ConfigError> {
         if name.contains(char::is_whitespace) {
@@ -340,6 +538,19 @@ impl<'a> KdlLayoutParser<'a> {
             )
         })?
         .with_initial_cwd(cwd);
+        let sha256 = KdlLayoutParser::parse_plugin_sha256(plugin_block, &run_plugin_or_alias)?;
+        let mut resource_limits =
+            KdlLayoutParser::parse_plugin_resource_limits(plugin_block, &run_plugin_or_alias)?;
+        if self.is_remote_layout() {
+            // otherwise a layout loaded from a URL could lift the limits of the plugins it starts
+            resource_limits = PluginResourceLimits::default();
+        }
+        let allowed_hosts =
+            KdlLayoutParser::parse_plugin_allowed_hosts(plugin_block, &run_plugin_or_alias)?;
+        let run_plugin_or_alias = run_plugin_or_alias
+            .with_sha256(sha256)
+            .with_resource_limits(resource_limits)
+            .with_allowed_hosts(allowed_hosts);
         Ok(Some(Run::Plugin(run_plugin_or_alias)))
     }
     pub fn parse_plugin_user_configuration(
@@ -407,6 +618,64 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    // env {
+    //     DATABASE_URL "postgres://localhost/staging"
+    // }
+    fn parse_env(
+        &self,
+        pane_node: &KdlNode,
+    ) -> Result<Option<BTreeMap<String, String>>, ConfigError> {
+        match kdl_get_child!(pane_node, "env") {
+            Some(kdl_env) => {
+                let env_vars = kdl_children_nodes!(kdl_env).unwrap_or_default();
+                if env_vars.is_empty() {
+                    return Err(kdl_parsing_error!(format!("env cannot be empty and should contain one or more environment variables (eg. env {{ KEY \"value\"; }})"), kdl_env));
+                }
+                let mut env = BTreeMap::new();
+                for env_var in env_vars {
+                    let env_var_name = kdl_name!(env_var);
+                    let env_var_value = kdl_first_entry_as_string!(env_var)
+                        .map(|s| s.to_string())
+                        .or_else(|| kdl_first_entry_as_i64!(env_var).map(|i| i.to_string()))
+                        .ok_or_else(|| {
+                            kdl_parsing_error!(
+                                format!("Failed to parse env var: {:?}", env_var_name),
+                                env_var
+                            )
+                        })?;
+                    env.insert(env_var_name.to_string(), env_var_value);
+                }
+                Ok(Some(env))
+            },
+            None => Ok(None),
+        }
+    }
+    // restart "on-failure" max_restarts=5
+    fn parse_restart(
+        &self,
+        pane_node: &KdlNode,
+    ) -> Result<(Option<RestartPolicy>, Option<usize>), ConfigError> {
+        let restart = match kdl_get_string_property_or_child_value_with_error!(pane_node, "restart")
+        {
+            Some(restart) => Some(
+                RestartPolicy::from_str(restart).map_err(|e| kdl_parsing_error!(e, pane_node))?,
+            ),
+            None => None,
+        };
+        let max_restarts = match kdl_get_int_property_or_child_value!(pane_node, "max_restarts") {
+            Some(max_restarts) => Some(usize::try_from(max_restarts).map_err(|_| {
+                kdl_parsing_error!(
+                    format!(
+                        "max_restarts must be a positive number, found: {}",
+                        max_restarts
+                    ),
+                    pane_node
+                )
+            })?),
+            None => None,
+        };
+        Ok((restart, max_restarts))
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -415,6 +684,30 @@ impl<'a> KdlLayoutParser<'a> {
             (None, None) => None,
         })
     }
+    fn is_remote_layout(&self) -> bool {
+        self.file_name
+            .as_ref()
+            .and_then(|f| f.to_str())
+            .map(|f| f.starts_with("http://") || f.starts_with("https://"))
+            .unwrap_or(false)
+    }
+    // properties that read or write local files are not trusted in layouts loaded from a URL
+    fn assert_not_in_remote_layout(
+        &self,
+        kdl_node: &KdlNode,
+        property_name: &str,
+    ) -> Result<(), ConfigError> {
+        if self.is_remote_layout() {
+            return Err(kdl_parsing_error!(
+                format!(
+                    "{} cannot be used in a layout loaded from a URL",
+                    property_name
+                ),
+                kdl_node
+            ));
+        }
+        Ok(())
+    }
     fn parse_path(
         &self,
         kdl_node: &KdlNode,
@@ -428,6 +721,275 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_log_output(&self, kdl_node: &KdlNode) -> Result<Option<LogOutput>, ConfigError> {
+        let Some(path) = self.parse_path(kdl_node, "log_output")? else {
+            return Ok(None);
+        };
+        self.assert_not_in_remote_layout(kdl_node, "log_output")?;
+        // relative paths are relative to the layout file, like contents_file
+        let path = match self.file_name.as_ref().and_then(|f| f.parent()) {
+            Some(layout_folder) if path.is_relative() => layout_folder.join(path),
+            _ => path,
+        };
+        self.assert_log_path_is_allowed(&path, kdl_node)?;
+        let mut log_output = LogOutput {
+            path,
+            ..Default::default()
+        };
+        // the options can only be given in the node form, eg.
+        // log_output "/tmp/ci.log" strip_ansi=true timestamps=true max_size="10M" max_files=3
+        if let Some(log_output_node) = kdl_get_child!(kdl_node, "log_output") {
+            log_output.strip_ansi =
+                kdl_get_bool_property_or_child_value_with_error!(log_output_node, "strip_ansi")
+                    .unwrap_or(false);
+            log_output.timestamps =
+                kdl_get_bool_property_or_child_value_with_error!(log_output_node, "timestamps")
+                    .unwrap_or(false);
+            if let Some(max_size) = kdl_get_property_or_child!(log_output_node, "max_size") {
+                let max_size = match max_size.value().as_i64() {
+                    Some(max_size) => parse_log_size(&max_size.to_string()),
+                    None => match max_size.value().as_string() {
+                        Some(max_size) => parse_log_size(max_size),
+                        None => Err(format!("Invalid size: {}", max_size.value())),
+                    },
+                };
+                log_output.max_size =
+                    Some(max_size.map_err(|e| kdl_parsing_error!(e, log_output_node))?);
+            }
+            if let Some(max_files) =
+                kdl_get_int_property_or_child_value!(log_output_node, "max_files")
+            {
+                log_output.max_files = Some(usize::try_from(max_files).map_err(|_| {
+                    kdl_parsing_error!(
+                        format!("max_files must be a positive number, found: {}", max_files),
+                        log_output_node
+                    )
+                })?);
+            }
+        }
+        Ok(Some(log_output))
+    }
+    // layouts can only log to their own folder or to the zellij data folder, lest they be used to
+    // append to arbitrary files (eg. ~/.bashrc)
+    fn assert_log_path_is_allowed(
+        &self,
+        path: &Path,
+        kdl_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        let layout_folder = self
+            .file_name
+            .as_ref()
+            .and_then(|f| f.parent())
+            .filter(|f| !f.as_os_str().is_empty());
+        // resurrection layouts are written by zellij itself, from the logs of the session's panes
+        let is_resurrection_layout = layout_folder
+            .map(|f| f.starts_with(&*ZELLIJ_SESSION_INFO_CACHE_DIR))
+            .unwrap_or(false);
+        let data_dir = xdg_data_dir();
+        let is_in_allowed_folder = layout_folder.map(|f| path.starts_with(f)).unwrap_or(false)
+            || path.starts_with(&data_dir);
+        let leaves_its_folder = path
+            .components()
+            .any(|component| component == Component::ParentDir);
+        if is_resurrection_layout || (is_in_allowed_folder && !leaves_its_folder) {
+            Ok(())
+        } else {
+            Err(kdl_parsing_error!(
+                format!(
+                    "log_output must be in the folder of the layout or in {}, found: {}",
+                    data_dir.display(),
+                    path.display()
+                ),
+                kdl_node
+            ))
+        }
+    }
+    // monitor_activity=true monitor_silence=30, on top of the monitors of the template (if any)
+    fn parse_pane_monitors(
+        &self,
+        kdl_node: &KdlNode,
+        template_monitors: Option<PaneMonitors>,
+    ) -> Result<Option<PaneMonitors>, ConfigError> {
+        let activity =
+            kdl_get_bool_property_or_child_value_with_error!(kdl_node, "monitor_activity");
+        let silence = kdl_get_int_property_or_child_value!(kdl_node, "monitor_silence");
+        if activity.is_none() && silence.is_none() {
+            return Ok(template_monitors);
+        }
+        let mut monitors = template_monitors.unwrap_or_default();
+        if let Some(activity) = activity {
+            monitors.activity = activity;
+        }
+        if let Some(silence) = silence {
+            let silence_secs = u64::try_from(silence)
+                .ok()
+                .filter(|silence_secs| *silence_secs > 0)
+                .ok_or_else(|| {
+                    kdl_parsing_error!(
+                        format!(
+                            "monitor_silence must be a positive number of seconds, found: {}",
+                            silence
+                        ),
+                        kdl_node
+                    )
+                })?;
+            monitors.silence_secs = Some(silence_secs);
+        }
+        Ok(Some(monitors))
+    }
+    // panes sharing a broadcast_group can have their input synced together
+    fn parse_broadcast_group(&self, kdl_node: &KdlNode) -> Result<Option<String>, ConfigError> {
+        Ok(
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "broadcast_group")
+                .map(|broadcast_group| broadcast_group.to_string()),
+        )
+    }
+    // depends_on "db" (waits for "db" to exit successfully), or with one of the readiness probes:
+    // depends_on "db" output="ready to accept connections" / port=5432 / file="/tmp/db.ready"
+    fn parse_depends_on(&self, kdl_node: &KdlNode) -> Result<Vec<PaneDependency>, ConfigError> {
+        let mut depends_on = vec![];
+        if let Some(pane_name) = kdl_node.get("depends_on") {
+            let pane_name = pane_name.value().as_string().ok_or_else(|| {
+                kdl_parsing_error!(
+                    "depends_on should be the name of a pane in this tab".into(),
+                    kdl_node
+                )
+            })?;
+            depends_on.push(PaneDependency {
+                pane_name: pane_name.to_owned(),
+                readiness: ReadinessProbe::ExitSuccess,
+            });
+        }
+        for dependency_node in kdl_children_nodes!(kdl_node)
+            .unwrap_or_default()
+            .iter()
+            .filter(|n| kdl_name!(n) == "depends_on")
+        {
+            let pane_name = kdl_first_entry_as_string!(dependency_node).ok_or_else(|| {
+                kdl_parsing_error!(
+                    "depends_on should be the name of a pane in this tab".into(),
+                    dependency_node
+                )
+            })?;
+            let output =
+                kdl_get_string_property_or_child_value_with_error!(dependency_node, "output");
+            let port = kdl_get_int_property_or_child_value!(dependency_node, "port");
+            let file = self.parse_path(dependency_node, "file")?;
+            let readiness = match (output, port, file) {
+                (None, None, None) => ReadinessProbe::ExitSuccess,
+                (Some(output), None, None) => {
+                    Regex::new(output).map_err(|e| {
+                        kdl_parsing_error!(
+                            format!("Invalid output regex for depends_on: {}", e),
+                            dependency_node
+                        )
+                    })?;
+                    ReadinessProbe::OutputMatches(output.to_owned())
+                },
+                (None, Some(port), None) => {
+                    ReadinessProbe::PortOpen(u16::try_from(port).map_err(|_| {
+                        kdl_parsing_error!(
+                            format!("port must be a valid TCP port, found: {}", port),
+                            dependency_node
+                        )
+                    })?)
+                },
+                (None, None, Some(file)) => ReadinessProbe::FileExists(file),
+                _ => {
+                    return Err(kdl_parsing_error!(
+                        "depends_on can only have one of output, port or file".into(),
+                        dependency_node
+                    ));
+                },
+            };
+            depends_on.push(PaneDependency {
+                pane_name: pane_name.to_owned(),
+                readiness,
+            });
+        }
+        Ok(depends_on)
+    }
+    // every depends_on should name exactly one pane in its tab, and panes cannot wait for each
+    // other in a cycle - this is checked once the panes of tab templates are in place
+    fn assert_valid_pane_dependencies(
+        &self,
+        layout: &Layout,
+        layout_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        let tabs = layout
+            .tabs
+            .iter()
+            .map(|(_, tiled_panes, floating_panes)| (tiled_panes, floating_panes))
+            .chain(
+                layout
+                    .template
+                    .iter()
+                    .map(|(tiled_panes, floating_panes)| (tiled_panes, floating_panes)),
+            );
+        for (tiled_panes, floating_panes) in tabs {
+            let mut panes = vec![];
+            collect_pane_dependencies(tiled_panes, &mut panes);
+            for floating_pane in floating_panes {
+                panes.push((floating_pane.name.as_deref(), &floating_pane.depends_on));
+            }
+            let mut panes_by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+            let mut name_counts: HashMap<&str, usize> = HashMap::new();
+            for (pane_name, depends_on) in &panes {
+                if let Some(pane_name) = pane_name {
+                    *name_counts.entry(pane_name).or_default() += 1;
+                    panes_by_name.insert(
+                        pane_name,
+                        depends_on.iter().map(|d| d.pane_name.as_str()).collect(),
+                    );
+                }
+            }
+            for dependency in panes.iter().flat_map(|(_, depends_on)| depends_on.iter()) {
+                let error = match name_counts.get(dependency.pane_name.as_str()) {
+                    None => "there is no pane with this name in its tab",
+                    Some(count) if *count > 1 => "more than one pane in its tab has this name",
+                    Some(_) => continue,
+                };
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "depends_on \"{}\" is invalid: {}",
+                        dependency.pane_name, error
+                    ),
+                    layout_node.span().offset(),
+                    layout_node.span().len(),
+                ));
+            }
+            if let Some(cycle) = dependency_cycle(&panes_by_name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("Panes cannot depend on each other: {}", cycle.join(" -> ")),
+                    layout_node.span().offset(),
+                    layout_node.span().len(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    // panes with dependencies start suspended and are launched once all of them are ready
+    fn suspend_until_dependencies_are_ready(
+        &self,
+        run: &mut Option<Run>,
+        depends_on: &[PaneDependency],
+        pane_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        if depends_on.is_empty() {
+            return Ok(());
+        }
+        match run {
+            Some(run @ Run::Command(_)) => {
+                run.add_start_suspended(Some(true));
+                Ok(())
+            },
+            _ => Err(ConfigError::new_layout_kdl_error(
+                "depends_on can only be set if a command was specified".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            )),
+        }
+    }
     fn parse_pane_command(
         &self,
         pane_node: &KdlNode,
@@ -437,18 +999,27 @@ impl<'a> KdlLayoutParser<'a> {
         let edit = self.parse_path(pane_node, "edit")?;
         let cwd = self.parse_path(pane_node, "cwd")?;
         let args = self.parse_args(pane_node)?;
+        let env = self.parse_env(pane_node)?;
         let close_on_exit =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
         let start_suspended =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "start_suspended");
+        let (restart, max_restarts) = self.parse_restart(pane_node)?;
         if !is_template {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
                 &args,
+                &env,
                 &close_on_exit,
                 &start_suspended,
                 pane_node,
             )?;
+            self.assert_restart_has_a_command(
+                command.is_some(),
+                &restart,
+                &max_restarts,
+                pane_node,
+            )?;
         }
         let hold_on_close = close_on_exit.map(|c| !c).unwrap_or(true);
         let hold_on_start = start_suspended.map(|c| c).unwrap_or(false);
@@ -460,6 +1031,9 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                env: env.unwrap_or_default(),
+                restart: restart.unwrap_or_default(),
+                max_restarts,
                 ..Default::default()
             }))),
             (None, Some(edit), Some(cwd)) => {
@@ -538,8 +1112,20 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let scrollback_snapshot_file = kdl_get_string_property_or_child_value_with_error!(
+            kdl_node,
+            "scrollback_snapshot_file"
+        );
+        if scrollback_snapshot_file.is_some() {
+            self.assert_not_in_remote_layout(kdl_node, "scrollback_snapshot_file")?;
+        }
         let split_size = self.parse_split_size(kdl_node)?;
-        let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let mut run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let log_output = self.parse_log_output(kdl_node)?;
+        let monitors = self.parse_pane_monitors(kdl_node, None)?;
+        let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+        let depends_on = self.parse_depends_on(kdl_node)?;
+        self.suspend_until_dependencies_are_ready(&mut run, &depends_on, kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -580,6 +1166,12 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
+        let pane_scrollback_snapshot = scrollback_snapshot_file.and_then(|snapshot_file| {
+            self.file_name
+                .as_ref()
+                .and_then(|f| f.parent())
+                .and_then(|parent_folder| std::fs::read(parent_folder.join(snapshot_file)).ok())
+        });
         Ok(TiledPaneLayout {
             borderless,
             focus,
@@ -593,8 +1185,13 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            pane_scrollback_snapshot,
             default_fg,
             default_bg,
+            log_output,
+            monitors,
+            broadcast_group,
+            depends_on,
             ..Default::default()
         })
     }
@@ -609,12 +1206,24 @@ impl<'a> KdlLayoutParser<'a> {
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
         let borderless = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "borderless");
-        let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let mut run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let log_output = self.parse_log_output(kdl_node)?;
+        let monitors = self.parse_pane_monitors(kdl_node, None)?;
+        let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+        let depends_on = self.parse_depends_on(kdl_node)?;
+        self.suspend_until_dependencies_are_ready(&mut run, &depends_on, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let scrollback_snapshot_file = kdl_get_string_property_or_child_value_with_error!(
+            kdl_node,
+            "scrollback_snapshot_file"
+        );
+        if scrollback_snapshot_file.is_some() {
+            self.assert_not_in_remote_layout(kdl_node, "scrollback_snapshot_file")?;
+        }
         let default_fg = kdl_get_string_property_or_child_value_with_error!(kdl_node, "default_fg")
             .map(|s| s.to_string());
         let default_bg = kdl_get_string_property_or_child_value_with_error!(kdl_node, "default_bg")
@@ -628,6 +1237,12 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
+        let pane_scrollback_snapshot = scrollback_snapshot_file.and_then(|snapshot_file| {
+            self.file_name
+                .as_ref()
+                .and_then(|f| f.parent())
+                .and_then(|parent_folder| std::fs::read(parent_folder.join(snapshot_file)).ok())
+        });
         Ok(FloatingPaneLayout {
             name,
             height,
@@ -639,8 +1254,13 @@ impl<'a> KdlLayoutParser<'a> {
             pinned,
             borderless,
             pane_initial_contents,
+            pane_scrollback_snapshot,
             default_fg,
             default_bg,
+            log_output,
+            monitors,
+            broadcast_group,
+            depends_on,
             ..Default::default()
         })
     }
@@ -729,14 +1349,20 @@ impl<'a> KdlLayoutParser<'a> {
                 let is_expanded_in_stack =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart, max_restarts) = self.parse_restart(kdl_node)?;
                 let split_size = self.parse_split_size(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+                let log_output = self.parse_log_output(kdl_node)?;
+                let monitors = self.parse_pane_monitors(kdl_node, pane_template.monitors)?;
+                let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+                let depends_on = self.parse_depends_on(kdl_node)?;
 
                 let external_children_index = if should_mark_external_children_index {
                     self.populate_external_children_index(kdl_node)?
@@ -747,10 +1373,17 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
                 )?;
+                self.assert_restart_has_a_command(
+                    run.is_some() || pane_template.run.is_some(),
+                    &restart,
+                    &max_restarts,
+                    kdl_node,
+                )?;
                 self.insert_children_to_pane_template(
                     kdl_node,
                     &mut pane_template,
@@ -761,8 +1394,10 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart(restart, max_restarts);
                 };
                 if let Some(borderless) = borderless {
                     pane_template.borderless = Some(borderless);
@@ -776,6 +1411,21 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(exclude_from_sync) = exclude_from_sync {
                     pane_template.exclude_from_sync = Some(exclude_from_sync);
                 }
+                if let Some(log_output) = log_output {
+                    pane_template.log_output = Some(log_output);
+                }
+                pane_template.monitors = monitors;
+                if let Some(broadcast_group) = broadcast_group {
+                    pane_template.broadcast_group = Some(broadcast_group);
+                }
+                if !depends_on.is_empty() {
+                    pane_template.depends_on = depends_on;
+                }
+                self.suspend_until_dependencies_are_ready(
+                    &mut pane_template.run,
+                    &pane_template.depends_on,
+                    kdl_node,
+                )?;
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
@@ -835,26 +1485,37 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart, max_restarts) = self.parse_restart(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
                 )?;
+                self.assert_restart_has_a_command(
+                    run.is_some() || pane_template.run.is_some(),
+                    &restart,
+                    &max_restarts,
+                    kdl_node,
+                )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart(restart, max_restarts);
                 };
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
@@ -867,6 +1528,10 @@ impl<'a> KdlLayoutParser<'a> {
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
                 let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+                let log_output = self.parse_log_output(kdl_node)?;
+                let monitors = self.parse_pane_monitors(kdl_node, pane_template.monitors)?;
+                let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+                let depends_on = self.parse_depends_on(kdl_node)?;
                 if let Some(height) = height {
                     pane_template.height = Some(height);
                 }
@@ -882,6 +1547,21 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(pinned) = pinned {
                     pane_template.pinned = Some(pinned);
                 }
+                if let Some(log_output) = log_output {
+                    pane_template.log_output = Some(log_output);
+                }
+                pane_template.monitors = monitors;
+                if let Some(broadcast_group) = broadcast_group {
+                    pane_template.broadcast_group = Some(broadcast_group);
+                }
+                if !depends_on.is_empty() {
+                    pane_template.depends_on = depends_on;
+                }
+                self.suspend_until_dependencies_are_ready(
+                    &mut pane_template.run,
+                    &pane_template.depends_on,
+                    kdl_node,
+                )?;
                 Ok(pane_template)
             },
             PaneOrFloatingPane::Either(mut pane_template) => {
@@ -889,26 +1569,37 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
+                let (restart, max_restarts) = self.parse_restart(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
                 )?;
+                self.assert_restart_has_a_command(
+                    run.is_some() || pane_template.run.is_some(),
+                    &restart,
+                    &max_restarts,
+                    kdl_node,
+                )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
+                    pane_template_run_command.add_restart(restart, max_restarts);
                 };
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
@@ -921,6 +1612,10 @@ impl<'a> KdlLayoutParser<'a> {
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
                 let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
                 let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+                let log_output = self.parse_log_output(kdl_node)?;
+                let monitors = self.parse_pane_monitors(kdl_node, pane_template.monitors)?;
+                let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+                let depends_on = self.parse_depends_on(kdl_node)?;
                 let mut floating_pane = FloatingPaneLayout::from(&pane_template);
                 if let Some(height) = height {
                     floating_pane.height = Some(height);
@@ -937,6 +1632,21 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(pinned) = pinned {
                     floating_pane.pinned = Some(pinned);
                 }
+                if let Some(log_output) = log_output {
+                    floating_pane.log_output = Some(log_output);
+                }
+                floating_pane.monitors = monitors;
+                if let Some(broadcast_group) = broadcast_group {
+                    floating_pane.broadcast_group = Some(broadcast_group);
+                }
+                if !depends_on.is_empty() {
+                    floating_pane.depends_on = depends_on;
+                }
+                self.suspend_until_dependencies_are_ready(
+                    &mut floating_pane.run,
+                    &floating_pane.depends_on,
+                    kdl_node,
+                )?;
                 Ok(floating_pane)
             },
         }
@@ -1088,6 +1798,9 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let monitors = self.parse_pane_monitors(kdl_node, None)?;
+        let broadcast_group = self.parse_broadcast_group(kdl_node)?;
+        let depends_on = self.parse_depends_on(kdl_node)?;
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
         let can_be_either_floating_or_tiled =
@@ -1100,6 +1813,9 @@ impl<'a> KdlLayoutParser<'a> {
                     PaneOrFloatingPane::Either(TiledPaneLayout {
                         focus,
                         run,
+                        monitors,
+                        broadcast_group,
+                        depends_on,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1124,6 +1840,9 @@ impl<'a> KdlLayoutParser<'a> {
                         x,
                         y,
                         pinned,
+                        monitors,
+                        broadcast_group,
+                        depends_on,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1162,6 +1881,9 @@ impl<'a> KdlLayoutParser<'a> {
                         children: pane_parts,
                         children_are_stacked,
                         is_expanded_in_stack,
+                        monitors,
+                        broadcast_group,
+                        depends_on,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1343,6 +2065,7 @@ impl<'a> KdlLayoutParser<'a> {
         pane_run: &Option<Run>,
         pane_template_run: &Option<Run>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1353,6 +2076,12 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_node
             ));
         }
+        if let (None, None, true) = (pane_run, pane_template_run, env.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("env can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
         if let (None, None, true) = (pane_run, pane_template_run, close_on_exit.is_some()) {
             return Err(kdl_parsing_error!(
                 format!("close_on_exit can only be specified if a command was specified either in the pane_template or in the pane"),
@@ -1371,6 +2100,7 @@ impl<'a> KdlLayoutParser<'a> {
         &self,
         command: &Option<PathBuf>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1397,6 +2127,36 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
+            if env.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "env can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    fn assert_restart_has_a_command(
+        &self,
+        has_command: bool,
+        restart: &Option<RestartPolicy>,
+        max_restarts: &Option<usize>,
+        pane_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        if !has_command && restart.is_some() {
+            return Err(ConfigError::new_layout_kdl_error(
+                "restart can only be set if a command was specified".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
+        }
+        if !has_command && max_restarts.is_some() {
+            return Err(ConfigError::new_layout_kdl_error(
+                "max_restarts can only be set if a command was specified".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
         }
         Ok(())
     }
@@ -1527,9 +2287,11 @@ impl<'a> KdlLayoutParser<'a> {
                 _ => true,
             })
             .unwrap_or(false);
+        let has_log_output_prop =
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "log_output").is_some();
         let has_nested_nodes_or_children_block = self.has_child_panes_tabs_or_templates(kdl_node);
         if has_nested_nodes_or_children_block
-            && (has_borderless_prop || has_non_cwd_run_prop || has_cwd_prop)
+            && (has_borderless_prop || has_non_cwd_run_prop || has_cwd_prop || has_log_output_prop)
         {
             let mut offending_nodes = vec![];
             if has_borderless_prop {
@@ -1541,6 +2303,9 @@ impl<'a> KdlLayoutParser<'a> {
             if has_cwd_prop {
                 offending_nodes.push("cwd");
             }
+            if has_log_output_prop {
+                offending_nodes.push("log_output");
+            }
             Err(ConfigError::new_layout_kdl_error(
                 format!(
                     "Cannot have both properties ({}) and nested children",
@@ -2392,12 +3157,163 @@ impl<'a> KdlLayoutParser<'a> {
         };
         Ok(())
     }
+    fn parse_parameter_declarations(
+        &self,
+        params_node: &KdlNode,
+    ) -> Result<BTreeMap<String, String>, ConfigError> {
+        let mut parameters = BTreeMap::new();
+        for parameter in kdl_children_nodes!(params_node).unwrap_or(&[]) {
+            let parameter_name = kdl_name!(parameter);
+            if !is_a_valid_parameter_name(parameter_name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Invalid parameter name \"{}\", parameter names can only contain letters, digits, dashes and underscores and must not start with a digit or a dash",
+                        parameter_name
+                    ),
+                    parameter.span().offset(),
+                    parameter.span().len(),
+                ));
+            }
+            let default_value = kdl_first_entry_as_string!(parameter)
+                .map(|s| s.to_owned())
+                .or_else(|| kdl_first_entry_as_i64!(parameter).map(|i| i.to_string()))
+                .ok_or(ConfigError::new_layout_kdl_error(
+                    format!(
+                        "Layout parameters must have a default value, eg. {} \"value\"",
+                        parameter_name
+                    ),
+                    parameter.span().offset(),
+                    parameter.span().len(),
+                ))?;
+            if parameters
+                .insert(parameter_name.to_owned(), default_value)
+                .is_some()
+            {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("Duplicate layout parameter: {}", parameter_name),
+                    parameter.span().offset(),
+                    parameter.span().len(),
+                ));
+            }
+        }
+        Ok(parameters)
+    }
+    // removes the params node from the layout node, so that the rest of the parser never sees it
+    fn take_params_node(&self, layout_node: &mut KdlNode) -> Result<Option<KdlNode>, ConfigError> {
+        let Some(layout_children) = layout_node.children_mut() else {
+            return Ok(None);
+        };
+        let mut params_nodes: Vec<KdlNode> = vec![];
+        layout_children.nodes_mut().retain(|node| {
+            if kdl_name!(node) == "params" {
+                params_nodes.push(node.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if let Some(extra_params_node) = params_nodes.get(1) {
+            return Err(ConfigError::new_layout_kdl_error(
+                "Only one params node per layout allowed".into(),
+                extra_params_node.span().offset(),
+                extra_params_node.span().len(),
+            ));
+        }
+        Ok(params_nodes.pop())
+    }
+    /// The parameters declared by this layout with their default values
+    pub fn parse_parameters(&self) -> Result<BTreeMap<String, String>, ConfigError> {
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        let layout_node = kdl_layout
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| kdl_name!(n) == "layout");
+        match layout_node {
+            Some(layout_node) => match self.take_params_node(layout_node)? {
+                Some(params_node) => self.parse_parameter_declarations(&params_node),
+                None => Ok(BTreeMap::new()),
+            },
+            None => Ok(BTreeMap::new()),
+        }
+    }
+    fn populate_parameters(&mut self, kdl_layout: &mut KdlDocument) -> Result<(), ConfigError> {
+        let Some(layout_node) = kdl_layout
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| kdl_name!(n) == "layout")
+        else {
+            // a missing layout node is reported when parsing it
+            return Ok(());
+        };
+        let params_node = self.take_params_node(layout_node)?;
+        let mut parameters = match &params_node {
+            Some(params_node) => self.parse_parameter_declarations(params_node)?,
+            None => BTreeMap::new(),
+        };
+        for (parameter_name, value) in &self.layout_args {
+            match parameters.get_mut(parameter_name) {
+                Some(parameter_value) => *parameter_value = value.clone(),
+                None => {
+                    let error_span = params_node
+                        .as_ref()
+                        .map(|p| p.span())
+                        .unwrap_or_else(|| layout_node.span());
+                    return Err(ConfigError::new_layout_kdl_error(
+                        format!(
+                            "This layout has no parameter named \"{}\" (available parameters: {})",
+                            parameter_name,
+                            if parameters.is_empty() {
+                                "none".to_owned()
+                            } else {
+                                parameters.keys().cloned().collect::<Vec<_>>().join(", ")
+                            }
+                        ),
+                        error_span.offset(),
+                        error_span.len(),
+                    ));
+                },
+            }
+        }
+        self.parameters = parameters;
+        self.substitute_parameters(std::slice::from_mut(layout_node));
+        Ok(())
+    }
+    // replaces references to parameters (eg. "{{service}}") in all the string values of these
+    // nodes and their children, references to parameters that were not declared are left as is
+    // so that eg. template strings meant for commands keep working
+    fn substitute_parameters(&self, kdl_nodes: &mut [KdlNode]) {
+        if self.parameters.is_empty() {
+            return;
+        }
+        let parameter_reference = Regex::new(PARAMETER_REFERENCE).unwrap();
+        let mut nodes_to_substitute: Vec<&mut KdlNode> = kdl_nodes.iter_mut().collect();
+        while let Some(kdl_node) = nodes_to_substitute.pop() {
+            for entry in kdl_node.entries_mut() {
+                if let KdlValue::String(value) | KdlValue::RawString(value) = entry.value_mut() {
+                    let substituted =
+                        parameter_reference.replace_all(value, |captures: &regex::Captures| {
+                            self.parameters
+                                .get(&captures[1])
+                                .cloned()
+                                .unwrap_or_else(|| captures[0].to_owned())
+                        });
+                    if let std::borrow::Cow::Owned(substituted) = substituted {
+                        *value = substituted;
+                    }
+                }
+            }
+            if let Some(children) = kdl_node.children_mut() {
+                nodes_to_substitute.extend(children.nodes_mut().iter_mut());
+            }
+        }
+    }
     pub fn parse_external_swap_layouts(
         &mut self,
         raw_swap_layouts: &str,
         mut existing_layout: Layout,
     ) -> Result<Layout, ConfigError> {
-        let kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        let mut kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        self.substitute_parameters(kdl_swap_layout.nodes_mut());
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
 
@@ -2441,7 +3357,8 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(existing_layout)
     }
     pub fn parse(&mut self) -> Result<Layout, ConfigError> {
-        let kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        self.populate_parameters(&mut kdl_layout)?;
         let layout_node = kdl_layout
             .nodes()
             .iter()
@@ -2495,7 +3412,7 @@ impl<'a> KdlLayoutParser<'a> {
                 )?;
             }
         }
-        if !child_tabs.is_empty() {
+        let layout = if !child_tabs.is_empty() {
             // Check if layout_node has tab properties when there are explicit tabs
             let layout_has_tab_name =
                 kdl_get_string_property_or_child_value!(layout_node, "name").is_some();
@@ -2596,6 +3513,51 @@ impl<'a> KdlLayoutParser<'a> {
                 hide_floating_panes,
                 tab_cwd,
             )
+        }?;
+        self.assert_valid_pane_dependencies(&layout, layout_node)?;
+        Ok(layout)
+    }
+}
+
+fn collect_pane_dependencies<'a>(
+    layout: &'a TiledPaneLayout,
+    panes: &mut Vec<(Option<&'a str>, &'a Vec<PaneDependency>)>,
+) {
+    panes.push((layout.name.as_deref(), &layout.depends_on));
+    for child in &layout.children {
+        collect_pane_dependencies(child, panes);
+    }
+}
+
+// the names of the panes in a dependency cycle, starting and ending with the same pane
+fn dependency_cycle<'a>(panes_by_name: &HashMap<&'a str, Vec<&'a str>>) -> Option<Vec<&'a str>> {
+    fn visit<'a>(
+        pane_name: &'a str,
+        panes_by_name: &HashMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<Vec<&'a str>> {
+        if let Some(position) = path.iter().position(|p| *p == pane_name) {
+            let mut cycle = path[position..].to_vec();
+            cycle.push(pane_name);
+            return Some(cycle);
+        }
+        if !done.insert(pane_name) {
+            return None;
+        }
+        path.push(pane_name);
+        for dependency in panes_by_name.get(pane_name).into_iter().flatten() {
+            if let Some(cycle) = visit(dependency, panes_by_name, path, done) {
+                return Some(cycle);
+            }
         }
+        path.pop();
+        None
     }
+    let mut pane_names: Vec<&str> = panes_by_name.keys().copied().collect();
+    pane_names.sort();
+    let mut done = HashSet::new();
+    pane_names
+        .into_iter()
+        .find_map(|pane_name| visit(pane_name, panes_by_name, &mut vec![], &mut done))
 }
@@ -1,21 +1,24 @@
 mod kdl_layout_parser;
 use crate::data::{
-    BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyWithModifier, LayoutInfo,
-    LayoutMetadata, MultiplayerColors, Palette, PaletteColor, PaneId, PaneInfo, PaneManifest,
-    PermissionType, Resize, SessionInfo, StyleDeclaration, Styling, TabInfo, WebSharing,
-    DEFAULT_STYLES,
+    BareKey, Direction, FilesystemPermission, FloatingPaneCoordinates, InputMode, KeyWithModifier,
+    LayoutInfo, LayoutMetadata, MultiplayerColors, Palette, PaletteColor, PaneId, PaneInfo,
+    PaneManifest, PermissionType, RecordingTarget, Resize, SessionInfo, StyleDeclaration, Styling,
+    TabInfo, WebSharing, DEFAULT_STYLES,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::keybinds::Keybinds;
 use crate::input::layout::{
-    Layout, PercentOrFixed, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, TabLayoutInfo,
+    Layout, PercentOrFixed, PluginResourceLimits, PluginUserConfiguration, RunPlugin,
+    RunPluginOrAlias, TabLayoutInfo,
 };
 use crate::input::options::{
     Clipboard, OnForceClose, Options, PaneFrameStyle, DEFAULT_WORD_SEPARATORS,
 };
-use crate::input::permission::{GrantedPermission, PermissionCache};
+use crate::input::permission::{
+    GrantedFilesystemPermission, GrantedPermission, GrantedWebAccess, PermissionCache,
+};
 use crate::input::plugins::PluginAliases;
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use crate::input::web_client::WebClientConfig;
@@ -29,11 +32,13 @@ use miette::NamedSource;
 
 use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 
-use crate::input::actions::{Action, SearchDirection, SearchOption};
+use crate::input::actions::{
+    Action, CopyModeMotion, CopyModeSelection, SearchDirection, SearchOption,
+};
 use crate::input::command::RunCommandAction;
 
 #[macro_export]
@@ -64,6 +69,7 @@ macro_rules! parse_kdl_action_arguments {
                 "ScrollToNextPrompt" => Ok(Action::ScrollToNextPrompt),
                 "SelectCommandAtScrollPosition" => Ok(Action::SelectCommandAtScrollPosition),
                 "CopyLastCommandOutput" => Ok(Action::CopyLastCommandOutput),
+                "CopyModeYank" => Ok(Action::CopyModeYank),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
@@ -77,6 +83,8 @@ macro_rules! parse_kdl_action_arguments {
                 "ShowFloatingPanes" => Ok(Action::ShowFloatingPanes { tab_id: None }),
                 "HideFloatingPanes" => Ok(Action::HideFloatingPanes { tab_id: None }),
                 "CloseFocus" => Ok(Action::CloseFocus),
+                "ReopenClosedPane" => Ok(Action::ReopenClosedPane),
+                "TogglePaneGroupSync" => Ok(Action::TogglePaneGroupSync),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
                 "NoOp" => Ok(Action::NoOp),
                 "GoToNextTab" => Ok(Action::GoToNextTab),
@@ -570,6 +578,13 @@ impl Action {
                 ansi: false,
             }),
             "DumpLayout" => Ok(Action::DumpLayout),
+            "StartRecording" => Ok(Action::StartRecording {
+                path: Some(PathBuf::from(string)).filter(|path| !path.as_os_str().is_empty()),
+                target: RecordingTarget::FocusedPane,
+            }),
+            "StopRecording" => Ok(Action::StopRecording {
+                path: Some(PathBuf::from(string)).filter(|path| !path.as_os_str().is_empty()),
+            }),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane {
@@ -612,6 +627,26 @@ impl Action {
                     option: toggle_option,
                 })
             },
+            "CopyModeMove" => {
+                let motion = CopyModeMotion::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid copy mode motion: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::CopyModeMove { motion })
+            },
+            "CopyModeSelect" => {
+                let selection = CopyModeSelection::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid copy mode selection: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::CopyModeSelect { selection })
+            },
             "Search" => {
                 let search_direction =
                     SearchDirection::from_str(string.as_str()).map_err(|_| {
@@ -626,6 +661,7 @@ impl Action {
                 })
             },
             "RenameSession" => Ok(Action::RenameSession { name: string }),
+            "SyncBroadcastGroup" => Ok(Action::SyncBroadcastGroup { name: string }),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -733,6 +769,24 @@ impl Action {
                 file_path: None, ..
             } => None,
             Action::DumpLayout => Some(KdlNode::new("DumpLayout")),
+            Action::StartRecording {
+                path,
+                target: RecordingTarget::FocusedPane,
+            } => {
+                let mut node = KdlNode::new("StartRecording");
+                if let Some(path) = path {
+                    node.push(path.display().to_string());
+                }
+                Some(node)
+            },
+            Action::StartRecording { .. } => None,
+            Action::StopRecording { path } => {
+                let mut node = KdlNode::new("StopRecording");
+                if let Some(path) = path {
+                    node.push(path.display().to_string());
+                }
+                Some(node)
+            },
             Action::EditScrollback { ansi } => {
                 let mut node = KdlNode::new("EditScrollback");
                 if *ansi {
@@ -806,6 +860,13 @@ impl Action {
                 Some(node)
             },
             Action::CloseFocus => Some(KdlNode::new("CloseFocus")),
+            Action::ReopenClosedPane => Some(KdlNode::new("ReopenClosedPane")),
+            Action::TogglePaneGroupSync => Some(KdlNode::new("TogglePaneGroupSync")),
+            Action::SyncBroadcastGroup { name } => {
+                let mut node = KdlNode::new("SyncBroadcastGroup");
+                node.push(name.clone());
+                Some(node)
+            },
             Action::PaneNameInput { input: bytes } => {
                 let mut node = KdlNode::new("PaneNameInput");
                 for byte in bytes {
@@ -1279,6 +1340,17 @@ impl Action {
                 node.push(format!("{:?}", search_toggle_option));
                 Some(node)
             },
+            Action::CopyModeMove { motion } => {
+                let mut node = KdlNode::new("CopyModeMove");
+                node.push(format!("{:?}", motion));
+                Some(node)
+            },
+            Action::CopyModeSelect { selection } => {
+                let mut node = KdlNode::new("CopyModeSelect");
+                node.push(format!("{:?}", selection));
+                Some(node)
+            },
+            Action::CopyModeYank => Some(KdlNode::new("CopyModeYank")),
             Action::ToggleMouseMode => Some(KdlNode::new("ToggleMouseMode")),
             Action::PreviousSwapLayout => Some(KdlNode::new("PreviousSwapLayout")),
             Action::NextSwapLayout => Some(KdlNode::new("NextSwapLayout")),
@@ -1594,6 +1666,9 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "CopyLastCommandOutput" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "CopyModeYank" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "PageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -1639,6 +1714,17 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 Ok(Action::HideFloatingPanes { tab_id })
             },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "ReopenClosedPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneGroupSync" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "SyncBroadcastGroup" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -1779,6 +1865,16 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "StartRecording" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "StopRecording" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "NewPane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -2017,6 +2113,16 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "CopyModeMove" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "CopyModeSelect" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "Run" => {
                 let arguments = action_arguments.iter().copied();
                 let mut args = kdl_arguments_that_are_strings(arguments)?;
@@ -2838,6 +2944,9 @@ impl Options {
         let scrollback_lines_to_serialize =
             kdl_property_first_arg_as_i64_or_error!(kdl_options, "scrollback_lines_to_serialize")
                 .map(|(v, _)| v as usize);
+        let serialize_pane_scrollback =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "serialize_pane_scrollback")
+                .map(|(v, _)| v);
         let styled_underlines =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "styled_underlines")
                 .map(|(v, _)| v);
@@ -3006,6 +3115,7 @@ impl Options {
             session_serialization,
             serialize_pane_viewport,
             scrollback_lines_to_serialize,
+            serialize_pane_scrollback,
             styled_underlines,
             serialization_interval,
             disable_session_metadata,
@@ -3847,6 +3957,38 @@ impl Options {
             None
         }
     }
+    fn serialize_pane_scrollback_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Whether the full scrollback of terminal panes (including styles, hyperlinks and prompt",
+            "// marks) is saved in a binary snapshot along with the session and restored when it is",
+            "// resurrected. This is bounded by `scrollback_lines_to_serialize` when it is set",
+            "// Options:",
+            "//   - true",
+            "//   - false (default)",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("serialize_pane_scrollback");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(serialize_pane_scrollback) = self.serialize_pane_scrollback {
+            let mut node = create_node(serialize_pane_scrollback);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(false);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
     fn styled_underlines_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
         let comment_text = format!(
             "{}\n{}\n{}\n{}\n{}\n{}",
@@ -4795,6 +4937,10 @@ impl Options {
         {
             nodes.push(scrollback_lines_to_serialize);
         }
+        if let Some(serialize_pane_scrollback) = self.serialize_pane_scrollback_to_kdl(add_comments)
+        {
+            nodes.push(serialize_pane_scrollback);
+        }
         if let Some(styled_underlines) = self.styled_underlines_to_kdl(add_comments) {
             nodes.push(styled_underlines);
         }
@@ -4909,7 +5055,23 @@ impl Layout {
         raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
         cwd: Option<PathBuf>,
     ) -> Result<Self, ConfigError> {
-        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone());
+        Layout::from_kdl_with_layout_args(
+            raw_layout,
+            file_name,
+            raw_swap_layouts,
+            cwd,
+            BTreeMap::new(),
+        )
+    }
+    pub fn from_kdl_with_layout_args(
+        raw_layout: &str,
+        file_name: Option<String>,
+        raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
+        cwd: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>, // values for the parameters declared in the layout
+    ) -> Result<Self, ConfigError> {
+        let mut kdl_layout_parser =
+            KdlLayoutParser::new(raw_layout, cwd, file_name.clone()).with_layout_args(layout_args);
         let layout = kdl_layout_parser.parse().map_err(|e| match e {
             ConfigError::KdlError(kdl_error) => ConfigError::KdlError(kdl_error.add_src(
                 file_name.unwrap_or_else(|| "N/A".to_owned()),
@@ -4946,6 +5108,10 @@ impl Layout {
             None => Ok(layout),
         }
     }
+    /// The parameters declared in a layout, with their default values
+    pub fn parameters_from_kdl(raw_layout: &str) -> Result<BTreeMap<String, String>, ConfigError> {
+        KdlLayoutParser::new(raw_layout, None, None).parse_parameters()
+    }
 }
 
 fn kdl_layout_error(kdl_error: kdl::KdlError, file_name: String, raw_layout: &str) -> ConfigError {
@@ -5354,9 +5520,87 @@ impl BareKey {
 }
 
 impl Config {
+    /// Parses configuration that does not come from the configuration file (eg. the one in a
+    /// layout, which might have been downloaded), in which `include` and `overlay` are not allowed
     pub fn from_kdl(kdl_config: &str, base_config: Option<Config>) -> Result<Config, ConfigError> {
-        let mut config = base_config.unwrap_or_else(|| Config::default());
+        let config = base_config.unwrap_or_default();
+        let kdl_config: KdlDocument = kdl_config.parse()?;
+        if let Some(kdl_node) = kdl_config
+            .nodes()
+            .iter()
+            .find(|n| kdl_name!(n) == "include" || kdl_name!(n) == "overlay")
+        {
+            return Err(kdl_parsing_error!(
+                format!(
+                    "{} can only be used in the configuration file",
+                    kdl_name!(kdl_node)
+                ),
+                kdl_node
+            ));
+        }
+        Config::from_kdl_document(&kdl_config, config, &mut vec![])
+    }
+    /// Like `from_kdl`, for a configuration read from `config_file_path` - relative `include`
+    /// paths are resolved against the folder of this file
+    pub fn from_kdl_file(
+        kdl_config: &str,
+        base_config: Option<Config>,
+        config_file_path: &Path,
+    ) -> Result<Config, ConfigError> {
+        let config = base_config.unwrap_or_default();
         let kdl_config: KdlDocument = kdl_config.parse()?;
+        let config_file_path = config_file_path
+            .canonicalize()
+            .unwrap_or_else(|_| config_file_path.to_path_buf());
+        Config::from_kdl_document(&kdl_config, config, &mut vec![config_file_path])
+    }
+    /// Whether the configuration file at `config_file_path` uses `include` or `overlay` - such a
+    /// file cannot be replaced with the configuration it resolves to without losing them
+    pub fn file_uses_includes_or_overlays(config_file_path: &Path) -> bool {
+        std::fs::read_to_string(config_file_path)
+            .ok()
+            .and_then(|raw_config| raw_config.parse::<KdlDocument>().ok())
+            .is_some_and(|kdl_config| {
+                kdl_config
+                    .nodes()
+                    .iter()
+                    .any(|n| kdl_name!(n) == "include" || kdl_name!(n) == "overlay")
+            })
+    }
+    /// The files included by the configuration file at `config_file_path`, directly or through
+    /// other included files, including those of overlays that do not apply to this host
+    pub fn included_files(config_file_path: &Path) -> Vec<PathBuf> {
+        let config_file_path = config_file_path
+            .canonicalize()
+            .unwrap_or_else(|_| config_file_path.to_path_buf());
+        let mut included_files = vec![];
+        if let Some(kdl_config) = std::fs::read_to_string(&config_file_path)
+            .ok()
+            .and_then(|raw_config| raw_config.parse::<KdlDocument>().ok())
+        {
+            collect_included_files(
+                &kdl_config,
+                &mut vec![config_file_path],
+                &mut included_files,
+            );
+        }
+        included_files
+    }
+    // file_stack is the chain of files currently being included, ending with the file this
+    // document was read from (it is empty if the document did not come from a file)
+    fn from_kdl_document(
+        kdl_config: &KdlDocument,
+        mut config: Config,
+        file_stack: &mut Vec<PathBuf>,
+    ) -> Result<Config, ConfigError> {
+        // included files are merged first, in order, so that the including file can override them
+        for kdl_include in kdl_config
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) == "include")
+        {
+            config = Config::from_included_file(kdl_include, config, file_stack)?;
+        }
 
         let config_options = Options::from_kdl(&kdl_config)?;
         config.options = config.options.merge(config_options);
@@ -5391,8 +5635,59 @@ impl Config {
             let config_web_client = WebClientConfig::from_kdl(&web_client_config)?;
             config.web_client = config.web_client.merge(config_web_client);
         }
+
+        // matching overlays are merged last so that they can override everything else
+        for kdl_overlay in kdl_config
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) == "overlay")
+        {
+            if overlay_applies(kdl_overlay)? {
+                if let Some(kdl_overlay_config) = kdl_overlay.children() {
+                    config = Config::from_kdl_document(kdl_overlay_config, config, file_stack)?;
+                }
+            }
+        }
         Ok(config)
     }
+    fn from_included_file(
+        kdl_include: &KdlNode,
+        config: Config,
+        file_stack: &mut Vec<PathBuf>,
+    ) -> Result<Config, ConfigError> {
+        let include_path = resolve_include_path(kdl_include, file_stack)?;
+        if file_stack.contains(&include_path) {
+            return Err(kdl_parsing_error!(
+                format!("{} includes itself", include_path.display()),
+                kdl_include
+            ));
+        }
+        let raw_config = std::fs::read_to_string(&include_path).map_err(|e| {
+            kdl_parsing_error!(
+                format!("Failed to read {}: {}", include_path.display(), e),
+                kdl_include
+            )
+        })?;
+
+        file_stack.push(include_path.clone());
+        let config = raw_config
+            .parse::<KdlDocument>()
+            .map_err(ConfigError::from)
+            .and_then(|kdl_config| Config::from_kdl_document(&kdl_config, config, file_stack));
+        file_stack.pop();
+
+        // errors are reported with the source of the file they happened in, errors from files
+        // included further down already have theirs
+        config.map_err(|e| match e {
+            ConfigError::KdlError(kdl_error) if kdl_error.src.is_none() => ConfigError::KdlError(
+                kdl_error.add_src(include_path.display().to_string(), raw_config),
+            ),
+            ConfigError::KdlDeserializationError(kdl_error) => {
+                kdl_layout_error(kdl_error, include_path.display().to_string(), &raw_config)
+            },
+            e => e,
+        })
+    }
     pub fn to_string(&self, add_comments: bool) -> String {
         let mut document = KdlDocument::new();
 
@@ -5428,6 +5723,141 @@ impl Config {
     }
 }
 
+// the absolute path of the file an `include` node points to, relative paths are resolved against
+// the folder of the including file (the last one in file_stack)
+fn resolve_include_path(
+    kdl_include: &KdlNode,
+    file_stack: &[PathBuf],
+) -> Result<PathBuf, ConfigError> {
+    let include_path = kdl_first_entry_as_string!(kdl_include).ok_or(kdl_parsing_error!(
+        "include should be given the path to a configuration file, eg. include \"base.kdl\"".into(),
+        kdl_include
+    ))?;
+    let include_path = shellexpand::full(include_path)
+        .map(|expanded| PathBuf::from(expanded.as_ref()))
+        .map_err(|e| {
+            kdl_parsing_error!(
+                format!("Failed to expand include path {}: {}", include_path, e),
+                kdl_include
+            )
+        })?;
+    let include_path = if include_path.is_relative() {
+        let including_dir = file_stack
+            .last()
+            .and_then(|including_file| including_file.parent())
+            .ok_or(kdl_parsing_error!(
+                "Relative include paths can only be used in configuration files".into(),
+                kdl_include
+            ))?;
+        including_dir.join(include_path)
+    } else {
+        include_path
+    };
+    Ok(include_path.canonicalize().unwrap_or(include_path))
+}
+
+// files that cannot be read or parsed are still collected (so that they can be watched until they
+// can), but what they include is not
+fn collect_included_files(
+    kdl_config: &KdlDocument,
+    file_stack: &mut Vec<PathBuf>,
+    included_files: &mut Vec<PathBuf>,
+) {
+    for kdl_node in kdl_config.nodes() {
+        match kdl_name!(kdl_node) {
+            "include" => {
+                let Ok(include_path) = resolve_include_path(kdl_node, file_stack) else {
+                    continue;
+                };
+                if file_stack.contains(&include_path) || included_files.contains(&include_path) {
+                    continue;
+                }
+                included_files.push(include_path.clone());
+                if let Some(kdl_included_config) = std::fs::read_to_string(&include_path)
+                    .ok()
+                    .and_then(|raw_config| raw_config.parse::<KdlDocument>().ok())
+                {
+                    file_stack.push(include_path);
+                    collect_included_files(&kdl_included_config, file_stack, included_files);
+                    file_stack.pop();
+                }
+            },
+            "overlay" => {
+                if let Some(kdl_overlay_config) = kdl_node.children() {
+                    collect_included_files(kdl_overlay_config, file_stack, included_files);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+// all the conditions of an overlay have to match for it to apply, eg.
+// overlay hostname="work-laptop" os="linux" env="SSH_CONNECTION" { ... }
+fn overlay_applies(kdl_overlay: &KdlNode) -> Result<bool, ConfigError> {
+    if kdl_overlay.entries().is_empty() {
+        return Err(kdl_parsing_error!(
+            "overlay should be given at least one condition, eg. overlay os=\"macos\"".into(),
+            kdl_overlay
+        ));
+    }
+    let mut applies = true;
+    for entry in kdl_overlay.entries() {
+        let (Some(condition), Some(value)) = (entry.name(), entry.value().as_string()) else {
+            return Err(kdl_parsing_error!(
+                "overlay conditions should be given as properties, eg. overlay hostname=\"my-laptop\""
+                    .into(),
+                entry
+            ));
+        };
+        applies &= match condition.value() {
+            "hostname" => current_hostname().is_some_and(|hostname| {
+                // also match the short name of hosts that report a fully qualified one
+                hostname.eq_ignore_ascii_case(value)
+                    || hostname
+                        .split('.')
+                        .next()
+                        .is_some_and(|short_name| short_name.eq_ignore_ascii_case(value))
+            }),
+            "os" => std::env::consts::OS == value,
+            // either env="NAME" (the variable is set) or env="NAME=value"
+            "env" => match value.split_once('=') {
+                Some((name, expected_value)) => {
+                    std::env::var(name).is_ok_and(|value| value == expected_value)
+                },
+                None => std::env::var_os(value).is_some(),
+            },
+            unknown_condition => {
+                return Err(kdl_parsing_error!(
+                    format!(
+                        "Unknown overlay condition \"{}\", expected one of: hostname, os, env",
+                        unknown_condition
+                    ),
+                    entry
+                ));
+            },
+        };
+    }
+    Ok(applies)
+}
+
+fn current_hostname() -> Option<String> {
+    #[cfg(unix)]
+    {
+        nix::unistd::gethostname()
+            .ok()
+            .and_then(|hostname| hostname.into_string().ok())
+    }
+    #[cfg(windows)]
+    {
+        std::env::var("COMPUTERNAME").ok()
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        None
+    }
+}
+
 impl PluginAliases {
     pub fn from_kdl(kdl_plugin_aliases: &KdlNode) -> Result<PluginAliases, ConfigError> {
         let mut aliases: BTreeMap<String, RunPlugin> = BTreeMap::new();
@@ -5445,7 +5875,25 @@ impl PluginAliases {
                     let run_plugin = RunPlugin::from_url(string_url)?
                         .with_configuration(configuration.inner().clone())
                         .with_initial_cwd(initial_cwd);
-                    aliases.insert(alias_name.to_owned(), run_plugin);
+                    let sha256 = KdlLayoutParser::parse_plugin_sha256(
+                        alias_definition,
+                        &RunPluginOrAlias::RunPlugin(run_plugin.clone()),
+                    )?;
+                    let resource_limits = KdlLayoutParser::parse_plugin_resource_limits(
+                        alias_definition,
+                        &RunPluginOrAlias::RunPlugin(run_plugin.clone()),
+                    )?;
+                    let allowed_hosts = KdlLayoutParser::parse_plugin_allowed_hosts(
+                        alias_definition,
+                        &RunPluginOrAlias::RunPlugin(run_plugin.clone()),
+                    )?;
+                    aliases.insert(
+                        alias_name.to_owned(),
+                        run_plugin
+                            .with_sha256(sha256)
+                            .with_resource_limits(resource_limits)
+                            .with_allowed_hosts(allowed_hosts),
+                    );
                 }
             }
         }
@@ -5468,6 +5916,23 @@ impl PluginAliases {
                 cwd_node.push(cwd.display().to_string());
                 plugin_alias_children.nodes_mut().push(cwd_node);
             }
+            if let Some(sha256) = plugin_alias.sha256.as_ref() {
+                has_children = true;
+                let mut sha256_node = KdlNode::new("sha256");
+                sha256_node.push(sha256.to_owned());
+                plugin_alias_children.nodes_mut().push(sha256_node);
+            }
+            let mut resource_limit_nodes = plugin_alias.resource_limits.to_kdl();
+            if !resource_limit_nodes.is_empty() {
+                has_children = true;
+                plugin_alias_children
+                    .nodes_mut()
+                    .append(&mut resource_limit_nodes);
+            }
+            if let Some(allowed_hosts_node) = allowed_hosts_to_kdl(&plugin_alias.allowed_hosts) {
+                has_children = true;
+                plugin_alias_children.nodes_mut().push(allowed_hosts_node);
+            }
             let configuration = plugin_alias.configuration.inner();
             if !configuration.is_empty() {
                 has_children = true;
@@ -5501,6 +5966,43 @@ impl PluginAliases {
     }
 }
 
+impl PluginResourceLimits {
+    pub fn to_kdl(&self) -> Vec<KdlNode> {
+        let limits = [
+            ("fuel_budget", self.fuel_budget),
+            ("max_memory_mb", self.max_memory_mb.map(|m| m as u64)),
+            (
+                "max_table_elements",
+                self.max_table_elements.map(|t| t as u64),
+            ),
+            ("max_pending_jobs", self.max_pending_jobs.map(|j| j as u64)),
+            (
+                "max_timers_per_second",
+                self.max_timers_per_second.map(|t| t as u64),
+            ),
+        ];
+        let mut nodes = vec![];
+        for (limit_name, limit) in limits {
+            if let Some(limit) = limit {
+                let mut limit_node = KdlNode::new(limit_name);
+                limit_node.push(KdlValue::Base10(limit as i64));
+                nodes.push(limit_node);
+            }
+        }
+        nodes
+    }
+}
+
+pub fn allowed_hosts_to_kdl(allowed_hosts: &[String]) -> Option<KdlNode> {
+    if allowed_hosts.is_empty() {
+        None
+    } else {
+        let mut allowed_hosts_node = KdlNode::new("allowed_hosts");
+        allowed_hosts_node.push(allowed_hosts.join(" "));
+        Some(allowed_hosts_node)
+    }
+}
+
 pub fn load_plugins_to_kdl(
     background_plugins: &HashSet<RunPluginOrAlias>,
     add_comments: bool,
@@ -5522,6 +6024,27 @@ pub fn load_plugins_to_kdl(
             cwd_node.push(cwd.display().to_string());
             background_plugin_children.nodes_mut().push(cwd_node);
         }
+        if let RunPluginOrAlias::RunPlugin(run_plugin) = run_plugin_or_alias {
+            if let Some(sha256) = run_plugin.sha256.as_ref() {
+                has_children = true;
+                let mut sha256_node = KdlNode::new("sha256");
+                sha256_node.push(sha256.to_owned());
+                background_plugin_children.nodes_mut().push(sha256_node);
+            }
+            let mut resource_limit_nodes = run_plugin.resource_limits.to_kdl();
+            if !resource_limit_nodes.is_empty() {
+                has_children = true;
+                background_plugin_children
+                    .nodes_mut()
+                    .append(&mut resource_limit_nodes);
+            }
+            if let Some(allowed_hosts_node) = allowed_hosts_to_kdl(&run_plugin.allowed_hosts) {
+                has_children = true;
+                background_plugin_children
+                    .nodes_mut()
+                    .push(allowed_hosts_node);
+            }
+        }
         let configuration = match run_plugin_or_alias {
             RunPluginOrAlias::RunPlugin(run_plugin) => {
                 Some(run_plugin.configuration.inner().clone())
@@ -5592,7 +6115,17 @@ fn load_plugins_from_kdl(
                 )
             })?
             .with_initial_cwd(cwd);
-            load_plugins.insert(run_plugin_or_alias);
+            let sha256 = KdlLayoutParser::parse_plugin_sha256(plugin_block, &run_plugin_or_alias)?;
+            let resource_limits =
+                KdlLayoutParser::parse_plugin_resource_limits(plugin_block, &run_plugin_or_alias)?;
+            let allowed_hosts =
+                KdlLayoutParser::parse_plugin_allowed_hosts(plugin_block, &run_plugin_or_alias)?;
+            load_plugins.insert(
+                run_plugin_or_alias
+                    .with_sha256(sha256)
+                    .with_resource_limits(resource_limits)
+                    .with_allowed_hosts(allowed_hosts),
+            );
         }
     }
     Ok(load_plugins)
@@ -5956,10 +6489,21 @@ impl Themes {
 }
 
 impl PermissionCache {
-    pub fn from_string(raw_string: String) -> Result<GrantedPermission, ConfigError> {
+    pub fn from_string(
+        raw_string: String,
+    ) -> Result<
+        (
+            GrantedPermission,
+            GrantedFilesystemPermission,
+            GrantedWebAccess,
+        ),
+        ConfigError,
+    > {
         let kdl_document: KdlDocument = raw_string.parse()?;
 
         let mut granted_permission = GrantedPermission::default();
+        let mut granted_filesystem_permission = GrantedFilesystemPermission::default();
+        let mut granted_web_access = GrantedWebAccess::default();
 
         for node in kdl_document.nodes() {
             if let Some(children) = node.children() {
@@ -5972,15 +6516,49 @@ impl PermissionCache {
                         PermissionType::from_str(v).ok()
                     })
                     .collect();
+                let filesystem_permissions: Vec<FilesystemPermission> = children
+                    .nodes()
+                    .iter()
+                    .filter(|p| kdl_name!(p) == "FilesystemAccess")
+                    .filter_map(|p| {
+                        let path = kdl_first_entry_as_string!(p)?;
+                        let read_only =
+                            kdl_get_bool_property_or_child_value!(p, "read_only").unwrap_or(true);
+                        Some(FilesystemPermission {
+                            path: path.to_owned(),
+                            read_only,
+                        })
+                    })
+                    .collect();
 
+                if !filesystem_permissions.is_empty() {
+                    granted_filesystem_permission.insert(key.into(), filesystem_permissions);
+                }
+                if let Some(web_access) = children.get("WebAccess") {
+                    let allowed_hosts = web_access
+                        .entries()
+                        .iter()
+                        .filter_map(|e| e.value().as_string())
+                        .map(|h| h.to_owned())
+                        .collect();
+                    granted_web_access.insert(key.into(), allowed_hosts);
+                }
                 granted_permission.insert(key.into(), permissions);
             }
         }
 
-        Ok(granted_permission)
+        Ok((
+            granted_permission,
+            granted_filesystem_permission,
+            granted_web_access,
+        ))
     }
 
-    pub fn to_string(granted: &GrantedPermission) -> String {
+    pub fn to_string(
+        granted: &GrantedPermission,
+        granted_filesystem: &GrantedFilesystemPermission,
+        granted_web_access: &GrantedWebAccess,
+    ) -> String {
         let mut kdl_doucment = KdlDocument::new();
 
         granted.iter().for_each(|(k, v)| {
@@ -5989,7 +6567,22 @@ impl PermissionCache {
 
             let permissions: HashSet<PermissionType> = v.clone().into_iter().collect();
             permissions.iter().for_each(|f| {
-                let n = KdlNode::new(f.to_string().as_str());
+                let mut n = KdlNode::new(f.to_string().as_str());
+                if f == &PermissionType::WebAccess {
+                    for allowed_host in granted_web_access.get(k).into_iter().flatten() {
+                        n.push(allowed_host.as_str());
+                    }
+                }
+                children.nodes_mut().push(n);
+            });
+            let filesystem_permissions: BTreeSet<&FilesystemPermission> = granted_filesystem
+                .get(k)
+                .map(|f| f.iter().collect())
+                .unwrap_or_default();
+            filesystem_permissions.iter().for_each(|f| {
+                let mut n = KdlNode::new("FilesystemAccess");
+                n.push(f.path.as_str());
+                n.push(KdlEntry::new_prop("read_only", f.read_only));
                 children.nodes_mut().push(n);
             });
 
@@ -6373,6 +6966,7 @@ impl TabInfo {
             tab_id,
             has_bell_notification: false,
             is_flashing_bell: false,
+            recently_closed_panes: vec![],
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -6811,6 +7405,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 selectable_floating_panes_count: 10,
                 tab_id: 0,
                 is_flashing_bell: false,
+                recently_closed_panes: vec![],
                 has_bell_notification: false,
             },
             TabInfo {
@@ -6832,6 +7427,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 selectable_floating_panes_count: 10,
                 tab_id: 1,
                 is_flashing_bell: false,
+                recently_closed_panes: vec![],
                 has_bell_notification: false,
             },
         ],
@@ -7145,6 +7741,8 @@ fn keybinds_to_string_with_all_actions() {
                     };
                 }
                 bind "Ctrl Alt k" { FocusLastPane; }
+                bind "Ctrl Alt l" { StartRecording "/tmp/recording.cast"; }
+                bind "Ctrl Alt m" { StopRecording; }
             }
         }"#;
     let document: KdlDocument = fake_config.parse().unwrap();
@@ -7555,6 +8153,160 @@ fn plugins_to_string_with_file_and_web() {
     insta::assert_snapshot!(serialized.to_string());
 }
 
+#[test]
+fn plugins_with_sha256_to_string() {
+    let fake_config = r##"
+        plugins {
+            pinned location="https://foo.com/plugin.wasm" {
+                sha256 "2C26B46B68FFC68FF99B453C1D30413413422D706483BFA0F98A5E886266E7AE"
+                some_config "value"
+            }
+        }"##;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    let deserialized = PluginAliases::from_kdl(document.get("plugins").unwrap()).unwrap();
+    let serialized = PluginAliases::to_kdl(&deserialized, true);
+    let deserialized_from_serialized = PluginAliases::from_kdl(
+        serialized
+            .to_string()
+            .parse::<KdlDocument>()
+            .unwrap()
+            .get("plugins")
+            .unwrap(),
+    )
+    .unwrap();
+    let pinned = deserialized_from_serialized.aliases.get("pinned").unwrap();
+    assert_eq!(
+        pinned.sha256.as_deref(),
+        Some("2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"),
+        "sha256 is normalized and survives serialization"
+    );
+    assert!(
+        pinned.configuration.inner().get("sha256").is_none(),
+        "sha256 is not passed to the plugin as configuration"
+    );
+    insta::assert_snapshot!(serialized.to_string());
+}
+
+#[test]
+fn plugins_with_allowed_hosts_to_string() {
+    let fake_config = r##"
+        plugins {
+            weather location="https://foo.com/weather.wasm" {
+                allowed_hosts "api.open-meteo.com https://wttr.in/"
+                city "Berlin"
+            }
+        }"##;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    let deserialized = PluginAliases::from_kdl(document.get("plugins").unwrap()).unwrap();
+    let serialized = PluginAliases::to_kdl(&deserialized, true);
+    let deserialized_from_serialized = PluginAliases::from_kdl(
+        serialized
+            .to_string()
+            .parse::<KdlDocument>()
+            .unwrap()
+            .get("plugins")
+            .unwrap(),
+    )
+    .unwrap();
+    let weather = deserialized_from_serialized.aliases.get("weather").unwrap();
+    assert_eq!(
+        weather.allowed_hosts,
+        vec![
+            "api.open-meteo.com".to_owned(),
+            "https://wttr.in/".to_owned()
+        ],
+        "allowed_hosts survive serialization"
+    );
+    assert!(
+        weather.configuration.inner().get("allowed_hosts").is_none(),
+        "allowed_hosts are not passed to the plugin as configuration"
+    );
+}
+
+#[test]
+fn load_plugins_with_sha256_on_a_local_plugin_is_an_error() {
+    let fake_config = r##"
+        load_plugins {
+            "file:/path/to/my/plugin.wasm" {
+                sha256 "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+            }
+        }"##;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    assert!(load_plugins_from_kdl(document.get("load_plugins").unwrap()).is_err());
+}
+
+#[test]
+fn plugins_with_fuel_budget_to_string() {
+    let fake_config = r##"
+        plugins {
+            limited location="file:/path/to/my/plugin.wasm" {
+                fuel_budget 5000
+                some_config "value"
+            }
+        }"##;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    let deserialized = PluginAliases::from_kdl(document.get("plugins").unwrap()).unwrap();
+    let serialized = PluginAliases::to_kdl(&deserialized, true);
+    let deserialized_from_serialized = PluginAliases::from_kdl(
+        serialized
+            .to_string()
+            .parse::<KdlDocument>()
+            .unwrap()
+            .get("plugins")
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        deserialized, deserialized_from_serialized,
+        "Deserialized serialized config equals original config"
+    );
+    let limited = deserialized_from_serialized.aliases.get("limited").unwrap();
+    assert_eq!(limited.resource_limits.fuel_budget, Some(5000));
+    assert!(
+        limited.configuration.inner().get("fuel_budget").is_none(),
+        "fuel_budget is not passed to the plugin as configuration"
+    );
+}
+
+#[test]
+fn load_plugins_with_resource_limits_to_string() {
+    let fake_config = r##"
+        load_plugins {
+            "file:/path/to/my/plugin.wasm" {
+                max_memory_mb 64
+                max_table_elements 10000
+                max_pending_jobs 4
+                max_timers_per_second 10
+            }
+        }"##;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    let deserialized = load_plugins_from_kdl(document.get("load_plugins").unwrap()).unwrap();
+    let serialized = load_plugins_to_kdl(&deserialized, false);
+    let deserialized_from_serialized = load_plugins_from_kdl(
+        serialized
+            .to_string()
+            .parse::<KdlDocument>()
+            .unwrap()
+            .get("load_plugins")
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        deserialized, deserialized_from_serialized,
+        "Deserialized serialized config equals original config"
+    );
+    let resource_limits = &deserialized_from_serialized
+        .iter()
+        .next()
+        .and_then(|p| p.get_run_plugin())
+        .unwrap()
+        .resource_limits;
+    assert_eq!(resource_limits.max_memory_mb, Some(64));
+    assert_eq!(resource_limits.max_table_elements, Some(10000));
+    assert_eq!(resource_limits.max_pending_jobs, Some(4));
+    assert_eq!(resource_limits.max_timers_per_second, Some(10));
+}
+
 #[test]
 fn ui_config_to_string() {
     let fake_config = r##"
@@ -7683,6 +8435,7 @@ fn config_options_to_string() {
         session_serialization true
         serialize_pane_viewport false
         scrollback_lines_to_serialize 1000
+        serialize_pane_scrollback true
         styled_underlines false
         serialization_interval 1
         disable_session_metadata true
@@ -7730,6 +8483,7 @@ fn config_options_to_string_with_comments() {
         session_serialization true
         serialize_pane_viewport false
         scrollback_lines_to_serialize 1000
+        serialize_pane_scrollback true
         styled_underlines false
         serialization_interval 1
         disable_session_metadata true
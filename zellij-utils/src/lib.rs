@@ -21,6 +21,8 @@ pub mod shared;
 
 // The following modules can't be used when targeting wasm
 #[cfg(not(target_family = "wasm"))]
+pub mod asciicast;
+#[cfg(not(target_family = "wasm"))]
 pub mod channels; // Requires tokio
 #[cfg(not(target_family = "wasm"))]
 pub mod common_path;
@@ -37,6 +39,8 @@ pub mod remote_session_tokens;
 #[cfg(not(target_family = "wasm"))]
 pub mod sessions;
 #[cfg(all(not(target_family = "wasm"), feature = "web_server_capability"))]
+pub mod web_audit_log;
+#[cfg(all(not(target_family = "wasm"), feature = "web_server_capability"))]
 pub mod web_authentication_tokens;
 #[cfg(all(not(target_family = "wasm"), feature = "web_server_capability"))]
 pub mod web_server_commands;
@@ -48,3 +52,17 @@ pub use ::prost;
 
 // Vendored libraries
 pub mod vendored;
+
+/// Runs `f` on a thread with a bigger stack than test threads get by default, parsing the whole
+/// CLI or a full configuration needs more than that in unoptimized builds.
+#[cfg(test)]
+pub(crate) fn run_with_large_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn_scoped(scope, f)
+            .unwrap()
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    })
+}
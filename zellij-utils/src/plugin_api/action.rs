@@ -11,6 +11,8 @@ pub use super::generated_api::api::{
         BareKey as ProtobufBareKey,
         // New layout-related types
         CommandOrPlugin as ProtobufCommandOrPlugin,
+        CopyModeMotion as ProtobufCopyModeMotion,
+        CopyModeSelection as ProtobufCopyModeSelection,
         DumpScreenPayload,
         EditFilePayload,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates,
@@ -46,6 +48,7 @@ pub use super::generated_api::api::{
         PercentOrFixed as ProtobufPercentOrFixed,
         PluginAlias as ProtobufPluginAlias,
         PluginConfiguration as ProtobufPluginConfiguration,
+        PluginResourceLimits as ProtobufPluginResourceLimits,
         PluginTag as ProtobufPluginTag,
         PluginUserConfiguration as ProtobufPluginUserConfiguration,
         Position as ProtobufPosition,
@@ -84,13 +87,13 @@ use crate::data::{
 };
 use crate::errors::prelude::*;
 use crate::input::actions::Action;
-use crate::input::actions::{SearchDirection, SearchOption};
+use crate::input::actions::{CopyModeMotion, CopyModeSelection, SearchDirection, SearchOption};
 use crate::input::command::{OpenFilePayload, RunCommandAction};
 use crate::input::layout::SplitSize;
 use crate::input::layout::{
-    FloatingPaneLayout, LayoutConstraint, PercentOrFixed, PluginAlias, PluginUserConfiguration,
-    Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection, SwapFloatingLayout,
-    SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+    FloatingPaneLayout, LayoutConstraint, PercentOrFixed, PluginAlias, PluginResourceLimits,
+    PluginUserConfiguration, Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection,
+    SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
 };
 use crate::input::mouse::{MouseEvent, MouseEventType};
 use crate::position::Position;
@@ -834,6 +837,30 @@ impl TryFrom<ProtobufAction> for Action {
                     _ => Err("Wrong payload for Action::SearchToggleOption"),
                 }
             },
+            Some(ProtobufActionName::CopyModeMove) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::CopyModeMovePayload(motion)) => Ok(Action::CopyModeMove {
+                    motion: ProtobufCopyModeMotion::try_from(motion)
+                        .ok()
+                        .ok_or("Malformed payload for Action::CopyModeMove")?
+                        .try_into()?,
+                }),
+                _ => Err("Wrong payload for Action::CopyModeMove"),
+            },
+            Some(ProtobufActionName::CopyModeSelect) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::CopyModeSelectPayload(selection)) => {
+                    Ok(Action::CopyModeSelect {
+                        selection: ProtobufCopyModeSelection::try_from(selection)
+                            .ok()
+                            .ok_or("Malformed payload for Action::CopyModeSelect")?
+                            .try_into()?,
+                    })
+                },
+                _ => Err("Wrong payload for Action::CopyModeSelect"),
+            },
+            Some(ProtobufActionName::CopyModeYank) => match protobuf_action.optional_payload {
+                Some(_) => Err("CopyModeYank should not have a payload"),
+                None => Ok(Action::CopyModeYank),
+            },
             Some(ProtobufActionName::ToggleMouseMode) => match protobuf_action.optional_payload {
                 Some(_) => Err("ToggleMouseMode should not have a payload"),
                 None => Ok(Action::ToggleMouseMode),
@@ -1786,6 +1813,26 @@ impl TryFrom<Action> for ProtobufAction {
                     )),
                 })
             },
+            Action::CopyModeMove { motion } => {
+                let motion: ProtobufCopyModeMotion = motion.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::CopyModeMove as i32,
+                    optional_payload: Some(OptionalPayload::CopyModeMovePayload(motion as i32)),
+                })
+            },
+            Action::CopyModeSelect { selection } => {
+                let selection: ProtobufCopyModeSelection = selection.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::CopyModeSelect as i32,
+                    optional_payload: Some(OptionalPayload::CopyModeSelectPayload(
+                        selection as i32,
+                    )),
+                })
+            },
+            Action::CopyModeYank => Ok(ProtobufAction {
+                name: ProtobufActionName::CopyModeYank as i32,
+                optional_payload: None,
+            }),
             Action::ToggleMouseMode => Ok(ProtobufAction {
                 name: ProtobufActionName::ToggleMouseMode as i32,
                 optional_payload: None,
@@ -2069,6 +2116,14 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::SkipConfirm { action: _ }
             | Action::SwitchSession { .. }
             | Action::SaveSession
+            | Action::StartRecording { .. }
+            | Action::StopRecording { .. }
+            | Action::StartPaneLog { .. }
+            | Action::StopPaneLog { .. }
+            | Action::MonitorPane { .. }
+            | Action::ReopenClosedPane
+            | Action::TogglePaneGroupSync
+            | Action::SyncBroadcastGroup { .. }
             | Action::ListTabs { .. }
             | Action::CurrentTabInfo { .. }
             | Action::SetPaneColor { .. } => Err("Unsupported action"),
@@ -2083,6 +2138,7 @@ impl TryFrom<ProtobufSearchOption> for SearchOption {
             ProtobufSearchOption::CaseSensitivity => Ok(SearchOption::CaseSensitivity),
             ProtobufSearchOption::WholeWord => Ok(SearchOption::WholeWord),
             ProtobufSearchOption::Wrap => Ok(SearchOption::Wrap),
+            ProtobufSearchOption::Regex => Ok(SearchOption::Regex),
         }
     }
 }
@@ -2094,6 +2150,97 @@ impl TryFrom<SearchOption> for ProtobufSearchOption {
             SearchOption::CaseSensitivity => Ok(ProtobufSearchOption::CaseSensitivity),
             SearchOption::WholeWord => Ok(ProtobufSearchOption::WholeWord),
             SearchOption::Wrap => Ok(ProtobufSearchOption::Wrap),
+            SearchOption::Regex => Ok(ProtobufSearchOption::Regex),
+        }
+    }
+}
+
+impl TryFrom<ProtobufCopyModeMotion> for CopyModeMotion {
+    type Error = &'static str;
+    fn try_from(protobuf_copy_mode_motion: ProtobufCopyModeMotion) -> Result<Self, &'static str> {
+        match protobuf_copy_mode_motion {
+            ProtobufCopyModeMotion::Left => Ok(CopyModeMotion::Left),
+            ProtobufCopyModeMotion::Right => Ok(CopyModeMotion::Right),
+            ProtobufCopyModeMotion::Up => Ok(CopyModeMotion::Up),
+            ProtobufCopyModeMotion::Down => Ok(CopyModeMotion::Down),
+            ProtobufCopyModeMotion::WordForward => Ok(CopyModeMotion::WordForward),
+            ProtobufCopyModeMotion::WordBackward => Ok(CopyModeMotion::WordBackward),
+            ProtobufCopyModeMotion::WordEnd => Ok(CopyModeMotion::WordEnd),
+            ProtobufCopyModeMotion::LineStart => Ok(CopyModeMotion::LineStart),
+            ProtobufCopyModeMotion::LineEnd => Ok(CopyModeMotion::LineEnd),
+            ProtobufCopyModeMotion::FirstNonBlank => Ok(CopyModeMotion::FirstNonBlank),
+            ProtobufCopyModeMotion::ParagraphForward => Ok(CopyModeMotion::ParagraphForward),
+            ProtobufCopyModeMotion::ParagraphBackward => Ok(CopyModeMotion::ParagraphBackward),
+            ProtobufCopyModeMotion::Top => Ok(CopyModeMotion::Top),
+            ProtobufCopyModeMotion::Bottom => Ok(CopyModeMotion::Bottom),
+            ProtobufCopyModeMotion::PageUp => Ok(CopyModeMotion::PageUp),
+            ProtobufCopyModeMotion::PageDown => Ok(CopyModeMotion::PageDown),
+            ProtobufCopyModeMotion::HalfPageUp => Ok(CopyModeMotion::HalfPageUp),
+            ProtobufCopyModeMotion::HalfPageDown => Ok(CopyModeMotion::HalfPageDown),
+            ProtobufCopyModeMotion::PreviousPrompt => Ok(CopyModeMotion::PreviousPrompt),
+            ProtobufCopyModeMotion::NextPrompt => Ok(CopyModeMotion::NextPrompt),
+            ProtobufCopyModeMotion::NextSearchResult => Ok(CopyModeMotion::NextSearchResult),
+            ProtobufCopyModeMotion::PreviousSearchResult => {
+                Ok(CopyModeMotion::PreviousSearchResult)
+            },
+            ProtobufCopyModeMotion::Unspecified => Err("Unspecified copy mode motion"),
+        }
+    }
+}
+
+impl TryFrom<CopyModeMotion> for ProtobufCopyModeMotion {
+    type Error = &'static str;
+    fn try_from(copy_mode_motion: CopyModeMotion) -> Result<Self, &'static str> {
+        match copy_mode_motion {
+            CopyModeMotion::Left => Ok(ProtobufCopyModeMotion::Left),
+            CopyModeMotion::Right => Ok(ProtobufCopyModeMotion::Right),
+            CopyModeMotion::Up => Ok(ProtobufCopyModeMotion::Up),
+            CopyModeMotion::Down => Ok(ProtobufCopyModeMotion::Down),
+            CopyModeMotion::WordForward => Ok(ProtobufCopyModeMotion::WordForward),
+            CopyModeMotion::WordBackward => Ok(ProtobufCopyModeMotion::WordBackward),
+            CopyModeMotion::WordEnd => Ok(ProtobufCopyModeMotion::WordEnd),
+            CopyModeMotion::LineStart => Ok(ProtobufCopyModeMotion::LineStart),
+            CopyModeMotion::LineEnd => Ok(ProtobufCopyModeMotion::LineEnd),
+            CopyModeMotion::FirstNonBlank => Ok(ProtobufCopyModeMotion::FirstNonBlank),
+            CopyModeMotion::ParagraphForward => Ok(ProtobufCopyModeMotion::ParagraphForward),
+            CopyModeMotion::ParagraphBackward => Ok(ProtobufCopyModeMotion::ParagraphBackward),
+            CopyModeMotion::Top => Ok(ProtobufCopyModeMotion::Top),
+            CopyModeMotion::Bottom => Ok(ProtobufCopyModeMotion::Bottom),
+            CopyModeMotion::PageUp => Ok(ProtobufCopyModeMotion::PageUp),
+            CopyModeMotion::PageDown => Ok(ProtobufCopyModeMotion::PageDown),
+            CopyModeMotion::HalfPageUp => Ok(ProtobufCopyModeMotion::HalfPageUp),
+            CopyModeMotion::HalfPageDown => Ok(ProtobufCopyModeMotion::HalfPageDown),
+            CopyModeMotion::PreviousPrompt => Ok(ProtobufCopyModeMotion::PreviousPrompt),
+            CopyModeMotion::NextPrompt => Ok(ProtobufCopyModeMotion::NextPrompt),
+            CopyModeMotion::NextSearchResult => Ok(ProtobufCopyModeMotion::NextSearchResult),
+            CopyModeMotion::PreviousSearchResult => {
+                Ok(ProtobufCopyModeMotion::PreviousSearchResult)
+            },
+        }
+    }
+}
+
+impl TryFrom<ProtobufCopyModeSelection> for CopyModeSelection {
+    type Error = &'static str;
+    fn try_from(
+        protobuf_copy_mode_selection: ProtobufCopyModeSelection,
+    ) -> Result<Self, &'static str> {
+        match protobuf_copy_mode_selection {
+            ProtobufCopyModeSelection::Character => Ok(CopyModeSelection::Character),
+            ProtobufCopyModeSelection::Line => Ok(CopyModeSelection::Line),
+            ProtobufCopyModeSelection::Block => Ok(CopyModeSelection::Block),
+            ProtobufCopyModeSelection::Unspecified => Err("Unspecified copy mode selection"),
+        }
+    }
+}
+
+impl TryFrom<CopyModeSelection> for ProtobufCopyModeSelection {
+    type Error = &'static str;
+    fn try_from(copy_mode_selection: CopyModeSelection) -> Result<Self, &'static str> {
+        match copy_mode_selection {
+            CopyModeSelection::Character => Ok(ProtobufCopyModeSelection::Character),
+            CopyModeSelection::Line => Ok(ProtobufCopyModeSelection::Line),
+            CopyModeSelection::Block => Ok(ProtobufCopyModeSelection::Block),
         }
     }
 }
@@ -2155,6 +2302,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let env = protobuf_run_command_action.env.into_iter().collect();
         Ok(RunCommandAction {
             command,
             args,
@@ -2162,6 +2310,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            env,
             ..Default::default()
         })
     }
@@ -2179,6 +2328,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let env = run_command_action.env.into_iter().collect();
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -2187,6 +2337,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             hold_on_close,
             hold_on_start,
             pane_name: None,
+            env,
         })
     }
 }
@@ -2882,6 +3033,12 @@ impl TryFrom<ProtobufRunPlugin> for RunPlugin {
             location,
             configuration,
             initial_cwd,
+            sha256: protobuf.sha256,
+            resource_limits: protobuf
+                .resource_limits
+                .map(|r| r.into())
+                .unwrap_or_default(),
+            allowed_hosts: protobuf.allowed_hosts,
         })
     }
 }
@@ -2894,10 +3051,37 @@ impl TryFrom<RunPlugin> for ProtobufRunPlugin {
             location: Some(internal.location.try_into()?),
             configuration: Some(internal.configuration.try_into()?),
             initial_cwd: internal.initial_cwd.map(|p| p.display().to_string()),
+            sha256: internal.sha256,
+            resource_limits: Some(internal.resource_limits.into()),
+            allowed_hosts: internal.allowed_hosts,
         })
     }
 }
 
+impl From<ProtobufPluginResourceLimits> for PluginResourceLimits {
+    fn from(protobuf: ProtobufPluginResourceLimits) -> Self {
+        PluginResourceLimits {
+            fuel_budget: protobuf.fuel_budget,
+            max_memory_mb: protobuf.max_memory_mb.map(|m| m as usize),
+            max_table_elements: protobuf.max_table_elements.map(|t| t as usize),
+            max_pending_jobs: protobuf.max_pending_jobs.map(|j| j as usize),
+            max_timers_per_second: protobuf.max_timers_per_second.map(|t| t as usize),
+        }
+    }
+}
+
+impl From<PluginResourceLimits> for ProtobufPluginResourceLimits {
+    fn from(internal: PluginResourceLimits) -> Self {
+        ProtobufPluginResourceLimits {
+            fuel_budget: internal.fuel_budget,
+            max_memory_mb: internal.max_memory_mb.map(|m| m as u64),
+            max_table_elements: internal.max_table_elements.map(|t| t as u64),
+            max_pending_jobs: internal.max_pending_jobs.map(|j| j as u64),
+            max_timers_per_second: internal.max_timers_per_second.map(|t| t as u64),
+        }
+    }
+}
+
 impl TryFrom<ProtobufPluginAlias> for PluginAlias {
     type Error = &'static str;
     fn try_from(protobuf: ProtobufPluginAlias) -> Result<Self, Self::Error> {
@@ -3159,6 +3343,11 @@ impl TryFrom<ProtobufTiledPaneLayout> for TiledPaneLayout {
             pane_initial_contents: protobuf.pane_initial_contents,
             default_fg: None,
             default_bg: None,
+            pane_scrollback_snapshot: None,
+            log_output: None,
+            monitors: None,
+            broadcast_group: None,
+            depends_on: vec![],
         })
     }
 }
@@ -3217,6 +3406,11 @@ impl TryFrom<ProtobufFloatingPaneLayout> for FloatingPaneLayout {
             borderless: protobuf.borderless,
             default_fg: None,
             default_bg: None,
+            pane_scrollback_snapshot: None,
+            log_output: None,
+            monitors: None,
+            broadcast_group: None,
+            depends_on: vec![],
         })
     }
 }
@@ -3389,4 +3583,24 @@ mod tests {
             assert_eq!(original, decoded);
         }
     }
+
+    #[test]
+    fn copy_mode_actions_protobuf_round_trip() {
+        for original in [
+            Action::CopyModeMove {
+                motion: CopyModeMotion::WordForward,
+            },
+            Action::CopyModeMove {
+                motion: CopyModeMotion::PreviousPrompt,
+            },
+            Action::CopyModeSelect {
+                selection: CopyModeSelection::Block,
+            },
+            Action::CopyModeYank,
+        ] {
+            let protobuf: ProtobufAction = original.clone().try_into().expect("encode");
+            let decoded: Action = protobuf.try_into().expect("decode");
+            assert_eq!(original, decoded);
+        }
+    }
 }
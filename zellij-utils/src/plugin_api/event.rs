@@ -7,12 +7,13 @@ pub use super::generated_api::api::{
         ActivePaneScrollPayload as ProtobufActivePaneScrollPayload,
         AvailableLayoutInfoPayload as ProtobufAvailableLayoutInfoPayload,
         ClientInfo as ProtobufClientInfo, ClientPaneHistory as ProtobufClientPaneHistory,
-        ClientTabHistory as ProtobufClientTabHistory,
-        CommandChangedPayload as ProtobufCommandChangedPayload, ContextItem as ProtobufContextItem,
-        CopyDestination as ProtobufCopyDestination, CwdChangedPayload as ProtobufCwdChangedPayload,
-        Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
-        EventType as ProtobufEventType, FileMetadata as ProtobufFileMetadata,
-        HintTextPayload as ProtobufHintTextPayload,
+        ClientTabHistory as ProtobufClientTabHistory, ClosedPaneInfo as ProtobufClosedPaneInfo,
+        CommandChangedPayload as ProtobufCommandChangedPayload,
+        CommandPaneRestartedPayload as ProtobufCommandPaneRestartedPayload,
+        ContextItem as ProtobufContextItem, CopyDestination as ProtobufCopyDestination,
+        CwdChangedPayload as ProtobufCwdChangedPayload, Event as ProtobufEvent,
+        EventNameList as ProtobufEventNameList, EventType as ProtobufEventType,
+        FileMetadata as ProtobufFileMetadata, HintTextPayload as ProtobufHintTextPayload,
         HostTerminalThemeChangedPayload as ProtobufHostTerminalThemeChangedPayload,
         HostTerminalThemeIndication as ProtobufHostTerminalThemeIndication,
         InputModeKeybinds as ProtobufInputModeKeybinds, KdlError as ProtobufKdlError,
@@ -24,12 +25,18 @@ pub use super::generated_api::api::{
         PaneFrameStyle as ProtobufPaneFrameStyle, PaneId as ProtobufPaneId,
         PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
         PaneMetadata as ProtobufPaneMetadata,
+        PaneMonitorAlertPayload as ProtobufPaneMonitorAlertPayload,
         PaneRenderReportPayload as ProtobufPaneRenderReportPayload,
         PaneScrollbackResponse as ProtobufPaneScrollbackResponse, PaneType as ProtobufPaneType,
         PluginConfigurationChangedPayload as ProtobufPluginConfigurationChangedPayload,
-        PluginInfo as ProtobufPluginInfo, ResurrectableSession as ProtobufResurrectableSession,
-        SelectedText as ProtobufSelectedText, SessionManifest as ProtobufSessionManifest,
+        PluginInfo as ProtobufPluginInfo, PluginReply as ProtobufPluginReply,
+        PluginReplyStatus as ProtobufPluginReplyStatus,
+        PluginRequestResultPayload as ProtobufPluginRequestResultPayload,
+        ResurrectableSession as ProtobufResurrectableSession, SelectedText as ProtobufSelectedText,
+        SessionManifest as ProtobufSessionManifest,
         SoftKeyboardVisibilityChangedPayload as ProtobufSoftKeyboardVisibilityChangedPayload,
+        StorageScope as ProtobufStorageScope,
+        StorageValueChangedPayload as ProtobufStorageValueChangedPayload,
         StyledText as ProtobufStyledText, StyledTextIndices as ProtobufStyledTextIndices,
         SyntaxError as ProtobufSyntaxError, TabInfo as ProtobufTabInfo,
         TabMetadata as ProtobufTabMetadata, UserActionPayload as ProtobufUserActionPayload,
@@ -42,11 +49,12 @@ pub use super::generated_api::api::{
 };
 #[allow(hidden_glob_reexports)]
 use crate::data::{
-    ClientId, ClientInfo, CopyDestination, Event, EventType, FileMetadata, HostTerminalThemeMode,
-    InputMode, KeyWithModifier, LayoutInfo, LayoutMetadata, ModeInfo, Mouse, PaneContents, PaneId,
-    PaneInfo, PaneManifest, PaneMetadata, PaneScrollbackResponse, PermissionStatus,
-    PluginCapabilities, PluginInfo, SelectedText, SessionInfo, Style, StyledText, TabInfo,
-    TabMetadata, WebServerStatus, WebSharing,
+    ClientId, ClientInfo, ClosedPaneInfo, CopyDestination, Event, EventType, FileMetadata,
+    HostTerminalThemeMode, InputMode, KeyWithModifier, LayoutInfo, LayoutMetadata, ModeInfo, Mouse,
+    PaneContents, PaneId, PaneInfo, PaneManifest, PaneMetadata, PaneMonitorAlert,
+    PaneScrollbackResponse, PermissionStatus, PluginCapabilities, PluginInfo, PluginReply,
+    SelectedText, SessionInfo, StorageScope, Style, StyledText, TabInfo, TabMetadata,
+    WebServerStatus, WebSharing,
 };
 
 use crate::errors::prelude::*;
@@ -619,6 +627,55 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for ActivePaneScroll Event"),
             },
+            Some(ProtobufEventType::PaneMonitorAlert) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneMonitorAlertPayload(payload)) => {
+                    let pane_id = payload
+                        .pane_id
+                        .ok_or("Malformed payload for PaneMonitorAlert Event")?;
+                    let alert = match payload.silence_secs {
+                        Some(silence_secs) => PaneMonitorAlert::Silence(silence_secs),
+                        None => PaneMonitorAlert::Activity,
+                    };
+                    Ok(Event::PaneMonitorAlert(PaneId::try_from(pane_id)?, alert))
+                },
+                _ => Err("Malformed payload for PaneMonitorAlert Event"),
+            },
+            Some(ProtobufEventType::PluginRequestResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PluginRequestResultPayload(payload)) => {
+                    let reply = payload
+                        .reply
+                        .ok_or("Malformed payload for PluginRequestResult Event")?;
+                    let context = payload
+                        .context
+                        .into_iter()
+                        .map(|c_i| (c_i.name, c_i.value))
+                        .collect();
+                    Ok(Event::PluginRequestResult(reply.try_into()?, context))
+                },
+                _ => Err("Malformed payload for PluginRequestResult Event"),
+            },
+            Some(ProtobufEventType::StorageValueChanged) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::StorageValueChangedPayload(payload)) => {
+                    let scope = ProtobufStorageScope::try_from(payload.scope)
+                        .map_err(|_| "Malformed payload for StorageValueChanged Event")?;
+                    Ok(Event::StorageValueChanged(
+                        scope.into(),
+                        payload.key,
+                        payload.value,
+                    ))
+                },
+                _ => Err("Malformed payload for StorageValueChanged Event"),
+            },
+            Some(ProtobufEventType::CommandPaneRestarted) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::CommandPaneRestartedPayload(payload)) => {
+                    Ok(Event::CommandPaneRestarted(
+                        payload.terminal_pane_id,
+                        payload.exit_code,
+                        payload.restart_count as usize,
+                    ))
+                },
+                _ => Err("Malformed payload for CommandPaneRestarted Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -1192,6 +1249,58 @@ impl TryFrom<Event> for ProtobufEvent {
                     payload: Some(event::Payload::ActivePaneScrollPayload(payload)),
                 })
             },
+            Event::PaneMonitorAlert(pane_id, alert) => {
+                let silence_secs = match alert {
+                    PaneMonitorAlert::Activity => None,
+                    PaneMonitorAlert::Silence(silence_secs) => Some(silence_secs),
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PaneMonitorAlert as i32,
+                    payload: Some(event::Payload::PaneMonitorAlertPayload(
+                        ProtobufPaneMonitorAlertPayload {
+                            pane_id: Some(pane_id.try_into()?),
+                            silence_secs,
+                        },
+                    )),
+                })
+            },
+            Event::PluginRequestResult(reply, context) => {
+                let context = context
+                    .into_iter()
+                    .map(|(name, value)| ProtobufContextItem { name, value })
+                    .collect();
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PluginRequestResult as i32,
+                    payload: Some(event::Payload::PluginRequestResultPayload(
+                        ProtobufPluginRequestResultPayload {
+                            reply: Some(reply.into()),
+                            context,
+                        },
+                    )),
+                })
+            },
+            Event::StorageValueChanged(scope, key, value) => Ok(ProtobufEvent {
+                name: ProtobufEventType::StorageValueChanged as i32,
+                payload: Some(event::Payload::StorageValueChangedPayload(
+                    ProtobufStorageValueChangedPayload {
+                        scope: ProtobufStorageScope::from(scope) as i32,
+                        key,
+                        value,
+                    },
+                )),
+            }),
+            Event::CommandPaneRestarted(terminal_pane_id, exit_code, restart_count) => {
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::CommandPaneRestarted as i32,
+                    payload: Some(event::Payload::CommandPaneRestartedPayload(
+                        ProtobufCommandPaneRestartedPayload {
+                            terminal_pane_id,
+                            exit_code,
+                            restart_count: restart_count as u32,
+                        },
+                    )),
+                })
+            },
             Event::InitialKeybinds(keybinds) => {
                 let mut protobuf_keybinds: Vec<ProtobufInputModeKeybinds> = vec![];
                 for (input_mode, input_mode_keybinds) in keybinds {
@@ -1309,6 +1418,7 @@ impl From<(u32, PluginInfo)> for ProtobufPluginInfo {
                 .into_iter()
                 .map(|(name, value)| ContextItem { name, value })
                 .collect(),
+            crash_reason: plugin_info.crash_reason,
         }
     }
 }
@@ -1342,6 +1452,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
                 PluginInfo {
                     location: plugin_info.plugin_url,
                     configuration,
+                    crash_reason: plugin_info.crash_reason,
                 },
             );
         }
@@ -1450,6 +1561,11 @@ impl TryFrom<ProtobufLayoutMetadata> for LayoutMetadata {
             tabs,
             creation_time: protobuf_metadata.creation_time,
             update_time: protobuf_metadata.update_time,
+            parameters: protobuf_metadata
+                .parameters
+                .into_iter()
+                .map(|p| (p.name, p.value))
+                .collect(),
         })
     }
 }
@@ -1466,6 +1582,11 @@ impl TryFrom<LayoutMetadata> for ProtobufLayoutMetadata {
             tabs,
             creation_time: metadata.creation_time,
             update_time: metadata.update_time,
+            parameters: metadata
+                .parameters
+                .into_iter()
+                .map(|(name, value)| ProtobufContextItem { name, value })
+                .collect(),
         })
     }
 }
@@ -1902,6 +2023,11 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
             tab_id: protobuf_tab_info.tab_id as usize,
             has_bell_notification: protobuf_tab_info.has_bell_notification,
             is_flashing_bell: protobuf_tab_info.is_flashing_bell,
+            recently_closed_panes: protobuf_tab_info
+                .recently_closed_panes
+                .into_iter()
+                .map(|closed_pane| closed_pane.into())
+                .collect(),
         })
     }
 }
@@ -1933,10 +2059,87 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
             tab_id: tab_info.tab_id as u32,
             has_bell_notification: tab_info.has_bell_notification,
             is_flashing_bell: tab_info.is_flashing_bell,
+            recently_closed_panes: tab_info
+                .recently_closed_panes
+                .into_iter()
+                .map(|closed_pane| closed_pane.into())
+                .collect(),
         })
     }
 }
 
+impl From<ProtobufClosedPaneInfo> for ClosedPaneInfo {
+    fn from(protobuf_closed_pane_info: ProtobufClosedPaneInfo) -> Self {
+        ClosedPaneInfo {
+            title: protobuf_closed_pane_info.title,
+            command: protobuf_closed_pane_info.command,
+            cwd: protobuf_closed_pane_info.cwd.map(PathBuf::from),
+            is_floating: protobuf_closed_pane_info.is_floating,
+            is_stacked: protobuf_closed_pane_info.is_stacked,
+        }
+    }
+}
+
+impl From<ClosedPaneInfo> for ProtobufClosedPaneInfo {
+    fn from(closed_pane_info: ClosedPaneInfo) -> Self {
+        ProtobufClosedPaneInfo {
+            title: closed_pane_info.title,
+            command: closed_pane_info.command,
+            cwd: closed_pane_info.cwd.map(|cwd| cwd.display().to_string()),
+            is_floating: closed_pane_info.is_floating,
+            is_stacked: closed_pane_info.is_stacked,
+        }
+    }
+}
+
+impl TryFrom<ProtobufPluginReply> for PluginReply {
+    type Error = &'static str;
+    fn try_from(protobuf_plugin_reply: ProtobufPluginReply) -> Result<Self, &'static str> {
+        match ProtobufPluginReplyStatus::try_from(protobuf_plugin_reply.status) {
+            Ok(ProtobufPluginReplyStatus::Replied) => {
+                Ok(PluginReply::Replied(protobuf_plugin_reply.payload))
+            },
+            Ok(ProtobufPluginReplyStatus::Failed) => Ok(PluginReply::Failed(
+                protobuf_plugin_reply.payload.unwrap_or_default(),
+            )),
+            Ok(ProtobufPluginReplyStatus::TimedOut) => Ok(PluginReply::TimedOut),
+            Err(_) => Err("Unknown PluginReply status"),
+        }
+    }
+}
+
+impl From<PluginReply> for ProtobufPluginReply {
+    fn from(plugin_reply: PluginReply) -> Self {
+        let (status, payload) = match plugin_reply {
+            PluginReply::Replied(payload) => (ProtobufPluginReplyStatus::Replied, payload),
+            PluginReply::Failed(error) => (ProtobufPluginReplyStatus::Failed, Some(error)),
+            PluginReply::TimedOut => (ProtobufPluginReplyStatus::TimedOut, None),
+        };
+        ProtobufPluginReply {
+            status: status as i32,
+            payload,
+        }
+    }
+}
+
+impl From<ProtobufStorageScope> for StorageScope {
+    fn from(protobuf_storage_scope: ProtobufStorageScope) -> Self {
+        match protobuf_storage_scope {
+            ProtobufStorageScope::PluginStorage => StorageScope::Plugin,
+            ProtobufStorageScope::SessionStorage => StorageScope::Session,
+        }
+    }
+}
+
+impl From<StorageScope> for ProtobufStorageScope {
+    fn from(storage_scope: StorageScope) -> Self {
+        match storage_scope {
+            StorageScope::Plugin => ProtobufStorageScope::PluginStorage,
+            StorageScope::Session => ProtobufStorageScope::SessionStorage,
+        }
+    }
+}
+
 impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
     type Error = &'static str;
     fn try_from(
@@ -2232,6 +2435,10 @@ impl TryFrom<ProtobufEventType> for EventType {
             },
             ProtobufEventType::HintText => EventType::HintText,
             ProtobufEventType::ActivePaneScroll => EventType::ActivePaneScroll,
+            ProtobufEventType::PaneMonitorAlert => EventType::PaneMonitorAlert,
+            ProtobufEventType::PluginRequestResult => EventType::PluginRequestResult,
+            ProtobufEventType::StorageValueChanged => EventType::StorageValueChanged,
+            ProtobufEventType::CommandPaneRestarted => EventType::CommandPaneRestarted,
         })
     }
 }
@@ -2290,6 +2497,10 @@ impl TryFrom<EventType> for ProtobufEventType {
             },
             EventType::HintText => ProtobufEventType::HintText,
             EventType::ActivePaneScroll => ProtobufEventType::ActivePaneScroll,
+            EventType::PaneMonitorAlert => ProtobufEventType::PaneMonitorAlert,
+            EventType::PluginRequestResult => ProtobufEventType::PluginRequestResult,
+            EventType::StorageValueChanged => ProtobufEventType::StorageValueChanged,
+            EventType::CommandPaneRestarted => ProtobufEventType::CommandPaneRestarted,
         })
     }
 }
@@ -2559,6 +2770,13 @@ fn serialize_tab_update_event_with_non_default_values() {
             tab_id: 0,
             has_bell_notification: false,
             is_flashing_bell: false,
+            recently_closed_panes: vec![ClosedPaneInfo {
+                title: "dev server".to_owned(),
+                command: vec!["npm".to_owned(), "run".to_owned(), "dev".to_owned()],
+                cwd: Some(PathBuf::from("/tmp/project")),
+                is_floating: true,
+                is_stacked: false,
+            }],
         },
         TabInfo {
             position: 1,
@@ -2580,6 +2798,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             tab_id: 1,
             has_bell_notification: false,
             is_flashing_bell: false,
+            recently_closed_panes: vec![],
         },
         TabInfo::default(),
     ]);
@@ -2658,6 +2877,94 @@ fn serialize_active_pane_scroll_event() {
     }
 }
 
+#[test]
+fn serialize_pane_monitor_alert_event() {
+    use prost::Message;
+    for pane_monitor_alert_event in [
+        Event::PaneMonitorAlert(PaneId::Terminal(2), PaneMonitorAlert::Activity),
+        Event::PaneMonitorAlert(PaneId::Terminal(3), PaneMonitorAlert::Silence(30)),
+    ] {
+        let protobuf_event: ProtobufEvent = pane_monitor_alert_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            pane_monitor_alert_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_plugin_request_result_event() {
+    use prost::Message;
+    let mut context = BTreeMap::new();
+    context.insert("query".to_owned(), "projects".to_owned());
+    for plugin_request_result_event in [
+        Event::PluginRequestResult(
+            PluginReply::Replied(Some("[\"zellij\"]".to_owned())),
+            context.clone(),
+        ),
+        Event::PluginRequestResult(PluginReply::Replied(None), context.clone()),
+        Event::PluginRequestResult(PluginReply::Failed("no such project".to_owned()), context),
+        Event::PluginRequestResult(PluginReply::TimedOut, BTreeMap::new()),
+    ] {
+        let protobuf_event: ProtobufEvent = plugin_request_result_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            plugin_request_result_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_storage_value_changed_event() {
+    use prost::Message;
+    for storage_value_changed_event in [
+        Event::StorageValueChanged(
+            StorageScope::Plugin,
+            "recent_projects".to_owned(),
+            Some("zellij,helix".to_owned()),
+        ),
+        Event::StorageValueChanged(StorageScope::Session, "selected".to_owned(), None),
+    ] {
+        let protobuf_event: ProtobufEvent = storage_value_changed_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            storage_value_changed_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_command_pane_restarted_event() {
+    use prost::Message;
+    for command_pane_restarted_event in [
+        Event::CommandPaneRestarted(1, Some(1), 3),
+        Event::CommandPaneRestarted(2, None, 1),
+    ] {
+        let protobuf_event: ProtobufEvent =
+            command_pane_restarted_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            command_pane_restarted_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
 #[test]
 fn serialize_key_event() {
     use crate::data::BareKey;
@@ -2905,6 +3212,7 @@ fn serialize_session_update_event_with_non_default_values() {
             tab_id: 0,
             has_bell_notification: false,
             is_flashing_bell: false,
+            recently_closed_panes: vec![],
         },
         TabInfo {
             position: 1,
@@ -2926,6 +3234,7 @@ fn serialize_session_update_event_with_non_default_values() {
             tab_id: 1,
             has_bell_notification: false,
             is_flashing_bell: false,
+            recently_closed_panes: vec![],
         },
         TabInfo::default(),
     ];
@@ -3003,6 +3312,7 @@ fn serialize_session_update_event_with_non_default_values() {
         PluginInfo {
             location: "https://example.com/my-plugin.wasm".to_owned(),
             configuration: plugin_configuration,
+            crash_reason: None,
         },
     );
     let mut tab_history = BTreeMap::new();
@@ -3021,6 +3331,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    parameters: Default::default(),
                 },
             ),
             LayoutInfo::BuiltIn("layout2".to_owned()),
@@ -3030,6 +3341,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    parameters: Default::default(),
                 },
             ),
         ],
@@ -3055,6 +3367,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    parameters: Default::default(),
                 },
             ),
             LayoutInfo::BuiltIn("layout2".to_owned()),
@@ -3064,6 +3377,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    parameters: Default::default(),
                 },
             ),
         ],
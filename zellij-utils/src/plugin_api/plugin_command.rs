@@ -3,7 +3,7 @@ pub use super::generated_api::api::{
     event::{
         EventNameList as ProtobufEventNameList, Header,
         ResurrectableSession as ProtobufResurrectableSession,
-        SessionManifest as ProtobufSessionManifest,
+        SessionManifest as ProtobufSessionManifest, StorageScope as ProtobufStorageScope,
     },
     input_mode::InputMode as ProtobufInputMode,
     pane_frame_style::PaneFrameStyle as ProtobufPaneFrameStyle,
@@ -15,7 +15,7 @@ pub use super::generated_api::api::{
         get_pane_running_command_response, get_session_list_response, hide_floating_panes_response,
         highlight_style::Style as ProtobufHighlightStyleVariant, new_tab_response,
         new_tab_unfocused_response, parse_layout_response, plugin_command::Payload,
-        rename_layout_response, save_layout_response, save_session_response,
+        recording_target, rename_layout_response, save_layout_response, save_session_response,
         show_floating_panes_response, BreakPanesToNewTabPayload,
         BreakPanesToNewTabResponse as ProtobufBreakPanesToNewTabResponse,
         BreakPanesToTabWithIdPayload,
@@ -63,13 +63,17 @@ pub use super::generated_api::api::{
         HighlightAndUnhighlightPanesPayload, HighlightLayer as ProtobufHighlightLayer,
         HighlightStyle as ProtobufHighlightStyle, HttpVerb as ProtobufHttpVerb, IdAndNewName,
         KeyToRebind, KeyToUnbind, KillSessionsPayload,
-        KillSessionsResponse as ProtobufKillSessionsResponse, ListTokensResponse,
-        LoadNewPluginPayload, MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload,
-        MovePaneWithPaneIdPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
-        NewTabPayload, NewTabResponse as ProtobufNewTabResponse, NewTabUnfocusedPayload,
+        KillSessionsResponse as ProtobufKillSessionsResponse,
+        ListGrantedPermissionsResponse as ProtobufListGrantedPermissionsResponse,
+        ListStorageValuesPayload, ListStorageValuesResponse as ProtobufListStorageValuesResponse,
+        ListTokensResponse, LoadNewPluginPayload, MessageToPluginPayload,
+        MovePaneWithPaneIdInDirectionPayload, MovePaneWithPaneIdPayload, MovePayload,
+        NewPluginArgs as ProtobufNewPluginArgs, NewTabPayload,
+        NewTabResponse as ProtobufNewTabResponse, NewTabUnfocusedPayload,
         NewTabUnfocusedResponse as ProtobufNewTabUnfocusedResponse,
-        NewTabsResponse as ProtobufNewTabsResponse, NewTabsWithLayoutInfoPayload,
-        NewTiledPaneInTabPayload, NewTiledPaneInTabResponse as ProtobufNewTiledPaneInTabResponse,
+        NewTabsResponse as ProtobufNewTabsResponse, NewTabsWithLayoutAndArgsPayload,
+        NewTabsWithLayoutInfoPayload, NewTiledPaneInTabPayload,
+        NewTiledPaneInTabResponse as ProtobufNewTiledPaneInTabResponse,
         OpenCommandPaneBackgroundResponse as ProtobufOpenCommandPaneBackgroundResponse,
         OpenCommandPaneFloatingNearPluginPayload,
         OpenCommandPaneFloatingNearPluginResponse as ProtobufOpenCommandPaneFloatingNearPluginResponse,
@@ -111,26 +115,31 @@ pub use super::generated_api::api::{
         PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType, ParseLayoutPayload,
         ParseLayoutResponse as ProtobufParseLayoutResponse, PluginCommand as ProtobufPluginCommand,
         PluginMessagePayload, RebindKeysPayload, ReconfigurePayload,
-        RegexHighlight as ProtobufRegexHighlight, ReloadPluginPayload, RenameLayoutPayload,
+        RecordingTarget as ProtobufRecordingTarget, RegexHighlight as ProtobufRegexHighlight,
+        ReloadPluginPayload, RenameLayoutPayload,
         RenameLayoutResponse as ProtobufRenameLayoutResponse, RenameTabWithIdPayload,
         RenameWebLoginTokenPayload, RenameWebTokenResponse, ReplacePaneWithExistingPanePayload,
-        RequestPluginPermissionPayload, RerunCommandPanePayload, ResizePaneIdWithDirectionPayload,
-        ResizePayload, RevokeAllWebTokensResponse, RevokeTokenResponse, RevokeWebLoginTokenPayload,
+        ReplyToPluginRequestPayload, RequestPluginPermissionPayload, RerunCommandPanePayload,
+        ResizePaneIdWithDirectionPayload, ResizePayload, RevokeAllWebTokensResponse,
+        RevokePluginPermissionsPayload, RevokeTokenResponse, RevokeWebLoginTokenPayload,
         RunActionPayload, RunCommandPayload, RunningCommand as ProtobufRunningCommand,
         SaveLayoutPayload, SaveLayoutResponse as ProtobufSaveLayoutResponse, SaveSessionPayload,
         SaveSessionResponse as ProtobufSaveSessionResponse, ScrollDownInPaneIdPayload,
         ScrollToBottomInPaneIdPayload, ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload,
-        SessionListSnapshot as ProtobufSessionListSnapshot, SetFloatingPanePinnedPayload,
-        SetPaneBorderlessPayload, SetPaneColorPayload,
-        SetPaneFrameStylePayload as ProtobufSetPaneFrameStylePayload,
+        SendPluginRequestPayload, SessionListSnapshot as ProtobufSessionListSnapshot,
+        SetFloatingPanePinnedPayload, SetPaneBorderlessPayload, SetPaneColorPayload,
+        SetPaneFrameStylePayload as ProtobufSetPaneFrameStylePayload, SetPaneMonitorsPayload,
         SetPaneRegexHighlightsPayload, SetSelfMouseSelectionSupportPayload,
-        SetSoftKeyboardPayload as ProtobufSetSoftKeyboardPayload, SetTimeoutPayload,
-        ShowCursorPayload, ShowFloatingPanesPayload as ProtobufShowFloatingPanesPayload,
+        SetSoftKeyboardPayload as ProtobufSetSoftKeyboardPayload, SetStorageValuePayload,
+        SetTimeoutPayload, ShowCursorPayload,
+        ShowFloatingPanesPayload as ProtobufShowFloatingPanesPayload,
         ShowFloatingPanesResponse as ProtobufShowFloatingPanesResponse, ShowPaneWithIdPayload,
-        StackPanesPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToIdPayload,
-        SwitchTabToPayload, ToggleFloatingPanesPayload, TogglePaneBorderlessPayload,
-        TogglePaneEmbedOrEjectForPaneIdPayload, TogglePaneIdFullscreenPayload, UnsubscribePayload,
-        WebRequestPayload, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+        StackPanesPayload, StartRecordingPayload, StopRecordingPayload, StorageKeyPayload,
+        StorageValueResponse as ProtobufStorageValueResponse, SubscribePayload,
+        SwitchSessionPayload, SwitchTabToIdPayload, SwitchTabToPayload, ToggleFloatingPanesPayload,
+        TogglePaneBorderlessPayload, TogglePaneEmbedOrEjectForPaneIdPayload,
+        TogglePaneIdFullscreenPayload, UnsubscribePayload, WebRequestPayload,
+        WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
@@ -141,8 +150,9 @@ use crate::data::{
     DeleteLayoutResponse, EditLayoutResponse, FloatingPaneCoordinates, GetFocusedPaneInfoResponse,
     GetPaneCwdResponse, GetPanePidResponse, GetPaneRunningCommandResponse, GetSessionListResponse,
     HighlightLayer, HighlightStyle, HttpVerb, InputMode, KeyWithModifier, KillSessionsResponse,
-    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand, RegexHighlight,
-    RenameLayoutResponse, SaveLayoutResponse, SessionInfo, SessionListSnapshot,
+    MessageToPlugin, NewPluginArgs, PaneId, PaneMonitors, PermissionType, PluginCommand,
+    RecordingTarget, RegexHighlight, RenameLayoutResponse, SaveLayoutResponse, SessionInfo,
+    SessionListSnapshot, StorageScope,
 };
 use crate::input::actions::Action;
 use crate::input::layout::PercentOrFixed;
@@ -151,6 +161,93 @@ use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
 
+fn storage_scope_from_protobuf(scope: i32) -> Result<StorageScope, &'static str> {
+    ProtobufStorageScope::try_from(scope)
+        .map(StorageScope::from)
+        .map_err(|_| "Unknown storage scope")
+}
+
+impl TryFrom<MessageToPluginPayload> for MessageToPlugin {
+    type Error = &'static str;
+    fn try_from(message_to_plugin_payload: MessageToPluginPayload) -> Result<Self, &'static str> {
+        let MessageToPluginPayload {
+            plugin_url,
+            plugin_config,
+            message_name,
+            message_payload,
+            message_args,
+            new_plugin_args,
+            destination_plugin_id,
+            floating_pane_coordinates,
+        } = message_to_plugin_payload;
+        let plugin_config: BTreeMap<String, String> = plugin_config
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        let message_args: BTreeMap<String, String> = message_args
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect();
+        Ok(MessageToPlugin {
+            plugin_url,
+            plugin_config,
+            message_name,
+            message_payload,
+            message_args,
+            new_plugin_args: new_plugin_args.map(|protobuf_new_plugin_args| NewPluginArgs {
+                should_float: protobuf_new_plugin_args.should_float,
+                pane_id_to_replace: protobuf_new_plugin_args
+                    .pane_id_to_replace
+                    .and_then(|p_id| PaneId::try_from(p_id).ok()),
+                pane_title: protobuf_new_plugin_args.pane_title,
+                cwd: protobuf_new_plugin_args.cwd.map(PathBuf::from),
+                skip_cache: protobuf_new_plugin_args.skip_cache,
+                should_focus: protobuf_new_plugin_args.should_focus,
+            }),
+            destination_plugin_id,
+            floating_pane_coordinates: floating_pane_coordinates.map(|f| f.into()),
+        })
+    }
+}
+
+impl From<MessageToPlugin> for MessageToPluginPayload {
+    fn from(message_to_plugin: MessageToPlugin) -> Self {
+        let plugin_config: Vec<_> = message_to_plugin
+            .plugin_config
+            .into_iter()
+            .map(|(name, value)| ContextItem { name, value })
+            .collect();
+        let message_args: Vec<_> = message_to_plugin
+            .message_args
+            .into_iter()
+            .map(|(name, value)| ContextItem { name, value })
+            .collect();
+        MessageToPluginPayload {
+            plugin_url: message_to_plugin.plugin_url,
+            plugin_config,
+            message_name: message_to_plugin.message_name,
+            message_payload: message_to_plugin.message_payload,
+            message_args,
+            new_plugin_args: message_to_plugin
+                .new_plugin_args
+                .map(|m_t_p| ProtobufNewPluginArgs {
+                    should_float: m_t_p.should_float,
+                    pane_id_to_replace: m_t_p
+                        .pane_id_to_replace
+                        .and_then(|p_id| ProtobufPaneId::try_from(p_id).ok()),
+                    pane_title: m_t_p.pane_title,
+                    cwd: m_t_p.cwd.map(|cwd| cwd.display().to_string()),
+                    skip_cache: m_t_p.skip_cache,
+                    should_focus: m_t_p.should_focus,
+                }),
+            destination_plugin_id: message_to_plugin.destination_plugin_id,
+            floating_pane_coordinates: message_to_plugin
+                .floating_pane_coordinates
+                .map(|f| f.into()),
+        }
+    }
+}
+
 impl Into<FloatingPaneCoordinates> for ProtobufFloatingPaneCoordinates {
     fn into(self) -> FloatingPaneCoordinates {
         FloatingPaneCoordinates {
@@ -306,6 +403,42 @@ impl TryFrom<PaneId> for ProtobufPaneId {
     }
 }
 
+impl TryFrom<ProtobufRecordingTarget> for RecordingTarget {
+    type Error = &'static str;
+    fn try_from(protobuf_recording_target: ProtobufRecordingTarget) -> Result<Self, &'static str> {
+        match protobuf_recording_target.target_type {
+            Some(recording_target::TargetType::FocusedPane(_)) => Ok(RecordingTarget::FocusedPane),
+            Some(recording_target::TargetType::PaneId(pane_id)) => {
+                Ok(RecordingTarget::Pane(pane_id.try_into()?))
+            },
+            Some(recording_target::TargetType::FocusedTab(_)) => Ok(RecordingTarget::FocusedTab),
+            Some(recording_target::TargetType::TabId(tab_id)) => {
+                Ok(RecordingTarget::Tab(tab_id as usize))
+            },
+            Some(recording_target::TargetType::Client(_)) => Ok(RecordingTarget::Client),
+            None => Err("Failed to convert RecordingTarget"),
+        }
+    }
+}
+
+impl TryFrom<RecordingTarget> for ProtobufRecordingTarget {
+    type Error = &'static str;
+    fn try_from(recording_target: RecordingTarget) -> Result<Self, &'static str> {
+        let target_type = match recording_target {
+            RecordingTarget::FocusedPane => recording_target::TargetType::FocusedPane(true),
+            RecordingTarget::Pane(pane_id) => {
+                recording_target::TargetType::PaneId(pane_id.try_into()?)
+            },
+            RecordingTarget::FocusedTab => recording_target::TargetType::FocusedTab(true),
+            RecordingTarget::Tab(tab_id) => recording_target::TargetType::TabId(tab_id as u32),
+            RecordingTarget::Client => recording_target::TargetType::Client(true),
+        };
+        Ok(ProtobufRecordingTarget {
+            target_type: Some(target_type),
+        })
+    }
+}
+
 impl TryFrom<ProtobufGetPanePidResponse> for GetPanePidResponse {
     type Error = &'static str;
     fn try_from(protobuf_response: ProtobufGetPanePidResponse) -> Result<Self, &'static str> {
@@ -1107,6 +1240,107 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 }
                 Ok(PluginCommand::FocusHostSession)
             },
+            Some(CommandName::StartRecording) => match protobuf_plugin_command.payload {
+                Some(Payload::StartRecordingPayload(payload)) => {
+                    Ok(PluginCommand::StartRecording {
+                        path: payload.path.map(PathBuf::from),
+                        target: payload
+                            .target
+                            .ok_or("Malformed StartRecording payload")?
+                            .try_into()?,
+                    })
+                },
+                _ => Err("Mismatched payload for StartRecording"),
+            },
+            Some(CommandName::StopRecording) => match protobuf_plugin_command.payload {
+                Some(Payload::StopRecordingPayload(payload)) => Ok(PluginCommand::StopRecording {
+                    path: payload.path.map(PathBuf::from),
+                }),
+                _ => Err("Mismatched payload for StopRecording"),
+            },
+            Some(CommandName::SetPaneMonitors) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPaneMonitorsPayload(payload)) => {
+                    Ok(PluginCommand::SetPaneMonitors {
+                        pane_id: payload
+                            .pane_id
+                            .and_then(|p_id| PaneId::try_from(p_id).ok())
+                            .ok_or("Malformed SetPaneMonitors payload")?,
+                        monitors: PaneMonitors {
+                            activity: payload.monitor_activity,
+                            silence_secs: payload.monitor_silence_secs,
+                        },
+                    })
+                },
+                _ => Err("Mismatched payload for SetPaneMonitors"),
+            },
+            Some(CommandName::GetStorageValue) => match protobuf_plugin_command.payload {
+                Some(Payload::GetStorageValuePayload(StorageKeyPayload { key, scope })) => {
+                    Ok(PluginCommand::GetStorageValue {
+                        key,
+                        scope: storage_scope_from_protobuf(scope)?,
+                    })
+                },
+                _ => Err("Mismatched payload for GetStorageValue"),
+            },
+            Some(CommandName::SetStorageValue) => match protobuf_plugin_command.payload {
+                Some(Payload::SetStorageValuePayload(SetStorageValuePayload {
+                    key,
+                    value,
+                    scope,
+                })) => Ok(PluginCommand::SetStorageValue {
+                    key,
+                    value,
+                    scope: storage_scope_from_protobuf(scope)?,
+                }),
+                _ => Err("Mismatched payload for SetStorageValue"),
+            },
+            Some(CommandName::DeleteStorageValue) => match protobuf_plugin_command.payload {
+                Some(Payload::DeleteStorageValuePayload(StorageKeyPayload { key, scope })) => {
+                    Ok(PluginCommand::DeleteStorageValue {
+                        key,
+                        scope: storage_scope_from_protobuf(scope)?,
+                    })
+                },
+                _ => Err("Mismatched payload for DeleteStorageValue"),
+            },
+            Some(CommandName::ListStorageValues) => match protobuf_plugin_command.payload {
+                Some(Payload::ListStorageValuesPayload(ListStorageValuesPayload {
+                    prefix,
+                    scope,
+                })) => Ok(PluginCommand::ListStorageValues {
+                    prefix,
+                    scope: storage_scope_from_protobuf(scope)?,
+                }),
+                _ => Err("Mismatched payload for ListStorageValues"),
+            },
+            Some(CommandName::SendPluginRequest) => match protobuf_plugin_command.payload {
+                Some(Payload::SendPluginRequestPayload(SendPluginRequestPayload {
+                    message: Some(message),
+                    timeout_ms,
+                    context,
+                })) => Ok(PluginCommand::SendPluginRequest {
+                    message: message.try_into()?,
+                    timeout_ms,
+                    context: context.into_iter().map(|e| (e.name, e.value)).collect(),
+                }),
+                _ => Err("Mismatched payload for SendPluginRequest"),
+            },
+            Some(CommandName::ReplyToPluginRequest) => match protobuf_plugin_command.payload {
+                Some(Payload::ReplyToPluginRequestPayload(ReplyToPluginRequestPayload {
+                    request_id,
+                    reply: Some(reply),
+                })) => Ok(PluginCommand::ReplyToPluginRequest {
+                    request_id,
+                    reply: reply.try_into()?,
+                }),
+                _ => Err("Mismatched payload for ReplyToPluginRequest"),
+            },
+            Some(CommandName::ReopenClosedPane) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    return Err("ReopenClosedPane should not have a payload");
+                }
+                Ok(PluginCommand::ReopenClosedPane)
+            },
             Some(CommandName::TogglePaneFrames) => {
                 if protobuf_plugin_command.payload.is_some() {
                     return Err("TogglePaneFrames should not have a payload");
@@ -1283,6 +1517,61 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for RequestPluginPermission"),
             },
+            Some(CommandName::RequestPluginPermissionsWithFilesystemAccess) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::RequestPluginPermissionsWithFilesystemAccessPayload(payload)) => {
+                        Ok(PluginCommand::RequestPluginPermissionsWithFilesystemAccess(
+                            payload
+                                .permissions
+                                .iter()
+                                .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                                .filter_map(|p| PermissionType::try_from(p).ok())
+                                .collect(),
+                            payload
+                                .filesystem_permissions
+                                .into_iter()
+                                .map(|p| p.into())
+                                .collect(),
+                        ))
+                    },
+                    _ => Err("Mismatched payload for RequestPluginPermissionsWithFilesystemAccess"),
+                }
+            },
+            Some(CommandName::ListGrantedPermissions) => match protobuf_plugin_command.payload {
+                Some(_) => Err("ListGrantedPermissions should have no payload"),
+                None => Ok(PluginCommand::ListGrantedPermissions),
+            },
+            Some(CommandName::RevokePluginPermissions) => match protobuf_plugin_command.payload {
+                Some(Payload::RevokePluginPermissionsPayload(payload)) => {
+                    Ok(PluginCommand::RevokePluginPermissions {
+                        plugin_url: payload.plugin_url,
+                        permissions: payload
+                            .permissions
+                            .iter()
+                            .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                            .filter_map(|p| PermissionType::try_from(p).ok())
+                            .collect(),
+                    })
+                },
+                _ => Err("Mismatched payload for RevokePluginPermissions"),
+            },
+            Some(CommandName::NewTabsWithLayoutAndArgs) => match protobuf_plugin_command.payload {
+                Some(Payload::NewTabsWithLayoutAndArgsPayload(payload)) => {
+                    let layout_info = payload
+                        .layout_info
+                        .and_then(|layout_info| layout_info.try_into().ok())
+                        .ok_or("Failed to parse NewTabsWithLayoutAndArgs command")?;
+                    Ok(PluginCommand::NewTabsWithLayoutAndArgs {
+                        layout_info,
+                        layout_args: payload
+                            .layout_args
+                            .into_iter()
+                            .map(|a| (a.name, a.value))
+                            .collect(),
+                    })
+                },
+                _ => Err("Mismatched payload for NewTabsWithLayoutAndArgs"),
+            },
             Some(CommandName::SwitchSession) => match protobuf_plugin_command.payload {
                 Some(Payload::SwitchSessionPayload(payload)) => {
                     let pane_id = match (payload.pane_id, payload.pane_id_is_plugin) {
@@ -1432,47 +1721,9 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 _ => Err("Mismatched payload for PipeOutput"),
             },
             Some(CommandName::MessageToPlugin) => match protobuf_plugin_command.payload {
-                Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
-                    plugin_url,
-                    plugin_config,
-                    message_name,
-                    message_payload,
-                    message_args,
-                    new_plugin_args,
-                    destination_plugin_id,
-                    floating_pane_coordinates,
-                })) => {
-                    let plugin_config: BTreeMap<String, String> = plugin_config
-                        .into_iter()
-                        .map(|e| (e.name, e.value))
-                        .collect();
-                    let message_args: BTreeMap<String, String> = message_args
-                        .into_iter()
-                        .map(|e| (e.name, e.value))
-                        .collect();
-                    Ok(PluginCommand::MessageToPlugin(MessageToPlugin {
-                        plugin_url,
-                        plugin_config,
-                        message_name,
-                        message_payload,
-                        message_args,
-                        new_plugin_args: new_plugin_args.and_then(|protobuf_new_plugin_args| {
-                            Some(NewPluginArgs {
-                                should_float: protobuf_new_plugin_args.should_float,
-                                pane_id_to_replace: protobuf_new_plugin_args
-                                    .pane_id_to_replace
-                                    .and_then(|p_id| PaneId::try_from(p_id).ok()),
-                                pane_title: protobuf_new_plugin_args.pane_title,
-                                cwd: protobuf_new_plugin_args.cwd.map(|cwd| PathBuf::from(cwd)),
-                                skip_cache: protobuf_new_plugin_args.skip_cache,
-                                should_focus: protobuf_new_plugin_args.should_focus,
-                            })
-                        }),
-                        destination_plugin_id,
-                        floating_pane_coordinates: floating_pane_coordinates
-                            .and_then(|f| f.try_into().ok()),
-                    }))
-                },
+                Some(Payload::MessageToPluginPayload(message_to_plugin_payload)) => Ok(
+                    PluginCommand::MessageToPlugin(message_to_plugin_payload.try_into()?),
+                ),
                 _ => Err("Mismatched payload for MessageToPlugin"),
             },
             Some(CommandName::DisconnectOtherClients) => match protobuf_plugin_command.payload {
@@ -1741,6 +1992,34 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for OverrideLayout"),
             },
+            Some(CommandName::OverrideLayoutWithArgs) => match protobuf_plugin_command.payload {
+                Some(Payload::OverrideLayoutWithArgsPayload(override_layout_payload)) => {
+                    let layout_info = override_layout_payload
+                        .layout_info
+                        .ok_or("OverrideLayoutWithArgs missing layout_info")?
+                        .try_into()
+                        .map_err(|_| "Failed to parse LayoutInfo")?;
+                    Ok(PluginCommand::OverrideLayoutWithArgs {
+                        layout_info,
+                        retain_existing_terminal_panes: override_layout_payload
+                            .retain_existing_terminal_panes,
+                        retain_existing_plugin_panes: override_layout_payload
+                            .retain_existing_plugin_panes,
+                        apply_only_to_active_tab: override_layout_payload.apply_only_to_active_tab,
+                        layout_args: override_layout_payload
+                            .layout_args
+                            .into_iter()
+                            .map(|a| (a.name, a.value))
+                            .collect(),
+                        context: override_layout_payload
+                            .context
+                            .into_iter()
+                            .map(|c| (c.name, c.value))
+                            .collect(),
+                    })
+                },
+                _ => Err("Mismatched payload for OverrideLayoutWithArgs"),
+            },
             Some(CommandName::SaveLayout) => match protobuf_plugin_command.payload {
                 Some(Payload::SaveLayoutPayload(save_layout_payload)) => {
                     Ok(PluginCommand::SaveLayout {
@@ -2391,6 +2670,8 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                     Ok(PluginCommand::GenerateWebLoginToken(
                         generate_web_login_token_payload.token_label,
                         generate_web_login_token_payload.read_only.unwrap_or(false),
+                        generate_web_login_token_payload.expires_in_secs,
+                        generate_web_login_token_payload.session_scope,
                     ))
                 },
                 _ => Err("GenerateWebLoginToken requires a payload"),
@@ -3106,6 +3387,90 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 name: CommandName::FocusHostSession as i32,
                 payload: None,
             }),
+            PluginCommand::StartRecording { path, target } => Ok(ProtobufPluginCommand {
+                name: CommandName::StartRecording as i32,
+                payload: Some(Payload::StartRecordingPayload(StartRecordingPayload {
+                    path: path.map(|p| p.display().to_string()),
+                    target: Some(target.try_into()?),
+                })),
+            }),
+            PluginCommand::StopRecording { path } => Ok(ProtobufPluginCommand {
+                name: CommandName::StopRecording as i32,
+                payload: Some(Payload::StopRecordingPayload(StopRecordingPayload {
+                    path: path.map(|p| p.display().to_string()),
+                })),
+            }),
+            PluginCommand::SetPaneMonitors { pane_id, monitors } => Ok(ProtobufPluginCommand {
+                name: CommandName::SetPaneMonitors as i32,
+                payload: Some(Payload::SetPaneMonitorsPayload(SetPaneMonitorsPayload {
+                    pane_id: ProtobufPaneId::try_from(pane_id).ok(),
+                    monitor_activity: monitors.activity,
+                    monitor_silence_secs: monitors.silence_secs,
+                })),
+            }),
+            PluginCommand::GetStorageValue { key, scope } => Ok(ProtobufPluginCommand {
+                name: CommandName::GetStorageValue as i32,
+                payload: Some(Payload::GetStorageValuePayload(StorageKeyPayload {
+                    key,
+                    scope: ProtobufStorageScope::from(scope) as i32,
+                })),
+            }),
+            PluginCommand::SetStorageValue { key, value, scope } => Ok(ProtobufPluginCommand {
+                name: CommandName::SetStorageValue as i32,
+                payload: Some(Payload::SetStorageValuePayload(SetStorageValuePayload {
+                    key,
+                    value,
+                    scope: ProtobufStorageScope::from(scope) as i32,
+                })),
+            }),
+            PluginCommand::DeleteStorageValue { key, scope } => Ok(ProtobufPluginCommand {
+                name: CommandName::DeleteStorageValue as i32,
+                payload: Some(Payload::DeleteStorageValuePayload(StorageKeyPayload {
+                    key,
+                    scope: ProtobufStorageScope::from(scope) as i32,
+                })),
+            }),
+            PluginCommand::ListStorageValues { prefix, scope } => Ok(ProtobufPluginCommand {
+                name: CommandName::ListStorageValues as i32,
+                payload: Some(Payload::ListStorageValuesPayload(
+                    ListStorageValuesPayload {
+                        prefix,
+                        scope: ProtobufStorageScope::from(scope) as i32,
+                    },
+                )),
+            }),
+            PluginCommand::SendPluginRequest {
+                message,
+                timeout_ms,
+                context,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::SendPluginRequest as i32,
+                payload: Some(Payload::SendPluginRequestPayload(
+                    SendPluginRequestPayload {
+                        message: Some(message.into()),
+                        timeout_ms,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            PluginCommand::ReplyToPluginRequest { request_id, reply } => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ReplyToPluginRequest as i32,
+                    payload: Some(Payload::ReplyToPluginRequestPayload(
+                        ReplyToPluginRequestPayload {
+                            request_id,
+                            reply: Some(reply.into()),
+                        },
+                    )),
+                })
+            },
+            PluginCommand::ReopenClosedPane => Ok(ProtobufPluginCommand {
+                name: CommandName::ReopenClosedPane as i32,
+                payload: None,
+            }),
             PluginCommand::TogglePaneFrames => Ok(ProtobufPluginCommand {
                 name: CommandName::TogglePaneFrames as i32,
                 payload: None,
@@ -3237,6 +3602,63 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                             .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
                             .map(|p| p as i32)
                             .collect(),
+                        filesystem_permissions: vec![],
+                    },
+                )),
+            }),
+            PluginCommand::RequestPluginPermissionsWithFilesystemAccess(
+                permissions,
+                filesystem_permissions,
+            ) => Ok(ProtobufPluginCommand {
+                name: CommandName::RequestPluginPermissionsWithFilesystemAccess as i32,
+                payload: Some(
+                    Payload::RequestPluginPermissionsWithFilesystemAccessPayload(
+                        RequestPluginPermissionPayload {
+                            permissions: permissions
+                                .iter()
+                                .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                                .map(|p| p as i32)
+                                .collect(),
+                            filesystem_permissions: filesystem_permissions
+                                .into_iter()
+                                .map(|p| p.into())
+                                .collect(),
+                        },
+                    ),
+                ),
+            }),
+            PluginCommand::ListGrantedPermissions => Ok(ProtobufPluginCommand {
+                name: CommandName::ListGrantedPermissions as i32,
+                payload: None,
+            }),
+            PluginCommand::RevokePluginPermissions {
+                plugin_url,
+                permissions,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::RevokePluginPermissions as i32,
+                payload: Some(Payload::RevokePluginPermissionsPayload(
+                    RevokePluginPermissionsPayload {
+                        plugin_url,
+                        permissions: permissions
+                            .iter()
+                            .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                            .map(|p| p as i32)
+                            .collect(),
+                    },
+                )),
+            }),
+            PluginCommand::NewTabsWithLayoutAndArgs {
+                layout_info,
+                layout_args,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::NewTabsWithLayoutAndArgs as i32,
+                payload: Some(Payload::NewTabsWithLayoutAndArgsPayload(
+                    NewTabsWithLayoutAndArgsPayload {
+                        layout_info: layout_info.try_into().ok(),
+                        layout_args: layout_args
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
                     },
                 )),
             }),
@@ -3354,44 +3776,10 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     output,
                 })),
             }),
-            PluginCommand::MessageToPlugin(message_to_plugin) => {
-                let plugin_config: Vec<_> = message_to_plugin
-                    .plugin_config
-                    .into_iter()
-                    .map(|(name, value)| ContextItem { name, value })
-                    .collect();
-                let message_args: Vec<_> = message_to_plugin
-                    .message_args
-                    .into_iter()
-                    .map(|(name, value)| ContextItem { name, value })
-                    .collect();
-                Ok(ProtobufPluginCommand {
-                    name: CommandName::MessageToPlugin as i32,
-                    payload: Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
-                        plugin_url: message_to_plugin.plugin_url,
-                        plugin_config,
-                        message_name: message_to_plugin.message_name,
-                        message_payload: message_to_plugin.message_payload,
-                        message_args,
-                        new_plugin_args: message_to_plugin.new_plugin_args.map(|m_t_p| {
-                            ProtobufNewPluginArgs {
-                                should_float: m_t_p.should_float,
-                                pane_id_to_replace: m_t_p
-                                    .pane_id_to_replace
-                                    .and_then(|p_id| ProtobufPaneId::try_from(p_id).ok()),
-                                pane_title: m_t_p.pane_title,
-                                cwd: m_t_p.cwd.map(|cwd| cwd.display().to_string()),
-                                skip_cache: m_t_p.skip_cache,
-                                should_focus: m_t_p.should_focus,
-                            }
-                        }),
-                        destination_plugin_id: message_to_plugin.destination_plugin_id,
-                        floating_pane_coordinates: message_to_plugin
-                            .floating_pane_coordinates
-                            .and_then(|f| f.try_into().ok()),
-                    })),
-                })
-            },
+            PluginCommand::MessageToPlugin(message_to_plugin) => Ok(ProtobufPluginCommand {
+                name: CommandName::MessageToPlugin as i32,
+                payload: Some(Payload::MessageToPluginPayload(message_to_plugin.into())),
+            }),
             PluginCommand::DisconnectOtherClients => Ok(ProtobufPluginCommand {
                 name: CommandName::DisconnectOtherClients as i32,
                 payload: None,
@@ -3607,8 +3995,35 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     retain_existing_terminal_panes,
                     retain_existing_plugin_panes,
                     apply_only_to_active_tab,
+                    layout_args: vec![],
                 })),
             }),
+            PluginCommand::OverrideLayoutWithArgs {
+                layout_info,
+                retain_existing_terminal_panes,
+                retain_existing_plugin_panes,
+                apply_only_to_active_tab,
+                layout_args,
+                context,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::OverrideLayoutWithArgs as i32,
+                payload: Some(Payload::OverrideLayoutWithArgsPayload(
+                    OverrideLayoutPayload {
+                        layout_info: layout_info.try_into().ok(),
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                        retain_existing_terminal_panes,
+                        retain_existing_plugin_panes,
+                        apply_only_to_active_tab,
+                        layout_args: layout_args
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
             PluginCommand::SaveLayout {
                 layout_name,
                 layout_kdl,
@@ -4164,17 +4579,22 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
-            PluginCommand::GenerateWebLoginToken(token_label, read_only) => {
-                Ok(ProtobufPluginCommand {
-                    name: CommandName::GenerateWebLoginToken as i32,
-                    payload: Some(Payload::GenerateWebLoginTokenPayload(
-                        GenerateWebLoginTokenPayload {
-                            token_label,
-                            read_only: Some(read_only),
-                        },
-                    )),
-                })
-            },
+            PluginCommand::GenerateWebLoginToken(
+                token_label,
+                read_only,
+                expires_in_secs,
+                session_scope,
+            ) => Ok(ProtobufPluginCommand {
+                name: CommandName::GenerateWebLoginToken as i32,
+                payload: Some(Payload::GenerateWebLoginTokenPayload(
+                    GenerateWebLoginTokenPayload {
+                        token_label,
+                        read_only: Some(read_only),
+                        expires_in_secs,
+                        session_scope,
+                    },
+                )),
+            }),
             PluginCommand::RevokeWebLoginToken(token_label) => Ok(ProtobufPluginCommand {
                 name: CommandName::RevokeWebLoginToken as i32,
                 payload: Some(Payload::RevokeWebLoginTokenPayload(
@@ -5282,4 +5702,104 @@ mod tests {
             other => panic!("expected ToggleFloatingPanes, got {:?}", other),
         }
     }
+
+    #[test]
+    fn start_recording_protobuf_round_trip() {
+        for (path, target) in [
+            (None, RecordingTarget::FocusedPane),
+            (
+                Some(PathBuf::from("/tmp/pane.cast")),
+                RecordingTarget::Pane(PaneId::Terminal(3)),
+            ),
+            (None, RecordingTarget::FocusedTab),
+            (None, RecordingTarget::Tab(2)),
+            (None, RecordingTarget::Client),
+        ] {
+            let original = PluginCommand::StartRecording {
+                path: path.clone(),
+                target,
+            };
+            let protobuf: ProtobufPluginCommand = original.try_into().expect("encode");
+            let decoded: PluginCommand = protobuf.try_into().expect("decode");
+            match decoded {
+                PluginCommand::StartRecording {
+                    path: decoded_path,
+                    target: decoded_target,
+                } => {
+                    assert_eq!(decoded_path, path);
+                    assert_eq!(decoded_target, target);
+                },
+                other => panic!("expected StartRecording, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn stop_recording_protobuf_round_trip() {
+        let original = PluginCommand::StopRecording {
+            path: Some(PathBuf::from("/tmp/pane.cast")),
+        };
+        let protobuf: ProtobufPluginCommand = original.try_into().expect("encode");
+        let decoded: PluginCommand = protobuf.try_into().expect("decode");
+        match decoded {
+            PluginCommand::StopRecording { path } => {
+                assert_eq!(path, Some(PathBuf::from("/tmp/pane.cast")))
+            },
+            other => panic!("expected StopRecording, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn set_pane_monitors_protobuf_round_trip() {
+        for monitors in [
+            PaneMonitors {
+                activity: true,
+                silence_secs: Some(30),
+            },
+            PaneMonitors::default(),
+        ] {
+            let original = PluginCommand::SetPaneMonitors {
+                pane_id: PaneId::Terminal(4),
+                monitors,
+            };
+            let protobuf: ProtobufPluginCommand = original.try_into().expect("encode");
+            let decoded: PluginCommand = protobuf.try_into().expect("decode");
+            match decoded {
+                PluginCommand::SetPaneMonitors {
+                    pane_id,
+                    monitors: decoded_monitors,
+                } => {
+                    assert_eq!(pane_id, PaneId::Terminal(4));
+                    assert_eq!(decoded_monitors, monitors);
+                },
+                other => panic!("expected SetPaneMonitors, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn generate_web_login_token_protobuf_round_trip() {
+        let original = PluginCommand::GenerateWebLoginToken(
+            Some("contractor".to_owned()),
+            true,
+            Some(4 * 60 * 60),
+            Some("pairing-*".to_owned()),
+        );
+        let protobuf: ProtobufPluginCommand = original.try_into().expect("encode");
+        let decoded: PluginCommand = protobuf.try_into().expect("decode");
+        match decoded {
+            PluginCommand::GenerateWebLoginToken(
+                token_label,
+                read_only,
+                expires_in_secs,
+                session_scope,
+            ) => {
+                assert_eq!(token_label.as_deref(), Some("contractor"));
+                assert!(read_only);
+                assert_eq!(expires_in_secs, Some(4 * 60 * 60));
+                assert_eq!(session_scope.as_deref(), Some("pairing-*"));
+            },
+            other => panic!("expected GenerateWebLoginToken, got {:?}", other),
+        }
+    }
 }
@@ -1,5 +1,9 @@
-pub use super::generated_api::api::plugin_permission::PermissionType as ProtobufPermissionType;
-use crate::data::PermissionType;
+pub use super::generated_api::api::plugin_permission::{
+    FilesystemPermission as ProtobufFilesystemPermission,
+    GrantedPluginPermissions as ProtobufGrantedPluginPermissions,
+    PermissionType as ProtobufPermissionType,
+};
+use crate::data::{FilesystemPermission, GrantedPluginPermissions, PermissionType};
 
 use std::convert::TryFrom;
 
@@ -72,3 +76,59 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
         }
     }
 }
+
+impl From<ProtobufFilesystemPermission> for FilesystemPermission {
+    fn from(protobuf_filesystem_permission: ProtobufFilesystemPermission) -> Self {
+        FilesystemPermission {
+            path: protobuf_filesystem_permission.path,
+            read_only: protobuf_filesystem_permission.read_only,
+        }
+    }
+}
+
+impl From<FilesystemPermission> for ProtobufFilesystemPermission {
+    fn from(filesystem_permission: FilesystemPermission) -> Self {
+        ProtobufFilesystemPermission {
+            path: filesystem_permission.path,
+            read_only: filesystem_permission.read_only,
+        }
+    }
+}
+
+impl From<ProtobufGrantedPluginPermissions> for GrantedPluginPermissions {
+    fn from(protobuf_granted: ProtobufGrantedPluginPermissions) -> Self {
+        GrantedPluginPermissions {
+            plugin_url: protobuf_granted.plugin_url,
+            permissions: protobuf_granted
+                .permissions
+                .iter()
+                .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                .filter_map(|p| PermissionType::try_from(p).ok())
+                .collect(),
+            filesystem_permissions: protobuf_granted
+                .filesystem_permissions
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<GrantedPluginPermissions> for ProtobufGrantedPluginPermissions {
+    fn from(granted: GrantedPluginPermissions) -> Self {
+        ProtobufGrantedPluginPermissions {
+            plugin_url: granted.plugin_url,
+            permissions: granted
+                .permissions
+                .iter()
+                .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                .map(|p| p as i32)
+                .collect(),
+            filesystem_permissions: granted
+                .filesystem_permissions
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
+        }
+    }
+}
@@ -3,11 +3,15 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 use crate::{
-    input::layout::PluginUserConfiguration,
+    data::PaneMonitors,
+    input::command::RestartPolicy,
     input::layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, Run, RunPluginOrAlias,
-        SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+        FloatingPaneLayout, Layout, LayoutConstraint, LogOutput, PercentOrFixed, Run,
+        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
+        TiledPaneLayout,
     },
+    input::layout::{PluginResourceLimits, PluginUserConfiguration},
+    kdl::allowed_hosts_to_kdl,
     pane_size::{Constraint, PaneGeom},
 };
 
@@ -36,14 +40,19 @@ pub struct PaneLayoutManifest {
     pub title: Option<String>,
     pub is_focused: bool,
     pub pane_contents: Option<String>,
+    pub scrollback_snapshot: Option<Vec<u8>>,
     pub default_fg: Option<String>,
     pub default_bg: Option<String>,
+    pub log_output: Option<LogOutput>,
+    pub monitors: Option<PaneMonitors>,
+    pub broadcast_group: Option<String>,
 }
 
 pub fn serialize_session_layout(
     global_layout_manifest: GlobalLayoutManifest,
-) -> Result<(String, BTreeMap<String, String>), &'static str> {
-    // BTreeMap is the pane contents and their file names
+) -> Result<(String, BTreeMap<String, Vec<u8>>), &'static str> {
+    // BTreeMap is the contents of the files referenced by the layout (pane contents and
+    // scrollback snapshots) keyed by their file names
     let mut document = KdlDocument::new();
     let mut pane_contents = BTreeMap::new();
     let mut layout_node = KdlNode::new("layout");
@@ -88,7 +97,7 @@ fn serialize_tab(
     hide_floating_panes: bool,
     tiled_panes: &Vec<PaneLayoutManifest>,
     floating_panes: &Vec<PaneLayoutManifest>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
 ) -> Option<KdlNode> {
     let mut serialized_tab = KdlNode::new("tab");
     let mut serialized_tab_children = KdlDocument::new();
@@ -147,7 +156,7 @@ fn tiled_panes_to_serialize(root: TiledPaneLayout) -> Vec<TiledPaneLayout> {
 fn serialize_tiled_and_floating_panes(
     tiled_panes: &Vec<TiledPaneLayout>,
     floating_panes_layout: Vec<FloatingPaneLayout>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
     serialized_tab_children: &mut KdlDocument,
 ) {
     for tiled_pane_layout in tiled_panes {
@@ -172,10 +181,15 @@ fn serialize_tiled_and_floating_panes(
 fn serialize_tiled_pane(
     layout: &TiledPaneLayout,
     ignore_size: bool,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
 ) -> KdlNode {
     let (command, args) = extract_command_and_args(&layout.run);
+    let env = extract_command_env(&layout.run);
+    let (restart, max_restarts) = extract_command_restart(&layout.run);
     let (plugin, plugin_config) = extract_plugin_and_config(&layout.run);
+    let plugin_sha256 = extract_plugin_sha256(&layout.run);
+    let plugin_resource_limits = extract_plugin_resource_limits(&layout.run);
+    let plugin_allowed_hosts = extract_plugin_allowed_hosts(&layout.run);
     let (edit, _line_number) = extract_edit_and_line_number(&layout.run);
     let cwd = layout.run.as_ref().and_then(|r| r.get_cwd());
     let has_children = layout.external_children_index.is_some() || !layout.children.is_empty();
@@ -192,6 +206,11 @@ fn serialize_tiled_pane(
         has_children,
         &mut tiled_pane_node,
     );
+    serialize_pane_scrollback_snapshot(
+        &layout.pane_scrollback_snapshot,
+        pane_contents,
+        &mut tiled_pane_node,
+    );
 
     serialize_tiled_layout_attributes(&layout, ignore_size, &mut tiled_pane_node);
     if let Some(ref fg) = layout.default_fg {
@@ -204,16 +223,29 @@ fn serialize_tiled_pane(
             .entries_mut()
             .push(KdlEntry::new_prop("default_bg", bg.to_owned()));
     }
+    serialize_pane_monitors(&layout.monitors, &mut tiled_pane_node);
+    serialize_broadcast_group(&layout.broadcast_group, &mut tiled_pane_node);
     let has_child_attributes = !layout.children.is_empty()
         || layout.external_children_index.is_some()
         || !args.is_empty()
         || plugin.is_some()
-        || command.is_some();
+        || command.is_some()
+        || layout.log_output.is_some();
     if has_child_attributes {
         let mut tiled_pane_node_children = KdlDocument::new();
         serialize_args(args, &mut tiled_pane_node_children);
+        serialize_env(env, &mut tiled_pane_node_children);
+        serialize_restart(restart, max_restarts, &mut tiled_pane_node_children);
+        serialize_log_output(&layout.log_output, &mut tiled_pane_node_children);
         serialize_start_suspended(&command, &mut tiled_pane_node_children);
-        serialize_plugin(plugin, plugin_config, &mut tiled_pane_node_children);
+        serialize_plugin(
+            plugin,
+            plugin_config,
+            plugin_sha256,
+            plugin_resource_limits,
+            plugin_allowed_hosts,
+            &mut tiled_pane_node_children,
+        );
         if layout.children.is_empty() && layout.external_children_index.is_some() {
             tiled_pane_node_children
                 .nodes_mut()
@@ -244,6 +276,18 @@ pub fn extract_command_and_args(layout_run: &Option<Run>) -> (Option<String>, Ve
         _ => (None, vec![]),
     }
 }
+pub fn extract_command_env(layout_run: &Option<Run>) -> BTreeMap<String, String> {
+    match layout_run {
+        Some(Run::Command(run_command)) => run_command.env.clone(),
+        _ => BTreeMap::new(),
+    }
+}
+pub fn extract_command_restart(layout_run: &Option<Run>) -> (RestartPolicy, Option<usize>) {
+    match layout_run {
+        Some(Run::Command(run_command)) => (run_command.restart, run_command.max_restarts),
+        _ => (RestartPolicy::Never, None),
+    }
+}
 pub fn extract_plugin_and_config(
     layout_run: &Option<Run>,
 ) -> (Option<String>, Option<PluginUserConfiguration>) {
@@ -272,6 +316,32 @@ pub fn extract_plugin_and_config(
         _ => (None, None),
     }
 }
+pub fn extract_plugin_sha256(layout_run: &Option<Run>) -> Option<String> {
+    match &layout_run {
+        Some(Run::Plugin(run_plugin_or_alias)) => run_plugin_or_alias
+            .get_run_plugin()
+            .and_then(|run_plugin| run_plugin.sha256),
+        _ => None,
+    }
+}
+pub fn extract_plugin_resource_limits(layout_run: &Option<Run>) -> PluginResourceLimits {
+    match &layout_run {
+        Some(Run::Plugin(run_plugin_or_alias)) => run_plugin_or_alias
+            .get_run_plugin()
+            .map(|run_plugin| run_plugin.resource_limits)
+            .unwrap_or_default(),
+        _ => Default::default(),
+    }
+}
+pub fn extract_plugin_allowed_hosts(layout_run: &Option<Run>) -> Vec<String> {
+    match &layout_run {
+        Some(Run::Plugin(run_plugin_or_alias)) => run_plugin_or_alias
+            .get_run_plugin()
+            .map(|run_plugin| run_plugin.allowed_hosts)
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
 pub fn extract_edit_and_line_number(layout_run: &Option<Run>) -> (Option<String>, Option<usize>) {
     match &layout_run {
         // TODO: line number in layouts?
@@ -289,7 +359,7 @@ fn serialize_pane_title_and_attributes(
     cwd: Option<PathBuf>,
     focus: Option<bool>,
     initial_pane_contents: &Option<String>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
     has_children: bool,
     kdl_node: &mut KdlNode,
 ) {
@@ -327,8 +397,68 @@ fn serialize_pane_title_and_attributes(
                 .entries_mut()
                 .push(KdlEntry::new_prop("contents_file", file_name.clone()));
 
-            pane_contents.insert(file_name, initial_pane_contents.clone());
+            pane_contents.insert(file_name, initial_pane_contents.clone().into_bytes());
+        }
+    }
+}
+
+fn serialize_pane_scrollback_snapshot(
+    pane_scrollback_snapshot: &Option<Vec<u8>>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
+    kdl_node: &mut KdlNode,
+) {
+    if let Some(pane_scrollback_snapshot) = pane_scrollback_snapshot.as_ref() {
+        let file_name = format!("scrollback_snapshot_{}", pane_contents.keys().len() + 1);
+        kdl_node.entries_mut().push(KdlEntry::new_prop(
+            "scrollback_snapshot_file",
+            file_name.clone(),
+        ));
+        pane_contents.insert(file_name, pane_scrollback_snapshot.clone());
+    }
+}
+
+fn serialize_pane_monitors(monitors: &Option<PaneMonitors>, pane_node: &mut KdlNode) {
+    if let Some(monitors) = monitors {
+        if monitors.activity {
+            pane_node
+                .entries_mut()
+                .push(KdlEntry::new_prop("monitor_activity", KdlValue::Bool(true)));
+        }
+        if let Some(silence_secs) = monitors.silence_secs {
+            pane_node.entries_mut().push(KdlEntry::new_prop(
+                "monitor_silence",
+                KdlValue::Base10(silence_secs as i64),
+            ));
+        }
+    }
+}
+
+fn serialize_broadcast_group(broadcast_group: &Option<String>, pane_node: &mut KdlNode) {
+    if let Some(broadcast_group) = broadcast_group {
+        pane_node.entries_mut().push(KdlEntry::new_prop(
+            "broadcast_group",
+            broadcast_group.to_owned(),
+        ));
+    }
+}
+
+fn serialize_log_output(log_output: &Option<LogOutput>, pane_node_children: &mut KdlDocument) {
+    if let Some(log_output) = log_output {
+        let mut log_output_node = KdlNode::new("log_output");
+        log_output_node.push(log_output.path.display().to_string());
+        if log_output.strip_ansi {
+            log_output_node.push(("strip_ansi", KdlValue::Bool(true)));
+        }
+        if log_output.timestamps {
+            log_output_node.push(("timestamps", KdlValue::Bool(true)));
         }
+        if let Some(max_size) = log_output.max_size {
+            log_output_node.push(("max_size", KdlValue::Base10(max_size as i64)));
+        }
+        if let Some(max_files) = log_output.max_files {
+            log_output_node.push(("max_files", KdlValue::Base10(max_files as i64)));
+        }
+        pane_node_children.nodes_mut().push(log_output_node);
     }
 }
 
@@ -342,9 +472,43 @@ fn serialize_args(args: Vec<String>, pane_node_children: &mut KdlDocument) {
     }
 }
 
+fn serialize_env(env: BTreeMap<String, String>, pane_node_children: &mut KdlDocument) {
+    if !env.is_empty() {
+        let mut env_node = KdlNode::new("env");
+        let mut env_vars = KdlDocument::new();
+        for (env_var_name, env_var_value) in env {
+            let mut env_var_node = KdlNode::new(env_var_name);
+            env_var_node.push(env_var_value);
+            env_vars.nodes_mut().push(env_var_node);
+        }
+        env_node.set_children(env_vars);
+        pane_node_children.nodes_mut().push(env_node);
+    }
+}
+
+fn serialize_restart(
+    restart: RestartPolicy,
+    max_restarts: Option<usize>,
+    pane_node_children: &mut KdlDocument,
+) {
+    if restart != RestartPolicy::Never {
+        let mut restart_node = KdlNode::new("restart");
+        restart_node.push(restart.to_string());
+        pane_node_children.nodes_mut().push(restart_node);
+        if let Some(max_restarts) = max_restarts {
+            let mut max_restarts_node = KdlNode::new("max_restarts");
+            max_restarts_node.push(max_restarts as i64);
+            pane_node_children.nodes_mut().push(max_restarts_node);
+        }
+    }
+}
+
 fn serialize_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
+    plugin_sha256: Option<String>,
+    plugin_resource_limits: PluginResourceLimits,
+    plugin_allowed_hosts: Vec<String>,
     pane_node_children: &mut KdlDocument,
 ) {
     if let Some(plugin) = plugin {
@@ -352,10 +516,21 @@ fn serialize_plugin(
         plugin_node
             .entries_mut()
             .push(KdlEntry::new_prop("location", plugin.to_owned()));
+        if let Some(sha256) = plugin_sha256 {
+            plugin_node
+                .entries_mut()
+                .push(KdlEntry::new_prop("sha256", sha256));
+        }
+        let mut plugin_node_children = KdlDocument::new();
+        plugin_node_children
+            .nodes_mut()
+            .append(&mut plugin_resource_limits.to_kdl());
+        if let Some(allowed_hosts_node) = allowed_hosts_to_kdl(&plugin_allowed_hosts) {
+            plugin_node_children.nodes_mut().push(allowed_hosts_node);
+        }
         if let Some(plugin_config) =
             plugin_config.and_then(|p| if p.inner().is_empty() { None } else { Some(p) })
         {
-            let mut plugin_node_children = KdlDocument::new();
             for (config_key, config_value) in plugin_config.inner() {
                 let mut config_node = KdlNode::new(config_key.to_owned());
                 config_node
@@ -363,6 +538,8 @@ fn serialize_plugin(
                     .push(KdlEntry::new(config_value.to_owned()));
                 plugin_node_children.nodes_mut().push(config_node);
             }
+        }
+        if !plugin_node_children.nodes().is_empty() {
             plugin_node.set_children(plugin_node_children);
         }
         pane_node_children.nodes_mut().push(plugin_node);
@@ -505,7 +682,7 @@ fn serialize_global_cwd(global_cwd: &Option<PathBuf>) -> Option<KdlNode> {
 
 fn serialize_new_tab_template(
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
     layout_children_node: &mut KdlDocument,
 ) {
     if let Some((tiled_panes, floating_panes)) = new_tab_template {
@@ -526,7 +703,7 @@ fn serialize_new_tab_template(
 
 fn serialize_swap_tiled_layouts(
     swap_tiled_layouts: Vec<SwapTiledLayout>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
     layout_node_children: &mut KdlDocument,
 ) {
     for swap_tiled_layout in swap_tiled_layouts {
@@ -585,7 +762,7 @@ fn serialize_layout_constraint(layout_constraint: LayoutConstraint) -> Option<Kd
 
 fn serialize_swap_floating_layouts(
     swap_floating_layouts: Vec<SwapFloatingLayout>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
     layout_children_node: &mut KdlDocument,
 ) {
     for swap_floating_layout in swap_floating_layouts {
@@ -626,7 +803,7 @@ fn serialize_swap_floating_layouts(
 
 fn serialize_multiple_tabs(
     tabs: Vec<(String, TabLayoutManifest)>,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
 ) -> Result<Vec<KdlNode>, &'static str> {
     let mut serialized_tabs: Vec<KdlNode> = vec![];
     for (tab_name, tab_layout_manifest) in tabs {
@@ -652,12 +829,17 @@ fn serialize_multiple_tabs(
 
 fn serialize_floating_pane(
     layout: &FloatingPaneLayout,
-    pane_contents: &mut BTreeMap<String, String>,
+    pane_contents: &mut BTreeMap<String, Vec<u8>>,
 ) -> KdlNode {
     let mut floating_pane_node = KdlNode::new("pane");
     let mut floating_pane_node_children = KdlDocument::new();
     let (command, args) = extract_command_and_args(&layout.run);
+    let env = extract_command_env(&layout.run);
+    let (restart, max_restarts) = extract_command_restart(&layout.run);
     let (plugin, plugin_config) = extract_plugin_and_config(&layout.run);
+    let plugin_sha256 = extract_plugin_sha256(&layout.run);
+    let plugin_resource_limits = extract_plugin_resource_limits(&layout.run);
+    let plugin_allowed_hosts = extract_plugin_allowed_hosts(&layout.run);
     let (edit, _line_number) = extract_edit_and_line_number(&layout.run);
     let cwd = layout.run.as_ref().and_then(|r| r.get_cwd());
     let has_children = false;
@@ -672,6 +854,11 @@ fn serialize_floating_pane(
         has_children,
         &mut floating_pane_node,
     );
+    serialize_pane_scrollback_snapshot(
+        &layout.pane_scrollback_snapshot,
+        pane_contents,
+        &mut floating_pane_node,
+    );
     if let Some(ref fg) = layout.default_fg {
         floating_pane_node
             .entries_mut()
@@ -682,10 +869,22 @@ fn serialize_floating_pane(
             .entries_mut()
             .push(KdlEntry::new_prop("default_bg", bg.to_owned()));
     }
+    serialize_pane_monitors(&layout.monitors, &mut floating_pane_node);
+    serialize_broadcast_group(&layout.broadcast_group, &mut floating_pane_node);
     serialize_start_suspended(&command, &mut floating_pane_node_children);
     serialize_floating_layout_attributes(&layout, &mut floating_pane_node_children);
     serialize_args(args, &mut floating_pane_node_children);
-    serialize_plugin(plugin, plugin_config, &mut floating_pane_node_children);
+    serialize_env(env, &mut floating_pane_node_children);
+    serialize_restart(restart, max_restarts, &mut floating_pane_node_children);
+    serialize_log_output(&layout.log_output, &mut floating_pane_node_children);
+    serialize_plugin(
+        plugin,
+        plugin_config,
+        plugin_sha256,
+        plugin_resource_limits,
+        plugin_allowed_hosts,
+        &mut floating_pane_node_children,
+    );
     floating_pane_node.set_children(floating_pane_node_children);
     floating_pane_node
 }
@@ -740,8 +939,12 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        pane_scrollback_snapshot,
         default_fg,
         default_bg,
+        log_output,
+        monitors,
+        broadcast_group,
     ) = manifest
         .map(|g| {
             let mut run = g.run.clone();
@@ -759,11 +962,17 @@ fn tiled_pane_layout_from_manifest(
                 g.title.clone(),
                 Some(g.is_focused),
                 g.pane_contents.clone(),
+                g.scrollback_snapshot.clone(),
                 g.default_fg.clone(),
                 g.default_bg.clone(),
+                g.log_output.clone(),
+                g.monitors,
+                g.broadcast_group.clone(),
             )
         })
-        .unwrap_or((None, None, false, None, None, None, None, None));
+        .unwrap_or((
+            None, None, false, None, None, None, None, None, None, None, None, None,
+        ));
     TiledPaneLayout {
         split_size,
         run,
@@ -772,8 +981,12 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        pane_scrollback_snapshot,
         default_fg,
         default_bg,
+        log_output,
+        monitors,
+        broadcast_group,
         ..Default::default()
     }
 }
@@ -880,10 +1093,15 @@ fn get_floating_panes_layout_from_panegeoms(
                 focus: Some(m.is_focused),
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
+                pane_scrollback_snapshot: m.scrollback_snapshot.clone(),
                 logical_position: None,
                 borderless: Some(m.is_borderless),
                 default_fg: m.default_fg.clone(),
                 default_bg: m.default_bg.clone(),
+                log_output: m.log_output.clone(),
+                monitors: m.monitors,
+                broadcast_group: m.broadcast_group.clone(),
+                depends_on: vec![],
             }
         })
         .collect()
@@ -1135,6 +1353,7 @@ fn get_split_sizes(constraints: &Vec<Constraint>) -> Vec<Option<SplitSize>> {
 mod tests {
 
     use super::*;
+    use crate::consts::session_layout_cache_file_name;
     use crate::pane_size::Dimension;
     use expect_test::expect;
     use insta::assert_snapshot;
@@ -1401,6 +1620,96 @@ mod tests {
         .assert_eq(&kdl.0);
     }
 
+    #[test]
+    fn can_serialize_command_pane_with_env() {
+        use crate::input::command::RunCommand;
+        let env = BTreeMap::from([
+            (
+                "DATABASE_URL".to_owned(),
+                "postgres://localhost/staging".to_owned(),
+            ),
+            ("PORT".to_owned(), "8081".to_owned()),
+        ]);
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                run: Some(Run::Command(
+                    RunCommand::new(PathBuf::from("./server")).with_env(env),
+                )),
+                geom: PaneGeom {
+                    x: 0,
+                    y: 0,
+                    rows: Dimension::fixed(10),
+                    cols: Dimension::fixed(10),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane command="./server" {
+                        env {
+                            DATABASE_URL "postgres://localhost/staging"
+                            PORT "8081"
+                        }
+                        start_suspended true
+                    }
+                }
+            }
+        "#]]
+        .assert_eq(&kdl.0);
+    }
+
+    #[test]
+    fn can_serialize_command_pane_with_restart_policy() {
+        use crate::input::command::RunCommand;
+        let mut run_command = RunCommand::new(PathBuf::from("./worker"));
+        run_command.restart = RestartPolicy::OnFailure;
+        run_command.max_restarts = Some(5);
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                run: Some(Run::Command(run_command)),
+                geom: PaneGeom {
+                    x: 0,
+                    y: 0,
+                    rows: Dimension::fixed(10),
+                    cols: Dimension::fixed(10),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane command="./worker" {
+                        restart "on-failure"
+                        max_restarts 5
+                        start_suspended true
+                    }
+                }
+            }
+        "#]]
+        .assert_eq(&kdl.0);
+    }
+
     #[test]
     fn can_serialize_tab_with_a_single_tiled_pane_and_a_floating_pane() {
         let tab_layout_manifest = TabLayoutManifest {
@@ -1455,6 +1764,178 @@ mod tests {
         .assert_eq(&kdl.0);
     }
 
+    #[test]
+    fn can_serialize_pane_scrollback_snapshots() {
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                pane_contents: Some("can has pane contents".to_owned()),
+                scrollback_snapshot: Some(vec![1, 2, 3]),
+                ..Default::default()
+            }],
+            floating_panes: vec![PaneLayoutManifest {
+                scrollback_snapshot: Some(vec![4, 5, 6]),
+                geom: PaneGeom {
+                    x: 1,
+                    y: 1,
+                    rows: Dimension::fixed(5),
+                    cols: Dimension::fixed(5),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let (kdl, files) = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane contents_file="initial_contents_1" scrollback_snapshot_file="scrollback_snapshot_2"
+                    floating_panes {
+                        pane scrollback_snapshot_file="scrollback_snapshot_3" {
+                            height 5
+                            width 5
+                            x 1
+                            y 1
+                        }
+                    }
+                }
+            }
+        "#]]
+        .assert_eq(&kdl);
+        assert_eq!(files.get("scrollback_snapshot_2"), Some(&vec![1, 2, 3]));
+        assert_eq!(files.get("scrollback_snapshot_3"), Some(&vec![4, 5, 6]));
+    }
+
+    #[test]
+    fn can_serialize_pane_output_logs() {
+        let log_output = LogOutput {
+            path: PathBuf::from("/tmp/ci.log"),
+            strip_ansi: true,
+            timestamps: false,
+            max_size: Some(1024),
+            max_files: Some(2),
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                log_output: Some(log_output.clone()),
+                ..Default::default()
+            }],
+            floating_panes: vec![PaneLayoutManifest {
+                log_output: Some(LogOutput {
+                    path: PathBuf::from("/tmp/floating.log"),
+                    ..Default::default()
+                }),
+                geom: PaneGeom {
+                    x: 1,
+                    y: 1,
+                    rows: Dimension::fixed(5),
+                    cols: Dimension::fixed(5),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let (kdl, _files) = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane {
+                        log_output "/tmp/ci.log" strip_ansi=true max_size=1024 max_files=2
+                    }
+                    floating_panes {
+                        pane {
+                            height 5
+                            width 5
+                            x 1
+                            y 1
+                            log_output "/tmp/floating.log"
+                        }
+                    }
+                }
+            }
+        "#]]
+        .assert_eq(&kdl);
+        // logs anywhere are allowed when resurrecting, they were set up in the original session
+        let resurrection_layout_file = session_layout_cache_file_name("logging-session");
+        let layout = Layout::from_kdl(
+            &kdl,
+            Some(resurrection_layout_file.display().to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        let tab = &layout.tabs[0];
+        assert_eq!(tab.1.children[0].log_output, Some(log_output));
+    }
+
+    #[test]
+    fn can_serialize_pane_monitors() {
+        let monitors = PaneMonitors {
+            activity: true,
+            silence_secs: Some(30),
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![PaneLayoutManifest {
+                monitors: Some(monitors),
+                ..Default::default()
+            }],
+            floating_panes: vec![PaneLayoutManifest {
+                monitors: Some(PaneMonitors {
+                    activity: false,
+                    silence_secs: Some(120),
+                }),
+                geom: PaneGeom {
+                    x: 1,
+                    y: 1,
+                    rows: Dimension::fixed(5),
+                    cols: Dimension::fixed(5),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let (kdl, _files) = serialize_session_layout(global_layout_manifest).unwrap();
+        expect![[r#"
+            layout {
+                tab name="Tab #1" {
+                    pane monitor_activity=true monitor_silence=30
+                    floating_panes {
+                        pane monitor_silence=120 {
+                            height 5
+                            width 5
+                            x 1
+                            y 1
+                        }
+                    }
+                }
+            }
+        "#]]
+        .assert_eq(&kdl);
+        let layout = Layout::from_kdl(&kdl, None, None, None).unwrap();
+        let tab = &layout.tabs[0];
+        assert_eq!(tab.1.children[0].monitors, Some(monitors));
+    }
+
     #[test]
     fn can_serialize_new_tab_template_with_a_single_pane() {
         let tiled_panes_layout = TiledPaneLayout {
@@ -649,16 +649,25 @@ impl Setup {
             let layout_info = LayoutInfo::from_config(&layout_dir, &chosen_layout);
             (layout_info, chosen_layout)
         };
+        // the layout is parsed here (and then again by the server) so that errors, eg. in the
+        // layout arguments, are reported before the session starts
+        let layout_args = cli_args.layout_args.iter().cloned().collect();
         match layout_info {
             Some(LayoutInfo::Url(ref layout_url)) => {
-                Layout::from_url(layout_url, config).map(|(_layout, config)| (layout_info, config))
+                Layout::from_url(layout_url, layout_args, config)
+                    .map(|(_layout, config)| (layout_info, config))
             },
             Some(LayoutInfo::Stringified(ref raw_layout)) => {
-                Layout::from_stringified_layout(raw_layout, config)
+                Layout::from_stringified_layout(raw_layout, layout_args, config)
                     .map(|(_layout, config)| (layout_info, config))
             },
-            _ => Layout::from_path_or_default(chosen_layout.as_ref(), layout_dir.clone(), config)
-                .map(|(_layout, config)| (layout_info, config)),
+            _ => Layout::from_path_or_default(
+                chosen_layout.as_ref(),
+                layout_dir.clone(),
+                layout_args,
+                config,
+            )
+            .map(|(_layout, config)| (layout_info, config)),
         }
     }
     fn handle_setup_commands(cli_args: &CliArgs) {
@@ -701,14 +710,19 @@ mod setup_test {
     use super::Setup;
     use crate::cli::{CliArgs, Command};
     use crate::data::LayoutInfo;
+    use crate::input::config::Config;
     use crate::input::options::Options;
     use insta::assert_snapshot;
     use std::path::PathBuf;
 
+    fn from_cli_args(cli_args: &CliArgs) -> (Config, Option<LayoutInfo>, Options, Config, Options) {
+        crate::run_with_large_stack(|| Setup::from_cli_args(cli_args).unwrap())
+    }
+
     #[test]
     fn default_config_with_no_cli_arguments() {
         let cli_args = CliArgs::default();
-        let (config, layout_info, options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (config, layout_info, options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", config));
         assert_snapshot!(format!("{:#?}", layout_info));
         assert_snapshot!(format!("{:#?}", options));
@@ -720,7 +734,7 @@ mod setup_test {
             simplified_ui: Some(true),
             ..Default::default()
         }));
-        let (_config, _layout_info, options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_config, _layout_info, options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", options));
     }
     #[test]
@@ -730,7 +744,7 @@ mod setup_test {
             "{}/src/test-fixtures/layout-with-options.kdl",
             env!("CARGO_MANIFEST_DIR")
         )));
-        let (_config, layout_info, options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_config, layout_info, options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", options));
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info doesn't have expected format");
@@ -754,7 +768,7 @@ mod setup_test {
             pane_frames: Some(true),
             ..Default::default()
         }));
-        let (_config, layout_info, options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_config, layout_info, options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", options));
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info doesn't have expected format");
@@ -778,7 +792,7 @@ mod setup_test {
             "{}/src/test-fixtures/layout-with-env-vars.kdl",
             env!("CARGO_MANIFEST_DIR")
         )));
-        let (config, _layout_info, _options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (config, _layout_info, _options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", config));
     }
     #[test]
@@ -792,7 +806,7 @@ mod setup_test {
             "{}/src/test-fixtures/layout-with-ui-config.kdl",
             env!("CARGO_MANIFEST_DIR")
         )));
-        let (config, _layout_info, _options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (config, _layout_info, _options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", config));
     }
     #[test]
@@ -806,7 +820,7 @@ mod setup_test {
             "{}/src/test-fixtures/layout-with-themes-config.kdl",
             env!("CARGO_MANIFEST_DIR")
         )));
-        let (config, _layout_info, _options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (config, _layout_info, _options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", config));
     }
     #[test]
@@ -820,7 +834,7 @@ mod setup_test {
             "{}/src/test-fixtures/layout-with-keybindings-config.kdl",
             env!("CARGO_MANIFEST_DIR")
         )));
-        let (config, _layout_info, _options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (config, _layout_info, _options, _, _) = from_cli_args(&cli_args);
         assert_snapshot!(format!("{:#?}", config));
     }
     #[test]
@@ -834,7 +848,7 @@ mod setup_test {
             config_dir: Some(config_dir.clone()),
             ..Default::default()
         };
-        let (_, layout_info, _, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_, layout_info, _, _, _) = from_cli_args(&cli_args);
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info has unexpected format");
         };
@@ -856,7 +870,7 @@ mod setup_test {
             config_dir: Some(config_dir.clone()),
             ..Default::default()
         };
-        let (_, layout_info, _, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_, layout_info, _, _, _) = from_cli_args(&cli_args);
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info has unexpected format");
         };
@@ -879,7 +893,7 @@ mod setup_test {
             layout: Some(PathBuf::from("assets/layouts/compact.kdl")),
             ..Default::default()
         };
-        let (_, layout_info, _, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_, layout_info, _, _, _) = from_cli_args(&cli_args);
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info has unexpected format: {:?}", &layout_info);
         };
@@ -898,7 +912,7 @@ mod setup_test {
             layout: Some(PathBuf::from("assets/layouts/compact")),
             ..Default::default()
         };
-        let (_, layout_info, _, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_, layout_info, _, _, _) = from_cli_args(&cli_args);
         let Some(LayoutInfo::File(layout_path, _)) = layout_info else {
             panic!("layout info has unexpected format");
         };
@@ -913,7 +927,7 @@ mod setup_test {
             layout_string: Some(layout_kdl.clone()),
             ..Default::default()
         };
-        let (_, layout_info, _, _, _) = Setup::from_cli_args(&cli_args).unwrap();
+        let (_, layout_info, _, _, _) = from_cli_args(&cli_args);
         let Some(LayoutInfo::Stringified(content)) = layout_info else {
             panic!(
                 "layout info should be Stringified variant, got: {:#?}",
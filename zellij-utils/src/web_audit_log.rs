@@ -0,0 +1,434 @@
+//! A durable, append-only record of who used the web server and how. Each line of the log file is
+//! a JSON serialized `AuditEntry`.
+use crate::consts::ZELLIJ_PROJ_DIR;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+// once the log grows beyond this it is moved to `<log>.1`, replacing the previous one
+const MAX_AUDIT_LOG_SIZE: u64 = 10 * 1024 * 1024;
+// failed logins are unauthenticated, so only this many of them are recorded per window
+const MAX_FAILED_LOGINS_PER_WINDOW: usize = 20;
+const FAILED_LOGINS_WINDOW: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    static ref FAILED_LOGINS: Mutex<FailedLoginLimiter> = Mutex::new(FailedLoginLimiter::default());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    LoginSucceeded,
+    LoginFailed,
+    Attached,
+    Detached,
+    TokenCreated,
+    TokenRevoked,
+    AllTokensRevoked,
+    ReadOnlyViolation,
+    SessionScopeViolation,
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AuditEvent::LoginSucceeded => "login_succeeded",
+            AuditEvent::LoginFailed => "login_failed",
+            AuditEvent::Attached => "attached",
+            AuditEvent::Detached => "detached",
+            AuditEvent::TokenCreated => "token_created",
+            AuditEvent::TokenRevoked => "token_revoked",
+            AuditEvent::AllTokensRevoked => "all_tokens_revoked",
+            AuditEvent::ReadOnlyViolation => "read_only_violation",
+            AuditEvent::SessionScopeViolation => "session_scope_violation",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String, // RFC 3339, UTC
+    pub event: AuditEvent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(event: AuditEvent) -> Self {
+        AuditEntry {
+            timestamp: now(),
+            event,
+            token_name: None,
+            session: None,
+            web_client_id: None,
+            remote_addr: None,
+            read_only: None,
+            detail: None,
+        }
+    }
+    pub fn token_name(mut self, token_name: Option<impl Into<String>>) -> Self {
+        self.token_name = token_name.map(|t| t.into());
+        self
+    }
+    pub fn session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+    pub fn web_client_id(mut self, web_client_id: impl Into<String>) -> Self {
+        self.web_client_id = Some(web_client_id.into());
+        self
+    }
+    pub fn remote_addr(mut self, remote_addr: Option<impl Into<String>>) -> Self {
+        self.remote_addr = remote_addr.map(|r| r.into());
+        self
+    }
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+    /// The same entry for another event, timestamped now
+    pub fn for_event(&self, event: AuditEvent) -> Self {
+        AuditEntry {
+            event,
+            timestamp: now(),
+            detail: None,
+            ..self.clone()
+        }
+    }
+    /// Append this entry to the audit log. Failing to do so is logged but otherwise ignored, so
+    /// that the audit log can never take the web server down with it.
+    ///
+    /// This blocks on file I/O, async callers should run it with `spawn_blocking`.
+    pub fn record(mut self) {
+        if self.event == AuditEvent::LoginFailed {
+            match FAILED_LOGINS.lock().unwrap().admit(Instant::now()) {
+                FailedLoginAdmission::Record => {},
+                FailedLoginAdmission::RecordWithSuppressed(suppressed) => {
+                    self.detail = Some(match self.detail.take() {
+                        Some(detail) => format!(
+                            "{}; {} earlier failed logins were not recorded",
+                            detail, suppressed
+                        ),
+                        None => format!("{} earlier failed logins were not recorded", suppressed),
+                    });
+                },
+                FailedLoginAdmission::Suppress => return,
+            }
+        }
+        let appended = get_audit_log_path().and_then(|log_path| append_entry(&log_path, &self));
+        if let Err(e) = appended {
+            log::error!("Failed to write to the web audit log: {}", e);
+        }
+    }
+    pub fn matches(&self, filter: &AuditFilter) -> bool {
+        let session_matches = filter
+            .session
+            .as_ref()
+            .map(|s| self.session.as_ref() == Some(s))
+            .unwrap_or(true);
+        let token_matches = filter
+            .token_name
+            .as_ref()
+            .map(|t| self.token_name.as_ref() == Some(t))
+            .unwrap_or(true);
+        let time_matches = filter
+            .since
+            .map(|since| {
+                humantime::parse_rfc3339(&self.timestamp)
+                    .map(|timestamp| timestamp >= since)
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+        session_matches && token_matches && time_matches
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.timestamp, self.event)?;
+        if let Some(token_name) = &self.token_name {
+            write!(f, " token={}", token_name)?;
+        }
+        if let Some(session) = &self.session {
+            write!(f, " session={}", session)?;
+        }
+        if let Some(read_only) = self.read_only {
+            write!(f, " read_only={}", read_only)?;
+        }
+        if let Some(remote_addr) = &self.remote_addr {
+            write!(f, " remote={}", remote_addr)?;
+        }
+        if let Some(web_client_id) = &self.web_client_id {
+            write!(f, " client={}", web_client_id)?;
+        }
+        if let Some(detail) = &self.detail {
+            write!(f, " ({})", detail)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub session: Option<String>,
+    pub token_name: Option<String>,
+    pub since: Option<SystemTime>,
+}
+
+pub fn get_audit_log_path() -> io::Result<PathBuf> {
+    let data_dir = ZELLIJ_PROJ_DIR.data_dir();
+    std::fs::create_dir_all(data_dir)?;
+
+    let log_path = if cfg!(debug_assertions) {
+        data_dir.join("web_audit_for_dev.log")
+    } else {
+        data_dir.join("web_audit.log")
+    };
+
+    Ok(log_path)
+}
+
+fn now() -> String {
+    humantime::format_rfc3339_seconds(SystemTime::now()).to_string()
+}
+
+fn rotated_log_path(log_path: &Path) -> PathBuf {
+    let mut rotated = log_path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+fn append_entry(log_path: &Path, entry: &AuditEntry) -> io::Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let log_size = std::fs::metadata(log_path).map(|m| m.len()).unwrap_or(0);
+    if log_size + line.len() as u64 > MAX_AUDIT_LOG_SIZE {
+        std::fs::rename(log_path, rotated_log_path(log_path))?;
+    }
+
+    let mut open_options = OpenOptions::new();
+    open_options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(0o600);
+    }
+    // a single write per entry keeps concurrent appends from interleaving
+    open_options.open(log_path)?.write_all(line.as_bytes())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FailedLoginAdmission {
+    Record,
+    // record, noting how many failed logins were suppressed in the previous window
+    RecordWithSuppressed(usize),
+    Suppress,
+}
+
+#[derive(Debug, Default)]
+struct FailedLoginLimiter {
+    window_started_at: Option<Instant>,
+    recorded: usize,
+    suppressed: usize,
+}
+
+impl FailedLoginLimiter {
+    fn admit(&mut self, now: Instant) -> FailedLoginAdmission {
+        let window_is_over = self
+            .window_started_at
+            .map(|started_at| now.duration_since(started_at) >= FAILED_LOGINS_WINDOW)
+            .unwrap_or(true);
+        if window_is_over {
+            let suppressed = self.suppressed;
+            *self = FailedLoginLimiter {
+                window_started_at: Some(now),
+                recorded: 1,
+                suppressed: 0,
+            };
+            if suppressed > 0 {
+                FailedLoginAdmission::RecordWithSuppressed(suppressed)
+            } else {
+                FailedLoginAdmission::Record
+            }
+        } else if self.recorded < MAX_FAILED_LOGINS_PER_WINDOW {
+            self.recorded += 1;
+            FailedLoginAdmission::Record
+        } else {
+            self.suppressed += 1;
+            FailedLoginAdmission::Suppress
+        }
+    }
+}
+
+/// Entries matching `filter`, oldest first. Lines that cannot be parsed are skipped.
+pub fn read_audit_log(filter: &AuditFilter) -> io::Result<Vec<AuditEntry>> {
+    let log_path = get_audit_log_path()?;
+    let mut entries = vec![];
+    for path in [rotated_log_path(&log_path), log_path] {
+        if path.exists() {
+            let file = std::fs::File::open(path)?;
+            entries.append(&mut parse_entries(BufReader::new(file), filter));
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_entries(reader: impl BufRead, filter: &AuditFilter) -> Vec<AuditEntry> {
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(&line).ok())
+        .filter(|entry| entry.matches(filter))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_at(event: AuditEvent, timestamp: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: timestamp.to_owned(),
+            ..AuditEntry::new(event)
+        }
+    }
+
+    #[test]
+    fn audit_entries_round_trip_as_json_lines() {
+        let entry = AuditEntry::new(AuditEvent::Attached)
+            .token_name(Some("contractor"))
+            .session("pairing")
+            .web_client_id("abc")
+            .read_only(true);
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains(r#""event":"attached""#));
+        assert!(
+            !line.contains("remote_addr"),
+            "fields that are not set are omitted"
+        );
+        let parsed = parse_entries(line.as_bytes(), &AuditFilter::default());
+        assert_eq!(parsed, vec![entry]);
+    }
+
+    #[test]
+    fn audit_entries_are_filtered_by_session_token_and_time() {
+        let log = [
+            entry_at(AuditEvent::LoginSucceeded, "2026-01-01T10:00:00Z").token_name(Some("a")),
+            entry_at(AuditEvent::Attached, "2026-01-01T11:00:00Z")
+                .token_name(Some("a"))
+                .session("one"),
+            entry_at(AuditEvent::Attached, "2026-01-01T12:00:00Z")
+                .token_name(Some("b"))
+                .session("two"),
+        ]
+        .iter()
+        .map(|e| serde_json::to_string(e).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+            + "\nthis line is not json\n";
+
+        let events = |filter: AuditFilter| -> Vec<String> {
+            parse_entries(log.as_bytes(), &filter)
+                .iter()
+                .map(|e| e.timestamp.clone())
+                .collect()
+        };
+
+        assert_eq!(events(AuditFilter::default()).len(), 3);
+        assert_eq!(
+            events(AuditFilter {
+                token_name: Some("a".to_owned()),
+                ..Default::default()
+            }),
+            vec!["2026-01-01T10:00:00Z", "2026-01-01T11:00:00Z"]
+        );
+        assert_eq!(
+            events(AuditFilter {
+                session: Some("two".to_owned()),
+                ..Default::default()
+            }),
+            vec!["2026-01-01T12:00:00Z"]
+        );
+        let since = humantime::parse_rfc3339("2026-01-01T10:30:00Z").unwrap();
+        assert_eq!(
+            events(AuditFilter {
+                since: Some(since + Duration::from_secs(60 * 60)),
+                ..Default::default()
+            }),
+            vec!["2026-01-01T12:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn entries_for_another_event_keep_everything_but_the_detail() {
+        let attached = entry_at(AuditEvent::Attached, "2026-01-01T10:00:00Z")
+            .token_name(Some("a"))
+            .session("one")
+            .detail("first attach");
+        let detached = attached.for_event(AuditEvent::Detached);
+        assert_eq!(detached.event, AuditEvent::Detached);
+        assert_eq!(detached.token_name, attached.token_name);
+        assert_eq!(detached.session, attached.session);
+        assert_eq!(detached.detail, None);
+        assert_ne!(detached.timestamp, attached.timestamp);
+    }
+
+    #[test]
+    fn failed_logins_beyond_the_limit_are_suppressed_until_the_next_window() {
+        let mut limiter = FailedLoginLimiter::default();
+        let start = Instant::now();
+        for _ in 0..MAX_FAILED_LOGINS_PER_WINDOW {
+            assert_eq!(limiter.admit(start), FailedLoginAdmission::Record);
+        }
+        assert_eq!(limiter.admit(start), FailedLoginAdmission::Suppress);
+        assert_eq!(limiter.admit(start), FailedLoginAdmission::Suppress);
+        assert_eq!(
+            limiter.admit(start + FAILED_LOGINS_WINDOW),
+            FailedLoginAdmission::RecordWithSuppressed(2)
+        );
+        assert_eq!(
+            limiter.admit(start + FAILED_LOGINS_WINDOW * 2),
+            FailedLoginAdmission::Record
+        );
+    }
+
+    #[test]
+    fn audit_log_is_rotated_once_it_grows_too_big() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("web_audit.log");
+        std::fs::write(&log_path, vec![b'x'; MAX_AUDIT_LOG_SIZE as usize]).unwrap();
+        append_entry(&log_path, &AuditEntry::new(AuditEvent::LoginFailed)).unwrap();
+        assert_eq!(
+            std::fs::metadata(rotated_log_path(&log_path))
+                .unwrap()
+                .len(),
+            MAX_AUDIT_LOG_SIZE
+        );
+        let entries = parse_entries(
+            BufReader::new(std::fs::File::open(&log_path).unwrap()),
+            &AuditFilter::default(),
+        );
+        assert_eq!(entries.len(), 1);
+    }
+}
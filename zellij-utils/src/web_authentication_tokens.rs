@@ -1,9 +1,11 @@
 // TODO: GATE THIS WHOLE FILE AND RELEVANT DEPS BEHIND web_server_capability
 use crate::consts::ZELLIJ_PROJ_DIR;
+use crate::web_audit_log::{AuditEntry, AuditEvent};
+use crate::web_server_commands::notify_webservers_of_revoked_login_tokens;
 use rusqlite::Connection;
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -11,6 +13,9 @@ pub struct TokenInfo {
     pub name: String,
     pub created_at: String,
     pub read_only: bool,
+    pub expires_at: Option<String>,
+    pub session_scope: Option<String>,
+    pub last_used_at: Option<String>,
 }
 
 #[derive(Debug)]
@@ -21,6 +26,7 @@ pub enum TokenError {
     DuplicateName(String),
     TokenNotFound(String),
     InvalidToken,
+    InvalidSessionScope(String),
 }
 
 impl std::fmt::Display for TokenError {
@@ -32,6 +38,9 @@ impl std::fmt::Display for TokenError {
             TokenError::DuplicateName(name) => write!(f, "Token name '{}' already exists", name),
             TokenError::TokenNotFound(name) => write!(f, "Token '{}' not found", name),
             TokenError::InvalidToken => write!(f, "Invalid token"),
+            TokenError::InvalidSessionScope(scope) => {
+                write!(f, "Invalid session scope '{}'", scope)
+            },
         }
     }
 }
@@ -115,22 +124,31 @@ fn init_db(conn: &Connection) -> Result<()> {
         [],
     )?;
 
-    // Migration: Add read_only column if it doesn't exist
-    match conn.execute(
+    // Migrations: add columns introduced after the initial schema
+    add_column_if_missing(
+        conn,
         "ALTER TABLE tokens ADD COLUMN read_only BOOLEAN NOT NULL DEFAULT 0",
-        [],
-    ) {
-        Ok(_) => {},
+    )?;
+    add_column_if_missing(conn, "ALTER TABLE tokens ADD COLUMN expires_at DATETIME")?;
+    add_column_if_missing(conn, "ALTER TABLE tokens ADD COLUMN session_scope TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE tokens ADD COLUMN last_used_at DATETIME")?;
+
+    Ok(())
+}
+
+fn add_column_if_missing(conn: &Connection, statement: &str) -> Result<()> {
+    match conn.execute(statement, []) {
+        Ok(_) => Ok(()),
         Err(e) => {
             let err_msg = e.to_string();
             // "duplicate column name" is the expected error when the column already exists
-            if !err_msg.contains("duplicate column name") {
-                return Err(TokenError::Database(e));
+            if err_msg.contains("duplicate column name") {
+                Ok(())
+            } else {
+                Err(TokenError::Database(e))
             }
         },
     }
-
-    Ok(())
 }
 
 pub fn hash_token(token: &str) -> String {
@@ -144,6 +162,23 @@ pub fn hash_token(token: &str) -> String {
 }
 
 pub fn create_token(name: Option<String>, read_only: bool) -> Result<(String, String)> {
+    create_restricted_token(name, read_only, None, None)
+}
+
+/// Create a login token that stops working after `expires_in` (if given) and can only attach to
+/// sessions whose name matches the `session_scope` glob (if given).
+pub fn create_restricted_token(
+    name: Option<String>,
+    read_only: bool,
+    expires_in: Option<Duration>,
+    session_scope: Option<String>,
+) -> Result<(String, String)> {
+    if let Some(scope) = &session_scope {
+        if scope.trim().is_empty() {
+            return Err(TokenError::InvalidSessionScope(scope.to_owned()));
+        }
+    }
+
     let conn = open_db()?;
 
     let token = Uuid::new_v4().to_string();
@@ -156,9 +191,24 @@ pub fn create_token(name: Option<String>, read_only: bool) -> Result<(String, St
         format!("token_{}", count + 1)
     };
 
+    let expires_at = expires_in.map(|expires_in| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        (now + expires_in.as_secs()) as i64
+    });
+
     match conn.execute(
-        "INSERT INTO tokens (token_hash, name, read_only) VALUES (?1, ?2, ?3)",
-        [&token_hash, &token_name, &(read_only as i64).to_string()],
+        "INSERT INTO tokens (token_hash, name, read_only, expires_at, session_scope)
+         VALUES (?1, ?2, ?3, datetime(?4, 'unixepoch'), ?5)",
+        rusqlite::params![
+            &token_hash,
+            &token_name,
+            read_only as i64,
+            expires_at,
+            &session_scope
+        ],
     ) {
         Err(rusqlite::Error::SqliteFailure(ffi_error, _))
             if ffi_error.code == rusqlite::ErrorCode::ConstraintViolation =>
@@ -166,7 +216,26 @@ pub fn create_token(name: Option<String>, read_only: bool) -> Result<(String, St
             Err(TokenError::DuplicateName(token_name))
         },
         Err(e) => Err(TokenError::Database(e)),
-        Ok(_) => Ok((token, token_name)),
+        Ok(_) => {
+            let mut restrictions = vec![];
+            if let Some(expires_in) = expires_in {
+                restrictions.push(format!(
+                    "expires in {}",
+                    humantime::format_duration(expires_in)
+                ));
+            }
+            if let Some(session_scope) = &session_scope {
+                restrictions.push(format!("sessions: {}", session_scope));
+            }
+            let mut entry = AuditEntry::new(AuditEvent::TokenCreated)
+                .token_name(Some(&token_name))
+                .read_only(read_only);
+            if !restrictions.is_empty() {
+                entry = entry.detail(restrictions.join(", "));
+            }
+            entry.record();
+            Ok((token, token_name))
+        },
     }
 }
 
@@ -178,7 +247,8 @@ pub fn create_session_token(auth_token: &str, remember_me: bool) -> Result<Strin
     let auth_token_hash = hash_token(auth_token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1
+         AND (expires_at IS NULL OR expires_at > datetime('now'))",
         [&auth_token_hash],
         |row| row.get(0),
     )?;
@@ -187,6 +257,11 @@ pub fn create_session_token(auth_token: &str, remember_me: bool) -> Result<Strin
         return Err(TokenError::InvalidToken);
     }
 
+    conn.execute(
+        "UPDATE tokens SET last_used_at = CURRENT_TIMESTAMP WHERE token_hash = ?1",
+        [&auth_token_hash],
+    )?;
+
     let session_token = Uuid::new_v4().to_string();
     let session_token_hash = hash_token(&session_token);
 
@@ -222,12 +297,24 @@ pub fn validate_session_token(session_token: &str) -> Result<bool> {
 
     let session_token_hash = hash_token(session_token);
 
+    // A session is only as good as the login token it was created from
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM session_tokens WHERE session_token_hash = ?1 AND expires_at > datetime('now')",
+        "SELECT COUNT(*) FROM session_tokens st
+         JOIN tokens t ON st.auth_token_hash = t.token_hash
+         WHERE st.session_token_hash = ?1 AND st.expires_at > datetime('now')
+         AND (t.expires_at IS NULL OR t.expires_at > datetime('now'))",
         [&session_token_hash],
         |row| row.get(0),
     )?;
 
+    if count > 0 {
+        conn.execute(
+            "UPDATE tokens SET last_used_at = CURRENT_TIMESTAMP WHERE token_hash =
+             (SELECT auth_token_hash FROM session_tokens WHERE session_token_hash = ?1)",
+            [&session_token_hash],
+        )?;
+    }
+
     Ok(count > 0)
 }
 
@@ -252,6 +339,125 @@ pub fn is_session_token_read_only(session_token: &str) -> Result<bool> {
     Ok(read_only != 0)
 }
 
+/// The name of the login token, if it exists
+pub fn token_name_for_auth_token(auth_token: &str) -> Result<Option<String>> {
+    let conn = open_db()?;
+
+    let auth_token_hash = hash_token(auth_token);
+    match conn.query_row(
+        "SELECT name FROM tokens WHERE token_hash = ?1",
+        [&auth_token_hash],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(name) => Ok(Some(name)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
+/// The name of the login token the session token with this hash was created from, if both exist
+pub fn token_name_for_session_token_hash(session_token_hash: &str) -> Result<Option<String>> {
+    let conn = open_db()?;
+
+    match conn.query_row(
+        "SELECT t.name FROM tokens t
+         JOIN session_tokens st ON st.auth_token_hash = t.token_hash
+         WHERE st.session_token_hash = ?1",
+        [&session_token_hash],
+        |row| row.get::<_, String>(0),
+    ) {
+        Ok(name) => Ok(Some(name)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
+/// The hash of the login token the session token with this hash was created from, and how many
+/// seconds are left until that login token expires (None if it never does)
+pub fn login_token_for_session_token_hash(
+    session_token_hash: &str,
+) -> Result<Option<(String, Option<i64>)>> {
+    let conn = open_db()?;
+
+    match conn.query_row(
+        "SELECT t.token_hash,
+         CAST(strftime('%s', t.expires_at) AS INTEGER) - CAST(strftime('%s', 'now') AS INTEGER)
+         FROM tokens t
+         JOIN session_tokens st ON st.auth_token_hash = t.token_hash
+         WHERE st.session_token_hash = ?1",
+        [&session_token_hash],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+    ) {
+        Ok(login_token) => Ok(Some(login_token)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
+/// Whether the login token with this hash still exists and has not expired
+pub fn is_login_token_hash_valid(token_hash: &str) -> Result<bool> {
+    let conn = open_db()?;
+
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1
+         AND (expires_at IS NULL OR expires_at > datetime('now'))",
+        [&token_hash],
+        |row| row.get(0),
+    )?;
+
+    Ok(count > 0)
+}
+
+/// The session name glob the login token behind this session token is restricted to, if any.
+pub fn session_token_scope(session_token: &str) -> Result<Option<String>> {
+    let conn = open_db()?;
+
+    let session_token_hash = hash_token(session_token);
+
+    match conn.query_row(
+        "SELECT t.session_scope FROM tokens t
+         JOIN session_tokens st ON st.auth_token_hash = t.token_hash
+         WHERE st.session_token_hash = ?1 AND st.expires_at > datetime('now')",
+        [&session_token_hash],
+        |row| row.get::<_, Option<String>>(0),
+    ) {
+        Ok(scope) => Ok(scope),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Err(TokenError::InvalidToken),
+        Err(e) => Err(TokenError::Database(e)),
+    }
+}
+
+/// Match a session name against a token's session scope, where `*` matches any run of characters
+/// and `?` matches a single character.
+pub fn session_matches_scope(scope: &str, session_name: &str) -> bool {
+    let scope: Vec<char> = scope.chars().collect();
+    let session_name: Vec<char> = session_name.chars().collect();
+    let (mut scope_index, mut name_index) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while name_index < session_name.len() {
+        match scope.get(scope_index) {
+            Some('*') => {
+                last_star = Some((scope_index, name_index));
+                scope_index += 1;
+            },
+            Some(c) if *c == '?' || *c == session_name[name_index] => {
+                scope_index += 1;
+                name_index += 1;
+            },
+            _ => match last_star {
+                Some((star_index, star_name_index)) => {
+                    // backtrack: let the last star swallow one more character
+                    scope_index = star_index + 1;
+                    name_index = star_name_index + 1;
+                    last_star = Some((star_index, star_name_index + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+    scope[scope_index..].iter().all(|c| *c == '*')
+}
+
 pub fn cleanup_expired_sessions() -> Result<usize> {
     let conn = open_db()?;
 
@@ -311,6 +517,13 @@ pub fn revoke_token(name: &str) -> Result<bool> {
 
     let rows_affected = tx.execute("DELETE FROM tokens WHERE name = ?1", [&name])?;
     tx.commit().map_err(TokenError::Database)?;
+    if rows_affected > 0 {
+        AuditEntry::new(AuditEvent::TokenRevoked)
+            .token_name(Some(name))
+            .record();
+        // running web servers close the connections that were made with this token
+        let _ = notify_webservers_of_revoked_login_tokens();
+    }
     Ok(rows_affected > 0)
 }
 
@@ -321,6 +534,10 @@ pub fn revoke_all_tokens() -> Result<usize> {
     tx.execute("DELETE FROM session_tokens", [])?;
     let rows_affected = tx.execute("DELETE FROM tokens", [])?;
     tx.commit().map_err(TokenError::Database)?;
+    AuditEntry::new(AuditEvent::AllTokensRevoked)
+        .detail(format!("{} tokens", rows_affected))
+        .record();
+    let _ = notify_webservers_of_revoked_login_tokens();
     Ok(rows_affected)
 }
 
@@ -354,13 +571,18 @@ pub fn rename_token(old_name: &str, new_name: &str) -> Result<()> {
 pub fn list_tokens() -> Result<Vec<TokenInfo>> {
     let conn = open_db()?;
 
-    let mut stmt =
-        conn.prepare("SELECT name, created_at, read_only FROM tokens ORDER BY created_at")?;
+    let mut stmt = conn.prepare(
+        "SELECT name, created_at, read_only, expires_at, session_scope, last_used_at
+         FROM tokens ORDER BY created_at",
+    )?;
     let rows = stmt.query_map([], |row| {
         Ok(TokenInfo {
             name: row.get::<_, String>(0)?,
             created_at: row.get::<_, String>(1)?,
             read_only: row.get::<_, i64>(2)? != 0,
+            expires_at: row.get::<_, Option<String>>(3)?,
+            session_scope: row.get::<_, Option<String>>(4)?,
+            last_used_at: row.get::<_, Option<String>>(5)?,
         })
     })?;
 
@@ -385,9 +607,32 @@ pub fn validate_token(token: &str) -> Result<bool> {
     let token_hash = hash_token(token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1
+         AND (expires_at IS NULL OR expires_at > datetime('now'))",
         [&token_hash],
         |row| row.get(0),
     )?;
     Ok(count > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::session_matches_scope;
+
+    #[test]
+    fn session_scope_globs() {
+        assert!(session_matches_scope("contractor-demo", "contractor-demo"));
+        assert!(!session_matches_scope(
+            "contractor-demo",
+            "contractor-demo-2"
+        ));
+        assert!(session_matches_scope("contractor-*", "contractor-demo"));
+        assert!(session_matches_scope("contractor-*", "contractor-"));
+        assert!(!session_matches_scope("contractor-*", "my-session"));
+        assert!(session_matches_scope("*-demo", "contractor-demo"));
+        assert!(session_matches_scope("*de*o", "contractor-demo"));
+        assert!(session_matches_scope("demo-?", "demo-1"));
+        assert!(!session_matches_scope("demo-?", "demo-12"));
+        assert!(session_matches_scope("*", "anything"));
+    }
+}
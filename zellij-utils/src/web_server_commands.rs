@@ -11,6 +11,16 @@ use std::io::{BufWriter, Read, Write};
 use std::path::PathBuf;
 
 pub fn shutdown_all_webserver_instances() -> Result<()> {
+    send_to_all_webserver_instances(InstructionForWebServer::ShutdownWebServer)
+}
+
+/// Lets running web servers know that login tokens were revoked, so that they close the
+/// connections made with them
+pub fn notify_webservers_of_revoked_login_tokens() -> Result<()> {
+    send_to_all_webserver_instances(InstructionForWebServer::LoginTokensRevoked)
+}
+
+fn send_to_all_webserver_instances(instruction: InstructionForWebServer) -> Result<()> {
     let entries = fs::read_dir(&*WEBSERVER_SOCKET_PATH)?;
 
     for entry in entries {
@@ -25,10 +35,7 @@ pub fn shutdown_all_webserver_instances() -> Result<()> {
                 if is_ipc_socket(&file_type) {
                     match create_webserver_sender(path.to_str().unwrap_or("")) {
                         Ok(mut sender) => {
-                            let _ = send_webserver_instruction(
-                                &mut sender,
-                                InstructionForWebServer::ShutdownWebServer,
-                            );
+                            let _ = send_webserver_instruction(&mut sender, instruction.clone());
                         },
                         Err(_) => {
                             // no-op
@@ -45,6 +52,7 @@ pub fn shutdown_all_webserver_instances() -> Result<()> {
 pub enum InstructionForWebServer {
     ShutdownWebServer,
     QueryVersion,
+    LoginTokensRevoked,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -4,8 +4,9 @@ use crate::web_server_commands::{
 };
 use crate::web_server_contract::web_server_contract::{
     instruction_for_web_server, web_server_response,
-    InstructionForWebServer as ProtoInstructionForWebServer, QueryVersionMsg, ShutdownWebServerMsg,
-    VersionResponseMsg, WebServerResponse as ProtoWebServerResponse,
+    InstructionForWebServer as ProtoInstructionForWebServer, LoginTokensRevokedMsg,
+    QueryVersionMsg, ShutdownWebServerMsg, VersionResponseMsg,
+    WebServerResponse as ProtoWebServerResponse,
 };
 
 // Convert Rust InstructionForWebServer to protobuf
@@ -18,6 +19,11 @@ impl From<RustInstructionForWebServer> for ProtoInstructionForWebServer {
             RustInstructionForWebServer::QueryVersion => {
                 instruction_for_web_server::Instruction::QueryVersion(QueryVersionMsg {})
             },
+            RustInstructionForWebServer::LoginTokensRevoked => {
+                instruction_for_web_server::Instruction::LoginTokensRevoked(
+                    LoginTokensRevokedMsg {},
+                )
+            },
         };
 
         ProtoInstructionForWebServer {
@@ -38,6 +44,9 @@ impl TryFrom<ProtoInstructionForWebServer> for RustInstructionForWebServer {
             Some(instruction_for_web_server::Instruction::QueryVersion(_)) => {
                 Ok(RustInstructionForWebServer::QueryVersion)
             },
+            Some(instruction_for_web_server::Instruction::LoginTokensRevoked(_)) => {
+                Ok(RustInstructionForWebServer::LoginTokensRevoked)
+            },
             None => Err(anyhow!("Missing instruction in InstructionForWebServer")),
         }
     }